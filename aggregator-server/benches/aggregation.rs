@@ -0,0 +1,202 @@
+use aggregator_server::config::AggregatorConfig;
+use aggregator_server::oracle::oracle_service_server::OracleService;
+use aggregator_server::oracle::{GetPriceRequest, PriceRequest};
+use aggregator_server::store::PriceStore;
+use aggregator_server::testing::PriceGenerator;
+use aggregator_server::{
+    median_price, trimmed_mean_price, AggregatorServiceImpl, PRICE_WINDOW_SECS,
+};
+use chrono::Utc;
+use criterion::{black_box, criterion_group, BenchmarkId, Criterion};
+use std::time::{Duration, Instant};
+use tokio::runtime::Runtime;
+use tonic::Request;
+
+const SEED: u64 = 42;
+const NODES: usize = 50;
+const STORE_SIZES: [usize; 3] = [1_000, 10_000, 100_000];
+const AGGREGATE_ENTRIES: usize = 1_000;
+const TRIM_RATIO: f64 = 0.1;
+
+fn now() -> u64 {
+    Utc::now().timestamp() as u64
+}
+
+fn generator() -> PriceGenerator {
+    PriceGenerator::new(SEED, NODES).with_now(now())
+}
+
+// 용량까지 채운 저장소 (이후 삽입마다 제거가 발생)
+fn filled_store(size: usize, generator: &mut PriceGenerator) -> PriceStore {
+    let mut store = PriceStore::new(size, size / NODES);
+    for entry in generator.entries(size) {
+        store.push(entry);
+    }
+    store
+}
+
+// 직접 호출용 서비스 (네트워크 없음)
+fn filled_service(runtime: &Runtime, generator: &mut PriceGenerator) -> AggregatorServiceImpl {
+    let service = AggregatorServiceImpl::with_config(AggregatorConfig {
+        max_price_entries: AGGREGATE_ENTRIES,
+        ..AggregatorConfig::default()
+    });
+    runtime.block_on(async {
+        // 생성기는 (node_id, timestamp, source)가 겹칠 수 있으므로 AlreadyExists 거부는 무시
+        for request in generator.requests(AGGREGATE_ENTRIES) {
+            let _ = service.submit_price(Request::new(request)).await;
+        }
+        service.publish_snapshot().await;
+    });
+    service
+}
+
+fn bench_store_insert(c: &mut Criterion) {
+    let mut group = c.benchmark_group("store_insert");
+
+    for size in STORE_SIZES {
+        let mut generator = generator();
+        let mut store = filled_store(size, &mut generator);
+        let entries = generator.entries(10_000);
+        let mut next = entries.iter().cycle();
+
+        group.bench_with_input(BenchmarkId::from_parameter(size), &size, |b, _| {
+            b.iter(|| store.push(next.next().unwrap().clone()))
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_aggregation(c: &mut Criterion) {
+    let now = now();
+    let entries = generator().entries(AGGREGATE_ENTRIES);
+    let mut group = c.benchmark_group("aggregation_1k");
+
+    group.bench_function("median", |b| {
+        b.iter(|| median_price(black_box(&entries), now, PRICE_WINDOW_SECS))
+    });
+    group.bench_function("trimmed_mean", |b| {
+        b.iter(|| trimmed_mean_price(black_box(&entries), now, PRICE_WINDOW_SECS, TRIM_RATIO))
+    });
+
+    group.finish();
+}
+
+fn bench_service(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let mut generator = generator();
+    let service = filled_service(&runtime, &mut generator);
+    let requests = generator.requests(10_000);
+    let mut next = requests.iter().cycle();
+    let mut group = c.benchmark_group("service_1k");
+
+    group.bench_function("submit_price", |b| {
+        b.iter(|| {
+            let request = Request::new(next.next().unwrap().clone());
+            runtime.block_on(service.submit_price(request)).ok()
+        })
+    });
+    group.bench_function("get_aggregated_price", |b| {
+        b.iter(|| {
+            let request = Request::new(GetPriceRequest {
+                source_filter: None,
+                include_stale: false,
+            });
+            runtime
+                .block_on(service.get_aggregated_price(request))
+                .unwrap()
+        })
+    });
+
+    group.finish();
+}
+
+// 일정 시간 동안 반복 실행한 초당 처리량
+fn ops_per_sec(mut f: impl FnMut()) -> f64 {
+    let budget = Duration::from_millis(200);
+    let start = Instant::now();
+    let mut ops = 0u64;
+    while start.elapsed() < budget {
+        f();
+        ops += 1;
+    }
+    ops as f64 / start.elapsed().as_secs_f64()
+}
+
+// 회귀 기준: 측정값이 목표 자릿수 아래로 떨어지면 실패
+fn assert_throughput(name: &str, min_ops_per_sec: f64, f: impl FnMut()) {
+    let measured = ops_per_sec(f);
+    println!(
+        "  {:<32} {:>12.0} ops/s (target >= {:.0})",
+        name, measured, min_ops_per_sec
+    );
+    assert!(
+        measured >= min_ops_per_sec,
+        "{} regressed: {:.0} ops/s < {:.0} ops/s",
+        name,
+        measured,
+        min_ops_per_sec
+    );
+}
+
+fn check_regression_thresholds() {
+    println!("Throughput regression thresholds:");
+
+    // 저장소 삽입: 크기에 비례하는 비용 (전체 초과 시 앞쪽 제거)
+    for (size, target) in STORE_SIZES.into_iter().zip([100_000.0, 10_000.0, 1_000.0]) {
+        let mut generator = generator();
+        let mut store = filled_store(size, &mut generator);
+        let entries = generator.entries(10_000);
+        let mut next = entries.iter().cycle();
+        assert_throughput(&format!("store_insert/{}", size), target, || {
+            store.push(next.next().unwrap().clone())
+        });
+    }
+
+    let now = now();
+    let entries = generator().entries(AGGREGATE_ENTRIES);
+    assert_throughput("aggregation_1k/median", 10_000.0, || {
+        black_box(median_price(&entries, now, PRICE_WINDOW_SECS));
+    });
+    assert_throughput("aggregation_1k/trimmed_mean", 10_000.0, || {
+        black_box(trimmed_mean_price(
+            &entries,
+            now,
+            PRICE_WINDOW_SECS,
+            TRIM_RATIO,
+        ));
+    });
+
+    let runtime = Runtime::new().unwrap();
+    let mut generator = generator();
+    let service = filled_service(&runtime, &mut generator);
+    let requests: Vec<PriceRequest> = generator.requests(10_000);
+    let mut next = requests.iter().cycle();
+    assert_throughput("service_1k/submit_price", 10_000.0, || {
+        let request = Request::new(next.next().unwrap().clone());
+        let _ = runtime.block_on(service.submit_price(request));
+    });
+    assert_throughput("service_1k/get_aggregated_price", 10_000.0, || {
+        let request = Request::new(GetPriceRequest {
+            source_filter: None,
+            include_stale: false,
+        });
+        runtime
+            .block_on(service.get_aggregated_price(request))
+            .unwrap();
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_store_insert,
+    bench_aggregation,
+    bench_service
+);
+
+fn main() {
+    benches();
+    Criterion::default().configure_from_args().final_summary();
+    check_regression_thresholds();
+}
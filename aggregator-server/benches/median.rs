@@ -0,0 +1,231 @@
+use aggregator_server::oracle::PriceDataPoint;
+use aggregator_server::{
+    median_exact_in_place, median_price, median_price_exact, median_price_one_vote_per_node,
+    PriceEntry, DEFAULT_PAIR, PRICE_WINDOW_SECS, RECENT_PRICES_LIMIT,
+};
+use criterion::{black_box, criterion_group, Criterion};
+use oracle_vm_common::Price;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+// 할당 횟수를 세는 전역 할당자
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const ENTRY_COUNT: usize = 1_000;
+const LARGE_ENTRY_COUNT: usize = 10_000;
+const NOW: u64 = 1_700_000_000;
+
+// String 기반의 기존 저장 구조 (비교용)
+#[derive(Clone)]
+struct OwnedEntry {
+    price: f64,
+    timestamp: u64,
+    source: String,
+    node_id: String,
+}
+
+fn entries() -> Vec<PriceEntry> {
+    entries_n(ENTRY_COUNT)
+}
+
+fn entries_n(count: usize) -> Vec<PriceEntry> {
+    let sources: Vec<Arc<str>> = ["binance", "coinbase", "kraken"]
+        .into_iter()
+        .map(Arc::from)
+        .collect();
+
+    (0..count)
+        .map(|i| PriceEntry {
+            price: Price::from_cents(7_000_000 + ((i as u64 * 7919) % 1000) * 50),
+            timestamp: NOW - (i as u64 % PRICE_WINDOW_SECS),
+            source: sources[i % sources.len()].clone(),
+            node_id: Arc::from(format!("oracle-node-{:08}", i % 50)),
+            pair: Arc::from(DEFAULT_PAIR),
+            volume: None,
+        })
+        .collect()
+}
+
+fn owned_entries(entries: &[PriceEntry]) -> Vec<OwnedEntry> {
+    entries
+        .iter()
+        .map(|e| OwnedEntry {
+            price: e.price.to_f64_dollars(),
+            timestamp: e.timestamp,
+            source: e.source.to_string(),
+            node_id: e.node_id.to_string(),
+        })
+        .collect()
+}
+
+// 기존 구현: 수집 후 복제하여 전체 정렬
+fn naive_median(entries: &[PriceEntry]) -> Option<f64> {
+    let recent_prices: Vec<f64> = entries
+        .iter()
+        .filter(|p| NOW - p.timestamp < PRICE_WINDOW_SECS)
+        .map(|p| p.price.to_f64_dollars())
+        .collect();
+
+    if recent_prices.is_empty() {
+        return None;
+    }
+
+    let mut sorted_prices = recent_prices.clone();
+    sorted_prices.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let len = sorted_prices.len();
+    if len.is_multiple_of(2) {
+        Some((sorted_prices[len / 2 - 1] + sorted_prices[len / 2]) / 2.0)
+    } else {
+        Some(sorted_prices[len / 2])
+    }
+}
+
+// 기존 구현: 락 안에서 String을 복제하며 PriceDataPoint 생성
+fn naive_history(entries: &[OwnedEntry]) -> Vec<PriceDataPoint> {
+    entries
+        .iter()
+        .rev()
+        .take(RECENT_PRICES_LIMIT)
+        .map(|p| PriceDataPoint {
+            price: p.price,
+            timestamp: p.timestamp,
+            source: p.source.clone(),
+            node_id: p.node_id.clone(),
+            price_scaled: 0,
+            price_decimals: 0,
+        })
+        .collect()
+}
+
+// 현재 구현: 락 안에서는 Arc 참조 카운트만 증가
+fn snapshot_history(entries: &[PriceEntry]) -> Vec<PriceEntry> {
+    entries
+        .iter()
+        .rev()
+        .take(RECENT_PRICES_LIMIT)
+        .cloned()
+        .collect()
+}
+
+fn count_allocations<T>(f: impl FnOnce() -> T) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    black_box(f());
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+fn report_allocations() {
+    let entries = entries();
+    let owned = owned_entries(&entries);
+
+    println!("Allocations per call at {} entries:", ENTRY_COUNT);
+    println!(
+        "  median   naive={} select_nth={}",
+        count_allocations(|| naive_median(&entries)),
+        count_allocations(|| median_price(&entries, NOW, PRICE_WINDOW_SECS))
+    );
+    println!(
+        "  history  naive (under lock)={} arc snapshot (under lock)={}",
+        count_allocations(|| naive_history(&owned)),
+        count_allocations(|| snapshot_history(&entries))
+    );
+}
+
+fn bench_median(c: &mut Criterion) {
+    let entries = entries();
+    let mut group = c.benchmark_group("median_1k");
+
+    group.bench_function("clone_and_sort", |b| {
+        b.iter(|| naive_median(black_box(&entries)))
+    });
+    group.bench_function("select_nth_unstable", |b| {
+        b.iter(|| median_price(black_box(&entries), NOW, PRICE_WINDOW_SECS))
+    });
+
+    group.finish();
+}
+
+// 고정소수점 기준 구현: 최근 가격을 복제 후 전체 정렬
+fn naive_exact_median(entries: &[PriceEntry]) -> Option<Price> {
+    let mut sorted: Vec<Price> = entries
+        .iter()
+        .filter(|p| NOW - p.timestamp < PRICE_WINDOW_SECS)
+        .map(|p| p.price)
+        .collect();
+    sorted.sort();
+
+    let len = sorted.len();
+    match len {
+        0 => None,
+        _ if len.is_multiple_of(2) => {
+            median_exact_in_place(&mut [sorted[len / 2 - 1], sorted[len / 2]])
+        }
+        _ => Some(sorted[len / 2]),
+    }
+}
+
+// calculate_median_price의 핵심 경로 (10k)
+fn bench_median_exact(c: &mut Criterion) {
+    let entries = entries_n(LARGE_ENTRY_COUNT);
+    let mut group = c.benchmark_group("median_exact_10k");
+
+    group.bench_function("clone_and_sort", |b| {
+        b.iter(|| naive_exact_median(black_box(&entries)))
+    });
+    group.bench_function("median_price_exact", |b| {
+        b.iter(|| median_price_exact(black_box(&entries), NOW, PRICE_WINDOW_SECS))
+    });
+    group.bench_function("one_vote_per_node", |b| {
+        b.iter(|| median_price_one_vote_per_node(black_box(&entries), NOW, PRICE_WINDOW_SECS))
+    });
+
+    group.finish();
+}
+
+fn bench_history(c: &mut Criterion) {
+    let entries = entries();
+    let owned = owned_entries(&entries);
+    let mut group = c.benchmark_group("history_1k");
+
+    group.bench_function("string_clone", |b| {
+        b.iter(|| naive_history(black_box(&owned)))
+    });
+    group.bench_function("arc_snapshot", |b| {
+        b.iter(|| snapshot_history(black_box(&entries)))
+    });
+    group.bench_function("arc_snapshot_to_proto", |b| {
+        b.iter(|| {
+            snapshot_history(black_box(&entries))
+                .into_iter()
+                .map(PriceDataPoint::from)
+                .collect::<Vec<_>>()
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_median, bench_median_exact, bench_history);
+
+fn main() {
+    report_allocations();
+    benches();
+    Criterion::default().configure_from_args().final_summary();
+}
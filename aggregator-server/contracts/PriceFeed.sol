@@ -0,0 +1,28 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+/// Minimal price feed the aggregator's on-chain publisher writes to.
+/// Only the deployer (the aggregator's account) may update the price.
+contract PriceFeed {
+    address public immutable updater;
+    uint256 private price;
+    uint256 private timestamp;
+
+    event PriceUpdated(uint256 price, uint256 timestamp);
+
+    constructor() {
+        updater = msg.sender;
+    }
+
+    function updatePrice(uint256 newPrice, uint256 newTimestamp) external {
+        require(msg.sender == updater, "not updater");
+        require(newTimestamp > timestamp, "stale price");
+        price = newPrice;
+        timestamp = newTimestamp;
+        emit PriceUpdated(newPrice, newTimestamp);
+    }
+
+    function latestPrice() external view returns (uint256, uint256) {
+        return (price, timestamp);
+    }
+}
@@ -0,0 +1,26 @@
+-- Accepted submissions, deduplicated on the same key as the in-memory store
+CREATE TABLE IF NOT EXISTS submissions (
+    pair TEXT NOT NULL,
+    node_id TEXT NOT NULL,
+    source TEXT NOT NULL,
+    price_scaled BIGINT NOT NULL,
+    price_decimals INTEGER NOT NULL,
+    observed_at BIGINT NOT NULL,
+    received_at BIGINT NOT NULL,
+    seq BIGINT,
+    PRIMARY KEY (node_id, observed_at, source, pair)
+);
+
+CREATE INDEX IF NOT EXISTS submissions_pair_observed_at ON submissions (pair, observed_at DESC);
+CREATE INDEX IF NOT EXISTS submissions_observed_at ON submissions (observed_at DESC);
+
+-- Published aggregates (aggregators sharing the database write each timestamp once)
+CREATE TABLE IF NOT EXISTS aggregates (
+    pair TEXT NOT NULL,
+    published_at BIGINT NOT NULL,
+    price_scaled BIGINT,
+    price_decimals INTEGER,
+    contributing_nodes INTEGER NOT NULL,
+    stale BOOLEAN NOT NULL,
+    PRIMARY KEY (pair, published_at)
+);
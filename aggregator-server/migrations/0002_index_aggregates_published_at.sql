@@ -0,0 +1,2 @@
+-- Retention deletes aggregates by publish time across all pairs
+CREATE INDEX IF NOT EXISTS aggregates_published_at ON aggregates (published_at);
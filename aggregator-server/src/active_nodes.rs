@@ -0,0 +1,175 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+/// 최근에 가격 또는 헬스체크를 보낸 노드 목록 (최대 크기 제한, 가장 오래된 노드부터 제거)
+#[derive(Debug)]
+pub struct ActiveNodes {
+    /// node_id -> (last_seen, 순번)
+    nodes: HashMap<Arc<str>, (u64, u64)>,
+    /// (last_seen, 순번) -> node_id, 오래된 순으로 정렬
+    by_last_seen: BTreeMap<(u64, u64), Arc<str>>,
+    /// node_id -> 마지막 가격 제출 시각 (헬스체크로는 갱신되지 않음)
+    last_submitted: HashMap<Arc<str>, u64>,
+    capacity: usize,
+    next_seq: u64,
+    evicted: u64,
+}
+
+impl ActiveNodes {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            nodes: HashMap::new(),
+            by_last_seen: BTreeMap::new(),
+            last_submitted: HashMap::new(),
+            capacity,
+            next_seq: 0,
+            evicted: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// 용량 초과로 제거된 노드 수 (누적)
+    pub fn evicted(&self) -> u64 {
+        self.evicted
+    }
+
+    pub fn contains(&self, node_id: &str) -> bool {
+        self.nodes.contains_key(node_id)
+    }
+
+    pub fn last_seen(&self, node_id: &str) -> Option<u64> {
+        self.nodes.get(node_id).map(|&(last_seen, _)| last_seen)
+    }
+
+    /// 노드의 마지막 가격 제출 시각 (헬스체크만 보낸 노드는 None)
+    pub fn last_submitted(&self, node_id: &str) -> Option<u64> {
+        self.last_submitted.get(node_id).copied()
+    }
+
+    /// 이미 등록된 node_id의 Arc를 반환 (문자열 재할당 방지)
+    pub fn get_key(&self, node_id: &str) -> Option<Arc<str>> {
+        self.nodes
+            .get_key_value(node_id)
+            .map(|(key, _)| key.clone())
+    }
+
+    /// 노드의 마지막 활동 시각 갱신, 가득 찬 경우 가장 오래된 노드를 제거
+    pub fn touch(&mut self, node_id: Arc<str>, now: u64) {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+
+        if let Some(previous) = self.nodes.insert(node_id.clone(), (now, seq)) {
+            self.by_last_seen.remove(&previous);
+        }
+        self.by_last_seen.insert((now, seq), node_id);
+
+        while self.nodes.len() > self.capacity {
+            let Some((_, oldest)) = self.by_last_seen.pop_first() else {
+                break;
+            };
+            self.nodes.remove(&oldest);
+            self.last_submitted.remove(&oldest);
+            self.evicted += 1;
+        }
+    }
+
+    /// 가격 제출 기록 (활동 시각과 마지막 제출 시각 모두 갱신)
+    pub fn record_submission(&mut self, node_id: Arc<str>, now: u64) {
+        self.last_submitted.insert(node_id.clone(), now);
+        self.touch(node_id, now);
+    }
+
+    /// `timeout_secs` 이상 활동이 없는 노드 제거
+    pub fn remove_inactive(&mut self, now: u64, timeout_secs: u64) {
+        while let Some(entry) = self.by_last_seen.first_entry() {
+            let (last_seen, _) = *entry.key();
+            if now.saturating_sub(last_seen) < timeout_secs {
+                break;
+            }
+            let node_id = entry.remove();
+            self.nodes.remove(&node_id);
+            self.last_submitted.remove(&node_id);
+        }
+    }
+
+    /// 오래된 순서의 node_id 목록
+    pub fn iter_oldest_first(&self) -> impl Iterator<Item = &Arc<str>> {
+        self.by_last_seen.values()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_touch_evicts_least_recently_seen() {
+        let mut nodes = ActiveNodes::new(2);
+
+        nodes.touch(Arc::from("a"), 100);
+        nodes.touch(Arc::from("b"), 100);
+        nodes.touch(Arc::from("a"), 101); // a 갱신 -> b가 가장 오래됨
+        nodes.touch(Arc::from("c"), 101);
+
+        assert_eq!(nodes.len(), 2);
+        assert!(nodes.contains("a"));
+        assert!(!nodes.contains("b"));
+        assert!(nodes.contains("c"));
+        assert_eq!(nodes.evicted(), 1);
+    }
+
+    #[test]
+    fn test_touch_same_node_does_not_grow() {
+        let mut nodes = ActiveNodes::new(10);
+
+        for now in 0..5 {
+            nodes.touch(Arc::from("a"), now);
+        }
+
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(nodes.last_seen("a"), Some(4));
+        assert_eq!(nodes.iter_oldest_first().count(), 1);
+    }
+
+    #[test]
+    fn test_touch_does_not_count_as_submission() {
+        let mut nodes = ActiveNodes::new(1);
+
+        nodes.record_submission(Arc::from("a"), 100);
+        nodes.touch(Arc::from("a"), 200);
+        assert_eq!(nodes.last_seen("a"), Some(200));
+        assert_eq!(nodes.last_submitted("a"), Some(100));
+
+        // 제거된 노드의 제출 기록도 함께 제거
+        nodes.touch(Arc::from("b"), 300);
+        assert_eq!(nodes.last_submitted("a"), None);
+        nodes.record_submission(Arc::from("a"), 400);
+        nodes.remove_inactive(1_000, 120);
+        assert_eq!(nodes.last_submitted("a"), None);
+    }
+
+    #[test]
+    fn test_remove_inactive() {
+        let mut nodes = ActiveNodes::new(10);
+
+        nodes.touch(Arc::from("old"), 100);
+        nodes.touch(Arc::from("new"), 200);
+        nodes.remove_inactive(250, 120);
+
+        assert!(!nodes.contains("old"));
+        assert!(nodes.contains("new"));
+        // 타임아웃 제거는 용량 초과 제거 카운터에 포함하지 않음
+        assert_eq!(nodes.evicted(), 0);
+    }
+}
@@ -0,0 +1,45 @@
+//! 관리자 RPC 인증
+
+use tonic::metadata::MetadataMap;
+use tonic::Status;
+
+/// 관리자 비밀값을 담는 gRPC 메타데이터 키
+pub const ADMIN_SECRET_HEADER: &str = "x-admin-secret";
+
+/// 관리자 RPC 인증 실패 사유
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdminAuthError {
+    /// 서버에 관리자 비밀값이 설정되지 않음
+    Disabled,
+    /// 비밀값이 없거나 일치하지 않음
+    InvalidSecret,
+}
+
+impl From<AdminAuthError> for Status {
+    fn from(error: AdminAuthError) -> Self {
+        match error {
+            AdminAuthError::Disabled => Status::permission_denied("admin RPCs are disabled"),
+            AdminAuthError::InvalidSecret => Status::unauthenticated("invalid admin secret"),
+        }
+    }
+}
+
+/// 요청 메타데이터의 비밀값이 설정된 관리자 비밀값과 일치하는지 확인
+///
+/// 비밀값이 설정되지 않은 서버는 관리자 RPC를 모두 거부한다.
+pub fn authorize(metadata: &MetadataMap, admin_secret: Option<&str>) -> Result<(), AdminAuthError> {
+    let expected = admin_secret.ok_or(AdminAuthError::Disabled)?;
+
+    let provided = metadata
+        .get(ADMIN_SECRET_HEADER)
+        .and_then(|value| value.to_str().ok());
+    match provided {
+        Some(provided) if constant_time_eq(provided.as_bytes(), expected.as_bytes()) => Ok(()),
+        _ => Err(AdminAuthError::InvalidSecret),
+    }
+}
+
+// 비교 시간으로 비밀값이 드러나지 않도록 모든 바이트를 비교
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
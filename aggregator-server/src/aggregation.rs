@@ -0,0 +1,940 @@
+use oracle_vm_common::Price;
+use std::collections::{HashMap, HashSet};
+
+use crate::PriceEntry;
+
+/// `now` 기준 `window_secs` 이내의 타임스탬프인지 확인 (미래 시각은 제외)
+pub(crate) fn is_recent(timestamp: u64, now: u64, window_secs: u64) -> bool {
+    now.checked_sub(timestamp)
+        .is_some_and(|age| age < window_secs)
+}
+
+/// `now` 기준 `max_age_secs` 이상 지난 타임스탬프인지 확인 (미래 시각은 만료되지 않음)
+pub(crate) fn is_stale(timestamp: u64, now: u64, max_age_secs: u64) -> bool {
+    now.checked_sub(timestamp)
+        .is_some_and(|age| age >= max_age_secs)
+}
+
+/// 평균과 짝수 개 중간값의 보간을 계산하는 공간
+///
+/// 배수로 움직이는 자산은 로그 가격에서 평균한 뒤 되돌리는 편이 높은 가격 쪽으로 치우치지 않는다.
+/// 기하 모드에서 두 가격의 평균은 `sqrt(a × b)`, 여러 가격의 평균은 `exp(mean(ln p))`이며,
+/// 로그를 취할 수 없는 0 이하의 가격은 집계에서 제외한다.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AggregationMode {
+    /// 가격 그대로 평균 (기본값)
+    #[default]
+    Arithmetic,
+    /// 로그 가격에서 평균한 뒤 지수로 되돌림
+    Geometric,
+}
+
+impl AggregationMode {
+    /// 설정 이름으로 선택 (arithmetic, geometric)
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "arithmetic" => Some(Self::Arithmetic),
+            "geometric" => Some(Self::Geometric),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Arithmetic => "arithmetic",
+            Self::Geometric => "geometric",
+        }
+    }
+
+    /// 이 모드로 집계할 수 있는 가격인지 (기하 모드는 0 제외)
+    pub fn accepts(self, price: &Price) -> bool {
+        match self {
+            Self::Arithmetic => true,
+            Self::Geometric => price.mantissa() > 0,
+        }
+    }
+
+    /// 두 가격의 평균 (산술 모드는 정수 공간에서 정확히, 기하 모드는 두 가격 중 큰 자릿수로 반올림)
+    pub fn midpoint(self, a: &Price, b: &Price) -> Option<Price> {
+        match self {
+            Self::Arithmetic => a.midpoint(b),
+            Self::Geometric if a == b => Some(*a),
+            Self::Geometric => {
+                if !self.accepts(a) || !self.accepts(b) {
+                    return None;
+                }
+                // 곱하기 전에 제곱근을 취해 큰 가격에서도 넘치지 않음
+                let mean = a.to_f64_dollars().sqrt() * b.to_f64_dollars().sqrt();
+                Price::from_f64_dollars(mean, a.decimals().max(b.decimals())).ok()
+            }
+        }
+    }
+
+    /// 가격(달러)의 평균 (기하 모드는 0 이하의 가격을 제외, 남은 가격이 없으면 None)
+    pub fn mean(self, prices: &[f64]) -> Option<f64> {
+        match self {
+            Self::Arithmetic if !prices.is_empty() => {
+                Some(prices.iter().sum::<f64>() / prices.len() as f64)
+            }
+            Self::Arithmetic => None,
+            Self::Geometric => {
+                let logs: Vec<f64> = prices
+                    .iter()
+                    .filter(|&&price| price > 0.0)
+                    .map(|price| price.ln())
+                    .collect();
+                if logs.is_empty() {
+                    return None;
+                }
+                Some((logs.iter().sum::<f64>() / logs.len() as f64).exp())
+            }
+        }
+    }
+}
+
+/// 가격 슬라이스의 중간값을 계산 (전체 정렬 없이 제자리에서 선택)
+pub fn median_in_place(prices: &mut [f64]) -> Option<f64> {
+    let len = prices.len();
+    if len == 0 {
+        return None;
+    }
+
+    let mid = len / 2;
+    let (lower, upper, _) = prices.select_nth_unstable_by(mid, f64::total_cmp);
+    let upper = *upper;
+
+    if len.is_multiple_of(2) {
+        // 짝수 개인 경우 왼쪽 구간의 최댓값이 mid - 1 번째 값
+        let lower = lower.iter().copied().max_by(f64::total_cmp)?;
+        Some((lower + upper) / 2.0)
+    } else {
+        Some(upper)
+    }
+}
+
+/// 정수 공간에서 계산하는 중간값 (부동소수점 오차 없음)
+///
+/// 짝수 개일 때 두 값의 평균이 정수로 나누어떨어지지 않으면 소수 자릿수를 하나 늘려 정확히 표현한다.
+/// 자릿수가 서로 다른 가격도 가장 큰 자릿수로 맞춰 비교한다.
+pub fn median_exact_in_place(prices: &mut [Price]) -> Option<Price> {
+    median_exact_in_place_with(prices, AggregationMode::Arithmetic)
+}
+
+/// `mode` 공간에서 보간하는 중간값 (홀수 개면 모드와 관계없이 가운데 가격)
+///
+/// 기하 모드에서는 0인 가격을 뒤로 보내고 나머지만 사용한다.
+pub fn median_exact_in_place_with(prices: &mut [Price], mode: AggregationMode) -> Option<Price> {
+    let prices = usable_prices(prices, mode);
+    let len = prices.len();
+    if len == 0 {
+        return None;
+    }
+
+    // 보통은 모든 가격의 자릿수가 같으므로 비교마다 u128로 확장하지 않고 mantissa만 비교
+    let decimals = prices[0].decimals();
+    let uniform = prices.iter().all(|p| p.decimals() == decimals);
+
+    let mid = len / 2;
+    let (lower, upper) = if uniform {
+        let (lower, upper, _) = prices.select_nth_unstable_by_key(mid, Price::mantissa);
+        (lower.iter().max_by_key(|p| p.mantissa()), *upper)
+    } else {
+        let (lower, upper, _) = prices.select_nth_unstable(mid);
+        (lower.iter().max(), *upper)
+    };
+
+    if len.is_multiple_of(2) {
+        mode.midpoint(lower?, &upper)
+    } else {
+        Some(upper)
+    }
+}
+
+// 기하 모드에서 0인 가격을 뒤로 보내고 앞쪽의 사용할 수 있는 구간만 반환
+fn usable_prices(prices: &mut [Price], mode: AggregationMode) -> &mut [Price] {
+    let mut usable = 0;
+    for i in 0..prices.len() {
+        if mode.accepts(&prices[i]) {
+            prices.swap(usable, i);
+            usable += 1;
+        }
+    }
+    &mut prices[..usable]
+}
+
+/// 가중 중간값: 가격 순으로 누적한 가중치가 전체의 절반에 처음 도달하는 가격
+///
+/// 누적 가중치가 정확히 절반에서 끝나면 다음 가격과의 평균을 사용하므로 가중치가 모두 같으면
+/// `median_exact_in_place`와 같은 값이 된다. 가중치가 0 이하이거나 유한하지 않은 가격은 무시한다.
+pub fn weighted_median_in_place(prices: &mut [(Price, f64)]) -> Option<Price> {
+    weighted_median_in_place_with(prices, AggregationMode::Arithmetic)
+}
+
+/// `mode` 공간에서 보간하는 가중 중간값 (기하 모드에서는 0인 가격도 무시)
+pub fn weighted_median_in_place_with(
+    prices: &mut [(Price, f64)],
+    mode: AggregationMode,
+) -> Option<Price> {
+    prices.sort_unstable_by_key(|&(price, _)| price);
+    let weighted = || {
+        prices
+            .iter()
+            .filter(|(price, weight)| weight.is_finite() && *weight > 0.0 && mode.accepts(price))
+    };
+
+    let total: f64 = weighted().map(|(_, weight)| weight).sum();
+    if total <= 0.0 {
+        return None;
+    }
+    let half = total / 2.0;
+    let tolerance = total * 1e-12;
+
+    let mut cumulative = 0.0;
+    let mut remaining = weighted();
+    while let Some((price, weight)) = remaining.next() {
+        cumulative += weight;
+        if (cumulative - half).abs() <= tolerance {
+            return match remaining.next() {
+                Some((next, _)) => mode.midpoint(price, next),
+                None => Some(*price),
+            };
+        }
+        if cumulative > half {
+            return Some(*price);
+        }
+    }
+    None
+}
+
+/// 최근 `window_secs` 이내에 가격을 보낸 서로 다른 노드 수
+pub fn contributing_nodes(entries: &[&PriceEntry], now: u64, window_secs: u64) -> usize {
+    entries
+        .iter()
+        .filter(|p| is_recent(p.timestamp, now, window_secs))
+        .map(|p| &*p.node_id)
+        .collect::<HashSet<&str>>()
+        .len()
+}
+
+/// 최근 `window_secs` 이내 가격 데이터의 중간값 (정수 공간에서 계산)
+pub fn median_price_exact(entries: &[PriceEntry], now: u64, window_secs: u64) -> Option<Price> {
+    // 한 번만 할당하도록 미리 용량 확보
+    let mut recent_prices = Vec::with_capacity(entries.len());
+    recent_prices.extend(
+        entries
+            .iter()
+            .filter(|p| is_recent(p.timestamp, now, window_secs))
+            .map(|p| p.price),
+    );
+
+    median_exact_in_place(&mut recent_prices)
+}
+
+/// 최근 `window_secs` 이내 가격 데이터의 중간값 (달러)
+pub fn median_price(entries: &[PriceEntry], now: u64, window_secs: u64) -> Option<f64> {
+    median_price_exact(entries, now, window_secs).map(|price| price.to_f64_dollars())
+}
+
+/// 노드마다 윈도우 내 가장 최근 가격 하나만 사용한 중간값
+///
+/// 한 노드가 여러 source로 같은 가격을 보내거나 반복 제출해도 한 표로만 계산된다.
+/// 타임스탬프가 같으면 나중에 저장된 데이터를 사용한다.
+pub fn median_price_one_vote_per_node(
+    entries: &[PriceEntry],
+    now: u64,
+    window_secs: u64,
+) -> Option<Price> {
+    median_exact_in_place(&mut one_vote_per_node_prices(entries, now, window_secs))
+}
+
+/// 노드마다 `window_secs` 이내의 가장 최근 가격 하나
+pub fn one_vote_per_node_prices<'a>(
+    entries: impl IntoIterator<Item = &'a PriceEntry>,
+    now: u64,
+    window_secs: u64,
+) -> Vec<Price> {
+    let mut latest: HashMap<&str, (u64, Price)> = HashMap::new();
+    for entry in entries
+        .into_iter()
+        .filter(|p| is_recent(p.timestamp, now, window_secs))
+    {
+        latest
+            .entry(&entry.node_id)
+            .and_modify(|vote| {
+                if entry.timestamp >= vote.0 {
+                    *vote = (entry.timestamp, entry.price);
+                }
+            })
+            .or_insert((entry.timestamp, entry.price));
+    }
+
+    latest.into_values().map(|(_, price)| price).collect()
+}
+
+/// 노드마다 가장 최근 제출에서 `window_secs` 이내의 이전 제출을 그 최근 제출 하나로 합침
+///
+/// 다운타임에서 회복한 노드가 몰아서 보낸 제출이 윈도우를 채워 중간값을 그 노드 쪽으로 끌지 않도록
+/// 한다. 그보다 오래된 제출과 저장 순서는 그대로 두며, 타임스탬프가 같으면 나중에 저장된 데이터를
+/// 남긴다.
+pub fn coalesce_bursts<'a>(entries: &[&'a PriceEntry], window_secs: u64) -> Vec<&'a PriceEntry> {
+    let mut latest: HashMap<&str, (u64, usize)> = HashMap::new();
+    for (index, entry) in entries.iter().enumerate() {
+        latest
+            .entry(&entry.node_id)
+            .and_modify(|last| {
+                if entry.timestamp >= last.0 {
+                    *last = (entry.timestamp, index);
+                }
+            })
+            .or_insert((entry.timestamp, index));
+    }
+
+    entries
+        .iter()
+        .enumerate()
+        .filter(|(index, entry)| {
+            let (timestamp, latest_index) = latest[&*entry.node_id];
+            *index == latest_index || timestamp.saturating_sub(entry.timestamp) > window_secs
+        })
+        .map(|(_, &entry)| entry)
+        .collect()
+}
+
+/// 정렬된 슬라이스의 백분위수 (선형 보간, `p`는 0.0 ~ 1.0)
+///
+/// 순위 `p × (n - 1)`의 앞뒤 값을 선형 보간한다 (numpy 기본 방식과 동일). 0.5는 중간값과 같다.
+pub fn percentile_sorted(sorted: &[f64], p: f64) -> Option<f64> {
+    if sorted.is_empty() {
+        return None;
+    }
+
+    let rank = p.clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    let weight = rank - lower as f64;
+
+    Some(sorted[lower] + (sorted[upper] - sorted[lower]) * weight)
+}
+
+/// 최근 `window_secs` 이내 가격 데이터의 (p25, p75)
+pub fn quartiles(entries: &[&PriceEntry], now: u64, window_secs: u64) -> Option<(f64, f64)> {
+    let mut recent_prices: Vec<f64> = entries
+        .iter()
+        .filter(|p| is_recent(p.timestamp, now, window_secs))
+        .map(|p| p.price.to_f64_dollars())
+        .collect();
+    recent_prices.sort_unstable_by(f64::total_cmp);
+
+    Some((
+        percentile_sorted(&recent_prices, 0.25)?,
+        percentile_sorted(&recent_prices, 0.75)?,
+    ))
+}
+
+/// 양 끝에서 `trim_ratio` 비율만큼 제외한 평균 (절사 평균)
+///
+/// `trim_ratio`는 0.0 ~ 0.5 미만으로 제한된다. 슬라이스는 정렬된다.
+pub fn trimmed_mean_in_place(prices: &mut [f64], trim_ratio: f64) -> Option<f64> {
+    trimmed_mean_in_place_with(prices, trim_ratio, AggregationMode::Arithmetic)
+}
+
+/// `mode` 공간에서 계산하는 절사 평균 (기하 모드는 0 이하의 가격을 절사 전에 제외)
+pub fn trimmed_mean_in_place_with(
+    prices: &mut [f64],
+    trim_ratio: f64,
+    mode: AggregationMode,
+) -> Option<f64> {
+    prices.sort_unstable_by(f64::total_cmp);
+    let prices = match mode {
+        AggregationMode::Arithmetic => &prices[..],
+        AggregationMode::Geometric => &prices[prices.partition_point(|&price| price <= 0.0)..],
+    };
+    if prices.is_empty() {
+        return None;
+    }
+
+    let trim_ratio = trim_ratio.clamp(0.0, 0.49);
+    let trim = (prices.len() as f64 * trim_ratio) as usize;
+    mode.mean(&prices[trim..prices.len() - trim])
+}
+
+/// 최근 `window_secs` 이내 가격 데이터의 절사 평균 계산
+pub fn trimmed_mean_price(
+    entries: &[PriceEntry],
+    now: u64,
+    window_secs: u64,
+    trim_ratio: f64,
+) -> Option<f64> {
+    let mut recent_prices = Vec::with_capacity(entries.len());
+    recent_prices.extend(
+        entries
+            .iter()
+            .filter(|p| is_recent(p.timestamp, now, window_secs))
+            .map(|p| p.price.to_f64_dollars()),
+    );
+
+    trimmed_mean_in_place(&mut recent_prices, trim_ratio)
+}
+
+/// 가격 데이터를 훑지 않고 조회할 수 있는 통계 값
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RunningStatsSnapshot {
+    pub count: usize,
+    /// 평균 가격 (달러)
+    pub mean: Option<f64>,
+    pub min: Option<Price>,
+    pub max: Option<Price>,
+    /// 가장 최근에 추가된 가격
+    pub last: Option<Price>,
+}
+
+/// 추가/제거 시 O(1)로 갱신되는 누적 통계 (count/mean/min/max/last)
+///
+/// 합계는 최대 소수 자릿수 기준 정수로 유지해 추가/제거를 반복해도 오차가 쌓이지 않는다.
+/// 제거된 값이 현재 최솟값/최댓값일 때만 남은 데이터를 다시 훑어 극값을 찾는다.
+#[derive(Debug, Clone, Default)]
+pub struct RunningStats {
+    count: usize,
+    sum: u128,
+    min: Option<Price>,
+    max: Option<Price>,
+    last: Option<Price>,
+}
+
+impl RunningStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// 새 가격 반영
+    pub fn insert(&mut self, price: Price) {
+        self.count += 1;
+        // 가격은 MAX_PRICE_DOLLARS 이하로 검증되므로 넘치지 않지만, 넘치더라도 패닉하거나 되감기지 않음
+        self.sum = self.sum.saturating_add(widen(price));
+        self.min = Some(self.min.map_or(price, |min| min.min(price)));
+        self.max = Some(self.max.map_or(price, |max| max.max(price)));
+        self.last = Some(price);
+    }
+
+    /// 만료/제거된 가격 반영
+    ///
+    /// `remaining`은 제거 후 남아 있는 가격들이며, 제거된 값이 극값이었을 때만 호출된다.
+    pub fn remove<I>(&mut self, price: Price, remaining: impl FnOnce() -> I)
+    where
+        I: IntoIterator<Item = Price>,
+    {
+        if self.count == 0 {
+            return;
+        }
+
+        self.count -= 1;
+        self.sum -= widen(price).min(self.sum);
+
+        if self.count == 0 {
+            *self = Self::default();
+            return;
+        }
+
+        if self.min == Some(price) || self.max == Some(price) {
+            let (min, max) = remaining().into_iter().fold(
+                (None, None),
+                |(min, max): (Option<Price>, Option<Price>), p| {
+                    (
+                        Some(min.map_or(p, |m| m.min(p))),
+                        Some(max.map_or(p, |m| m.max(p))),
+                    )
+                },
+            );
+            self.min = min;
+            self.max = max;
+        }
+    }
+
+    pub fn snapshot(&self) -> RunningStatsSnapshot {
+        RunningStatsSnapshot {
+            count: self.count,
+            mean: (self.count > 0).then(|| {
+                self.sum as f64 / 10f64.powi(Price::MAX_DECIMALS as i32) / self.count as f64
+            }),
+            min: self.min,
+            max: self.max,
+            last: self.last,
+        }
+    }
+}
+
+// 최대 소수 자릿수 기준 정수 값 (u128에 항상 들어감)
+fn widen(price: Price) -> u128 {
+    price.mantissa() as u128 * 10u128.pow(Price::MAX_DECIMALS - price.decimals())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing, DEFAULT_PAIR, PRICE_WINDOW_SECS};
+    use std::sync::Arc;
+
+    // 기존 구현 (복제 후 전체 정렬) - 결과 비교용
+    fn naive_median(entries: &[PriceEntry], now: u64, window_secs: u64) -> Option<f64> {
+        let recent_prices: Vec<f64> = entries
+            .iter()
+            .filter(|p| is_recent(p.timestamp, now, window_secs))
+            .map(|p| p.price.to_f64_dollars())
+            .collect();
+
+        if recent_prices.is_empty() {
+            return None;
+        }
+
+        let mut sorted_prices = recent_prices.clone();
+        sorted_prices.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let len = sorted_prices.len();
+        if len.is_multiple_of(2) {
+            Some((sorted_prices[len / 2 - 1] + sorted_prices[len / 2]) / 2.0)
+        } else {
+            Some(sorted_prices[len / 2])
+        }
+    }
+
+    fn entry(price: f64, timestamp: u64) -> PriceEntry {
+        PriceEntry {
+            price: Price::from_f64_dollars(price, Price::USD_DECIMALS).unwrap(),
+            timestamp,
+            source: Arc::from("binance"),
+            node_id: Arc::from("node-1"),
+            pair: Arc::from(DEFAULT_PAIR),
+            volume: None,
+        }
+    }
+
+    fn cents(values: &[u64]) -> Vec<Price> {
+        values.iter().copied().map(Price::from_cents).collect()
+    }
+
+    #[test]
+    fn test_median_matches_naive_implementation() {
+        let now = 1_700_000_000;
+
+        for len in 0..50u64 {
+            // 정렬되지 않은 의사 난수 가격 (중복 포함)
+            let entries: Vec<PriceEntry> = (0..len)
+                .map(|i| entry(70_000.0 + ((i * 7919) % 31) as f64 * 12.5, now - (i % 90)))
+                .collect();
+
+            assert_eq!(
+                median_price(&entries, now, PRICE_WINDOW_SECS),
+                naive_median(&entries, now, PRICE_WINDOW_SECS),
+                "median mismatch for {} entries",
+                len
+            );
+        }
+    }
+
+    #[test]
+    fn test_median_odd_and_even() {
+        assert_eq!(median_in_place(&mut []), None);
+        assert_eq!(
+            median_in_place(&mut [70_100.0, 70_000.0, 70_200.0]),
+            Some(70_100.0)
+        );
+        assert_eq!(
+            median_in_place(&mut [70_300.0, 70_000.0, 70_200.0, 70_100.0]),
+            Some(70_150.0)
+        );
+    }
+
+    #[test]
+    fn test_median_ignores_old_and_future_entries() {
+        let now = 1_700_000_000;
+        let entries = vec![
+            entry(70_000.0, now - 10),
+            entry(10_000.0, now - PRICE_WINDOW_SECS), // 윈도우 밖
+            entry(99_000.0, now + 30),                // 미래 타임스탬프
+        ];
+
+        assert_eq!(
+            median_price(&entries, now, PRICE_WINDOW_SECS),
+            Some(70_000.0)
+        );
+    }
+
+    #[test]
+    fn test_exact_median_matches_float_median_for_simple_cases() {
+        let cases: [&[u64]; 4] = [
+            &[7_010_000, 7_000_000, 7_020_000],
+            &[7_030_000, 7_000_000, 7_020_000, 7_010_000],
+            &[7_000_012, 7_000_012, 7_000_013],
+            &[5_000, 6_000],
+        ];
+
+        for case in cases {
+            let mut exact = cents(case);
+            let mut float: Vec<f64> = case.iter().map(|&c| c as f64 / 100.0).collect();
+
+            assert_eq!(
+                median_exact_in_place(&mut exact).map(|p| p.to_f64_dollars()),
+                median_in_place(&mut float),
+                "mismatch for {:?}",
+                case
+            );
+        }
+        assert_eq!(median_exact_in_place(&mut []), None);
+    }
+
+    #[test]
+    fn test_geometric_mode_guards_non_positive_prices() {
+        let geometric = AggregationMode::Geometric;
+        let near_200 = |mean: Option<f64>| mean.is_some_and(|mean| (mean - 200.0).abs() < 1e-9);
+        assert!(near_200(geometric.mean(&[100.0, 400.0])));
+        assert!(near_200(geometric.mean(&[-5.0, 0.0, 100.0, 400.0])));
+        assert_eq!(geometric.mean(&[-5.0, 0.0]), None);
+        assert_eq!(AggregationMode::Arithmetic.mean(&[]), None);
+
+        let (low, high) = (Price::from_cents(10_000), Price::from_cents(40_000));
+        assert_eq!(
+            geometric.midpoint(&low, &high),
+            Some(Price::from_cents(20_000))
+        );
+        assert_eq!(geometric.midpoint(&Price::from_cents(0), &high), None);
+        assert_eq!(geometric.midpoint(&high, &high), Some(high));
+
+        let mut prices = cents(&[0, 10_000, 0, 40_000]);
+        assert_eq!(
+            median_exact_in_place_with(&mut prices, geometric),
+            Some(Price::from_cents(20_000))
+        );
+        let mut weighted = vec![(Price::from_cents(0), 5.0), (high, 1.0)];
+        assert_eq!(
+            weighted_median_in_place_with(&mut weighted, geometric),
+            Some(high)
+        );
+        assert!(near_200(trimmed_mean_in_place_with(
+            &mut [-1.0, 100.0, 400.0],
+            0.0,
+            geometric
+        )));
+    }
+
+    #[test]
+    fn test_weighted_median() {
+        let weighted = |prices: &[(u64, f64)]| {
+            let mut prices: Vec<(Price, f64)> = prices
+                .iter()
+                .map(|&(cents, weight)| (Price::from_cents(cents), weight))
+                .collect();
+            weighted_median_in_place(&mut prices)
+        };
+
+        // 가중치가 같으면 일반 중간값과 같음
+        for case in [&[10, 30, 20][..], &[10, 20, 30, 40], &[101, 102]] {
+            let equal: Vec<(u64, f64)> = case.iter().map(|&cents| (cents, 1.0)).collect();
+            assert_eq!(weighted(&equal), median_exact_in_place(&mut cents(case)));
+        }
+
+        // 무거운 가격 쪽으로 이동
+        assert_eq!(
+            weighted(&[(100, 3.0), (200, 1.0), (300, 1.0)]),
+            Some(Price::from_cents(100))
+        );
+        // 가중치 0은 무시
+        assert_eq!(
+            weighted(&[(100, 0.0), (200, 1.0), (300, 1.0), (400, 1.0)]),
+            Some(Price::from_cents(300))
+        );
+        assert_eq!(weighted(&[(100, 0.0)]), None);
+        assert_eq!(weighted(&[]), None);
+    }
+
+    #[test]
+    fn test_exact_median_where_float_is_not() {
+        // $0.10, $0.20 -> float 평균은 0.15000000000000002
+        let mut float = [0.1, 0.2];
+        assert_ne!(median_in_place(&mut float), Some(0.15));
+        assert_eq!(
+            median_exact_in_place(&mut cents(&[10, 20])),
+            Some(Price::from_cents(15))
+        );
+
+        // 홀수 합: $1.01, $1.02 -> 정확히 $1.015 (자릿수 하나 추가)
+        let median = median_exact_in_place(&mut cents(&[101, 102])).unwrap();
+        assert_eq!(median.to_scaled(), (1_015, 3));
+
+        // 2^53을 넘는 cent 값: float로는 구분되지 않는 값도 정확히 유지
+        let big = 9_007_199_254_740_993;
+        let median = median_exact_in_place(&mut cents(&[big, big + 2, big - 10])).unwrap();
+        assert_eq!(median, Price::from_cents(big));
+        let mut float: Vec<f64> = cents(&[big, big + 2, big - 10])
+            .iter()
+            .map(Price::to_f64_dollars)
+            .collect();
+        let float_median = median_in_place(&mut float).unwrap();
+        assert_ne!(
+            Price::from_f64_dollars(float_median, Price::USD_DECIMALS).unwrap(),
+            median
+        );
+    }
+
+    // 전체 정렬 후 가운데 값을 고르는 기준 구현
+    fn sorted_exact_median(prices: &[Price]) -> Option<Price> {
+        let mut sorted = prices.to_vec();
+        sorted.sort();
+        let len = sorted.len();
+        match len {
+            0 => None,
+            _ if len.is_multiple_of(2) => sorted[len / 2 - 1].midpoint(&sorted[len / 2]),
+            _ => Some(sorted[len / 2]),
+        }
+    }
+
+    #[test]
+    fn test_exact_median_matches_sorted_reference() {
+        let mut generator = testing::PriceGenerator::new(5, 10);
+
+        for len in 0..200 {
+            let uniform: Vec<Price> = (0..len).map(|_| generator.next_entry().price).collect();
+            // 일부를 더 많은 자릿수로 표현 (값은 동일하거나 1 단위씩 어긋남)
+            let mixed: Vec<Price> = uniform
+                .iter()
+                .enumerate()
+                .map(|(i, p)| match i % 3 {
+                    0 => p.rescale(8).unwrap(),
+                    1 => Price::new(p.rescale(6).unwrap().mantissa() + 1, 6).unwrap(),
+                    _ => *p,
+                })
+                .collect();
+
+            // 자릿수가 같으면 표현까지 동일
+            let expected = sorted_exact_median(&uniform);
+            let actual = median_exact_in_place(&mut uniform.clone());
+            assert_eq!(
+                actual.map(|p| p.to_scaled()),
+                expected.map(|p| p.to_scaled())
+            );
+
+            // 자릿수가 섞이면 같은 값의 어느 표현이 골라질지는 정해져 있지 않으므로 값만 비교
+            let expected = sorted_exact_median(&mixed);
+            assert_eq!(median_exact_in_place(&mut mixed.clone()), expected);
+        }
+    }
+
+    #[test]
+    fn test_exact_median_mixed_decimals() {
+        let mut prices = vec![
+            Price::from_cents(7_000_000),
+            Price::new(70_000_500_000, 6).unwrap(),
+        ];
+
+        assert_eq!(
+            median_exact_in_place(&mut prices),
+            Some(Price::new(70_000_250_000, 6).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_trimmed_mean() {
+        assert_eq!(trimmed_mean_in_place(&mut [], 0.1), None);
+        // 10개 중 양 끝 1개씩 제외
+        let mut prices = [1.0, 100.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0];
+        assert_eq!(trimmed_mean_in_place(&mut prices, 0.1), Some(10.0));
+        // 비율 0이면 일반 평균
+        assert_eq!(trimmed_mean_in_place(&mut [1.0, 2.0, 6.0], 0.0), Some(3.0));
+    }
+
+    #[test]
+    fn test_trimmed_mean_resists_generated_outliers() {
+        let now = 1_700_000_000;
+        let entries = testing::PriceGenerator::new(7, 20)
+            .with_now(now)
+            .entries(1_000);
+
+        let trimmed = trimmed_mean_price(&entries, now, PRICE_WINDOW_SECS, 0.1).unwrap();
+        let median = median_price(&entries, now, PRICE_WINDOW_SECS).unwrap();
+
+        // ±0.1% 분포 안에 머무르며 중간값과 가까움
+        assert!((trimmed - 70_000.0).abs() < 70.0);
+        assert!((trimmed - median).abs() < 70.0);
+    }
+
+    #[test]
+    fn test_percentile_linear_interpolation() {
+        let sorted = [10.0, 20.0, 30.0, 40.0, 50.0];
+
+        assert_eq!(percentile_sorted(&sorted, 0.25), Some(20.0));
+        assert_eq!(percentile_sorted(&sorted, 0.5), Some(30.0));
+        assert_eq!(percentile_sorted(&sorted, 0.75), Some(40.0));
+        // 순위 0.75 -> 10 + (20 - 10) × 0.75
+        assert_eq!(
+            percentile_sorted(&[10.0, 20.0, 30.0, 40.0], 0.25),
+            Some(17.5)
+        );
+        assert_eq!(
+            percentile_sorted(&[10.0, 20.0, 30.0, 40.0], 0.75),
+            Some(32.5)
+        );
+        assert_eq!(percentile_sorted(&[42.0], 0.25), Some(42.0));
+        assert_eq!(percentile_sorted(&[], 0.25), None);
+    }
+
+    #[test]
+    fn test_quartiles_of_known_distribution() {
+        let now = 1_700_000_000;
+        // 70_000 ~ 70_100 균등 분포 (101개, 순서 섞음) + 윈도우 밖 이상치
+        let mut entries: Vec<PriceEntry> = (0..=100u64)
+            .map(|i| entry(70_000.0 + ((i * 37) % 101) as f64, now - (i % 30)))
+            .collect();
+        entries.push(entry(1.0, now - PRICE_WINDOW_SECS));
+        let entries: Vec<&PriceEntry> = entries.iter().collect();
+
+        assert_eq!(
+            quartiles(&entries, now, PRICE_WINDOW_SECS),
+            Some((70_025.0, 70_075.0))
+        );
+        assert_eq!(quartiles(&[], now, PRICE_WINDOW_SECS), None);
+    }
+
+    fn stats_of(values: &[u64]) -> RunningStats {
+        let mut stats = RunningStats::new();
+        for &c in values {
+            stats.insert(Price::from_cents(c));
+        }
+        stats
+    }
+
+    #[test]
+    fn test_running_stats_insert() {
+        let stats = stats_of(&[7_000_000, 6_990_000, 7_040_000]);
+        let snapshot = stats.snapshot();
+
+        assert_eq!(snapshot.count, 3);
+        assert_eq!(snapshot.mean, Some(70_100.0));
+        assert_eq!(snapshot.min, Some(Price::from_cents(6_990_000)));
+        assert_eq!(snapshot.max, Some(Price::from_cents(7_040_000)));
+        assert_eq!(snapshot.last, Some(Price::from_cents(7_040_000)));
+        assert_eq!(
+            RunningStats::new().snapshot(),
+            RunningStatsSnapshot::default()
+        );
+    }
+
+    #[test]
+    fn test_running_stats_sum_saturates_instead_of_overflowing() {
+        let huge = Price::from_scaled(u64::MAX, 0).unwrap();
+        let mut stats = RunningStats::new();
+        for _ in 0..40 {
+            stats.insert(huge);
+        }
+        stats.remove(huge, || vec![huge; 39]);
+
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.count, 39);
+        assert_eq!(snapshot.max, Some(huge));
+        assert!(snapshot
+            .mean
+            .is_some_and(|mean| mean.is_finite() && mean > 0.0));
+    }
+
+    #[test]
+    fn test_running_stats_remove_non_extremum_skips_rescan() {
+        let mut stats = stats_of(&[100, 300, 200]);
+
+        stats.remove(Price::from_cents(200), || -> Vec<Price> {
+            panic!("rescan should not be needed")
+        });
+
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.count, 2);
+        assert_eq!(snapshot.mean, Some(2.0));
+        assert_eq!(snapshot.min, Some(Price::from_cents(100)));
+        assert_eq!(snapshot.max, Some(Price::from_cents(300)));
+    }
+
+    #[test]
+    fn test_running_stats_min_ages_out_finds_next_min() {
+        // 오래된 순서: 100(최소), 500(최대), 300, 200
+        let mut window = cents(&[100, 500, 300, 200]);
+        let mut stats = stats_of(&[100, 500, 300, 200]);
+
+        let expired = window.remove(0);
+        let mut rescans = 0;
+        stats.remove(expired, || {
+            rescans += 1;
+            window.clone()
+        });
+
+        let snapshot = stats.snapshot();
+        assert_eq!(rescans, 1);
+        assert_eq!(snapshot.min, Some(Price::from_cents(200)));
+        assert_eq!(snapshot.max, Some(Price::from_cents(500)));
+        assert_eq!(snapshot.count, 3);
+        assert_eq!(snapshot.last, Some(Price::from_cents(200)));
+
+        // 이어서 최댓값도 만료
+        let expired = window.remove(0);
+        stats.remove(expired, || window.clone());
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.min, Some(Price::from_cents(200)));
+        assert_eq!(snapshot.max, Some(Price::from_cents(300)));
+        assert_eq!(snapshot.mean, Some(2.5));
+    }
+
+    #[test]
+    fn test_running_stats_duplicate_min_survives_removal() {
+        let mut window = cents(&[100, 100, 300]);
+        let mut stats = stats_of(&[100, 100, 300]);
+
+        let expired = window.remove(0);
+        stats.remove(expired, || window.clone());
+
+        assert_eq!(stats.snapshot().min, Some(Price::from_cents(100)));
+    }
+
+    #[test]
+    fn test_running_stats_remove_last_entry_resets() {
+        let mut stats = stats_of(&[100]);
+
+        stats.remove(Price::from_cents(100), Vec::new);
+
+        assert!(stats.is_empty());
+        assert_eq!(stats.snapshot(), RunningStatsSnapshot::default());
+        // 비어 있으면 무시
+        stats.remove(Price::from_cents(100), Vec::new);
+        assert_eq!(stats.count(), 0);
+    }
+
+    #[test]
+    fn test_running_stats_matches_full_scan() {
+        let mut generator = testing::PriceGenerator::new(3, 5);
+        let mut window: Vec<Price> = Vec::new();
+        let mut stats = RunningStats::new();
+
+        for i in 0..2_000 {
+            let price = generator.next_entry().price;
+            window.push(price);
+            stats.insert(price);
+            // 크기 50의 슬라이딩 윈도우
+            if window.len() > 50 {
+                let expired = window.remove(0);
+                stats.remove(expired, || window.clone());
+            }
+
+            if i % 97 == 0 {
+                let snapshot = stats.snapshot();
+                assert_eq!(snapshot.count, window.len());
+                assert_eq!(snapshot.min, window.iter().min().copied());
+                assert_eq!(snapshot.max, window.iter().max().copied());
+                let mean =
+                    window.iter().map(Price::to_f64_dollars).sum::<f64>() / window.len() as f64;
+                assert!((snapshot.mean.unwrap() - mean).abs() < 1e-6);
+            }
+        }
+    }
+}
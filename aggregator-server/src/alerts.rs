@@ -0,0 +1,765 @@
+//! 집계 가격 급변, 정족수 미달, 집계 끊김을 Slack/Discord 웹훅으로 알리는 경보
+//!
+//! 규칙은 집계 태스크가 집계를 게시할 때마다 그 스냅샷으로 평가한다 (`AlertSender::evaluate`). 조건이
+//! 시작되면 firing, 풀리면 resolved 알림을 만들어 bounded 채널에 `try_send`로만 넘기므로 웹훅이 느리거나
+//! 죽어 있어도 집계는 늦어지지 않는다 (가득 차면 버림). 알림 태스크가 채널에서 꺼내 설정된 Slack과
+//! Discord 웹훅 모두로 POST한다.
+//!
+//! 규칙마다 firing 알림은 `cooldown`에 한 번까지만 보낸다. 쿨다운 중에 다시 시작된 조건은 억제되고
+//! (`suppressed`), 쿨다운이 지난 뒤에도 계속되고 있으면 그때 알린다. 보내지 않은 firing에는 resolved도
+//! 보내지 않는다.
+//!
+//! - `price_move`: 집계 가격이 `window_secs` 안에 게시된 어느 집계 가격에서든 `threshold_bps` 이상
+//!   떨어져 있을 때 (stale 가격은 비교하지 않고, 신선한 가격이 없으면 상태를 유지)
+//! - `quorum`: 집계에 참여한 노드가 `min_nodes`보다 적을 때
+//! - `no_data`: 신선한 집계 가격이 `after_secs` 이상 없을 때 (서버 시작부터 셈)
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::sync::mpsc::{self, error::TrySendError};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use crate::metrics::AggregatorMetrics;
+use crate::snapshot::{AggregateSnapshot, AggregateUpdate};
+use crate::DEFAULT_PAIR;
+
+/// 경보 규칙
+#[derive(Debug, Clone, PartialEq)]
+pub enum AlertRule {
+    /// `window_secs` 안의 집계 가격 대비 `threshold_bps` 이상 변화
+    PriceMove {
+        threshold_bps: u32,
+        window_secs: u64,
+    },
+    /// 참여 노드 수가 `min_nodes` 미만
+    Quorum { min_nodes: usize },
+    /// 신선한 집계 가격이 `after_secs` 이상 없음
+    NoData { after_secs: u64 },
+}
+
+impl AlertRule {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::PriceMove { .. } => "price_move",
+            Self::Quorum { .. } => "quorum",
+            Self::NoData { .. } => "no_data",
+        }
+    }
+}
+
+/// 규칙 상태 변화
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertState {
+    /// 조건이 시작됨
+    Firing,
+    /// 조건이 풀림
+    Resolved,
+    /// 조건이 시작됐지만 쿨다운 중이라 알리지 않음 (알림으로 보내지 않고 지표로만 셈)
+    Suppressed,
+}
+
+impl AlertState {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Firing => "firing",
+            Self::Resolved => "resolved",
+            Self::Suppressed => "suppressed",
+        }
+    }
+}
+
+impl fmt::Display for AlertState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// 규칙 하나의 상태 변화 알림
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Alert {
+    pub rule: &'static str,
+    pub state: AlertState,
+    pub pair: String,
+    /// 사람이 읽는 설명 (예: "BTC/USD moved 5.20% in 300s (70000.00 -> 73640.00)")
+    pub summary: String,
+    /// 평가한 집계의 가격 (USD)
+    pub aggregated_price: Option<f64>,
+    pub contributing_nodes: usize,
+    /// 평가한 집계의 시각
+    pub timestamp: u64,
+}
+
+impl Alert {
+    /// 채팅 메시지 한 줄 (예: "🚨 [FIRING] quorum: ...")
+    pub fn message(&self) -> String {
+        let (icon, label) = match self.state {
+            AlertState::Firing => ("🚨", "FIRING"),
+            AlertState::Resolved => ("✅", "RESOLVED"),
+            AlertState::Suppressed => ("🔕", "SUPPRESSED"),
+        };
+        format!("{} [{}] {}: {}", icon, label, self.rule, self.summary)
+    }
+}
+
+/// 알림을 받는 채팅 웹훅 종류 (본문 형식만 다름)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotifierKind {
+    /// Slack incoming webhook (`{"text": ...}`)
+    Slack,
+    /// Discord webhook (`{"content": ...}`)
+    Discord,
+}
+
+impl NotifierKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Slack => "slack",
+            Self::Discord => "discord",
+        }
+    }
+
+    /// 웹훅 본문
+    pub fn payload(&self, alert: &Alert) -> serde_json::Value {
+        match self {
+            Self::Slack => serde_json::json!({ "text": alert.message() }),
+            Self::Discord => serde_json::json!({ "content": alert.message() }),
+        }
+    }
+}
+
+/// 경보 설정
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertsConfig {
+    pub rules: Vec<AlertRule>,
+    /// Slack incoming webhook URL
+    pub slack_urls: Vec<String>,
+    /// Discord webhook URL
+    pub discord_urls: Vec<String>,
+    /// 규칙마다 firing 알림 사이의 최소 간격
+    pub cooldown: Duration,
+    /// 전송 대기 채널 크기 (가득 차면 알림을 버림)
+    pub channel_capacity: usize,
+    /// 요청 하나의 시간 제한
+    pub request_timeout: Duration,
+    /// 종료할 때 미전송 알림을 기다리는 시간
+    pub drain_timeout: Duration,
+}
+
+impl Default for AlertsConfig {
+    fn default() -> Self {
+        Self {
+            rules: Vec::new(),
+            slack_urls: Vec::new(),
+            discord_urls: Vec::new(),
+            cooldown: Duration::from_secs(900),
+            channel_capacity: 100,
+            request_timeout: Duration::from_secs(5),
+            drain_timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct RuleState {
+    rule: AlertRule,
+    /// firing을 보냈고 아직 풀리지 않음
+    firing: bool,
+    /// 쿨다운으로 억제된 조건이 계속되는 중 (억제는 한 번만 셈)
+    suppressed: bool,
+    last_fired_at: Option<u64>,
+}
+
+/// 게시된 집계를 순서대로 받아 규칙별 상태 변화를 계산하는 엔진 (시각은 스냅샷 시각 기준)
+#[derive(Debug)]
+pub struct AlertEngine {
+    rules: Vec<RuleState>,
+    cooldown_secs: u64,
+    /// 가장 긴 price_move 윈도우 안에 게시된 신선한 집계 가격 (오래된 순)
+    prices: VecDeque<(u64, f64)>,
+    last_fresh_at: Option<u64>,
+}
+
+impl AlertEngine {
+    pub fn new(rules: Vec<AlertRule>, cooldown: Duration) -> Self {
+        Self {
+            rules: rules
+                .into_iter()
+                .map(|rule| RuleState {
+                    rule,
+                    firing: false,
+                    suppressed: false,
+                    last_fired_at: None,
+                })
+                .collect(),
+            cooldown_secs: cooldown.as_secs(),
+            prices: VecDeque::new(),
+            last_fresh_at: None,
+        }
+    }
+
+    /// 집계 하나를 평가해 규칙별 상태 변화를 반환
+    pub fn evaluate(&mut self, snapshot: &AggregateSnapshot) -> Vec<Alert> {
+        let now = snapshot.timestamp;
+        let price = snapshot
+            .aggregated_price
+            .filter(|_| !snapshot.stale)
+            .map(|price| price.to_f64_dollars());
+        if price.is_some() || self.last_fresh_at.is_none() {
+            self.last_fresh_at = Some(now);
+        }
+        let max_window = self
+            .rules
+            .iter()
+            .filter_map(|state| match state.rule {
+                AlertRule::PriceMove { window_secs, .. } => Some(window_secs),
+                _ => None,
+            })
+            .max();
+
+        let mut alerts = Vec::new();
+        for index in 0..self.rules.len() {
+            let Some((active, summary)) = self.check(&self.rules[index].rule, now, price, snapshot)
+            else {
+                continue;
+            };
+            let cooldown_secs = self.cooldown_secs;
+            let state = &mut self.rules[index];
+            let transition = match (active, state.firing) {
+                (true, false) => {
+                    let cooled_down = state
+                        .last_fired_at
+                        .is_none_or(|fired_at| now.saturating_sub(fired_at) >= cooldown_secs);
+                    if cooled_down {
+                        state.firing = true;
+                        state.suppressed = false;
+                        state.last_fired_at = Some(now);
+                        Some(AlertState::Firing)
+                    } else if !state.suppressed {
+                        state.suppressed = true;
+                        Some(AlertState::Suppressed)
+                    } else {
+                        None
+                    }
+                }
+                (false, true) => {
+                    state.firing = false;
+                    Some(AlertState::Resolved)
+                }
+                (false, false) => {
+                    state.suppressed = false;
+                    None
+                }
+                (true, true) => None,
+            };
+            if let Some(transition) = transition {
+                alerts.push(Alert {
+                    rule: state.rule.name(),
+                    state: transition,
+                    pair: DEFAULT_PAIR.to_string(),
+                    summary,
+                    aggregated_price: snapshot
+                        .aggregated_price
+                        .map(|price| price.to_f64_dollars()),
+                    contributing_nodes: snapshot.contributing_nodes,
+                    timestamp: now,
+                });
+            }
+        }
+
+        if let (Some(price), Some(window)) = (price, max_window) {
+            self.prices.push_back((now, price));
+            while self
+                .prices
+                .front()
+                .is_some_and(|(at, _)| now.saturating_sub(*at) > window)
+            {
+                self.prices.pop_front();
+            }
+        }
+        alerts
+    }
+
+    // 규칙 조건이 성립하는지와 설명 (판단할 수 없으면 None이며 상태를 유지)
+    fn check(
+        &self,
+        rule: &AlertRule,
+        now: u64,
+        price: Option<f64>,
+        snapshot: &AggregateSnapshot,
+    ) -> Option<(bool, String)> {
+        match *rule {
+            AlertRule::PriceMove {
+                threshold_bps,
+                window_secs,
+            } => {
+                let price = price?;
+                let (reference, bps) = self
+                    .prices
+                    .iter()
+                    .filter(|(at, reference)| {
+                        now.saturating_sub(*at) <= window_secs && *reference > 0.0
+                    })
+                    .map(|&(_, reference)| {
+                        (reference, (price - reference).abs() / reference * 10_000.0)
+                    })
+                    .max_by(|a, b| a.1.total_cmp(&b.1))
+                    .unwrap_or((price, 0.0));
+                let active = bps >= f64::from(threshold_bps);
+                let summary = if active {
+                    format!(
+                        "{} moved {:.2}% in {}s ({:.2} -> {:.2})",
+                        DEFAULT_PAIR,
+                        bps / 100.0,
+                        window_secs,
+                        reference,
+                        price
+                    )
+                } else {
+                    format!(
+                        "{} moved less than {:.2}% over the last {}s ({:.2})",
+                        DEFAULT_PAIR,
+                        f64::from(threshold_bps) / 100.0,
+                        window_secs,
+                        price
+                    )
+                };
+                Some((active, summary))
+            }
+            AlertRule::Quorum { min_nodes } => {
+                let nodes = snapshot.contributing_nodes;
+                let summary = if nodes < min_nodes {
+                    format!(
+                        "{} has {} contributing node(s), below the quorum of {}",
+                        DEFAULT_PAIR, nodes, min_nodes
+                    )
+                } else {
+                    format!(
+                        "{} is back to {} contributing node(s) (quorum {})",
+                        DEFAULT_PAIR, nodes, min_nodes
+                    )
+                };
+                Some((nodes < min_nodes, summary))
+            }
+            AlertRule::NoData { after_secs } => {
+                let silent_secs = now.saturating_sub(self.last_fresh_at.unwrap_or(now));
+                let active = price.is_none() && silent_secs >= after_secs;
+                let summary = match price {
+                    Some(price) => format!("Fresh {} aggregate again ({:.2})", DEFAULT_PAIR, price),
+                    None => format!("No fresh {} aggregate for {}s", DEFAULT_PAIR, silent_secs),
+                };
+                Some((active, summary))
+            }
+        }
+    }
+}
+
+/// 집계 태스크가 게시할 때마다 규칙을 평가하고 알림을 알림 태스크로 넘기는 핸들
+#[derive(Clone)]
+pub struct AlertSender {
+    engine: Arc<Mutex<AlertEngine>>,
+    tx: mpsc::Sender<Alert>,
+    metrics: AggregatorMetrics,
+}
+
+impl AlertSender {
+    /// 게시된 집계 하나로 규칙 평가 (알림은 기다리지 않고 넘기며 채널이 가득 차면 버림)
+    pub fn evaluate(&self, snapshot: &AggregateSnapshot) {
+        let alerts = self
+            .engine
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .evaluate(snapshot);
+        for alert in alerts {
+            self.metrics.record_alert(alert.rule, alert.state.name());
+            if alert.state == AlertState::Suppressed {
+                debug!("🔕 {}", alert.message());
+                continue;
+            }
+            info!("{}", alert.message());
+            match self.tx.try_send(alert) {
+                Ok(()) => {}
+                Err(TrySendError::Full(alert)) | Err(TrySendError::Closed(alert)) => {
+                    warn!("⚠️ Dropped {} alert for {}", alert.state, alert.rule);
+                }
+            }
+        }
+    }
+}
+
+struct Notifier {
+    kind: NotifierKind,
+    url: reqwest::Url,
+    /// 지표 라벨 (URL 경로에 토큰이 들어 있어 종류와 호스트만)
+    label: String,
+}
+
+/// 알림 태스크 실행
+///
+/// 반환한 `AlertSender`를 `AggregatorServiceImpl::with_alerts`로 넘긴다. `updates`는 종료 알림을 받기
+/// 위한 구독이며, 종료 알림을 받으면 이미 받은 알림을 `drain_timeout`까지 보낸 뒤 태스크가 끝난다.
+pub fn spawn(
+    config: AlertsConfig,
+    metrics: AggregatorMetrics,
+    updates: broadcast::Receiver<AggregateUpdate>,
+) -> Result<(AlertSender, JoinHandle<()>)> {
+    let client = reqwest::Client::builder()
+        .timeout(config.request_timeout)
+        .build()
+        .context("Failed to build alert HTTP client")?;
+
+    let urls = config
+        .slack_urls
+        .iter()
+        .map(|url| (NotifierKind::Slack, url))
+        .chain(
+            config
+                .discord_urls
+                .iter()
+                .map(|url| (NotifierKind::Discord, url)),
+        );
+    let mut notifiers = Vec::new();
+    for (kind, url) in urls {
+        let url = reqwest::Url::parse(url)
+            .with_context(|| format!("Invalid {} alert webhook URL", kind.name()))?;
+        let label = format!("{} {}", kind.name(), url.host_str().unwrap_or_default());
+        notifiers.push(Notifier { kind, url, label });
+    }
+    let rules: Vec<&str> = config.rules.iter().map(AlertRule::name).collect();
+    info!(
+        "🚨 Alerting on {:?} to {} webhook(s), at most once per {:?} per rule",
+        rules,
+        notifiers.len(),
+        config.cooldown
+    );
+
+    let (tx, rx) = mpsc::channel(config.channel_capacity.max(1));
+    let sender = AlertSender {
+        engine: Arc::new(Mutex::new(AlertEngine::new(config.rules, config.cooldown))),
+        tx,
+        metrics: metrics.clone(),
+    };
+    let task = NotifyTask {
+        client,
+        notifiers,
+        metrics,
+        drain_timeout: config.drain_timeout,
+    };
+    Ok((sender, tokio::spawn(task.run(rx, updates))))
+}
+
+struct NotifyTask {
+    client: reqwest::Client,
+    notifiers: Vec<Notifier>,
+    metrics: AggregatorMetrics,
+    drain_timeout: Duration,
+}
+
+impl NotifyTask {
+    async fn run(
+        self,
+        mut alerts: mpsc::Receiver<Alert>,
+        mut updates: broadcast::Receiver<AggregateUpdate>,
+    ) {
+        loop {
+            tokio::select! {
+                alert = alerts.recv() => match alert {
+                    Some(alert) => self.notify(&alert).await,
+                    None => return,
+                },
+                update = updates.recv() => match update {
+                    Ok(AggregateUpdate::Shutdown) | Err(RecvError::Closed) => break,
+                    Ok(AggregateUpdate::Published(_)) | Err(RecvError::Lagged(_)) => {}
+                },
+            }
+        }
+
+        // 종료: 이미 평가된 알림을 시간 제한 안에서 보냄
+        let drain = async {
+            while let Ok(alert) = alerts.try_recv() {
+                self.notify(&alert).await;
+            }
+        };
+        if tokio::time::timeout(self.drain_timeout, drain)
+            .await
+            .is_err()
+        {
+            warn!("⚠️ Gave up on pending alerts");
+        }
+        info!("🚨 Alert notifier stopped");
+    }
+
+    // 모든 웹훅으로 한 번씩 POST (실패는 로그와 지표만 남김)
+    async fn notify(&self, alert: &Alert) {
+        for notifier in &self.notifiers {
+            let outcome = match self
+                .client
+                .post(notifier.url.clone())
+                .json(&notifier.kind.payload(alert))
+                .send()
+                .await
+            {
+                Ok(response) if response.status().is_success() => "delivered",
+                Ok(response) => {
+                    warn!(
+                        "⚠️ {} alert webhook answered HTTP {}",
+                        notifier.label,
+                        response.status()
+                    );
+                    "failed"
+                }
+                Err(e) => {
+                    warn!("⚠️ {} alert webhook failed: {}", notifier.label, e);
+                    "failed"
+                }
+            };
+            self.metrics
+                .record_webhook_delivery(&notifier.label, outcome);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oracle::oracle_service_server::OracleService;
+    use crate::oracle::PriceRequest;
+    use crate::AggregatorServiceImpl;
+    use axum::extract::State;
+    use axum::http::StatusCode;
+    use axum::routing::post;
+    use axum::{Json, Router};
+    use oracle_vm_common::Price;
+    use tonic::Request;
+
+    const T0: u64 = 1_700_000_000;
+
+    fn snapshot(timestamp: u64, price: Option<f64>, nodes: usize) -> AggregateSnapshot {
+        AggregateSnapshot {
+            aggregated_price: price.map(|price| Price::from_f64_dollars(price, 8).unwrap()),
+            contributing_nodes: nodes,
+            timestamp,
+            ..AggregateSnapshot::default()
+        }
+    }
+
+    // 상태 변화만 (규칙, 상태)로
+    fn transitions(alerts: &[Alert]) -> Vec<(&'static str, AlertState)> {
+        alerts
+            .iter()
+            .map(|alert| (alert.rule, alert.state))
+            .collect()
+    }
+
+    #[test]
+    fn test_price_move_fires_cools_down_and_resolves() {
+        let mut engine = AlertEngine::new(
+            vec![AlertRule::PriceMove {
+                threshold_bps: 200,
+                window_secs: 300,
+            }],
+            Duration::from_secs(600),
+        );
+        let mut at = |offset: u64, price: f64| {
+            transitions(&engine.evaluate(&snapshot(T0 + offset, Some(price), 3)))
+        };
+        let firing = [("price_move", AlertState::Firing)];
+        let resolved = [("price_move", AlertState::Resolved)];
+        let suppressed = [("price_move", AlertState::Suppressed)];
+
+        assert!(at(0, 70_000.0).is_empty());
+        assert!(at(60, 70_700.0).is_empty()); // 1%
+                                              // 2분 동안 3% 상승
+        assert_eq!(at(120, 72_100.0), firing);
+        assert!(at(180, 72_200.0).is_empty());
+        // 70,000이 윈도우를 벗어나도 70,700 대비 여전히 2% 이상
+        assert!(at(330, 72_150.0).is_empty());
+        // 상승 전 가격이 모두 윈도우를 벗어나면 풀림
+        assert_eq!(at(400, 72_150.0), resolved);
+
+        // 쿨다운(600초) 안에 다시 급락하면 한 번만 억제로 셈
+        assert_eq!(at(460, 70_000.0), suppressed);
+        assert!(at(520, 69_900.0).is_empty());
+        // 쿨다운이 지나도 조건이 계속되면 그때 firing
+        assert_eq!(at(720, 68_000.0), firing);
+        assert_eq!(at(1_100, 68_010.0), resolved);
+
+        // 억제된 조건이 알리기 전에 풀리면 resolved도 보내지 않음
+        assert_eq!(at(1_200, 70_000.0), suppressed);
+        assert!(at(1_600, 70_010.0).is_empty());
+    }
+
+    #[test]
+    fn test_quorum_and_no_data_rules() {
+        let mut engine = AlertEngine::new(
+            vec![
+                AlertRule::Quorum { min_nodes: 3 },
+                AlertRule::NoData { after_secs: 120 },
+            ],
+            Duration::from_secs(60),
+        );
+
+        let alerts = engine.evaluate(&snapshot(T0, Some(70_000.0), 3));
+        assert!(alerts.is_empty());
+
+        let alerts = engine.evaluate(&snapshot(T0 + 10, Some(70_000.0), 2));
+        assert_eq!(transitions(&alerts), [("quorum", AlertState::Firing)]);
+        assert_eq!(
+            alerts[0].summary,
+            "BTC/USD has 2 contributing node(s), below the quorum of 3"
+        );
+        assert_eq!(alerts[0].aggregated_price, Some(70_000.0));
+
+        // 집계가 끊기면 정족수는 이미 firing이고, no_data는 120초가 지나야 firing
+        assert!(engine.evaluate(&snapshot(T0 + 60, None, 0)).is_empty());
+        let alerts = engine.evaluate(&snapshot(T0 + 130, None, 0));
+        assert_eq!(transitions(&alerts), [("no_data", AlertState::Firing)]);
+        assert_eq!(alerts[0].summary, "No fresh BTC/USD aggregate for 120s");
+        assert_eq!(alerts[0].aggregated_price, None);
+
+        // stale 가격은 신선한 집계가 아님
+        let mut stale = snapshot(T0 + 140, Some(70_000.0), 0);
+        stale.stale = true;
+        assert!(engine.evaluate(&stale).is_empty());
+
+        let alerts = engine.evaluate(&snapshot(T0 + 200, Some(70_100.0), 3));
+        assert_eq!(
+            transitions(&alerts),
+            [
+                ("quorum", AlertState::Resolved),
+                ("no_data", AlertState::Resolved)
+            ]
+        );
+        assert_eq!(
+            alerts[1].message(),
+            "✅ [RESOLVED] no_data: Fresh BTC/USD aggregate again (70100.00)"
+        );
+    }
+
+    #[test]
+    fn test_no_data_counts_from_startup() {
+        let mut engine =
+            AlertEngine::new(vec![AlertRule::NoData { after_secs: 30 }], Duration::ZERO);
+        assert!(engine.evaluate(&snapshot(T0, None, 0)).is_empty());
+        assert_eq!(
+            transitions(&engine.evaluate(&snapshot(T0 + 30, None, 0))),
+            [("no_data", AlertState::Firing)]
+        );
+    }
+
+    type Received = Arc<Mutex<Vec<(String, serde_json::Value)>>>;
+
+    async fn receive(
+        State((received, kind)): State<(Received, &'static str)>,
+        Json(body): Json<serde_json::Value>,
+    ) -> StatusCode {
+        received.lock().unwrap().push((kind.to_string(), body));
+        StatusCode::NO_CONTENT
+    }
+
+    async fn serve(received: Received) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = Router::new()
+            .route(
+                "/slack",
+                post(receive).with_state((received.clone(), "slack")),
+            )
+            .route("/discord", post(receive).with_state((received, "discord")));
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        format!("http://{}", addr)
+    }
+
+    fn price_request(price: f64, node_id: &str) -> Request<PriceRequest> {
+        Request::new(PriceRequest {
+            price,
+            timestamp: chrono::Utc::now().timestamp() as u64,
+            source: "binance".to_string(),
+            node_id: node_id.to_string(),
+            signature: None,
+            price_scaled: None,
+            price_decimals: None,
+            symbol: Some("BTCUSDT".to_string()),
+            historical: false,
+            volume: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_published_aggregates_notify_slack_and_discord() {
+        let received = Received::default();
+        let base = serve(received.clone()).await;
+        let config = AlertsConfig {
+            rules: vec![AlertRule::Quorum { min_nodes: 2 }],
+            slack_urls: vec![format!("{}/slack", base)],
+            discord_urls: vec![format!("{}/discord", base)],
+            ..AlertsConfig::default()
+        };
+
+        let service = AggregatorServiceImpl::new();
+        let metrics = service.metrics().clone();
+        let (alerts, task) = spawn(config, metrics.clone(), service.subscribe()).unwrap();
+        let service = service.with_alerts(alerts);
+
+        // 노드 하나로 게시하면 정족수 미달, 두 번째 노드가 들어오면 해제
+        service
+            .submit_price(price_request(70_000.0, "node-a"))
+            .await
+            .unwrap();
+        service.publish_snapshot().await;
+        service.publish_snapshot().await;
+        service
+            .submit_price(price_request(70_010.0, "node-b"))
+            .await
+            .unwrap();
+        service.publish_snapshot().await;
+        service.shutdown();
+        task.await.unwrap();
+
+        let received = received.lock().unwrap();
+        let slack: Vec<&serde_json::Value> = received
+            .iter()
+            .filter(|(kind, _)| kind == "slack")
+            .map(|(_, body)| body)
+            .collect();
+        let discord: Vec<&serde_json::Value> = received
+            .iter()
+            .filter(|(kind, _)| kind == "discord")
+            .map(|(_, body)| body)
+            .collect();
+        assert_eq!(
+            slack,
+            [
+                &serde_json::json!({
+                    "text": "🚨 [FIRING] quorum: BTC/USD has 1 contributing node(s), below the quorum of 2"
+                }),
+                &serde_json::json!({
+                    "text": "✅ [RESOLVED] quorum: BTC/USD is back to 2 contributing node(s) (quorum 2)"
+                }),
+            ]
+        );
+        let discord: Vec<&str> = discord
+            .iter()
+            .map(|body| body["content"].as_str().unwrap())
+            .collect();
+        let slack: Vec<&str> = slack
+            .iter()
+            .map(|body| body["text"].as_str().unwrap())
+            .collect();
+        assert_eq!(discord, slack);
+
+        let text = metrics.encode().unwrap();
+        assert!(text.contains(r#"oracle_aggregator_alerts_total{rule="quorum",state="firing"} 1"#));
+        assert!(
+            text.contains(r#"oracle_aggregator_alerts_total{rule="quorum",state="resolved"} 1"#)
+        );
+        // URL 경로의 토큰은 지표 라벨에 남기지 않음
+        assert!(!text.contains("/slack"));
+    }
+}
@@ -0,0 +1,239 @@
+//! 집계 가격 서명 (ed25519)
+//!
+//! 키 파일은 노드 신원 키와 같은 형식(`{"algorithm":"ed25519","public_key":..,"secret_key":..}`,
+//! hex)이라 `oracle-node keygen --out <path>`로 만들 수 있다. 서명할 내용과 정규 인코딩은
+//! `oracle_vm_common::attestation`에 있어 소비자는 같은 코드로 검증한다. 키를 교체하려면
+//! 파일을 바꾸고 SIGHUP을 보내면 되며, 공개키 지문인 key_id가 바뀌어 어떤 키로 서명했는지 구분된다.
+
+use anyhow::{bail, Context, Result};
+use ed25519_dalek::{SigningKey, VerifyingKey, PUBLIC_KEY_LENGTH, SECRET_KEY_LENGTH};
+use oracle_vm_common::attestation::{key_id, PriceAttestation, SignedPriceAttestation};
+use serde::Deserialize;
+use std::path::Path;
+use tonic::Status;
+
+use crate::oracle::GetSignedPriceResponse;
+use crate::snapshot::AggregateSnapshot;
+
+/// 키 파일의 알고리즘 표기
+pub const KEY_ALGORITHM: &str = "ed25519";
+
+/// 서명된 가격을 줄 수 없는 이유
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttestationUnavailable {
+    /// 서명 키가 설정되지 않음
+    NotConfigured,
+    /// 아직 집계 가격이 없음
+    NoPrice,
+}
+
+impl From<AttestationUnavailable> for Status {
+    fn from(reason: AttestationUnavailable) -> Self {
+        match reason {
+            AttestationUnavailable::NotConfigured => {
+                Status::failed_precondition("price attestation is not configured")
+            }
+            AttestationUnavailable::NoPrice => {
+                Status::unavailable("no aggregated price to attest yet")
+            }
+        }
+    }
+}
+
+// 키 파일 형식 (노드 신원 키 파일과 같음)
+#[derive(Deserialize)]
+struct KeyFile {
+    algorithm: String,
+    public_key: String,
+    secret_key: String,
+}
+
+/// 집계 가격 서명 키
+pub struct AttestationSigner {
+    signing_key: SigningKey,
+    key_id: String,
+}
+
+impl std::fmt::Debug for AttestationSigner {
+    // 비밀키는 출력하지 않음
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AttestationSigner")
+            .field("key_id", &self.key_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl AttestationSigner {
+    /// 비밀키 바이트로 생성
+    pub fn from_secret_bytes(secret: &[u8; SECRET_KEY_LENGTH]) -> Self {
+        let signing_key = SigningKey::from_bytes(secret);
+        let key_id = key_id(&signing_key.verifying_key());
+        Self {
+            signing_key,
+            key_id,
+        }
+    }
+
+    /// 키 파일 읽기 (공개키가 비밀키와 맞지 않으면 에러)
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Cannot read attestation key {}", path.display()))?;
+        let file: KeyFile = serde_json::from_str(&contents)
+            .with_context(|| format!("Malformed attestation key {}", path.display()))?;
+        if file.algorithm != KEY_ALGORITHM {
+            bail!(
+                "Attestation key {} uses {:?}, expected {:?}",
+                path.display(),
+                file.algorithm,
+                KEY_ALGORITHM
+            );
+        }
+        let secret: [u8; SECRET_KEY_LENGTH] = decode_key(&file.secret_key)
+            .with_context(|| format!("Malformed secret_key in {}", path.display()))?;
+        let public: [u8; PUBLIC_KEY_LENGTH] = decode_key(&file.public_key)
+            .with_context(|| format!("Malformed public_key in {}", path.display()))?;
+
+        let signer = Self::from_secret_bytes(&secret);
+        if signer.public_key().as_bytes() != &public {
+            bail!("public_key does not match secret_key in {}", path.display());
+        }
+        Ok(signer)
+    }
+
+    /// 서명 공개키
+    pub fn public_key(&self) -> VerifyingKey {
+        self.signing_key.verifying_key()
+    }
+
+    /// 공개키 지문 (공개키 SHA-256의 앞 8바이트, hex)
+    pub fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    /// 스냅샷의 집계 가격에 서명 (집계 가격이 없으면 None)
+    ///
+    /// 시각은 집계 가격을 계산한 시각이므로 stale로 이어받은 가격도 원래 계산 시각으로 서명한다.
+    pub fn sign(&self, pair: &str, snapshot: &AggregateSnapshot) -> Option<SignedPriceAttestation> {
+        let price = snapshot.aggregated_price?;
+        let attestation = PriceAttestation::new(
+            pair,
+            price,
+            snapshot.aggregated_at,
+            u32::try_from(snapshot.contributing_nodes).unwrap_or(u32::MAX),
+        );
+        SignedPriceAttestation::sign(attestation, &self.signing_key).ok()
+    }
+}
+
+fn decode_key<const N: usize>(value: &str) -> Result<[u8; N]> {
+    let bytes = hex::decode(value)?;
+    let len = bytes.len();
+    bytes
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("expected {} bytes, got {}", N, len))
+}
+
+impl TryFrom<&SignedPriceAttestation> for GetSignedPriceResponse {
+    type Error = hex::FromHexError;
+
+    fn try_from(signed: &SignedPriceAttestation) -> Result<Self, Self::Error> {
+        let attestation = &signed.attestation;
+        Ok(Self {
+            version: u32::from(attestation.version),
+            pair: attestation.pair.clone(),
+            price_scaled: attestation.price_scaled,
+            price_decimals: attestation.price_decimals,
+            timestamp: attestation.timestamp,
+            contributing_nodes: attestation.contributing_nodes,
+            key_id: signed.key_id.clone(),
+            public_key: hex::decode(&signed.public_key)?,
+            canonical: hex::decode(&signed.canonical)?,
+            signature: hex::decode(&signed.signature)?,
+        })
+    }
+}
+
+impl From<&GetSignedPriceResponse> for SignedPriceAttestation {
+    fn from(response: &GetSignedPriceResponse) -> Self {
+        Self {
+            attestation: PriceAttestation {
+                version: u8::try_from(response.version).unwrap_or(u8::MAX),
+                pair: response.pair.clone(),
+                price_scaled: response.price_scaled,
+                price_decimals: response.price_decimals,
+                timestamp: response.timestamp,
+                contributing_nodes: response.contributing_nodes,
+            },
+            key_id: response.key_id.clone(),
+            public_key: hex::encode(&response.public_key),
+            canonical: hex::encode(&response.canonical),
+            signature: hex::encode(&response.signature),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oracle_vm_common::attestation::verify_attestation;
+    use oracle_vm_common::Price;
+
+    fn temp_path() -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("attestation-key-{}.json", uuid::Uuid::new_v4()))
+    }
+
+    fn write_key(path: &Path, secret: &[u8; 32], public: &[u8]) {
+        let json = format!(
+            r#"{{"algorithm":"ed25519","public_key":"{}","secret_key":"{}"}}"#,
+            hex::encode(public),
+            hex::encode(secret)
+        );
+        std::fs::write(path, json).unwrap();
+    }
+
+    #[test]
+    fn test_load_is_deterministic_and_checks_the_public_key() {
+        let secret = [9u8; 32];
+        let expected = AttestationSigner::from_secret_bytes(&secret);
+        let path = temp_path();
+        write_key(&path, &secret, expected.public_key().as_bytes());
+
+        let first = AttestationSigner::load(&path).unwrap();
+        let second = AttestationSigner::load(&path).unwrap();
+        assert_eq!(first.public_key(), expected.public_key());
+        assert_eq!(first.key_id(), second.key_id());
+        assert!(!format!("{:?}", first).contains(&hex::encode(secret)));
+
+        let other = AttestationSigner::from_secret_bytes(&[1u8; 32]);
+        write_key(&path, &secret, other.public_key().as_bytes());
+        let err = AttestationSigner::load(&path).unwrap_err();
+        assert!(err.to_string().contains("does not match"), "{}", err);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_signed_snapshot_survives_the_proto_round_trip() {
+        let signer = AttestationSigner::from_secret_bytes(&[9u8; 32]);
+        assert!(signer
+            .sign("BTC/USD", &AggregateSnapshot::default())
+            .is_none());
+
+        let snapshot = AggregateSnapshot {
+            aggregated_price: Some(Price::from_cents(7_000_012)),
+            aggregated_at: 1_700_000_000,
+            contributing_nodes: 3,
+            timestamp: 1_700_000_005,
+            ..AggregateSnapshot::default()
+        };
+        let signed = signer.sign("BTC/USD", &snapshot).unwrap();
+        assert_eq!(signed.attestation.timestamp, 1_700_000_000);
+        assert_eq!(signed.key_id, signer.key_id());
+
+        let response = GetSignedPriceResponse::try_from(&signed).unwrap();
+        assert_eq!(response.signature.len(), 64);
+        let decoded = SignedPriceAttestation::from(&response);
+        assert_eq!(decoded, signed);
+        let trusted = [signer.public_key().to_bytes()];
+        assert!(verify_attestation(&decoded, &trusted).is_ok());
+    }
+}
@@ -0,0 +1,62 @@
+//! 집계 게시 주기 (벽시계 기준 정렬)
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 현재 시각을 제공하는 시계 (테스트에서 교체 가능)
+pub trait Clock: Send + Sync + 'static {
+    /// Unix 시각 (밀리초)
+    fn now_millis(&self) -> u64;
+
+    /// Unix 시각 (초)
+    fn now_secs(&self) -> u64 {
+        self.now_millis() / 1_000
+    }
+}
+
+/// 시스템 시계
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_millis(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_millis() as u64)
+    }
+}
+
+/// `now_millis` 이후 가장 가까운 `interval_secs` 경계 (밀리초, 경계 위라면 다음 경계)
+///
+/// 경계는 Unix epoch 기준으로 정렬되므로 10초 주기는 항상 :00, :10, :20 ... 에 맞춰진다.
+pub fn next_boundary_millis(now_millis: u64, interval_secs: u64) -> u64 {
+    let interval = interval_secs.max(1) * 1_000;
+    (now_millis / interval + 1) * interval
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_boundary_is_aligned_to_the_wall_clock() {
+        assert_eq!(
+            next_boundary_millis(1_700_000_003_250, 10),
+            1_700_000_010_000
+        );
+        assert_eq!(
+            next_boundary_millis(1_700_000_009_999, 10),
+            1_700_000_010_000
+        );
+        // 경계 위에서는 다음 경계
+        assert_eq!(
+            next_boundary_millis(1_700_000_010_000, 10),
+            1_700_000_020_000
+        );
+        assert_eq!(
+            next_boundary_millis(1_700_000_010_000, 60),
+            1_700_000_040_000
+        );
+        // 0초 주기는 1초로 취급
+        assert_eq!(next_boundary_millis(1_500, 0), 2_000);
+    }
+}
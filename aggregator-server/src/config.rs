@@ -0,0 +1,438 @@
+use oracle_vm_common::Price;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+
+use crate::aggregation::AggregationMode;
+use crate::reputation::DEFAULT_REPUTATION_HALF_LIFE_SECS;
+use crate::sla::DEFAULT_SLA_WINDOW_SECS;
+use crate::source_weights::SourceWeights;
+use crate::strategy::{AggregationStrategy, Median, MinPoints, OneVotePerNodeMedian};
+use crate::{MAX_PRICE_ENTRIES, PRICE_WINDOW_SECS};
+
+/// 기본 최대 활성 노드 수
+pub const DEFAULT_MAX_ACTIVE_NODES: usize = 10_000;
+/// 노드별 할당량 계산에 사용하는 기본 예상 노드 수
+pub const DEFAULT_EXPECTED_NODES: usize = 5;
+/// 일괄 전송 한 번에 받는 기본 최대 가격 데이터 수
+pub const DEFAULT_MAX_BATCH_SIZE: usize = 1_000;
+/// 가격 데이터 조회 한 번에 돌려주는 기본 최대 개수
+pub const DEFAULT_MAX_HISTORY_LIMIT: usize = 10_000;
+
+/// 응답의 f64 가격 필드가 쓰는 단위 (고정소수점 필드는 자체 자릿수를 가지므로 영향 없음)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PriceUnit {
+    /// 호가 통화 한 단위 (예: 70000.5, 기본값)
+    #[default]
+    Dollars,
+    /// 호가 통화의 1/100 단위 (예: 7000050)
+    Cents,
+}
+
+impl PriceUnit {
+    /// 설정 이름으로 선택 (dollars, cents)
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "dollars" => Some(Self::Dollars),
+            "cents" => Some(Self::Cents),
+            _ => None,
+        }
+    }
+
+    /// 응답의 `unit` 필드에 쓰는 이름
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Dollars => "dollars",
+            Self::Cents => "cents",
+        }
+    }
+
+    /// 가격을 이 단위의 f64로 (센트 아래 자릿수가 있으면 소수로 남음)
+    pub fn price(self, price: Price) -> f64 {
+        match self {
+            Self::Dollars => price.to_f64_dollars(),
+            Self::Cents => match price.rescale(Price::USD_DECIMALS) {
+                Some(cents) => cents.mantissa() as f64,
+                None => self.convert(price.to_f64_dollars()),
+            },
+        }
+    }
+
+    /// 달러 단위 값을 이 단위로
+    pub fn convert(self, dollars: f64) -> f64 {
+        match self {
+            Self::Dollars => dollars,
+            Self::Cents => dollars * 100.0,
+        }
+    }
+}
+
+/// Aggregator 설정
+#[derive(Debug, Clone)]
+pub struct AggregatorConfig {
+    /// 추적하는 활성 노드의 최대 수 (초과 시 가장 오래된 노드부터 제거)
+    pub max_active_nodes: usize,
+    /// 메모리에 유지하는 최대 가격 데이터 수
+    pub max_price_entries: usize,
+    /// 가격 데이터 최대 보관 시간 (초)
+    ///
+    /// 이보다 오래된 데이터는 저장할 때와 집계 태스크에서 제거되므로 최근 가격 조회에도 나타나지 않는다.
+    pub max_price_age_secs: u64,
+    /// 예상 참여 노드 수 (노드별 할당량 기본값 계산용)
+    pub expected_nodes: usize,
+    /// 노드별 최대 보관 데이터 수 (None이면 max_price_entries / expected_nodes)
+    pub per_node_quota: Option<usize>,
+    /// 중간값 계산 시 노드마다 윈도우 내 최신 가격 하나만 반영 (strategy가 없을 때만 사용)
+    pub one_vote_per_node: bool,
+    /// 집계 방식 (None이면 one_vote_per_node에 따라 Median 또는 OneVotePerNodeMedian)
+    pub strategy: Option<Arc<dyn AggregationStrategy>>,
+    /// strategy가 없을 때 기본 중간값을 계산하는 공간 (Geometric이면 로그 가격에서 보간)
+    pub aggregation_mode: AggregationMode,
+    /// 윈도우 내 가격이 이보다 적으면 집계 방식과 관계없이 `aggregation_mode` 공간의 중간값으로 집계
+    /// (None이면 항상 집계 방식 사용)
+    pub min_points: Option<usize>,
+    /// 노드의 마지막 제출 후 이 시간(초)이 지나면 중간값에서 제외 (None이면 가격 유효 기간만 적용)
+    ///
+    /// 노드는 NODE_TIMEOUT_SECS 동안 활성으로 남아 있지만, 그보다 짧은 이 시간이 지나면 집계에는 참여하지 않는다.
+    pub max_contribution_age_secs: Option<u64>,
+    /// 노드의 가장 최근 제출에서 이 시간(초) 이내의 이전 제출은 집계 전에 그 최근 제출 하나로 합침
+    /// (None이면 합치지 않음)
+    pub coalesce_window_secs: Option<u64>,
+    /// 집계 게시 주기 (초, 벽시계 경계에 정렬)
+    ///
+    /// 설정하면 집계 태스크는 경계마다 한 번만 집계하고, submit_price 응답도 마지막 게시 결과를 사용한다.
+    /// None이면 새 가격이 저장될 때마다 집계한다.
+    pub publish_interval_secs: Option<u64>,
+    /// 관리자 RPC 인증용 비밀값 (None이면 관리자 RPC 비활성)
+    pub admin_secret: Option<String>,
+    /// 거래소별 초기 가중치 (가중 중간값에서 사용, 목록에 없는 거래소는 1.0, update_config로 변경 가능)
+    pub source_weights: HashMap<String, f64>,
+    /// 노드 평판이 기본값으로 회복되는 반감기 (초, None이면 회복하지 않음)
+    pub reputation_half_life_secs: Option<u64>,
+    /// SubmitPriceBatch 한 번에 받는 최대 가격 데이터 수 (넘으면 invalid_argument)
+    pub max_batch_size: usize,
+    /// GetPriceHistory/GetNodeHistory에 요청할 수 있는 최대 개수 (넘으면 invalid_argument)
+    ///
+    /// 노드별 제출 기록도 노드마다 이 개수만큼 보관한다.
+    pub max_history_limit: usize,
+    /// 실시간 제출 타임스탬프가 서버 시각과 이 시간(초)보다 많이 차이 나면 경고 후 서버 시각으로 대체
+    /// (None이면 그대로 사용)
+    pub skew_clamp_secs: Option<u64>,
+    /// 실시간 제출 타임스탬프가 서버 시각과 이 시간(초)보다 많이 차이 나면 거부 (None이면 거부하지 않음)
+    pub skew_reject_secs: Option<u64>,
+    /// 최근 가용성(정족수를 채운 신선한 집계가 있었던 시간의 비율)을 계산하는 윈도우 (초)
+    pub sla_window_secs: u64,
+    /// 최근 가용성 목표 (0~1, 이보다 낮아지면 경고, None이면 경고하지 않음)
+    pub sla_target: Option<f64>,
+    /// 윈도우의 가격이 모두 만료되어도 마지막 유효 집계 가격을 stale로 표시해 이 시간(초) 동안 게시
+    /// (None이면 바로 집계 가격 없음)
+    pub stale_grace_secs: Option<u64>,
+    /// GetAggregatedPrice/StreamPrices 응답의 f64 가격 단위
+    pub price_unit: PriceUnit,
+    /// 제출을 받는 노드 ID (비어 있으면 모든 노드, 목록에 없는 노드의 제출은 node_not_allowed로 거부)
+    pub allowed_nodes: BTreeSet<String>,
+}
+
+impl AggregatorConfig {
+    /// 활성 노드 최대 수 지정
+    pub fn with_max_active_nodes(mut self, max_active_nodes: usize) -> Self {
+        self.max_active_nodes = max_active_nodes;
+        self
+    }
+
+    /// 메모리에 유지하는 최대 가격 데이터 수 지정
+    pub fn with_max_price_entries(mut self, max_price_entries: usize) -> Self {
+        self.max_price_entries = max_price_entries;
+        self
+    }
+
+    /// 가격 데이터 최대 보관 시간(초) 지정
+    pub fn with_max_price_age_secs(mut self, max_price_age_secs: u64) -> Self {
+        self.max_price_age_secs = max_price_age_secs;
+        self
+    }
+
+    /// 예상 참여 노드 수 지정
+    pub fn with_expected_nodes(mut self, expected_nodes: usize) -> Self {
+        self.expected_nodes = expected_nodes;
+        self
+    }
+
+    /// 노드별 최대 보관 데이터 수 지정
+    pub fn with_per_node_quota(mut self, per_node_quota: usize) -> Self {
+        self.per_node_quota = Some(per_node_quota);
+        self
+    }
+
+    /// 노드마다 윈도우 내 최신 가격 하나만 반영
+    pub fn with_one_vote_per_node(mut self, enabled: bool) -> Self {
+        self.one_vote_per_node = enabled;
+        self
+    }
+
+    /// 집계 방식 지정
+    pub fn with_strategy(mut self, strategy: Arc<dyn AggregationStrategy>) -> Self {
+        self.strategy = Some(strategy);
+        self
+    }
+
+    /// 기본 중간값을 계산하는 공간 지정
+    pub fn with_aggregation_mode(mut self, aggregation_mode: AggregationMode) -> Self {
+        self.aggregation_mode = aggregation_mode;
+        self
+    }
+
+    /// 집계 방식을 적용하는 최소 가격 수 지정 (미만이면 중간값)
+    pub fn with_min_points(mut self, min_points: usize) -> Self {
+        self.min_points = Some(min_points);
+        self
+    }
+
+    /// 노드의 마지막 제출 후 집계에서 제외하기까지의 시간(초) 지정
+    pub fn with_max_contribution_age_secs(mut self, max_contribution_age_secs: u64) -> Self {
+        self.max_contribution_age_secs = Some(max_contribution_age_secs);
+        self
+    }
+
+    /// 노드별 제출을 합치는 시간(초) 지정
+    pub fn with_coalesce_window_secs(mut self, coalesce_window_secs: u64) -> Self {
+        self.coalesce_window_secs = Some(coalesce_window_secs);
+        self
+    }
+
+    /// 집계 게시 주기(초) 지정
+    pub fn with_publish_interval_secs(mut self, publish_interval_secs: u64) -> Self {
+        self.publish_interval_secs = Some(publish_interval_secs);
+        self
+    }
+
+    /// 관리자 RPC 비밀값 지정 (관리자 RPC 활성화)
+    pub fn with_admin_secret(mut self, admin_secret: impl Into<String>) -> Self {
+        self.admin_secret = Some(admin_secret.into());
+        self
+    }
+
+    /// 거래소 하나의 초기 가중치 지정
+    pub fn with_source_weight(mut self, source: impl Into<String>, weight: f64) -> Self {
+        self.source_weights.insert(source.into(), weight);
+        self
+    }
+
+    /// 거래소별 초기 가중치 지정 (기존 값을 대체)
+    pub fn with_source_weights(mut self, source_weights: HashMap<String, f64>) -> Self {
+        self.source_weights = source_weights;
+        self
+    }
+
+    /// 평판 회복 반감기(초) 지정 (None이면 회복하지 않음)
+    pub fn with_reputation_half_life_secs(mut self, half_life_secs: Option<u64>) -> Self {
+        self.reputation_half_life_secs = half_life_secs;
+        self
+    }
+
+    /// SubmitPriceBatch 한 번에 받는 최대 가격 데이터 수 지정
+    pub fn with_max_batch_size(mut self, max_batch_size: usize) -> Self {
+        self.max_batch_size = max_batch_size;
+        self
+    }
+
+    /// GetPriceHistory 최대 개수 지정
+    pub fn with_max_history_limit(mut self, max_history_limit: usize) -> Self {
+        self.max_history_limit = max_history_limit;
+        self
+    }
+
+    /// 서버 시각으로 대체하기 시작하는 시계 오차(초) 지정
+    pub fn with_skew_clamp_secs(mut self, skew_clamp_secs: u64) -> Self {
+        self.skew_clamp_secs = Some(skew_clamp_secs);
+        self
+    }
+
+    /// 거부하기 시작하는 시계 오차(초) 지정
+    pub fn with_skew_reject_secs(mut self, skew_reject_secs: u64) -> Self {
+        self.skew_reject_secs = Some(skew_reject_secs);
+        self
+    }
+
+    /// 최근 가용성 윈도우(초) 지정
+    pub fn with_sla_window_secs(mut self, sla_window_secs: u64) -> Self {
+        self.sla_window_secs = sla_window_secs;
+        self
+    }
+
+    /// 최근 가용성 목표(0~1) 지정
+    pub fn with_sla_target(mut self, sla_target: f64) -> Self {
+        self.sla_target = Some(sla_target);
+        self
+    }
+
+    /// 마지막 유효 집계 가격을 stale로 게시하는 시간(초) 지정
+    pub fn with_stale_grace_secs(mut self, stale_grace_secs: u64) -> Self {
+        self.stale_grace_secs = Some(stale_grace_secs);
+        self
+    }
+
+    /// 응답의 f64 가격 단위 지정
+    pub fn with_price_unit(mut self, price_unit: PriceUnit) -> Self {
+        self.price_unit = price_unit;
+        self
+    }
+
+    /// 제출을 받는 노드 ID 지정 (비어 있으면 모든 노드)
+    pub fn with_allowed_nodes<I, S>(mut self, allowed_nodes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.allowed_nodes = allowed_nodes.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// 실제로 적용되는 집계 방식 (min_points가 있으면 가격이 부족할 때 중간값으로 대체)
+    pub fn effective_strategy(&self) -> Arc<dyn AggregationStrategy> {
+        let strategy = self.configured_strategy();
+        match self.min_points {
+            Some(min_points) => {
+                Arc::new(MinPoints::new(strategy, min_points, self.aggregation_mode))
+            }
+            None => strategy,
+        }
+    }
+
+    // strategy 또는 one_vote_per_node에 따른 집계 방식
+    fn configured_strategy(&self) -> Arc<dyn AggregationStrategy> {
+        match &self.strategy {
+            Some(strategy) => strategy.clone(),
+            None if self.one_vote_per_node => Arc::new(OneVotePerNodeMedian {
+                mode: self.aggregation_mode,
+                ..OneVotePerNodeMedian::default()
+            }),
+            None => Arc::new(Median {
+                mode: self.aggregation_mode,
+                ..Median::default()
+            }),
+        }
+    }
+
+    /// 실제로 적용되는 노드별 할당량 (최소 1)
+    pub fn effective_per_node_quota(&self) -> usize {
+        self.per_node_quota
+            .unwrap_or(self.max_price_entries / self.expected_nodes.max(1))
+            .max(1)
+    }
+}
+
+/// 실행 중에 바뀔 수 있는 설정 (update_config로 변경)
+///
+/// 상태에는 `Arc`로 보관하고 변경할 때는 새 값으로 통째로 바꾼다. 핸들러는 시작할 때 한 번의 읽기
+/// 락으로 스냅샷을 얻어 끝까지 그 값만 쓰므로, 한 번의 처리나 집계가 이전 값과 새 값을 섞어 읽지 않는다.
+#[derive(Debug, Clone, Default)]
+pub struct RuntimeConfig {
+    /// 거래소별 가중치 (가중 중간값)
+    pub source_weights: SourceWeights,
+    /// 마지막 제출 후 집계 참여 최대 시간 (초)
+    pub max_contribution_age_secs: Option<u64>,
+    /// 노드별 제출을 하나로 합치는 시간 (초)
+    pub coalesce_window_secs: Option<u64>,
+    /// 이보다 큰 시계 오차는 서버 시각으로 대체 (초)
+    pub skew_clamp_secs: Option<u64>,
+    /// 이보다 큰 시계 오차는 거부 (초)
+    pub skew_reject_secs: Option<u64>,
+}
+
+impl RuntimeConfig {
+    /// 시작 설정의 초기값
+    pub fn new(config: &AggregatorConfig) -> Self {
+        Self {
+            source_weights: SourceWeights::new(config.source_weights.clone()),
+            max_contribution_age_secs: config.max_contribution_age_secs,
+            coalesce_window_secs: config.coalesce_window_secs,
+            skew_clamp_secs: config.skew_clamp_secs,
+            skew_reject_secs: config.skew_reject_secs,
+        }
+    }
+}
+
+impl Default for AggregatorConfig {
+    fn default() -> Self {
+        Self {
+            max_active_nodes: DEFAULT_MAX_ACTIVE_NODES,
+            max_price_entries: MAX_PRICE_ENTRIES,
+            max_price_age_secs: PRICE_WINDOW_SECS,
+            expected_nodes: DEFAULT_EXPECTED_NODES,
+            per_node_quota: None,
+            one_vote_per_node: false,
+            strategy: None,
+            aggregation_mode: AggregationMode::Arithmetic,
+            min_points: None,
+            max_contribution_age_secs: None,
+            coalesce_window_secs: None,
+            publish_interval_secs: None,
+            admin_secret: None,
+            source_weights: HashMap::new(),
+            reputation_half_life_secs: Some(DEFAULT_REPUTATION_HALF_LIFE_SECS),
+            max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+            max_history_limit: DEFAULT_MAX_HISTORY_LIMIT,
+            skew_clamp_secs: None,
+            skew_reject_secs: None,
+            sla_window_secs: DEFAULT_SLA_WINDOW_SECS,
+            sla_target: None,
+            stale_grace_secs: None,
+            price_unit: PriceUnit::Dollars,
+            allowed_nodes: BTreeSet::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::WeightedMedian;
+
+    #[test]
+    fn test_builder_overrides_one_field_and_keeps_defaults() {
+        let config = AggregatorConfig::default().with_publish_interval_secs(10);
+        let defaults = AggregatorConfig::default();
+
+        assert_eq!(config.publish_interval_secs, Some(10));
+        assert_eq!(config.max_active_nodes, defaults.max_active_nodes);
+        assert_eq!(config.max_price_entries, defaults.max_price_entries);
+        assert_eq!(config.max_price_age_secs, defaults.max_price_age_secs);
+        assert_eq!(
+            config.effective_per_node_quota(),
+            defaults.effective_per_node_quota()
+        );
+        assert_eq!(
+            config.reputation_half_life_secs,
+            defaults.reputation_half_life_secs
+        );
+        assert_eq!(config.max_batch_size, DEFAULT_MAX_BATCH_SIZE);
+        assert_eq!(config.max_history_limit, DEFAULT_MAX_HISTORY_LIMIT);
+        assert!(config.admin_secret.is_none());
+        assert!(config.source_weights.is_empty());
+        assert_eq!(config.skew_clamp_secs, None);
+        assert_eq!(config.skew_reject_secs, None);
+        assert_eq!(config.stale_grace_secs, None);
+        assert_eq!(config.price_unit, PriceUnit::Dollars);
+        assert_eq!(config.effective_strategy().name(), "median");
+    }
+
+    #[test]
+    fn test_builder_chains() {
+        let config = AggregatorConfig::default()
+            .with_expected_nodes(2)
+            .with_max_price_entries(100)
+            .with_admin_secret("s3cret")
+            .with_source_weight("coinbase", 2.0)
+            .with_strategy(Arc::new(WeightedMedian::default()))
+            .with_reputation_half_life_secs(None);
+
+        assert_eq!(config.effective_per_node_quota(), 50);
+        assert_eq!(config.admin_secret.as_deref(), Some("s3cret"));
+        assert_eq!(config.source_weights.get("coinbase"), Some(&2.0));
+        assert_eq!(config.effective_strategy().name(), "weighted median");
+        assert_eq!(config.reputation_half_life_secs, None);
+
+        // 설정으로 서비스 생성
+        let _service = crate::AggregatorServiceImpl::with_config(config);
+    }
+}
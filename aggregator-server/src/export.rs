@@ -0,0 +1,521 @@
+//! 가격 기록 CSV 내보내기
+//!
+//! REST `/v1/history.csv`는 보관 중인 가격 데이터를, `aggregator-server export`는 WAL에 남은 제출을
+//! 같은 열(`timestamp,node_id,source,price,included_in_aggregate`)의 CSV로 내보낸다. 시각은
+//! RFC3339(UTC), 가격은 고정소수점 값을 그대로 쓰고, 필드는 RFC 4180에 따라 쉼표·따옴표·줄바꿈이
+//! 있으면 따옴표로 감싼다. 두 경로 모두 행을 조금씩 만들어 쓰므로 전체 결과를 메모리에 올리지 않는다.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, SecondsFormat};
+use clap::Args;
+use oracle_vm_common::{AssetPair, Price};
+use std::collections::BinaryHeap;
+use std::convert::Infallible;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::info;
+
+use crate::oracle::PriceRequest;
+use crate::wal::{self, WalDecision, WalRecord};
+use crate::{request_pair, request_price, AggregatorServiceImpl, DEFAULT_PAIR};
+
+/// CSV 머리글
+pub const CSV_HEADER: &str = "timestamp,node_id,source,price,included_in_aggregate";
+/// `/v1/history.csv`가 한 번에 읽는 행 수 (상태 읽기 잠금을 잡는 단위)
+pub const HISTORY_CHUNK_ROWS: usize = 1_000;
+// 아직 보내지 못한 조각 수 (느린 클라이언트가 메모리를 쌓지 않도록)
+const STREAM_BUFFER_CHUNKS: usize = 2;
+
+/// CSV 한 행
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvRow<'a> {
+    pub timestamp: u64,
+    pub node_id: &'a str,
+    pub source: &'a str,
+    pub price: Price,
+    pub included_in_aggregate: bool,
+}
+
+impl CsvRow<'_> {
+    /// 줄바꿈(CRLF)까지 `out`에 추가
+    pub fn write_to(&self, out: &mut String) {
+        out.push_str(&rfc3339(self.timestamp));
+        out.push(',');
+        push_field(out, self.node_id);
+        out.push(',');
+        push_field(out, self.source);
+        out.push(',');
+        out.push_str(&self.price.to_string());
+        out.push(',');
+        out.push_str(if self.included_in_aggregate {
+            "true"
+        } else {
+            "false"
+        });
+        out.push_str("\r\n");
+    }
+}
+
+// 쉼표, 따옴표, 줄바꿈이 있으면 따옴표로 감싸고 안의 따옴표는 두 번 씀
+fn push_field(out: &mut String, value: &str) {
+    if !value.contains([',', '"', '\r', '\n']) {
+        out.push_str(value);
+        return;
+    }
+    out.push('"');
+    out.push_str(&value.replace('"', "\"\""));
+    out.push('"');
+}
+
+/// Unix 초를 RFC3339(UTC, 초 단위)로
+pub fn rfc3339(timestamp: u64) -> String {
+    i64::try_from(timestamp)
+        .ok()
+        .and_then(|secs| DateTime::from_timestamp(secs, 0))
+        .map_or_else(
+            || timestamp.to_string(),
+            |time| time.to_rfc3339_opts(SecondsFormat::Secs, true),
+        )
+}
+
+/// 시각 해석: Unix 초 또는 RFC3339 (`2024-01-01T00:00:00Z`)
+pub fn parse_time(value: &str) -> Result<u64, String> {
+    if let Ok(secs) = value.parse::<u64>() {
+        return Ok(secs);
+    }
+    let time = DateTime::parse_from_rfc3339(value)
+        .map_err(|_| format!("expected Unix seconds or RFC3339, got {:?}", value))?;
+    u64::try_from(time.timestamp()).map_err(|_| format!("{:?} is before 1970", value))
+}
+
+/// 보관 중인 `pair` 가격 데이터 중 `from..=to` 시각의 행을 시각 순으로 보내는 CSV 본문
+///
+/// 읽기 잠금은 `chunk_rows`개씩 읽을 때만 잡고, 다음 조각은 마지막으로 보낸 행 다음부터 찾으므로
+/// 그 사이에 오래된 데이터가 정리되거나 새 데이터가 들어와도 같은 행을 두 번 보내지 않는다.
+/// `included_in_aggregate`는 요청 시점에 게시된 집계에 들어간 데이터인지를 나타낸다.
+pub fn history_csv(
+    service: AggregatorServiceImpl,
+    pair: AssetPair,
+    from: u64,
+    to: u64,
+    chunk_rows: usize,
+) -> ReceiverStream<Result<String, Infallible>> {
+    let (tx, rx) = mpsc::channel(STREAM_BUFFER_CHUNKS);
+    let chunk_rows = chunk_rows.max(1);
+    let snapshot = service.snapshot();
+    // 게시된 집계 가격이 현재 윈도우에서 계산된 경우에만 포함 여부를 판단
+    let aggregated_at = snapshot
+        .aggregated_price
+        .filter(|_| !snapshot.stale)
+        .map(|_| snapshot.aggregated_at);
+
+    tokio::spawn(async move {
+        if tx.send(Ok(format!("{}\r\n", CSV_HEADER))).await.is_err() {
+            return;
+        }
+
+        let mut cursor: Option<(u64, Arc<str>, Arc<str>)> = None;
+        loop {
+            let mut chunk = String::new();
+            let rows = {
+                let state = service.state.read().await;
+                let entries: Vec<_> = state.prices.entries().collect();
+                let after = cursor
+                    .as_ref()
+                    .map(|(timestamp, node_id, source)| (*timestamp, &**node_id, &**source));
+
+                // 커서 다음의 가장 앞선 `chunk_rows`개만 힙에 유지
+                let mut heap = BinaryHeap::with_capacity(chunk_rows + 1);
+                for (i, entry) in entries.iter().enumerate() {
+                    let key = (entry.timestamp, &*entry.node_id, &*entry.source);
+                    if &*entry.pair != pair.as_str()
+                        || !(from..=to).contains(&entry.timestamp)
+                        || after.is_some_and(|after| key <= after)
+                    {
+                        continue;
+                    }
+                    heap.push((key, i));
+                    if heap.len() > chunk_rows {
+                        heap.pop();
+                    }
+                }
+
+                let rows = heap.into_sorted_vec();
+                for &(_, i) in &rows {
+                    let entry = entries[i];
+                    CsvRow {
+                        timestamp: entry.timestamp,
+                        node_id: &entry.node_id,
+                        source: &entry.source,
+                        price: entry.price,
+                        included_in_aggregate: aggregated_at
+                            .is_some_and(|now| state.contributes(entry, now, &state.runtime)),
+                    }
+                    .write_to(&mut chunk);
+                }
+                if let Some(&(_, i)) = rows.last() {
+                    let last = entries[i];
+                    cursor = Some((last.timestamp, last.node_id.clone(), last.source.clone()));
+                }
+                rows.len()
+            };
+
+            if rows == 0 || tx.send(Ok(chunk)).await.is_err() || rows < chunk_rows {
+                return;
+            }
+        }
+    });
+    ReceiverStream::new(rx)
+}
+
+/// `aggregator-server export` 인수
+#[derive(Debug, Clone, PartialEq, Eq, Args)]
+pub struct ExportArgs {
+    /// 이 시각부터 (Unix 초 또는 RFC3339, 생략하면 처음부터)
+    #[arg(long, value_parser = parse_time)]
+    pub from: Option<u64>,
+
+    /// 이 시각까지 (Unix 초 또는 RFC3339, 생략하면 끝까지)
+    #[arg(long, value_parser = parse_time)]
+    pub to: Option<u64>,
+
+    /// 내보낼 pair (생략하면 기본 pair)
+    #[arg(long)]
+    pub pair: Option<String>,
+
+    /// 출력 CSV 파일
+    #[arg(long)]
+    pub out: PathBuf,
+}
+
+/// WAL의 제출을 CSV로 내보내고 쓴 행 수 반환 (`wal_dir`은 서버 설정과 같음)
+///
+/// 행은 WAL에 기록된 순서이며, 가격을 해석할 수 있는 저장·과거 관측·거부 제출을 내보낸다
+/// (중복 제출은 제외). 시각은 노드가 보낸 관측 시각이고, `included_in_aggregate`는 기본 pair로
+/// 저장되어 집계에 쓰인 제출인지를 나타낸다.
+pub fn export_wal(wal_dir: &Path, args: &ExportArgs) -> Result<usize> {
+    let pair = match args.pair.as_deref() {
+        Some(symbol) => {
+            AssetPair::from_symbol(symbol).with_context(|| format!("Unknown pair {:?}", symbol))?
+        }
+        None => AssetPair(DEFAULT_PAIR.to_string()),
+    };
+    let from = args.from.unwrap_or(0);
+    let to = args.to.unwrap_or(u64::MAX);
+    if from > to {
+        bail!("--from must not be after --to");
+    }
+
+    let file =
+        File::create(&args.out).with_context(|| format!("Cannot create {}", args.out.display()))?;
+    let mut out = BufWriter::new(file);
+    write!(out, "{}\r\n", CSV_HEADER)?;
+
+    let mut rows = 0;
+    let mut line = String::new();
+    wal::for_each_record(wal_dir, |record| {
+        let Some(row) = wal_row(&record, pair.as_str(), from, to) else {
+            return Ok(());
+        };
+        line.clear();
+        row.write_to(&mut line);
+        rows += 1;
+        out.write_all(line.as_bytes())
+    })
+    .with_context(|| format!("Failed to export WAL in {}", wal_dir.display()))?;
+    out.flush()?;
+
+    info!(
+        "📤 Exported {} row(s) for {} to {}",
+        rows,
+        pair.as_str(),
+        args.out.display()
+    );
+    Ok(rows)
+}
+
+// 내보낼 레코드의 행 (범위 밖이거나 가격을 해석할 수 없으면 None)
+fn wal_row<'a>(record: &'a WalRecord, pair: &str, from: u64, to: u64) -> Option<CsvRow<'a>> {
+    let included = match record.decision {
+        WalDecision::Accepted => true,
+        WalDecision::Historical | WalDecision::Rejected { .. } => false,
+        WalDecision::Duplicate => return None,
+    };
+    if !(from..=to).contains(&record.request.timestamp) {
+        return None;
+    }
+    let request = PriceRequest::from(record.request.clone());
+    if request_pair(&request) != pair {
+        return None;
+    }
+    Some(CsvRow {
+        timestamp: request.timestamp,
+        node_id: &record.request.node_id,
+        source: &record.request.source,
+        price: request_price(&request)?,
+        included_in_aggregate: included && pair == DEFAULT_PAIR,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AggregatorConfig;
+    use crate::oracle::oracle_service_server::OracleService;
+    use crate::testing::ManualClock;
+    use crate::wal::{WalConfig, WalRequest};
+    use tokio_stream::StreamExt;
+    use tonic::Request;
+
+    const NOW: u64 = 1_700_000_100;
+
+    // CSV 본문을 읽어 머리글과 행 반환
+    fn parse(csv_text: &str) -> (Vec<String>, Vec<Vec<String>>) {
+        let mut reader = csv::Reader::from_reader(csv_text.as_bytes());
+        let header = reader
+            .headers()
+            .unwrap()
+            .iter()
+            .map(str::to_string)
+            .collect();
+        let rows = reader
+            .records()
+            .map(|record| record.unwrap().iter().map(str::to_string).collect())
+            .collect();
+        (header, rows)
+    }
+
+    fn request(
+        price: f64,
+        timestamp: u64,
+        source: &str,
+        node_id: &str,
+        symbol: &str,
+    ) -> PriceRequest {
+        PriceRequest {
+            price,
+            timestamp,
+            source: source.to_string(),
+            node_id: node_id.to_string(),
+            symbol: Some(symbol.to_string()),
+            ..PriceRequest::default()
+        }
+    }
+
+    #[test]
+    fn test_fields_are_escaped_and_parse_back() {
+        let mut text = format!("{}\r\n", CSV_HEADER);
+        CsvRow {
+            timestamp: 1_700_000_000,
+            node_id: "node,\"a\"",
+            source: "binance\nspot",
+            price: Price::from_cents(7_000_012),
+            included_in_aggregate: true,
+        }
+        .write_to(&mut text);
+        assert!(text.contains("\"node,\"\"a\"\"\""));
+
+        let (header, rows) = parse(&text);
+        assert_eq!(header.join(","), CSV_HEADER);
+        assert_eq!(
+            rows,
+            vec![vec![
+                "2023-11-14T22:13:20Z".to_string(),
+                "node,\"a\"".to_string(),
+                "binance\nspot".to_string(),
+                "70000.12".to_string(),
+                "true".to_string(),
+            ]]
+        );
+    }
+
+    #[test]
+    fn test_parse_time_accepts_unix_seconds_and_rfc3339() {
+        assert_eq!(parse_time("1700000000"), Ok(1_700_000_000));
+        assert_eq!(parse_time("2023-11-14T22:13:20Z"), Ok(1_700_000_000));
+        assert_eq!(parse_time("2023-11-15T07:13:20+09:00"), Ok(1_700_000_000));
+        assert!(parse_time("yesterday").is_err());
+        assert!(parse_time("1969-12-31T23:59:59Z").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_history_csv_streams_every_row_in_time_order() {
+        // 가격 윈도우 밖의 데이터도 보관
+        let service = AggregatorServiceImpl::with_config(AggregatorConfig {
+            max_price_age_secs: 3_600,
+            ..AggregatorConfig::default()
+        })
+        .with_clock(Arc::new(ManualClock::starting_at(NOW * 1_000)));
+        for request in [
+            request(70_010.0, NOW - 5, "kraken", "node,\"b\"", "BTC-USD"),
+            request(70_000.12, NOW - 10, "binance", "node-a", "BTC-USD"),
+            // 가격 윈도우 밖이라 집계에 들어가지 않음
+            request(69_000.0, NOW - 70, "okx", "node-d", "BTC-USD"),
+            request(70_020.0, NOW - 1, "binance", "node-c", "BTC-USD"),
+            request(3_500.0, NOW - 8, "binance", "node-a", "ETH-USD"),
+        ] {
+            service.submit_price(Request::new(request)).await.unwrap();
+        }
+        service.publish_snapshot().await;
+
+        // 한 번에 두 행씩 읽어도 빠짐없이 시각 순
+        let chunks: Vec<String> = history_csv(
+            service.clone(),
+            AssetPair(DEFAULT_PAIR.to_string()),
+            0,
+            u64::MAX,
+            2,
+        )
+        .map(Result::unwrap)
+        .collect()
+        .await;
+        assert_eq!(chunks.len(), 3);
+        let (header, rows) = parse(&chunks.concat());
+        assert_eq!(header.join(","), CSV_HEADER);
+        let summary: Vec<(&str, &str, &str, &str)> = rows
+            .iter()
+            .map(|row| (&*row[1], &*row[2], &*row[3], &*row[4]))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("node-d", "okx", "69000.00000000", "false"),
+                ("node-a", "binance", "70000.12000000", "true"),
+                ("node,\"b\"", "kraken", "70010.00000000", "true"),
+                ("node-c", "binance", "70020.00000000", "true"),
+            ]
+        );
+        assert_eq!(rows[0][0], rfc3339(NOW - 70));
+        assert_eq!(rows[3][0], "2023-11-14T22:14:59Z");
+
+        // 범위는 양 끝 포함
+        let chunks: Vec<String> = history_csv(
+            service,
+            AssetPair("ETH/USD".to_string()),
+            NOW - 8,
+            NOW - 8,
+            HISTORY_CHUNK_ROWS,
+        )
+        .map(Result::unwrap)
+        .collect()
+        .await;
+        let (_, rows) = parse(&chunks.concat());
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0][3..], ["3500.00000000", "false"]);
+    }
+
+    #[tokio::test]
+    async fn test_export_wal_writes_stored_and_rejected_submissions() {
+        let base = std::env::temp_dir().join(format!("aggregator-export-{}", uuid::Uuid::new_v4()));
+        let wal_dir = base.join("wal");
+        let (sender, writer) = wal::spawn_writer(WalConfig {
+            dir: wal_dir.clone(),
+            ..WalConfig::default()
+        })
+        .unwrap();
+        let records = [
+            (
+                request(70_000.12, NOW - 10, "binance", "node-a", "BTC-USD"),
+                WalDecision::Accepted,
+            ),
+            (
+                request(70_000.12, NOW - 10, "binance", "node-a", "BTC-USD"),
+                WalDecision::Duplicate,
+            ),
+            (
+                request(90_000.0, NOW - 9, "kraken", "node,\"b\"", "BTC-USD"),
+                WalDecision::Rejected {
+                    reason: "outlier".to_string(),
+                    code: None,
+                },
+            ),
+            (
+                request(69_000.0, NOW - 500, "binance", "node-c", "BTC-USD"),
+                WalDecision::Historical,
+            ),
+            (
+                request(3_500.0, NOW - 8, "binance", "node-a", "ETH-USD"),
+                WalDecision::Accepted,
+            ),
+            (
+                request(70_010.0, NOW + 100, "binance", "node-a", "BTC-USD"),
+                WalDecision::Accepted,
+            ),
+        ];
+        for (request, decision) in records {
+            sender
+                .send(WalRecord {
+                    seq: None,
+                    received_at: NOW,
+                    request: WalRequest::from(&request),
+                    clamped_from: None,
+                    decision,
+                    aggregate: None,
+                    contributing_nodes: 0,
+                })
+                .await;
+        }
+        drop(sender);
+        writer.await.unwrap().unwrap();
+
+        let out = base.join("history.csv");
+        let args = ExportArgs {
+            from: Some(NOW - 600),
+            to: Some(NOW),
+            pair: None,
+            out: out.clone(),
+        };
+        assert_eq!(export_wal(&wal_dir, &args).unwrap(), 3);
+        let (header, rows) = parse(&std::fs::read_to_string(&out).unwrap());
+        assert_eq!(header.join(","), CSV_HEADER);
+        assert_eq!(
+            rows,
+            vec![
+                vec![
+                    "2023-11-14T22:14:50Z",
+                    "node-a",
+                    "binance",
+                    "70000.12000000",
+                    "true"
+                ],
+                vec![
+                    "2023-11-14T22:14:51Z",
+                    "node,\"b\"",
+                    "kraken",
+                    "90000.00000000",
+                    "false"
+                ],
+                vec![
+                    "2023-11-14T22:06:40Z",
+                    "node-c",
+                    "binance",
+                    "69000.00000000",
+                    "false"
+                ],
+            ]
+        );
+
+        let eth = ExportArgs {
+            pair: Some("eth-usd".to_string()),
+            ..args.clone()
+        };
+        assert_eq!(export_wal(&wal_dir, &eth).unwrap(), 1);
+        let (_, rows) = parse(&std::fs::read_to_string(&out).unwrap());
+        assert_eq!(
+            rows[0][1..],
+            ["node-a", "binance", "3500.00000000", "false"]
+        );
+
+        let reversed = ExportArgs {
+            from: Some(NOW),
+            to: Some(NOW - 1),
+            ..args
+        };
+        assert!(export_wal(&wal_dir, &reversed).is_err());
+        std::fs::remove_dir_all(base).unwrap();
+    }
+}
@@ -0,0 +1,371 @@
+//! 브라우저 소비자를 위한 grpc-web 지원 (tonic-web)
+//!
+//! 켜면 gRPC 포트가 HTTP/1.1 요청도 받고, `application/grpc-web(+proto)`(바이너리)와
+//! `application/grpc-web-text`(base64) 요청을 gRPC로 변환해 같은 OracleService로 보낸다.
+//! 일반 gRPC 요청은 그대로 통과한다. grpc-web은 단항 호출과 서버 스트리밍만 지원하므로 브라우저는
+//! StreamPrices 대신 SubscribePrices로 집계 가격을 구독한다. 다른 origin의 페이지에서 호출하려면
+//! `cors_origins`에 origin을 지정한다 (비어 있으면 CORS 헤더를 붙이지 않아 같은 origin만 호출 가능).
+
+use anyhow::Result;
+use axum::http::{HeaderName, Method};
+use std::time::Duration;
+use tonic::Status;
+use tonic_web::GrpcWebLayer;
+use tower::layer::util::{Identity, Stack};
+use tower::util::Either;
+use tower::ServiceBuilder;
+use tower_http::cors::CorsLayer;
+
+use crate::rest;
+
+/// preflight 결과를 브라우저가 보관하는 시간
+pub const PREFLIGHT_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+// grpc-web 클라이언트가 보내는 요청 헤더
+const ALLOW_HEADERS: [&str; 4] = ["x-grpc-web", "content-type", "x-user-agent", "grpc-timeout"];
+
+/// grpc-web 설정
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GrpcWebConfig {
+    /// CORS로 허용할 origin (`*`이면 모두 허용, 비어 있으면 CORS 헤더를 붙이지 않음)
+    pub cors_origins: Vec<String>,
+}
+
+/// CORS 처리 후 grpc-web 변환을 적용하는 레이어
+pub type GrpcWebLayers = Stack<GrpcWebLayer, Stack<Either<CorsLayer, Identity>, Identity>>;
+
+impl GrpcWebConfig {
+    /// gRPC 서버에 씌울 레이어 (서버는 `accept_http1(true)`이어야 함)
+    pub fn layer(&self) -> Result<GrpcWebLayers> {
+        let cors = rest::allow_origin(&self.cors_origins)?.map(|allow_origin| {
+            CorsLayer::new()
+                .allow_origin(allow_origin)
+                .allow_methods([Method::POST])
+                .allow_headers(ALLOW_HEADERS.map(HeaderName::from_static))
+                .expose_headers([
+                    Status::GRPC_STATUS,
+                    Status::GRPC_MESSAGE,
+                    Status::GRPC_STATUS_DETAILS,
+                ])
+                .max_age(PREFLIGHT_MAX_AGE)
+        });
+        Ok(ServiceBuilder::new()
+            .option_layer(cors)
+            .layer(GrpcWebLayer::new())
+            .into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oracle::oracle_service_server::{OracleService, OracleServiceServer};
+    use crate::oracle::{AggregatedPriceUpdate, GetPriceRequest, GetPriceResponse};
+    use crate::oracle::{PriceRequest, SubscribePricesRequest};
+    use crate::rest::tests::price_request;
+    use crate::{AggregateUpdate, AggregatorServiceImpl};
+    use base64::engine::general_purpose::STANDARD;
+    use base64::Engine;
+    use prost::Message;
+    use std::net::SocketAddr;
+    use tokio::net::TcpListener;
+    use tokio_stream::wrappers::TcpListenerStream;
+    use tonic::transport::Server;
+    use tonic::{Code, Request};
+
+    const DASHBOARD: &str = "https://dash.example";
+    const TRAILER_FLAG: u8 = 0x80;
+
+    // main.rs와 같은 순서로 레이어를 씌운 gRPC 서버
+    async fn start(service: AggregatorServiceImpl, config: GrpcWebConfig) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let handle = service.clone();
+        tokio::spawn(
+            Server::builder()
+                .accept_http1(true)
+                .layer(config.layer().unwrap())
+                .layer(service.metrics().grpc_layer())
+                .add_service(OracleServiceServer::new(service))
+                .serve_with_incoming_shutdown(TcpListenerStream::new(listener), async move {
+                    let mut updates = handle.subscribe();
+                    while !matches!(updates.recv().await, Ok(AggregateUpdate::Shutdown)) {}
+                }),
+        );
+        addr
+    }
+
+    async fn submit(service: &AggregatorServiceImpl, requests: Vec<PriceRequest>) {
+        for request in requests {
+            service.submit_price(Request::new(request)).await.unwrap();
+        }
+    }
+
+    // grpc-web 프레임: 플래그 1바이트 + 길이 4바이트(big endian) + 내용
+    fn frame(message: &impl Message) -> Vec<u8> {
+        let payload = message.encode_to_vec();
+        let mut frame = vec![0];
+        frame.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        frame.extend_from_slice(&payload);
+        frame
+    }
+
+    // 응답 본문을 (플래그, 내용) 프레임으로 나눔 (끝에 덜 받은 프레임이 있으면 제외)
+    fn frames(mut body: &[u8]) -> Vec<(u8, Vec<u8>)> {
+        let mut frames = Vec::new();
+        while body.len() >= 5 {
+            let len = u32::from_be_bytes(body[1..5].try_into().unwrap()) as usize;
+            if body.len() < 5 + len {
+                break;
+            }
+            frames.push((body[0], body[5..5 + len].to_vec()));
+            body = &body[5 + len..];
+        }
+        frames
+    }
+
+    // grpc-web-text 응답은 조각마다 따로 base64로 인코딩되므로 4글자 단위로 디코딩
+    fn decode_text(text: &[u8]) -> Vec<u8> {
+        text.chunks_exact(4)
+            .flat_map(|quantum| STANDARD.decode(quantum).unwrap())
+            .collect()
+    }
+
+    fn call(
+        client: &reqwest::Client,
+        addr: SocketAddr,
+        method: &str,
+        content_type: &str,
+        body: Vec<u8>,
+    ) -> reqwest::RequestBuilder {
+        client
+            .post(format!("http://{}/oracle.OracleService/{}", addr, method))
+            .header("content-type", content_type)
+            .header("accept", content_type)
+            .header("x-grpc-web", "1")
+            .header("origin", DASHBOARD)
+            .body(body)
+    }
+
+    #[tokio::test]
+    async fn test_grpc_web_unary_call_returns_the_aggregate_and_trailers() {
+        let service = AggregatorServiceImpl::new();
+        submit(
+            &service,
+            vec![
+                price_request(70_000.0, "node-a", "BTC/USD"),
+                price_request(70_010.0, "node-b", "BTC/USD"),
+                price_request(70_020.0, "node-c", "BTC/USD"),
+            ],
+        )
+        .await;
+        service.publish_snapshot().await;
+        let config = GrpcWebConfig {
+            cors_origins: vec![DASHBOARD.to_string()],
+        };
+        let addr = start(service.clone(), config).await;
+
+        let response = call(
+            &reqwest::Client::new(),
+            addr,
+            "GetAggregatedPrice",
+            "application/grpc-web+proto",
+            frame(&GetPriceRequest::default()),
+        )
+        .send()
+        .await
+        .unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+        assert_eq!(
+            response.headers()["content-type"],
+            "application/grpc-web+proto"
+        );
+        assert_eq!(response.headers()["access-control-allow-origin"], DASHBOARD);
+        let body = response.bytes().await.unwrap();
+
+        let received = frames(&body);
+        assert_eq!(received.len(), 2, "{:?}", received);
+        let (flag, message) = &received[0];
+        assert_eq!(*flag, 0);
+        let price = GetPriceResponse::decode(message.as_slice()).unwrap();
+        assert_eq!(price.aggregated_price, 70_010.0);
+        assert_eq!(price.data_points, 3);
+        // 트레일러는 HTTP/1.1 본문의 마지막 프레임으로 옴
+        let (flag, trailers) = &received[1];
+        assert_eq!(*flag, TRAILER_FLAG);
+        let trailers = String::from_utf8(trailers.clone()).unwrap();
+        assert!(trailers.contains("grpc-status:0"), "{}", trailers);
+
+        // 오류도 같은 트레일러 프레임으로 전달
+        let response = call(
+            &reqwest::Client::new(),
+            addr,
+            "GetAggregatedPrice",
+            "application/grpc-web+proto",
+            b"\x00\x00\x00\x00\x02\xff\xff".to_vec(),
+        )
+        .send()
+        .await
+        .unwrap();
+        let status = response
+            .headers()
+            .get("grpc-status")
+            .map(|value| value.to_str().unwrap().to_string());
+        let body = response.bytes().await.unwrap();
+        let trailers = frames(&body)
+            .into_iter()
+            .find(|(flag, _)| *flag == TRAILER_FLAG)
+            .map(|(_, trailers)| String::from_utf8(trailers).unwrap());
+        let code = status
+            .or(trailers)
+            .expect("grpc-status in headers or trailers");
+        assert!(
+            code.contains(&(Code::Internal as i32).to_string()),
+            "{}",
+            code
+        );
+        service.shutdown();
+    }
+
+    #[tokio::test]
+    async fn test_grpc_web_text_subscription_streams_published_updates() {
+        let service = AggregatorServiceImpl::new();
+        let addr = start(service.clone(), GrpcWebConfig::default()).await;
+
+        let request = SubscribePricesRequest {
+            client_id: "dashboard".to_string(),
+        };
+        let mut response = call(
+            &reqwest::Client::new(),
+            addr,
+            "SubscribePrices",
+            "application/grpc-web-text",
+            STANDARD.encode(frame(&request)).into_bytes(),
+        )
+        .send()
+        .await
+        .unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+        assert_eq!(
+            response.headers()["content-type"],
+            "application/grpc-web-text+proto"
+        );
+        // cors_origins가 비어 있으면 CORS 헤더를 붙이지 않음
+        assert!(response
+            .headers()
+            .get("access-control-allow-origin")
+            .is_none());
+
+        // 응답 헤더를 받은 뒤에는 구독이 등록되어 있음
+        for (price, nodes) in [
+            (70_000.0, ["node-a", "node-b", "node-c"]),
+            (70_100.0, ["node-d", "node-e", "node-f"]),
+        ] {
+            submit(
+                &service,
+                nodes
+                    .into_iter()
+                    .map(|node_id| price_request(price, node_id, "BTC/USD"))
+                    .collect(),
+            )
+            .await;
+            service.publish_snapshot().await;
+        }
+
+        let mut text = Vec::new();
+        let updates = loop {
+            let body = decode_text(&text);
+            let updates: Vec<AggregatedPriceUpdate> = frames(&body)
+                .into_iter()
+                .map(|(flag, message)| {
+                    assert_eq!(flag, 0);
+                    AggregatedPriceUpdate::decode(message.as_slice()).unwrap()
+                })
+                .collect();
+            if updates.len() == 2 {
+                break updates;
+            }
+            let chunk = tokio::time::timeout(Duration::from_secs(5), response.chunk())
+                .await
+                .expect("subscription update")
+                .unwrap()
+                .expect("open stream");
+            text.extend_from_slice(&chunk);
+        };
+        assert_eq!(updates[0].aggregated_price, 70_000.0);
+        assert_eq!(updates[1].aggregated_price, 70_050.0);
+        assert_eq!(updates[1].data_points, 6);
+        assert_eq!(updates[1].pair, "BTC/USD");
+
+        // 종료하면 스트림이 grpc-status 0 트레일러로 끝남
+        service.shutdown();
+        while let Some(chunk) = response.chunk().await.unwrap() {
+            text.extend_from_slice(&chunk);
+        }
+        let body = decode_text(&text);
+        let (flag, trailers) = frames(&body).pop().unwrap();
+        assert_eq!(flag, TRAILER_FLAG);
+        assert!(String::from_utf8(trailers)
+            .unwrap()
+            .contains("grpc-status:0"));
+    }
+
+    #[tokio::test]
+    async fn test_preflight_allows_configured_origins_only() {
+        let service = AggregatorServiceImpl::new();
+        let config = GrpcWebConfig {
+            cors_origins: vec![DASHBOARD.to_string()],
+        };
+        let addr = start(service.clone(), config).await;
+        let client = reqwest::Client::new();
+        let preflight = |origin: &'static str| {
+            client
+                .request(
+                    reqwest::Method::OPTIONS,
+                    format!("http://{}/oracle.OracleService/SubscribePrices", addr),
+                )
+                .header("origin", origin)
+                .header("access-control-request-method", "POST")
+                .header(
+                    "access-control-request-headers",
+                    "content-type,x-grpc-web,x-user-agent",
+                )
+                .send()
+        };
+
+        let response = preflight(DASHBOARD).await.unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+        let headers = response.headers();
+        assert_eq!(headers["access-control-allow-origin"], DASHBOARD);
+        assert_eq!(headers["access-control-allow-methods"], "POST");
+        let allowed = headers["access-control-allow-headers"].to_str().unwrap();
+        for header in ["content-type", "x-grpc-web", "x-user-agent", "grpc-timeout"] {
+            assert!(allowed.contains(header), "{}", allowed);
+        }
+        assert_eq!(headers["access-control-max-age"], "86400");
+
+        let response = preflight("https://evil.example").await.unwrap();
+        assert!(response
+            .headers()
+            .get("access-control-allow-origin")
+            .is_none());
+
+        // 일반 gRPC 클라이언트는 grpc-web 레이어를 그대로 통과
+        let mut client = crate::oracle::oracle_service_client::OracleServiceClient::connect(
+            format!("http://{}", addr),
+        )
+        .await
+        .unwrap();
+        let response = client
+            .get_aggregated_price(GetPriceRequest::default())
+            .await
+            .unwrap();
+        assert_eq!(response.into_inner().data_points, 0);
+
+        let config = GrpcWebConfig {
+            cors_origins: vec!["bad\norigin".to_string()],
+        };
+        assert!(config.layer().is_err());
+        service.shutdown();
+    }
+}
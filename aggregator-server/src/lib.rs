@@ -0,0 +1,4638 @@
+use arc_swap::{ArcSwap, ArcSwapOption};
+use oracle_vm_common::attestation::SignedPriceAttestation;
+use oracle_vm_common::{AssetPair, Price};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::{broadcast, mpsc, Notify, RwLock};
+use tokio::task::JoinHandle;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::Stream;
+use tonic::{Request, Response, Status};
+use tracing::{debug, field, info, warn, Span};
+
+pub mod active_nodes;
+pub mod admin;
+pub mod aggregation;
+pub mod alerts;
+pub mod attestation;
+pub mod cadence;
+pub mod config;
+pub mod export;
+pub mod grpc_web;
+pub mod metrics;
+pub mod node_history;
+pub mod onchain;
+pub mod parquet_archive;
+pub mod readiness;
+pub mod reputation;
+pub mod rest;
+pub mod settings;
+pub mod sink;
+pub mod sla;
+pub mod snapshot;
+pub mod snapshot_archive;
+pub mod source_weights;
+pub mod storage;
+pub mod store;
+pub mod strategy;
+pub mod telemetry;
+pub mod testing;
+pub mod tls;
+pub mod transport;
+pub mod trust;
+pub mod wal;
+pub mod webhook;
+pub mod ws;
+
+// gRPC 서버 코드 (tonic-build로 자동 생성됨)
+pub mod oracle {
+    tonic::include_proto!("oracle");
+}
+
+use active_nodes::ActiveNodes;
+pub use aggregation::{
+    contributing_nodes, median_exact_in_place, median_in_place, median_price, median_price_exact,
+    median_price_one_vote_per_node, percentile_sorted, quartiles, trimmed_mean_in_place,
+    trimmed_mean_price, AggregationMode,
+};
+use alerts::AlertSender;
+use attestation::{AttestationSigner, AttestationUnavailable};
+use cadence::{Clock, SystemClock};
+use config::{AggregatorConfig, PriceUnit, RuntimeConfig};
+use metrics::AggregatorMetrics;
+use node_history::{NodeHistory, SubmissionRecord};
+use readiness::{NotReady, Readiness};
+use reputation::{Reputation, REPUTATION_DECAY_INTERVAL_SECS};
+use sink::{SinkSender, SubmissionMessage};
+use sla::{SlaReport, SlaTracker};
+use snapshot::{AggregateSnapshot, AggregateUpdate};
+use snapshot_archive::{SnapshotArchive, SnapshotArchiveConfig};
+use storage::{HistoryQuery, Storage};
+use store::PriceStore;
+pub use strategy::{AggregationResult, AggregationStrategy, WeightContext};
+use wal::{WalDecision, WalRecord, WalRequest, WalSender};
+
+use oracle::{
+    oracle_service_server::OracleService, AggregatedPriceUpdate, ConfigRequest, ConfigResponse,
+    GetPriceRequest, GetPriceResponse, GetSignedPriceRequest, GetSignedPriceResponse,
+    GetSlaRequest, GetSlaResponse, GetStatsRequest, GetStatsResponse, GetTwapRequest,
+    GetTwapResponse, HealthRequest, HealthResponse, NodeHistoryRequest, NodeHistoryResponse,
+    NodeSubmission, NodeUsage, PairUsage, PriceBatchRequest, PriceBatchResponse, PriceDataPoint,
+    PriceHistoryRequest, PriceHistoryResponse, PriceRequest, PriceResponse, RegisterNodeRequest,
+    RegisterNodeResponse, ResponseCode, SetNodeReputationRequest, SetNodeReputationResponse,
+    SourceHealth, SubscribePricesRequest,
+};
+
+/// 중간값 계산에 사용하는 가격 유효 기간 (초)
+pub const PRICE_WINDOW_SECS: u64 = 60;
+/// 이 시간 이상 응답 없는 노드는 비활성으로 간주 (초)
+pub const NODE_TIMEOUT_SECS: u64 = 120;
+/// 메모리에 유지하는 최대 가격 데이터 수 (기본값)
+pub const MAX_PRICE_ENTRIES: usize = 100;
+/// GetAggregatedPrice 응답에 포함하는 최근 가격 데이터 수
+pub const RECENT_PRICES_LIMIT: usize = 10;
+/// 집계 결과를 신뢰하기 위한 최소 참여 노드 수 (정족수)
+pub const MIN_QUORUM_NODES: usize = 3;
+/// 기본 통화쌍: 심볼이 없는 요청에 사용하며 집계 가격도 이 pair 기준
+pub const DEFAULT_PAIR: &str = "BTC/USD";
+/// 게시된 집계 결과 구독 채널 크기
+pub const UPDATE_CHANNEL_CAPACITY: usize = 16;
+/// 소스의 최신 가격이 이 시간 이상 지나면 헬스체크에서 비정상으로 표시 (초)
+pub const SOURCE_STALE_SECS: u64 = 120;
+/// 이 시간 이상 가격이 없는 소스는 헬스체크 대상에서 제외 (초)
+pub const SOURCE_FORGET_SECS: u64 = 3_600;
+/// 새 가격이 없어도 집계 태스크가 만료 데이터를 정리하는 주기 (초)
+pub const PRUNE_INTERVAL_SECS: u64 = 10;
+/// 고정소수점 필드 없이 f64로만 보낸 가격을 저장할 때의 소수 자릿수
+pub const LEGACY_PRICE_DECIMALS: u32 = 8;
+/// 현재 시각보다 이만큼 넘게 앞선 타임스탬프는 초 단위가 아닌 것으로 보고 거부 (약 3년)
+///
+/// 밀리초 타임스탬프를 초로 해석하면 수만 년 뒤가 되므로 시계 오차와 혼동되지 않는다.
+pub const MAX_TIMESTAMP_AHEAD_SECS: u64 = 3 * 365 * 24 * 3_600;
+
+/// 이보다 큰 가격(달러)은 거부
+///
+/// pair별 누적 통계는 가격 합계를 18자리 고정소수점 u128로 유지하므로, 가격 하나를 10^30 이하로
+/// 묶어 두면 약 3억 개를 보관해도 합계가 넘치지 않는다.
+pub const MAX_PRICE_DOLLARS: u64 = 1_000_000_000_000;
+
+impl ResponseCode {
+    /// 코드에 대응하는 고정 응답 메시지
+    pub fn message(self) -> &'static str {
+        match self {
+            ResponseCode::Unspecified => "Unspecified",
+            ResponseCode::Ok => "Price received successfully",
+            ResponseCode::BelowQuorum => "Price received, but too few nodes are reporting",
+            ResponseCode::InvalidPrice => "Price must be a positive finite number",
+            ResponseCode::MissingNodeId => "node_id is required",
+            ResponseCode::InvalidTimestamp => {
+                "timestamp must be Unix seconds (this looks like milliseconds)"
+            }
+            ResponseCode::ClockSkew => {
+                "timestamp is too far from server time (check the node clock)"
+            }
+            ResponseCode::NodeNotAllowed => "node_id is not in the aggregator's allowed_nodes",
+        }
+    }
+
+    /// 가격이 저장된 경우인지 여부
+    pub fn is_success(self) -> bool {
+        matches!(self, ResponseCode::Ok | ResponseCode::BelowQuorum)
+    }
+
+    /// 거부 코드의 거부 사유 (저장된 경우는 None)
+    pub fn rejection_reason(self) -> Option<RejectionReason> {
+        match self {
+            ResponseCode::InvalidPrice => Some(RejectionReason::InvalidPrice),
+            ResponseCode::MissingNodeId => Some(RejectionReason::MissingNodeId),
+            ResponseCode::InvalidTimestamp => Some(RejectionReason::InvalidTimestamp),
+            ResponseCode::ClockSkew => Some(RejectionReason::ClockSkew),
+            ResponseCode::NodeNotAllowed => Some(RejectionReason::NodeNotAllowed),
+            ResponseCode::Unspecified | ResponseCode::Ok | ResponseCode::BelowQuorum => None,
+        }
+    }
+}
+
+/// 제출 거부 사유 (`rejections_total` 지표의 reason 레이블)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RejectionReason {
+    /// 가격이 없거나 0, 해석할 수 없는 고정소수점 값
+    InvalidPrice,
+    /// node_id 누락
+    MissingNodeId,
+    /// 초 단위가 아닌 타임스탬프
+    InvalidTimestamp,
+    /// 서버 시각과 허용 한도 이상 차이 나는 타임스탬프
+    ClockSkew,
+    /// 같은 노드, 시각, source로 이미 저장된 다른 가격
+    Conflict,
+    /// 허용 목록에 없는 노드
+    NodeNotAllowed,
+}
+
+impl RejectionReason {
+    /// 모든 거부 사유 (지표를 0부터 내보내기 위함)
+    pub const ALL: [RejectionReason; 6] = [
+        RejectionReason::InvalidPrice,
+        RejectionReason::MissingNodeId,
+        RejectionReason::InvalidTimestamp,
+        RejectionReason::ClockSkew,
+        RejectionReason::Conflict,
+        RejectionReason::NodeNotAllowed,
+    ];
+
+    /// 지표 레이블 값
+    pub fn label(self) -> &'static str {
+        match self {
+            RejectionReason::InvalidPrice => "invalid_price",
+            RejectionReason::MissingNodeId => "missing_node_id",
+            RejectionReason::InvalidTimestamp => "invalid_timestamp",
+            RejectionReason::ClockSkew => "clock_skew",
+            RejectionReason::Conflict => "conflict",
+            RejectionReason::NodeNotAllowed => "node_not_allowed",
+        }
+    }
+}
+
+impl PriceResponse {
+    /// 응답 코드로부터 응답 생성
+    ///
+    /// 집계 가격은 기존 f64 필드와 고정소수점 필드에 모두 채운다.
+    pub fn from_code(code: ResponseCode, aggregated_price: Option<Price>, timestamp: u64) -> Self {
+        let scaled = aggregated_price.map(|price| price.to_scaled());
+        Self {
+            success: code.is_success(),
+            message: code.message().to_string(),
+            aggregated_price: aggregated_price.map(|price| price.to_f64_dollars()),
+            timestamp,
+            code: code.into(),
+            aggregated_price_scaled: scaled.map(|(mantissa, _)| mantissa),
+            aggregated_price_decimals: scaled.map(|(_, decimals)| decimals),
+        }
+    }
+}
+
+// 가격 데이터 저장용 구조체
+// source/node_id는 Arc<str>로 공유되어 복사 비용이 참조 카운트 증가뿐이다
+#[derive(Clone, Debug)]
+pub struct PriceEntry {
+    pub price: Price,
+    pub timestamp: u64,
+    pub source: Arc<str>,
+    pub node_id: Arc<str>,
+    pub pair: Arc<str>,
+    /// 거래소가 보고한 24시간 거래량 (없으면 None)
+    pub volume: Option<u64>,
+}
+
+impl From<PriceEntry> for PriceDataPoint {
+    fn from(entry: PriceEntry) -> Self {
+        let (price_scaled, price_decimals) = entry.price.to_scaled();
+        Self {
+            price: entry.price.to_f64_dollars(),
+            timestamp: entry.timestamp,
+            source: entry.source.to_string(),
+            node_id: entry.node_id.to_string(),
+            price_scaled,
+            price_decimals,
+        }
+    }
+}
+
+impl From<SubmissionMessage> for PriceDataPoint {
+    fn from(message: SubmissionMessage) -> Self {
+        Self {
+            price: message.price,
+            timestamp: message.timestamp,
+            source: message.source,
+            node_id: message.node_id,
+            price_scaled: message.price_scaled,
+            price_decimals: message.price_decimals,
+        }
+    }
+}
+
+// Aggregator 서버 상태
+struct AggregatorState {
+    prices: PriceStore,                       // 전체 최대 크기 + 노드별 할당량
+    active_nodes: ActiveNodes,                // node_id -> last_seen_timestamp (최대 크기 제한)
+    sources: HashSet<Arc<str>>,               // 인터닝된 source 문자열
+    source_last_seen: HashMap<Arc<str>, u64>, // source -> 가장 최근 가격의 타임스탬프
+    pairs: HashSet<Arc<str>>,                 // 인터닝된 pair 문자열
+    strategy: Arc<dyn AggregationStrategy>,   // 집계 방식
+    next_seq: u64,                            // 다음 저장 순번 (WAL 재적용 순서)
+    reputation: Reputation,                   // 노드별 평판 (시간이 지나면 기본값으로 회복)
+    runtime: Arc<RuntimeConfig>,              // update_config로 바뀌는 설정 (통째로 교체)
+}
+
+// 가격 저장 시도 결과
+enum InsertOutcome {
+    Stored { seq: u64 },
+    Duplicate,
+    Conflict { existing: Price },
+}
+
+impl AggregatorState {
+    fn new(config: &AggregatorConfig) -> Self {
+        Self {
+            prices: PriceStore::new(config.max_price_entries, config.effective_per_node_quota())
+                .with_max_age(config.max_price_age_secs),
+            active_nodes: ActiveNodes::new(config.max_active_nodes),
+            sources: HashSet::new(),
+            source_last_seen: HashMap::new(),
+            pairs: HashSet::new(),
+            strategy: config.effective_strategy(),
+            next_seq: 0,
+            reputation: Reputation::with_half_life(config.reputation_half_life_secs),
+            runtime: Arc::new(RuntimeConfig::new(config)),
+        }
+    }
+
+    // 검증된 가격 저장 (같은 node_id/timestamp/source가 이미 있으면 저장하지 않음)
+    //
+    // 중복 확인은 노드가 보낸 timestamp(`submitted_at`)로 한다. 시계 오차로 보정된 제출을 재전송하면
+    // 보정된 시각은 매번 달라지므로, 보정된 값으로 확인하면 같은 제출이 두 번 저장된다.
+    fn insert(
+        &mut self,
+        request: &PriceRequest,
+        price: Price,
+        now: u64,
+        submitted_at: u64,
+    ) -> InsertOutcome {
+        let existing = self
+            .prices
+            .find_submission(&request.node_id, submitted_at, &request.source)
+            .map(|entry| entry.price);
+
+        match existing {
+            Some(existing) if existing == price => return InsertOutcome::Duplicate,
+            Some(existing) => return InsertOutcome::Conflict { existing },
+            None => {}
+        }
+
+        // 보관 시간이 지난 데이터를 먼저 제거
+        self.prune(now);
+
+        let node_id = self.intern_node_id(&request.node_id);
+        let source = self.intern_source(&request.source);
+        let pair = self.intern_pair(infer_pair(request).as_str());
+
+        // 소스별 최신 가격 시각 (가격 데이터가 만료되어도 유지)
+        let last_seen = self.source_last_seen.entry(source.clone()).or_insert(0);
+        *last_seen = (*last_seen).max(request.timestamp);
+
+        // 가격 추가 (노드 할당량 초과 시 그 노드의 가장 오래된 데이터 제거)
+        self.prices.push_submitted(
+            submitted_at,
+            PriceEntry {
+                price,
+                timestamp: request.timestamp,
+                source,
+                node_id: node_id.clone(),
+                pair,
+                volume: request.volume,
+            },
+        );
+
+        // 활성 노드 업데이트 (가득 차면 가장 오래된 노드 제거)
+        self.active_nodes.record_submission(node_id, now);
+
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        InsertOutcome::Stored { seq }
+    }
+
+    // 최대 보관 시간이 지난 가격 데이터 제거
+    fn prune(&mut self, now: u64) {
+        let pruned = self.prices.prune(now);
+        if pruned > 0 {
+            debug!("🧹 Pruned {} expired price entries", pruned);
+        }
+    }
+
+    // 비활성 노드와 더 이상 쓰이지 않는 문자열 정리
+    fn expire(&mut self, now: u64) {
+        // 120초 이상 응답 없는 노드 제거
+        self.active_nodes.remove_inactive(now, NODE_TIMEOUT_SECS);
+
+        // 오랫동안 가격이 없는 source는 헬스체크에서 제외
+        self.source_last_seen
+            .retain(|_, last_seen| !aggregation::is_stale(*last_seen, now, SOURCE_FORGET_SECS));
+
+        // 더 이상 참조되지 않는 source 문자열 해제
+        self.sources.retain(|source| Arc::strong_count(source) > 1);
+    }
+
+    // 집계에 참여하는 가격 데이터
+    // (기본 pair만 사용, 마지막 제출이 오래된 노드의 데이터 제외, 설정되면 노드별 몰린 제출을 합침)
+    fn contributing_entries(&self, now: u64, config: &RuntimeConfig) -> Vec<&PriceEntry> {
+        let entries = self.filtered_entries(now, config);
+        match config.coalesce_window_secs {
+            Some(window_secs) => aggregation::coalesce_bursts(&entries, window_secs),
+            None => entries,
+        }
+    }
+
+    // 기본 pair이고 마지막 제출이 최근인 노드의 가격 데이터 (버퍼의 항목을 복사하지 않고 참조)
+    fn filtered_entries(&self, now: u64, config: &RuntimeConfig) -> Vec<&PriceEntry> {
+        let single_pair = self.prices.contains_only_pair(DEFAULT_PAIR);
+        self.prices
+            .entries()
+            .filter(|entry| single_pair || &*entry.pair == DEFAULT_PAIR)
+            .filter(|entry| self.node_contributes(&entry.node_id, now, config))
+            .collect()
+    }
+
+    // 마지막 제출이 최근이라 집계에 들어가는 노드인지 (max_contribution_age_secs가 없으면 모두)
+    fn node_contributes(&self, node_id: &str, now: u64, config: &RuntimeConfig) -> bool {
+        config.max_contribution_age_secs.is_none_or(|max_age| {
+            self.active_nodes
+                .last_submitted(node_id)
+                .is_some_and(|last_submitted| aggregation::is_recent(last_submitted, now, max_age))
+        })
+    }
+
+    // `now` 시각의 집계에 들어가는 가격 데이터인지 (기본 pair, 가격 윈도우 이내, 최근에 제출한 노드)
+    fn contributes(&self, entry: &PriceEntry, now: u64, config: &RuntimeConfig) -> bool {
+        &*entry.pair == DEFAULT_PAIR
+            && aggregation::is_recent(entry.timestamp, now, PRICE_WINDOW_SECS)
+            && self.node_contributes(&entry.node_id, now, config)
+    }
+
+    // 설정된 집계 방식으로 집계 가격 계산
+    fn median(&self, entries: &[&PriceEntry], now: u64, config: &RuntimeConfig) -> Option<Price> {
+        let context = WeightContext {
+            sources: &config.source_weights,
+            reputation: &self.reputation,
+        };
+        self.strategy
+            .aggregate_weighted(entries, now, &context)
+            .map(|result| result.price)
+    }
+
+    // `config` 스냅샷 기준의 중간값(median)과 참여 노드 수
+    fn aggregate(&self, now: u64, config: &RuntimeConfig) -> (Option<Price>, usize) {
+        let entries = self.contributing_entries(now, config);
+        (
+            self.median(&entries, now, config),
+            contributing_nodes(&entries, now, PRICE_WINDOW_SECS),
+        )
+    }
+
+    // 현재 상태로 집계 스냅샷 생성 (최근 데이터는 Arc 참조 카운트만 증가)
+    fn snapshot(&self, now: u64) -> AggregateSnapshot {
+        let config = &self.runtime;
+        let entries = self.contributing_entries(now, config);
+        let quartiles = quartiles(&entries, now, PRICE_WINDOW_SECS);
+        AggregateSnapshot {
+            aggregated_price: self.median(&entries, now, config),
+            stale: false,
+            aggregated_at: now,
+            p25: quartiles.map(|(p25, _)| p25),
+            p75: quartiles.map(|(_, p75)| p75),
+            contributing_nodes: contributing_nodes(&entries, now, PRICE_WINDOW_SECS),
+            active_nodes: self.active_nodes.len(),
+            active_node_ids: self.active_nodes.iter_oldest_first().cloned().collect(),
+            stored_prices: self.prices.len(),
+            recent_prices: self
+                .prices
+                .entries()
+                .rev()
+                .take(RECENT_PRICES_LIMIT)
+                .cloned()
+                .collect(),
+            source_last_seen: {
+                let mut sources: Vec<(Arc<str>, u64)> = self
+                    .source_last_seen
+                    .iter()
+                    .map(|(source, &last_seen)| (source.clone(), last_seen))
+                    .collect();
+                sources.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+                sources
+            },
+            timestamp: now,
+        }
+    }
+
+    // 이미 알고 있는 node_id면 기존 Arc를 재사용
+    fn intern_node_id(&self, node_id: &str) -> Arc<str> {
+        self.active_nodes
+            .get_key(node_id)
+            .unwrap_or_else(|| Arc::from(node_id))
+    }
+
+    // 이미 알고 있는 pair면 기존 Arc를 재사용
+    fn intern_pair(&mut self, pair: &str) -> Arc<str> {
+        if let Some(existing) = self.pairs.get(pair) {
+            return existing.clone();
+        }
+        let interned: Arc<str> = Arc::from(pair);
+        self.pairs.insert(interned.clone());
+        interned
+    }
+
+    // 이미 알고 있는 source면 기존 Arc를 재사용
+    fn intern_source(&mut self, source: &str) -> Arc<str> {
+        if let Some(existing) = self.sources.get(source) {
+            return existing.clone();
+        }
+        let interned: Arc<str> = Arc::from(source);
+        self.sources.insert(interned.clone());
+        interned
+    }
+}
+
+/// 요청 심볼로 pair 추론 (심볼이 없거나 해석할 수 없으면 기본 pair)
+fn infer_pair(request: &PriceRequest) -> AssetPair {
+    let Some(symbol) = request.symbol.as_deref() else {
+        debug!("No symbol from {}, using {}", request.node_id, DEFAULT_PAIR);
+        return AssetPair(DEFAULT_PAIR.to_string());
+    };
+
+    match AssetPair::from_symbol(symbol) {
+        Some(pair) => {
+            debug!(
+                "🔎 Inferred pair {} from symbol {:?} ({})",
+                pair.as_str(),
+                symbol,
+                request.source
+            );
+            pair
+        }
+        None => {
+            warn!(
+                "⚠️ Unknown symbol {:?} from {}, defaulting to {}",
+                symbol, request.node_id, DEFAULT_PAIR
+            );
+            AssetPair(DEFAULT_PAIR.to_string())
+        }
+    }
+}
+
+/// 기본 pair의 호가 통화 (조회/스트림 응답의 가격 통화)
+fn default_quote_currency() -> String {
+    AssetPair(DEFAULT_PAIR.to_string()).quote().to_string()
+}
+
+/// 요청 pair (`infer_pair`와 같되 로그를 남기지 않음, span과 싱크 레코드용)
+fn request_pair(request: &PriceRequest) -> String {
+    request
+        .symbol
+        .as_deref()
+        .and_then(AssetPair::from_symbol)
+        .map_or_else(|| DEFAULT_PAIR.to_string(), |pair| pair.0)
+}
+
+// 집계 span의 결과
+fn aggregation_outcome(snapshot: &AggregateSnapshot) -> &'static str {
+    if snapshot.aggregated_price.is_none() {
+        "no_price"
+    } else if snapshot.stale {
+        "stale"
+    } else if !snapshot.meets_quorum() {
+        "below_quorum"
+    } else {
+        "published"
+    }
+}
+
+/// 요청 가격 결정: 고정소수점 필드가 있으면 우선 사용, 없으면 기존 f64 필드
+fn request_price(request: &PriceRequest) -> Option<Price> {
+    match (request.price_scaled, request.price_decimals) {
+        (Some(scaled), Some(decimals)) => Price::from_scaled(scaled, decimals).ok(),
+        // 자릿수 없는 고정소수점 값은 해석할 수 없음
+        (Some(_), None) => None,
+        (None, _) => Price::from_f64_dollars(request.price, LEGACY_PRICE_DECIMALS).ok(),
+    }
+}
+
+// 가격이 MAX_PRICE_DOLLARS보다 큰지 (자릿수가 커서 한도가 u64를 넘으면 어떤 가격도 넘지 않음)
+fn exceeds_max_price(price: Price) -> bool {
+    let limit = 10u64
+        .checked_pow(price.decimals())
+        .and_then(|scale| MAX_PRICE_DOLLARS.checked_mul(scale));
+    limit.is_some_and(|limit| price.mantissa() > limit)
+}
+
+/// 가격 요청 검증 (유효하면 가격, 아니면 거부 코드 반환)
+fn validate_price_request(request: &PriceRequest, now: u64) -> Result<Price, ResponseCode> {
+    let price = request_price(request).ok_or(ResponseCode::InvalidPrice)?;
+    if price.is_zero() || exceeds_max_price(price) {
+        return Err(ResponseCode::InvalidPrice);
+    }
+    if request.node_id.trim().is_empty() {
+        return Err(ResponseCode::MissingNodeId);
+    }
+    if request.timestamp > now.saturating_add(MAX_TIMESTAMP_AHEAD_SECS) {
+        return Err(ResponseCode::InvalidTimestamp);
+    }
+    Ok(price)
+}
+
+/// 서버 시각과 제출 타임스탬프의 차이에 따른 처리
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SkewDecision {
+    /// 그대로 사용
+    Untouched,
+    /// 서버 시각으로 대체
+    Clamped,
+    /// 거부
+    Rejected,
+}
+
+/// 차이가 reject 한도를 넘으면 거부, clamp 한도를 넘으면 서버 시각으로 대체 (None이면 해당 처리 없음)
+fn skew_decision(
+    timestamp: u64,
+    now: u64,
+    clamp: Option<u64>,
+    reject: Option<u64>,
+) -> SkewDecision {
+    let skew = timestamp.abs_diff(now);
+    if reject.is_some_and(|limit| skew > limit) {
+        SkewDecision::Rejected
+    } else if clamp.is_some_and(|limit| skew > limit) {
+        SkewDecision::Clamped
+    } else {
+        SkewDecision::Untouched
+    }
+}
+
+// 기록 도중 패닉이 나도 가용성 조회는 계속 동작
+fn lock_sla(sla: &Mutex<SlaTracker>) -> std::sync::MutexGuard<'_, SlaTracker> {
+    sla.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+// 기록 도중 패닉이 나도 노드 기록 조회는 계속 동작
+fn lock_history(history: &Mutex<NodeHistory>) -> std::sync::MutexGuard<'_, NodeHistory> {
+    history.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+// Aggregator 서비스 구현 (복제본은 같은 상태와 구독 채널을 공유)
+#[derive(Clone)]
+pub struct AggregatorServiceImpl {
+    state: Arc<RwLock<AggregatorState>>, // 쓰기 경로의 기준 상태
+    snapshot: Arc<ArcSwap<AggregateSnapshot>>, // 읽기 경로용 최신 집계 결과
+    aggregation_trigger: Arc<Notify>,    // 새 가격 저장 시 집계 태스크 깨우기
+    wal: Option<WalSender>,              // 제출 기록 (설정된 경우)
+    sinks: Vec<SinkSender>,              // 수락된 제출을 내보내는 싱크들
+    storage: Option<Arc<dyn Storage>>,   // 메모리 버퍼보다 오래된 기록 조회 (설정된 경우)
+    publish_interval: Option<u64>,       // 집계 게시 주기 (초)
+    clock: Arc<dyn Clock>,               // 현재 시각 (테스트에서 교체)
+    updates: broadcast::Sender<AggregateUpdate>, // 게시된 집계 결과와 종료 알림
+    admin_secret: Option<Arc<str>>,      // 관리자 RPC 인증용 비밀값
+    max_batch_size: usize,               // 일괄 전송 최대 크기
+    max_history_limit: usize,            // 가격 데이터 조회 최대 개수
+    sla: Arc<Mutex<SlaTracker>>,         // 집계 가격 가용성
+    history: Arc<Mutex<NodeHistory>>,    // 노드별 최근 제출과 처리 결과
+    stale_grace_secs: Option<u64>,       // 윈도우가 비었을 때 마지막 집계 가격을 게시하는 시간
+    metrics: AggregatorMetrics,          // 운영 지표 (`/metrics`)
+    price_unit: PriceUnit,               // 조회/스트림 응답의 f64 가격 단위
+    attestation: Arc<ArcSwapOption<AttestationSigner>>, // 집계 가격 서명 키 (설정된 경우)
+    alerts: Option<AlertSender>,         // 게시마다 평가하는 경보 규칙 (설정된 경우)
+    allowed_nodes: Arc<BTreeSet<String>>, // 제출을 받는 노드 ID (비어 있으면 모든 노드)
+    aggregation_mode: AggregationMode,   // 중간값 계산 공간 (RegisterNode 응답용)
+    max_price_age_secs: u64,             // 가격 데이터 최대 보관 시간 (RegisterNode 응답용)
+    readiness: Readiness,                // `/readyz` 준비 상태
+}
+
+// 집계 게시에 필요한 공유 핸들
+#[derive(Clone)]
+struct Publisher {
+    state: Arc<RwLock<AggregatorState>>,
+    snapshot: Arc<ArcSwap<AggregateSnapshot>>,
+    updates: broadcast::Sender<AggregateUpdate>,
+    sla: Arc<Mutex<SlaTracker>>,
+    stale_grace_secs: Option<u64>,
+    metrics: AggregatorMetrics,
+    alerts: Option<AlertSender>,
+}
+
+impl Publisher {
+    // 만료 데이터를 정리하고 `now` 시점의 윈도우로 집계하여 스냅샷 교체 후 구독자에게 전달
+    // (윈도우가 비었으면 유예 시간 동안 이전 집계 가격을 stale로 게시)
+    #[tracing::instrument(
+        name = "aggregate",
+        skip_all,
+        fields(pair = DEFAULT_PAIR, contributing_nodes = field::Empty, outcome = field::Empty)
+    )]
+    async fn publish(&self, now: u64) {
+        let started = Instant::now();
+        let mut state = self.state.write().await;
+        state.prune(now);
+        let mut next = state.downgrade().snapshot(now);
+        let previous = self.snapshot.load();
+        if let Some(grace_secs) = self.stale_grace_secs {
+            if next.fall_back_to(&previous, grace_secs) {
+                if !previous.stale {
+                    warn!(
+                        "⏳ No prices in the window, serving the last median as stale for up to {}s",
+                        grace_secs
+                    );
+                }
+            } else if previous.stale && next.aggregated_price.is_none() {
+                warn!(
+                    "⌛ Stale median expired after {}s without fresh prices",
+                    grace_secs
+                );
+            }
+        }
+        self.metrics
+            .record_aggregation(DEFAULT_PAIR, &next, started.elapsed());
+        let span = Span::current();
+        span.record("contributing_nodes", next.contributing_nodes);
+        span.record("outcome", aggregation_outcome(&next));
+        let next = Arc::new(next);
+        self.snapshot.store(next.clone());
+        lock_sla(&self.sla).record(now, next.meets_quorum());
+        if let Some(alerts) = &self.alerts {
+            alerts.evaluate(&next);
+        }
+        // 구독자가 없으면 무시
+        let _ = self.updates.send(AggregateUpdate::Published(next));
+    }
+}
+
+impl AggregatorServiceImpl {
+    pub fn new() -> Self {
+        Self::with_config(AggregatorConfig::default())
+    }
+
+    pub fn with_config(config: AggregatorConfig) -> Self {
+        Self::from_state(AggregatorState::new(&config), &config)
+    }
+
+    fn from_state(state: AggregatorState, config: &AggregatorConfig) -> Self {
+        Self {
+            state: Arc::new(RwLock::new(state)),
+            snapshot: Arc::new(ArcSwap::from_pointee(AggregateSnapshot::default())),
+            aggregation_trigger: Arc::new(Notify::new()),
+            wal: None,
+            sinks: Vec::new(),
+            storage: None,
+            publish_interval: config.publish_interval_secs,
+            clock: Arc::new(SystemClock),
+            updates: broadcast::channel(UPDATE_CHANNEL_CAPACITY).0,
+            admin_secret: config.admin_secret.as_deref().map(Arc::from),
+            max_batch_size: config.max_batch_size,
+            max_history_limit: config.max_history_limit,
+            sla: Arc::new(Mutex::new(SlaTracker::new(
+                config.sla_window_secs,
+                config.sla_target,
+            ))),
+            history: Arc::new(Mutex::new(NodeHistory::new(
+                config.max_history_limit,
+                config.max_active_nodes,
+            ))),
+            stale_grace_secs: config.stale_grace_secs,
+            metrics: AggregatorMetrics::new(),
+            price_unit: config.price_unit,
+            attestation: Arc::new(ArcSwapOption::empty()),
+            alerts: None,
+            allowed_nodes: Arc::new(config.allowed_nodes.clone()),
+            aggregation_mode: config.aggregation_mode,
+            max_price_age_secs: config.max_price_age_secs,
+            readiness: Readiness::default(),
+        }
+    }
+
+    /// 시계 교체 (테스트용)
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// 게시되는 집계 결과 구독 (서버 종료 시 마지막으로 AggregateUpdate::Shutdown 수신)
+    pub fn subscribe(&self) -> broadcast::Receiver<AggregateUpdate> {
+        self.updates.subscribe()
+    }
+
+    /// 계획된 종료를 구독자에게 알림
+    ///
+    /// stream_prices 스트림은 이 알림을 받으면 정상 종료되므로 클라이언트는 장애(연결 끊김)와
+    /// 구분할 수 있다. 집계 태스크를 멈춘 뒤 서버의 graceful shutdown 신호와 함께 호출한다.
+    pub fn shutdown(&self) {
+        info!(
+            "🛑 Closing {} update subscriber(s) for shutdown",
+            self.updates.receiver_count()
+        );
+        // 구독자가 없으면 무시
+        let _ = self.updates.send(AggregateUpdate::Shutdown);
+    }
+
+    fn publisher(&self) -> Publisher {
+        Publisher {
+            state: self.state.clone(),
+            snapshot: self.snapshot.clone(),
+            updates: self.updates.clone(),
+            sla: self.sla.clone(),
+            stale_grace_secs: self.stale_grace_secs,
+            metrics: self.metrics.clone(),
+            alerts: self.alerts.clone(),
+        }
+    }
+
+    /// 모든 제출과 처리 결과를 WAL로 기록
+    pub fn with_wal(mut self, wal: WalSender) -> Self {
+        self.wal = Some(wal);
+        self
+    }
+
+    /// 수락된 제출을 싱크로도 전달 (집계 결과는 싱크 태스크가 게시 채널을 구독해 받음)
+    ///
+    /// 여러 번 호출하면 모든 싱크로 전달한다.
+    pub fn with_sink(mut self, sink: SinkSender) -> Self {
+        self.sinks.push(sink);
+        self
+    }
+
+    /// 메모리 버퍼에 없는 이전 구간의 가격 기록을 저장소에서 조회
+    ///
+    /// 저장소에 기록하는 태스크(`storage::spawn`)가 반환한 `SinkSender`는 `with_sink`로 따로 등록한다.
+    pub fn with_storage(mut self, storage: Arc<dyn Storage>) -> Self {
+        self.storage = Some(storage);
+        self
+    }
+
+    /// 게시되는 집계마다 경보 규칙 평가 (`alerts::spawn`이 반환한 핸들)
+    pub fn with_alerts(mut self, alerts: AlertSender) -> Self {
+        self.alerts = Some(alerts);
+        self
+    }
+
+    /// `/readyz` 준비 상태 설정 (warm-up 유예 시간, 종료 대기 시간, 확인할 저장소)
+    pub fn with_readiness(mut self, readiness: Readiness) -> Self {
+        self.readiness = readiness;
+        self
+    }
+
+    /// 준비 상태 핸들 (종료 시 `drain`으로 `/readyz`를 먼저 503으로 바꿈)
+    pub fn readiness(&self) -> &Readiness {
+        &self.readiness
+    }
+
+    /// gRPC 수신 소켓이 바인드됨 (warm-up 유예 시간 시작)
+    pub fn mark_grpc_bound(&self) {
+        self.readiness.grpc_bound(self.clock.now_secs());
+    }
+
+    /// 현재 시각과 마지막 집계 결과 기준의 준비 상태 (`/readyz`)
+    pub async fn readiness_check(&self) -> Result<(), NotReady> {
+        self.readiness
+            .check(self.clock.now_secs(), &self.snapshot())
+            .await
+    }
+
+    /// 집계 가격 서명 키 설정 (GetSignedPrice와 `/v1/attestation`을 켬)
+    pub fn with_attestation_signer(self, signer: AttestationSigner) -> Self {
+        self.rotate_attestation_signer(signer);
+        self
+    }
+
+    /// 서명 키 교체 (복제본도 모두 새 키로 서명)
+    pub fn rotate_attestation_signer(&self, signer: AttestationSigner) {
+        info!("🔏 Signing price attestations with key {}", signer.key_id());
+        self.attestation.store(Some(Arc::new(signer)));
+    }
+
+    /// 가장 최근에 게시된 집계 가격의 서명된 증명
+    ///
+    /// 서명 키가 없으면 `NotConfigured`, 첫 집계 가격이 게시되기 전이면 `NoPrice`를 반환한다.
+    pub fn signed_price(&self) -> Result<SignedPriceAttestation, AttestationUnavailable> {
+        let signer = self.attestation.load();
+        let signer = signer
+            .as_deref()
+            .ok_or(AttestationUnavailable::NotConfigured)?;
+        signer
+            .sign(DEFAULT_PAIR, &self.snapshot())
+            .ok_or(AttestationUnavailable::NoPrice)
+    }
+
+    /// WAL 레코드로 상태 복구
+    ///
+    /// 저장된 제출만 원래 순번대로 다시 적용하며, 수신 시각을 기준으로 노드 활동도 재현한다.
+    /// 노드별 제출 기록은 거부된 제출을 포함해 기록된 순서대로 다시 채운다.
+    pub fn replay(config: AggregatorConfig, records: impl IntoIterator<Item = WalRecord>) -> Self {
+        let mut history = NodeHistory::new(config.max_history_limit, config.max_active_nodes);
+        let mut accepted: Vec<(u64, WalRecord)> = Vec::new();
+        for record in records {
+            let request = PriceRequest::from(record.request.clone());
+            if let Some(entry) = SubmissionRecord::new(
+                &request,
+                request_price(&request),
+                record.received_at,
+                &record.decision,
+            ) {
+                history.record(&request.node_id, entry);
+            }
+            if record.decision == WalDecision::Accepted {
+                if let Some(seq) = record.seq {
+                    accepted.push((seq, record));
+                }
+            }
+        }
+        accepted.sort_unstable_by_key(|(seq, _)| *seq);
+
+        let mut state = AggregatorState::new(&config);
+        for (seq, record) in accepted {
+            let request = PriceRequest::from(record.request);
+            if let Ok(price) = validate_price_request(&request, record.received_at) {
+                state.next_seq = seq;
+                let submitted_at = record.clamped_from.unwrap_or(request.timestamp);
+                state.insert(&request, price, record.received_at, submitted_at);
+                state.expire(record.received_at);
+            }
+        }
+
+        let service = Self::from_state(state, &config);
+        *lock_history(&service.history) = history;
+        service
+    }
+
+    // 허용 목록이 있으면 목록에 있는 노드만 받음
+    fn check_allowed(&self, node_id: &str) -> Result<(), ResponseCode> {
+        if self.allowed_nodes.is_empty() || self.allowed_nodes.contains(node_id) {
+            Ok(())
+        } else {
+            Err(ResponseCode::NodeNotAllowed)
+        }
+    }
+
+    // 실시간 제출의 시계 오차 처리 (재전송된 과거 관측값은 원래 오래된 것이므로 제외)
+    fn apply_skew_policy(
+        request: &mut PriceRequest,
+        now: u64,
+        config: &RuntimeConfig,
+    ) -> Result<(), ResponseCode> {
+        if request.historical {
+            return Ok(());
+        }
+        match skew_decision(
+            request.timestamp,
+            now,
+            config.skew_clamp_secs,
+            config.skew_reject_secs,
+        ) {
+            SkewDecision::Untouched => Ok(()),
+            SkewDecision::Clamped => {
+                warn!(
+                    "⏱️ Clamped timestamp from {:?} to server time ({}s skew)",
+                    request.node_id,
+                    request.timestamp as i128 - now as i128
+                );
+                request.timestamp = now;
+                Ok(())
+            }
+            SkewDecision::Rejected => Err(ResponseCode::ClockSkew),
+        }
+    }
+
+    /// 가장 최근에 게시된 집계 스냅샷 (락 없음)
+    pub fn snapshot(&self) -> Arc<AggregateSnapshot> {
+        self.snapshot.load_full()
+    }
+
+    /// 운영 지표 레지스트리 (gRPC 서버에 `grpc_layer`를 붙여 요청 시간도 기록)
+    pub fn metrics(&self) -> &AggregatorMetrics {
+        &self.metrics
+    }
+
+    /// 버퍼 사용량을 현재 값으로 채운 뒤 Prometheus 텍스트 형식으로 출력
+    pub async fn encode_metrics(&self) -> anyhow::Result<String> {
+        {
+            let state = self.state.read().await;
+            self.metrics
+                .set_buffer_occupancy(state.prices.len(), state.prices.max_entries());
+        }
+        self.metrics.encode()
+    }
+
+    /// 현재 시각까지의 집계 가격 가용성
+    pub fn sla_report(&self) -> SlaReport {
+        lock_sla(&self.sla).report(self.clock.now_secs())
+    }
+
+    /// 현재 상태로 집계하여 스냅샷을 즉시 게시
+    pub async fn publish_snapshot(&self) {
+        self.publisher().publish(self.clock.now_secs()).await;
+    }
+
+    /// 집계 태스크 시작
+    ///
+    /// 게시 주기가 없으면 새 가격이 저장될 때마다 집계한다 (몰린 알림은 합쳐져 한 번만 집계).
+    /// 새 가격이 없어도 PRUNE_INTERVAL_SECS마다 만료 데이터를 정리하고 다시 게시한다.
+    /// 게시 주기가 있으면 벽시계 경계마다 그 시각 기준의 윈도우로 한 번 집계하고
+    /// 경계 시각을 스냅샷 시각으로 사용한다. 경계 이후의 제출은 다음 게시에만 반영된다.
+    pub fn spawn_aggregation_task(&self) -> JoinHandle<()> {
+        let publisher = self.publisher();
+        let trigger = self.aggregation_trigger.clone();
+        let clock = self.clock.clone();
+        let interval = self.publish_interval;
+
+        tokio::spawn(async move {
+            match interval {
+                None => loop {
+                    let prune_interval = Duration::from_secs(PRUNE_INTERVAL_SECS);
+                    let _ = tokio::time::timeout(prune_interval, trigger.notified()).await;
+                    publisher.publish(clock.now_secs()).await;
+                },
+                Some(interval) => loop {
+                    let now = clock.now_millis();
+                    let boundary = cadence::next_boundary_millis(now, interval);
+                    tokio::time::sleep(Duration::from_millis(boundary - now)).await;
+                    publisher.publish(boundary / 1_000).await;
+                },
+            }
+        })
+    }
+
+    /// 평판 회복 태스크 시작
+    ///
+    /// REPUTATION_DECAY_INTERVAL_SECS마다 지난 회복 이후 흐른 시간만큼 모든 노드의 평판을
+    /// 기본값 쪽으로 회복시키므로, 제출이 드문 노드도 한 번 깎인 평판에 머무르지 않는다.
+    pub fn spawn_reputation_decay_task(&self) -> JoinHandle<()> {
+        let state = self.state.clone();
+        let clock = self.clock.clone();
+
+        tokio::spawn(async move {
+            state.write().await.reputation.decay(clock.now_secs());
+            loop {
+                tokio::time::sleep(Duration::from_secs(REPUTATION_DECAY_INTERVAL_SECS)).await;
+                let recovered = state.write().await.reputation.decay(clock.now_secs());
+                if recovered > 0 {
+                    debug!(
+                        "💚 {} node(s) recovered to the default reputation",
+                        recovered
+                    );
+                }
+            }
+        })
+    }
+
+    /// 스냅샷 보관 태스크 시작
+    ///
+    /// `interval_secs`마다 마지막으로 게시된 스냅샷을 압축해 기록하고 오래된 파일을 정리한다.
+    /// 기록 실패는 로그만 남기고 다음 주기에 다시 시도한다.
+    pub fn spawn_snapshot_archive_task(
+        &self,
+        config: SnapshotArchiveConfig,
+    ) -> std::io::Result<JoinHandle<()>> {
+        let archive = Arc::new(std::sync::Mutex::new(SnapshotArchive::open(
+            &config.dir,
+            config.keep,
+        )?));
+        let snapshot = self.snapshot.clone();
+        info!(
+            "🗜️ Archiving snapshots to {} every {}s (keeping {})",
+            config.dir.display(),
+            config.interval_secs,
+            config.keep
+        );
+
+        Ok(tokio::spawn(async move {
+            let mut ticker =
+                tokio::time::interval(Duration::from_secs(config.interval_secs.max(1)));
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let latest = snapshot.load_full();
+                let archive = archive.clone();
+                let written =
+                    tokio::task::spawn_blocking(move || archive.lock().unwrap().write(&latest))
+                        .await;
+                match written {
+                    Ok(Ok(path)) => debug!("🗜️ Archived snapshot {}", path.display()),
+                    Ok(Err(e)) => warn!("⚠️ Failed to archive snapshot: {}", e),
+                    Err(e) => warn!("⚠️ Snapshot archive task failed: {}", e),
+                }
+            }
+        }))
+    }
+
+    // 현재 설정 스냅샷 (한 번의 읽기 락으로 모든 필드를 함께 가져옴)
+    async fn runtime_config(&self) -> Arc<RuntimeConfig> {
+        self.state.read().await.runtime.clone()
+    }
+
+    // 현재 설정으로 중간값(median)과 참여 노드 수 계산
+    #[cfg(test)]
+    async fn calculate_median_price(&self) -> (Option<Price>, usize) {
+        let state = self.state.read().await;
+        let current_time = self.clock.now_secs();
+
+        state.aggregate(current_time, &state.runtime)
+    }
+
+    // 요청 처리 시작 때 가져온 설정으로 중간값(median)과 참여 노드 수 계산
+    async fn calculate_median_price_with(&self, config: &RuntimeConfig) -> (Option<Price>, usize) {
+        let state = self.state.read().await;
+        let current_time = self.clock.now_secs();
+
+        state.aggregate(current_time, config)
+    }
+
+    // 활성 노드 정리
+    async fn cleanup_inactive_nodes(&self) {
+        let current_time = self.clock.now_secs();
+        self.state.write().await.expire(current_time);
+    }
+
+    // 과거 관측값을 WAL에만 기록하고 마지막 게시 결과로 응답
+    //
+    // 실시간 버퍼에 넣으면 오래된 값이 최신 데이터를 할당량에서 밀어낼 수 있으므로 저장하지 않는다.
+    async fn accept_historical(
+        &self,
+        request: &PriceRequest,
+        price: Price,
+        current_time: u64,
+    ) -> PriceResponse {
+        info!(
+            "🗄️ Received historical price: ${} from {} ({}) at {}",
+            price, request.node_id, request.source, request.timestamp
+        );
+
+        let snapshot = self.snapshot.load();
+        let aggregate = (snapshot.aggregated_price, snapshot.contributing_nodes);
+        self.record_submission(
+            request,
+            None,
+            None,
+            current_time,
+            WalDecision::Historical,
+            aggregate,
+        )
+        .await;
+
+        let code = if aggregate.1 < MIN_QUORUM_NODES {
+            ResponseCode::BelowQuorum
+        } else {
+            ResponseCode::Ok
+        };
+        PriceResponse::from_code(code, aggregate.0, current_time)
+    }
+
+    // 스트림 구독자에게 보낼 집계 결과 (스냅샷만 읽음)
+    fn price_update(&self, snapshot: &AggregateSnapshot) -> AggregatedPriceUpdate {
+        let active_nodes = snapshot
+            .active_node_ids
+            .iter()
+            .map(|node_id| node_id.to_string())
+            .collect();
+
+        AggregatedPriceUpdate {
+            aggregated_price: snapshot
+                .aggregated_price
+                .map_or(0.0, |price| self.price_unit.price(price)),
+            data_points: snapshot.contributing_nodes as u32,
+            timestamp: snapshot.timestamp,
+            active_nodes,
+            stale: snapshot.stale,
+            pair: DEFAULT_PAIR.to_string(),
+            quote_currency: default_quote_currency(),
+            unit: self.price_unit.name().to_string(),
+            submission: None,
+        }
+    }
+
+    // 배치나 스트림으로 받은 제출 하나를 submit_price와 같은 경로로 처리
+    // 에러 상태도 실패 응답으로 바꿔 돌려주므로 한 제출의 실패가 나머지에 영향 없음
+    async fn submit_one(&self, price_data: PriceRequest) -> PriceResponse {
+        match self.submit_price(Request::new(price_data)).await {
+            Ok(response) => response.into_inner(),
+            Err(status) => PriceResponse {
+                success: false,
+                message: status.message().to_string(),
+                timestamp: self.clock.now_secs(),
+                ..PriceResponse::default()
+            },
+        }
+    }
+
+    // 노드별 제출 기록에 남기고, WAL이 설정된 경우 WAL에도 기록
+    // (`clamped_from`은 timestamp가 보정된 제출의 원래 timestamp)
+    async fn record_submission(
+        &self,
+        request: &PriceRequest,
+        clamped_from: Option<u64>,
+        seq: Option<u64>,
+        received_at: u64,
+        decision: WalDecision,
+        aggregate: (Option<Price>, usize),
+    ) {
+        let known = self.allowed_nodes.contains(&request.node_id)
+            || self
+                .state
+                .read()
+                .await
+                .active_nodes
+                .contains(&request.node_id);
+        self.metrics
+            .record_submission(known.then_some(request.node_id.as_str()), &decision);
+        Span::current().record("outcome", decision.name());
+        if let (WalDecision::Accepted, Some(price), false) =
+            (&decision, request_price(request), self.sinks.is_empty())
+        {
+            let message = SubmissionMessage::new(
+                request_pair(request),
+                &request.node_id,
+                &request.source,
+                price,
+                request.timestamp,
+                received_at,
+                seq,
+            );
+            for sink in &self.sinks {
+                sink.send(message.clone());
+            }
+        }
+        if !request.node_id.trim().is_empty() {
+            if let Some(entry) =
+                SubmissionRecord::new(request, request_price(request), received_at, &decision)
+            {
+                lock_history(&self.history).record(&request.node_id, entry);
+            }
+        }
+
+        if let Some(wal) = &self.wal {
+            wal.send(WalRecord {
+                seq,
+                received_at,
+                request: WalRequest::from(request),
+                clamped_from,
+                decision,
+                aggregate: aggregate.0,
+                contributing_nodes: aggregate.1,
+            })
+            .await;
+        }
+    }
+}
+
+impl Default for AggregatorServiceImpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[tonic::async_trait]
+impl OracleService for AggregatorServiceImpl {
+    type StreamPricesStream =
+        Pin<Box<dyn Stream<Item = Result<AggregatedPriceUpdate, Status>> + Send + 'static>>;
+    type SubscribePricesStream = Self::StreamPricesStream;
+
+    // 요청 전체(서명, 메타데이터 등)는 기록하지 않고 필터링에 필요한 필드만 span에 남긴다
+    // (노드가 traceparent를 보냈으면 노드 라운드 trace를 이어감)
+    #[tracing::instrument(
+        name = "submit_price",
+        skip_all,
+        fields(
+            node_id = %request.get_ref().node_id,
+            pair = %request_pair(request.get_ref()),
+            source = %request.get_ref().source,
+            price = request.get_ref().price,
+            outcome = field::Empty,
+        )
+    )]
+    async fn submit_price(
+        &self,
+        request: Request<PriceRequest>,
+    ) -> Result<Response<PriceResponse>, Status> {
+        telemetry::continue_remote_trace(&Span::current(), request.metadata());
+        let mut price_data = request.into_inner();
+        // 시계 오차 보정 전의 timestamp (재전송된 제출을 알아보는 기준)
+        let submitted_at = price_data.timestamp;
+        let current_time = self.clock.now_secs();
+        // 처리 도중 update_config가 설정을 바꿔도 이 요청은 처음 읽은 설정만 사용
+        let config = self.runtime_config().await;
+
+        // 유효하지 않은 요청은 저장하지 않고 거부
+        let validated = validate_price_request(&price_data, current_time)
+            .and_then(|price| self.check_allowed(&price_data.node_id).map(|()| price))
+            .and_then(|price| {
+                Self::apply_skew_policy(&mut price_data, current_time, &config).map(|()| price)
+            });
+        let price = match validated {
+            Ok(price) => price,
+            Err(code) => {
+                warn!(
+                    "🚫 Rejected price from {:?}: {}",
+                    price_data.node_id,
+                    code.message()
+                );
+                let rejection = code.rejection_reason();
+                if let Some(reason) = rejection {
+                    self.metrics.record_rejection(reason);
+                }
+                let reason = code.message().to_string();
+                self.record_submission(
+                    &price_data,
+                    None,
+                    None,
+                    current_time,
+                    WalDecision::Rejected {
+                        reason,
+                        code: rejection,
+                    },
+                    (None, 0),
+                )
+                .await;
+                return Ok(Response::new(PriceResponse::from_code(
+                    code,
+                    None,
+                    current_time,
+                )));
+            }
+        };
+
+        // 재전송된 과거 관측값은 기록만 하고 실시간 버퍼와 집계에는 반영하지 않음
+        if price_data.historical {
+            return Ok(Response::new(
+                self.accept_historical(&price_data, price, current_time)
+                    .await,
+            ));
+        }
+
+        info!(
+            "📊 Received price: ${} from {} ({})",
+            price, price_data.node_id, price_data.source
+        );
+
+        // 가격 데이터 저장 (재전송된 동일 제출은 한 번만 저장)
+        let clamped_from = (price_data.timestamp != submitted_at).then_some(submitted_at);
+        let outcome =
+            self.state
+                .write()
+                .await
+                .insert(&price_data, price, current_time, submitted_at);
+
+        let seq = match outcome {
+            InsertOutcome::Stored { seq } => {
+                // 비활성 노드 정리
+                self.cleanup_inactive_nodes().await;
+
+                // 게시 주기가 없으면 집계 태스크에 스냅샷 갱신 요청
+                if self.publish_interval.is_none() {
+                    self.aggregation_trigger.notify_one();
+                }
+                Some(seq)
+            }
+            InsertOutcome::Duplicate => {
+                info!(
+                    "🔁 Duplicate submission from {} ({}) ignored",
+                    price_data.node_id, price_data.source
+                );
+                None
+            }
+            InsertOutcome::Conflict { existing } => {
+                warn!(
+                    "🚫 Conflicting resubmission from {:?} at {}: ${} != ${}",
+                    price_data.node_id, price_data.timestamp, price, existing
+                );
+                let status = Status::already_exists(
+                    "A different price was already submitted for this node, timestamp and source",
+                );
+                self.metrics.record_rejection(RejectionReason::Conflict);
+                let reason = status.message().to_string();
+                self.record_submission(
+                    &price_data,
+                    clamped_from,
+                    None,
+                    current_time,
+                    WalDecision::Rejected {
+                        reason,
+                        code: Some(RejectionReason::Conflict),
+                    },
+                    (None, 0),
+                )
+                .await;
+                return Err(status);
+            }
+        };
+
+        // 게시 주기가 있으면 마지막 게시 결과, 없으면 즉시 중간값 계산
+        let (median_price, node_count) = if self.publish_interval.is_some() {
+            let snapshot = self.snapshot.load();
+            (snapshot.aggregated_price, snapshot.contributing_nodes)
+        } else {
+            self.calculate_median_price_with(&config).await
+        };
+
+        let code = if node_count < MIN_QUORUM_NODES {
+            ResponseCode::BelowQuorum
+        } else {
+            ResponseCode::Ok
+        };
+        let response = PriceResponse::from_code(code, median_price, current_time);
+
+        let decision = if seq.is_some() {
+            WalDecision::Accepted
+        } else {
+            WalDecision::Duplicate
+        };
+        self.record_submission(
+            &price_data,
+            clamped_from,
+            seq,
+            current_time,
+            decision,
+            (median_price, node_count),
+        )
+        .await;
+
+        if let Some(price) = median_price {
+            info!("💰 Current median price: ${} ({} nodes)", price, node_count);
+        }
+
+        Ok(Response::new(response))
+    }
+
+    // 노드가 보낸 가격은 submit_price와 같은 경로로 처리해 제출마다 결과를 돌려주고,
+    // 게시되는 집계 결과도 계속 전달
+    // 서버 종료 알림을 받으면 스트림을 정상 종료한다 (장애 시에는 연결 오류로 끊김)
+    async fn stream_prices(
+        &self,
+        request: Request<tonic::Streaming<PriceRequest>>,
+    ) -> Result<Response<Self::StreamPricesStream>, Status> {
+        let mut inbound = request.into_inner();
+        let mut updates = self.subscribe();
+        let (sender, receiver) = mpsc::channel(UPDATE_CHANNEL_CAPACITY);
+        let service = self.clone();
+        let subscriber = self.metrics.stream_subscriber();
+
+        tokio::spawn(async move {
+            let _subscriber = subscriber;
+            let mut inbound_open = true;
+            loop {
+                tokio::select! {
+                    message = inbound.message(), if inbound_open => match message {
+                        Ok(Some(price_data)) => {
+                            // 거부된 제출도 처리 결과를 같은 스트림으로 돌려줌
+                            let update = AggregatedPriceUpdate {
+                                submission: Some(service.submit_one(price_data).await),
+                                ..AggregatedPriceUpdate::default()
+                            };
+                            if sender.send(Ok(update)).await.is_err() {
+                                break;
+                            }
+                        }
+                        // 제출을 마친 뒤에도 구독은 유지
+                        Ok(None) => inbound_open = false,
+                        Err(status) => {
+                            debug!("Price stream closed by client: {}", status);
+                            break;
+                        }
+                    },
+                    update = updates.recv() => match update {
+                        Ok(AggregateUpdate::Published(snapshot)) => {
+                            let update = service.price_update(&snapshot);
+                            if sender.send(Ok(update)).await.is_err() {
+                                break;
+                            }
+                        }
+                        Ok(AggregateUpdate::Shutdown) | Err(RecvError::Closed) => break,
+                        Err(RecvError::Lagged(skipped)) => {
+                            warn!("⚠️ Price stream subscriber lagged, skipped {} updates", skipped);
+                        }
+                    },
+                    _ = sender.closed() => break,
+                }
+            }
+        });
+
+        Ok(Response::new(Box::pin(ReceiverStream::new(receiver))))
+    }
+
+    // StreamPrices의 구독 부분만 제공 (grpc-web은 양방향 스트림을 지원하지 않음)
+    async fn subscribe_prices(
+        &self,
+        request: Request<SubscribePricesRequest>,
+    ) -> Result<Response<Self::SubscribePricesStream>, Status> {
+        let client_id = request.into_inner().client_id;
+        debug!("📺 Price subscription from: {}", client_id);
+        let mut updates = self.subscribe();
+        let (sender, receiver) = mpsc::channel(UPDATE_CHANNEL_CAPACITY);
+        let service = self.clone();
+        let subscriber = self.metrics.stream_subscriber();
+
+        tokio::spawn(async move {
+            let _subscriber = subscriber;
+            loop {
+                tokio::select! {
+                    update = updates.recv() => match update {
+                        Ok(AggregateUpdate::Published(snapshot)) => {
+                            let update = service.price_update(&snapshot);
+                            if sender.send(Ok(update)).await.is_err() {
+                                break;
+                            }
+                        }
+                        Ok(AggregateUpdate::Shutdown) | Err(RecvError::Closed) => break,
+                        Err(RecvError::Lagged(skipped)) => {
+                            warn!("⚠️ Price subscriber {} lagged, skipped {} updates", client_id, skipped);
+                        }
+                    },
+                    _ = sender.closed() => break,
+                }
+            }
+        });
+
+        Ok(Response::new(Box::pin(ReceiverStream::new(receiver))))
+    }
+
+    async fn health_check(
+        &self,
+        request: Request<HealthRequest>,
+    ) -> Result<Response<HealthResponse>, Status> {
+        let req = request.into_inner();
+        let snapshot = self.snapshot.load();
+        let now = self.clock.now_secs();
+
+        info!("🏥 Health check from: {}", req.node_id);
+
+        // 헬스체크도 노드 활동으로 기록 (가격 제출이 멈춰도 활성으로 유지, 집계 참여와는 무관)
+        // 허용 목록에 없는 노드는 기록하지 않음 (활성 노드 목록을 채워 허용된 노드를 밀어내지 않도록)
+        let active_nodes = {
+            let mut state = self.state.write().await;
+            if !req.node_id.is_empty() && self.check_allowed(&req.node_id).is_ok() {
+                let node_id = state.intern_node_id(&req.node_id);
+                state.active_nodes.touch(node_id, now);
+            }
+            state.expire(now);
+            state.active_nodes.len()
+        };
+
+        // 소스별 최신 가격 이후 경과 시간 (마지막 게시 시점의 소스 목록 기준)
+        let sources: Vec<SourceHealth> = snapshot
+            .source_last_seen
+            .iter()
+            .map(|(source, last_timestamp)| {
+                let age_secs = now.saturating_sub(*last_timestamp);
+                SourceHealth {
+                    source: source.to_string(),
+                    last_timestamp: *last_timestamp,
+                    age_secs,
+                    healthy: age_secs < SOURCE_STALE_SECS,
+                }
+            })
+            .collect();
+        for source in sources.iter().filter(|source| !source.healthy) {
+            warn!(
+                "⏰ Source {} has not reported for {}s",
+                source.source, source.age_secs
+            );
+        }
+
+        let reason = snapshot.degradation(now);
+        if let Some(reason) = reason {
+            warn!("🏥 Reporting unhealthy: {}", reason);
+        }
+
+        let response = HealthResponse {
+            healthy: reason.is_none(),
+            timestamp: now,
+            active_nodes: active_nodes as u32,
+            version: "1.0.0".to_string(),
+            sources,
+            reason: reason.unwrap_or_default().to_string(),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn register_node(
+        &self,
+        request: Request<RegisterNodeRequest>,
+    ) -> Result<Response<RegisterNodeResponse>, Status> {
+        let req = request.into_inner();
+        let node_id = req.node_id.trim();
+        if node_id.is_empty() {
+            return Err(Status::invalid_argument("node_id is required"));
+        }
+        if self.check_allowed(node_id).is_err() {
+            warn!("🚫 Registration from {:?} refused: not allowed", node_id);
+            return Err(Status::permission_denied(
+                ResponseCode::NodeNotAllowed.message(),
+            ));
+        }
+        let now = self.clock.now_secs();
+
+        // 등록도 노드 활동으로 기록하고, 처리 중에 바뀌지 않는 한 번의 스냅샷으로 설정을 읽음
+        let (config, strategy) = {
+            let mut state = self.state.write().await;
+            let node_id = state.intern_node_id(node_id);
+            state.active_nodes.touch(node_id, now);
+            (state.runtime.clone(), state.strategy.name().to_string())
+        };
+        info!(
+            "🪪 Registered node {} (version {:?}, sources {:?})",
+            node_id, req.version, req.sources
+        );
+
+        Ok(Response::new(RegisterNodeResponse {
+            timestamp: now,
+            version: "1.0.0".to_string(),
+            window_secs: PRICE_WINDOW_SECS,
+            min_quorum_nodes: MIN_QUORUM_NODES as u32,
+            allowed_nodes: self.allowed_nodes.iter().cloned().collect(),
+            aggregation_mode: self.aggregation_mode.name().to_string(),
+            strategy,
+            max_price_age_secs: self.max_price_age_secs,
+            publish_interval_secs: self.publish_interval,
+            max_contribution_age_secs: config.max_contribution_age_secs,
+            skew_clamp_secs: config.skew_clamp_secs,
+            skew_reject_secs: config.skew_reject_secs,
+            max_batch_size: self.max_batch_size as u32,
+            source_weights: config
+                .source_weights
+                .as_map()
+                .iter()
+                .map(|(source, &weight)| (source.clone(), weight))
+                .collect(),
+        }))
+    }
+
+    async fn update_config(
+        &self,
+        request: Request<ConfigRequest>,
+    ) -> Result<Response<ConfigResponse>, Status> {
+        // 거래소 가중치는 모든 노드의 집계에 영향을 주므로 관리자만 변경
+        if !request.get_ref().source_weights.is_empty() {
+            admin::authorize(request.metadata(), self.admin_secret.as_deref())?;
+            let weights = request.into_inner().source_weights;
+            if let Some((source, weight)) = weights
+                .iter()
+                .find(|(_, &weight)| !source_weights::is_valid_weight(weight))
+            {
+                return Err(Status::invalid_argument(format!(
+                    "invalid weight {} for source {}",
+                    weight, source
+                )));
+            }
+
+            {
+                // 새 설정을 만들어 통째로 교체 (처리 중인 요청은 이전 스냅샷을 계속 사용)
+                let mut state = self.state.write().await;
+                let mut runtime = RuntimeConfig::clone(&state.runtime);
+                for (source, &weight) in &weights {
+                    runtime.source_weights.set(source, weight);
+                }
+                info!(
+                    "⚖️ Updated {} source weight(s): {:?}",
+                    weights.len(),
+                    runtime.source_weights.as_map()
+                );
+                state.runtime = Arc::new(runtime);
+            }
+            self.aggregation_trigger.notify_one();
+
+            return Ok(Response::new(ConfigResponse {
+                success: true,
+                message: format!("Updated {} source weight(s)", weights.len()),
+            }));
+        }
+
+        let response = ConfigResponse {
+            success: true,
+            message: "Config update not implemented".to_string(),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn get_aggregated_price(
+        &self,
+        request: Request<GetPriceRequest>,
+    ) -> Result<Response<GetPriceResponse>, Status> {
+        let include_stale = request.into_inner().include_stale;
+        // 락 없이 최신 스냅샷만 사용 (최근 10개 가격 데이터 포함)
+        let snapshot = self.snapshot.load_full();
+
+        // 요청하지 않으면 집계 윈도우 밖의 데이터는 제외
+        let recent_prices: Vec<PriceDataPoint> = snapshot
+            .recent_prices
+            .iter()
+            .filter(|entry| {
+                include_stale
+                    || !aggregation::is_stale(
+                        entry.timestamp,
+                        snapshot.timestamp,
+                        PRICE_WINDOW_SECS,
+                    )
+            })
+            .map(|entry| PriceDataPoint {
+                price: self.price_unit.price(entry.price),
+                ..PriceDataPoint::from(entry.clone())
+            })
+            .collect();
+
+        let aggregated_scaled = snapshot.aggregated_price.map(|price| price.to_scaled());
+        let response = GetPriceResponse {
+            success: true,
+            aggregated_price: snapshot
+                .aggregated_price
+                .map_or(0.0, |price| self.price_unit.price(price)),
+            aggregated_price_scaled: aggregated_scaled.map(|(mantissa, _)| mantissa),
+            aggregated_price_decimals: aggregated_scaled.map(|(_, decimals)| decimals),
+            p25: snapshot.p25.map_or(0.0, |p25| self.price_unit.convert(p25)),
+            p75: snapshot.p75.map_or(0.0, |p75| self.price_unit.convert(p75)),
+            data_points: recent_prices.len() as u32,
+            last_update: snapshot.timestamp,
+            recent_prices,
+            stale: snapshot.stale,
+            price_timestamp: snapshot.aggregated_at,
+            quote_currency: default_quote_currency(),
+            unit: self.price_unit.name().to_string(),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn get_stats(
+        &self,
+        _request: Request<GetStatsRequest>,
+    ) -> Result<Response<GetStatsResponse>, Status> {
+        let state = self.state.read().await;
+        let evictions = state.prices.evictions();
+        let sla = self.sla_report();
+
+        let response = GetStatsResponse {
+            active_nodes: state.active_nodes.len() as u32,
+            max_active_nodes: state.active_nodes.capacity() as u32,
+            evicted_nodes: state.active_nodes.evicted(),
+            stored_prices: state.prices.len() as u32,
+            per_node_quota: state.prices.per_node_quota() as u32,
+            node_usage: state
+                .prices
+                .usage()
+                .into_iter()
+                .map(|(node_id, stored_prices)| NodeUsage {
+                    node_id: node_id.to_string(),
+                    stored_prices: stored_prices as u32,
+                })
+                .collect(),
+            wal_dropped_records: self.wal.as_ref().map_or(0, WalSender::dropped),
+            pair_usage: state
+                .prices
+                .pair_stats()
+                .into_iter()
+                .map(|(pair, stats)| PairUsage {
+                    pair: pair.to_string(),
+                    stored_prices: stats.count as u32,
+                })
+                .collect(),
+            evicted_by_age: evictions.age,
+            evicted_by_count: evictions.count,
+            evicted_by_quota: evictions.quota,
+            oldest_price_timestamp: state.prices.oldest_timestamp(),
+            estimated_bytes: state.prices.estimated_bytes() as u64,
+            sla_fresh_secs: sla.fresh_secs,
+            sla_total_secs: sla.total_secs,
+            sla_rolling_availability: sla.rolling_availability(),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn submit_price_batch(
+        &self,
+        request: Request<PriceBatchRequest>,
+    ) -> Result<Response<PriceBatchResponse>, Status> {
+        let prices = request.into_inner().prices;
+        if prices.len() > self.max_batch_size {
+            warn!(
+                "🚫 Rejected batch of {} prices (max {})",
+                prices.len(),
+                self.max_batch_size
+            );
+            return Err(Status::invalid_argument(format!(
+                "batch of {} prices exceeds the maximum of {}",
+                prices.len(),
+                self.max_batch_size
+            )));
+        }
+
+        let mut results = Vec::with_capacity(prices.len());
+        for price_data in prices {
+            results.push(self.submit_one(price_data).await);
+        }
+
+        Ok(Response::new(PriceBatchResponse { results }))
+    }
+
+    async fn get_price_history(
+        &self,
+        request: Request<PriceHistoryRequest>,
+    ) -> Result<Response<PriceHistoryResponse>, Status> {
+        let request = request.into_inner();
+        let limit = match request.limit as usize {
+            0 => RECENT_PRICES_LIMIT,
+            limit if limit > self.max_history_limit => {
+                return Err(Status::invalid_argument(format!(
+                    "history limit {} exceeds the maximum of {}",
+                    limit, self.max_history_limit
+                )));
+            }
+            limit => limit,
+        };
+        let pair =
+            match request.pair.as_deref() {
+                Some(symbol) => Some(AssetPair::from_symbol(symbol).ok_or_else(|| {
+                    Status::invalid_argument(format!("unknown pair {:?}", symbol))
+                })?),
+                None => None,
+            };
+        let from = request.from_timestamp.unwrap_or(0);
+        let to = request.to_timestamp.unwrap_or(u64::MAX);
+        if from > to {
+            return Err(Status::invalid_argument(format!(
+                "from_timestamp {} is after to_timestamp {}",
+                from, to
+            )));
+        }
+
+        let (mut prices, oldest) = {
+            let state = self.state.read().await;
+            let prices: Vec<PriceDataPoint> = state
+                .prices
+                .entries()
+                .rev()
+                .filter(|entry| {
+                    request
+                        .node_id
+                        .as_deref()
+                        .is_none_or(|node_id| &*entry.node_id == node_id)
+                        && pair
+                            .as_ref()
+                            .is_none_or(|pair| &*entry.pair == pair.as_str())
+                        && (from..=to).contains(&entry.timestamp)
+                })
+                .take(limit)
+                .cloned()
+                .map(PriceDataPoint::from)
+                .collect();
+            (prices, state.prices.oldest_timestamp())
+        };
+
+        // 메모리 버퍼의 가장 오래된 시각보다 이전 구간은 저장소에서 채움 (실패하면 메모리 결과만)
+        let older_to = oldest.map_or(Some(to), |oldest| oldest.checked_sub(1).map(|o| o.min(to)));
+        if let (Some(storage), Some(older_to)) = (&self.storage, older_to) {
+            if prices.len() < limit && from <= older_to {
+                let query = HistoryQuery {
+                    pair: pair.as_ref().map(|pair| pair.as_str().to_string()),
+                    node_id: request.node_id.clone(),
+                    from,
+                    to: older_to,
+                    limit: limit - prices.len(),
+                };
+                match storage.price_history(&query).await {
+                    Ok(older) => prices.extend(older.into_iter().map(PriceDataPoint::from)),
+                    Err(e) => warn!(
+                        "⚠️ Price history from {} is unavailable, returning in-memory results only: {:#}",
+                        storage.describe(),
+                        e
+                    ),
+                }
+            }
+        }
+
+        Ok(Response::new(PriceHistoryResponse { prices }))
+    }
+
+    async fn get_twap(
+        &self,
+        request: Request<GetTwapRequest>,
+    ) -> Result<Response<GetTwapResponse>, Status> {
+        let request = request.into_inner();
+        let storage = self
+            .storage
+            .as_ref()
+            .ok_or_else(|| Status::failed_precondition("TWAP requires postgres_url storage"))?;
+        let pair = match request.pair.as_deref() {
+            Some(symbol) => AssetPair::from_symbol(symbol)
+                .ok_or_else(|| Status::invalid_argument(format!("unknown pair {:?}", symbol)))?
+                .as_str()
+                .to_string(),
+            None => DEFAULT_PAIR.to_string(),
+        };
+        let from = request.from_timestamp;
+        let to = request
+            .to_timestamp
+            .unwrap_or_else(|| self.clock.now_secs());
+        if from > to {
+            return Err(Status::invalid_argument(format!(
+                "from_timestamp {} is after to_timestamp {}",
+                from, to
+            )));
+        }
+
+        // 게시된 집계는 메모리에 남지 않으므로 저장소에서만 계산
+        let twap = storage.twap(&pair, from, to).await.map_err(|e| {
+            warn!(
+                "⚠️ TWAP from {} is unavailable: {:#}",
+                storage.describe(),
+                e
+            );
+            Status::unavailable(format!("{} is unavailable", storage.describe()))
+        })?;
+
+        Ok(Response::new(GetTwapResponse {
+            twap: twap.map(|twap| self.price_unit.convert(twap)),
+            pair,
+            from_timestamp: from,
+            to_timestamp: to,
+            unit: self.price_unit.name().to_string(),
+        }))
+    }
+
+    async fn get_node_history(
+        &self,
+        request: Request<NodeHistoryRequest>,
+    ) -> Result<Response<NodeHistoryResponse>, Status> {
+        let request = request.into_inner();
+        if request.node_id.trim().is_empty() {
+            return Err(Status::invalid_argument("node_id is required"));
+        }
+        let limit = match request.limit as usize {
+            0 => RECENT_PRICES_LIMIT,
+            limit if limit > self.max_history_limit => {
+                return Err(Status::invalid_argument(format!(
+                    "history limit {} exceeds the maximum of {}",
+                    limit, self.max_history_limit
+                )));
+            }
+            limit => limit,
+        };
+
+        let submissions = lock_history(&self.history)
+            .recent(&request.node_id, limit)
+            .map(NodeSubmission::from)
+            .collect();
+
+        Ok(Response::new(NodeHistoryResponse {
+            node_id: request.node_id,
+            submissions,
+        }))
+    }
+
+    async fn get_signed_price(
+        &self,
+        _request: Request<GetSignedPriceRequest>,
+    ) -> Result<Response<GetSignedPriceResponse>, Status> {
+        let signed = self.signed_price()?;
+        GetSignedPriceResponse::try_from(&signed)
+            .map(Response::new)
+            .map_err(|e| Status::internal(format!("failed to encode attestation: {}", e)))
+    }
+
+    async fn get_sla(
+        &self,
+        _request: Request<GetSlaRequest>,
+    ) -> Result<Response<GetSlaResponse>, Status> {
+        let report = self.sla_report();
+
+        Ok(Response::new(GetSlaResponse {
+            fresh_secs: report.fresh_secs,
+            total_secs: report.total_secs,
+            availability: report.availability(),
+            window_secs: report.window_secs,
+            rolling_availability: report.rolling_availability(),
+            target: report.target,
+            below_target: report.below_target,
+            fresh: report.fresh,
+        }))
+    }
+
+    async fn set_node_reputation(
+        &self,
+        request: Request<SetNodeReputationRequest>,
+    ) -> Result<Response<SetNodeReputationResponse>, Status> {
+        admin::authorize(request.metadata(), self.admin_secret.as_deref())?;
+
+        let request = request.into_inner();
+        if request.node_id.is_empty() {
+            return Err(Status::invalid_argument("node_id is required"));
+        }
+        if request.reputation.is_some_and(f64::is_nan) {
+            return Err(Status::invalid_argument("reputation must be a number"));
+        }
+
+        let mut state = self.state.write().await;
+        let (reputation, clamped) = match request.reputation {
+            Some(requested) => {
+                let node_id = state.intern_node_id(&request.node_id);
+                let applied = state.reputation.set(node_id, requested);
+                (applied, applied != requested)
+            }
+            None => (state.reputation.reset(&request.node_id), false),
+        };
+
+        if clamped {
+            warn!(
+                "⚠️ Reputation for {} clamped to {} (requested {:?})",
+                request.node_id, reputation, request.reputation
+            );
+        }
+        info!(
+            "🛠️ Reputation for {} set to {}",
+            request.node_id, reputation
+        );
+
+        Ok(Response::new(SetNodeReputationResponse {
+            node_id: request.node_id,
+            reputation,
+            clamped,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn price_request(price: f64, node_id: &str, source: &str) -> Request<PriceRequest> {
+        Request::new(PriceRequest {
+            price,
+            timestamp: Utc::now().timestamp() as u64,
+            source: source.to_string(),
+            node_id: node_id.to_string(),
+            signature: None,
+            price_scaled: None,
+            price_decimals: None,
+            symbol: None,
+            historical: false,
+            volume: None,
+        })
+    }
+
+    fn scaled_price_request(
+        legacy_price: f64,
+        price_scaled: u64,
+        price_decimals: u32,
+        node_id: &str,
+    ) -> Request<PriceRequest> {
+        let mut request = price_request(legacy_price, node_id, "binance");
+        request.get_mut().price_scaled = Some(price_scaled);
+        request.get_mut().price_decimals = Some(price_decimals);
+        request
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_submission_is_idempotent() {
+        let service = AggregatorServiceImpl::new();
+        let request = price_request(70_000.0, "node-a", "binance");
+        let retry = Request::new(request.get_ref().clone());
+
+        let first = service.submit_price(request).await.unwrap().into_inner();
+        let second = service.submit_price(retry).await.unwrap().into_inner();
+
+        assert!(second.success);
+        assert_eq!(second.code, first.code);
+        assert_eq!(second.aggregated_price, first.aggregated_price);
+
+        let state = service.state.read().await;
+        assert_eq!(state.prices.len(), 1);
+        assert_eq!(state.prices.node_usage("node-a"), 1);
+        assert_eq!(state.prices.stats(DEFAULT_PAIR).unwrap().count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_detection_compares_value_not_encoding() {
+        let service = AggregatorServiceImpl::new();
+        let mut legacy = price_request(70_000.12, "node-a", "binance");
+        let mut scaled = scaled_price_request(0.0, 7_000_012, 2, "node-a");
+        legacy.get_mut().timestamp = 1_700_000_000;
+        scaled.get_mut().timestamp = 1_700_000_000;
+
+        service.submit_price(legacy).await.unwrap();
+        assert!(
+            service
+                .submit_price(scaled)
+                .await
+                .unwrap()
+                .into_inner()
+                .success
+        );
+
+        assert_eq!(service.state.read().await.prices.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_conflicting_resubmission_is_rejected() {
+        let service = AggregatorServiceImpl::new();
+        let request = price_request(70_000.0, "node-a", "binance");
+        let mut conflicting = Request::new(request.get_ref().clone());
+        conflicting.get_mut().price = 70_500.0;
+        // 같은 노드, 같은 시각이어도 다른 source는 별도 관측
+        let mut other_source = Request::new(request.get_ref().clone());
+        other_source.get_mut().source = "coinbase".to_string();
+        other_source.get_mut().price = 70_500.0;
+
+        service.submit_price(request).await.unwrap();
+        let status = service.submit_price(conflicting).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::AlreadyExists);
+
+        service.submit_price(other_source).await.unwrap();
+
+        let state = service.state.read().await;
+        let prices: Vec<f64> = state
+            .prices
+            .entries()
+            .map(|e| e.price.to_f64_dollars())
+            .collect();
+        assert_eq!(prices, vec![70_000.0, 70_500.0]);
+    }
+
+    #[tokio::test]
+    async fn test_silent_node_stops_contributing_before_timeout() {
+        let service = AggregatorServiceImpl::with_config(AggregatorConfig {
+            max_contribution_age_secs: Some(30),
+            ..AggregatorConfig::default()
+        });
+        let now = Utc::now().timestamp() as u64;
+
+        // node-a는 45초 전에 한 번 제출한 뒤 조용함 (가격 윈도우 60초 이내)
+        let mut silent = price_request(90_000.0, "node-a", "binance");
+        silent.get_mut().timestamp = now - 45;
+        service.submit_price(silent).await.unwrap();
+        service
+            .state
+            .write()
+            .await
+            .active_nodes
+            .record_submission(Arc::from("node-a"), now - 45);
+
+        for (node_id, price) in [("node-b", 70_000.0), ("node-c", 71_000.0)] {
+            service
+                .submit_price(price_request(price, node_id, "binance"))
+                .await
+                .unwrap();
+        }
+
+        // 노드는 아직 활성이지만 집계에서는 제외 (포함했다면 중간값은 71_000)
+        assert!(service.state.read().await.active_nodes.contains("node-a"));
+        assert_eq!(
+            service.calculate_median_price().await,
+            (Some(Price::from_cents(7_050_000)), 2)
+        );
+
+        service.publish_snapshot().await;
+        let snapshot = service.snapshot();
+        assert_eq!(snapshot.contributing_nodes, 2);
+        assert_eq!(snapshot.active_nodes, 3);
+    }
+
+    #[tokio::test]
+    async fn test_replay_from_wal_matches_live_state() {
+        let dir = std::env::temp_dir().join(format!("aggregator-wal-{}", uuid::Uuid::new_v4()));
+        let config = AggregatorConfig {
+            max_price_entries: 40,
+            ..AggregatorConfig::default()
+        };
+        let (wal, writer) = wal::spawn_writer(wal::WalConfig {
+            dir: dir.clone(),
+            max_segment_bytes: 4 * 1024,
+            ..wal::WalConfig::default()
+        })
+        .unwrap();
+        let service = AggregatorServiceImpl::with_config(config.clone()).with_wal(wal);
+
+        // 할당량/전체 한도에 의한 제거가 일어나도록 충분히 제출
+        let mut generator =
+            testing::PriceGenerator::new(11, 8).with_now(Utc::now().timestamp() as u64);
+        let requests = generator.requests(200);
+        for request in &requests {
+            let _ = service.submit_price(Request::new(request.clone())).await;
+        }
+        // 재전송과 거부도 함께 기록
+        service
+            .submit_price(Request::new(requests[199].clone()))
+            .await
+            .unwrap();
+        service
+            .submit_price(price_request(-1.0, "node-bad", "binance"))
+            .await
+            .unwrap();
+
+        let live = service.calculate_median_price().await;
+        let node_history = |service: &AggregatorServiceImpl, node_id: &str| {
+            lock_history(&service.history)
+                .recent(node_id, usize::MAX)
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+        let live_history = node_history(&service, &requests[0].node_id);
+        let live_rejected = node_history(&service, "node-bad");
+        let live_entries: Vec<(String, u64, Price)> = service
+            .state
+            .read()
+            .await
+            .prices
+            .entries()
+            .map(|e| (e.node_id.to_string(), e.timestamp, e.price))
+            .collect();
+        drop(service);
+        writer.await.unwrap().unwrap();
+
+        let records = wal::read_records(&dir).unwrap();
+        assert!(wal::segment_paths(&dir).unwrap().len() > 1);
+        assert_eq!(records.len(), 202);
+        assert_eq!(records[200].decision, WalDecision::Duplicate);
+        assert_eq!(records[200].request, WalRequest::from(&requests[199]));
+        assert!(matches!(
+            records[201].decision,
+            WalDecision::Rejected { .. }
+        ));
+        assert_eq!(records[199].aggregate, records[200].aggregate);
+
+        let replayed = AggregatorServiceImpl::replay(config, records);
+        let replayed_entries: Vec<(String, u64, Price)> = replayed
+            .state
+            .read()
+            .await
+            .prices
+            .entries()
+            .map(|e| (e.node_id.to_string(), e.timestamp, e.price))
+            .collect();
+        assert_eq!(replayed_entries, live_entries);
+        assert_eq!(replayed.calculate_median_price().await, live);
+        // 노드별 제출 기록도 거부된 제출까지 그대로 복구
+        assert_eq!(node_history(&replayed, &requests[0].node_id), live_history);
+        assert_eq!(node_history(&replayed, "node-bad"), live_rejected);
+        assert_eq!(live_rejected.len(), 1);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_historical_submission_is_recorded_but_not_aggregated() {
+        let dir = std::env::temp_dir().join(format!("aggregator-wal-{}", uuid::Uuid::new_v4()));
+        let (wal, writer) = wal::spawn_writer(wal::WalConfig {
+            dir: dir.clone(),
+            ..wal::WalConfig::default()
+        })
+        .unwrap();
+        let service = AggregatorServiceImpl::new().with_wal(wal);
+
+        service
+            .submit_price(price_request(70_000.0, "node-a", "binance"))
+            .await
+            .unwrap();
+        service.publish_snapshot().await;
+
+        // 10분 전 관측값을 연결 복구 후 재전송
+        let mut historical = price_request(90_000.0, "node-b", "binance");
+        historical.get_mut().timestamp -= 600;
+        historical.get_mut().historical = true;
+        let response = service.submit_price(historical).await.unwrap().into_inner();
+        assert!(response.success);
+        assert_eq!(response.aggregated_price, Some(70_000.0));
+
+        let state = service.state.read().await;
+        assert_eq!(state.prices.len(), 1);
+        assert_eq!(state.prices.node_usage("node-b"), 0);
+        assert_eq!(state.active_nodes.len(), 1);
+        drop(state);
+        assert_eq!(
+            service.calculate_median_price().await,
+            (Some(Price::from_cents(7_000_000)), 1)
+        );
+
+        drop(service);
+        writer.await.unwrap().unwrap();
+        let records = wal::read_records(&dir).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].decision, WalDecision::Historical);
+        assert!(records[1].request.historical);
+        assert_eq!(records[1].seq, None);
+
+        // 복구 시에도 실시간 상태에 반영하지 않음
+        let replayed = AggregatorServiceImpl::replay(AggregatorConfig::default(), records);
+        assert_eq!(replayed.state.read().await.prices.len(), 1);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    fn symbol_request(price: f64, node_id: &str, symbol: Option<&str>) -> Request<PriceRequest> {
+        let mut request = price_request(price, node_id, "binance");
+        request.get_mut().symbol = symbol.map(str::to_string);
+        request
+    }
+
+    #[tokio::test]
+    async fn test_submit_price_infers_pair_from_symbol() {
+        let service = AggregatorServiceImpl::new();
+
+        for (node_id, symbol) in [("node-a", "BTCUSDT"), ("node-b", "XXBTZUSD")] {
+            service
+                .submit_price(symbol_request(70_000.0, node_id, Some(symbol)))
+                .await
+                .unwrap();
+        }
+        service
+            .submit_price(symbol_request(3_500.0, "node-c", Some("ETH-USD")))
+            .await
+            .unwrap();
+
+        let state = service.state.read().await;
+        let pairs: Vec<&str> = state.prices.entries().map(|e| &*e.pair).collect();
+        assert_eq!(pairs, vec!["BTC/USD", "BTC/USD", "ETH/USD"]);
+        let entries: Vec<_> = state.prices.entries().collect();
+        assert!(Arc::ptr_eq(&entries[0].pair, &entries[1].pair));
+        drop(state);
+
+        // 다른 pair의 가격은 BTC/USD 집계에 섞이지 않음
+        assert_eq!(
+            service.calculate_median_price().await,
+            (Some(Price::from_cents(7_000_000)), 2)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_submit_price_falls_back_to_default_pair() {
+        let service = AggregatorServiceImpl::new();
+
+        service
+            .submit_price(symbol_request(70_000.0, "node-a", None))
+            .await
+            .unwrap();
+        service
+            .submit_price(symbol_request(70_100.0, "node-b", Some("not a symbol")))
+            .await
+            .unwrap();
+
+        let state = service.state.read().await;
+        assert!(state.prices.entries().all(|e| &*e.pair == DEFAULT_PAIR));
+        assert_eq!(state.prices.stats(DEFAULT_PAIR).unwrap().count, 2);
+    }
+
+    fn timed_request(price: f64, node_id: &str, timestamp: u64) -> Request<PriceRequest> {
+        let mut request = price_request(price, node_id, "binance");
+        request.get_mut().timestamp = timestamp;
+        request
+    }
+
+    // 다음으로 게시된 집계 결과 (종료 알림이면 실패)
+    async fn next_snapshot(
+        updates: &mut broadcast::Receiver<AggregateUpdate>,
+    ) -> Arc<AggregateSnapshot> {
+        match updates.recv().await.unwrap() {
+            AggregateUpdate::Published(snapshot) => snapshot,
+            AggregateUpdate::Shutdown => panic!("unexpected shutdown"),
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_cadence_publishes_on_wall_clock_boundaries() {
+        let clock = Arc::new(testing::TokioClock::starting_at(1_700_000_003_250));
+        let service = AggregatorServiceImpl::with_config(AggregatorConfig {
+            publish_interval_secs: Some(10),
+            ..AggregatorConfig::default()
+        })
+        .with_clock(clock.clone());
+        let mut updates = service.subscribe();
+
+        for node_id in ["node-a", "node-b", "node-c"] {
+            let response = service
+                .submit_price(timed_request(70_000.0, node_id, 1_700_000_002))
+                .await
+                .unwrap()
+                .into_inner();
+            // 아직 게시된 결과가 없음
+            assert_eq!(response.aggregated_price, None);
+        }
+        let task = service.spawn_aggregation_task();
+
+        let mut published = Vec::new();
+        for _ in 0..3 {
+            let update = next_snapshot(&mut updates).await;
+            assert_eq!(clock.now_millis() % 10_000, 0);
+            published.push(update.timestamp);
+        }
+        assert_eq!(published, vec![1_700_000_010, 1_700_000_020, 1_700_000_030]);
+        assert_eq!(
+            service.snapshot().aggregated_price,
+            Some(Price::from_cents(7_000_000))
+        );
+
+        task.abort();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_cadence_freezes_window_until_next_tick() {
+        let clock = Arc::new(testing::TokioClock::starting_at(1_700_000_003_000));
+        let service = AggregatorServiceImpl::with_config(AggregatorConfig {
+            publish_interval_secs: Some(10),
+            ..AggregatorConfig::default()
+        })
+        .with_clock(clock.clone());
+        let mut updates = service.subscribe();
+
+        for node_id in ["node-a", "node-b", "node-c"] {
+            service
+                .submit_price(timed_request(70_000.0, node_id, 1_700_000_002))
+                .await
+                .unwrap();
+        }
+        let task = service.spawn_aggregation_task();
+        let first = next_snapshot(&mut updates).await;
+        assert_eq!(first.timestamp, 1_700_000_010);
+        assert_eq!(first.aggregated_price, Some(Price::from_cents(7_000_000)));
+
+        // 경계 직후의 제출은 응답에도 현재 게시 결과에도 반영되지 않음
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        for node_id in ["node-d", "node-e", "node-f", "node-g"] {
+            let response = service
+                .submit_price(timed_request(80_000.0, node_id, 1_700_000_012))
+                .await
+                .unwrap()
+                .into_inner();
+            assert_eq!(response.aggregated_price, Some(70_000.0));
+            assert_eq!(response.code(), ResponseCode::Ok);
+        }
+        assert_eq!(service.snapshot().timestamp, 1_700_000_010);
+
+        let second = next_snapshot(&mut updates).await;
+        assert_eq!(second.timestamp, 1_700_000_020);
+        assert_eq!(second.aggregated_price, Some(Price::from_cents(8_000_000)));
+        assert_eq!(second.contributing_nodes, 7);
+        task.abort();
+
+        // 경계 이후 타임스탬프의 가격은 경계 시점 윈도우에 포함되지 않음
+        service
+            .submit_price(timed_request(90_000.0, "node-h", 1_700_000_031))
+            .await
+            .unwrap();
+        service.publisher().publish(1_700_000_030).await;
+        assert_eq!(service.snapshot().contributing_nodes, 7);
+    }
+
+    async fn recent_prices(
+        service: &AggregatorServiceImpl,
+        include_stale: bool,
+    ) -> GetPriceResponse {
+        service
+            .get_aggregated_price(Request::new(GetPriceRequest {
+                source_filter: None,
+                include_stale,
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+    }
+
+    #[tokio::test]
+    async fn test_price_responses_label_configured_unit() {
+        let dollars = AggregatorServiceImpl::new();
+        let cents = AggregatorServiceImpl::with_config(
+            AggregatorConfig::default().with_price_unit(PriceUnit::Cents),
+        );
+        for service in [&dollars, &cents] {
+            for (price, node_id) in [(70_000.25, "node-a"), (70_000.75, "node-b")] {
+                service
+                    .submit_price(price_request(price, node_id, "binance"))
+                    .await
+                    .unwrap();
+            }
+            service.publish_snapshot().await;
+        }
+
+        let response = recent_prices(&dollars, false).await;
+        assert_eq!(response.quote_currency, "USD");
+        assert_eq!(response.unit, "dollars");
+        assert_eq!(response.aggregated_price, 70_000.5);
+
+        let response = recent_prices(&cents, false).await;
+        assert_eq!(response.quote_currency, "USD");
+        assert_eq!(response.unit, "cents");
+        assert_eq!(response.aggregated_price, 7_000_050.0);
+        assert!(response.p25 > 7_000_000.0 && response.p75 < 7_000_100.0);
+        let mut points: Vec<f64> = response.recent_prices.iter().map(|p| p.price).collect();
+        points.sort_by(f64::total_cmp);
+        assert_eq!(points, vec![7_000_025.0, 7_000_075.0]);
+        // 고정소수점 필드는 단위 설정과 무관하게 자체 자릿수를 가짐
+        let scaled = Price::from_scaled(
+            response.aggregated_price_scaled.unwrap(),
+            response.aggregated_price_decimals.unwrap(),
+        );
+        assert_eq!(scaled.unwrap().to_f64_dollars(), 70_000.5);
+
+        let update = cents.price_update(&cents.snapshot());
+        assert_eq!(update.pair, "BTC/USD");
+        assert_eq!(update.quote_currency, "USD");
+        assert_eq!(update.unit, "cents");
+        assert_eq!(update.aggregated_price, 7_000_050.0);
+        // 활성 노드 목록도 스냅샷에서 읽으므로 상태 락을 잡지 않음
+        assert_eq!(update.active_nodes, ["node-a", "node-b"]);
+        let _state = cents.state.write().await;
+        assert_eq!(
+            cents.price_update(&cents.snapshot()).active_nodes,
+            ["node-a", "node-b"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prices_age_out_of_window_as_clock_advances() {
+        let clock = Arc::new(testing::ManualClock::starting_at(1_700_000_000_000));
+        let service = AggregatorServiceImpl::with_config(AggregatorConfig {
+            max_price_age_secs: 3_600,
+            ..AggregatorConfig::default()
+        })
+        .with_clock(clock.clone());
+
+        for node_id in ["node-a", "node-b", "node-c"] {
+            service
+                .submit_price(timed_request(70_000.0, node_id, 1_700_000_000))
+                .await
+                .unwrap();
+        }
+        assert_eq!(
+            service.calculate_median_price().await,
+            (Some(Price::from_cents(7_000_000)), 3)
+        );
+
+        // 윈도우 끝 직전까지는 그대로 집계
+        clock.advance(Duration::from_secs(PRICE_WINDOW_SECS - 1));
+        assert_eq!(service.calculate_median_price().await.1, 3);
+
+        // 윈도우를 벗어나면 집계에서 빠지지만 노드는 아직 활성
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(service.calculate_median_price().await, (None, 0));
+        service.cleanup_inactive_nodes().await;
+        assert_eq!(service.state.read().await.active_nodes.len(), 3);
+
+        // 타임아웃이 지나면 비활성 노드 정리
+        clock.set_millis((1_700_000_000 + NODE_TIMEOUT_SECS + 1) * 1_000);
+        service.cleanup_inactive_nodes().await;
+        assert_eq!(service.state.read().await.active_nodes.len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_sla_tracks_fresh_and_stale_periods() {
+        let start = 1_700_000_000;
+        let clock = Arc::new(testing::ManualClock::starting_at(start * 1_000));
+        let config = AggregatorConfig::default()
+            .with_sla_window_secs(100)
+            .with_sla_target(0.9);
+        let service = AggregatorServiceImpl::with_config(config).with_clock(clock.clone());
+
+        // 처음 30초는 가격이 없음
+        service.publish_snapshot().await;
+        clock.advance(Duration::from_secs(30));
+        for node_id in ["node-a", "node-b", "node-c"] {
+            service
+                .submit_price(timed_request(70_000.0, node_id, start + 30))
+                .await
+                .unwrap();
+        }
+        service.publish_snapshot().await;
+        assert!(service.sla_report().fresh);
+
+        // 가격이 윈도우를 벗어날 때까지 60초 동안 신선
+        clock.advance(Duration::from_secs(PRICE_WINDOW_SECS));
+        service.publish_snapshot().await;
+        clock.advance(Duration::from_secs(30));
+
+        let sla = service
+            .get_sla(Request::new(GetSlaRequest {}))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!((sla.fresh_secs, sla.total_secs), (60, 120));
+        assert_eq!(sla.availability, Some(0.5));
+        assert_eq!(sla.rolling_availability, Some(0.6));
+        assert_eq!(sla.target, Some(0.9));
+        assert!(!sla.fresh);
+        // 목표 판정은 게시할 때
+        assert!(!sla.below_target);
+
+        service.publish_snapshot().await;
+        assert!(service.sla_report().below_target);
+
+        let stats = service
+            .get_stats(Request::new(GetStatsRequest {}))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!((stats.sla_fresh_secs, stats.sla_total_secs), (60, 120));
+        assert_eq!(stats.sla_rolling_availability, Some(0.6));
+    }
+
+    #[tokio::test]
+    async fn test_last_median_is_served_as_stale_within_grace() {
+        let start = 1_700_000_000;
+        let clock = Arc::new(testing::ManualClock::starting_at(start * 1_000));
+        let service = AggregatorServiceImpl::with_config(
+            AggregatorConfig::default().with_stale_grace_secs(90),
+        )
+        .with_clock(clock.clone());
+        let mut updates = service.subscribe();
+
+        for node_id in ["node-a", "node-b", "node-c"] {
+            service
+                .submit_price(timed_request(70_000.0, node_id, start))
+                .await
+                .unwrap();
+        }
+        service.publish_snapshot().await;
+        let response = recent_prices(&service, false).await;
+        assert_eq!(response.aggregated_price, 70_000.0);
+        assert!(!response.stale);
+        assert_eq!(response.price_timestamp, start);
+        assert!(!next_snapshot(&mut updates).await.stale);
+
+        // 모든 가격이 윈도우를 벗어나도 유예 시간 동안은 마지막 중간값을 stale로 게시
+        clock.advance(Duration::from_secs(PRICE_WINDOW_SECS + 1));
+        service.publish_snapshot().await;
+        let response = recent_prices(&service, false).await;
+        assert!(response.stale);
+        assert_eq!(response.aggregated_price, 70_000.0);
+        assert!(response.aggregated_price_scaled.is_some());
+        assert_eq!(response.price_timestamp, start);
+        assert_eq!(response.last_update, start + PRICE_WINDOW_SECS + 1);
+        let snapshot = next_snapshot(&mut updates).await;
+        assert_eq!(snapshot.contributing_nodes, 0);
+        assert!(!snapshot.meets_quorum());
+        assert!(service.price_update(&snapshot).stale);
+        assert!(!service.sla_report().fresh);
+
+        // 유예 시간은 마지막 유효 집계 시각부터 계산
+        clock.set_millis((start + 90) * 1_000);
+        service.publish_snapshot().await;
+        assert!(recent_prices(&service, false).await.stale);
+
+        clock.set_millis((start + 91) * 1_000);
+        service.publish_snapshot().await;
+        let response = recent_prices(&service, false).await;
+        assert!(!response.stale);
+        assert_eq!(response.aggregated_price, 0.0);
+        assert_eq!(response.aggregated_price_scaled, None);
+        assert_eq!(service.snapshot().aggregated_price, None);
+
+        // 새 가격이 들어오면 다시 신선한 중간값
+        service
+            .submit_price(timed_request(71_000.0, "node-a", start + 91))
+            .await
+            .unwrap();
+        service.publish_snapshot().await;
+        let response = recent_prices(&service, false).await;
+        assert!(!response.stale);
+        assert_eq!(response.aggregated_price, 71_000.0);
+        assert_eq!(response.price_timestamp, start + 91);
+    }
+
+    #[tokio::test]
+    async fn test_empty_window_has_no_price_without_grace() {
+        let start = 1_700_000_000;
+        let clock = Arc::new(testing::ManualClock::starting_at(start * 1_000));
+        let service = AggregatorServiceImpl::new().with_clock(clock.clone());
+
+        service
+            .submit_price(timed_request(70_000.0, "node-a", start))
+            .await
+            .unwrap();
+        service.publish_snapshot().await;
+        assert_eq!(
+            service.snapshot().aggregated_price,
+            Some(Price::from_cents(7_000_000))
+        );
+
+        clock.advance(Duration::from_secs(PRICE_WINDOW_SECS + 1));
+        service.publish_snapshot().await;
+        let snapshot = service.snapshot();
+        assert_eq!(snapshot.aggregated_price, None);
+        assert!(!snapshot.stale);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_expired_entries_are_pruned_from_aggregate_and_history() {
+        let clock = Arc::new(testing::TokioClock::starting_at(1_700_000_000_000));
+        let service = AggregatorServiceImpl::new().with_clock(clock.clone());
+
+        for node_id in ["node-a", "node-b", "node-c"] {
+            service
+                .submit_price(timed_request(70_000.0, node_id, 1_700_000_000))
+                .await
+                .unwrap();
+        }
+        service.publish_snapshot().await;
+        assert_eq!(recent_prices(&service, false).await.data_points, 3);
+
+        // 40분 동안 제출이 없다가 집계 태스크가 정리
+        tokio::time::advance(Duration::from_secs(2_400)).await;
+        service.publish_snapshot().await;
+
+        let snapshot = service.snapshot();
+        assert_eq!(snapshot.aggregated_price, None);
+        assert_eq!(snapshot.stored_prices, 0);
+        let response = recent_prices(&service, true).await;
+        assert_eq!(response.aggregated_price, 0.0);
+        assert!(response.recent_prices.is_empty());
+
+        // 저장 시에도 만료 데이터가 먼저 제거됨
+        service
+            .submit_price(timed_request(71_000.0, "node-a", 1_700_002_400))
+            .await
+            .unwrap();
+        tokio::time::advance(Duration::from_secs(60)).await;
+        service
+            .submit_price(timed_request(72_000.0, "node-b", 1_700_002_460))
+            .await
+            .unwrap();
+        let state = service.state.read().await;
+        assert_eq!(state.prices.len(), 1);
+        assert_eq!(state.prices.node_usage("node-a"), 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_stale_entries_are_hidden_unless_requested() {
+        let clock = Arc::new(testing::TokioClock::starting_at(1_700_000_000_000));
+        let service = AggregatorServiceImpl::with_config(AggregatorConfig {
+            max_price_age_secs: 3_600,
+            ..AggregatorConfig::default()
+        })
+        .with_clock(clock.clone());
+
+        for node_id in ["node-a", "node-b", "node-c"] {
+            service
+                .submit_price(timed_request(70_000.0, node_id, 1_700_000_000))
+                .await
+                .unwrap();
+        }
+        tokio::time::advance(Duration::from_secs(120)).await;
+        service.publish_snapshot().await;
+
+        // 보관은 되어 있지만 집계 윈도우 밖
+        let snapshot = service.snapshot();
+        assert_eq!(snapshot.stored_prices, 3);
+        assert_eq!(snapshot.aggregated_price, None);
+
+        let fresh = recent_prices(&service, false).await;
+        assert_eq!(fresh.data_points, 0);
+        assert!(fresh.recent_prices.is_empty());
+
+        let all = recent_prices(&service, true).await;
+        assert_eq!(all.data_points, 3);
+        assert!(all
+            .recent_prices
+            .iter()
+            .all(|point| point.timestamp == 1_700_000_000));
+    }
+
+    #[tokio::test]
+    async fn test_one_vote_per_node_uses_latest_submission() {
+        let service = AggregatorServiceImpl::with_config(AggregatorConfig {
+            one_vote_per_node: true,
+            ..AggregatorConfig::default()
+        });
+
+        // node-a가 여러 source로 높은 가격을 반복 제출한 뒤 최신 가격으로 갱신
+        for source in ["binance", "coinbase", "kraken"] {
+            let mut earlier = price_request(90_000.0, "node-a", source);
+            earlier.get_mut().timestamp -= 1;
+            service.submit_price(earlier).await.unwrap();
+        }
+        service
+            .submit_price(price_request(70_050.0, "node-a", "binance"))
+            .await
+            .unwrap();
+
+        service
+            .submit_price(price_request(70_000.0, "node-b", "binance"))
+            .await
+            .unwrap();
+        let response = service
+            .submit_price(price_request(70_100.0, "node-c", "binance"))
+            .await
+            .unwrap()
+            .into_inner();
+
+        // 노드당 한 표: [70_050, 70_000, 70_100]
+        assert_eq!(response.aggregated_price, Some(70_050.0));
+
+        // 플래그가 없으면 node-a의 90_000 세 건이 중간값을 끌어올림
+        let state = service.state.read().await;
+        let now = Utc::now().timestamp() as u64;
+        assert_eq!(
+            median_price(
+                &state.prices.entries().cloned().collect::<Vec<_>>(),
+                now,
+                PRICE_WINDOW_SECS
+            ),
+            Some(80_050.0)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_coalesce_window_collapses_burst_from_one_node() {
+        let start = 1_700_000_000;
+        let aggregate = |config: AggregatorConfig| async move {
+            let clock = Arc::new(testing::ManualClock::starting_at(start * 1_000));
+            let service = AggregatorServiceImpl::with_config(config).with_clock(clock);
+            for node_id in ["node-b", "node-c", "node-d"] {
+                service
+                    .submit_price(timed_request(70_000.0, node_id, start))
+                    .await
+                    .unwrap();
+            }
+            // 다운타임 전의 제출 하나와 회복 직후 몰아서 보낸 제출
+            service
+                .submit_price(timed_request(70_000.0, "node-a", start - 20))
+                .await
+                .unwrap();
+            for offset in (0..5).rev() {
+                service
+                    .submit_price(timed_request(75_000.0, "node-a", start - offset))
+                    .await
+                    .unwrap();
+            }
+            service.publish_snapshot().await;
+
+            let state = service.state.read().await;
+            let contributions = state.contributing_entries(start, &state.runtime).len();
+            (service.snapshot().aggregated_price, contributions)
+        };
+
+        // 합치지 않으면 node-a의 75_000 다섯 건이 중간값을 끌어올림
+        assert_eq!(
+            aggregate(AggregatorConfig::default()).await,
+            (Some(Price::from_cents(7_500_000)), 9)
+        );
+
+        // 5초 안의 다섯 건은 마지막 하나로 합쳐지고 20초 전 제출은 남음: [70_000 × 4, 75_000]
+        assert_eq!(
+            aggregate(AggregatorConfig::default().with_coalesce_window_secs(5)).await,
+            (Some(Price::from_cents(7_000_000)), 5)
+        );
+    }
+
+    // 가장 높은 가격을 집계 가격으로 쓰는 테스트용 집계 방식
+    #[derive(Debug)]
+    struct HighestPrice;
+
+    impl AggregationStrategy for HighestPrice {
+        fn name(&self) -> &str {
+            "highest"
+        }
+
+        fn aggregate(&self, entries: &[&PriceEntry], _now: u64) -> Option<AggregationResult> {
+            entries
+                .iter()
+                .map(|entry| entry.price)
+                .max()
+                .map(|price| AggregationResult {
+                    price,
+                    data_points: entries.len(),
+                })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_custom_strategy_is_used_for_aggregation() {
+        let service = AggregatorServiceImpl::with_config(AggregatorConfig {
+            strategy: Some(Arc::new(HighestPrice)),
+            ..AggregatorConfig::default()
+        });
+
+        for (price, node_id) in [
+            (70_000.0, "node-a"),
+            (70_300.0, "node-b"),
+            (70_100.0, "node-c"),
+        ] {
+            service
+                .submit_price(price_request(price, node_id, "binance"))
+                .await
+                .unwrap();
+        }
+
+        service.publish_snapshot().await;
+        assert_eq!(
+            service.snapshot().aggregated_price,
+            Some(Price::from_cents(7_030_000))
+        );
+        assert_eq!(
+            service.calculate_median_price().await,
+            (Some(Price::from_cents(7_030_000)), 3)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_strategy_runs_only_with_min_points() {
+        let service = AggregatorServiceImpl::with_config(
+            AggregatorConfig::default()
+                .with_strategy(Arc::new(HighestPrice))
+                .with_min_points(3),
+        );
+
+        for (price, node_id) in [(70_000.0, "node-a"), (70_300.0, "node-b")] {
+            service
+                .submit_price(price_request(price, node_id, "binance"))
+                .await
+                .unwrap();
+        }
+        // 2개: 하한 미만이라 중간값
+        assert_eq!(
+            service.calculate_median_price().await,
+            (Some(Price::from_cents(7_015_000)), 2)
+        );
+
+        service
+            .submit_price(price_request(70_100.0, "node-c", "binance"))
+            .await
+            .unwrap();
+        assert_eq!(
+            service.calculate_median_price().await,
+            (Some(Price::from_cents(7_030_000)), 3)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_submit_price_prefers_scaled_integer_price() {
+        let service = AggregatorServiceImpl::new();
+
+        // 고정소수점 필드가 있으면 기존 f64 필드는 무시
+        let response = service
+            .submit_price(scaled_price_request(1.0, 7_000_012, 2, "node-a"))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(response.success);
+        assert_eq!(response.aggregated_price, Some(70_000.12));
+
+        // 자릿수가 달라도 같은 가격으로 해석
+        service
+            .submit_price(scaled_price_request(0.0, 70_000_120_000, 6, "node-b"))
+            .await
+            .unwrap();
+
+        let state = service.state.read().await;
+        let prices: Vec<f64> = state
+            .prices
+            .entries()
+            .map(|e| e.price.to_f64_dollars())
+            .collect();
+        assert_eq!(prices, vec![70_000.12, 70_000.12]);
+    }
+
+    #[tokio::test]
+    async fn test_submit_price_legacy_f64_path() {
+        let service = AggregatorServiceImpl::new();
+
+        // 고정소수점 필드가 없는 기존 노드
+        let response = service
+            .submit_price(price_request(70_000.5, "node-a", "binance"))
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert!(response.success);
+        assert_eq!(response.aggregated_price, Some(70_000.5));
+    }
+
+    #[tokio::test]
+    async fn test_submit_price_rejects_invalid_scaled_price() {
+        let service = AggregatorServiceImpl::new();
+
+        let mut missing_decimals = price_request(70_000.0, "node-a", "binance");
+        missing_decimals.get_mut().price_scaled = Some(7_000_000);
+
+        for request in [
+            scaled_price_request(70_000.0, 0, 2, "node-a"),
+            scaled_price_request(70_000.0, 7_000_000, Price::MAX_DECIMALS + 1, "node-a"),
+            missing_decimals,
+        ] {
+            let response = service.submit_price(request).await.unwrap().into_inner();
+            assert_eq!(response.code(), ResponseCode::InvalidPrice);
+        }
+
+        assert!(service.state.read().await.prices.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_submit_price_rejects_prices_above_the_bound() {
+        let service = AggregatorServiceImpl::new();
+
+        // 하나당 약 1.8e37 (18자리 기준)이라 스무 개 남짓이면 u128 합계가 넘침
+        for i in 0..40 {
+            let node_id = format!("node-{}", i);
+            for (scaled, decimals) in [
+                (u64::MAX, 0),
+                (MAX_PRICE_DOLLARS + 1, 0),
+                (MAX_PRICE_DOLLARS * 100 + 1, 2),
+            ] {
+                let request = scaled_price_request(0.0, scaled, decimals, &node_id);
+                let response = service.submit_price(request).await.unwrap().into_inner();
+                assert_eq!(response.code(), ResponseCode::InvalidPrice);
+            }
+        }
+        assert!(service.state.read().await.prices.is_empty());
+
+        // 한도 이하는 그대로 받고 평균도 맞음
+        for i in 0..40 {
+            let node_id = format!("node-{}", i);
+            let request = scaled_price_request(0.0, MAX_PRICE_DOLLARS * 100, 2, &node_id);
+            let response = service.submit_price(request).await.unwrap().into_inner();
+            assert_ne!(response.code(), ResponseCode::InvalidPrice);
+        }
+        let stats = service
+            .state
+            .read()
+            .await
+            .prices
+            .stats(DEFAULT_PAIR)
+            .unwrap();
+        assert_eq!(stats.count, 40);
+        assert_eq!(stats.mean, Some(MAX_PRICE_DOLLARS as f64));
+        // 자릿수가 크면 u64 가격은 모두 한도 이하
+        let tiny = Price::from_scaled(u64::MAX, Price::MAX_DECIMALS).unwrap();
+        assert!(!exceeds_max_price(tiny));
+    }
+
+    #[tokio::test]
+    async fn test_submit_price_interns_node_id_and_source() {
+        let service = AggregatorServiceImpl::new();
+
+        let first = price_request(70_000.0, "node-a", "binance");
+        let mut second = price_request(70_100.0, "node-a", "binance");
+        second.get_mut().timestamp = first.get_ref().timestamp - 1;
+
+        service.submit_price(first).await.unwrap();
+        service.submit_price(second).await.unwrap();
+
+        let state = service.state.read().await;
+        assert_eq!(state.prices.len(), 2);
+        let entries: Vec<_> = state.prices.entries().collect();
+        assert!(Arc::ptr_eq(&entries[0].node_id, &entries[1].node_id));
+        assert!(Arc::ptr_eq(&entries[0].source, &entries[1].source));
+    }
+
+    #[tokio::test]
+    async fn test_submit_price_returns_ok_code_with_quorum() {
+        let service = AggregatorServiceImpl::new();
+
+        for node_id in ["node-a", "node-b"] {
+            service
+                .submit_price(price_request(70_000.0, node_id, "binance"))
+                .await
+                .unwrap();
+        }
+        let response = service
+            .submit_price(price_request(70_100.0, "node-c", "binance"))
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert!(response.success);
+        assert_eq!(response.code(), ResponseCode::Ok);
+        assert_eq!(response.message, ResponseCode::Ok.message());
+        assert_eq!(response.aggregated_price, Some(70_000.0));
+    }
+
+    #[tokio::test]
+    async fn test_submit_price_returns_below_quorum_code() {
+        let service = AggregatorServiceImpl::new();
+
+        let response = service
+            .submit_price(price_request(70_000.0, "node-a", "binance"))
+            .await
+            .unwrap()
+            .into_inner();
+
+        // 저장은 되지만 정족수 미만임을 코드로 알림
+        assert!(response.success);
+        assert_eq!(response.code(), ResponseCode::BelowQuorum);
+        assert_eq!(response.aggregated_price, Some(70_000.0));
+    }
+
+    #[tokio::test]
+    async fn test_submit_price_returns_rejection_codes() {
+        let service = AggregatorServiceImpl::new();
+
+        for price in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            let response = service
+                .submit_price(price_request(price, "node-a", "binance"))
+                .await
+                .unwrap()
+                .into_inner();
+
+            assert!(!response.success);
+            assert_eq!(response.code(), ResponseCode::InvalidPrice);
+            assert_eq!(response.aggregated_price, None);
+        }
+
+        let response = service
+            .submit_price(price_request(70_000.0, " ", "binance"))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(!response.success);
+        assert_eq!(response.code(), ResponseCode::MissingNodeId);
+
+        // 거부된 요청은 저장되지 않음
+        assert!(service.state.read().await.prices.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_submit_price_rejects_millisecond_timestamps() {
+        let clock = Arc::new(testing::ManualClock::starting_at(1_700_000_000_000));
+        let service = AggregatorServiceImpl::new().with_clock(clock);
+
+        // 초 단위 타임스탬프는 그대로 받음
+        let mut seconds = price_request(70_000.0, "node-a", "binance");
+        seconds.get_mut().timestamp = 1_700_000_000;
+        let response = service.submit_price(seconds).await.unwrap().into_inner();
+        assert!(response.success);
+
+        // 바이낸스 원본 K-line처럼 밀리초로 보낸 타임스탬프는 거부
+        let mut millis = price_request(70_000.0, "node-b", "binance");
+        millis.get_mut().timestamp = 1_700_000_000_000;
+        let response = service.submit_price(millis).await.unwrap().into_inner();
+        assert!(!response.success);
+        assert_eq!(response.code(), ResponseCode::InvalidTimestamp);
+        assert!(response.message.contains("milliseconds"));
+
+        let state = service.state.read().await;
+        assert_eq!(state.prices.len(), 1);
+        assert!(!state.active_nodes.contains("node-b"));
+    }
+
+    #[tokio::test]
+    async fn test_register_node_returns_effective_config() {
+        let config = AggregatorConfig::default()
+            .with_allowed_nodes(["node-b", "node-a"])
+            .with_aggregation_mode(AggregationMode::Geometric)
+            .with_publish_interval_secs(10)
+            .with_max_price_age_secs(90)
+            .with_skew_reject_secs(30)
+            .with_max_batch_size(50)
+            .with_source_weight("coinbase", 2.0);
+        let service = AggregatorServiceImpl::with_config(config);
+        let register = |node_id: &str| {
+            Request::new(RegisterNodeRequest {
+                node_id: node_id.to_string(),
+                sources: vec!["binance".to_string()],
+                version: "0.1.0".to_string(),
+            })
+        };
+
+        let response = service
+            .register_node(register("node-a"))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(response.window_secs, PRICE_WINDOW_SECS);
+        assert_eq!(response.min_quorum_nodes, MIN_QUORUM_NODES as u32);
+        assert_eq!(response.allowed_nodes, ["node-a", "node-b"]);
+        assert_eq!(response.aggregation_mode, "geometric");
+        assert_eq!(response.strategy, "geometric median");
+        assert_eq!(response.max_price_age_secs, 90);
+        assert_eq!(response.publish_interval_secs, Some(10));
+        assert_eq!(response.max_contribution_age_secs, None);
+        assert_eq!(response.skew_clamp_secs, None);
+        assert_eq!(response.skew_reject_secs, Some(30));
+        assert_eq!(response.max_batch_size, 50);
+        assert_eq!(
+            response.source_weights,
+            HashMap::from([("coinbase".to_string(), 2.0)])
+        );
+        // 등록도 노드 활동
+        assert!(service.state.read().await.active_nodes.contains("node-a"));
+
+        let status = service.register_node(register("node-c")).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::PermissionDenied);
+        let status = service.register_node(register(" ")).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+        // 허용 목록에 없는 노드의 제출은 저장하지 않고 거부
+        let response = service
+            .submit_price(price_request(70_000.0, "node-c", "binance"))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(response.code(), ResponseCode::NodeNotAllowed);
+        assert!(!response.success);
+        let text = service.metrics().encode().unwrap();
+        assert!(text.contains(r#"oracle_aggregator_rejections_total{reason="node_not_allowed"} 1"#));
+        assert!(service.state.read().await.prices.is_empty());
+
+        // 허용 목록이 없으면 모든 노드를 받음
+        let response = AggregatorServiceImpl::new()
+            .register_node(register("node-c"))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(response.allowed_nodes.is_empty());
+        assert_eq!(response.aggregation_mode, "arithmetic");
+        assert_eq!(response.strategy, "median");
+    }
+
+    #[tokio::test]
+    async fn test_health_check_from_disallowed_node_is_not_recorded() {
+        let service = AggregatorServiceImpl::with_config(
+            AggregatorConfig::default().with_allowed_nodes(["node-a"]),
+        );
+
+        let response = service
+            .health_check(Request::new(HealthRequest {
+                node_id: "node-b".to_string(),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(response.active_nodes, 0);
+        assert_eq!(service.state.read().await.active_nodes.len(), 0);
+
+        let response = service
+            .health_check(Request::new(HealthRequest {
+                node_id: "node-a".to_string(),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(response.active_nodes, 1);
+    }
+
+    #[tokio::test]
+    async fn test_each_rejection_path_counts_its_reason() {
+        let now = 1_700_000_000;
+        let clock = Arc::new(testing::ManualClock::starting_at(now * 1_000));
+        let config = AggregatorConfig::default().with_skew_reject_secs(30);
+        let service = AggregatorServiceImpl::with_config(config).with_clock(clock);
+        let count = |service: &AggregatorServiceImpl, reason: RejectionReason| {
+            let line = format!(
+                "oracle_aggregator_rejections_total{{reason=\"{}\"}} ",
+                reason.label()
+            );
+            let text = service.metrics().encode().unwrap();
+            text.lines()
+                .find_map(|l| l.strip_prefix(&line))
+                .and_then(|value| value.parse::<u64>().ok())
+                .unwrap_or_else(|| panic!("missing {:?} in\n{}", line, text))
+        };
+        for reason in RejectionReason::ALL {
+            assert_eq!(count(&service, reason), 0, "{:?}", reason);
+        }
+
+        let timed = |price: f64, node_id: &str, timestamp: u64| {
+            let mut request = price_request(price, node_id, "binance");
+            request.get_mut().timestamp = timestamp;
+            request
+        };
+        let mut missing_decimals = timed(70_000.0, "node-a", now);
+        missing_decimals.get_mut().price_scaled = Some(7_000_000);
+        let cases = [
+            (timed(0.0, "node-a", now), RejectionReason::InvalidPrice),
+            (missing_decimals, RejectionReason::InvalidPrice),
+            (timed(70_000.0, " ", now), RejectionReason::MissingNodeId),
+            (
+                timed(70_000.0, "node-a", now * 1_000),
+                RejectionReason::InvalidTimestamp,
+            ),
+            (
+                timed(70_000.0, "node-a", now + 31),
+                RejectionReason::ClockSkew,
+            ),
+        ];
+        for (request, reason) in cases {
+            let response = service.submit_price(request).await.unwrap().into_inner();
+            assert_eq!(response.code().rejection_reason(), Some(reason));
+        }
+
+        service
+            .submit_price(timed(70_000.0, "node-a", now))
+            .await
+            .unwrap();
+        assert!(service
+            .submit_price(timed(70_001.0, "node-a", now))
+            .await
+            .is_err());
+
+        assert_eq!(count(&service, RejectionReason::InvalidPrice), 2);
+        for reason in [
+            RejectionReason::MissingNodeId,
+            RejectionReason::InvalidTimestamp,
+            RejectionReason::ClockSkew,
+            RejectionReason::Conflict,
+        ] {
+            assert_eq!(count(&service, reason), 1, "{:?}", reason);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_skewed_timestamps_clamped_or_rejected() {
+        let now = 1_700_000_000;
+        let clock = Arc::new(testing::ManualClock::starting_at(now * 1_000));
+        let config = AggregatorConfig::default()
+            .with_skew_clamp_secs(5)
+            .with_skew_reject_secs(30);
+        let service = AggregatorServiceImpl::with_config(config).with_clock(clock);
+
+        let submit = |node_id: &'static str, timestamp: u64| {
+            let service = service.clone();
+            async move {
+                let mut request = price_request(70_000.0, node_id, "binance");
+                request.get_mut().timestamp = timestamp;
+                service.submit_price(request).await.unwrap().into_inner()
+            }
+        };
+
+        // 허용 오차 이내는 그대로
+        assert!(submit("node-a", now - 5).await.success);
+        // 허용 오차를 넘지만 한도 이내면 서버 시각으로 대체 (앞서거나 늦은 시계 모두)
+        assert!(submit("node-b", now + 20).await.success);
+        assert!(submit("node-c", now - 30).await.success);
+        // 한도를 넘으면 거부
+        let response = submit("node-d", now + 31).await;
+        assert!(!response.success);
+        assert_eq!(response.code(), ResponseCode::ClockSkew);
+        assert!(!submit("node-e", now - 600).await.success);
+
+        let state = service.state.read().await;
+        let mut timestamps: Vec<(String, u64)> = state
+            .prices
+            .entries()
+            .map(|entry| (entry.node_id.to_string(), entry.timestamp))
+            .collect();
+        timestamps.sort();
+        assert_eq!(
+            timestamps,
+            vec![
+                ("node-a".to_string(), now - 5),
+                ("node-b".to_string(), now),
+                ("node-c".to_string(), now),
+            ]
+        );
+        assert!(!state.active_nodes.contains("node-d"));
+    }
+
+    #[tokio::test]
+    async fn test_clamped_retry_is_deduplicated_on_the_submitted_timestamp() {
+        let now = 1_700_000_000;
+        let clock = Arc::new(testing::ManualClock::starting_at(now * 1_000));
+        let config = AggregatorConfig::default()
+            .with_skew_clamp_secs(5)
+            .with_skew_reject_secs(30);
+        let (sender, mut records) = wal::channel(16, wal::WalBackpressure::Block);
+        let service = AggregatorServiceImpl::with_config(config.clone())
+            .with_clock(clock.clone())
+            .with_wal(sender);
+
+        // 앞서는 시계의 제출이 서버 시각으로 보정된 뒤, 응답을 못 받은 노드가 2초 뒤 재전송
+        service
+            .submit_price(timed_request(70_000.0, "node-a", now + 20))
+            .await
+            .unwrap();
+        clock.advance(Duration::from_secs(2));
+        service
+            .submit_price(timed_request(70_000.0, "node-a", now + 20))
+            .await
+            .unwrap();
+        // 같은 원래 timestamp에 다른 가격이면 충돌
+        let conflict = service
+            .submit_price(timed_request(70_100.0, "node-a", now + 20))
+            .await
+            .unwrap_err();
+        assert_eq!(conflict.code(), tonic::Code::AlreadyExists);
+
+        let state = service.state.read().await;
+        let stored: Vec<(u64, Price)> = state
+            .prices
+            .entries()
+            .map(|entry| (entry.timestamp, entry.price))
+            .collect();
+        assert_eq!(stored, vec![(now, Price::from_cents(7_000_000))]);
+        drop(state);
+
+        let logged: Vec<WalRecord> = std::iter::from_fn(|| records.try_recv().ok()).collect();
+        let decisions: Vec<(&str, u64, Option<u64>)> = logged
+            .iter()
+            .map(|record| {
+                (
+                    record.decision.name(),
+                    record.request.timestamp,
+                    record.clamped_from,
+                )
+            })
+            .collect();
+        assert_eq!(
+            decisions,
+            vec![
+                ("accepted", now, Some(now + 20)),
+                ("duplicate", now + 2, Some(now + 20)),
+                ("rejected", now + 2, Some(now + 20)),
+            ]
+        );
+
+        // WAL에서 복구한 상태도 원래 timestamp로 재전송을 알아봄
+        let replayed = AggregatorServiceImpl::replay(config, logged).with_clock(clock);
+        let response = replayed
+            .submit_price(timed_request(70_000.0, "node-a", now + 20))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(response.success);
+        assert_eq!(replayed.state.read().await.prices.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_skew_policy_skips_historical_submissions() {
+        let now = 1_700_000_000;
+        let clock = Arc::new(testing::ManualClock::starting_at(now * 1_000));
+        let config = AggregatorConfig::default()
+            .with_skew_clamp_secs(5)
+            .with_skew_reject_secs(30);
+        let service = AggregatorServiceImpl::with_config(config).with_clock(clock);
+
+        // 오프라인 큐에서 재전송된 관측값은 원래 시각 그대로 기록
+        let mut request = price_request(70_000.0, "node-a", "binance");
+        request.get_mut().timestamp = now - 600;
+        request.get_mut().historical = true;
+        let response = service.submit_price(request).await.unwrap().into_inner();
+        assert!(response.success);
+    }
+
+    #[test]
+    fn test_skew_decision() {
+        let now = 1_000;
+        assert_eq!(
+            skew_decision(now + 100, now, None, None),
+            SkewDecision::Untouched
+        );
+        assert_eq!(
+            skew_decision(now + 2, now, Some(2), Some(10)),
+            SkewDecision::Untouched
+        );
+        assert_eq!(
+            skew_decision(now - 3, now, Some(2), Some(10)),
+            SkewDecision::Clamped
+        );
+        assert_eq!(
+            skew_decision(now + 11, now, Some(2), Some(10)),
+            SkewDecision::Rejected
+        );
+        assert_eq!(
+            skew_decision(now + 11, now, None, Some(10)),
+            SkewDecision::Rejected
+        );
+    }
+
+    #[tokio::test]
+    async fn test_active_nodes_capped_with_lru_eviction() {
+        let service = AggregatorServiceImpl::new();
+        let total = 20_000;
+
+        for i in 0..total {
+            let node_id = format!("node-{:05}", i);
+            service
+                .submit_price(price_request(70_000.0, &node_id, "binance"))
+                .await
+                .unwrap();
+        }
+
+        let stats = service
+            .get_stats(Request::new(GetStatsRequest {}))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(stats.active_nodes, config::DEFAULT_MAX_ACTIVE_NODES as u32);
+        assert_eq!(
+            stats.max_active_nodes,
+            config::DEFAULT_MAX_ACTIVE_NODES as u32
+        );
+        assert_eq!(
+            stats.evicted_nodes,
+            (total - config::DEFAULT_MAX_ACTIVE_NODES) as u64
+        );
+
+        // 먼저 들어온 노드부터 제거되고 최근 노드만 남음
+        let state = service.state.read().await;
+        assert!(!state.active_nodes.contains("node-00000"));
+        assert!(!state.active_nodes.contains("node-09999"));
+        assert!(state.active_nodes.contains("node-10000"));
+        assert!(state.active_nodes.contains("node-19999"));
+        assert_eq!(
+            state
+                .active_nodes
+                .iter_oldest_first()
+                .next()
+                .map(|id| &**id),
+            Some("node-10000")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_active_nodes_cap_is_configurable() {
+        let service = AggregatorServiceImpl::with_config(AggregatorConfig {
+            max_active_nodes: 2,
+            ..AggregatorConfig::default()
+        });
+
+        for node_id in ["node-a", "node-b", "node-c"] {
+            service
+                .submit_price(price_request(70_000.0, node_id, "binance"))
+                .await
+                .unwrap();
+        }
+        service.publish_snapshot().await;
+
+        let health = service
+            .health_check(Request::new(HealthRequest {
+                node_id: "node-a".to_string(),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(health.active_nodes, 2);
+    }
+
+    fn heartbeat(node_id: &str) -> Request<HealthRequest> {
+        Request::new(HealthRequest {
+            node_id: node_id.to_string(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_heartbeats_keep_node_active_without_extending_contribution() {
+        let clock = Arc::new(testing::ManualClock::starting_at(1_700_000_000_000));
+        let service = AggregatorServiceImpl::with_config(AggregatorConfig {
+            max_contribution_age_secs: Some(30),
+            max_price_age_secs: 3_600,
+            ..AggregatorConfig::default()
+        })
+        .with_clock(clock.clone());
+        service
+            .submit_price(timed_request(70_000.0, "node-a", 1_700_000_000))
+            .await
+            .unwrap();
+
+        // 제출 없이 헬스체크만 계속
+        for _ in 0..4 {
+            clock.advance(Duration::from_secs(50));
+            let health = service.health_check(heartbeat("node-a")).await.unwrap();
+            assert_eq!(health.into_inner().active_nodes, 1);
+        }
+        assert!(service.state.read().await.active_nodes.contains("node-a"));
+        // 헬스체크는 오래된 가격을 다시 집계에 넣지 않음
+        assert_eq!(service.calculate_median_price().await, (None, 0));
+
+        // 헬스체크가 멈추면 타임아웃 후 제외
+        clock.advance(Duration::from_secs(NODE_TIMEOUT_SECS));
+        let health = service.health_check(heartbeat("")).await.unwrap();
+        assert_eq!(health.into_inner().active_nodes, 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_health_reports_per_source_staleness() {
+        let clock = Arc::new(testing::TokioClock::starting_at(1_700_000_000_000));
+        let service = AggregatorServiceImpl::new().with_clock(clock.clone());
+
+        for source in ["binance", "kraken"] {
+            let mut request = timed_request(70_000.0, "node-a", 1_700_000_000);
+            request.get_mut().source = source.to_string();
+            service.submit_price(request).await.unwrap();
+        }
+
+        // 5분 뒤 binance만 계속 보고
+        tokio::time::advance(Duration::from_secs(300)).await;
+        service
+            .submit_price(timed_request(70_100.0, "node-a", 1_700_000_300))
+            .await
+            .unwrap();
+        service.publish_snapshot().await;
+
+        let health = service
+            .health_check(Request::new(HealthRequest {
+                node_id: "node-a".to_string(),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(
+            health.sources,
+            vec![
+                SourceHealth {
+                    source: "binance".to_string(),
+                    last_timestamp: 1_700_000_300,
+                    age_secs: 0,
+                    healthy: true,
+                },
+                SourceHealth {
+                    source: "kraken".to_string(),
+                    last_timestamp: 1_700_000_000,
+                    age_secs: 300,
+                    healthy: false,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_health_reports_degradation_reason() {
+        let start = 1_700_000_000;
+        let clock = Arc::new(testing::ManualClock::starting_at(start * 1_000));
+        let service = AggregatorServiceImpl::with_config(
+            AggregatorConfig::default().with_stale_grace_secs(90),
+        )
+        .with_clock(clock.clone());
+        let health = |service: AggregatorServiceImpl| async move {
+            let health = service.health_check(heartbeat("")).await.unwrap();
+            let health = health.into_inner();
+            (health.healthy, health.reason)
+        };
+
+        assert_eq!(
+            health(service.clone()).await,
+            (false, "no sources".to_string())
+        );
+
+        for node_id in ["node-a", "node-b"] {
+            service
+                .submit_price(timed_request(70_000.0, node_id, start))
+                .await
+                .unwrap();
+        }
+        service.publish_snapshot().await;
+        assert_eq!(
+            health(service.clone()).await,
+            (false, "below quorum".to_string())
+        );
+
+        service
+            .submit_price(timed_request(70_000.0, "node-c", start))
+            .await
+            .unwrap();
+        service.publish_snapshot().await;
+        assert_eq!(health(service.clone()).await, (true, String::new()));
+
+        // 윈도우가 비어 이전 중간값을 이어받는 동안 (소스는 아직 최근에 보고함)
+        clock.advance(Duration::from_secs(PRICE_WINDOW_SECS + 1));
+        service.publish_snapshot().await;
+        assert_eq!(
+            health(service.clone()).await,
+            (false, "stale median".to_string())
+        );
+
+        // 소스가 모두 보고를 멈춤
+        clock.advance(Duration::from_secs(SOURCE_STALE_SECS));
+        service.publish_snapshot().await;
+        assert_eq!(
+            health(service.clone()).await,
+            (false, "no sources".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_aggregated_price_returns_latest_entries_in_order() {
+        let service = AggregatorServiceImpl::new();
+
+        for i in 0..15 {
+            let node_id = format!("node-{}", i);
+            service
+                .submit_price(price_request(70_000.0 + i as f64, &node_id, "binance"))
+                .await
+                .unwrap();
+        }
+        service.publish_snapshot().await;
+
+        let response = service
+            .get_aggregated_price(Request::new(GetPriceRequest {
+                source_filter: None,
+                include_stale: false,
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert_eq!(response.data_points, RECENT_PRICES_LIMIT as u32);
+        assert_eq!(response.aggregated_price, 70_007.0);
+        // 70_000 ~ 70_014 -> 순위 3.5 / 10.5
+        assert_eq!(response.p25, 70_003.5);
+        assert_eq!(response.p75, 70_010.5);
+
+        // 최신 데이터부터 역순으로 반환
+        let expected: Vec<PriceDataPoint> = (5..15)
+            .rev()
+            .map(|i| PriceDataPoint {
+                price: 70_000.0 + i as f64,
+                timestamp: response.recent_prices[14 - i].timestamp,
+                source: "binance".to_string(),
+                node_id: format!("node-{}", i),
+                price_scaled: (70_000 + i as u64) * 100_000_000,
+                price_decimals: LEGACY_PRICE_DECIMALS,
+            })
+            .collect();
+        assert_eq!(response.recent_prices, expected);
+    }
+
+    #[tokio::test]
+    async fn test_per_node_quota_keeps_minority_history_intact() {
+        let service = AggregatorServiceImpl::new();
+        let quota = AggregatorConfig::default().effective_per_node_quota();
+
+        for i in 0..5 {
+            for node_id in ["node-a", "node-b"] {
+                let mut request = price_request(70_000.0 + i as f64, node_id, "binance");
+                request.get_mut().timestamp -= 5 - i;
+                service.submit_price(request).await.unwrap();
+            }
+        }
+        // 같은 시각에 여러 source로 몰아서 제출
+        for i in 0..200 {
+            let source = format!("source-{}", i);
+            service
+                .submit_price(price_request(80_000.0 + i as f64, "node-flood", &source))
+                .await
+                .unwrap();
+        }
+
+        // 소수 노드의 데이터는 모두 남아 있음
+        {
+            let state = service.state.read().await;
+            for node_id in ["node-a", "node-b"] {
+                let prices: Vec<f64> = state
+                    .prices
+                    .entries()
+                    .filter(|e| &*e.node_id == node_id)
+                    .map(|e| e.price.to_f64_dollars())
+                    .collect();
+                assert_eq!(
+                    prices,
+                    vec![70_000.0, 70_001.0, 70_002.0, 70_003.0, 70_004.0]
+                );
+            }
+
+            // 많이 보낸 노드는 자신의 최신 데이터만 할당량만큼 유지
+            let flood: Vec<f64> = state
+                .prices
+                .entries()
+                .filter(|e| &*e.node_id == "node-flood")
+                .map(|e| e.price.to_f64_dollars())
+                .collect();
+            assert_eq!(flood.len(), quota);
+            assert_eq!(flood.last(), Some(&80_199.0));
+        }
+
+        let stats = service
+            .get_stats(Request::new(GetStatsRequest {}))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(stats.per_node_quota, quota as u32);
+        assert_eq!(stats.stored_prices, (quota + 10) as u32);
+        assert_eq!(
+            stats.node_usage,
+            vec![
+                NodeUsage {
+                    node_id: "node-a".to_string(),
+                    stored_prices: 5,
+                },
+                NodeUsage {
+                    node_id: "node-b".to_string(),
+                    stored_prices: 5,
+                },
+                NodeUsage {
+                    node_id: "node-flood".to_string(),
+                    stored_prices: quota as u32,
+                },
+            ]
+        );
+
+        // 할당량으로만 제거됨 (전체 크기와 보관 시간에는 걸리지 않음)
+        assert_eq!(stats.evicted_by_quota, (200 - quota) as u64);
+        assert_eq!(stats.evicted_by_count, 0);
+        assert_eq!(stats.evicted_by_age, 0);
+        assert_eq!(
+            stats.pair_usage,
+            vec![PairUsage {
+                pair: DEFAULT_PAIR.to_string(),
+                stored_prices: (quota + 10) as u32,
+            }]
+        );
+        let oldest = service
+            .state
+            .read()
+            .await
+            .prices
+            .entries()
+            .next()
+            .unwrap()
+            .timestamp;
+        assert_eq!(stats.oldest_price_timestamp, Some(oldest));
+        assert!(
+            stats.estimated_bytes >= (MAX_PRICE_ENTRIES * std::mem::size_of::<PriceEntry>()) as u64
+        );
+    }
+
+    #[tokio::test]
+    async fn test_aggregation_task_publishes_snapshot() {
+        let service = AggregatorServiceImpl::new();
+        let task = service.spawn_aggregation_task();
+
+        assert_eq!(service.snapshot().aggregated_price, None);
+
+        service
+            .submit_price(price_request(70_000.0, "node-a", "binance"))
+            .await
+            .unwrap();
+
+        let published = tokio::time::timeout(std::time::Duration::from_secs(1), async {
+            while service.snapshot().aggregated_price.is_none() {
+                tokio::task::yield_now().await;
+            }
+        })
+        .await;
+        assert!(published.is_ok(), "snapshot was not published");
+
+        let snapshot = service.snapshot();
+        assert_eq!(
+            snapshot.aggregated_price,
+            Some(Price::from_cents(7_000_000))
+        );
+        assert_eq!(snapshot.active_nodes, 1);
+        assert_eq!(snapshot.stored_prices, 1);
+
+        task.abort();
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_snapshot_reads_never_block_during_writes() {
+        use std::time::{Duration, Instant};
+
+        let service = Arc::new(AggregatorServiceImpl::with_config(AggregatorConfig {
+            max_price_entries: 1_000,
+            ..AggregatorConfig::default()
+        }));
+        let task = service.spawn_aggregation_task();
+
+        // 연속 쓰기
+        let writer = {
+            let service = service.clone();
+            tokio::spawn(async move {
+                for i in 0..2_000 {
+                    let node_id = format!("node-{}", i % 20);
+                    let source = format!("source-{}", i / 20);
+                    let price = 70_000.0 + (i % 100) as f64;
+                    service
+                        .submit_price(price_request(price, &node_id, &source))
+                        .await
+                        .unwrap();
+                }
+            })
+        };
+
+        // 쓰기 락을 오래 잡고 있어도 읽기는 기다리지 않아야 함
+        let lock_holder = {
+            let service = service.clone();
+            tokio::spawn(async move {
+                let _guard = service.state.write().await;
+                tokio::time::sleep(Duration::from_millis(300)).await;
+            })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        let readers: Vec<_> = (0..5_000)
+            .map(|_| {
+                let service = service.clone();
+                tokio::spawn(async move {
+                    let start = Instant::now();
+                    let response = service
+                        .get_aggregated_price(Request::new(GetPriceRequest {
+                            source_filter: None,
+                            include_stale: false,
+                        }))
+                        .await
+                        .unwrap()
+                        .into_inner();
+                    (start.elapsed(), response)
+                })
+            })
+            .collect();
+
+        for reader in readers {
+            let (elapsed, response) = reader.await.unwrap();
+
+            assert!(
+                elapsed < Duration::from_millis(50),
+                "reader blocked for {:?}",
+                elapsed
+            );
+
+            // 한 스냅샷에서 나온 값끼리 일관됨
+            assert_eq!(response.data_points as usize, response.recent_prices.len());
+            if response.data_points > 0 {
+                assert!((70_000.0..70_100.0).contains(&response.aggregated_price));
+                assert!(response
+                    .recent_prices
+                    .windows(2)
+                    .all(|w| w[0].timestamp >= w[1].timestamp));
+                assert!(response
+                    .recent_prices
+                    .iter()
+                    .all(|p| (70_000.0..70_100.0).contains(&p.price)));
+            }
+        }
+
+        lock_holder.await.unwrap();
+        writer.await.unwrap();
+        task.abort();
+    }
+
+    // (span 이름, [(필드 이름, 값)])
+    type CapturedSpan = (String, Vec<(String, String)>);
+
+    // 생성된 span의 이름과 필드, 진입 여부를 기록하는 테스트용 Layer
+    #[tokio::test]
+    async fn test_price_stream_ends_cleanly_on_shutdown() {
+        use oracle::oracle_service_client::OracleServiceClient;
+        use oracle::oracle_service_server::OracleServiceServer;
+        use tokio_stream::wrappers::TcpListenerStream;
+
+        let service = AggregatorServiceImpl::new();
+        let handle = service.clone();
+        let task = service.spawn_aggregation_task();
+        let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(OracleServiceServer::new(service))
+                .serve_with_incoming_shutdown(TcpListenerStream::new(listener), async move {
+                    let _ = stopped.await;
+                    task.abort();
+                    handle.shutdown();
+                }),
+        );
+
+        let mut client = OracleServiceClient::connect(format!("http://{}", addr))
+            .await
+            .unwrap();
+        let (prices, inbound) = mpsc::channel(4);
+        let mut stream = client
+            .stream_prices(ReceiverStream::new(inbound))
+            .await
+            .unwrap()
+            .into_inner();
+
+        // 스트림으로 보낸 가격의 처리 결과와 집계가 같은 스트림으로 돌아옴
+        prices
+            .send(price_request(70_000.0, "node-a", "binance").into_inner())
+            .await
+            .unwrap();
+        let result = stream.message().await.unwrap().unwrap();
+        assert!(result.submission.unwrap().success);
+        let update = stream.message().await.unwrap().unwrap();
+        assert!(update.submission.is_none());
+        assert_eq!(update.aggregated_price, 70_000.0);
+        assert_eq!(update.active_nodes, vec!["node-a".to_string()]);
+
+        // 계획된 종료: 클라이언트가 송신 측을 열어 둔 채여도 에러 없이 스트림이 끝남
+        stop.send(()).unwrap();
+        assert!(stream.message().await.unwrap().is_none());
+        server.await.unwrap().unwrap();
+        drop(prices);
+    }
+
+    #[tokio::test]
+    async fn test_price_stream_exchanges_several_submissions_on_one_stream() {
+        use oracle::oracle_service_client::OracleServiceClient;
+        use oracle::oracle_service_server::OracleServiceServer;
+        use tokio_stream::wrappers::TcpListenerStream;
+
+        let service = AggregatorServiceImpl::new();
+        let task = service.spawn_aggregation_task();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(OracleServiceServer::new(service))
+                .serve_with_incoming(TcpListenerStream::new(listener)),
+        );
+
+        let mut client = OracleServiceClient::connect(format!("http://{}", addr))
+            .await
+            .unwrap();
+        let (prices, inbound) = mpsc::channel(4);
+        let mut stream = client
+            .stream_prices(ReceiverStream::new(inbound))
+            .await
+            .unwrap()
+            .into_inner();
+
+        // 한 연결에서 제출하고, 제출마다 게시된 집계를 같은 스트림으로 받음
+        let mut received = Vec::new();
+        for (price, source) in [
+            (70_000.0, "binance"),
+            (70_200.0, "coinbase"),
+            (70_400.0, "kraken"),
+        ] {
+            prices
+                .send(price_request(price, "node-a", source).into_inner())
+                .await
+                .unwrap();
+            let result = stream.message().await.unwrap().unwrap();
+            assert!(result.submission.unwrap().success);
+            let update = stream.message().await.unwrap().unwrap();
+            assert_eq!(update.active_nodes, vec!["node-a".to_string()]);
+            received.push(update.aggregated_price);
+        }
+        // 업데이트마다 그때까지 제출한 가격의 중간값
+        assert_eq!(received, [70_000.0, 70_100.0, 70_200.0]);
+        task.abort();
+    }
+
+    #[tokio::test]
+    async fn test_price_stream_returns_rejections_and_stays_open() {
+        use oracle::oracle_service_client::OracleServiceClient;
+        use oracle::oracle_service_server::OracleServiceServer;
+        use tokio_stream::wrappers::TcpListenerStream;
+
+        let service = AggregatorServiceImpl::with_config(
+            AggregatorConfig::default().with_allowed_nodes(["node-a"]),
+        );
+        let handle = service.clone();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(OracleServiceServer::new(service))
+                .serve_with_incoming(TcpListenerStream::new(listener)),
+        );
+
+        let mut client = OracleServiceClient::connect(format!("http://{}", addr))
+            .await
+            .unwrap();
+        let (prices, inbound) = mpsc::channel(4);
+        let mut stream = client
+            .stream_prices(ReceiverStream::new(inbound))
+            .await
+            .unwrap()
+            .into_inner();
+
+        // 거부 사유가 같은 스트림으로 돌아오고, 순서대로 다음 제출의 결과가 이어짐
+        for request in [
+            price_request(70_000.0, "node-b", "binance"),
+            price_request(-1.0, "node-a", "binance"),
+            price_request(70_000.0, "node-a", "binance"),
+        ] {
+            prices.send(request.into_inner()).await.unwrap();
+        }
+        let mut results = Vec::new();
+        for _ in 0..3 {
+            let result = stream.message().await.unwrap().unwrap().submission.unwrap();
+            results.push((result.success, result.code()));
+        }
+        assert_eq!(
+            results,
+            [
+                (false, ResponseCode::NodeNotAllowed),
+                (false, ResponseCode::InvalidPrice),
+                (true, ResponseCode::BelowQuorum),
+            ]
+        );
+        assert_eq!(handle.state.read().await.prices.len(), 1);
+    }
+
+    fn limited_service() -> AggregatorServiceImpl {
+        AggregatorServiceImpl::with_config(AggregatorConfig {
+            max_batch_size: 5,
+            max_history_limit: 20,
+            ..AggregatorConfig::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn test_batch_size_is_limited() {
+        let service = limited_service();
+        let batch = |size: usize| {
+            Request::new(PriceBatchRequest {
+                prices: (0..size)
+                    .map(|i| price_request(70_000.0 + i as f64, &format!("node-{i}"), "binance"))
+                    .map(Request::into_inner)
+                    .collect(),
+            })
+        };
+
+        let response = service.submit_price_batch(batch(5)).await.unwrap();
+        let results = response.into_inner().results;
+        assert_eq!(results.len(), 5);
+        assert!(results.iter().all(|result| result.success));
+        assert_eq!(service.state.read().await.prices.len(), 5);
+
+        let status = service.submit_price_batch(batch(6)).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+        // 거부된 일괄 전송은 하나도 저장하지 않음
+        assert_eq!(service.state.read().await.prices.len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_history_limit_is_limited() {
+        let service = limited_service();
+        for i in 0..25 {
+            let mut request = price_request(70_000.0 + i as f64, "node-a", "binance");
+            request.get_mut().timestamp -= 25 - i;
+            service.submit_price(request).await.unwrap();
+        }
+        let history = |limit: u32| {
+            Request::new(PriceHistoryRequest {
+                limit,
+                ..PriceHistoryRequest::default()
+            })
+        };
+
+        let prices = service
+            .get_price_history(history(20))
+            .await
+            .unwrap()
+            .into_inner()
+            .prices;
+        assert_eq!(prices.len(), 20);
+        // 최신순
+        assert_eq!(prices[0].price, 70_024.0);
+
+        let status = service.get_price_history(history(21)).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+        // 0이면 기본 개수
+        let prices = service
+            .get_price_history(history(0))
+            .await
+            .unwrap()
+            .into_inner()
+            .prices;
+        assert_eq!(prices.len(), RECENT_PRICES_LIMIT);
+    }
+
+    #[tokio::test]
+    async fn test_history_filters_by_pair_and_time_range() {
+        let service = AggregatorServiceImpl::new();
+        let now = Utc::now().timestamp() as u64;
+        for (i, symbol) in ["BTC-USD", "ETH-USD", "BTC-USD", "BTC-USD"]
+            .iter()
+            .enumerate()
+        {
+            let mut request = symbol_request(70_000.0 + i as f64, "node-a", Some(symbol));
+            request.get_mut().timestamp = now - 30 + i as u64 * 10;
+            service.submit_price(request).await.unwrap();
+        }
+        let history = |pair: &str, from: Option<u64>, to: Option<u64>| {
+            Request::new(PriceHistoryRequest {
+                pair: Some(pair.to_string()),
+                from_timestamp: from,
+                to_timestamp: to,
+                ..PriceHistoryRequest::default()
+            })
+        };
+
+        let prices = service
+            .get_price_history(history("btc/usd", Some(now - 30), Some(now - 10)))
+            .await
+            .unwrap()
+            .into_inner()
+            .prices;
+        let timestamps: Vec<u64> = prices.iter().map(|p| p.timestamp).collect();
+        assert_eq!(timestamps, [now - 10, now - 30]);
+
+        let prices = service
+            .get_price_history(history("ETHUSDT", None, None))
+            .await
+            .unwrap()
+            .into_inner()
+            .prices;
+        assert_eq!(prices.len(), 1);
+        assert_eq!(prices[0].price, 70_001.0);
+
+        for request in [
+            history("not a pair", None, None),
+            history("BTC-USD", Some(now), Some(now - 1)),
+        ] {
+            let status = service.get_price_history(request).await.unwrap_err();
+            assert_eq!(status.code(), tonic::Code::InvalidArgument);
+        }
+    }
+
+    fn node_history_request(node_id: &str, limit: u32) -> Request<NodeHistoryRequest> {
+        Request::new(NodeHistoryRequest {
+            node_id: node_id.to_string(),
+            limit,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_node_history_reports_accepted_and_rejected_submissions() {
+        let service = limited_service();
+        service
+            .submit_price(price_request(70_000.0, "node-a", "binance"))
+            .await
+            .unwrap();
+        service
+            .submit_price(price_request(-1.0, "node-a", "kraken"))
+            .await
+            .unwrap();
+        let mut historical = price_request(69_000.0, "node-a", "coinbase");
+        historical.get_mut().historical = true;
+        service.submit_price(historical).await.unwrap();
+        // 다른 노드와 중복 제출은 기록에 나타나지 않음
+        service
+            .submit_price(price_request(70_100.0, "node-b", "binance"))
+            .await
+            .unwrap();
+        service
+            .submit_price(price_request(70_000.0, "node-a", "binance"))
+            .await
+            .unwrap();
+
+        let response = service
+            .get_node_history(node_history_request("node-a", 0))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(response.node_id, "node-a");
+        let submissions = response.submissions;
+        assert_eq!(submissions.len(), 3);
+
+        // 최신순
+        assert_eq!(submissions[0].source, "coinbase");
+        assert!(submissions[0].accepted);
+        assert!(submissions[0].historical);
+
+        assert_eq!(submissions[1].source, "kraken");
+        assert!(!submissions[1].accepted);
+        assert_eq!(
+            submissions[1].rejection_reason.as_deref(),
+            Some(ResponseCode::InvalidPrice.message())
+        );
+        assert_eq!(submissions[1].price, -1.0);
+        assert_eq!(submissions[1].price_scaled, None);
+
+        assert_eq!(submissions[2].source, "binance");
+        assert!(submissions[2].accepted);
+        assert!(!submissions[2].historical);
+        assert_eq!(submissions[2].price, 70_000.0);
+        assert_eq!(submissions[2].rejection_reason, None);
+
+        let unknown = service
+            .get_node_history(node_history_request("node-z", 0))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(unknown.submissions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_node_history_limit_is_limited() {
+        let service = limited_service();
+        for i in 0..25 {
+            let mut request = price_request(70_000.0 + i as f64, "node-a", "binance");
+            request.get_mut().timestamp -= 25 - i;
+            service.submit_price(request).await.unwrap();
+        }
+
+        let submissions = service
+            .get_node_history(node_history_request("node-a", 20))
+            .await
+            .unwrap()
+            .into_inner()
+            .submissions;
+        assert_eq!(submissions.len(), 20);
+        assert_eq!(submissions[0].price, 70_024.0);
+
+        // 0이면 기본 개수
+        let submissions = service
+            .get_node_history(node_history_request("node-a", 0))
+            .await
+            .unwrap()
+            .into_inner()
+            .submissions;
+        assert_eq!(submissions.len(), RECENT_PRICES_LIMIT);
+
+        for (node_id, limit) in [("node-a", 21), (" ", 1)] {
+            let status = service
+                .get_node_history(node_history_request(node_id, limit))
+                .await
+                .unwrap_err();
+            assert_eq!(status.code(), tonic::Code::InvalidArgument);
+        }
+    }
+
+    fn reputation_request(
+        node_id: &str,
+        reputation: Option<f64>,
+        secret: Option<&str>,
+    ) -> Request<SetNodeReputationRequest> {
+        let mut request = Request::new(SetNodeReputationRequest {
+            node_id: node_id.to_string(),
+            reputation,
+        });
+        if let Some(secret) = secret {
+            request
+                .metadata_mut()
+                .insert(admin::ADMIN_SECRET_HEADER, secret.parse().unwrap());
+        }
+        request
+    }
+
+    fn admin_service() -> AggregatorServiceImpl {
+        AggregatorServiceImpl::with_config(AggregatorConfig {
+            admin_secret: Some("s3cret".to_string()),
+            ..AggregatorConfig::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn test_set_node_reputation_sets_and_resets() {
+        let service = admin_service();
+
+        let response = service
+            .set_node_reputation(reputation_request("node-a", Some(0.25), Some("s3cret")))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(response.node_id, "node-a");
+        assert_eq!(response.reputation, 0.25);
+        assert!(!response.clamped);
+        assert_eq!(service.state.read().await.reputation.get("node-a"), 0.25);
+
+        // 값 없이 호출하면 기본값으로 초기화
+        let response = service
+            .set_node_reputation(reputation_request("node-a", None, Some("s3cret")))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(response.reputation, reputation::DEFAULT_REPUTATION);
+        assert!(service.state.read().await.reputation.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_set_node_reputation_clamps_to_bounds() {
+        let service = admin_service();
+
+        let high = service
+            .set_node_reputation(reputation_request("node-a", Some(7.5), Some("s3cret")))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(high.reputation, reputation::MAX_REPUTATION);
+        assert!(high.clamped);
+
+        let low = service
+            .set_node_reputation(reputation_request("node-b", Some(-1.0), Some("s3cret")))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(low.reputation, reputation::MIN_REPUTATION);
+        assert!(low.clamped);
+
+        let state = service.state.read().await;
+        assert_eq!(state.reputation.get("node-a"), reputation::MAX_REPUTATION);
+        assert_eq!(state.reputation.get("node-b"), reputation::MIN_REPUTATION);
+        drop(state);
+
+        let nan = service
+            .set_node_reputation(reputation_request("node-c", Some(f64::NAN), Some("s3cret")))
+            .await
+            .unwrap_err();
+        assert_eq!(nan.code(), tonic::Code::InvalidArgument);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_reputation_recovers_without_submissions() {
+        let clock = Arc::new(testing::TokioClock::starting_at(1_700_000_000_000));
+        let service = AggregatorServiceImpl::with_config(AggregatorConfig {
+            admin_secret: Some("s3cret".to_string()),
+            reputation_half_life_secs: Some(600),
+            ..AggregatorConfig::default()
+        })
+        .with_clock(clock);
+        let task = service.spawn_reputation_decay_task();
+
+        service
+            .set_node_reputation(reputation_request("node-a", Some(0.2), Some("s3cret")))
+            .await
+            .unwrap();
+
+        // 반감기 한 번: 기본값과의 차이가 절반
+        tokio::time::sleep(Duration::from_secs(601)).await;
+        let after_half_life = service.state.read().await.reputation.get("node-a");
+        assert!((after_half_life - 0.6).abs() < 1e-9, "{}", after_half_life);
+
+        // 충분히 지나면 완전히 회복
+        tokio::time::sleep(Duration::from_secs(10 * 600)).await;
+        assert_eq!(
+            service.state.read().await.reputation.get("node-a"),
+            reputation::DEFAULT_REPUTATION
+        );
+        assert!(service.state.read().await.reputation.is_empty());
+
+        task.abort();
+    }
+
+    #[tokio::test]
+    async fn test_set_node_reputation_rejects_unauthorized_calls() {
+        let service = admin_service();
+
+        for secret in [None, Some("wrong"), Some("s3cre")] {
+            let status = service
+                .set_node_reputation(reputation_request("node-a", Some(0.5), secret))
+                .await
+                .unwrap_err();
+            assert_eq!(status.code(), tonic::Code::Unauthenticated);
+        }
+
+        // 비밀값이 설정되지 않은 서버는 관리자 RPC를 모두 거부
+        let status = AggregatorServiceImpl::new()
+            .set_node_reputation(reputation_request("node-a", Some(0.5), Some("s3cret")))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::PermissionDenied);
+
+        assert!(service.state.read().await.reputation.is_empty());
+    }
+
+    fn source_weights_request(
+        weights: &[(&str, f64)],
+        secret: Option<&str>,
+    ) -> Request<ConfigRequest> {
+        let mut request = Request::new(ConfigRequest {
+            source_weights: weights
+                .iter()
+                .map(|(source, weight)| (source.to_string(), *weight))
+                .collect(),
+            ..ConfigRequest::default()
+        });
+        if let Some(secret) = secret {
+            request
+                .metadata_mut()
+                .insert(admin::ADMIN_SECRET_HEADER, secret.parse().unwrap());
+        }
+        request
+    }
+
+    #[tokio::test]
+    async fn test_source_weight_pulls_weighted_median_toward_source() {
+        let service = AggregatorServiceImpl::with_config(AggregatorConfig {
+            admin_secret: Some("s3cret".to_string()),
+            strategy: Some(Arc::new(strategy::WeightedMedian::default())),
+            ..AggregatorConfig::default()
+        });
+        for (price, node_id, source) in [
+            (70_000.0, "node-a", "coinbase"),
+            (71_000.0, "node-b", "thinex"),
+            (71_100.0, "node-c", "thinex"),
+        ] {
+            service
+                .submit_price(price_request(price, node_id, source))
+                .await
+                .unwrap();
+        }
+        service.publish_snapshot().await;
+        assert_eq!(
+            recent_prices(&service, false).await.aggregated_price,
+            71_000.0
+        );
+
+        // 가중치 변경은 관리자만 가능하고 유효한 값이어야 함
+        let status = service
+            .update_config(source_weights_request(&[("coinbase", 3.0)], None))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unauthenticated);
+        let status = service
+            .update_config(source_weights_request(
+                &[("coinbase", f64::NAN)],
+                Some("s3cret"),
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+        let response = service
+            .update_config(source_weights_request(&[("coinbase", 3.0)], Some("s3cret")))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(response.success);
+        service.publish_snapshot().await;
+        assert_eq!(
+            recent_prices(&service, false).await.aggregated_price,
+            70_000.0
+        );
+
+        // 1.0이면 기본값으로 되돌아감
+        service
+            .update_config(source_weights_request(&[("coinbase", 1.0)], Some("s3cret")))
+            .await
+            .unwrap();
+        assert!(service.state.read().await.runtime.source_weights.is_empty());
+        service.publish_snapshot().await;
+        assert_eq!(
+            recent_prices(&service, false).await.aggregated_price,
+            71_000.0
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_submissions_never_see_a_partially_applied_config_update() {
+        let service = AggregatorServiceImpl::with_config(AggregatorConfig {
+            admin_secret: Some("s3cret".to_string()),
+            strategy: Some(Arc::new(strategy::WeightedMedian::default())),
+            ..AggregatorConfig::default()
+        });
+        let submissions = [
+            (70_000.0, "node-a", "coinbase"),
+            (71_000.0, "node-b", "kraken"),
+            (72_000.0, "node-c", "thinex"),
+        ];
+        for (price, node_id, source) in submissions {
+            service
+                .submit_price(price_request(price, node_id, source))
+                .await
+                .unwrap();
+        }
+
+        // 두 설정 모두 한쪽 끝으로 중간값을 끌어당기고, 섞이면(둘 다 5 또는 둘 다 1) 71,000이 됨
+        let favour_coinbase = [("coinbase", 5.0), ("thinex", 1.0)];
+        let favour_thinex = [("coinbase", 1.0), ("thinex", 5.0)];
+        service
+            .update_config(source_weights_request(&favour_coinbase, Some("s3cret")))
+            .await
+            .unwrap();
+
+        let updater = {
+            let service = service.clone();
+            tokio::spawn(async move {
+                for round in 0..200 {
+                    let weights = if round % 2 == 0 {
+                        &favour_thinex
+                    } else {
+                        &favour_coinbase
+                    };
+                    service
+                        .update_config(source_weights_request(weights, Some("s3cret")))
+                        .await
+                        .unwrap();
+                    tokio::task::yield_now().await;
+                }
+            })
+        };
+        let submitters: Vec<_> = submissions
+            .into_iter()
+            .map(|(price, node_id, source)| {
+                let service = service.clone();
+                tokio::spawn(async move {
+                    let mut medians = Vec::new();
+                    for _ in 0..200 {
+                        let response = service
+                            .submit_price(price_request(price, node_id, source))
+                            .await
+                            .unwrap()
+                            .into_inner();
+                        medians.push(response.aggregated_price.unwrap());
+                        tokio::task::yield_now().await;
+                    }
+                    medians
+                })
+            })
+            .collect();
+
+        updater.await.unwrap();
+        for submitter in submitters {
+            for median in submitter.await.unwrap() {
+                assert!(
+                    median == 70_000.0 || median == 72_000.0,
+                    "median {} computed from a mix of two configs",
+                    median
+                );
+            }
+        }
+        assert_eq!(
+            service.calculate_median_price().await.0,
+            Some(Price::from_cents(7_000_000))
+        );
+    }
+
+    #[derive(Clone, Default)]
+    struct SpanCapture {
+        spans: Arc<std::sync::Mutex<Vec<CapturedSpan>>>,
+        entered: Arc<std::sync::Mutex<Vec<String>>>,
+    }
+
+    struct FieldVisitor(Vec<(String, String)>);
+
+    impl tracing::field::Visit for FieldVisitor {
+        fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+            self.0
+                .push((field.name().to_string(), format!("{:?}", value)));
+        }
+    }
+
+    impl<S> tracing_subscriber::Layer<S> for SpanCapture
+    where
+        S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+    {
+        fn on_new_span(
+            &self,
+            attrs: &tracing::span::Attributes<'_>,
+            _id: &tracing::span::Id,
+            _ctx: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            let mut visitor = FieldVisitor(Vec::new());
+            attrs.record(&mut visitor);
+            self.spans
+                .lock()
+                .unwrap()
+                .push((attrs.metadata().name().to_string(), visitor.0));
+        }
+
+        fn on_enter(&self, id: &tracing::span::Id, ctx: tracing_subscriber::layer::Context<'_, S>) {
+            if let Some(span) = ctx.span(id) {
+                self.entered.lock().unwrap().push(span.name().to_string());
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_submit_price_span_carries_request_fields() {
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let capture = SpanCapture::default();
+        let subscriber = tracing_subscriber::registry().with(capture.clone());
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let service = AggregatorServiceImpl::new();
+        let mut request = price_request(70_123.5, "node-a", "kraken");
+        request.get_mut().signature = Some("secret-signature".to_string());
+        service.submit_price(request).await.unwrap();
+
+        let spans = capture.spans.lock().unwrap();
+        let (_, fields) = spans
+            .iter()
+            .find(|(name, _)| name == "submit_price")
+            .expect("submit_price span was not created");
+
+        let field = |name: &str| {
+            fields
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.as_str())
+        };
+        assert_eq!(field("node_id"), Some("node-a"));
+        assert_eq!(field("source"), Some("kraken"));
+        assert_eq!(field("price"), Some("70123.5"));
+        assert_eq!(field("pair"), Some("BTC/USD"));
+
+        // 서명 등 요청의 다른 내용은 span에 포함되지 않음 (outcome은 처리 후 기록)
+        assert_eq!(fields.len(), 4);
+        assert!(fields.iter().all(|(_, value)| !value.contains("secret")));
+
+        assert!(capture
+            .entered
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|name| name == "submit_price"));
+    }
+}
@@ -1,243 +1,392 @@
-use anyhow::Result;
-use chrono::Utc;
-use std::collections::HashMap;
+use aggregator_server::{
+    alerts,
+    attestation::AttestationSigner,
+    export,
+    onchain::OnchainConfig,
+    oracle::oracle_service_server::OracleServiceServer,
+    parquet_archive::ParquetArchiveConfig,
+    readiness::Readiness,
+    rest,
+    settings::{self, Cli, Command, Settings},
+    sink::{self, FileSink, KafkaConfig, RedisConfig, SinkConfig, SinkSender},
+    storage::{PostgresConfig, Storage},
+    telemetry, wal, webhook, AggregatorServiceImpl,
+};
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use std::path::PathBuf;
 use std::sync::Arc;
-use std::pin::Pin;
-use tokio::sync::RwLock;
-use tokio_stream::Stream;
-use tonic::{transport::Server, Request, Response, Status};
-use tracing::info;
-
-// gRPC 서버 코드 (tonic-build로 자동 생성됨)
-pub mod oracle {
-    tonic::include_proto!("oracle");
-}
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+use tonic::transport::server::TcpIncoming;
+use tracing::{info, warn};
 
-use oracle::{
-    oracle_service_server::{OracleService, OracleServiceServer},
-    AggregatedPriceUpdate, ConfigRequest, ConfigResponse, GetPriceRequest, GetPriceResponse,
-    HealthRequest, HealthResponse, PriceDataPoint, PriceRequest, PriceResponse,
-};
+#[tokio::main]
+async fn main() -> Result<()> {
+    // CLI 인수 파싱 (CLI > ORACLE_AGG_* 환경변수 > 설정 파일 > 기본값)
+    let cli = Cli::parse();
 
-// 가격 데이터 저장용 구조체
-#[derive(Clone, Debug)]
-struct PriceEntry {
-    price: f64,
-    timestamp: u64,
-    source: String,
-    node_id: String,
-}
+    let file = Settings::load_file(cli.config.as_deref())?;
+    let settings = Settings::resolve(cli.settings, file)?;
+    if cli.dump_config {
+        print!("{}", settings.dump()?);
+        return Ok(());
+    }
 
-// Aggregator 서버 상태
-struct AggregatorState {
-    prices: Vec<PriceEntry>,
-    active_nodes: HashMap<String, u64>, // node_id -> last_seen_timestamp
-}
+    // export: 서버를 띄우지 않고 WAL의 제출을 CSV로 내보낸 뒤 종료
+    if let Some(Command::Export(args)) = &cli.command {
+        telemetry::init(None)?;
+        let Some(wal_dir) = &settings.wal_dir else {
+            bail!("export reads the WAL, set wal_dir (--wal-dir or ORACLE_AGG_WAL_DIR)");
+        };
+        export::export_wal(wal_dir, args)?;
+        return Ok(());
+    }
 
-// Aggregator 서비스 구현
-pub struct AggregatorServiceImpl {
-    state: Arc<RwLock<AggregatorState>>,
-}
+    // 로깅 초기화 (otlp_endpoint가 있으면 span도 내보냄)
+    let tracer_provider = telemetry::init(settings.otlp_endpoint.as_deref())?;
+    for name in settings::legacy_env_vars() {
+        warn!(
+            "⚠️ {} is no longer read, use ORACLE_AGG_{} instead",
+            name,
+            &name[settings::LEGACY_ENV_PREFIX.len()..]
+        );
+    }
 
-impl AggregatorServiceImpl {
-    pub fn new() -> Self {
-        Self {
-            state: Arc::new(RwLock::new(AggregatorState {
-                prices: Vec::new(),
-                active_nodes: HashMap::new(),
-            })),
-        }
+    let addr = settings.listen_addr();
+    info!("🚀 Starting BTCFi Aggregator Server on {}", addr);
+
+    let config = settings.aggregator_config()?;
+    info!(
+        "🧮 Aggregation strategy: {}",
+        config.effective_strategy().name()
+    );
+    if settings.strategy.as_deref() == Some("trust-weighted-median") {
+        info!(
+            "⚖️ Trust coefficients: {:?}",
+            settings.trust_coefficients()?
+        );
+    }
+    let mut aggregator = AggregatorServiceImpl::with_config(config);
+
+    // wal_dir이 설정되면 모든 제출을 WAL로 기록
+    if let Some(wal_config) = settings.wal_config() {
+        let (sender, _writer) = wal::spawn_writer(wal_config)?;
+        aggregator = aggregator.with_wal(sender);
+    }
+
+    // kafka_brokers가 설정되면 수락된 제출과 게시된 집계를 Kafka로, redis_url이 설정되면 최신 집계를
+    // Redis로, file_sink_path가 설정되면 게시된 집계를 JSONL 파일로 전송 (여러 개면 모두)
+    let mut sink_tasks = Vec::new();
+    if let Some(kafka_config) = settings.kafka_config() {
+        let (sink, task) = spawn_kafka_sink(kafka_config, &aggregator)?;
+        aggregator = aggregator.with_sink(sink);
+        sink_tasks.push(task);
+    }
+    if let Some(redis_config) = settings.redis_config() {
+        let (sink, task) = spawn_redis_sink(redis_config, &aggregator)?;
+        aggregator = aggregator.with_sink(sink);
+        sink_tasks.push(task);
     }
+    if let Some(file_config) = settings.file_sink_config()? {
+        let path = file_config.path.clone();
+        let file_sink = FileSink::open(file_config)
+            .with_context(|| format!("Failed to open file sink {}", path.display()))?;
+        let (sink, task) = sink::spawn(file_sink, SinkConfig::default(), aggregator.subscribe());
+        aggregator = aggregator.with_sink(sink);
+        sink_tasks.push(task);
+    }
+
+    // postgres_url이 설정되면 수락된 제출과 게시된 집계를 PostgreSQL에도 기록하고,
+    // 메모리 버퍼에 없는 이전 구간의 GetPriceHistory 조회에 사용
+    if let Some(postgres_config) = settings.postgres_config()? {
+        let (sink, task, storage) = spawn_postgres_storage(postgres_config, &aggregator).await?;
+        aggregator = aggregator.with_sink(sink).with_storage(storage);
+        sink_tasks.push(task);
+    }
+
+    // webhook_urls가 설정되면 게시된 집계와 가격 급변을 HTTP POST로 알림
+    if let Some(webhook_config) = settings.webhook_config()? {
+        sink_tasks.push(webhook::spawn(
+            webhook_config,
+            aggregator.metrics().clone(),
+            aggregator.subscribe(),
+        )?);
+    }
+
+    // 경보 규칙과 Slack/Discord 웹훅이 설정되면 게시마다 규칙을 평가해 상태 변화를 알림
+    if let Some(alerts_config) = settings.alerts_config()? {
+        let (alerts, task) = alerts::spawn(
+            alerts_config,
+            aggregator.metrics().clone(),
+            aggregator.subscribe(),
+        )?;
+        aggregator = aggregator.with_alerts(alerts);
+        sink_tasks.push(task);
+    }
+
+    // onchain_rpc_url이 설정되면 가격이 임계값 이상 움직이거나 heartbeat가 지날 때 컨트랙트에 게시
+    if let Some(onchain_config) = settings.onchain_config()? {
+        sink_tasks.push(spawn_onchain_publisher(onchain_config, &aggregator)?);
+    }
+
+    // parquet_dir이 설정되면 매시 지난 시간대의 WAL 제출과 게시된 집계 결과를 Parquet로 보관
+    let parquet_task = match settings.parquet_archive_config()? {
+        Some(parquet_config) => Some(spawn_parquet_archive(parquet_config, &aggregator)?),
+        None => None,
+    };
+
+    // attestation_key가 설정되면 집계 가격에 서명 (SIGHUP을 받으면 키 파일을 다시 읽어 교체)
+    if let Some(path) = settings.attestation_key.clone() {
+        aggregator = aggregator.with_attestation_signer(AttestationSigner::load(&path)?);
+        spawn_attestation_key_reload(path, aggregator.clone())?;
+    }
+
+    // /readyz: gRPC 바인드, 저장소 디렉터리, 신선한 제출(또는 warm-up 유예 시간)을 확인
+    aggregator = aggregator.with_readiness(Readiness::new(settings.readiness_config()));
+
+    let aggregation_task = aggregator.spawn_aggregation_task();
+    let reputation_task = aggregator.spawn_reputation_decay_task();
 
-    // 중간값(median) 계산
-    async fn calculate_median_price(&self) -> Option<f64> {
-        let state = self.state.read().await;
-        let current_time = Utc::now().timestamp() as u64;
-        
-        // 최근 60초 이내의 가격 데이터만 사용
-        let recent_prices: Vec<f64> = state
-            .prices
-            .iter()
-            .filter(|p| current_time - p.timestamp < 60)
-            .map(|p| p.price)
-            .collect();
-
-        if recent_prices.is_empty() {
-            return None;
+    // snapshot_dir이 설정되면 스냅샷을 gzip으로 보관
+    let archive_task = match settings.snapshot_archive_config() {
+        Some(archive_config) => Some(aggregator.spawn_snapshot_archive_task(archive_config)?),
+        None => None,
+    };
+    let shutdown_handle = aggregator.clone();
+
+    // http_addr이 설정되면 REST/JSON 게이트웨이도 실행 (종료 알림을 받으면 함께 종료)
+    let rest_task = match settings.rest_config() {
+        Some(rest_config) => Some(rest::spawn(&rest_config, aggregator.clone()).await?.1),
+        None => None,
+    };
+
+    // tls가 켜져 있으면 TLS로 서비스 (tls_client_ca를 지정하면 클라이언트 인증서를 요구)
+    // 응답 없는 연결은 keepalive로 닫고, 오래 걸리는 요청은 시간 제한으로 끝냄
+    let transport = settings.transport_config();
+    let mut server = transport.server();
+    if let Some(tls) = settings.tls_config() {
+        server = server
+            .tls_config(tls.load().context("Failed to load TLS certificate")?)
+            .context("Invalid TLS configuration")?;
+        info!("🔐 TLS enabled ({})", tls.cert_path.display());
+        if let Some(ca) = &tls.client_ca_path {
+            info!(
+                "🔐 Requiring client certificates signed by {}",
+                ca.display()
+            );
         }
+    }
 
-        let mut sorted_prices = recent_prices.clone();
-        sorted_prices.sort_by(|a, b| a.partial_cmp(b).unwrap());
-        
-        let len = sorted_prices.len();
-        if len % 2 == 0 {
-            Some((sorted_prices[len / 2 - 1] + sorted_prices[len / 2]) / 2.0)
-        } else {
-            Some(sorted_prices[len / 2])
+    // grpc_web이 켜져 있으면 같은 포트에서 브라우저의 grpc-web 요청도 받음
+    let grpc_web = match settings.grpc_web_config() {
+        Some(grpc_web) => {
+            server = server.accept_http1(true);
+            info!("🌐 grpc-web enabled");
+            Some(grpc_web.layer()?)
         }
+        None => None,
+    };
+
+    // 바인드한 뒤에야 /readyz가 준비 상태가 될 수 있음
+    let listener = TcpListener::bind(addr)
+        .await
+        .with_context(|| format!("Failed to bind {}", addr))?;
+    let incoming = TcpIncoming::from_listener(listener, false, None)
+        .map_err(|e| anyhow::anyhow!("Failed to accept on {}: {}", addr, e))?;
+    aggregator.mark_grpc_bound();
+    info!("📡 Listening for Oracle Nodes at {}", addr);
+
+    // Ctrl+C: 먼저 /readyz를 503으로 바꾸고 shutdown_drain_secs만큼 기다린 뒤
+    // 집계를 멈추고 구독 스트림을 정상 종료한 뒤 진행 중인 요청이 끝나면 종료
+    // 메서드별 요청 시간은 REST 게이트웨이의 /metrics로 내보냄
+    server
+        .layer(tower::util::option_layer(grpc_web))
+        .layer(aggregator.metrics().grpc_layer())
+        .layer(transport.deadline_layer())
+        .add_service(OracleServiceServer::new(aggregator))
+        .serve_with_incoming_shutdown(incoming, async move {
+            if let Err(e) = tokio::signal::ctrl_c().await {
+                warn!("⚠️ Failed to listen for shutdown signal: {}", e);
+                std::future::pending::<()>().await;
+            }
+            info!("🛑 Shutdown requested");
+            shutdown_handle.readiness().drain().await;
+            aggregation_task.abort();
+            reputation_task.abort();
+            if let Some(archive_task) = &archive_task {
+                archive_task.abort();
+            }
+            if let Some(parquet_task) = &parquet_task {
+                parquet_task.abort();
+            }
+            shutdown_handle.shutdown();
+        })
+        .await?;
+
+    // 진행 중인 REST 요청이 끝날 때까지 대기
+    if let Some(rest_task) = rest_task {
+        let _ = rest_task.await;
     }
 
-    // 활성 노드 정리
-    async fn cleanup_inactive_nodes(&self) {
-        let mut state = self.state.write().await;
-        let current_time = Utc::now().timestamp() as u64;
-        
-        // 120초 이상 응답 없는 노드 제거
-        state.active_nodes.retain(|_, last_seen| {
-            current_time - *last_seen < 120
-        });
+    // 싱크, 웹훅과 경보가 남은 레코드를 보내고 flush할 때까지 대기
+    for sink_task in sink_tasks {
+        let _ = sink_task.await;
     }
-}
 
-#[tonic::async_trait]
-impl OracleService for AggregatorServiceImpl {
-    type StreamPricesStream = Pin<Box<dyn Stream<Item = Result<AggregatedPriceUpdate, Status>> + Send + 'static>>;
-    async fn submit_price(
-        &self,
-        request: Request<PriceRequest>,
-    ) -> Result<Response<PriceResponse>, Status> {
-        let price_data = request.into_inner();
-        
-        info!(
-            "📊 Received price: ${:.2} from {} ({})",
-            price_data.price, price_data.node_id, price_data.source
-        );
+    info!("👋 Aggregator stopped");
 
-        let current_time = Utc::now().timestamp() as u64;
-        
-        // 가격 데이터 저장
-        {
-            let mut state = self.state.write().await;
-            
-            // 가격 추가
-            state.prices.push(PriceEntry {
-                price: price_data.price,
-                timestamp: price_data.timestamp,
-                source: price_data.source,
-                node_id: price_data.node_id.clone(),
-            });
-            
-            // 오래된 데이터 제거 (최대 100개 유지)
-            if state.prices.len() > 100 {
-                let drain_count = state.prices.len() - 100;
-                state.prices.drain(0..drain_count);
-            }
-            
-            // 활성 노드 업데이트
-            state.active_nodes.insert(price_data.node_id, current_time);
+    // 남은 span 전송
+    if let Some(provider) = tracer_provider {
+        if let Err(e) = provider.shutdown() {
+            warn!("⚠️ Failed to flush spans: {}", e);
         }
+    }
 
-        // 비활성 노드 정리
-        self.cleanup_inactive_nodes().await;
-
-        // 중간값 계산
-        let median_price = self.calculate_median_price().await;
-        
-        let response = PriceResponse {
-            success: true,
-            message: format!("Price received successfully"),
-            aggregated_price: median_price,
-            timestamp: current_time,
-        };
+    Ok(())
+}
 
-        if let Some(price) = median_price {
-            info!("💰 Current median price: ${:.2}", price);
-        }
+#[cfg(feature = "kafka")]
+fn spawn_kafka_sink(
+    config: KafkaConfig,
+    aggregator: &AggregatorServiceImpl,
+) -> Result<(SinkSender, JoinHandle<()>)> {
+    use aggregator_server::sink::{self, KafkaProducer, KafkaSink, SinkConfig};
 
-        Ok(Response::new(response))
-    }
-
-    async fn stream_prices(
-        &self,
-        _request: Request<tonic::Streaming<PriceRequest>>,
-    ) -> Result<Response<Self::StreamPricesStream>, Status> {
-        Err(Status::unimplemented("Stream prices not implemented"))
-    }
-
-    async fn health_check(
-        &self,
-        request: Request<HealthRequest>,
-    ) -> Result<Response<HealthResponse>, Status> {
-        let req = request.into_inner();
-        let state = self.state.read().await;
-        
-        info!("🏥 Health check from: {}", req.node_id);
-        
-        let response = HealthResponse {
-            healthy: true,
-            timestamp: Utc::now().timestamp() as u64,
-            active_nodes: state.active_nodes.len() as u32,
-            version: "1.0.0".to_string(),
-        };
+    let producer = KafkaProducer::new(&config.brokers)?;
+    Ok(sink::spawn(
+        KafkaSink::new(producer, config),
+        SinkConfig::default(),
+        aggregator.subscribe(),
+    ))
+}
 
-        Ok(Response::new(response))
-    }
+#[cfg(not(feature = "kafka"))]
+fn spawn_kafka_sink(
+    _config: KafkaConfig,
+    _aggregator: &AggregatorServiceImpl,
+) -> Result<(SinkSender, JoinHandle<()>)> {
+    anyhow::bail!(
+        "kafka_brokers is set but this build has no Kafka support (build with --features kafka)"
+    )
+}
 
-    async fn update_config(
-        &self,
-        _request: Request<ConfigRequest>,
-    ) -> Result<Response<ConfigResponse>, Status> {
-        let response = ConfigResponse {
-            success: true,
-            message: "Config update not implemented".to_string(),
-        };
+#[cfg(feature = "redis")]
+fn spawn_redis_sink(
+    config: RedisConfig,
+    aggregator: &AggregatorServiceImpl,
+) -> Result<(SinkSender, JoinHandle<()>)> {
+    use aggregator_server::sink::{self, RedisConnection, RedisSink, SinkConfig};
 
-        Ok(Response::new(response))
-    }
-
-    async fn get_aggregated_price(
-        &self,
-        _request: Request<GetPriceRequest>,
-    ) -> Result<Response<GetPriceResponse>, Status> {
-        let state = self.state.read().await;
-        let current_time = Utc::now().timestamp() as u64;
-        
-        // 최근 10개 가격 데이터
-        let recent_prices: Vec<PriceDataPoint> = state
-            .prices
-            .iter()
-            .rev()
-            .take(10)
-            .map(|p| PriceDataPoint {
-                price: p.price,
-                timestamp: p.timestamp,
-                source: p.source.clone(),
-                node_id: p.node_id.clone(),
-            })
-            .collect();
-
-        let median_price = self.calculate_median_price().await.unwrap_or(0.0);
-        
-        let response = GetPriceResponse {
-            success: true,
-            aggregated_price: median_price,
-            data_points: recent_prices.len() as u32,
-            last_update: current_time,
-            recent_prices,
-        };
+    let connection = RedisConnection::new(&config.url)?;
+    Ok(sink::spawn(
+        RedisSink::new(connection, config),
+        SinkConfig::default(),
+        aggregator.subscribe(),
+    ))
+}
+
+#[cfg(not(feature = "redis"))]
+fn spawn_redis_sink(
+    _config: RedisConfig,
+    _aggregator: &AggregatorServiceImpl,
+) -> Result<(SinkSender, JoinHandle<()>)> {
+    anyhow::bail!(
+        "redis_url is set but this build has no Redis support (build with --features redis)"
+    )
+}
 
-        Ok(Response::new(response))
+#[cfg(feature = "postgres")]
+async fn spawn_postgres_storage(
+    config: PostgresConfig,
+    aggregator: &AggregatorServiceImpl,
+) -> Result<(SinkSender, JoinHandle<()>, Arc<dyn Storage>)> {
+    use aggregator_server::storage::{self, PostgresStorage};
+
+    // 데이터베이스가 내려가 있어도 시작은 하고, 마이그레이션은 처음 쓸 때 다시 시도
+    let postgres = PostgresStorage::connect_lazy(&config)?;
+    if let Err(e) = postgres.migrate().await {
+        tracing::error!(
+            "🚨 {} is unavailable, continuing in memory only: {:#}",
+            postgres.describe(),
+            e
+        );
     }
+    let storage: Arc<dyn Storage> = Arc::new(postgres);
+    let (sink, _aggregates, task) =
+        storage::spawn(storage.clone(), config.writer, aggregator.subscribe());
+    Ok((sink, task, storage))
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
-    // 로깅 초기화
-    tracing_subscriber::fmt()
-        .with_max_level(tracing::Level::INFO)
-        .init();
+#[cfg(not(feature = "postgres"))]
+async fn spawn_postgres_storage(
+    _config: PostgresConfig,
+    _aggregator: &AggregatorServiceImpl,
+) -> Result<(SinkSender, JoinHandle<()>, Arc<dyn Storage>)> {
+    anyhow::bail!(
+        "postgres_url is set but this build has no PostgreSQL support (build with --features postgres)"
+    )
+}
 
-    info!("🚀 Starting BTCFi Aggregator Server on port 50051");
+#[cfg(feature = "onchain")]
+fn spawn_onchain_publisher(
+    config: OnchainConfig,
+    aggregator: &AggregatorServiceImpl,
+) -> Result<JoinHandle<()>> {
+    use aggregator_server::onchain::{self, AlloyPriceFeed};
 
-    let addr = "127.0.0.1:50051".parse()?;
-    let aggregator = AggregatorServiceImpl::new();
+    let contract = AlloyPriceFeed::new(&config)?;
+    Ok(onchain::spawn(contract, config, aggregator.subscribe()))
+}
 
-    info!("📡 Listening for Oracle Nodes at {}", addr);
+#[cfg(not(feature = "onchain"))]
+fn spawn_onchain_publisher(
+    _config: OnchainConfig,
+    _aggregator: &AggregatorServiceImpl,
+) -> Result<JoinHandle<()>> {
+    anyhow::bail!(
+        "onchain_rpc_url is set but this build has no on-chain support (build with --features onchain)"
+    )
+}
 
-    Server::builder()
-        .add_service(OracleServiceServer::new(aggregator))
-        .serve(addr)
-        .await?;
+#[cfg(feature = "parquet")]
+fn spawn_parquet_archive(
+    config: ParquetArchiveConfig,
+    aggregator: &AggregatorServiceImpl,
+) -> Result<JoinHandle<()>> {
+    use aggregator_server::parquet_archive;
 
+    Ok(parquet_archive::spawn(config, aggregator.subscribe()))
+}
+
+#[cfg(not(feature = "parquet"))]
+fn spawn_parquet_archive(
+    _config: ParquetArchiveConfig,
+    _aggregator: &AggregatorServiceImpl,
+) -> Result<JoinHandle<()>> {
+    anyhow::bail!(
+        "parquet_dir is set but this build has no Parquet support (build with --features parquet)"
+    )
+}
+
+#[cfg(unix)]
+fn spawn_attestation_key_reload(path: PathBuf, aggregator: AggregatorServiceImpl) -> Result<()> {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut hangups = signal(SignalKind::hangup()).context("Failed to listen for SIGHUP")?;
+    tokio::spawn(async move {
+        while hangups.recv().await.is_some() {
+            // 새 키를 읽지 못하면 기존 키로 계속 서명
+            match AttestationSigner::load(&path) {
+                Ok(signer) => aggregator.rotate_attestation_signer(signer),
+                Err(e) => warn!("⚠️ Keeping the current attestation key: {:#}", e),
+            }
+        }
+    });
     Ok(())
-}
\ No newline at end of file
+}
+
+#[cfg(not(unix))]
+fn spawn_attestation_key_reload(_path: PathBuf, _aggregator: AggregatorServiceImpl) -> Result<()> {
+    Ok(())
+}
@@ -0,0 +1,399 @@
+//! Aggregator 운영 지표 (Prometheus 텍스트 형식, REST 게이트웨이의 `/metrics`)
+//!
+//! 서비스가 레지스트리 하나를 소유하고 제출 처리, 집계 게시, gRPC 요청마다 기록한다. gRPC 요청
+//! 시간은 `GrpcMetricsLayer`를 서버에 붙여 메서드별로 잰다 (스트리밍 메서드는 응답 헤더까지).
+//! 스트림 구독자 수는 구독이 살아 있는 동안 `StreamSubscriber`로 세고, 버퍼 사용량은 내보낼 때
+//! 현재 값으로 채운다.
+
+use anyhow::Result;
+use prometheus::{
+    Encoder, GaugeVec, Histogram, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, Opts,
+    Registry, TextEncoder,
+};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tonic::codegen::http;
+use tower::{Layer, Service};
+
+use crate::snapshot::AggregateSnapshot;
+use crate::wal::WalDecision;
+use crate::RejectionReason;
+
+/// 지표 이름 앞에 붙는 이름
+const NAMESPACE: &str = "oracle_aggregator";
+/// 알 수 없는 노드와 사유 코드 없는 거부에 쓰는 레이블 값
+const UNKNOWN_LABEL: &str = "unknown";
+
+/// 서비스가 소유하는 지표 레지스트리 (복제본은 같은 값 공유)
+#[derive(Debug, Clone)]
+pub struct AggregatorMetrics {
+    registry: Registry,
+    submissions: IntCounterVec,
+    rejected_submissions: IntCounterVec,
+    rejections: IntCounterVec,
+    aggregated_price: GaugeVec,
+    contributing_nodes: IntGauge,
+    aggregation_duration: Histogram,
+    stream_subscribers: IntGauge,
+    buffer_entries: IntGauge,
+    buffer_capacity: IntGauge,
+    grpc_request_duration: HistogramVec,
+    webhook_deliveries: IntCounterVec,
+    alerts: IntCounterVec,
+}
+
+impl Default for AggregatorMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AggregatorMetrics {
+    pub fn new() -> Self {
+        let registry = Registry::new();
+        let metrics = Self {
+            submissions: IntCounterVec::new(
+                opts(
+                    "submissions_total",
+                    "Price submissions received per node by result",
+                ),
+                &["node_id", "result"],
+            )
+            .expect("valid metric"),
+            rejected_submissions: IntCounterVec::new(
+                opts(
+                    "rejected_submissions_total",
+                    "Rejected price submissions per node by reason",
+                ),
+                &["node_id", "reason"],
+            )
+            .expect("valid metric"),
+            rejections: IntCounterVec::new(
+                opts("rejections_total", "Rejected price submissions by reason"),
+                &["reason"],
+            )
+            .expect("valid metric"),
+            aggregated_price: GaugeVec::new(
+                opts("aggregated_price", "Last published aggregated price (USD)"),
+                &["pair"],
+            )
+            .expect("valid metric"),
+            contributing_nodes: IntGauge::with_opts(opts(
+                "contributing_nodes",
+                "Distinct nodes in the last published aggregate",
+            ))
+            .expect("valid metric"),
+            aggregation_duration: Histogram::with_opts(
+                HistogramOpts::new(
+                    "aggregation_duration_seconds",
+                    "Duration of aggregating and publishing a snapshot",
+                )
+                .namespace(NAMESPACE),
+            )
+            .expect("valid metric"),
+            stream_subscribers: IntGauge::with_opts(opts(
+                "stream_subscribers",
+                "Subscribers to published aggregates (gRPC streams and WebSockets)",
+            ))
+            .expect("valid metric"),
+            buffer_entries: IntGauge::with_opts(opts(
+                "buffer_entries",
+                "Prices held in the aggregation buffer",
+            ))
+            .expect("valid metric"),
+            buffer_capacity: IntGauge::with_opts(opts(
+                "buffer_capacity",
+                "Maximum prices held in the aggregation buffer",
+            ))
+            .expect("valid metric"),
+            grpc_request_duration: HistogramVec::new(
+                HistogramOpts::new(
+                    "grpc_request_duration_seconds",
+                    "Duration of gRPC requests until the response headers",
+                )
+                .namespace(NAMESPACE),
+                &["method"],
+            )
+            .expect("valid metric"),
+            webhook_deliveries: IntCounterVec::new(
+                opts(
+                    "webhook_deliveries_total",
+                    "Webhook deliveries per endpoint by outcome",
+                ),
+                &["endpoint", "outcome"],
+            )
+            .expect("valid metric"),
+            alerts: IntCounterVec::new(
+                opts(
+                    "alerts_total",
+                    "Alert transitions per rule by state (firing, resolved, suppressed)",
+                ),
+                &["rule", "state"],
+            )
+            .expect("valid metric"),
+            registry,
+        };
+
+        let collectors: [Box<dyn prometheus::core::Collector>; 12] = [
+            Box::new(metrics.submissions.clone()),
+            Box::new(metrics.rejected_submissions.clone()),
+            Box::new(metrics.rejections.clone()),
+            Box::new(metrics.aggregated_price.clone()),
+            Box::new(metrics.contributing_nodes.clone()),
+            Box::new(metrics.aggregation_duration.clone()),
+            Box::new(metrics.stream_subscribers.clone()),
+            Box::new(metrics.buffer_entries.clone()),
+            Box::new(metrics.buffer_capacity.clone()),
+            Box::new(metrics.grpc_request_duration.clone()),
+            Box::new(metrics.webhook_deliveries.clone()),
+            Box::new(metrics.alerts.clone()),
+        ];
+        for collector in collectors {
+            metrics
+                .registry
+                .register(collector)
+                .expect("metric names are unique");
+        }
+        // 아직 한 번도 거부하지 않은 사유도 0으로 내보냄
+        for reason in RejectionReason::ALL {
+            metrics.rejections.with_label_values(&[reason.label()]);
+        }
+        metrics
+    }
+
+    /// 제출 하나의 처리 결과 (거부면 사유별로도 기록)
+    ///
+    /// `node_id`는 허용 목록이나 활성 노드 목록에 있는 노드일 때만 넘긴다. 없으면 `unknown`
+    /// 레이블로 묶어 아무 node_id나 보내는 요청이 시계열을 늘리지 못하게 한다.
+    pub fn record_submission(&self, node_id: Option<&str>, decision: &WalDecision) {
+        let node_id = node_id.unwrap_or(UNKNOWN_LABEL);
+        if let WalDecision::Rejected { code, .. } = decision {
+            let reason = code.map_or(UNKNOWN_LABEL, RejectionReason::label);
+            self.rejected_submissions
+                .with_label_values(&[node_id, reason])
+                .inc();
+        }
+        self.submissions
+            .with_label_values(&[node_id, decision.name()])
+            .inc();
+    }
+
+    /// 거부 사유별 횟수 (노드 구분 없이, 어떤 검증이 자주 걸리는지 보기 위함)
+    pub fn record_rejection(&self, reason: RejectionReason) {
+        self.rejections.with_label_values(&[reason.label()]).inc();
+    }
+
+    /// 게시한 집계 결과와 집계에 걸린 시간 (집계 가격이 없으면 가격 지표를 지움)
+    pub fn record_aggregation(&self, pair: &str, snapshot: &AggregateSnapshot, latency: Duration) {
+        match snapshot.aggregated_price {
+            Some(price) => self
+                .aggregated_price
+                .with_label_values(&[pair])
+                .set(price.to_f64_dollars()),
+            None => {
+                let _ = self.aggregated_price.remove_label_values(&[pair]);
+            }
+        }
+        self.contributing_nodes
+            .set(snapshot.contributing_nodes as i64);
+        self.aggregation_duration.observe(latency.as_secs_f64());
+    }
+
+    /// 스트림 구독 하나를 셈 (반환값을 버리면 구독 종료로 봄)
+    pub fn stream_subscriber(&self) -> StreamSubscriber {
+        self.stream_subscribers.inc();
+        StreamSubscriber {
+            gauge: self.stream_subscribers.clone(),
+        }
+    }
+
+    /// 집계 버퍼에 보관 중인 가격 수와 최대 크기
+    pub fn set_buffer_occupancy(&self, entries: usize, capacity: usize) {
+        self.buffer_entries.set(entries as i64);
+        self.buffer_capacity.set(capacity as i64);
+    }
+
+    /// gRPC 요청 하나의 처리 시간
+    pub fn observe_grpc_request(&self, method: &str, latency: Duration) {
+        self.grpc_request_duration
+            .with_label_values(&[method])
+            .observe(latency.as_secs_f64());
+    }
+
+    /// 웹훅 전송 하나의 결과 (delivered, retried, failed, short_circuited, dropped)
+    pub fn record_webhook_delivery(&self, endpoint: &str, outcome: &str) {
+        self.webhook_deliveries
+            .with_label_values(&[endpoint, outcome])
+            .inc();
+    }
+
+    /// 경보 규칙의 상태 변화 하나 (firing, resolved, 쿨다운으로 억제된 suppressed)
+    pub fn record_alert(&self, rule: &str, state: &str) {
+        self.alerts.with_label_values(&[rule, state]).inc();
+    }
+
+    /// gRPC 서버에 붙여 메서드별 요청 시간을 기록하는 레이어
+    pub fn grpc_layer(&self) -> GrpcMetricsLayer {
+        GrpcMetricsLayer {
+            metrics: self.clone(),
+        }
+    }
+
+    /// Prometheus 텍스트 형식으로 모든 지표 출력
+    pub fn encode(&self) -> Result<String> {
+        let mut buffer = Vec::new();
+        TextEncoder::new().encode(&self.registry.gather(), &mut buffer)?;
+        Ok(String::from_utf8(buffer)?)
+    }
+}
+
+fn opts(name: &str, help: &str) -> Opts {
+    Opts::new(name, help).namespace(NAMESPACE)
+}
+
+/// 살아 있는 스트림 구독 (gRPC stream_prices 또는 WebSocket)
+#[derive(Debug)]
+pub struct StreamSubscriber {
+    gauge: IntGauge,
+}
+
+impl Drop for StreamSubscriber {
+    fn drop(&mut self) {
+        self.gauge.dec();
+    }
+}
+
+/// gRPC 요청 시간을 기록하는 tower 레이어
+#[derive(Debug, Clone)]
+pub struct GrpcMetricsLayer {
+    metrics: AggregatorMetrics,
+}
+
+impl<S> Layer<S> for GrpcMetricsLayer {
+    type Service = GrpcMetrics<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        GrpcMetrics {
+            inner,
+            metrics: self.metrics.clone(),
+        }
+    }
+}
+
+/// `GrpcMetricsLayer`가 감싼 서비스
+#[derive(Debug, Clone)]
+pub struct GrpcMetrics<S> {
+    inner: S,
+    metrics: AggregatorMetrics,
+}
+
+impl<S, B> Service<http::Request<B>> for GrpcMetrics<S>
+where
+    S: Service<http::Request<B>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: http::Request<B>) -> Self::Future {
+        let method = grpc_method(request.uri().path()).to_string();
+        let metrics = self.metrics.clone();
+        let started = Instant::now();
+        let response = self.inner.call(request);
+        Box::pin(async move {
+            let result = response.await;
+            metrics.observe_grpc_request(&method, started.elapsed());
+            result
+        })
+    }
+}
+
+// `/oracle.OracleService/SubmitPrice` -> `SubmitPrice`
+fn grpc_method(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oracle_vm_common::Price;
+
+    #[test]
+    fn test_encodes_recorded_metrics_with_labels() {
+        let metrics = AggregatorMetrics::new();
+        metrics.record_submission(Some("node-a"), &WalDecision::Accepted);
+        metrics.record_submission(Some("node-a"), &WalDecision::Duplicate);
+        metrics.clone().record_submission(
+            Some("node-b"),
+            &WalDecision::Rejected {
+                reason: "Price must be a positive finite number".to_string(),
+                code: Some(RejectionReason::InvalidPrice),
+            },
+        );
+        for node_id in ["spoofed-1", "spoofed-2"] {
+            metrics.record_submission(
+                None,
+                &WalDecision::Rejected {
+                    reason: format!("node {} is not allowed", node_id),
+                    code: Some(RejectionReason::NodeNotAllowed),
+                },
+            );
+        }
+        let snapshot = AggregateSnapshot {
+            aggregated_price: Some(Price::from_cents(7_000_050)),
+            contributing_nodes: 3,
+            ..AggregateSnapshot::default()
+        };
+        metrics.record_rejection(RejectionReason::MissingNodeId);
+        metrics.record_aggregation("BTC/USD", &snapshot, Duration::from_millis(2));
+        metrics.set_buffer_occupancy(12, 100);
+        let subscriber = metrics.stream_subscriber();
+        let ended = metrics.stream_subscriber();
+        drop(ended);
+        metrics.observe_grpc_request(
+            grpc_method("/oracle.OracleService/SubmitPrice"),
+            Duration::from_millis(1),
+        );
+
+        let text = metrics.encode().unwrap();
+        for line in [
+            r#"oracle_aggregator_submissions_total{node_id="node-a",result="accepted"} 1"#,
+            r#"oracle_aggregator_submissions_total{node_id="node-a",result="duplicate"} 1"#,
+            r#"oracle_aggregator_submissions_total{node_id="node-b",result="rejected"} 1"#,
+            r#"oracle_aggregator_rejected_submissions_total{node_id="node-b",reason="invalid_price"} 1"#,
+            r#"oracle_aggregator_submissions_total{node_id="unknown",result="rejected"} 2"#,
+            r#"oracle_aggregator_rejected_submissions_total{node_id="unknown",reason="node_not_allowed"} 2"#,
+            r#"oracle_aggregator_rejections_total{reason="missing_node_id"} 1"#,
+            r#"oracle_aggregator_rejections_total{reason="conflict"} 0"#,
+            r#"oracle_aggregator_aggregated_price{pair="BTC/USD"} 70000.5"#,
+            "oracle_aggregator_contributing_nodes 3",
+            "oracle_aggregator_aggregation_duration_seconds_count 1",
+            "oracle_aggregator_stream_subscribers 1",
+            "oracle_aggregator_buffer_entries 12",
+            "oracle_aggregator_buffer_capacity 100",
+            r#"oracle_aggregator_grpc_request_duration_seconds_count{method="SubmitPrice"} 1"#,
+        ] {
+            assert!(text.contains(line), "missing {:?} in\n{}", line, text);
+        }
+
+        drop(subscriber);
+        assert!(metrics
+            .encode()
+            .unwrap()
+            .contains("oracle_aggregator_stream_subscribers 0"));
+
+        // 집계 가격이 없어지면 마지막 가격을 계속 내보내지 않음
+        metrics.record_aggregation("BTC/USD", &AggregateSnapshot::default(), Duration::ZERO);
+        assert!(!metrics
+            .encode()
+            .unwrap()
+            .contains("oracle_aggregator_aggregated_price{"));
+    }
+}
@@ -0,0 +1,217 @@
+use oracle_vm_common::Price;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+
+use crate::oracle::{NodeSubmission, PriceRequest};
+use crate::wal::WalDecision;
+
+/// 제출 처리 결과
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubmissionOutcome {
+    /// 저장되어 집계에 반영
+    Accepted,
+    /// 재전송된 과거 관측값 (기록만 하고 집계에는 반영하지 않음)
+    Historical,
+    /// 거부 (사유 포함)
+    Rejected { reason: String },
+}
+
+/// 노드가 보낸 제출 하나의 기록
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubmissionRecord {
+    /// 해석된 가격 (가격이 유효하지 않아 거부된 경우 None)
+    pub price: Option<Price>,
+    /// 요청의 f64 가격
+    pub reported_price: f64,
+    pub timestamp: u64,
+    pub source: String,
+    /// 서버 수신 시각
+    pub received_at: u64,
+    pub outcome: SubmissionOutcome,
+}
+
+impl SubmissionRecord {
+    /// 요청과 처리 결과로 기록 생성 (중복 제출은 이미 기록되어 있으므로 None)
+    pub fn new(
+        request: &PriceRequest,
+        price: Option<Price>,
+        received_at: u64,
+        decision: &WalDecision,
+    ) -> Option<Self> {
+        let outcome = match decision {
+            WalDecision::Accepted => SubmissionOutcome::Accepted,
+            WalDecision::Historical => SubmissionOutcome::Historical,
+            WalDecision::Rejected { reason, .. } => SubmissionOutcome::Rejected {
+                reason: reason.clone(),
+            },
+            WalDecision::Duplicate => return None,
+        };
+        Some(Self {
+            price,
+            reported_price: request.price,
+            timestamp: request.timestamp,
+            source: request.source.clone(),
+            received_at,
+            outcome,
+        })
+    }
+}
+
+impl From<&SubmissionRecord> for NodeSubmission {
+    fn from(record: &SubmissionRecord) -> Self {
+        let scaled = record.price.map(|price| price.to_scaled());
+        Self {
+            price: record
+                .price
+                .map_or(record.reported_price, |price| price.to_f64_dollars()),
+            timestamp: record.timestamp,
+            source: record.source.clone(),
+            price_scaled: scaled.map(|(mantissa, _)| mantissa),
+            price_decimals: scaled.map(|(_, decimals)| decimals),
+            accepted: !matches!(record.outcome, SubmissionOutcome::Rejected { .. }),
+            historical: record.outcome == SubmissionOutcome::Historical,
+            rejection_reason: match &record.outcome {
+                SubmissionOutcome::Rejected { reason } => Some(reason.clone()),
+                _ => None,
+            },
+            received_at: record.received_at,
+        }
+    }
+}
+
+/// 노드별 최근 제출 기록 (GetNodeHistory)
+///
+/// 가격 버퍼와 달리 거부된 제출도 남기므로, 문제가 된 노드가 무엇을 보냈고 어떻게 처리됐는지
+/// 확인할 수 있다. 노드마다 최근 `per_node`개, 최대 `max_nodes`개 노드만 보관하며 가득 차면
+/// 마지막 제출이 가장 오래된 노드부터 제거한다.
+#[derive(Debug)]
+pub struct NodeHistory {
+    nodes: HashMap<Arc<str>, VecDeque<SubmissionRecord>>,
+    per_node: usize,
+    max_nodes: usize,
+}
+
+impl NodeHistory {
+    pub fn new(per_node: usize, max_nodes: usize) -> Self {
+        Self {
+            nodes: HashMap::new(),
+            per_node: per_node.max(1),
+            max_nodes: max_nodes.max(1),
+        }
+    }
+
+    /// 노드의 제출 기록 추가 (노드 기록이 가득 차면 가장 오래된 기록 제거)
+    pub fn record(&mut self, node_id: &str, record: SubmissionRecord) {
+        if !self.nodes.contains_key(node_id) && self.nodes.len() >= self.max_nodes {
+            let oldest = self
+                .nodes
+                .iter()
+                .min_by_key(|(_, records)| records.back().map_or(0, |last| last.received_at))
+                .map(|(node_id, _)| node_id.clone());
+            if let Some(oldest) = oldest {
+                self.nodes.remove(&oldest);
+            }
+        }
+
+        let records = self
+            .nodes
+            .entry(Arc::from(node_id))
+            .or_insert_with(|| VecDeque::with_capacity(self.per_node));
+        if records.len() == self.per_node {
+            records.pop_front();
+        }
+        records.push_back(record);
+    }
+
+    /// 노드의 최근 제출 최대 `limit`개 (최신순)
+    pub fn recent(&self, node_id: &str, limit: usize) -> impl Iterator<Item = &SubmissionRecord> {
+        self.nodes
+            .get(node_id)
+            .into_iter()
+            .flat_map(|records| records.iter().rev())
+            .take(limit)
+    }
+
+    /// 기록이 있는 노드 수
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(received_at: u64, outcome: SubmissionOutcome) -> SubmissionRecord {
+        SubmissionRecord {
+            price: Some(Price::from_cents(5_000_000)),
+            reported_price: 50_000.0,
+            timestamp: received_at,
+            source: "binance".to_string(),
+            received_at,
+            outcome,
+        }
+    }
+
+    #[test]
+    fn test_keeps_latest_records_per_node_newest_first() {
+        let mut history = NodeHistory::new(3, 10);
+        for received_at in 1..=5 {
+            history.record("node-a", record(received_at, SubmissionOutcome::Accepted));
+        }
+
+        let timestamps: Vec<u64> = history
+            .recent("node-a", 10)
+            .map(|record| record.received_at)
+            .collect();
+        assert_eq!(timestamps, [5, 4, 3]);
+        assert_eq!(history.recent("node-a", 2).count(), 2);
+        assert_eq!(history.recent("unknown", 10).count(), 0);
+    }
+
+    #[test]
+    fn test_evicts_least_recently_submitting_node() {
+        let mut history = NodeHistory::new(3, 2);
+        history.record("node-a", record(1, SubmissionOutcome::Accepted));
+        history.record("node-b", record(2, SubmissionOutcome::Accepted));
+        history.record("node-a", record(3, SubmissionOutcome::Accepted));
+        history.record("node-c", record(4, SubmissionOutcome::Accepted));
+
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.recent("node-b", 10).count(), 0);
+        assert_eq!(history.recent("node-a", 10).count(), 2);
+        assert_eq!(history.recent("node-c", 10).count(), 1);
+    }
+
+    #[test]
+    fn test_submission_outcome_in_proto() {
+        let accepted = NodeSubmission::from(&record(1, SubmissionOutcome::Accepted));
+        assert!(accepted.accepted);
+        assert!(!accepted.historical);
+        assert_eq!(accepted.rejection_reason, None);
+        assert_eq!(accepted.price_scaled, Some(5_000_000));
+        assert_eq!(accepted.price_decimals, Some(2));
+
+        let historical = NodeSubmission::from(&record(1, SubmissionOutcome::Historical));
+        assert!(historical.accepted);
+        assert!(historical.historical);
+
+        let mut rejected = record(
+            1,
+            SubmissionOutcome::Rejected {
+                reason: "bad".to_string(),
+            },
+        );
+        rejected.price = None;
+        rejected.reported_price = -1.0;
+        let rejected = NodeSubmission::from(&rejected);
+        assert!(!rejected.accepted);
+        assert_eq!(rejected.rejection_reason.as_deref(), Some("bad"));
+        assert_eq!(rejected.price, -1.0);
+        assert_eq!(rejected.price_scaled, None);
+    }
+}
@@ -0,0 +1,900 @@
+//! 집계 가격을 EVM 컨트랙트에 게시하는 퍼블리셔
+//!
+//! 퍼블리셔 태스크는 집계 게시 채널을 구독해 최신 집계를 들고 있다가 `poll_interval`마다 컨트랙트의
+//! `latestPrice()`와 비교해, 가격이 `deviation_bps` 이상 움직였거나 마지막 게시 후 `heartbeat`가 지났을
+//! 때만 `updatePrice(uint256 price, uint256 timestamp)` 트랜잭션을 보낸다. 온체인 값이 임계값 안이면
+//! 가스를 쓰지 않고 건너뛴다. 가격은 `price_decimals` 자리 고정소수점, 타임스탬프는 집계 가격을
+//! 계산한 시각(Unix 초)이다.
+//!
+//! - nonce: 처음과 전송이 실패한 뒤에는 노드의 pending nonce를 읽고, 그 사이에는 직접 늘려 쓴다.
+//! - 가스 가격: 노드의 추정값을 쓰되 `max_gas_price`를 넘으면 상한으로 낮춘다.
+//! - 대기 중인 트랜잭션: 영수증이 나올 때까지 새 트랜잭션을 보내지 않고, `replace_after`가 지나도
+//!   채굴되지 않으면 같은 nonce로 가스 가격을 `gas_bump_percent`만큼 올려 다시 보낸다 (상한까지).
+//!
+//! 체인 접근은 `PriceFeedContract`로 추상화되어 있고, alloy 구현(`AlloyPriceFeed`)은 `onchain`
+//! feature로만 빌드된다.
+
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use oracle_vm_common::Price;
+
+use crate::snapshot::{AggregateSnapshot, AggregateUpdate};
+
+/// 1 gwei (wei)
+pub const GWEI: u128 = 1_000_000_000;
+
+/// 트랜잭션 서명 키를 읽을 곳
+#[derive(Clone, PartialEq)]
+pub enum SignerSource {
+    /// 16진수 개인 키 (환경변수 등)
+    PrivateKey(String),
+    /// 암호화된 JSON keystore 파일과 그 비밀번호
+    Keystore { path: PathBuf, password: String },
+}
+
+impl fmt::Debug for SignerSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PrivateKey(_) => f.write_str("PrivateKey(<redacted>)"),
+            Self::Keystore { path, .. } => f
+                .debug_struct("Keystore")
+                .field("path", path)
+                .finish_non_exhaustive(),
+        }
+    }
+}
+
+/// 온체인 퍼블리셔 설정
+#[derive(Debug, Clone, PartialEq)]
+pub struct OnchainConfig {
+    /// JSON-RPC 주소 (예: http://localhost:8545)
+    pub rpc_url: String,
+    /// 가격 컨트랙트 주소 (0x...)
+    pub contract: String,
+    pub signer: SignerSource,
+    /// 컨트랙트에 쓰는 가격의 소수 자릿수
+    pub price_decimals: u32,
+    /// 가격 변화가 없어도 이 시간이 지나면 게시 (집계 시각 기준)
+    pub heartbeat: Duration,
+    /// 온체인 가격 대비 이만큼(bp) 움직이면 게시
+    pub deviation_bps: u32,
+    /// 게시 조건과 대기 중인 트랜잭션을 확인하는 주기
+    pub poll_interval: Duration,
+    /// 가스 가격 상한 (wei)
+    pub max_gas_price: u128,
+    /// 채굴되지 않은 트랜잭션을 교체하기까지 기다리는 시간
+    pub replace_after: Duration,
+    /// 교체할 때 가스 가격 인상률 (%)
+    pub gas_bump_percent: u32,
+}
+
+impl OnchainConfig {
+    /// 기본값으로 채운 설정
+    pub fn new(rpc_url: String, contract: String, signer: SignerSource) -> Self {
+        Self {
+            rpc_url,
+            contract,
+            signer,
+            price_decimals: 8,
+            heartbeat: Duration::from_secs(3_600),
+            deviation_bps: 50,
+            poll_interval: Duration::from_secs(5),
+            max_gas_price: 200 * GWEI,
+            replace_after: Duration::from_secs(60),
+            gas_bump_percent: 20,
+        }
+    }
+}
+
+/// 컨트랙트에 저장된 가격
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OnchainPrice {
+    /// `price_decimals` 자리 고정소수점 가격
+    pub price: u128,
+    /// 가격의 집계 시각 (Unix 초)
+    pub timestamp: u64,
+}
+
+/// `updatePrice` 트랜잭션 하나
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PriceUpdateTx {
+    pub update: OnchainPrice,
+    pub nonce: u64,
+    /// 가스 가격 (wei)
+    pub gas_price: u128,
+}
+
+/// 보낸 트랜잭션의 상태
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxStatus {
+    /// 아직 영수증이 없음
+    Pending,
+    /// 채굴되어 성공
+    Confirmed,
+    /// 채굴되었지만 revert
+    Reverted,
+}
+
+/// 가격 컨트랙트와 그 컨트랙트를 부르는 계정
+#[tonic::async_trait]
+pub trait PriceFeedContract: Send + 'static {
+    /// 로그에 표시할 대상
+    fn describe(&self) -> String;
+
+    /// 컨트랙트에 저장된 가격 (아직 게시된 적이 없으면 None)
+    async fn latest(&mut self) -> Result<Option<OnchainPrice>, String>;
+
+    /// 계정의 다음 nonce (mempool의 트랜잭션 포함)
+    async fn pending_nonce(&mut self) -> Result<u64, String>;
+
+    /// 노드가 추정한 가스 가격 (wei)
+    async fn gas_price(&mut self) -> Result<u128, String>;
+
+    /// `updatePrice` 트랜잭션 전송 (트랜잭션 해시 반환)
+    async fn submit(&mut self, tx: &PriceUpdateTx) -> Result<String, String>;
+
+    /// 보낸 트랜잭션의 상태
+    async fn status(&mut self, tx_hash: &str) -> Result<TxStatus, String>;
+}
+
+/// 게시하는 이유
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Trigger {
+    /// 온체인에 가격이 없음
+    Initial,
+    /// 온체인 가격 대비 변화 (bp)
+    Deviation(f64),
+    /// 마지막 게시 후 heartbeat가 지남
+    Heartbeat,
+}
+
+impl fmt::Display for Trigger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Initial => write!(f, "initial"),
+            Self::Deviation(bps) => write!(f, "deviation {:.1} bps", bps),
+            Self::Heartbeat => write!(f, "heartbeat"),
+        }
+    }
+}
+
+/// `candidate`를 게시해야 하는지 (온체인 값보다 새롭지 않거나 임계값 안이면 None)
+pub fn trigger(
+    config: &OnchainConfig,
+    onchain: Option<OnchainPrice>,
+    candidate: OnchainPrice,
+) -> Option<Trigger> {
+    let Some(onchain) = onchain.filter(|onchain| onchain.price > 0) else {
+        return Some(Trigger::Initial);
+    };
+    if candidate.timestamp <= onchain.timestamp {
+        return None;
+    }
+
+    let bps = candidate.price.abs_diff(onchain.price) as f64 / onchain.price as f64 * 10_000.0;
+    if bps >= f64::from(config.deviation_bps) {
+        Some(Trigger::Deviation(bps))
+    } else if candidate.timestamp - onchain.timestamp >= config.heartbeat.as_secs() {
+        Some(Trigger::Heartbeat)
+    } else {
+        None
+    }
+}
+
+/// 집계 결과를 게시할 값으로 (집계 가격이 없거나 stale이면 None)
+pub fn candidate(snapshot: &AggregateSnapshot, decimals: u32) -> Option<OnchainPrice> {
+    if snapshot.stale {
+        return None;
+    }
+    let price = to_contract_units(snapshot.aggregated_price?, decimals)?;
+    Some(OnchainPrice {
+        price,
+        timestamp: snapshot.aggregated_at,
+    })
+}
+
+// `decimals` 자리 고정소수점 (자릿수를 줄이면 반올림)
+fn to_contract_units(price: Price, decimals: u32) -> Option<u128> {
+    let mantissa = u128::from(price.mantissa());
+    if decimals >= price.decimals() {
+        mantissa.checked_mul(10u128.checked_pow(decimals - price.decimals())?)
+    } else {
+        let divisor = 10u128.checked_pow(price.decimals() - decimals)?;
+        Some((mantissa + divisor / 2) / divisor)
+    }
+}
+
+/// 퍼블리셔 실행
+///
+/// `updates`는 집계 게시 구독(`AggregatorServiceImpl::subscribe`)이며, 종료 알림을 받으면 태스크가
+/// 끝난다 (이미 보낸 트랜잭션은 그대로 둠).
+pub fn spawn<C: PriceFeedContract>(
+    contract: C,
+    config: OnchainConfig,
+    updates: broadcast::Receiver<AggregateUpdate>,
+) -> JoinHandle<()> {
+    info!(
+        "⛓️ Publishing aggregates to {} (deviation {} bps, heartbeat {:?})",
+        contract.describe(),
+        config.deviation_bps,
+        config.heartbeat
+    );
+    let publisher = OnchainPublisher::new(contract, config);
+    tokio::spawn(publisher.run(updates))
+}
+
+/// 아직 채굴되지 않은 트랜잭션
+#[derive(Debug, Clone)]
+struct PendingTx {
+    hash: String,
+    tx: PriceUpdateTx,
+    sent_at: Instant,
+}
+
+struct OnchainPublisher<C> {
+    contract: C,
+    config: OnchainConfig,
+    latest: Option<Arc<AggregateSnapshot>>,
+    nonce: Option<u64>,
+    pending: Option<PendingTx>,
+}
+
+impl<C: PriceFeedContract> OnchainPublisher<C> {
+    fn new(contract: C, config: OnchainConfig) -> Self {
+        Self {
+            contract,
+            config,
+            latest: None,
+            nonce: None,
+            pending: None,
+        }
+    }
+
+    async fn run(mut self, mut updates: broadcast::Receiver<AggregateUpdate>) {
+        let mut ticker = tokio::time::interval(self.config.poll_interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            tokio::select! {
+                update = updates.recv() => match update {
+                    Ok(AggregateUpdate::Published(snapshot)) => self.latest = Some(snapshot),
+                    Ok(AggregateUpdate::Shutdown) | Err(RecvError::Closed) => break,
+                    Err(RecvError::Lagged(_)) => {}
+                },
+                _ = ticker.tick() => self.tick().await,
+            }
+        }
+        if let Some(pending) = &self.pending {
+            info!(
+                "⛓️ Stopping with transaction {} still pending (nonce {})",
+                pending.hash, pending.tx.nonce
+            );
+        }
+    }
+
+    // 대기 중인 트랜잭션을 확인하고, 없으면 게시 조건을 확인해 새로 보냄
+    async fn tick(&mut self) {
+        if self.pending.is_some() {
+            self.check_pending().await;
+            if self.pending.is_some() {
+                return;
+            }
+        }
+
+        let Some(candidate) = self
+            .latest
+            .as_deref()
+            .and_then(|snapshot| candidate(snapshot, self.config.price_decimals))
+        else {
+            return;
+        };
+        let onchain = match self.contract.latest().await {
+            Ok(onchain) => onchain,
+            Err(e) => {
+                warn!("⚠️ Failed to read on-chain price: {}", e);
+                return;
+            }
+        };
+        let Some(reason) = trigger(&self.config, onchain, candidate) else {
+            debug!(
+                "⛓️ On-chain price {:?} is within threshold of {:?}, skipping",
+                onchain, candidate
+            );
+            return;
+        };
+        self.publish(candidate, reason).await;
+    }
+
+    async fn check_pending(&mut self) {
+        let Some(pending) = self.pending.clone() else {
+            return;
+        };
+        match self.contract.status(&pending.hash).await {
+            Ok(TxStatus::Confirmed) => {
+                info!(
+                    "⛓️ Price {} at {} confirmed ({})",
+                    pending.tx.update.price, pending.tx.update.timestamp, pending.hash
+                );
+                self.pending = None;
+            }
+            Ok(TxStatus::Reverted) => {
+                warn!("❌ Price update {} reverted", pending.hash);
+                self.pending = None;
+            }
+            Ok(TxStatus::Pending) if pending.sent_at.elapsed() >= self.config.replace_after => {
+                self.replace(pending).await;
+            }
+            Ok(TxStatus::Pending) => {}
+            Err(e) => warn!("⚠️ Failed to check transaction {}: {}", pending.hash, e),
+        }
+    }
+
+    // 채굴되지 않은 트랜잭션을 같은 nonce, 더 높은 가스 가격으로 교체
+    async fn replace(&mut self, pending: PendingTx) {
+        let bumped = pending.tx.gas_price
+            + pending.tx.gas_price * u128::from(self.config.gas_bump_percent) / 100;
+        let gas_price = bumped.min(self.config.max_gas_price);
+        if gas_price <= pending.tx.gas_price {
+            debug!(
+                "⛓️ Transaction {} pending at the gas price cap, waiting",
+                pending.hash
+            );
+            return;
+        }
+
+        let tx = PriceUpdateTx {
+            gas_price,
+            ..pending.tx
+        };
+        match self.contract.submit(&tx).await {
+            Ok(hash) => {
+                warn!(
+                    "⛓️ Transaction {} not mined after {:?}, replaced by {} at {} wei",
+                    pending.hash, self.config.replace_after, hash, gas_price
+                );
+                self.pending = Some(PendingTx {
+                    hash,
+                    tx,
+                    sent_at: Instant::now(),
+                });
+            }
+            // 원래 트랜잭션이 그 사이 채굴되었을 수 있으므로 다음 확인에 맡김
+            Err(e) => warn!("⚠️ Failed to replace transaction {}: {}", pending.hash, e),
+        }
+    }
+
+    async fn publish(&mut self, update: OnchainPrice, reason: Trigger) {
+        let gas_price = match self.contract.gas_price().await {
+            Ok(estimate) if estimate > self.config.max_gas_price => {
+                warn!(
+                    "⛽ Gas price {} wei is above the cap, using {} wei",
+                    estimate, self.config.max_gas_price
+                );
+                self.config.max_gas_price
+            }
+            Ok(estimate) => estimate,
+            Err(e) => {
+                warn!("⚠️ Failed to estimate gas price: {}", e);
+                return;
+            }
+        };
+        let nonce = match self.nonce {
+            Some(nonce) => nonce,
+            None => match self.contract.pending_nonce().await {
+                Ok(nonce) => nonce,
+                Err(e) => {
+                    warn!("⚠️ Failed to read account nonce: {}", e);
+                    return;
+                }
+            },
+        };
+
+        let tx = PriceUpdateTx {
+            update,
+            nonce,
+            gas_price,
+        };
+        match self.contract.submit(&tx).await {
+            Ok(hash) => {
+                info!(
+                    "⛓️ Submitted price {} at {} ({}, nonce {}, {})",
+                    update.price, update.timestamp, reason, nonce, hash
+                );
+                self.nonce = Some(nonce + 1);
+                self.pending = Some(PendingTx {
+                    hash,
+                    tx,
+                    sent_at: Instant::now(),
+                });
+            }
+            Err(e) => {
+                // nonce가 어긋났을 수 있으므로 다음 전송 때 노드에서 다시 읽음
+                warn!("⚠️ Failed to submit price update: {}", e);
+                self.nonce = None;
+            }
+        }
+    }
+}
+
+#[cfg(feature = "onchain")]
+alloy::sol! {
+    #[sol(rpc)]
+    interface IPriceFeed {
+        function updatePrice(uint256 price, uint256 timestamp) external;
+        function latestPrice() external view returns (uint256 price, uint256 timestamp);
+    }
+}
+
+/// alloy JSON-RPC 구현
+#[cfg(feature = "onchain")]
+pub struct AlloyPriceFeed {
+    provider: alloy::providers::DynProvider,
+    contract: alloy::primitives::Address,
+    account: alloy::primitives::Address,
+}
+
+#[cfg(feature = "onchain")]
+impl AlloyPriceFeed {
+    /// `config`의 키로 서명하는 클라이언트 (연결은 첫 요청 때 이루어짐)
+    pub fn new(config: &OnchainConfig) -> anyhow::Result<Self> {
+        use alloy::providers::{Provider, ProviderBuilder};
+        use alloy::signers::local::PrivateKeySigner;
+        use anyhow::Context;
+
+        let signer = match &config.signer {
+            SignerSource::PrivateKey(key) => key
+                .trim()
+                .parse::<PrivateKeySigner>()
+                .context("Invalid on-chain private key")?,
+            SignerSource::Keystore { path, password } => {
+                PrivateKeySigner::decrypt_keystore(path, password)
+                    .with_context(|| format!("Failed to decrypt keystore {}", path.display()))?
+            }
+        };
+        let account = signer.address();
+        let contract = config
+            .contract
+            .parse()
+            .with_context(|| format!("Invalid contract address '{}'", config.contract))?;
+        let url = config
+            .rpc_url
+            .parse()
+            .with_context(|| format!("Invalid RPC URL '{}'", config.rpc_url))?;
+        let provider = ProviderBuilder::new()
+            .wallet(signer)
+            .connect_http(url)
+            .erased();
+        Ok(Self {
+            provider,
+            contract,
+            account,
+        })
+    }
+
+    /// 트랜잭션을 보내는 계정
+    pub fn account(&self) -> alloy::primitives::Address {
+        self.account
+    }
+}
+
+#[cfg(feature = "onchain")]
+#[tonic::async_trait]
+impl PriceFeedContract for AlloyPriceFeed {
+    fn describe(&self) -> String {
+        format!("contract {} (from {})", self.contract, self.account)
+    }
+
+    async fn latest(&mut self) -> Result<Option<OnchainPrice>, String> {
+        let feed = IPriceFeed::new(self.contract, &self.provider);
+        let latest = feed.latestPrice().call().await.map_err(|e| e.to_string())?;
+        let price = u128::try_from(latest.price).map_err(|e| e.to_string())?;
+        let timestamp = u64::try_from(latest.timestamp).map_err(|e| e.to_string())?;
+        Ok((price > 0).then_some(OnchainPrice { price, timestamp }))
+    }
+
+    async fn pending_nonce(&mut self) -> Result<u64, String> {
+        use alloy::providers::Provider;
+        self.provider
+            .get_transaction_count(self.account)
+            .pending()
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    async fn gas_price(&mut self) -> Result<u128, String> {
+        use alloy::providers::Provider;
+        self.provider
+            .get_gas_price()
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    async fn submit(&mut self, tx: &PriceUpdateTx) -> Result<String, String> {
+        use alloy::primitives::U256;
+        let feed = IPriceFeed::new(self.contract, &self.provider);
+        let pending = feed
+            .updatePrice(U256::from(tx.update.price), U256::from(tx.update.timestamp))
+            .from(self.account)
+            .nonce(tx.nonce)
+            .gas_price(tx.gas_price)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(pending.tx_hash().to_string())
+    }
+
+    async fn status(&mut self, tx_hash: &str) -> Result<TxStatus, String> {
+        use alloy::providers::Provider;
+        let hash = tx_hash
+            .parse::<alloy::primitives::TxHash>()
+            .map_err(|e| e.to_string())?;
+        let receipt = self
+            .provider
+            .get_transaction_receipt(hash)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(match receipt {
+            None => TxStatus::Pending,
+            Some(receipt) if receipt.status() => TxStatus::Confirmed,
+            Some(_) => TxStatus::Reverted,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// 컨트랙트 상태를 흉내 내는 mock (보낸 트랜잭션을 기록, `mine`으로 채굴)
+    #[derive(Default)]
+    struct Chain {
+        stored: Option<OnchainPrice>,
+        nonce: u64,
+        gas_price: u128,
+        sent: Vec<(String, PriceUpdateTx)>,
+        mined: Vec<String>,
+        fail_submit: bool,
+    }
+
+    impl Chain {
+        // 보낸 트랜잭션 중 `hash`를 채굴 (같은 nonce의 다른 트랜잭션은 버려짐)
+        fn mine(&mut self, hash: &str) {
+            let tx = self
+                .sent
+                .iter()
+                .find(|(sent, _)| sent == hash)
+                .map(|(_, tx)| *tx)
+                .unwrap();
+            self.stored = Some(tx.update);
+            self.nonce = self.nonce.max(tx.nonce + 1);
+            self.mined.push(hash.to_string());
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MockFeed {
+        chain: Arc<Mutex<Chain>>,
+    }
+
+    #[tonic::async_trait]
+    impl PriceFeedContract for MockFeed {
+        fn describe(&self) -> String {
+            "mock".to_string()
+        }
+
+        async fn latest(&mut self) -> Result<Option<OnchainPrice>, String> {
+            Ok(self.chain.lock().unwrap().stored)
+        }
+
+        async fn pending_nonce(&mut self) -> Result<u64, String> {
+            Ok(self.chain.lock().unwrap().nonce)
+        }
+
+        async fn gas_price(&mut self) -> Result<u128, String> {
+            Ok(self.chain.lock().unwrap().gas_price)
+        }
+
+        async fn submit(&mut self, tx: &PriceUpdateTx) -> Result<String, String> {
+            let mut chain = self.chain.lock().unwrap();
+            if chain.fail_submit {
+                return Err("nonce too low".to_string());
+            }
+            let hash = format!("0x{:02}", chain.sent.len());
+            chain.sent.push((hash.clone(), *tx));
+            Ok(hash)
+        }
+
+        async fn status(&mut self, tx_hash: &str) -> Result<TxStatus, String> {
+            let chain = self.chain.lock().unwrap();
+            Ok(if chain.mined.iter().any(|mined| mined == tx_hash) {
+                TxStatus::Confirmed
+            } else {
+                TxStatus::Pending
+            })
+        }
+    }
+
+    fn config() -> OnchainConfig {
+        OnchainConfig {
+            replace_after: Duration::ZERO,
+            ..OnchainConfig::new(
+                "http://localhost:8545".to_string(),
+                "0x0000000000000000000000000000000000000001".to_string(),
+                SignerSource::PrivateKey("0x01".to_string()),
+            )
+        }
+    }
+
+    // 8자리 고정소수점 가격
+    fn e8(dollars: f64) -> u128 {
+        (dollars * 1e8).round() as u128
+    }
+
+    fn price(price: u128, timestamp: u64) -> OnchainPrice {
+        OnchainPrice { price, timestamp }
+    }
+
+    fn snapshot(dollars: f64, aggregated_at: u64) -> Arc<AggregateSnapshot> {
+        Arc::new(AggregateSnapshot {
+            aggregated_price: Some(Price::from_f64_dollars(dollars, 2).unwrap()),
+            aggregated_at,
+            timestamp: aggregated_at,
+            ..AggregateSnapshot::default()
+        })
+    }
+
+    #[test]
+    fn test_trigger_skips_within_threshold() {
+        let config = config();
+        let onchain = Some(price(e8(70_000.0), 1_000));
+
+        assert_eq!(
+            trigger(&config, None, price(e8(70_000.0), 1_000)),
+            Some(Trigger::Initial)
+        );
+        // 0.3% 변화, heartbeat 전이면 건너뜀
+        assert_eq!(trigger(&config, onchain, price(e8(70_210.0), 1_060)), None);
+        // 0.5% 이상이면 게시
+        let Some(Trigger::Deviation(bps)) = trigger(&config, onchain, price(e8(69_600.0), 1_060))
+        else {
+            panic!("expected a deviation trigger");
+        };
+        assert!((bps - 57.14).abs() < 0.01, "{}", bps);
+        // 변화가 없어도 heartbeat가 지나면 게시
+        assert_eq!(
+            trigger(&config, onchain, price(e8(70_000.0), 4_600)),
+            Some(Trigger::Heartbeat)
+        );
+        // 온체인 값보다 오래된 집계는 게시하지 않음
+        assert_eq!(trigger(&config, onchain, price(e8(80_000.0), 1_000)), None);
+    }
+
+    #[test]
+    fn test_candidate_scales_price_and_skips_stale_aggregates() {
+        let fresh = snapshot(70_123.45, 1_000);
+        assert_eq!(candidate(&fresh, 8), Some(price(e8(70_123.45), 1_000)));
+        // 자릿수를 줄이면 반올림
+        assert_eq!(candidate(&fresh, 1), Some(price(701_235, 1_000)));
+
+        let stale = AggregateSnapshot {
+            aggregated_price: fresh.aggregated_price,
+            stale: true,
+            ..AggregateSnapshot::default()
+        };
+        assert_eq!(candidate(&stale, 8), None);
+        assert_eq!(candidate(&AggregateSnapshot::default(), 8), None);
+    }
+
+    #[tokio::test]
+    async fn test_publisher_skips_unchanged_prices_and_tracks_nonces() {
+        let feed = MockFeed::default();
+        feed.chain.lock().unwrap().nonce = 7;
+        feed.chain.lock().unwrap().gas_price = 30 * GWEI;
+        let mut publisher = OnchainPublisher::new(feed.clone(), config());
+
+        // 집계가 없으면 아무것도 보내지 않음
+        publisher.tick().await;
+        assert!(feed.chain.lock().unwrap().sent.is_empty());
+
+        publisher.latest = Some(snapshot(70_000.0, 1_000));
+        publisher.tick().await;
+        let (hash, tx) = feed.chain.lock().unwrap().sent[0].clone();
+        assert_eq!(tx.nonce, 7);
+        assert_eq!(tx.gas_price, 30 * GWEI);
+        assert_eq!(tx.update, price(e8(70_000.0), 1_000));
+
+        // 채굴되기 전에는 새 트랜잭션을 보내지 않음
+        publisher.latest = Some(snapshot(75_000.0, 1_010));
+        let mut quiet = OnchainPublisher {
+            config: OnchainConfig {
+                replace_after: Duration::from_secs(3_600),
+                ..config()
+            },
+            ..publisher
+        };
+        quiet.tick().await;
+        assert_eq!(feed.chain.lock().unwrap().sent.len(), 1);
+
+        // 채굴 후 임계값 안의 가격은 건너뛰고, 벗어나면 다음 nonce로 보냄
+        feed.chain.lock().unwrap().mine(&hash);
+        quiet.latest = Some(snapshot(70_010.0, 1_020));
+        quiet.tick().await;
+        assert!(quiet.pending.is_none());
+        assert_eq!(feed.chain.lock().unwrap().sent.len(), 1);
+
+        quiet.latest = Some(snapshot(71_000.0, 1_030));
+        quiet.tick().await;
+        let tx = feed.chain.lock().unwrap().sent[1].1;
+        assert_eq!(tx.nonce, 8);
+        assert_eq!(tx.update, price(e8(71_000.0), 1_030));
+    }
+
+    #[tokio::test]
+    async fn test_publisher_caps_gas_and_replaces_stuck_transactions() {
+        let feed = MockFeed::default();
+        feed.chain.lock().unwrap().gas_price = 500 * GWEI;
+        let config = OnchainConfig {
+            max_gas_price: 100 * GWEI,
+            ..config()
+        };
+        let mut publisher = OnchainPublisher::new(feed.clone(), config);
+        publisher.latest = Some(snapshot(70_000.0, 1_000));
+
+        // 추정값이 상한을 넘으면 상한으로 보냄
+        publisher.tick().await;
+        let first = feed.chain.lock().unwrap().sent[0].1;
+        assert_eq!(first.gas_price, 100 * GWEI);
+
+        // 상한에서 멈춘 트랜잭션은 더 올릴 수 없으므로 기다림
+        publisher.tick().await;
+        assert_eq!(feed.chain.lock().unwrap().sent.len(), 1);
+
+        // 상한 아래에서 멈춘 트랜잭션은 같은 nonce, 20% 높은 가스 가격으로 교체
+        feed.chain.lock().unwrap().gas_price = 10 * GWEI;
+        let mut publisher = OnchainPublisher::new(feed.clone(), publisher.config);
+        publisher.latest = Some(snapshot(72_000.0, 1_010));
+        publisher.nonce = Some(first.nonce + 1);
+        publisher.tick().await;
+        publisher.tick().await;
+        let chain = feed.chain.lock().unwrap();
+        let original = chain.sent[1].1;
+        let (replacement_hash, replacement) = chain.sent[2].clone();
+        assert_eq!(replacement.nonce, original.nonce);
+        assert_eq!(replacement.update, original.update);
+        assert_eq!(replacement.gas_price, 12 * GWEI);
+        assert_eq!(
+            publisher
+                .pending
+                .as_ref()
+                .map(|pending| pending.hash.clone()),
+            Some(replacement_hash)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_submission_resyncs_nonce() {
+        let feed = MockFeed::default();
+        feed.chain.lock().unwrap().nonce = 3;
+        let mut publisher = OnchainPublisher::new(feed.clone(), config());
+        publisher.nonce = Some(1);
+        publisher.latest = Some(snapshot(70_000.0, 1_000));
+
+        feed.chain.lock().unwrap().fail_submit = true;
+        publisher.tick().await;
+        assert_eq!(publisher.nonce, None);
+        assert!(publisher.pending.is_none());
+
+        feed.chain.lock().unwrap().fail_submit = false;
+        publisher.tick().await;
+        let tx = feed.chain.lock().unwrap().sent[0].1;
+        assert_eq!(tx.nonce, 3);
+        assert_eq!(publisher.nonce, Some(4));
+    }
+
+    #[test]
+    fn test_signer_debug_hides_secrets() {
+        let key = SignerSource::PrivateKey("0xdeadbeef".to_string());
+        assert!(!format!("{:?}", key).contains("deadbeef"));
+        let keystore = SignerSource::Keystore {
+            path: PathBuf::from("keys/oracle.json"),
+            password: "hunter2".to_string(),
+        };
+        let debug = format!("{:?}", keystore);
+        assert!(debug.contains("oracle.json") && !debug.contains("hunter2"));
+    }
+
+    #[cfg(feature = "onchain")]
+    const ANVIL_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    #[cfg(feature = "onchain")]
+    #[test]
+    fn test_alloy_feed_signs_with_configured_key() {
+        let config = OnchainConfig::new(
+            "http://127.0.0.1:8545".to_string(),
+            "0x5FbDB2315678afecb367f032d93F642f64180aa3".to_string(),
+            SignerSource::PrivateKey(ANVIL_KEY.to_string()),
+        );
+        let feed = AlloyPriceFeed::new(&config).unwrap();
+        assert_eq!(
+            feed.account().to_string(),
+            "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266"
+        );
+
+        let bad_key = OnchainConfig {
+            signer: SignerSource::PrivateKey("not-a-key".to_string()),
+            ..config.clone()
+        };
+        assert!(AlloyPriceFeed::new(&bad_key).is_err());
+        let missing_keystore = OnchainConfig {
+            signer: SignerSource::Keystore {
+                path: PathBuf::from("/nonexistent/keystore.json"),
+                password: "hunter2".to_string(),
+            },
+            ..config
+        };
+        assert!(AlloyPriceFeed::new(&missing_keystore).is_err());
+    }
+
+    /// 로컬 anvil에 배포한 `contracts/PriceFeed.sol`로 게시 (기본 테스트에서는 제외)
+    ///
+    /// `anvil`을 띄우고 컨트랙트를 배포한 뒤 `ORACLE_ONCHAIN_TEST_CONTRACT`에 주소를 넣고
+    /// `cargo test --features onchain -- --ignored onchain`으로 실행한다. RPC 주소는
+    /// `ORACLE_ONCHAIN_TEST_RPC_URL` (기본 http://127.0.0.1:8545), 키는 anvil의 첫 개발 계정이다.
+    #[cfg(feature = "onchain")]
+    #[tokio::test]
+    #[ignore]
+    async fn test_publishes_to_local_anvil() {
+        const ANVIL_KEY: &str =
+            "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+        let contract = std::env::var("ORACLE_ONCHAIN_TEST_CONTRACT")
+            .expect("ORACLE_ONCHAIN_TEST_CONTRACT must be a deployed PriceFeed address");
+        let rpc_url = std::env::var("ORACLE_ONCHAIN_TEST_RPC_URL")
+            .unwrap_or_else(|_| "http://127.0.0.1:8545".to_string());
+        let config = OnchainConfig {
+            poll_interval: Duration::from_millis(200),
+            ..OnchainConfig::new(
+                rpc_url,
+                contract,
+                SignerSource::PrivateKey(ANVIL_KEY.to_string()),
+            )
+        };
+        let feed = AlloyPriceFeed::new(&config).unwrap();
+        let mut publisher = OnchainPublisher::new(feed, config);
+
+        // 온체인 값보다 새로운 시각이어야 게시됨
+        let now = chrono::Utc::now().timestamp() as u64;
+        let before = publisher.contract.latest().await.unwrap();
+        publisher.latest = Some(snapshot(70_123.45, now));
+        publisher.tick().await;
+        assert!(
+            publisher.pending.is_some(),
+            "nothing submitted over {:?}",
+            before
+        );
+
+        for _ in 0..50 {
+            publisher.tick().await;
+            if publisher.pending.is_none() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(200)).await;
+        }
+        assert!(publisher.pending.is_none(), "transaction was not mined");
+        assert_eq!(
+            publisher.contract.latest().await.unwrap(),
+            Some(price(e8(70_123.45), now))
+        );
+
+        // 같은 가격은 다시 게시하지 않음
+        publisher.latest = Some(snapshot(70_123.45, now + 1));
+        publisher.tick().await;
+        assert!(publisher.pending.is_none());
+    }
+}
@@ -0,0 +1,682 @@
+//! 제출과 집계 결과의 시간별 Parquet 보관 (`parquet` feature)
+//!
+//! 매시 정각이 조금 지나면 직전 시간대(수신 시각 기준)의 WAL 제출과 그 시간대에 게시된 집계 결과를
+//! `<dir>/submissions/dt=YYYY-MM-DD/hour=HH/`와 `<dir>/aggregates/dt=YYYY-MM-DD/hour=HH/` 아래
+//! Parquet 파일로 기록한다. 기록한 파일의 행 수를 WAL에서 다시 센 값과 비교해 맞을 때만 보관된 것으로
+//! 보고, 보존 기간이 설정되면 보관이 끝난 WAL 세그먼트를 지운다. 파티션에 파일이 이미 있는 시간대는
+//! 다시 기록하지 않으므로 재시작 뒤에도 밀린 시간대를 이어서 보관한다. 집계 결과는 메모리에 모았다가
+//! 기록하므로 재시작 전 시간대의 집계 결과는 보관되지 않는다.
+
+use chrono::{DateTime, Timelike};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::snapshot::AggregateSnapshot;
+use crate::wal::WalRecord;
+use crate::{request_pair, request_price, DEFAULT_PAIR};
+
+#[cfg(feature = "parquet")]
+use {
+    crate::snapshot::AggregateUpdate,
+    crate::wal,
+    anyhow::{Context, Result},
+    arrow::array::{
+        ArrayRef, BooleanArray, RecordBatch, StringArray, TimestampSecondArray, UInt32Array,
+        UInt64Array,
+    },
+    arrow::datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit},
+    parquet::arrow::ArrowWriter,
+    parquet::basic::Compression,
+    parquet::file::properties::WriterProperties,
+    parquet::file::reader::{FileReader, SerializedFileReader},
+    std::collections::{BTreeMap, HashMap},
+    std::fs::{self, File},
+    std::sync::Arc,
+    std::time::{SystemTime, UNIX_EPOCH},
+    tokio::sync::broadcast::{self, error::RecvError},
+    tokio::task::JoinHandle,
+    tracing::{info, warn},
+};
+
+/// 제출 테이블 디렉터리 이름
+pub const SUBMISSIONS_TABLE: &str = "submissions";
+/// 집계 결과 테이블 디렉터리 이름
+pub const AGGREGATES_TABLE: &str = "aggregates";
+/// 한 파티션의 시간 (초)
+pub const HOUR_SECS: u64 = 3_600;
+/// 정각 뒤 이만큼 기다렸다가 보관 (WAL 기록 채널에 남은 직전 시간대 레코드가 기록되도록)
+pub const ARCHIVE_DELAY: Duration = Duration::from_secs(60);
+
+/// Parquet 보관 설정
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParquetArchiveConfig {
+    /// Parquet 파일 디렉터리
+    pub dir: PathBuf,
+    /// 보관할 제출을 읽는 WAL 디렉터리
+    pub wal_dir: PathBuf,
+    /// 보관이 끝난 WAL 세그먼트를 지우기까지의 기간 (None이면 지우지 않음)
+    pub wal_retention: Option<Duration>,
+}
+
+/// 제출 테이블의 한 행
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubmissionRow {
+    pub pair: String,
+    pub node_id: String,
+    pub source: String,
+    /// 고정소수점 가격 (해석할 수 없는 가격으로 거부된 제출은 None)
+    pub price_scaled: Option<u64>,
+    pub price_decimals: Option<u32>,
+    /// 노드가 보낸 관측 시각 (Unix 초)
+    pub timestamp: u64,
+    /// 서버 수신 시각 (Unix 초, 파티션 기준)
+    pub received_at: u64,
+    /// accepted, duplicate, historical, rejected
+    pub decision: String,
+    /// 상태에 반영된 순서 (저장된 제출만)
+    pub seq: Option<u64>,
+}
+
+impl From<&WalRecord> for SubmissionRow {
+    fn from(record: &WalRecord) -> Self {
+        let request = record.request.clone().into();
+        let scaled = request_price(&request).map(|price| price.to_scaled());
+        Self {
+            pair: request_pair(&request),
+            node_id: request.node_id,
+            source: request.source,
+            price_scaled: scaled.map(|(mantissa, _)| mantissa),
+            price_decimals: scaled.map(|(_, decimals)| decimals),
+            timestamp: request.timestamp,
+            received_at: record.received_at,
+            decision: record.decision.name().to_string(),
+            seq: record.seq,
+        }
+    }
+}
+
+/// 집계 결과 테이블의 한 행
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AggregateRow {
+    pub pair: String,
+    /// 고정소수점 집계 가격 (집계 가격이 없으면 None)
+    pub price_scaled: Option<u64>,
+    pub price_decimals: Option<u32>,
+    /// 집계 가격을 계산한 시각 (Unix 초)
+    pub aggregated_at: u64,
+    /// 게시 시각 (Unix 초, 파티션 기준)
+    pub published_at: u64,
+    pub stale: bool,
+    pub contributing_nodes: u64,
+    pub active_nodes: u64,
+}
+
+impl From<&AggregateSnapshot> for AggregateRow {
+    fn from(snapshot: &AggregateSnapshot) -> Self {
+        let scaled = snapshot.aggregated_price.map(|price| price.to_scaled());
+        Self {
+            pair: DEFAULT_PAIR.to_string(),
+            price_scaled: scaled.map(|(mantissa, _)| mantissa),
+            price_decimals: scaled.map(|(_, decimals)| decimals),
+            aggregated_at: snapshot.aggregated_at,
+            published_at: snapshot.timestamp,
+            stale: snapshot.stale,
+            contributing_nodes: snapshot.contributing_nodes as u64,
+            active_nodes: snapshot.active_nodes as u64,
+        }
+    }
+}
+
+/// `timestamp`가 속한 시간대의 시작 (Unix 초)
+pub fn hour_start(timestamp: u64) -> u64 {
+    timestamp - timestamp % HOUR_SECS
+}
+
+/// 시간대의 파티션 경로 (`dt=YYYY-MM-DD/hour=HH`, UTC)
+pub fn partition(hour_start: u64) -> PathBuf {
+    let time = i64::try_from(hour_start)
+        .ok()
+        .and_then(|secs| DateTime::from_timestamp(secs, 0))
+        .unwrap_or_default();
+    Path::new(&format!("dt={}", time.format("%Y-%m-%d"))).join(format!("hour={:02}", time.hour()))
+}
+
+/// 시간별 Parquet 기록기
+#[cfg(feature = "parquet")]
+#[derive(Debug)]
+pub struct ParquetArchiver {
+    config: ParquetArchiveConfig,
+    // 아직 기록하지 않은 집계 결과 (시간대 시작 -> 행)
+    aggregates: BTreeMap<u64, Vec<AggregateRow>>,
+}
+
+/// 한 번의 보관 결과
+#[cfg(feature = "parquet")]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ArchiveReport {
+    /// 제출을 보관한 시간대 (시작 시각)
+    pub hours: Vec<u64>,
+    pub submissions: usize,
+    pub aggregates: usize,
+    /// 행 수가 WAL과 맞지 않아 버린 시간대
+    pub unverified_hours: Vec<u64>,
+    pub pruned_segments: usize,
+}
+
+#[cfg(feature = "parquet")]
+impl ParquetArchiver {
+    pub fn new(config: ParquetArchiveConfig) -> Self {
+        Self {
+            config,
+            aggregates: BTreeMap::new(),
+        }
+    }
+
+    /// 게시된 집계 결과를 다음 보관 때까지 보관
+    pub fn record_aggregate(&mut self, snapshot: &AggregateSnapshot) {
+        self.aggregates
+            .entry(hour_start(snapshot.timestamp))
+            .or_default()
+            .push(AggregateRow::from(snapshot));
+    }
+
+    /// `now`가 속한 시간대 이전에 끝난 시간대를 모두 보관하고, 보존 기간이 지난 WAL 세그먼트 정리
+    pub fn archive_completed_hours(&mut self, now: u64) -> Result<ArchiveReport> {
+        let current_hour = hour_start(now);
+        let mut report = ArchiveReport::default();
+
+        // WAL을 한 번 읽어 아직 파일이 없는 지난 시간대의 제출만 모음
+        let mut archived: HashMap<u64, bool> = HashMap::new();
+        let mut pending: BTreeMap<u64, Vec<SubmissionRow>> = BTreeMap::new();
+        wal::for_each_record(&self.config.wal_dir, |record| {
+            let hour = hour_start(record.received_at);
+            if hour >= current_hour {
+                return Ok(());
+            }
+            let done = *archived
+                .entry(hour)
+                .or_insert_with(|| self.has_files(SUBMISSIONS_TABLE, hour));
+            if !done {
+                pending
+                    .entry(hour)
+                    .or_default()
+                    .push(SubmissionRow::from(&record));
+            }
+            Ok(())
+        })
+        .with_context(|| format!("Failed to read WAL in {}", self.config.wal_dir.display()))?;
+
+        let stored = if pending.is_empty() {
+            HashMap::new()
+        } else {
+            self.count_wal_records(&pending)?
+        };
+        for (hour, rows) in pending {
+            let batch = submissions_batch(&rows)?;
+            let expected = stored.get(&hour).copied().unwrap_or(0);
+            if self.write_verified(SUBMISSIONS_TABLE, hour, &batch, expected)? {
+                report.hours.push(hour);
+                report.submissions += rows.len();
+            } else {
+                report.unverified_hours.push(hour);
+            }
+        }
+
+        // 끝난 시간대의 집계 결과 (기록하지 못하면 다음 보관 때 다시 시도)
+        let current = self.aggregates.split_off(&current_hour);
+        let completed = std::mem::replace(&mut self.aggregates, current);
+        for (hour, rows) in completed {
+            let batch = aggregates_batch(&rows)?;
+            if self.write_verified(AGGREGATES_TABLE, hour, &batch, rows.len())? {
+                report.aggregates += rows.len();
+            } else {
+                self.aggregates.insert(hour, rows);
+            }
+        }
+
+        if let Some(retention) = self.config.wal_retention {
+            // 보관하지 못한 시간대와 기록 중인 세그먼트는 남김
+            let cutoff = report
+                .unverified_hours
+                .iter()
+                .copied()
+                .chain([current_hour, now.saturating_sub(retention.as_secs())])
+                .min()
+                .unwrap_or(0);
+            if let Some(active) = wal::last_segment_index(&self.config.wal_dir)? {
+                report.pruned_segments = wal::prune_segments(&self.config.wal_dir, cutoff, active)?;
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// 시간대 파티션 디렉터리
+    pub fn partition_dir(&self, table: &str, hour: u64) -> PathBuf {
+        self.config.dir.join(table).join(partition(hour))
+    }
+
+    // 파티션에 Parquet 파일이 있는지
+    fn has_files(&self, table: &str, hour: u64) -> bool {
+        parquet_files(&self.partition_dir(table, hour)).is_ok_and(|files| !files.is_empty())
+    }
+
+    // 시간대별 WAL 레코드 수를 다시 셈 (기록한 파일과 비교할 기준)
+    fn count_wal_records<T>(&self, hours: &BTreeMap<u64, T>) -> Result<HashMap<u64, usize>> {
+        let mut counts = HashMap::new();
+        wal::for_each_record(&self.config.wal_dir, |record| {
+            let hour = hour_start(record.received_at);
+            if hours.contains_key(&hour) {
+                *counts.entry(hour).or_insert(0) += 1;
+            }
+            Ok(())
+        })?;
+        Ok(counts)
+    }
+
+    // 파티션에 새 파일로 기록하고 행 수가 `expected`와 같은지 확인 (다르면 파일을 지우고 false)
+    fn write_verified(
+        &self,
+        table: &str,
+        hour: u64,
+        batch: &RecordBatch,
+        expected: usize,
+    ) -> Result<bool> {
+        let dir = self.partition_dir(table, hour);
+        fs::create_dir_all(&dir)?;
+        let path = dir.join(format!("part-{:05}.parquet", parquet_files(&dir)?.len()));
+        write_batch(&path, batch)?;
+
+        let written = row_count(&path)?;
+        if written != expected {
+            warn!(
+                "⚠️ {} has {} rows but storage has {}, discarding it",
+                path.display(),
+                written,
+                expected
+            );
+            fs::remove_file(&path)?;
+            return Ok(false);
+        }
+        Ok(true)
+    }
+}
+
+/// 제출 테이블 스키마
+#[cfg(feature = "parquet")]
+pub fn submissions_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("pair", DataType::Utf8, false),
+        Field::new("node_id", DataType::Utf8, false),
+        Field::new("source", DataType::Utf8, false),
+        Field::new("price_scaled", DataType::UInt64, true),
+        Field::new("price_decimals", DataType::UInt32, true),
+        Field::new("timestamp", utc_seconds(), false),
+        Field::new("received_at", utc_seconds(), false),
+        Field::new("decision", DataType::Utf8, false),
+        Field::new("seq", DataType::UInt64, true),
+    ]))
+}
+
+/// 집계 결과 테이블 스키마
+#[cfg(feature = "parquet")]
+pub fn aggregates_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("pair", DataType::Utf8, false),
+        Field::new("price_scaled", DataType::UInt64, true),
+        Field::new("price_decimals", DataType::UInt32, true),
+        Field::new("aggregated_at", utc_seconds(), false),
+        Field::new("published_at", utc_seconds(), false),
+        Field::new("stale", DataType::Boolean, false),
+        Field::new("contributing_nodes", DataType::UInt64, false),
+        Field::new("active_nodes", DataType::UInt64, false),
+    ]))
+}
+
+#[cfg(feature = "parquet")]
+fn utc_seconds() -> DataType {
+    DataType::Timestamp(TimeUnit::Second, Some("UTC".into()))
+}
+
+#[cfg(feature = "parquet")]
+fn timestamps(values: impl Iterator<Item = u64>) -> ArrayRef {
+    let seconds = values.map(|secs| i64::try_from(secs).unwrap_or(i64::MAX));
+    Arc::new(TimestampSecondArray::from_iter_values(seconds).with_timezone("UTC"))
+}
+
+#[cfg(feature = "parquet")]
+fn strings<'a>(values: impl Iterator<Item = &'a str>) -> ArrayRef {
+    Arc::new(StringArray::from_iter_values(values))
+}
+
+#[cfg(feature = "parquet")]
+fn submissions_batch(rows: &[SubmissionRow]) -> Result<RecordBatch> {
+    let columns: Vec<ArrayRef> = vec![
+        strings(rows.iter().map(|row| row.pair.as_str())),
+        strings(rows.iter().map(|row| row.node_id.as_str())),
+        strings(rows.iter().map(|row| row.source.as_str())),
+        Arc::new(UInt64Array::from_iter(
+            rows.iter().map(|row| row.price_scaled),
+        )),
+        Arc::new(UInt32Array::from_iter(
+            rows.iter().map(|row| row.price_decimals),
+        )),
+        timestamps(rows.iter().map(|row| row.timestamp)),
+        timestamps(rows.iter().map(|row| row.received_at)),
+        strings(rows.iter().map(|row| row.decision.as_str())),
+        Arc::new(UInt64Array::from_iter(rows.iter().map(|row| row.seq))),
+    ];
+    Ok(RecordBatch::try_new(submissions_schema(), columns)?)
+}
+
+#[cfg(feature = "parquet")]
+fn aggregates_batch(rows: &[AggregateRow]) -> Result<RecordBatch> {
+    let columns: Vec<ArrayRef> = vec![
+        strings(rows.iter().map(|row| row.pair.as_str())),
+        Arc::new(UInt64Array::from_iter(
+            rows.iter().map(|row| row.price_scaled),
+        )),
+        Arc::new(UInt32Array::from_iter(
+            rows.iter().map(|row| row.price_decimals),
+        )),
+        timestamps(rows.iter().map(|row| row.aggregated_at)),
+        timestamps(rows.iter().map(|row| row.published_at)),
+        Arc::new(BooleanArray::from_iter(
+            rows.iter().map(|row| Some(row.stale)),
+        )),
+        Arc::new(UInt64Array::from_iter_values(
+            rows.iter().map(|row| row.contributing_nodes),
+        )),
+        Arc::new(UInt64Array::from_iter_values(
+            rows.iter().map(|row| row.active_nodes),
+        )),
+    ];
+    Ok(RecordBatch::try_new(aggregates_schema(), columns)?)
+}
+
+// 임시 파일에 쓴 뒤 교체 (기록 도중 장애가 나도 온전한 파일만 남음)
+#[cfg(feature = "parquet")]
+fn write_batch(path: &Path, batch: &RecordBatch) -> Result<()> {
+    let tmp = path.with_extension("tmp");
+    let properties = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .build();
+    let mut writer = ArrowWriter::try_new(File::create(&tmp)?, batch.schema(), Some(properties))?;
+    writer.write(batch)?;
+    writer.into_inner()?.sync_all()?;
+    fs::rename(&tmp, path).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(())
+}
+
+/// Parquet 파일의 행 수 (메타데이터 기준)
+#[cfg(feature = "parquet")]
+pub fn row_count(path: &Path) -> Result<usize> {
+    let reader = SerializedFileReader::new(File::open(path)?)
+        .with_context(|| format!("Malformed Parquet file {}", path.display()))?;
+    Ok(usize::try_from(reader.metadata().file_metadata().num_rows()).unwrap_or(0))
+}
+
+/// 파티션 디렉터리의 Parquet 파일 목록 (이름 순, 디렉터리가 없으면 빈 목록)
+#[cfg(feature = "parquet")]
+pub fn parquet_files(dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "parquet"))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// Parquet 보관 태스크 시작
+///
+/// 게시된 집계 결과를 모으다가 매시 정각 `ARCHIVE_DELAY` 뒤에 지난 시간대를 기록한다. 기록
+/// 실패는 로그만 남기고 다음 시간에 다시 시도하며, 서버 종료 알림을 받으면 멈춘다.
+#[cfg(feature = "parquet")]
+pub fn spawn(
+    config: ParquetArchiveConfig,
+    updates: broadcast::Receiver<AggregateUpdate>,
+) -> JoinHandle<()> {
+    info!(
+        "🧊 Archiving hourly Parquet files to {} (WAL pruning: {:?})",
+        config.dir.display(),
+        config.wal_retention
+    );
+    tokio::spawn(run(ParquetArchiver::new(config), updates))
+}
+
+#[cfg(feature = "parquet")]
+async fn run(mut archiver: ParquetArchiver, mut updates: broadcast::Receiver<AggregateUpdate>) {
+    loop {
+        let now = unix_now();
+        let next = hour_start(now) + HOUR_SECS + ARCHIVE_DELAY.as_secs();
+        let wait = Duration::from_secs(next.saturating_sub(now));
+        let sleep = tokio::time::sleep(wait);
+        tokio::pin!(sleep);
+
+        loop {
+            tokio::select! {
+                update = updates.recv() => match update {
+                    Ok(AggregateUpdate::Published(snapshot)) => archiver.record_aggregate(&snapshot),
+                    Ok(AggregateUpdate::Shutdown) | Err(RecvError::Closed) => return,
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!("⚠️ Parquet archive missed {} aggregate(s)", skipped);
+                    }
+                },
+                _ = &mut sleep => break,
+            }
+        }
+
+        // WAL 읽기와 파일 기록은 블로킹 스레드에서
+        let (returned, result) = match tokio::task::spawn_blocking(move || {
+            let result = archiver.archive_completed_hours(unix_now());
+            (archiver, result)
+        })
+        .await
+        {
+            Ok(done) => done,
+            Err(e) => {
+                warn!("⚠️ Parquet archive task failed: {}", e);
+                return;
+            }
+        };
+        archiver = returned;
+        match result {
+            Ok(report) if report.hours.is_empty() && report.aggregates == 0 => {}
+            Ok(report) => info!(
+                "🧊 Archived {} submission(s) and {} aggregate(s) for {} hour(s), pruned {} WAL segment(s)",
+                report.submissions,
+                report.aggregates,
+                report.hours.len(),
+                report.pruned_segments
+            ),
+            Err(e) => warn!("⚠️ Failed to archive to Parquet: {:#}", e),
+        }
+    }
+}
+
+#[cfg(feature = "parquet")]
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RejectionReason;
+
+    #[test]
+    fn test_partition_is_the_utc_hour() {
+        // 2023-11-14T22:13:20Z
+        assert_eq!(hour_start(1_700_000_000), 1_699_999_200);
+        assert_eq!(
+            partition(hour_start(1_700_000_000)),
+            Path::new("dt=2023-11-14").join("hour=22")
+        );
+        assert_eq!(partition(0), Path::new("dt=1970-01-01").join("hour=00"));
+    }
+
+    #[cfg(feature = "parquet")]
+    mod files {
+        use super::super::*;
+        use crate::oracle::PriceRequest;
+        use crate::wal::{WalConfig, WalDecision, WalRequest};
+        use arrow::array::{Array, AsArray};
+        use arrow::datatypes::{TimestampSecondType, UInt64Type};
+        use oracle_vm_common::Price;
+        use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+        // 2023-11-14T22:00:00Z
+        const HOUR: u64 = 1_699_999_200;
+
+        fn temp_dir() -> PathBuf {
+            std::env::temp_dir().join(format!("aggregator-parquet-{}", uuid::Uuid::new_v4()))
+        }
+
+        fn record(node_id: &str, received_at: u64, decision: WalDecision) -> WalRecord {
+            let request = PriceRequest {
+                price: 70_000.12,
+                timestamp: received_at - 1,
+                source: "binance".to_string(),
+                node_id: node_id.to_string(),
+                symbol: Some("BTC-USD".to_string()),
+                ..PriceRequest::default()
+            };
+            WalRecord {
+                seq: matches!(decision, WalDecision::Accepted).then_some(received_at),
+                received_at,
+                request: WalRequest::from(&request),
+                clamped_from: None,
+                decision,
+                aggregate: None,
+                contributing_nodes: 0,
+            }
+        }
+
+        fn read(path: &Path) -> (SchemaRef, Vec<RecordBatch>) {
+            let builder =
+                ParquetRecordBatchReaderBuilder::try_new(File::open(path).unwrap()).unwrap();
+            let schema = builder.schema().clone();
+            let batches = builder.build().unwrap().map(Result::unwrap).collect();
+            (schema, batches)
+        }
+
+        #[tokio::test]
+        async fn test_archives_a_completed_hour_and_prunes_the_wal() {
+            let base = temp_dir();
+            let wal_dir = base.join("wal");
+            let (sender, writer) = wal::spawn_writer(WalConfig {
+                dir: wal_dir.clone(),
+                max_segment_bytes: 1_000,
+                ..WalConfig::default()
+            })
+            .unwrap();
+            let mut records: Vec<WalRecord> = (0..20)
+                .map(|i| {
+                    record(
+                        &format!("node-{}", i % 4),
+                        HOUR + i * 60,
+                        WalDecision::Accepted,
+                    )
+                })
+                .collect();
+            let mut invalid = record(
+                "node-x",
+                HOUR + 1_500,
+                WalDecision::Rejected {
+                    reason: "Price must be a positive finite number".to_string(),
+                    code: Some(RejectionReason::InvalidPrice),
+                },
+            );
+            invalid.request.price_scaled = Some(7_000_000);
+            records.push(invalid);
+            // 다음 시간대는 아직 보관하지 않음
+            records.push(record(
+                "node-a",
+                HOUR + HOUR_SECS + 5,
+                WalDecision::Accepted,
+            ));
+            for record in records {
+                sender.send(record).await;
+            }
+            drop(sender);
+            writer.await.unwrap().unwrap();
+            assert!(wal::segment_paths(&wal_dir).unwrap().len() > 2);
+
+            let mut archiver = ParquetArchiver::new(ParquetArchiveConfig {
+                dir: base.join("archive"),
+                wal_dir: wal_dir.clone(),
+                wal_retention: Some(Duration::ZERO),
+            });
+            for (offset, price) in [(10, Some(7_000_012)), (70, None), (HOUR_SECS + 10, Some(1))] {
+                archiver.record_aggregate(&AggregateSnapshot {
+                    aggregated_price: price.map(Price::from_cents),
+                    aggregated_at: HOUR + offset,
+                    contributing_nodes: 3,
+                    active_nodes: 4,
+                    timestamp: HOUR + offset,
+                    ..AggregateSnapshot::default()
+                });
+            }
+
+            let now = HOUR + HOUR_SECS + 120;
+            let report = archiver.archive_completed_hours(now).unwrap();
+            assert_eq!(report.hours, [HOUR]);
+            assert_eq!(report.submissions, 21);
+            assert_eq!(report.aggregates, 2);
+            assert!(report.unverified_hours.is_empty());
+
+            let dir = archiver.partition_dir(SUBMISSIONS_TABLE, HOUR);
+            assert!(dir.ends_with("submissions/dt=2023-11-14/hour=22"));
+            let files = parquet_files(&dir).unwrap();
+            assert_eq!(files.len(), 1);
+            let (schema, batches) = read(&files[0]);
+            assert_eq!(schema, submissions_schema());
+            assert_eq!(batches.iter().map(RecordBatch::num_rows).sum::<usize>(), 21);
+            let batch = &batches[0];
+            let column = |name: &str| batch.column_by_name(name).unwrap();
+            assert_eq!(column("pair").as_string::<i32>().value(0), "BTC/USD");
+            assert_eq!(column("node_id").as_string::<i32>().value(1), "node-1");
+            assert_eq!(
+                column("price_scaled").as_primitive::<UInt64Type>().value(0),
+                7_000_012_000_000
+            );
+            assert_eq!(
+                column("received_at")
+                    .as_primitive::<TimestampSecondType>()
+                    .value(0),
+                HOUR as i64
+            );
+            let prices = column("price_scaled").as_primitive::<UInt64Type>();
+            assert_eq!(prices.null_count(), 1);
+            assert_eq!(column("decision").as_string::<i32>().value(20), "rejected");
+
+            let aggregates =
+                parquet_files(&archiver.partition_dir(AGGREGATES_TABLE, HOUR)).unwrap();
+            let (schema, batches) = read(&aggregates[0]);
+            assert_eq!(schema, aggregates_schema());
+            assert_eq!(batches[0].num_rows(), 2);
+            assert_eq!(row_count(&aggregates[0]).unwrap(), 2);
+
+            // 보관한 시간대의 세그먼트만 지우고 다음 시간대 레코드는 남김
+            assert!(report.pruned_segments > 0);
+            let remaining = wal::read_records(&wal_dir).unwrap();
+            assert!(remaining
+                .iter()
+                .any(|record| record.received_at >= HOUR + HOUR_SECS));
+
+            // 이미 보관한 시간대는 다시 기록하지 않음
+            let again = archiver.archive_completed_hours(now).unwrap();
+            assert_eq!(again, ArchiveReport::default());
+            assert_eq!(parquet_files(&dir).unwrap().len(), 1);
+
+            fs::remove_dir_all(base).unwrap();
+        }
+    }
+}
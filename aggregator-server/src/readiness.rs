@@ -0,0 +1,345 @@
+//! 로드 밸런서와 Kubernetes 프로브용 준비 상태 (`/healthz`, `/readyz`)
+//!
+//! `/healthz`는 프로세스가 살아 있는지(이벤트 루프가 새 태스크를 바로 실행하는지)만 본다. `/readyz`는
+//! 다음을 모두 만족할 때만 200이다.
+//!
+//! - gRPC 수신 소켓이 바인드됨
+//! - 설정된 저장소 디렉터리(WAL, 스냅샷 보관 등)에 접근할 수 있음
+//! - 마지막 게시 결과에 신선한 제출이 있음, 또는 gRPC 바인드 후 `warmup_grace`가 아직 지나지 않음
+//! - 종료 중이 아님
+//!
+//! 종료할 때는 `drain`으로 먼저 `/readyz`를 503으로 바꾸고 `drain_delay`만큼 기다린 뒤 gRPC 수신을
+//! 닫으므로, 로드 밸런서가 새 연결을 보내지 않게 된 다음에 서버가 내려간다.
+
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::info;
+
+use crate::snapshot::AggregateSnapshot;
+
+/// 기본 warm-up 유예 시간 (초)
+pub const DEFAULT_WARMUP_GRACE_SECS: u64 = 60;
+
+/// 준비 상태 설정
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadinessConfig {
+    /// gRPC 바인드 후 신선한 제출이 없어도 준비된 것으로 보는 시간
+    pub warmup_grace: Duration,
+    /// 종료할 때 `/readyz`를 503으로 바꾼 뒤 gRPC 수신을 닫기까지 기다리는 시간
+    pub drain_delay: Duration,
+    /// 접근할 수 있어야 하는 저장소 디렉터리
+    pub storage: Vec<PathBuf>,
+}
+
+impl Default for ReadinessConfig {
+    fn default() -> Self {
+        Self {
+            warmup_grace: Duration::from_secs(DEFAULT_WARMUP_GRACE_SECS),
+            drain_delay: Duration::ZERO,
+            storage: Vec::new(),
+        }
+    }
+}
+
+/// 준비되지 않은 이유 (`/readyz` 응답의 reason)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotReady {
+    /// 종료 중
+    Draining,
+    /// gRPC 수신 소켓이 아직 바인드되지 않음
+    GrpcNotBound,
+    /// 저장소 디렉터리에 접근할 수 없음
+    StorageUnreachable(PathBuf),
+    /// warm-up 유예 시간이 지났는데 신선한 제출이 없음
+    NoFreshSubmissions,
+}
+
+impl fmt::Display for NotReady {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Draining => f.write_str("draining"),
+            Self::GrpcNotBound => f.write_str("grpc listener not bound"),
+            Self::StorageUnreachable(path) => {
+                write!(f, "storage {} unreachable", path.display())
+            }
+            Self::NoFreshSubmissions => f.write_str("no fresh submissions"),
+        }
+    }
+}
+
+/// 준비 상태 (복제본은 같은 상태를 공유)
+#[derive(Debug, Clone)]
+pub struct Readiness {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    config: ReadinessConfig,
+    /// gRPC 바인드 시각 (초, 0이면 아직 바인드 안 됨)
+    bound_at: AtomicU64,
+    draining: AtomicBool,
+}
+
+impl Readiness {
+    pub fn new(config: ReadinessConfig) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                config,
+                bound_at: AtomicU64::new(0),
+                draining: AtomicBool::new(false),
+            }),
+        }
+    }
+
+    pub fn config(&self) -> &ReadinessConfig {
+        &self.inner.config
+    }
+
+    /// gRPC 수신 소켓이 `now`(초)에 바인드됨 (warm-up 유예 시간은 이때부터 셈)
+    pub fn grpc_bound(&self, now: u64) {
+        self.inner.bound_at.store(now.max(1), Ordering::SeqCst);
+    }
+
+    /// 종료 중인지
+    pub fn is_draining(&self) -> bool {
+        self.inner.draining.load(Ordering::SeqCst)
+    }
+
+    /// 종료 시작: `/readyz`를 503으로 바꾸고 `drain_delay`만큼 대기
+    ///
+    /// gRPC 서버의 종료 신호 안에서 수신을 닫기 전에 호출한다.
+    pub async fn drain(&self) {
+        if self.inner.draining.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let delay = self.inner.config.drain_delay;
+        info!(
+            "🚦 Reporting not ready, closing the gRPC listener in {:?}",
+            delay
+        );
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// `now`(초)의 준비 상태 (`snapshot`은 마지막으로 게시된 집계 결과)
+    pub async fn check(&self, now: u64, snapshot: &AggregateSnapshot) -> Result<(), NotReady> {
+        if self.is_draining() {
+            return Err(NotReady::Draining);
+        }
+        let bound_at = self.inner.bound_at.load(Ordering::SeqCst);
+        if bound_at == 0 {
+            return Err(NotReady::GrpcNotBound);
+        }
+        for path in &self.inner.config.storage {
+            let reachable = tokio::fs::metadata(path)
+                .await
+                .is_ok_and(|metadata| metadata.is_dir());
+            if !reachable {
+                return Err(NotReady::StorageUnreachable(path.clone()));
+            }
+        }
+        let warming_up = now.saturating_sub(bound_at) < self.inner.config.warmup_grace.as_secs();
+        if snapshot.contributing_nodes == 0 && !warming_up {
+            return Err(NotReady::NoFreshSubmissions);
+        }
+        Ok(())
+    }
+}
+
+impl Default for Readiness {
+    fn default() -> Self {
+        Self::new(ReadinessConfig::default())
+    }
+}
+
+/// 이벤트 루프가 새 태스크를 `timeout` 안에 실행하는지 (`/healthz`)
+pub async fn event_loop_responsive(timeout: Duration) -> bool {
+    matches!(
+        tokio::time::timeout(timeout, tokio::spawn(async {})).await,
+        Ok(Ok(()))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oracle::oracle_service_client::OracleServiceClient;
+    use crate::oracle::oracle_service_server::{OracleService, OracleServiceServer};
+    use crate::oracle::{HealthRequest, PriceRequest};
+    use crate::rest::{self, RestConfig};
+    use crate::testing::ManualClock;
+    use crate::ws::WsConfig;
+    use crate::AggregatorServiceImpl;
+    use tokio::net::TcpListener;
+    use tokio::sync::oneshot;
+    use tokio_stream::wrappers::TcpListenerStream;
+    use tonic::Request;
+
+    const T0: u64 = 1_700_000_000;
+
+    fn snapshot(contributing_nodes: usize) -> AggregateSnapshot {
+        AggregateSnapshot {
+            contributing_nodes,
+            ..AggregateSnapshot::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ready_during_warmup_then_only_with_fresh_submissions() {
+        let readiness = Readiness::new(ReadinessConfig {
+            warmup_grace: Duration::from_secs(30),
+            ..ReadinessConfig::default()
+        });
+        let empty = snapshot(0);
+        assert_eq!(
+            readiness.check(T0, &empty).await,
+            Err(NotReady::GrpcNotBound)
+        );
+
+        readiness.grpc_bound(T0);
+        assert_eq!(readiness.check(T0, &empty).await, Ok(()));
+        assert_eq!(readiness.check(T0 + 29, &empty).await, Ok(()));
+        assert_eq!(
+            readiness.check(T0 + 30, &empty).await,
+            Err(NotReady::NoFreshSubmissions)
+        );
+        assert_eq!(readiness.check(T0 + 30, &snapshot(1)).await, Ok(()));
+        assert_eq!(
+            readiness.check(T0 + 600, &empty).await,
+            Err(NotReady::NoFreshSubmissions)
+        );
+
+        readiness.drain().await;
+        assert_eq!(
+            readiness.check(T0 + 600, &snapshot(3)).await,
+            Err(NotReady::Draining)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_storage_is_not_ready() {
+        let dir = std::env::temp_dir().join(format!("readiness-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let readiness = Readiness::new(ReadinessConfig {
+            storage: vec![dir.clone()],
+            ..ReadinessConfig::default()
+        });
+        readiness.grpc_bound(T0);
+        assert_eq!(readiness.check(T0, &snapshot(0)).await, Ok(()));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        let result = readiness.check(T0, &snapshot(3)).await;
+        assert_eq!(result, Err(NotReady::StorageUnreachable(dir.clone())));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            format!("storage {} unreachable", dir.display())
+        );
+    }
+
+    async fn get(addr: std::net::SocketAddr, path: &str) -> (u16, serde_json::Value) {
+        let response = reqwest::get(format!("http://{}{}", addr, path))
+            .await
+            .unwrap();
+        let status = response.status().as_u16();
+        (status, response.json().await.unwrap())
+    }
+
+    fn price_request(node_id: &str, now: u64) -> Request<PriceRequest> {
+        Request::new(PriceRequest {
+            price: 70_000.0,
+            timestamp: now,
+            source: "binance".to_string(),
+            node_id: node_id.to_string(),
+            ..PriceRequest::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn test_readyz_flips_before_grpc_listener_closes() {
+        let clock = Arc::new(ManualClock::starting_at(T0 * 1_000));
+        let readiness = Readiness::new(ReadinessConfig {
+            warmup_grace: Duration::from_secs(30),
+            drain_delay: Duration::from_millis(500),
+            ..ReadinessConfig::default()
+        });
+        let service = AggregatorServiceImpl::new()
+            .with_clock(clock.clone())
+            .with_readiness(readiness.clone());
+        let config = RestConfig {
+            addr: "127.0.0.1:0".parse().unwrap(),
+            cors_origins: Vec::new(),
+            ws: WsConfig::default(),
+        };
+        let (http_addr, rest_task) = rest::spawn(&config, service.clone()).await.unwrap();
+
+        // 바인드 전에는 살아 있지만 준비되지 않음
+        assert_eq!(get(http_addr, "/healthz").await.0, 200);
+        let (status, body) = get(http_addr, "/readyz").await;
+        assert_eq!(status, 503);
+        assert_eq!(body["reason"], "grpc listener not bound");
+
+        // main과 같은 순서: 바인드한 뒤 준비, 종료 신호를 받으면 drain 후 종료 알림
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let grpc_addr = listener.local_addr().unwrap();
+        service.mark_grpc_bound();
+        let (stop, stop_rx) = oneshot::channel::<()>();
+        let shutdown_handle = service.clone();
+        let server = tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(OracleServiceServer::new(service.clone()))
+                .serve_with_incoming_shutdown(TcpListenerStream::new(listener), async move {
+                    let _ = stop_rx.await;
+                    shutdown_handle.readiness().drain().await;
+                    shutdown_handle.shutdown();
+                }),
+        );
+        let (status, body) = get(http_addr, "/readyz").await;
+        assert_eq!((status, body["status"].as_str()), (200, Some("ready")));
+
+        // warm-up이 지나면 신선한 제출이 있어야 준비
+        clock.advance(Duration::from_secs(30));
+        assert_eq!(get(http_addr, "/readyz").await.0, 503);
+        service
+            .submit_price(price_request("node-a", T0 + 30))
+            .await
+            .unwrap();
+        service.publish_snapshot().await;
+        assert_eq!(get(http_addr, "/readyz").await.0, 200);
+
+        let mut client = OracleServiceClient::connect(format!("http://{}", grpc_addr))
+            .await
+            .unwrap();
+        stop.send(()).unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        // drain 중: readyz는 503이지만 gRPC는 아직 요청을 받음
+        let (status, body) = get(http_addr, "/readyz").await;
+        assert_eq!((status, body["reason"].as_str()), (503, Some("draining")));
+        assert_eq!(get(http_addr, "/healthz").await.0, 200);
+        client
+            .health_check(HealthRequest {
+                node_id: "probe".to_string(),
+            })
+            .await
+            .unwrap();
+        assert!(!server.is_finished());
+
+        // drain이 끝나면 gRPC와 REST가 함께 종료
+        tokio::time::timeout(Duration::from_secs(5), server)
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        rest_task.await.unwrap();
+        assert!(
+            OracleServiceClient::connect(format!("http://{}", grpc_addr))
+                .await
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,119 @@
+//! 노드 평판 (관리자가 수동으로 조정한 값, 시간이 지나면 기본값으로 회복)
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// 허용되는 최소 평판
+pub const MIN_REPUTATION: f64 = 0.0;
+/// 허용되는 최대 평판
+pub const MAX_REPUTATION: f64 = 1.0;
+/// 조정되지 않은 노드의 평판
+pub const DEFAULT_REPUTATION: f64 = 1.0;
+/// 기본값과의 차이가 절반으로 줄어드는 기본 시간 (초)
+pub const DEFAULT_REPUTATION_HALF_LIFE_SECS: u64 = 3_600;
+/// 평판 회복 태스크 실행 주기 (초)
+pub const REPUTATION_DECAY_INTERVAL_SECS: u64 = 60;
+/// 기본값과의 차이가 이보다 작아지면 완전히 회복된 것으로 보고 제거
+const RECOVERED_EPSILON: f64 = 1e-3;
+
+/// 노드별 평판 (기본값과 다른 노드만 보관)
+#[derive(Debug, Default)]
+pub struct Reputation {
+    overrides: HashMap<Arc<str>, f64>,
+    half_life_secs: Option<u64>,
+    decayed_at: Option<u64>,
+}
+
+impl Reputation {
+    /// 기본값과의 차이가 `half_life_secs`마다 절반으로 줄어드는 평판 (None이면 회복하지 않음)
+    pub fn with_half_life(half_life_secs: Option<u64>) -> Self {
+        Self {
+            half_life_secs: half_life_secs.filter(|&secs| secs > 0),
+            ..Self::default()
+        }
+    }
+
+    /// 노드의 현재 평판
+    pub fn get(&self, node_id: &str) -> f64 {
+        self.overrides
+            .get(node_id)
+            .copied()
+            .unwrap_or(DEFAULT_REPUTATION)
+    }
+
+    /// 평판을 허용 범위로 보정하여 설정하고 적용된 값을 반환
+    ///
+    /// NaN은 호출 전에 거부해야 한다.
+    pub fn set(&mut self, node_id: Arc<str>, reputation: f64) -> f64 {
+        debug_assert!(!reputation.is_nan());
+        let applied = reputation.clamp(MIN_REPUTATION, MAX_REPUTATION);
+        self.overrides.insert(node_id, applied);
+        applied
+    }
+
+    /// 평판을 기본값으로 초기화
+    pub fn reset(&mut self, node_id: &str) -> f64 {
+        self.overrides.remove(node_id);
+        DEFAULT_REPUTATION
+    }
+
+    /// 마지막 회복 이후 `now`까지 흐른 시간만큼 모든 평판을 기본값 쪽으로 지수적으로 회복
+    ///
+    /// 제출이 없는 노드도 회복된다. 기본값에 충분히 가까워진 노드는 제거하며 그 수를 반환한다.
+    /// 첫 호출은 기준 시각만 기록한다.
+    pub fn decay(&mut self, now: u64) -> usize {
+        let last = self.decayed_at.replace(now);
+        let (Some(half_life), Some(last)) = (self.half_life_secs, last) else {
+            return 0;
+        };
+        let elapsed = now.saturating_sub(last);
+        if elapsed == 0 {
+            return 0;
+        }
+
+        let factor = 0.5_f64.powf(elapsed as f64 / half_life as f64);
+        let before = self.overrides.len();
+        self.overrides.retain(|_, reputation| {
+            *reputation = DEFAULT_REPUTATION + (*reputation - DEFAULT_REPUTATION) * factor;
+            (*reputation - DEFAULT_REPUTATION).abs() >= RECOVERED_EPSILON
+        });
+        before - self.overrides.len()
+    }
+
+    /// 조정된 노드 수
+    pub fn len(&self) -> usize {
+        self.overrides.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.overrides.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decay_halves_the_gap_every_half_life() {
+        let mut reputation = Reputation::with_half_life(Some(600));
+        reputation.decay(1_000);
+        reputation.set(Arc::from("node-a"), 0.2);
+        reputation.set(Arc::from("node-b"), 0.999_5);
+
+        // 한 번의 반감기 동안 여러 번 나누어 회복해도 결과는 같음
+        for now in [1_300, 1_600] {
+            reputation.decay(now);
+        }
+        assert!((reputation.get("node-a") - 0.6).abs() < 1e-9);
+        // 거의 회복된 노드는 제거
+        assert_eq!(reputation.len(), 1);
+
+        // 반감기가 없으면 그대로 유지
+        let mut fixed = Reputation::default();
+        fixed.set(Arc::from("node-a"), 0.2);
+        fixed.decay(0);
+        fixed.decay(100_000);
+        assert_eq!(fixed.get("node-a"), 0.2);
+    }
+}
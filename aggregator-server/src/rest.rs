@@ -0,0 +1,915 @@
+//! gRPC 서버와 함께 도는 선택적 REST/JSON 게이트웨이
+//!
+//! 대시보드나 브라우저처럼 gRPC 클라이언트를 쓰기 어려운 소비자를 위해 `/v1/price`, `/v1/history`,
+//! `/v1/twap`, `/v1/nodes`, `/v1/attestation`(GetSignedPrice와 같은 서명된 집계 가격)을 JSON으로,
+//! `/v1/history.csv`로 보관 중인 가격 데이터를 CSV로, `/v1/ws`로 게시되는 집계 결과를 WebSocket으로,
+//! `/metrics`로 운영 지표를 Prometheus 형식으로, `/healthz`와 `/readyz`로 로드 밸런서와 Kubernetes
+//! 프로브용 생존/준비 상태를 제공한다. gRPC 처리기와 같은 스냅샷과 상태를 읽으므로(기록 조회는
+//! GetPriceHistory, TWAP는 GetTwap을 그대로 호출) 두 경로의 값이 어긋나지 않는다. 서비스의 종료
+//! 알림을 받으면 gRPC 서버와 함께 정상 종료한다.
+
+use anyhow::{Context, Result};
+use axum::body::Body;
+use axum::extract::rejection::QueryRejection;
+use axum::extract::{Query, State};
+use axum::http::{header, HeaderValue, Method, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Extension, Json, Router};
+use oracle_vm_common::attestation::SignedPriceAttestation;
+use oracle_vm_common::{AssetPair, Price};
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::task::JoinHandle;
+use tonic::{Code, Request, Status};
+use tower_http::cors::{AllowOrigin, CorsLayer};
+use tracing::{error, info};
+
+use crate::export;
+use crate::oracle::oracle_service_server::OracleService;
+use crate::oracle::{GetTwapRequest, PriceDataPoint, PriceHistoryRequest};
+use crate::readiness;
+use crate::ws::{self, WsConfig};
+use crate::{AggregateUpdate, AggregatorServiceImpl, DEFAULT_PAIR};
+
+/// `/healthz`에서 이벤트 루프가 새 태스크를 실행하기까지 기다리는 최대 시간
+const HEALTHZ_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// 모든 origin을 허용하는 CORS 설정값
+pub const CORS_ANY_ORIGIN: &str = "*";
+
+/// REST 게이트웨이 설정
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RestConfig {
+    /// HTTP 수신 주소 (gRPC와 다른 포트)
+    pub addr: SocketAddr,
+    /// CORS로 허용할 origin (`*`이면 모두 허용, 비어 있으면 CORS 헤더를 붙이지 않음)
+    pub cors_origins: Vec<String>,
+    /// `/v1/ws` 연결 설정
+    pub ws: WsConfig,
+}
+
+/// JSON 오류 응답 (`{"error": "..."}`)
+#[derive(Debug)]
+pub struct ApiError {
+    status: StatusCode,
+    message: String,
+}
+
+impl ApiError {
+    fn bad_request(message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::BAD_REQUEST,
+            message: message.into(),
+        }
+    }
+
+    fn not_found(message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::NOT_FOUND,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<Status> for ApiError {
+    fn from(status: Status) -> Self {
+        let code = match status.code() {
+            Code::InvalidArgument | Code::OutOfRange => StatusCode::BAD_REQUEST,
+            Code::NotFound => StatusCode::NOT_FOUND,
+            Code::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+            Code::FailedPrecondition => StatusCode::NOT_IMPLEMENTED,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        Self {
+            status: code,
+            message: status.message().to_string(),
+        }
+    }
+}
+
+impl From<QueryRejection> for ApiError {
+    fn from(rejection: QueryRejection) -> Self {
+        Self::bad_request(rejection.body_text())
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct ErrorBody {
+    pub(crate) error: String,
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (
+            self.status,
+            Json(ErrorBody {
+                error: self.message,
+            }),
+        )
+            .into_response()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PriceQuery {
+    pair: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HistoryQuery {
+    pair: Option<String>,
+    node_id: Option<String>,
+    from: Option<u64>,
+    to: Option<u64>,
+    limit: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TwapQuery {
+    pair: Option<String>,
+    from: u64,
+    to: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HistoryCsvQuery {
+    pair: Option<String>,
+    /// Unix 초 또는 RFC3339
+    from: Option<String>,
+    to: Option<String>,
+}
+
+/// 보관 중인 가격 데이터의 pair별 통계 (달러)
+#[derive(Debug, Serialize)]
+pub struct PairStats {
+    pub count: usize,
+    pub mean: Option<f64>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub last: Option<f64>,
+}
+
+/// `/v1/price` 응답
+#[derive(Debug, Serialize)]
+pub struct PriceBody {
+    pub pair: String,
+    /// 집계 가격 (달러, 집계 가격이 없으면 null)
+    pub price: Option<f64>,
+    pub price_scaled: Option<u64>,
+    pub price_decimals: Option<u32>,
+    pub p25: Option<f64>,
+    pub p75: Option<f64>,
+    /// 윈도우가 비어 마지막 집계 가격을 이어받았는지
+    pub stale: bool,
+    /// 집계 가격을 계산한 시각 (집계 가격이 없으면 null)
+    pub price_timestamp: Option<u64>,
+    /// 스냅샷 게시 시각
+    pub timestamp: u64,
+    pub contributing_nodes: usize,
+    pub active_nodes: usize,
+    pub stats: PairStats,
+}
+
+/// 가격 데이터 하나 (GetPriceHistory의 PriceDataPoint와 같은 값)
+#[derive(Debug, Serialize)]
+pub struct PricePoint {
+    pub price: f64,
+    pub price_scaled: u64,
+    pub price_decimals: u32,
+    pub timestamp: u64,
+    pub source: String,
+    pub node_id: String,
+}
+
+impl From<PriceDataPoint> for PricePoint {
+    fn from(point: PriceDataPoint) -> Self {
+        Self {
+            price: point.price,
+            price_scaled: point.price_scaled,
+            price_decimals: point.price_decimals,
+            timestamp: point.timestamp,
+            source: point.source,
+            node_id: point.node_id,
+        }
+    }
+}
+
+/// `/v1/history` 응답
+#[derive(Debug, Serialize)]
+pub struct HistoryBody {
+    pub pair: String,
+    /// 보관 중인 가격 데이터 (최신순)
+    pub prices: Vec<PricePoint>,
+}
+
+/// `/v1/twap` 응답 (GetTwap과 같은 값)
+#[derive(Debug, Serialize)]
+pub struct TwapBody {
+    pub pair: String,
+    /// 시간 가중 평균 가격 (구간에 가격이 있는 집계가 없으면 null)
+    pub twap: Option<f64>,
+    pub from: u64,
+    pub to: u64,
+    /// twap의 단위 ("dollars" 또는 "cents")
+    pub unit: String,
+}
+
+/// `/healthz`, `/readyz` 응답
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProbeBody {
+    pub status: String,
+    /// 준비되지 않은 이유
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// 활성 노드 하나
+#[derive(Debug, Serialize)]
+pub struct NodeBody {
+    pub node_id: String,
+    /// 마지막 가격 또는 헬스체크 시각
+    pub last_seen: u64,
+    /// 마지막 가격 제출 시각 (헬스체크만 보낸 노드는 null)
+    pub last_submitted: Option<u64>,
+    pub stored_prices: usize,
+    pub reputation: f64,
+}
+
+/// `/v1/nodes` 응답
+#[derive(Debug, Serialize)]
+pub struct NodesBody {
+    /// 최근에 활동한 노드부터
+    pub nodes: Vec<NodeBody>,
+}
+
+/// `/v1/price`, `/v1/history`, `/v1/history.csv`, `/v1/twap`, `/v1/nodes`, `/v1/attestation`, `/v1/ws`,
+/// `/metrics`, `/healthz`, `/readyz` 라우터 (origin이 올바른 헤더 값이 아니면 에러)
+pub fn router(service: AggregatorServiceImpl, config: &RestConfig) -> Result<Router> {
+    let router = Router::new()
+        .route("/v1/price", get(price_handler))
+        .route("/v1/history", get(history_handler))
+        .route("/v1/history.csv", get(history_csv_handler))
+        .route("/v1/twap", get(twap_handler))
+        .route("/v1/nodes", get(nodes_handler))
+        .route("/v1/attestation", get(attestation_handler))
+        .route("/v1/ws", get(ws::upgrade).layer(Extension(config.ws)))
+        .route("/metrics", get(metrics_handler))
+        .route("/healthz", get(healthz_handler))
+        .route("/readyz", get(readyz_handler))
+        .with_state(service);
+
+    Ok(match cors_layer(&config.cors_origins)? {
+        Some(cors) => router.layer(cors),
+        None => router,
+    })
+}
+
+fn cors_layer(origins: &[String]) -> Result<Option<CorsLayer>> {
+    Ok(allow_origin(origins)?.map(|allow_origin| {
+        CorsLayer::new()
+            .allow_origin(allow_origin)
+            .allow_methods([Method::GET])
+    }))
+}
+
+/// CORS로 허용할 origin (`*`이면 모두, 비어 있으면 None)
+pub(crate) fn allow_origin(origins: &[String]) -> Result<Option<AllowOrigin>> {
+    if origins.is_empty() {
+        return Ok(None);
+    }
+    if origins.iter().any(|origin| origin == CORS_ANY_ORIGIN) {
+        return Ok(Some(AllowOrigin::any()));
+    }
+    let origins = origins
+        .iter()
+        .map(|origin| {
+            HeaderValue::from_str(origin)
+                .with_context(|| format!("Invalid CORS origin {:?}", origin))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Some(AllowOrigin::list(origins)))
+}
+
+/// 요청 pair 해석 (생략하면 기본 pair, `BTC-USD`처럼 구분자가 달라도 됨)
+///
+/// 해석할 수 없으면 400, 집계하지도 않고 보관 중인 데이터도 없는 pair면 404.
+async fn resolve_pair(
+    service: &AggregatorServiceImpl,
+    pair: Option<&str>,
+) -> Result<AssetPair, ApiError> {
+    let Some(symbol) = pair else {
+        return Ok(AssetPair(DEFAULT_PAIR.to_string()));
+    };
+    let pair = AssetPair::from_symbol(symbol)
+        .ok_or_else(|| ApiError::bad_request(format!("unknown pair {:?}", symbol)))?;
+    if pair.as_str() != DEFAULT_PAIR
+        && service
+            .state
+            .read()
+            .await
+            .prices
+            .stats(pair.as_str())
+            .is_none()
+    {
+        return Err(ApiError::not_found(format!(
+            "no prices for pair {}",
+            pair.as_str()
+        )));
+    }
+    Ok(pair)
+}
+
+async fn price_handler(
+    State(service): State<AggregatorServiceImpl>,
+    query: Result<Query<PriceQuery>, QueryRejection>,
+) -> Result<Json<PriceBody>, ApiError> {
+    let Query(query) = query?;
+    let pair = resolve_pair(&service, query.pair.as_deref()).await?;
+    let stats = service.state.read().await.prices.stats(pair.as_str());
+    // GetAggregatedPrice와 같은 스냅샷 (집계는 기본 pair만)
+    let snapshot = service.snapshot();
+    let aggregated = pair.as_str() == DEFAULT_PAIR;
+    let price = snapshot.aggregated_price.filter(|_| aggregated);
+    let scaled = price.map(|price| price.to_scaled());
+    let to_dollars = |price: Option<Price>| price.map(|price| price.to_f64_dollars());
+
+    Ok(Json(PriceBody {
+        pair: pair.as_str().to_string(),
+        price: to_dollars(price),
+        price_scaled: scaled.map(|(mantissa, _)| mantissa),
+        price_decimals: scaled.map(|(_, decimals)| decimals),
+        p25: snapshot.p25.filter(|_| aggregated),
+        p75: snapshot.p75.filter(|_| aggregated),
+        stale: aggregated && snapshot.stale,
+        price_timestamp: price.map(|_| snapshot.aggregated_at),
+        timestamp: snapshot.timestamp,
+        contributing_nodes: if aggregated {
+            snapshot.contributing_nodes
+        } else {
+            0
+        },
+        active_nodes: snapshot.active_nodes,
+        stats: PairStats {
+            count: stats.as_ref().map_or(0, |stats| stats.count),
+            mean: stats.as_ref().and_then(|stats| stats.mean),
+            min: to_dollars(stats.as_ref().and_then(|stats| stats.min)),
+            max: to_dollars(stats.as_ref().and_then(|stats| stats.max)),
+            last: to_dollars(stats.as_ref().and_then(|stats| stats.last)),
+        },
+    }))
+}
+
+async fn history_handler(
+    State(service): State<AggregatorServiceImpl>,
+    query: Result<Query<HistoryQuery>, QueryRejection>,
+) -> Result<Json<HistoryBody>, ApiError> {
+    let Query(query) = query?;
+    let pair = resolve_pair(&service, query.pair.as_deref()).await?;
+    // 개수 제한과 범위 검증은 GetPriceHistory와 같음
+    let prices = service
+        .get_price_history(Request::new(PriceHistoryRequest {
+            node_id: query.node_id,
+            limit: query.limit.unwrap_or(0),
+            pair: Some(pair.as_str().to_string()),
+            from_timestamp: query.from,
+            to_timestamp: query.to,
+        }))
+        .await?
+        .into_inner()
+        .prices;
+
+    Ok(Json(HistoryBody {
+        pair: pair.as_str().to_string(),
+        prices: prices.into_iter().map(PricePoint::from).collect(),
+    }))
+}
+
+// 저장소가 없으면 501, 저장소 조회가 실패하면 503 (GetTwap의 상태 코드를 그대로 변환)
+async fn twap_handler(
+    State(service): State<AggregatorServiceImpl>,
+    query: Result<Query<TwapQuery>, QueryRejection>,
+) -> Result<Json<TwapBody>, ApiError> {
+    let Query(query) = query?;
+    let response = service
+        .get_twap(Request::new(GetTwapRequest {
+            pair: query.pair,
+            from_timestamp: query.from,
+            to_timestamp: query.to,
+        }))
+        .await?
+        .into_inner();
+
+    Ok(Json(TwapBody {
+        pair: response.pair,
+        twap: response.twap,
+        from: response.from_timestamp,
+        to: response.to_timestamp,
+        unit: response.unit,
+    }))
+}
+
+// 보관 중인 가격 데이터를 CSV로 (시각 순, 조각 단위로 읽어 보냄)
+async fn history_csv_handler(
+    State(service): State<AggregatorServiceImpl>,
+    query: Result<Query<HistoryCsvQuery>, QueryRejection>,
+) -> Result<Response, ApiError> {
+    let Query(query) = query?;
+    let pair = resolve_pair(&service, query.pair.as_deref()).await?;
+    let parse = |value: Option<&str>, default: u64| {
+        value.map_or(Ok(default), |value| {
+            export::parse_time(value).map_err(ApiError::bad_request)
+        })
+    };
+    let from = parse(query.from.as_deref(), 0)?;
+    let to = parse(query.to.as_deref(), u64::MAX)?;
+    if from > to {
+        return Err(ApiError::bad_request("from must not be after to"));
+    }
+
+    let rows = export::history_csv(service, pair, from, to, export::HISTORY_CHUNK_ROWS);
+    Ok((
+        [(header::CONTENT_TYPE, "text/csv; charset=utf-8")],
+        Body::from_stream(rows),
+    )
+        .into_response())
+}
+
+async fn nodes_handler(State(service): State<AggregatorServiceImpl>) -> Json<NodesBody> {
+    let state = service.state.read().await;
+    let mut node_ids: Vec<_> = state.active_nodes.iter_oldest_first().collect();
+    node_ids.reverse();
+    let nodes = node_ids
+        .into_iter()
+        .map(|node_id| NodeBody {
+            node_id: node_id.to_string(),
+            last_seen: state.active_nodes.last_seen(node_id).unwrap_or(0),
+            last_submitted: state.active_nodes.last_submitted(node_id),
+            stored_prices: state.prices.node_usage(node_id),
+            reputation: state.reputation.get(node_id),
+        })
+        .collect();
+    Json(NodesBody { nodes })
+}
+
+// 서명 키가 없으면 501, 아직 집계 가격이 없으면 503
+async fn attestation_handler(
+    State(service): State<AggregatorServiceImpl>,
+) -> Result<Json<SignedPriceAttestation>, ApiError> {
+    Ok(Json(service.signed_price().map_err(Status::from)?))
+}
+
+async fn metrics_handler(
+    State(service): State<AggregatorServiceImpl>,
+) -> (StatusCode, [(header::HeaderName, &'static str); 1], String) {
+    let content_type = [(header::CONTENT_TYPE, "text/plain; version=0.0.4")];
+    match service.encode_metrics().await {
+        Ok(text) => (StatusCode::OK, content_type, text),
+        Err(e) => {
+            error!("❌ Failed to encode metrics: {:#}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                content_type,
+                String::new(),
+            )
+        }
+    }
+}
+
+// 이벤트 루프가 응답하면 200, 아니면 503
+async fn healthz_handler() -> (StatusCode, Json<ProbeBody>) {
+    let (status, text) = if readiness::event_loop_responsive(HEALTHZ_TIMEOUT).await {
+        (StatusCode::OK, "ok")
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, "unresponsive")
+    };
+    let body = ProbeBody {
+        status: text.to_string(),
+        reason: None,
+    };
+    (status, Json(body))
+}
+
+// 준비되었으면 200, 아니면 이유와 함께 503 (종료 중에는 gRPC 수신을 닫기 전에 503)
+async fn readyz_handler(
+    State(service): State<AggregatorServiceImpl>,
+) -> (StatusCode, Json<ProbeBody>) {
+    match service.readiness_check().await {
+        Ok(()) => (
+            StatusCode::OK,
+            Json(ProbeBody {
+                status: "ready".to_string(),
+                reason: None,
+            }),
+        ),
+        Err(reason) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ProbeBody {
+                status: "not_ready".to_string(),
+                reason: Some(reason.to_string()),
+            }),
+        ),
+    }
+}
+
+/// REST 게이트웨이 시작 (주소에 바인드한 뒤 서비스 종료 알림까지 실행, 실제로 바인드한 주소 반환)
+pub async fn spawn(
+    config: &RestConfig,
+    service: AggregatorServiceImpl,
+) -> Result<(SocketAddr, JoinHandle<()>)> {
+    let router = router(service.clone(), config)?;
+    let listener = TcpListener::bind(config.addr)
+        .await
+        .with_context(|| format!("Failed to bind REST gateway to {}", config.addr))?;
+    let addr = listener.local_addr()?;
+    info!("🌐 REST gateway at http://{}/v1/price", addr);
+
+    let mut updates = service.subscribe();
+    let task = tokio::spawn(async move {
+        let result = axum::serve(listener, router)
+            .with_graceful_shutdown(async move {
+                loop {
+                    match updates.recv().await {
+                        Ok(AggregateUpdate::Shutdown) | Err(RecvError::Closed) => break,
+                        Ok(AggregateUpdate::Published(_)) | Err(RecvError::Lagged(_)) => {}
+                    }
+                }
+            })
+            .await;
+        if let Err(e) = result {
+            error!("❌ REST gateway failed: {}", e);
+        }
+    });
+    Ok((addr, task))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::attestation::AttestationSigner;
+    use crate::oracle::oracle_service_client::OracleServiceClient;
+    use crate::oracle::oracle_service_server::OracleServiceServer;
+    use crate::oracle::{GetPriceRequest, GetSignedPriceRequest, PriceRequest};
+    use chrono::Utc;
+    use oracle_vm_common::attestation::verify_attestation;
+    use serde_json::Value;
+    use tokio_stream::wrappers::TcpListenerStream;
+
+    pub(crate) fn price_request(price: f64, node_id: &str, symbol: &str) -> PriceRequest {
+        PriceRequest {
+            price,
+            timestamp: Utc::now().timestamp() as u64,
+            source: "binance".to_string(),
+            node_id: node_id.to_string(),
+            symbol: Some(symbol.to_string()),
+            ..PriceRequest::default()
+        }
+    }
+
+    pub(crate) fn local_config(cors_origins: Vec<String>) -> RestConfig {
+        RestConfig {
+            addr: "127.0.0.1:0".parse().unwrap(),
+            cors_origins,
+            ws: WsConfig::default(),
+        }
+    }
+
+    // gRPC 서버와 REST 게이트웨이를 같은 서비스로 띄우고 각 주소 반환
+    pub(crate) async fn start(
+        service: AggregatorServiceImpl,
+        config: RestConfig,
+    ) -> (SocketAddr, SocketAddr, JoinHandle<()>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let grpc_addr = listener.local_addr().unwrap();
+        let handle = service.clone();
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .layer(service.metrics().grpc_layer())
+                .add_service(OracleServiceServer::new(service.clone()))
+                .serve_with_incoming_shutdown(TcpListenerStream::new(listener), async move {
+                    let mut updates = handle.subscribe();
+                    while !matches!(updates.recv().await, Ok(AggregateUpdate::Shutdown)) {}
+                }),
+        );
+
+        let (http_addr, task) = spawn(&config, service).await.unwrap();
+        (grpc_addr, http_addr, task)
+    }
+
+    async fn get(addr: SocketAddr, path: &str) -> (u16, Value) {
+        let response = reqwest::get(format!("http://{}{}", addr, path))
+            .await
+            .unwrap();
+        let status = response.status().as_u16();
+        (status, response.json().await.unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_rest_gateway_serves_what_grpc_accepted() {
+        let service = AggregatorServiceImpl::new();
+        let (grpc_addr, http_addr, task) = start(service.clone(), local_config(Vec::new())).await;
+
+        let mut client = OracleServiceClient::connect(format!("http://{}", grpc_addr))
+            .await
+            .unwrap();
+        for (price, node_id) in [
+            (70_000.0, "node-a"),
+            (70_010.0, "node-b"),
+            (70_020.0, "node-c"),
+        ] {
+            client
+                .submit_price(price_request(price, node_id, "BTC-USD"))
+                .await
+                .unwrap();
+        }
+        let mut eth = price_request(3_500.0, "node-a", "ETH-USD");
+        eth.source = "kraken".to_string();
+        client.submit_price(eth).await.unwrap();
+        service.publish_snapshot().await;
+
+        let grpc = client
+            .get_aggregated_price(GetPriceRequest::default())
+            .await
+            .unwrap()
+            .into_inner();
+        let (status, price) = get(http_addr, "/v1/price?pair=BTC-USD").await;
+        assert_eq!(status, 200);
+        assert_eq!(price["pair"], "BTC/USD");
+        assert_eq!(price["price"], grpc.aggregated_price);
+        assert_eq!(price["price_scaled"], grpc.aggregated_price_scaled.unwrap());
+        assert_eq!(price["p25"], grpc.p25);
+        assert_eq!(price["p75"], grpc.p75);
+        assert_eq!(price["price_timestamp"], grpc.price_timestamp);
+        assert_eq!(price["stale"], false);
+        assert_eq!(price["contributing_nodes"], 3);
+        assert_eq!(price["stats"]["count"], 3);
+        assert_eq!(price["stats"]["max"], 70_020.0);
+
+        // 다른 pair는 집계 가격 없이 보관 통계만
+        let (status, eth) = get(http_addr, "/v1/price?pair=eth/usd").await;
+        assert_eq!(status, 200);
+        assert_eq!(eth["price"], Value::Null);
+        assert_eq!(eth["stats"]["last"], 3_500.0);
+
+        let (status, history) = get(http_addr, "/v1/history?pair=BTC-USD&limit=2").await;
+        assert_eq!(status, 200);
+        let prices = history["prices"].as_array().unwrap();
+        assert_eq!(prices.len(), 2);
+        assert_eq!(prices[0]["node_id"], "node-c");
+        assert_eq!(prices[0]["price"], 70_020.0);
+
+        let (status, nodes) = get(http_addr, "/v1/nodes").await;
+        assert_eq!(status, 200);
+        let nodes = nodes["nodes"].as_array().unwrap();
+        assert_eq!(nodes.len(), 3);
+        let node_a = nodes
+            .iter()
+            .find(|node| node["node_id"] == "node-a")
+            .unwrap();
+        assert_eq!(node_a["stored_prices"], 2);
+        assert_eq!(node_a["reputation"], 1.0);
+
+        // 종료 알림을 받으면 게이트웨이도 종료
+        service.shutdown();
+        task.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_errors_are_json_with_matching_status() {
+        let service = AggregatorServiceImpl::new();
+        let (_, http_addr, _) = start(service, local_config(Vec::new())).await;
+
+        for (path, expected) in [
+            ("/v1/price?pair=not-a-pair", 400),
+            ("/v1/price?pair=SOL-USD", 404),
+            ("/v1/history?pair=SOL-USD", 404),
+            ("/v1/history?from=20&to=10", 400),
+            ("/v1/history?limit=100000", 400),
+            ("/v1/history?limit=many", 400),
+            ("/v1/history.csv?pair=SOL-USD", 404),
+            ("/v1/history.csv?from=20&to=10", 400),
+            ("/v1/history.csv?from=yesterday", 400),
+            ("/v1/twap", 400),
+            ("/v1/twap?from=0", 501),
+        ] {
+            let (status, body) = get(http_addr, path).await;
+            assert_eq!(status, expected, "{}", path);
+            assert!(body["error"].is_string(), "{}: {}", path, body);
+        }
+
+        // 아직 집계 가격이 없어도 기본 pair는 조회 가능
+        let (status, price) = get(http_addr, "/v1/price").await;
+        assert_eq!(status, 200);
+        assert_eq!(price["price"], Value::Null);
+    }
+
+    #[tokio::test]
+    async fn test_history_csv_matches_stored_prices() {
+        let service = AggregatorServiceImpl::new();
+        let (_, http_addr, _) = start(service.clone(), local_config(Vec::new())).await;
+        let now = Utc::now().timestamp() as u64;
+        for (price, node_id, offset) in [(70_000.0, "node-a", 2), (70_010.0, "node, \"b\"", 1)] {
+            let mut request = price_request(price, node_id, "BTC-USD");
+            request.timestamp = now - offset;
+            service.submit_price(Request::new(request)).await.unwrap();
+        }
+        service
+            .submit_price(Request::new(price_request(3_500.0, "node-a", "ETH-USD")))
+            .await
+            .unwrap();
+        service.publish_snapshot().await;
+
+        let from = export::rfc3339(now - 2);
+        let response = reqwest::get(format!(
+            "http://{}/v1/history.csv?pair=BTC-USD&from={}&to={}",
+            http_addr,
+            from,
+            now + 60
+        ))
+        .await
+        .unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+        assert_eq!(
+            response.headers()["content-type"],
+            "text/csv; charset=utf-8"
+        );
+        let body = response.text().await.unwrap();
+
+        let mut reader = csv::Reader::from_reader(body.as_bytes());
+        assert_eq!(
+            reader
+                .headers()
+                .unwrap()
+                .iter()
+                .collect::<Vec<_>>()
+                .join(","),
+            export::CSV_HEADER
+        );
+        let rows: Vec<csv::StringRecord> = reader.records().map(Result::unwrap).collect();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(
+            rows[0].iter().collect::<Vec<_>>(),
+            [from.as_str(), "node-a", "binance", "70000.00000000", "true"]
+        );
+        assert_eq!(&rows[1][1], "node, \"b\"");
+        assert_eq!(&rows[1][3], "70010.00000000");
+    }
+
+    #[tokio::test]
+    async fn test_attestation_matches_grpc_and_follows_key_rotation() {
+        let service = AggregatorServiceImpl::new();
+        let (grpc_addr, http_addr, _) = start(service.clone(), local_config(Vec::new())).await;
+        let mut client = OracleServiceClient::connect(format!("http://{}", grpc_addr))
+            .await
+            .unwrap();
+
+        // 서명 키가 없으면 501, 집계 가격이 없으면 503
+        let (status, body) = get(http_addr, "/v1/attestation").await;
+        assert_eq!(status, 501, "{}", body);
+        let status = client
+            .get_signed_price(GetSignedPriceRequest {})
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), Code::FailedPrecondition);
+
+        let first = AttestationSigner::from_secret_bytes(&[1u8; 32]);
+        let first_key = [first.public_key().to_bytes()];
+        let service = service.with_attestation_signer(first);
+        let (status, _) = get(http_addr, "/v1/attestation").await;
+        assert_eq!(status, 503);
+
+        for (price, node_id) in [
+            (70_000.0, "node-a"),
+            (70_010.0, "node-b"),
+            (70_020.0, "node-c"),
+        ] {
+            client
+                .submit_price(price_request(price, node_id, "BTC-USD"))
+                .await
+                .unwrap();
+        }
+        service.publish_snapshot().await;
+
+        let (status, body) = get(http_addr, "/v1/attestation").await;
+        assert_eq!(status, 200);
+        let signed: SignedPriceAttestation = serde_json::from_value(body).unwrap();
+        let attestation = verify_attestation(&signed, &first_key).unwrap();
+        assert_eq!(attestation.pair, "BTC/USD");
+        assert_eq!(attestation.price(), service.snapshot().aggregated_price);
+        assert_eq!(attestation.price().unwrap().to_f64_dollars(), 70_010.0);
+        assert_eq!(attestation.contributing_nodes, 3);
+        assert_eq!(attestation.timestamp, service.snapshot().aggregated_at);
+
+        let grpc = client
+            .get_signed_price(GetSignedPriceRequest {})
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(SignedPriceAttestation::from(&grpc), signed);
+
+        // 교체한 키는 복제본(서버)에도 반영되고 이전 키만 신뢰하는 소비자는 거부
+        let second = AttestationSigner::from_secret_bytes(&[2u8; 32]);
+        let second_key = [second.public_key().to_bytes()];
+        service.rotate_attestation_signer(second);
+        let (_, body) = get(http_addr, "/v1/attestation").await;
+        let rotated: SignedPriceAttestation = serde_json::from_value(body).unwrap();
+        assert_ne!(rotated.key_id, signed.key_id);
+        assert_eq!(rotated.attestation, signed.attestation);
+        assert!(verify_attestation(&rotated, &first_key).is_err());
+        assert!(verify_attestation(&rotated, &second_key).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_cors_allows_configured_origins_only() {
+        let service = AggregatorServiceImpl::new();
+        let (_, http_addr, _) = start(
+            service,
+            local_config(vec!["https://dash.example".to_string()]),
+        )
+        .await;
+        let client = reqwest::Client::new();
+        let allowed_origin = |origin: &'static str| {
+            let request = client
+                .get(format!("http://{}/v1/nodes", http_addr))
+                .header("Origin", origin);
+            async move {
+                request
+                    .send()
+                    .await
+                    .unwrap()
+                    .headers()
+                    .get("access-control-allow-origin")
+                    .map(|value| value.to_str().unwrap().to_string())
+            }
+        };
+
+        assert_eq!(
+            allowed_origin("https://dash.example").await.as_deref(),
+            Some("https://dash.example")
+        );
+        assert_eq!(allowed_origin("https://evil.example").await, None);
+
+        let config = local_config(vec!["bad\norigin".to_string()]);
+        assert!(router(AggregatorServiceImpl::new(), &config).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_metrics_reflect_grpc_traffic() {
+        let service = AggregatorServiceImpl::new();
+        let (grpc_addr, http_addr, _) = start(service.clone(), local_config(Vec::new())).await;
+        let mut client = OracleServiceClient::connect(format!("http://{}", grpc_addr))
+            .await
+            .unwrap();
+
+        for node_id in ["node-a", "node-b", "node-c"] {
+            client
+                .submit_price(price_request(70_000.0, node_id, "BTC-USD"))
+                .await
+                .unwrap();
+        }
+        // 활성 노드의 거부는 노드별로, 처음 보는 노드의 거부는 unknown으로 셈
+        for node_id in ["node-a", "node-d"] {
+            let response = client
+                .submit_price(price_request(-1.0, node_id, "BTC-USD"))
+                .await
+                .unwrap();
+            assert!(!response.into_inner().success);
+        }
+        // 제출을 마친 스트림도 구독은 유지
+        let _stream = client
+            .stream_prices(tokio_stream::empty::<PriceRequest>())
+            .await
+            .unwrap();
+        service.publish_snapshot().await;
+
+        let response = reqwest::get(format!("http://{}/metrics", http_addr))
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+        assert_eq!(
+            response.headers()["content-type"],
+            "text/plain; version=0.0.4"
+        );
+        let text = response.text().await.unwrap();
+        for line in [
+            r#"oracle_aggregator_submissions_total{node_id="node-a",result="accepted"} 1"#,
+            r#"oracle_aggregator_submissions_total{node_id="node-a",result="rejected"} 1"#,
+            r#"oracle_aggregator_rejected_submissions_total{node_id="node-a",reason="invalid_price"} 1"#,
+            r#"oracle_aggregator_submissions_total{node_id="unknown",result="rejected"} 1"#,
+            r#"oracle_aggregator_rejected_submissions_total{node_id="unknown",reason="invalid_price"} 1"#,
+            r#"oracle_aggregator_aggregated_price{pair="BTC/USD"} 70000"#,
+            "oracle_aggregator_contributing_nodes 3",
+            "# TYPE oracle_aggregator_aggregation_duration_seconds histogram",
+            "oracle_aggregator_stream_subscribers 1",
+            "oracle_aggregator_buffer_entries 3",
+            "oracle_aggregator_buffer_capacity 100",
+            r#"oracle_aggregator_grpc_request_duration_seconds_count{method="SubmitPrice"} 5"#,
+            r#"oracle_aggregator_grpc_request_duration_seconds_count{method="StreamPrices"} 1"#,
+        ] {
+            assert!(text.contains(line), "missing {:?} in\n{}", line, text);
+        }
+    }
+}
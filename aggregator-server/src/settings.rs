@@ -0,0 +1,1850 @@
+//! Aggregator 실행 설정: 기본값 < 설정 파일 < `ORACLE_AGG_*` 환경변수 < CLI 인수
+//!
+//! 설정 파일(TOML)의 항목 이름에서 CLI 인수와 환경변수 이름이 정해진다. 예를 들어
+//! `max_price_age_secs`는 `--max-price-age-secs`와 `ORACLE_AGG_MAX_PRICE_AGE_SECS`가 된다.
+//! CLI와 환경변수는 clap이 함께 읽으므로 그 값이 비어 있을 때만 설정 파일 값을 사용한다.
+
+use anyhow::{Context, Result};
+use clap::{Args, Parser, Subcommand};
+use oracle_vm_common::jsonl::{FsyncPolicy, JsonlConfig};
+use oracle_vm_common::Price;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::aggregation::AggregationMode;
+use crate::alerts::{AlertRule, AlertsConfig};
+use crate::config::{AggregatorConfig, PriceUnit};
+use crate::export::ExportArgs;
+use crate::grpc_web::GrpcWebConfig;
+use crate::onchain::{OnchainConfig, SignerSource, GWEI};
+use crate::parquet_archive::ParquetArchiveConfig;
+use crate::readiness::ReadinessConfig;
+use crate::reputation::DEFAULT_REPUTATION_HALF_LIFE_SECS;
+use crate::rest::RestConfig;
+use crate::sink::{KafkaConfig, RedisConfig};
+use crate::snapshot_archive::SnapshotArchiveConfig;
+use crate::source_weights;
+use crate::storage::{
+    PostgresConfig, StorageConfig, DEFAULT_POSTGRES_MAX_CONNECTIONS,
+    DEFAULT_POSTGRES_STATEMENT_TIMEOUT_SECS,
+};
+use crate::strategy::{self, AggregationStrategy, TrustWeightedMedian};
+use crate::tls::TlsConfig;
+use crate::transport::TransportConfig;
+use crate::trust::TrustCoefficients;
+use crate::wal::WalConfig;
+use crate::webhook::{WebhookConfig, WebhookEvent};
+use crate::ws::WsConfig;
+
+/// 설정 파일 기본 경로 (없으면 무시)
+pub const DEFAULT_CONFIG_PATH: &str = "config/aggregator.toml";
+/// 기본 수신 주소
+pub const DEFAULT_LISTEN_ADDR: &str = "127.0.0.1:50051";
+/// 기본 집계 방식
+pub const DEFAULT_STRATEGY: &str = "median";
+/// 가격 급변 경보의 기본 비교 기간 (초)
+pub const DEFAULT_ALERT_PRICE_MOVE_WINDOW_SECS: u64 = 300;
+/// 설정 출력에서 비밀값 대신 쓰는 문자열
+pub const REDACTED: &str = "<redacted>";
+/// 더 이상 읽지 않는 이전 환경변수 접두사 (`ORACLE_AGG_`로 바뀜)
+pub const LEGACY_ENV_PREFIX: &str = "AGGREGATOR_";
+
+/// Aggregator CLI
+#[derive(Debug, Parser)]
+#[command(name = "aggregator-server")]
+#[command(about = "BTCFi Aggregator Server for oracle price aggregation")]
+pub struct Cli {
+    /// 설정 파일 경로 (기본 경로의 파일은 없어도 됨)
+    #[arg(short, long, global = true, env = "ORACLE_AGG_CONFIG")]
+    pub config: Option<PathBuf>,
+
+    /// 최종 적용되는 설정을 TOML로 출력하고 종료 (비밀값은 가림)
+    #[arg(long)]
+    pub dump_config: bool,
+
+    #[command(flatten)]
+    pub settings: Settings,
+
+    /// 서버 대신 실행할 명령 (생략하면 서버 실행)
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Aggregator 보조 명령
+#[derive(Debug, Clone, PartialEq, Eq, Subcommand)]
+pub enum Command {
+    /// WAL(`wal_dir`)에 기록된 제출을 CSV로 내보내기
+    Export(ExportArgs),
+}
+
+/// Aggregator 설정 (설정 파일과 CLI/환경변수가 같은 항목을 사용, 지정하지 않은 값은 None)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Args)]
+#[serde(default, deny_unknown_fields)]
+pub struct Settings {
+    /// gRPC 수신 주소
+    #[arg(long, env = "ORACLE_AGG_LISTEN_ADDR")]
+    pub listen_addr: Option<SocketAddr>,
+
+    /// gRPC 연결에 HTTP/2 PING을 보내는 주기 (초)
+    #[arg(long, env = "ORACLE_AGG_GRPC_KEEPALIVE_INTERVAL_SECS")]
+    pub grpc_keepalive_interval_secs: Option<u64>,
+
+    /// PING 응답이 이 시간(초) 안에 없으면 gRPC 연결을 닫음
+    #[arg(long, env = "ORACLE_AGG_GRPC_KEEPALIVE_TIMEOUT_SECS")]
+    pub grpc_keepalive_timeout_secs: Option<u64>,
+
+    /// gRPC 요청 하나의 최대 처리 시간 (초, 넘으면 deadline_exceeded)
+    #[arg(long, env = "ORACLE_AGG_GRPC_REQUEST_TIMEOUT_SECS")]
+    pub grpc_request_timeout_secs: Option<u64>,
+
+    /// gRPC 포트에서 브라우저용 grpc-web 요청도 받음 (HTTP/1.1 허용)
+    #[arg(
+        long,
+        env = "ORACLE_AGG_GRPC_WEB",
+        num_args = 0..=1,
+        default_missing_value = "true"
+    )]
+    pub grpc_web: Option<bool>,
+
+    /// grpc-web 요청을 CORS로 허용할 origin (쉼표로 구분, *이면 모두 허용)
+    #[arg(long, env = "ORACLE_AGG_GRPC_WEB_CORS_ORIGINS", value_delimiter = ',')]
+    pub grpc_web_cors_origins: Option<Vec<String>>,
+
+    /// REST/JSON 게이트웨이 수신 주소 (생략하면 게이트웨이를 띄우지 않음)
+    #[arg(long, env = "ORACLE_AGG_HTTP_ADDR")]
+    pub http_addr: Option<SocketAddr>,
+
+    /// REST 게이트웨이가 CORS로 허용할 origin (쉼표로 구분, *이면 모두 허용)
+    #[arg(long, env = "ORACLE_AGG_HTTP_CORS_ORIGINS", value_delimiter = ',')]
+    pub http_cors_origins: Option<Vec<String>>,
+
+    /// `/v1/ws` 연결에 ping을 보내는 주기 (초)
+    #[arg(long, env = "ORACLE_AGG_WS_PING_INTERVAL_SECS")]
+    pub ws_ping_interval_secs: Option<u64>,
+
+    /// 이 시간(초) 동안 아무 메시지도 없는 `/v1/ws` 연결을 끊음
+    #[arg(long, env = "ORACLE_AGG_WS_IDLE_TIMEOUT_SECS")]
+    pub ws_idle_timeout_secs: Option<u64>,
+
+    /// gRPC 바인드 후 신선한 제출이 없어도 `/readyz`가 200인 warm-up 유예 시간 (초)
+    #[arg(long, env = "ORACLE_AGG_READINESS_WARMUP_SECS")]
+    pub readiness_warmup_secs: Option<u64>,
+
+    /// 종료할 때 `/readyz`를 503으로 바꾼 뒤 gRPC 수신을 닫기까지 기다리는 시간 (초)
+    #[arg(long, env = "ORACLE_AGG_SHUTDOWN_DRAIN_SECS")]
+    pub shutdown_drain_secs: Option<u64>,
+
+    /// 집계 방식 (median, trimmed-by-one-median, one-vote-per-node, weighted-median, trust-weighted-median)
+    #[arg(long, env = "ORACLE_AGG_STRATEGY")]
+    pub strategy: Option<String>,
+
+    /// 중간값/평균을 계산하는 공간 (arithmetic, geometric)
+    #[arg(long, env = "ORACLE_AGG_AGGREGATION_MODE")]
+    pub aggregation_mode: Option<String>,
+
+    /// 윈도우 내 가격이 이보다 적으면 집계 방식과 관계없이 중간값으로 집계
+    #[arg(long, env = "ORACLE_AGG_MIN_POINTS")]
+    pub min_points: Option<usize>,
+
+    /// 조회/스트림 응답의 f64 가격 단위 (dollars, cents)
+    #[arg(long, env = "ORACLE_AGG_PRICE_UNIT")]
+    pub price_unit: Option<String>,
+
+    /// 거래소별 초기 가중치 (예: coinbase=2,kraken=0.5)
+    #[arg(long, env = "ORACLE_AGG_SOURCE_WEIGHTS", value_parser = parse_source_weights)]
+    pub source_weights: Option<BTreeMap<String, f64>>,
+
+    /// 가격 데이터 최대 보관 시간 (초)
+    #[arg(long, env = "ORACLE_AGG_MAX_PRICE_AGE_SECS")]
+    pub max_price_age_secs: Option<u64>,
+
+    /// 추적하는 활성 노드의 최대 수
+    #[arg(long, env = "ORACLE_AGG_MAX_ACTIVE_NODES")]
+    pub max_active_nodes: Option<usize>,
+
+    /// 메모리에 유지하는 최대 가격 데이터 수
+    #[arg(long, env = "ORACLE_AGG_MAX_PRICE_ENTRIES")]
+    pub max_price_entries: Option<usize>,
+
+    /// 예상 참여 노드 수 (노드별 할당량 기본값 계산용)
+    #[arg(long, env = "ORACLE_AGG_EXPECTED_NODES")]
+    pub expected_nodes: Option<usize>,
+
+    /// 노드별 최대 보관 데이터 수 (생략하면 max_price_entries / expected_nodes)
+    #[arg(long, env = "ORACLE_AGG_PER_NODE_QUOTA")]
+    pub per_node_quota: Option<usize>,
+
+    /// 노드의 마지막 제출 후 집계에서 제외하기까지의 시간 (초)
+    #[arg(long, env = "ORACLE_AGG_MAX_CONTRIBUTION_AGE_SECS")]
+    pub max_contribution_age_secs: Option<u64>,
+
+    /// 노드의 가장 최근 제출에서 이 시간 이내의 이전 제출을 집계 전에 하나로 합침 (초, 생략하면 합치지 않음)
+    #[arg(long, env = "ORACLE_AGG_COALESCE_WINDOW_SECS")]
+    pub coalesce_window_secs: Option<u64>,
+
+    /// 집계 게시 주기 (초, 생략하면 제출마다 집계)
+    #[arg(long, env = "ORACLE_AGG_PUBLISH_INTERVAL_SECS")]
+    pub publish_interval_secs: Option<u64>,
+
+    /// 제출을 받는 노드 ID (쉼표로 구분, 생략하면 모든 노드)
+    #[arg(long, env = "ORACLE_AGG_ALLOWED_NODES", value_delimiter = ',')]
+    pub allowed_nodes: Option<Vec<String>>,
+
+    /// 관리자 RPC 비밀값 (생략하면 관리자 RPC 비활성)
+    #[arg(long, env = "ORACLE_AGG_ADMIN_SECRET", hide_env_values = true)]
+    pub admin_secret: Option<String>,
+
+    /// 평판 회복 반감기 (초, 0이면 회복하지 않음)
+    #[arg(long, env = "ORACLE_AGG_REPUTATION_HALF_LIFE_SECS")]
+    pub reputation_half_life_secs: Option<u64>,
+
+    /// SubmitPriceBatch 한 번에 받는 최대 가격 데이터 수
+    #[arg(long, env = "ORACLE_AGG_MAX_BATCH_SIZE")]
+    pub max_batch_size: Option<usize>,
+
+    /// GetPriceHistory/GetNodeHistory에 요청할 수 있는 최대 개수 (노드별 제출 기록 보관 수)
+    #[arg(long, env = "ORACLE_AGG_MAX_HISTORY_LIMIT")]
+    pub max_history_limit: Option<usize>,
+
+    /// 서버 시각과 이보다 많이 차이 나는 타임스탬프는 서버 시각으로 대체 (초)
+    #[arg(long, env = "ORACLE_AGG_SKEW_CLAMP_SECS")]
+    pub skew_clamp_secs: Option<u64>,
+
+    /// 서버 시각과 이보다 많이 차이 나는 타임스탬프는 거부 (초)
+    #[arg(long, env = "ORACLE_AGG_SKEW_REJECT_SECS")]
+    pub skew_reject_secs: Option<u64>,
+
+    /// 최근 가용성을 계산하는 윈도우 (초)
+    #[arg(long, env = "ORACLE_AGG_SLA_WINDOW_SECS")]
+    pub sla_window_secs: Option<u64>,
+
+    /// 최근 가용성 목표 (0~1, 이보다 낮아지면 경고)
+    #[arg(long, env = "ORACLE_AGG_SLA_TARGET")]
+    pub sla_target: Option<f64>,
+
+    /// 윈도우가 비어도 마지막 유효 집계 가격을 stale로 게시하는 시간 (초, 생략하면 게시하지 않음)
+    #[arg(long, env = "ORACLE_AGG_STALE_GRACE_SECS")]
+    pub stale_grace_secs: Option<u64>,
+
+    /// 신뢰 가중 중간값의 평판 지수
+    #[arg(long, env = "ORACLE_AGG_TRUST_REPUTATION_EXPONENT")]
+    pub trust_reputation_exponent: Option<f64>,
+
+    /// 신뢰 가중 중간값의 거래량 지수
+    #[arg(long, env = "ORACLE_AGG_TRUST_VOLUME_EXPONENT")]
+    pub trust_volume_exponent: Option<f64>,
+
+    /// 신뢰 가중 중간값의 최신성 반감기 (초)
+    #[arg(long, env = "ORACLE_AGG_TRUST_RECENCY_HALF_LIFE_SECS")]
+    pub trust_recency_half_life_secs: Option<u64>,
+
+    /// 모든 제출을 기록할 WAL 디렉터리
+    #[arg(long, env = "ORACLE_AGG_WAL_DIR")]
+    pub wal_dir: Option<PathBuf>,
+
+    /// WAL 보존 기간 (초, 지나면 세그먼트 단위로 삭제, 생략하면 모두 보존)
+    #[arg(long, env = "ORACLE_AGG_WAL_RETENTION_SECS")]
+    pub wal_retention_secs: Option<u64>,
+
+    /// 스냅샷을 gzip으로 보관할 디렉터리
+    #[arg(long, env = "ORACLE_AGG_SNAPSHOT_DIR")]
+    pub snapshot_dir: Option<PathBuf>,
+
+    /// 유지할 스냅샷 파일 수
+    #[arg(long, env = "ORACLE_AGG_SNAPSHOT_KEEP")]
+    pub snapshot_keep: Option<usize>,
+
+    /// 스냅샷 기록 주기 (초)
+    #[arg(long, env = "ORACLE_AGG_SNAPSHOT_INTERVAL_SECS")]
+    pub snapshot_interval_secs: Option<u64>,
+
+    /// 시간별 제출과 집계 결과를 Parquet로 보관할 디렉터리 (wal_dir 필요, `parquet` feature)
+    #[arg(long, env = "ORACLE_AGG_PARQUET_DIR")]
+    pub parquet_dir: Option<PathBuf>,
+
+    /// Parquet로 보관하고 검증한 WAL 세그먼트를 지우기까지의 기간 (초, 생략하면 지우지 않음)
+    #[arg(long, env = "ORACLE_AGG_PARQUET_WAL_RETENTION_SECS")]
+    pub parquet_wal_retention_secs: Option<u64>,
+
+    /// TLS로 서비스
+    #[arg(
+        long,
+        env = "ORACLE_AGG_TLS",
+        num_args = 0..=1,
+        default_missing_value = "true"
+    )]
+    pub tls: Option<bool>,
+
+    /// PEM 인증서 체인 경로
+    #[arg(long, env = "ORACLE_AGG_TLS_CERT")]
+    pub tls_cert: Option<PathBuf>,
+
+    /// PEM 개인키 경로
+    #[arg(long, env = "ORACLE_AGG_TLS_KEY")]
+    pub tls_key: Option<PathBuf>,
+
+    /// 클라이언트 인증서를 검증할 PEM CA 경로 (지정하면 mTLS)
+    #[arg(long, env = "ORACLE_AGG_TLS_CLIENT_CA")]
+    pub tls_client_ca: Option<PathBuf>,
+
+    /// 수락된 제출과 집계를 보낼 Kafka 브로커 (예: kafka-1:9092,kafka-2:9092, kafka feature 필요)
+    #[arg(long, env = "ORACLE_AGG_KAFKA_BROKERS")]
+    pub kafka_brokers: Option<String>,
+
+    /// 수락된 제출을 보내는 Kafka 토픽
+    #[arg(long, env = "ORACLE_AGG_KAFKA_SUBMISSIONS_TOPIC")]
+    pub kafka_submissions_topic: Option<String>,
+
+    /// 게시된 집계를 보내는 Kafka 토픽
+    #[arg(long, env = "ORACLE_AGG_KAFKA_AGGREGATES_TOPIC")]
+    pub kafka_aggregates_topic: Option<String>,
+
+    /// 최신 집계를 저장하고 발행할 Redis 주소 (예: redis://localhost:6379, redis feature 필요)
+    #[arg(long, env = "ORACLE_AGG_REDIS_URL")]
+    pub redis_url: Option<String>,
+
+    /// 최신 집계를 저장하는 Redis 키 접두사 (키는 접두사 + pair, 예: oracle:price:BTC-USD)
+    #[arg(long, env = "ORACLE_AGG_REDIS_KEY_PREFIX")]
+    pub redis_key_prefix: Option<String>,
+
+    /// 집계를 발행하는 Redis pub/sub 채널
+    #[arg(long, env = "ORACLE_AGG_REDIS_CHANNEL")]
+    pub redis_channel: Option<String>,
+
+    /// 게시된 집계를 한 줄씩 덧붙일 JSONL 파일
+    #[arg(long, env = "ORACLE_AGG_FILE_SINK_PATH")]
+    pub file_sink_path: Option<PathBuf>,
+
+    /// JSONL 파일 fsync 주기 (always, every:N, never)
+    #[arg(long, env = "ORACLE_AGG_FILE_SINK_FSYNC")]
+    pub file_sink_fsync: Option<String>,
+
+    /// JSONL 파일을 로테이션하는 크기 (바이트, 생략하면 로테이션 안 함)
+    #[arg(long, env = "ORACLE_AGG_FILE_SINK_MAX_BYTES")]
+    pub file_sink_max_bytes: Option<u64>,
+
+    /// 제출과 집계를 기록할 PostgreSQL 주소 (예: postgres://oracle:secret@db:5432/oracle, postgres feature 필요)
+    #[arg(long, env = "ORACLE_AGG_POSTGRES_URL")]
+    pub postgres_url: Option<String>,
+
+    /// PostgreSQL 연결 풀 최대 크기
+    #[arg(long, env = "ORACLE_AGG_POSTGRES_MAX_CONNECTIONS")]
+    pub postgres_max_connections: Option<u32>,
+
+    /// PostgreSQL 쿼리와 연결 획득 제한 시간 (초)
+    #[arg(long, env = "ORACLE_AGG_POSTGRES_STATEMENT_TIMEOUT_SECS")]
+    pub postgres_statement_timeout_secs: Option<u64>,
+
+    /// PostgreSQL에 한 번에 기록하는 최대 제출 수
+    #[arg(long, env = "ORACLE_AGG_POSTGRES_BATCH_SIZE")]
+    pub postgres_batch_size: Option<usize>,
+
+    /// PostgreSQL 기록 보존 기간 (초, 지나면 제출과 집계를 삭제, 생략하면 모두 보존)
+    #[arg(long, env = "ORACLE_AGG_POSTGRES_RETENTION_SECS")]
+    pub postgres_retention_secs: Option<u64>,
+
+    /// 보존 기간 정리 뒤 PostgreSQL 테이블을 VACUUM하는 주기 (초, 보존 기간을 설정한 경우만)
+    #[arg(long, env = "ORACLE_AGG_POSTGRES_VACUUM_INTERVAL_SECS")]
+    pub postgres_vacuum_interval_secs: Option<u64>,
+
+    /// 게시된 집계와 가격 급변을 POST로 보낼 웹훅 URL (쉼표로 구분)
+    #[arg(long, env = "ORACLE_AGG_WEBHOOK_URLS", value_delimiter = ',')]
+    pub webhook_urls: Option<Vec<String>>,
+
+    /// 웹훅 본문의 HMAC-SHA256 서명 비밀 키 (X-Oracle-Signature 헤더, 생략하면 서명 안 함)
+    #[arg(long, env = "ORACLE_AGG_WEBHOOK_SECRET")]
+    pub webhook_secret: Option<String>,
+
+    /// 보낼 웹훅 이벤트 (aggregate, deviation 중 쉼표로 구분)
+    #[arg(long, env = "ORACLE_AGG_WEBHOOK_EVENTS", value_delimiter = ',')]
+    pub webhook_events: Option<Vec<String>>,
+
+    /// deviation 이벤트를 보내는 직전 집계 대비 가격 변화 (bp)
+    #[arg(long, env = "ORACLE_AGG_WEBHOOK_DEVIATION_BPS")]
+    pub webhook_deviation_bps: Option<u32>,
+
+    /// 집계 가격이 alert_price_move_window_secs 안에 이만큼 움직이면 경보 (bp)
+    #[arg(long, env = "ORACLE_AGG_ALERT_PRICE_MOVE_BPS")]
+    pub alert_price_move_bps: Option<u32>,
+
+    /// 가격 급변 경보가 비교하는 기간 (초)
+    #[arg(long, env = "ORACLE_AGG_ALERT_PRICE_MOVE_WINDOW_SECS")]
+    pub alert_price_move_window_secs: Option<u64>,
+
+    /// 집계에 참여한 노드가 이보다 적으면 경보
+    #[arg(long, env = "ORACLE_AGG_ALERT_QUORUM_NODES")]
+    pub alert_quorum_nodes: Option<usize>,
+
+    /// 신선한 집계 가격이 이 시간 동안 없으면 경보 (초)
+    #[arg(long, env = "ORACLE_AGG_ALERT_NO_DATA_SECS")]
+    pub alert_no_data_secs: Option<u64>,
+
+    /// 규칙마다 경보를 다시 보내기까지의 최소 간격 (초)
+    #[arg(long, env = "ORACLE_AGG_ALERT_COOLDOWN_SECS")]
+    pub alert_cooldown_secs: Option<u64>,
+
+    /// 경보를 보낼 Slack incoming webhook URL (쉼표로 구분)
+    #[arg(long, env = "ORACLE_AGG_ALERT_SLACK_URLS", value_delimiter = ',')]
+    pub alert_slack_urls: Option<Vec<String>>,
+
+    /// 경보를 보낼 Discord webhook URL (쉼표로 구분)
+    #[arg(long, env = "ORACLE_AGG_ALERT_DISCORD_URLS", value_delimiter = ',')]
+    pub alert_discord_urls: Option<Vec<String>>,
+
+    /// 집계 가격을 게시할 EVM JSON-RPC 주소 (예: http://localhost:8545, onchain feature 필요)
+    #[arg(long, env = "ORACLE_AGG_ONCHAIN_RPC_URL")]
+    pub onchain_rpc_url: Option<String>,
+
+    /// `updatePrice(uint256,uint256)`를 부를 가격 컨트랙트 주소
+    #[arg(long, env = "ORACLE_AGG_ONCHAIN_CONTRACT")]
+    pub onchain_contract: Option<String>,
+
+    /// 트랜잭션 서명 개인 키 (16진수, onchain_keystore 대신)
+    #[arg(long, env = "ORACLE_AGG_ONCHAIN_PRIVATE_KEY")]
+    pub onchain_private_key: Option<String>,
+
+    /// 트랜잭션 서명 키가 든 암호화된 JSON keystore 경로
+    #[arg(long, env = "ORACLE_AGG_ONCHAIN_KEYSTORE")]
+    pub onchain_keystore: Option<PathBuf>,
+
+    /// onchain_keystore 비밀번호
+    #[arg(long, env = "ORACLE_AGG_ONCHAIN_KEYSTORE_PASSWORD")]
+    pub onchain_keystore_password: Option<String>,
+
+    /// 컨트랙트에 쓰는 가격의 소수 자릿수
+    #[arg(long, env = "ORACLE_AGG_ONCHAIN_PRICE_DECIMALS")]
+    pub onchain_price_decimals: Option<u32>,
+
+    /// 가격 변화가 없어도 이 시간(초)이 지나면 게시
+    #[arg(long, env = "ORACLE_AGG_ONCHAIN_HEARTBEAT_SECS")]
+    pub onchain_heartbeat_secs: Option<u64>,
+
+    /// 온체인 가격 대비 이만큼(bp) 움직이면 게시
+    #[arg(long, env = "ORACLE_AGG_ONCHAIN_DEVIATION_BPS")]
+    pub onchain_deviation_bps: Option<u32>,
+
+    /// 가스 가격 상한 (gwei)
+    #[arg(long, env = "ORACLE_AGG_ONCHAIN_MAX_GAS_PRICE_GWEI")]
+    pub onchain_max_gas_price_gwei: Option<u64>,
+
+    /// 집계 가격 서명 키 파일 (ed25519, 설정하면 GetSignedPrice와 `/v1/attestation`을 켬, SIGHUP으로 다시 읽음)
+    #[arg(long, env = "ORACLE_AGG_ATTESTATION_KEY")]
+    pub attestation_key: Option<PathBuf>,
+
+    /// span을 내보낼 OTLP/gRPC 수집기 주소 (예: http://localhost:4317, 생략하면 추적 안 함)
+    #[arg(long, env = "ORACLE_AGG_OTLP_ENDPOINT")]
+    pub otlp_endpoint: Option<String>,
+}
+
+impl Settings {
+    /// 설정 파일 읽기
+    ///
+    /// 경로를 지정하지 않았고 기본 경로에 파일이 없으면 빈 설정을 반환한다.
+    pub fn load_file(path: Option<&Path>) -> Result<Self> {
+        let (path, explicit) = match path {
+            Some(path) => (path, true),
+            None => (Path::new(DEFAULT_CONFIG_PATH), false),
+        };
+
+        match std::fs::read_to_string(path) {
+            Ok(contents) => toml::from_str(&contents)
+                .with_context(|| format!("Invalid config file {}", path.display())),
+            Err(e) if !explicit && e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => {
+                Err(e).with_context(|| format!("Failed to read config file {}", path.display()))
+            }
+        }
+    }
+
+    /// 모든 항목의 기본값 (기본적으로 꺼져 있는 기능은 None)
+    pub fn defaults() -> Self {
+        let config = AggregatorConfig::default();
+        let tls = TlsConfig::default();
+        let snapshots = SnapshotArchiveConfig::default();
+        let trust = TrustCoefficients::default();
+        let readiness = ReadinessConfig::default();
+        let ws = WsConfig::default();
+        let transport = TransportConfig::default();
+        let kafka = KafkaConfig::default();
+        let redis = RedisConfig::default();
+        let storage = StorageConfig::default();
+        let webhook = WebhookConfig::default();
+        let alerts = AlertsConfig::default();
+        let onchain = OnchainConfig::new(
+            String::new(),
+            String::new(),
+            SignerSource::PrivateKey(String::new()),
+        );
+        Self {
+            listen_addr: DEFAULT_LISTEN_ADDR.parse().ok(),
+            grpc_keepalive_interval_secs: Some(transport.keepalive_interval.as_secs()),
+            grpc_keepalive_timeout_secs: Some(transport.keepalive_timeout.as_secs()),
+            grpc_request_timeout_secs: Some(transport.request_timeout.as_secs()),
+            grpc_web: Some(false),
+            grpc_web_cors_origins: Some(Vec::new()),
+            http_addr: None,
+            http_cors_origins: Some(Vec::new()),
+            ws_ping_interval_secs: Some(ws.ping_interval.as_secs()),
+            ws_idle_timeout_secs: Some(ws.idle_timeout.as_secs()),
+            readiness_warmup_secs: Some(readiness.warmup_grace.as_secs()),
+            shutdown_drain_secs: Some(readiness.drain_delay.as_secs()),
+            strategy: Some(DEFAULT_STRATEGY.to_string()),
+            aggregation_mode: Some(AggregationMode::default().name().to_string()),
+            min_points: None,
+            price_unit: Some(PriceUnit::default().name().to_string()),
+            source_weights: Some(BTreeMap::new()),
+            max_price_age_secs: Some(config.max_price_age_secs),
+            max_active_nodes: Some(config.max_active_nodes),
+            max_price_entries: Some(config.max_price_entries),
+            expected_nodes: Some(config.expected_nodes),
+            per_node_quota: None,
+            max_contribution_age_secs: None,
+            coalesce_window_secs: None,
+            publish_interval_secs: None,
+            allowed_nodes: None,
+            admin_secret: None,
+            reputation_half_life_secs: Some(DEFAULT_REPUTATION_HALF_LIFE_SECS),
+            max_batch_size: Some(config.max_batch_size),
+            max_history_limit: Some(config.max_history_limit),
+            skew_clamp_secs: None,
+            skew_reject_secs: None,
+            sla_window_secs: Some(config.sla_window_secs),
+            sla_target: None,
+            stale_grace_secs: None,
+            trust_reputation_exponent: Some(trust.reputation_exponent),
+            trust_volume_exponent: Some(trust.volume_exponent),
+            trust_recency_half_life_secs: trust.recency_half_life_secs,
+            wal_dir: None,
+            wal_retention_secs: None,
+            snapshot_dir: None,
+            snapshot_keep: Some(snapshots.keep),
+            snapshot_interval_secs: Some(snapshots.interval_secs),
+            parquet_dir: None,
+            parquet_wal_retention_secs: None,
+            tls: Some(false),
+            tls_cert: Some(tls.cert_path),
+            tls_key: Some(tls.key_path),
+            tls_client_ca: None,
+            kafka_brokers: None,
+            kafka_submissions_topic: Some(kafka.submissions_topic),
+            kafka_aggregates_topic: Some(kafka.aggregates_topic),
+            redis_url: None,
+            redis_key_prefix: Some(redis.key_prefix),
+            redis_channel: Some(redis.channel),
+            file_sink_path: None,
+            file_sink_fsync: Some(FsyncPolicy::default().to_string()),
+            file_sink_max_bytes: None,
+            postgres_url: None,
+            postgres_max_connections: Some(DEFAULT_POSTGRES_MAX_CONNECTIONS),
+            postgres_statement_timeout_secs: Some(DEFAULT_POSTGRES_STATEMENT_TIMEOUT_SECS),
+            postgres_batch_size: Some(storage.batch_size),
+            postgres_retention_secs: None,
+            postgres_vacuum_interval_secs: Some(storage.vacuum_interval.as_secs()),
+            webhook_urls: None,
+            webhook_secret: None,
+            webhook_events: Some(
+                webhook
+                    .events
+                    .iter()
+                    .map(|event| event.name().to_string())
+                    .collect(),
+            ),
+            webhook_deviation_bps: Some(webhook.deviation_bps),
+            alert_price_move_bps: None,
+            alert_price_move_window_secs: Some(DEFAULT_ALERT_PRICE_MOVE_WINDOW_SECS),
+            alert_quorum_nodes: None,
+            alert_no_data_secs: None,
+            alert_cooldown_secs: Some(alerts.cooldown.as_secs()),
+            alert_slack_urls: None,
+            alert_discord_urls: None,
+            onchain_rpc_url: None,
+            onchain_contract: None,
+            onchain_private_key: None,
+            onchain_keystore: None,
+            onchain_keystore_password: None,
+            onchain_price_decimals: Some(onchain.price_decimals),
+            onchain_heartbeat_secs: Some(onchain.heartbeat.as_secs()),
+            onchain_deviation_bps: Some(onchain.deviation_bps),
+            onchain_max_gas_price_gwei: Some((onchain.max_gas_price / GWEI) as u64),
+            attestation_key: None,
+            otlp_endpoint: None,
+        }
+    }
+
+    /// 항목마다 이 설정의 값을, 없으면 `lower`의 값을 사용
+    pub fn or(self, lower: Self) -> Self {
+        Self {
+            listen_addr: self.listen_addr.or(lower.listen_addr),
+            grpc_keepalive_interval_secs: self
+                .grpc_keepalive_interval_secs
+                .or(lower.grpc_keepalive_interval_secs),
+            grpc_keepalive_timeout_secs: self
+                .grpc_keepalive_timeout_secs
+                .or(lower.grpc_keepalive_timeout_secs),
+            grpc_request_timeout_secs: self
+                .grpc_request_timeout_secs
+                .or(lower.grpc_request_timeout_secs),
+            grpc_web: self.grpc_web.or(lower.grpc_web),
+            grpc_web_cors_origins: self.grpc_web_cors_origins.or(lower.grpc_web_cors_origins),
+            http_addr: self.http_addr.or(lower.http_addr),
+            http_cors_origins: self.http_cors_origins.or(lower.http_cors_origins),
+            ws_ping_interval_secs: self.ws_ping_interval_secs.or(lower.ws_ping_interval_secs),
+            ws_idle_timeout_secs: self.ws_idle_timeout_secs.or(lower.ws_idle_timeout_secs),
+            readiness_warmup_secs: self.readiness_warmup_secs.or(lower.readiness_warmup_secs),
+            shutdown_drain_secs: self.shutdown_drain_secs.or(lower.shutdown_drain_secs),
+            strategy: self.strategy.or(lower.strategy),
+            aggregation_mode: self.aggregation_mode.or(lower.aggregation_mode),
+            min_points: self.min_points.or(lower.min_points),
+            price_unit: self.price_unit.or(lower.price_unit),
+            source_weights: self.source_weights.or(lower.source_weights),
+            max_price_age_secs: self.max_price_age_secs.or(lower.max_price_age_secs),
+            max_active_nodes: self.max_active_nodes.or(lower.max_active_nodes),
+            max_price_entries: self.max_price_entries.or(lower.max_price_entries),
+            expected_nodes: self.expected_nodes.or(lower.expected_nodes),
+            per_node_quota: self.per_node_quota.or(lower.per_node_quota),
+            max_contribution_age_secs: self
+                .max_contribution_age_secs
+                .or(lower.max_contribution_age_secs),
+            coalesce_window_secs: self.coalesce_window_secs.or(lower.coalesce_window_secs),
+            publish_interval_secs: self.publish_interval_secs.or(lower.publish_interval_secs),
+            allowed_nodes: self.allowed_nodes.or(lower.allowed_nodes),
+            admin_secret: self.admin_secret.or(lower.admin_secret),
+            reputation_half_life_secs: self
+                .reputation_half_life_secs
+                .or(lower.reputation_half_life_secs),
+            max_batch_size: self.max_batch_size.or(lower.max_batch_size),
+            max_history_limit: self.max_history_limit.or(lower.max_history_limit),
+            skew_clamp_secs: self.skew_clamp_secs.or(lower.skew_clamp_secs),
+            skew_reject_secs: self.skew_reject_secs.or(lower.skew_reject_secs),
+            sla_window_secs: self.sla_window_secs.or(lower.sla_window_secs),
+            sla_target: self.sla_target.or(lower.sla_target),
+            stale_grace_secs: self.stale_grace_secs.or(lower.stale_grace_secs),
+            trust_reputation_exponent: self
+                .trust_reputation_exponent
+                .or(lower.trust_reputation_exponent),
+            trust_volume_exponent: self.trust_volume_exponent.or(lower.trust_volume_exponent),
+            trust_recency_half_life_secs: self
+                .trust_recency_half_life_secs
+                .or(lower.trust_recency_half_life_secs),
+            wal_dir: self.wal_dir.or(lower.wal_dir),
+            wal_retention_secs: self.wal_retention_secs.or(lower.wal_retention_secs),
+            snapshot_dir: self.snapshot_dir.or(lower.snapshot_dir),
+            snapshot_keep: self.snapshot_keep.or(lower.snapshot_keep),
+            snapshot_interval_secs: self.snapshot_interval_secs.or(lower.snapshot_interval_secs),
+            parquet_dir: self.parquet_dir.or(lower.parquet_dir),
+            parquet_wal_retention_secs: self
+                .parquet_wal_retention_secs
+                .or(lower.parquet_wal_retention_secs),
+            tls: self.tls.or(lower.tls),
+            tls_cert: self.tls_cert.or(lower.tls_cert),
+            tls_key: self.tls_key.or(lower.tls_key),
+            tls_client_ca: self.tls_client_ca.or(lower.tls_client_ca),
+            kafka_brokers: self.kafka_brokers.or(lower.kafka_brokers),
+            kafka_submissions_topic: self
+                .kafka_submissions_topic
+                .or(lower.kafka_submissions_topic),
+            kafka_aggregates_topic: self.kafka_aggregates_topic.or(lower.kafka_aggregates_topic),
+            redis_url: self.redis_url.or(lower.redis_url),
+            redis_key_prefix: self.redis_key_prefix.or(lower.redis_key_prefix),
+            redis_channel: self.redis_channel.or(lower.redis_channel),
+            file_sink_path: self.file_sink_path.or(lower.file_sink_path),
+            file_sink_fsync: self.file_sink_fsync.or(lower.file_sink_fsync),
+            file_sink_max_bytes: self.file_sink_max_bytes.or(lower.file_sink_max_bytes),
+            postgres_url: self.postgres_url.or(lower.postgres_url),
+            postgres_max_connections: self
+                .postgres_max_connections
+                .or(lower.postgres_max_connections),
+            postgres_statement_timeout_secs: self
+                .postgres_statement_timeout_secs
+                .or(lower.postgres_statement_timeout_secs),
+            postgres_batch_size: self.postgres_batch_size.or(lower.postgres_batch_size),
+            postgres_retention_secs: self
+                .postgres_retention_secs
+                .or(lower.postgres_retention_secs),
+            postgres_vacuum_interval_secs: self
+                .postgres_vacuum_interval_secs
+                .or(lower.postgres_vacuum_interval_secs),
+            webhook_urls: self.webhook_urls.or(lower.webhook_urls),
+            webhook_secret: self.webhook_secret.or(lower.webhook_secret),
+            webhook_events: self.webhook_events.or(lower.webhook_events),
+            webhook_deviation_bps: self.webhook_deviation_bps.or(lower.webhook_deviation_bps),
+            alert_price_move_bps: self.alert_price_move_bps.or(lower.alert_price_move_bps),
+            alert_price_move_window_secs: self
+                .alert_price_move_window_secs
+                .or(lower.alert_price_move_window_secs),
+            alert_quorum_nodes: self.alert_quorum_nodes.or(lower.alert_quorum_nodes),
+            alert_no_data_secs: self.alert_no_data_secs.or(lower.alert_no_data_secs),
+            alert_cooldown_secs: self.alert_cooldown_secs.or(lower.alert_cooldown_secs),
+            alert_slack_urls: self.alert_slack_urls.or(lower.alert_slack_urls),
+            alert_discord_urls: self.alert_discord_urls.or(lower.alert_discord_urls),
+            onchain_rpc_url: self.onchain_rpc_url.or(lower.onchain_rpc_url),
+            onchain_contract: self.onchain_contract.or(lower.onchain_contract),
+            onchain_private_key: self.onchain_private_key.or(lower.onchain_private_key),
+            onchain_keystore: self.onchain_keystore.or(lower.onchain_keystore),
+            onchain_keystore_password: self
+                .onchain_keystore_password
+                .or(lower.onchain_keystore_password),
+            onchain_price_decimals: self.onchain_price_decimals.or(lower.onchain_price_decimals),
+            onchain_heartbeat_secs: self.onchain_heartbeat_secs.or(lower.onchain_heartbeat_secs),
+            onchain_deviation_bps: self.onchain_deviation_bps.or(lower.onchain_deviation_bps),
+            onchain_max_gas_price_gwei: self
+                .onchain_max_gas_price_gwei
+                .or(lower.onchain_max_gas_price_gwei),
+            attestation_key: self.attestation_key.or(lower.attestation_key),
+            otlp_endpoint: self.otlp_endpoint.or(lower.otlp_endpoint),
+        }
+    }
+
+    /// CLI/환경변수 인수, 설정 파일, 기본값을 병합하고 검증
+    pub fn resolve(args: Self, file: Self) -> Result<Self> {
+        let settings = args.or(file).or(Self::defaults());
+        settings.aggregator_config()?;
+        settings.file_sink_config()?;
+        settings.postgres_config()?;
+        settings.webhook_config()?;
+        settings.alerts_config()?;
+        settings.onchain_config()?;
+        Ok(settings)
+    }
+
+    /// 신뢰 가중 중간값 계수
+    pub fn trust_coefficients(&self) -> Result<TrustCoefficients> {
+        let defaults = TrustCoefficients::default();
+        let coefficients = TrustCoefficients {
+            reputation_exponent: self
+                .trust_reputation_exponent
+                .unwrap_or(defaults.reputation_exponent),
+            volume_exponent: self
+                .trust_volume_exponent
+                .unwrap_or(defaults.volume_exponent),
+            recency_half_life_secs: self.trust_recency_half_life_secs,
+        };
+        coefficients.validate().map_err(anyhow::Error::msg)?;
+        Ok(coefficients)
+    }
+
+    /// 중간값/평균을 계산하는 공간
+    pub fn aggregation_mode(&self) -> Result<AggregationMode> {
+        match self.aggregation_mode.as_deref() {
+            Some(name) => AggregationMode::from_name(name)
+                .ok_or_else(|| anyhow::anyhow!("unknown aggregation mode {:?}", name)),
+            None => Ok(AggregationMode::default()),
+        }
+    }
+
+    /// 조회/스트림 응답의 f64 가격 단위
+    pub fn price_unit(&self) -> Result<PriceUnit> {
+        match self.price_unit.as_deref() {
+            Some(name) => PriceUnit::from_name(name)
+                .ok_or_else(|| anyhow::anyhow!("unknown price unit {:?}", name)),
+            None => Ok(PriceUnit::default()),
+        }
+    }
+
+    /// 집계 방식
+    pub fn strategy(&self) -> Result<Arc<dyn AggregationStrategy>> {
+        let mode = self.aggregation_mode()?;
+        match self.strategy.as_deref().unwrap_or(DEFAULT_STRATEGY) {
+            "trust-weighted-median" => Ok(Arc::new(TrustWeightedMedian {
+                coefficients: self.trust_coefficients()?,
+                mode,
+                ..TrustWeightedMedian::default()
+            })),
+            name => strategy::by_name_with_mode(name, mode)
+                .ok_or_else(|| anyhow::anyhow!("unknown strategy {:?}", name)),
+        }
+    }
+
+    /// 서비스 설정
+    pub fn aggregator_config(&self) -> Result<AggregatorConfig> {
+        let defaults = AggregatorConfig::default();
+        let source_weights = self.source_weights.clone().unwrap_or_default();
+        if let Some((source, _)) = source_weights
+            .iter()
+            .find(|(_, weight)| !source_weights::is_valid_weight(**weight))
+        {
+            anyhow::bail!("weight for {:?} must be finite and >= 0", source);
+        }
+        if let Some(target) = self.sla_target {
+            if !(0.0..=1.0).contains(&target) {
+                anyhow::bail!("sla_target must be between 0 and 1");
+            }
+        }
+        if self.min_points == Some(0) {
+            anyhow::bail!("min_points must be positive");
+        }
+        if self.sla_window_secs == Some(0) {
+            anyhow::bail!("sla_window_secs must be positive");
+        }
+        if self.wal_retention_secs == Some(0) {
+            anyhow::bail!("wal_retention_secs must be positive");
+        }
+        if self.ws_ping_interval_secs == Some(0) || self.ws_idle_timeout_secs == Some(0) {
+            anyhow::bail!("ws_ping_interval_secs and ws_idle_timeout_secs must be positive");
+        }
+        if [
+            self.grpc_keepalive_interval_secs,
+            self.grpc_keepalive_timeout_secs,
+            self.grpc_request_timeout_secs,
+        ]
+        .contains(&Some(0))
+        {
+            anyhow::bail!("grpc keepalive and request timeouts must be positive");
+        }
+
+        Ok(AggregatorConfig {
+            max_active_nodes: self.max_active_nodes.unwrap_or(defaults.max_active_nodes),
+            max_price_entries: self.max_price_entries.unwrap_or(defaults.max_price_entries),
+            max_price_age_secs: self
+                .max_price_age_secs
+                .unwrap_or(defaults.max_price_age_secs),
+            expected_nodes: self.expected_nodes.unwrap_or(defaults.expected_nodes),
+            per_node_quota: self.per_node_quota,
+            strategy: Some(self.strategy()?),
+            aggregation_mode: self.aggregation_mode()?,
+            min_points: self.min_points,
+            max_contribution_age_secs: self.max_contribution_age_secs,
+            coalesce_window_secs: self.coalesce_window_secs,
+            publish_interval_secs: self.publish_interval_secs,
+            admin_secret: self.admin_secret.clone(),
+            source_weights: source_weights.into_iter().collect(),
+            reputation_half_life_secs: match self.reputation_half_life_secs {
+                Some(0) => None,
+                Some(secs) => Some(secs),
+                None => defaults.reputation_half_life_secs,
+            },
+            max_batch_size: self.max_batch_size.unwrap_or(defaults.max_batch_size),
+            max_history_limit: self.max_history_limit.unwrap_or(defaults.max_history_limit),
+            skew_clamp_secs: self.skew_clamp_secs,
+            skew_reject_secs: self.skew_reject_secs,
+            sla_window_secs: self.sla_window_secs.unwrap_or(defaults.sla_window_secs),
+            sla_target: self.sla_target,
+            stale_grace_secs: self.stale_grace_secs,
+            price_unit: self.price_unit()?,
+            allowed_nodes: self
+                .allowed_nodes
+                .iter()
+                .flatten()
+                .map(|node_id| node_id.trim().to_string())
+                .filter(|node_id| !node_id.is_empty())
+                .collect(),
+            ..defaults
+        })
+    }
+
+    /// gRPC 수신 주소
+    pub fn listen_addr(&self) -> SocketAddr {
+        self.listen_addr
+            .unwrap_or_else(|| DEFAULT_LISTEN_ADDR.parse().expect("valid default address"))
+    }
+
+    /// gRPC 서버 연결 설정 (keepalive, 요청 시간 제한)
+    pub fn transport_config(&self) -> TransportConfig {
+        let defaults = TransportConfig::default();
+        TransportConfig {
+            keepalive_interval: self
+                .grpc_keepalive_interval_secs
+                .map_or(defaults.keepalive_interval, Duration::from_secs),
+            keepalive_timeout: self
+                .grpc_keepalive_timeout_secs
+                .map_or(defaults.keepalive_timeout, Duration::from_secs),
+            request_timeout: self
+                .grpc_request_timeout_secs
+                .map_or(defaults.request_timeout, Duration::from_secs),
+        }
+    }
+
+    /// grpc-web 설정 (grpc_web을 켠 경우만)
+    pub fn grpc_web_config(&self) -> Option<GrpcWebConfig> {
+        self.grpc_web.unwrap_or(false).then(|| GrpcWebConfig {
+            cors_origins: trimmed_origins(&self.grpc_web_cors_origins),
+        })
+    }
+
+    /// REST 게이트웨이 설정 (수신 주소를 지정한 경우만)
+    pub fn rest_config(&self) -> Option<RestConfig> {
+        let ws = WsConfig::default();
+        self.http_addr.map(|addr| RestConfig {
+            addr,
+            cors_origins: trimmed_origins(&self.http_cors_origins),
+            ws: WsConfig {
+                ping_interval: self
+                    .ws_ping_interval_secs
+                    .map_or(ws.ping_interval, Duration::from_secs),
+                idle_timeout: self
+                    .ws_idle_timeout_secs
+                    .map_or(ws.idle_timeout, Duration::from_secs),
+                ..ws
+            },
+        })
+    }
+
+    /// `/readyz` 준비 상태 설정 (설정된 WAL, 스냅샷/Parquet 보관, JSONL 싱크 디렉터리에 접근할 수 있어야 함)
+    pub fn readiness_config(&self) -> ReadinessConfig {
+        let defaults = ReadinessConfig::default();
+        let sink_dir = self
+            .file_sink_path
+            .as_deref()
+            .and_then(Path::parent)
+            .filter(|dir| !dir.as_os_str().is_empty());
+        let storage = [
+            self.wal_dir.as_deref(),
+            self.snapshot_dir.as_deref(),
+            self.parquet_dir.as_deref(),
+            sink_dir,
+        ]
+        .into_iter()
+        .flatten()
+        .map(Path::to_path_buf)
+        .collect();
+        ReadinessConfig {
+            warmup_grace: self
+                .readiness_warmup_secs
+                .map_or(defaults.warmup_grace, Duration::from_secs),
+            drain_delay: self
+                .shutdown_drain_secs
+                .map_or(defaults.drain_delay, Duration::from_secs),
+            storage,
+        }
+    }
+
+    /// WAL 설정 (디렉터리를 지정한 경우만)
+    pub fn wal_config(&self) -> Option<WalConfig> {
+        self.wal_dir.clone().map(|dir| WalConfig {
+            dir,
+            retention: self.wal_retention_secs.map(Duration::from_secs),
+            ..WalConfig::default()
+        })
+    }
+
+    /// 스냅샷 보관 설정 (디렉터리를 지정한 경우만)
+    pub fn snapshot_archive_config(&self) -> Option<SnapshotArchiveConfig> {
+        let defaults = SnapshotArchiveConfig::default();
+        self.snapshot_dir.clone().map(|dir| SnapshotArchiveConfig {
+            dir,
+            keep: self.snapshot_keep.unwrap_or(defaults.keep),
+            interval_secs: self
+                .snapshot_interval_secs
+                .unwrap_or(defaults.interval_secs),
+        })
+    }
+
+    /// Parquet 보관 설정 (디렉터리를 지정한 경우만, 제출은 WAL에서 읽으므로 wal_dir이 없으면 에러)
+    pub fn parquet_archive_config(&self) -> Result<Option<ParquetArchiveConfig>> {
+        let Some(dir) = self.parquet_dir.clone() else {
+            return Ok(None);
+        };
+        let wal_dir = self
+            .wal_dir
+            .clone()
+            .context("wal_dir is required when parquet_dir is set")?;
+        Ok(Some(ParquetArchiveConfig {
+            dir,
+            wal_dir,
+            wal_retention: self.parquet_wal_retention_secs.map(Duration::from_secs),
+        }))
+    }
+
+    /// Kafka 싱크 설정 (브로커를 지정한 경우만)
+    pub fn kafka_config(&self) -> Option<KafkaConfig> {
+        let defaults = KafkaConfig::default();
+        self.kafka_brokers.clone().map(|brokers| KafkaConfig {
+            brokers,
+            submissions_topic: self
+                .kafka_submissions_topic
+                .clone()
+                .unwrap_or(defaults.submissions_topic),
+            aggregates_topic: self
+                .kafka_aggregates_topic
+                .clone()
+                .unwrap_or(defaults.aggregates_topic),
+        })
+    }
+
+    /// Redis 싱크 설정 (주소를 지정한 경우만)
+    ///
+    /// 키는 가격 유효 기간의 두 배 동안 유지되므로 집계가 끊기면 오래된 가격은 곧 사라진다.
+    pub fn redis_config(&self) -> Option<RedisConfig> {
+        let defaults = RedisConfig::default();
+        let max_price_age_secs = self
+            .max_price_age_secs
+            .unwrap_or(AggregatorConfig::default().max_price_age_secs);
+        self.redis_url.clone().map(|url| RedisConfig {
+            url,
+            key_prefix: self.redis_key_prefix.clone().unwrap_or(defaults.key_prefix),
+            channel: self.redis_channel.clone().unwrap_or(defaults.channel),
+            ttl: Duration::from_secs(max_price_age_secs.saturating_mul(2)),
+        })
+    }
+
+    /// JSONL 파일 싱크 설정 (경로를 지정한 경우만)
+    pub fn file_sink_config(&self) -> Result<Option<JsonlConfig>> {
+        let Some(path) = self.file_sink_path.clone() else {
+            return Ok(None);
+        };
+        let fsync = match &self.file_sink_fsync {
+            Some(name) => name.parse().map_err(anyhow::Error::msg)?,
+            None => FsyncPolicy::default(),
+        };
+        if self.file_sink_max_bytes == Some(0) {
+            anyhow::bail!("file_sink_max_bytes must be positive");
+        }
+        Ok(Some(JsonlConfig {
+            path,
+            fsync,
+            max_bytes: self.file_sink_max_bytes,
+        }))
+    }
+
+    /// PostgreSQL 저장소 설정 (주소를 지정한 경우만)
+    pub fn postgres_config(&self) -> Result<Option<PostgresConfig>> {
+        let Some(url) = self.postgres_url.clone() else {
+            return Ok(None);
+        };
+        let max_connections = self
+            .postgres_max_connections
+            .unwrap_or(DEFAULT_POSTGRES_MAX_CONNECTIONS);
+        let statement_timeout_secs = self
+            .postgres_statement_timeout_secs
+            .unwrap_or(DEFAULT_POSTGRES_STATEMENT_TIMEOUT_SECS);
+        let writer = StorageConfig {
+            batch_size: self
+                .postgres_batch_size
+                .unwrap_or(StorageConfig::default().batch_size),
+            retention: self.postgres_retention_secs.map(Duration::from_secs),
+            vacuum_interval: self.postgres_vacuum_interval_secs.map_or(
+                StorageConfig::default().vacuum_interval,
+                Duration::from_secs,
+            ),
+            ..StorageConfig::default()
+        };
+        if max_connections == 0 {
+            anyhow::bail!("postgres_max_connections must be positive");
+        }
+        if statement_timeout_secs == 0 {
+            anyhow::bail!("postgres_statement_timeout_secs must be positive");
+        }
+        if writer.batch_size == 0 {
+            anyhow::bail!("postgres_batch_size must be positive");
+        }
+        if writer.retention == Some(Duration::ZERO) {
+            anyhow::bail!("postgres_retention_secs must be positive");
+        }
+        if writer.vacuum_interval.is_zero() {
+            anyhow::bail!("postgres_vacuum_interval_secs must be positive");
+        }
+        Ok(Some(PostgresConfig {
+            url,
+            max_connections,
+            statement_timeout: Duration::from_secs(statement_timeout_secs),
+            writer,
+        }))
+    }
+
+    /// 웹훅 디스패처 설정 (URL을 지정한 경우만)
+    pub fn webhook_config(&self) -> Result<Option<WebhookConfig>> {
+        let defaults = WebhookConfig::default();
+        let urls: Vec<String> = self
+            .webhook_urls
+            .iter()
+            .flatten()
+            .map(|url| url.trim().to_string())
+            .filter(|url| !url.is_empty())
+            .collect();
+        if urls.is_empty() {
+            return Ok(None);
+        }
+        for url in &urls {
+            let parsed = url
+                .parse::<reqwest::Url>()
+                .with_context(|| format!("Invalid webhook URL '{}'", url))?;
+            if !matches!(parsed.scheme(), "http" | "https") {
+                anyhow::bail!("webhook URL '{}' must use http or https", url);
+            }
+        }
+        let events = match &self.webhook_events {
+            Some(names) => names
+                .iter()
+                .map(|name| name.parse().map_err(anyhow::Error::msg))
+                .collect::<Result<Vec<WebhookEvent>>>()?,
+            None => defaults.events.clone(),
+        };
+        if events.is_empty() {
+            anyhow::bail!("webhook_events must name at least one event");
+        }
+        Ok(Some(WebhookConfig {
+            urls,
+            secret: self.webhook_secret.clone(),
+            events,
+            deviation_bps: self.webhook_deviation_bps.unwrap_or(defaults.deviation_bps),
+            ..defaults
+        }))
+    }
+
+    /// 경보 설정 (규칙을 하나라도 지정한 경우만)
+    ///
+    /// 규칙과 Slack/Discord 웹훅 URL은 함께 지정해야 한다.
+    pub fn alerts_config(&self) -> Result<Option<AlertsConfig>> {
+        let defaults = AlertsConfig::default();
+        let mut rules = Vec::new();
+        if let Some(threshold_bps) = self.alert_price_move_bps {
+            let window_secs = self
+                .alert_price_move_window_secs
+                .unwrap_or(DEFAULT_ALERT_PRICE_MOVE_WINDOW_SECS);
+            if threshold_bps == 0 || window_secs == 0 {
+                anyhow::bail!(
+                    "alert_price_move_bps and alert_price_move_window_secs must be greater than 0"
+                );
+            }
+            rules.push(AlertRule::PriceMove {
+                threshold_bps,
+                window_secs,
+            });
+        }
+        if let Some(min_nodes) = self.alert_quorum_nodes {
+            if min_nodes == 0 {
+                anyhow::bail!("alert_quorum_nodes must be greater than 0");
+            }
+            rules.push(AlertRule::Quorum { min_nodes });
+        }
+        if let Some(after_secs) = self.alert_no_data_secs {
+            if after_secs == 0 {
+                anyhow::bail!("alert_no_data_secs must be greater than 0");
+            }
+            rules.push(AlertRule::NoData { after_secs });
+        }
+
+        let urls = |urls: &Option<Vec<String>>| -> Result<Vec<String>> {
+            let urls: Vec<String> = urls
+                .iter()
+                .flatten()
+                .map(|url| url.trim().to_string())
+                .filter(|url| !url.is_empty())
+                .collect();
+            for url in &urls {
+                // URL 경로에 토큰이 들어 있으므로 오류 메시지에 남기지 않음
+                let parsed = url
+                    .parse::<reqwest::Url>()
+                    .context("Invalid alert webhook URL")?;
+                if !matches!(parsed.scheme(), "http" | "https") {
+                    anyhow::bail!("alert webhook URLs must use http or https");
+                }
+            }
+            Ok(urls)
+        };
+        let slack_urls = urls(&self.alert_slack_urls)?;
+        let discord_urls = urls(&self.alert_discord_urls)?;
+        let has_urls = !slack_urls.is_empty() || !discord_urls.is_empty();
+        match (rules.is_empty(), has_urls) {
+            (true, false) => return Ok(None),
+            (true, true) => anyhow::bail!(
+                "alert_slack_urls/alert_discord_urls need at least one alert rule \
+                 (alert_price_move_bps, alert_quorum_nodes or alert_no_data_secs)"
+            ),
+            (false, false) => {
+                anyhow::bail!("alert rules need alert_slack_urls or alert_discord_urls")
+            }
+            (false, true) => {}
+        }
+        Ok(Some(AlertsConfig {
+            rules,
+            slack_urls,
+            discord_urls,
+            cooldown: self
+                .alert_cooldown_secs
+                .map_or(defaults.cooldown, Duration::from_secs),
+            ..defaults
+        }))
+    }
+
+    /// 온체인 퍼블리셔 설정 (RPC 주소를 지정한 경우만)
+    ///
+    /// 서명 키는 onchain_private_key와 onchain_keystore 중 하나로만 지정한다.
+    pub fn onchain_config(&self) -> Result<Option<OnchainConfig>> {
+        let Some(rpc_url) = self.onchain_rpc_url.clone() else {
+            return Ok(None);
+        };
+        let contract = self
+            .onchain_contract
+            .clone()
+            .context("onchain_contract is required when onchain_rpc_url is set")?;
+        let signer = match (&self.onchain_private_key, &self.onchain_keystore) {
+            (Some(key), None) => SignerSource::PrivateKey(key.clone()),
+            (None, Some(path)) => SignerSource::Keystore {
+                path: path.clone(),
+                password: self
+                    .onchain_keystore_password
+                    .clone()
+                    .context("onchain_keystore_password is required with onchain_keystore")?,
+            },
+            _ => anyhow::bail!(
+                "set exactly one of onchain_private_key and onchain_keystore to sign price updates"
+            ),
+        };
+
+        let defaults = OnchainConfig::new(rpc_url, contract, signer);
+        let price_decimals = self
+            .onchain_price_decimals
+            .unwrap_or(defaults.price_decimals);
+        if price_decimals > Price::MAX_DECIMALS {
+            anyhow::bail!(
+                "onchain_price_decimals must be at most {}",
+                Price::MAX_DECIMALS
+            );
+        }
+        if self.onchain_heartbeat_secs == Some(0) || self.onchain_max_gas_price_gwei == Some(0) {
+            anyhow::bail!("onchain_heartbeat_secs and onchain_max_gas_price_gwei must be positive");
+        }
+        Ok(Some(OnchainConfig {
+            price_decimals,
+            heartbeat: self
+                .onchain_heartbeat_secs
+                .map_or(defaults.heartbeat, Duration::from_secs),
+            deviation_bps: self.onchain_deviation_bps.unwrap_or(defaults.deviation_bps),
+            max_gas_price: self
+                .onchain_max_gas_price_gwei
+                .map_or(defaults.max_gas_price, |gwei| u128::from(gwei) * GWEI),
+            ..defaults
+        }))
+    }
+
+    /// TLS 설정 (tls를 켠 경우만)
+    pub fn tls_config(&self) -> Option<TlsConfig> {
+        if !self.tls.unwrap_or(false) {
+            return None;
+        }
+        let defaults = TlsConfig::default();
+        Some(TlsConfig {
+            cert_path: self.tls_cert.clone().unwrap_or(defaults.cert_path),
+            key_path: self.tls_key.clone().unwrap_or(defaults.key_path),
+            client_ca_path: self.tls_client_ca.clone(),
+        })
+    }
+
+    /// 비밀값을 가린 설정
+    pub fn redacted(&self) -> Self {
+        Self {
+            admin_secret: self.admin_secret.as_ref().map(|_| REDACTED.to_string()),
+            webhook_secret: self.webhook_secret.as_ref().map(|_| REDACTED.to_string()),
+            // 접속 URL에 비밀번호가 들어 있음
+            postgres_url: self.postgres_url.as_ref().map(|_| REDACTED.to_string()),
+            // Slack/Discord 웹훅 URL은 경로가 곧 토큰
+            alert_slack_urls: self
+                .alert_slack_urls
+                .as_ref()
+                .map(|urls| vec![REDACTED.to_string(); urls.len()]),
+            alert_discord_urls: self
+                .alert_discord_urls
+                .as_ref()
+                .map(|urls| vec![REDACTED.to_string(); urls.len()]),
+            onchain_private_key: self
+                .onchain_private_key
+                .as_ref()
+                .map(|_| REDACTED.to_string()),
+            onchain_keystore_password: self
+                .onchain_keystore_password
+                .as_ref()
+                .map(|_| REDACTED.to_string()),
+            ..self.clone()
+        }
+    }
+
+    /// 비밀값을 가린 설정을 설정 파일 형식(TOML)으로 출력
+    pub fn dump(&self) -> Result<String> {
+        toml::to_string(&self.redacted()).context("Failed to serialize settings")
+    }
+}
+
+/// 설정되어 있지만 더 이상 읽지 않는 `AGGREGATOR_*` 환경변수 이름
+pub fn legacy_env_vars() -> Vec<String> {
+    let mut names: Vec<String> = std::env::vars_os()
+        .filter_map(|(name, _)| name.into_string().ok())
+        .filter(|name| name.starts_with(LEGACY_ENV_PREFIX))
+        .collect();
+    names.sort();
+    names
+}
+
+// 쉼표로 구분한 source=weight 목록
+fn parse_source_weights(spec: &str) -> Result<BTreeMap<String, f64>, String> {
+    source_weights::parse(spec).map(|weights| weights.into_iter().collect())
+}
+
+// 앞뒤 공백을 지우고 빈 항목을 뺀 CORS origin 목록
+fn trimmed_origins(origins: &Option<Vec<String>>) -> Vec<String> {
+    origins
+        .iter()
+        .flatten()
+        .map(|origin| origin.trim().to_string())
+        .filter(|origin| !origin.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    // 환경변수를 바꾸는 테스트끼리 겹치지 않도록
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+    fn parse_with_env(vars: &[(&str, &str)], args: &[&str]) -> Cli {
+        let _guard = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        for (name, value) in vars {
+            std::env::set_var(name, value);
+        }
+        let cli =
+            Cli::try_parse_from(std::iter::once("aggregator-server").chain(args.iter().copied()));
+        for (name, _) in vars {
+            std::env::remove_var(name);
+        }
+        cli.unwrap()
+    }
+
+    #[test]
+    fn test_each_layer_overrides_the_previous() {
+        let file: Settings = toml::from_str(
+            r#"
+            max_price_age_secs = 90
+            max_batch_size = 50
+            publish_interval_secs = 5
+            strategy = "weighted-median"
+            allowed_nodes = ["node-b", " node-a ", ""]
+
+            [source_weights]
+            kraken = 0.5
+            "#,
+        )
+        .unwrap();
+        let cli = parse_with_env(
+            &[
+                ("ORACLE_AGG_MAX_BATCH_SIZE", "20"),
+                ("ORACLE_AGG_PUBLISH_INTERVAL_SECS", "10"),
+                ("ORACLE_AGG_SOURCE_WEIGHTS", "coinbase=2, kraken=1"),
+            ],
+            &["--publish-interval-secs", "15"],
+        );
+        let settings = Settings::resolve(cli.settings, file).unwrap();
+
+        // 기본값 < 설정 파일
+        assert_eq!(settings.max_price_age_secs, Some(90));
+        assert_eq!(settings.strategy.as_deref(), Some("weighted-median"));
+        // 설정 파일 < 환경변수 (목록은 쉼표로 구분)
+        assert_eq!(settings.max_batch_size, Some(20));
+        assert_eq!(
+            settings.source_weights,
+            Some(BTreeMap::from([
+                ("coinbase".to_string(), 2.0),
+                ("kraken".to_string(), 1.0)
+            ]))
+        );
+        // 환경변수 < CLI
+        assert_eq!(settings.publish_interval_secs, Some(15));
+        // 어디에도 없는 값은 기본값
+        assert_eq!(settings.listen_addr(), DEFAULT_LISTEN_ADDR.parse().unwrap());
+        assert_eq!(
+            settings.max_history_limit,
+            Settings::defaults().max_history_limit
+        );
+        assert!(settings.tls_config().is_none());
+        assert!(settings.wal_config().is_none());
+        assert!(settings.rest_config().is_none());
+        assert!(settings.redis_config().is_none());
+        assert!(settings.webhook_config().unwrap().is_none());
+        assert_eq!(settings.transport_config(), TransportConfig::default());
+        let cli = parse_with_env(
+            &[(
+                "ORACLE_AGG_WEBHOOK_URLS",
+                "https://hooks.example/oracle, http://10.0.0.5:9000/alerts",
+            )],
+            &[
+                "--webhook-events",
+                "deviation",
+                "--webhook-deviation-bps",
+                "50",
+            ],
+        );
+        let webhook = Settings::resolve(cli.settings, Settings::default())
+            .unwrap()
+            .webhook_config()
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            webhook.urls,
+            [
+                "https://hooks.example/oracle",
+                "http://10.0.0.5:9000/alerts"
+            ]
+        );
+        assert_eq!(webhook.events, [WebhookEvent::Deviation]);
+        assert_eq!(webhook.deviation_bps, 50);
+        assert_eq!(webhook.secret, None);
+        assert!(settings.alerts_config().unwrap().is_none());
+        let cli = parse_with_env(
+            &[(
+                "ORACLE_AGG_ALERT_SLACK_URLS",
+                "https://hooks.slack.com/services/T0/B0/x",
+            )],
+            &[
+                "--alert-price-move-bps",
+                "500",
+                "--alert-quorum-nodes",
+                "3",
+                "--alert-discord-urls",
+                "https://discord.com/api/webhooks/1/y",
+                "--alert-cooldown-secs",
+                "600",
+            ],
+        );
+        let alerts = Settings::resolve(cli.settings, Settings::default())
+            .unwrap()
+            .alerts_config()
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            alerts.rules,
+            [
+                AlertRule::PriceMove {
+                    threshold_bps: 500,
+                    window_secs: DEFAULT_ALERT_PRICE_MOVE_WINDOW_SECS
+                },
+                AlertRule::Quorum { min_nodes: 3 }
+            ]
+        );
+        assert_eq!(
+            alerts.slack_urls,
+            ["https://hooks.slack.com/services/T0/B0/x"]
+        );
+        assert_eq!(
+            alerts.discord_urls,
+            ["https://discord.com/api/webhooks/1/y"]
+        );
+        assert_eq!(alerts.cooldown, Duration::from_secs(600));
+        assert!(settings.onchain_config().unwrap().is_none());
+        let cli = parse_with_env(
+            &[
+                ("ORACLE_AGG_ONCHAIN_RPC_URL", "http://localhost:8545"),
+                (
+                    "ORACLE_AGG_ONCHAIN_CONTRACT",
+                    "0x5FbDB2315678afecb367f032d93F642f64180aa3",
+                ),
+                ("ORACLE_AGG_ONCHAIN_KEYSTORE", "keys/oracle.json"),
+                ("ORACLE_AGG_ONCHAIN_KEYSTORE_PASSWORD", "hunter2"),
+            ],
+            &[
+                "--onchain-deviation-bps",
+                "25",
+                "--onchain-max-gas-price-gwei",
+                "50",
+            ],
+        );
+        let onchain = Settings::resolve(cli.settings, Settings::default())
+            .unwrap()
+            .onchain_config()
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            onchain.signer,
+            SignerSource::Keystore {
+                path: PathBuf::from("keys/oracle.json"),
+                password: "hunter2".to_string(),
+            }
+        );
+        assert_eq!(onchain.deviation_bps, 25);
+        assert_eq!(onchain.max_gas_price, 50 * GWEI);
+        assert_eq!(onchain.price_decimals, 8);
+        assert_eq!(onchain.heartbeat, Duration::from_secs(3_600));
+        let cli = parse_with_env(
+            &[("ORACLE_AGG_GRPC_REQUEST_TIMEOUT_SECS", "5")],
+            &["--grpc-keepalive-interval-secs", "60"],
+        );
+        let transport = Settings::resolve(cli.settings, Settings::default())
+            .unwrap()
+            .transport_config();
+        assert_eq!(transport.keepalive_interval, Duration::from_secs(60));
+        assert_eq!(
+            transport.keepalive_timeout,
+            TransportConfig::default().keepalive_timeout
+        );
+        assert_eq!(transport.request_timeout, Duration::from_secs(5));
+        let redis = Settings {
+            redis_url: Some("redis://cache:6379".to_string()),
+            ..settings.clone()
+        }
+        .redis_config()
+        .unwrap();
+        // 키는 가격 유효 기간(90초)의 두 배 동안 유지
+        assert_eq!(redis.ttl, Duration::from_secs(180));
+        assert_eq!(redis.key("BTC/USD"), "oracle:price:BTC-USD");
+        assert_eq!(redis.channel, "oracle:updates");
+        assert!(settings.postgres_config().unwrap().is_none());
+        let cli = parse_with_env(
+            &[
+                ("ORACLE_AGG_POSTGRES_BATCH_SIZE", "1000"),
+                ("ORACLE_AGG_POSTGRES_RETENTION_SECS", "604800"),
+                ("ORACLE_AGG_POSTGRES_VACUUM_INTERVAL_SECS", "3600"),
+            ],
+            &[
+                "--postgres-url",
+                "postgres://oracle@db/oracle",
+                "--postgres-statement-timeout-secs",
+                "2",
+            ],
+        );
+        let postgres = Settings::resolve(cli.settings, Settings::default())
+            .unwrap()
+            .postgres_config()
+            .unwrap()
+            .unwrap();
+        assert_eq!(postgres.url, "postgres://oracle@db/oracle");
+        assert_eq!(postgres.max_connections, DEFAULT_POSTGRES_MAX_CONNECTIONS);
+        assert_eq!(postgres.statement_timeout, Duration::from_secs(2));
+        assert_eq!(postgres.writer.batch_size, 1000);
+        assert_eq!(
+            postgres.writer.retention,
+            Some(Duration::from_secs(604_800))
+        );
+        assert_eq!(postgres.writer.vacuum_interval, Duration::from_secs(3_600));
+        let wal = Settings {
+            wal_dir: Some(PathBuf::from("wal")),
+            wal_retention_secs: Some(86_400),
+            ..Settings::default()
+        }
+        .wal_config()
+        .unwrap();
+        assert_eq!(wal.retention, Some(Duration::from_secs(86_400)));
+        let parquet = Settings {
+            parquet_dir: Some(PathBuf::from("archive")),
+            parquet_wal_retention_secs: Some(7 * 86_400),
+            ..Settings::default()
+        };
+        assert!(parquet.parquet_archive_config().is_err());
+        let parquet = Settings {
+            wal_dir: Some(PathBuf::from("wal")),
+            ..parquet
+        }
+        .parquet_archive_config()
+        .unwrap()
+        .unwrap();
+        assert_eq!(parquet.wal_dir, PathBuf::from("wal"));
+        assert_eq!(parquet.wal_retention, Some(Duration::from_secs(604_800)));
+        let cli = parse_with_env(
+            &[(
+                "ORACLE_AGG_HTTP_CORS_ORIGINS",
+                "https://dash.example, https://ops.example",
+            )],
+            &["--http-addr", "127.0.0.1:8080"],
+        );
+        let rest = Settings::resolve(cli.settings, Settings::default())
+            .unwrap()
+            .rest_config()
+            .unwrap();
+        assert_eq!(rest.addr, "127.0.0.1:8080".parse().unwrap());
+        assert_eq!(
+            rest.cors_origins,
+            ["https://dash.example", "https://ops.example"]
+        );
+        assert_eq!(rest.ws, WsConfig::default());
+        assert_eq!(settings.grpc_web_config(), None);
+        let cli = parse_with_env(
+            &[(
+                "ORACLE_AGG_GRPC_WEB_CORS_ORIGINS",
+                " https://dash.example,,",
+            )],
+            &["--grpc-web"],
+        );
+        let grpc_web = Settings::resolve(cli.settings, Settings::default())
+            .unwrap()
+            .grpc_web_config()
+            .unwrap();
+        assert_eq!(grpc_web.cors_origins, ["https://dash.example"]);
+        assert_eq!(settings.file_sink_config().unwrap(), None);
+        let cli = parse_with_env(
+            &[("ORACLE_AGG_FILE_SINK_FSYNC", "every:100")],
+            &["--file-sink-path", "data/aggregates.jsonl"],
+        );
+        let file_sink = Settings::resolve(cli.settings, Settings::default())
+            .unwrap()
+            .file_sink_config()
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            file_sink,
+            JsonlConfig::new("data/aggregates.jsonl").with_fsync(FsyncPolicy::EveryN(100))
+        );
+        let cli = parse_with_env(
+            &[("ORACLE_AGG_SHUTDOWN_DRAIN_SECS", "5")],
+            &[
+                "--wal-dir",
+                "wal",
+                "--file-sink-path",
+                "data/aggregates.jsonl",
+            ],
+        );
+        let readiness = Settings::resolve(cli.settings, Settings::default())
+            .unwrap()
+            .readiness_config();
+        assert_eq!(readiness.warmup_grace, Duration::from_secs(60));
+        assert_eq!(readiness.drain_delay, Duration::from_secs(5));
+        assert_eq!(
+            readiness.storage,
+            [PathBuf::from("wal"), PathBuf::from("data")]
+        );
+
+        let config = settings.aggregator_config().unwrap();
+        assert_eq!(config.max_batch_size, 20);
+        assert_eq!(config.publish_interval_secs, Some(15));
+        assert_eq!(config.effective_strategy().name(), "weighted median");
+        assert_eq!(config.source_weights.get("coinbase"), Some(&2.0));
+        assert_eq!(
+            config.allowed_nodes.iter().collect::<Vec<_>>(),
+            ["node-a", "node-b"]
+        );
+
+        let geometric = Settings {
+            aggregation_mode: Some("geometric".to_string()),
+            ..settings
+        };
+        let config = geometric.aggregator_config().unwrap();
+        assert_eq!(config.aggregation_mode, AggregationMode::Geometric);
+        assert_eq!(
+            config.effective_strategy().name(),
+            "weighted geometric median"
+        );
+        let floored = Settings {
+            min_points: Some(3),
+            ..geometric
+        };
+        let config = floored.aggregator_config().unwrap();
+        assert_eq!(config.min_points, Some(3));
+        assert_eq!(
+            config.effective_strategy().name(),
+            "weighted geometric median"
+        );
+    }
+
+    #[test]
+    fn test_dump_redacts_secrets_and_round_trips() {
+        let cli = parse_with_env(
+            &[
+                ("ORACLE_AGG_ADMIN_SECRET", "s3cret-from-env"),
+                ("ORACLE_AGG_WEBHOOK_SECRET", "hook-s3cret-from-env"),
+                ("ORACLE_AGG_ONCHAIN_PRIVATE_KEY", "0xkey-from-env"),
+                (
+                    "ORACLE_AGG_ALERT_SLACK_URLS",
+                    "https://hooks.slack.com/services/T0/B0/slack-token",
+                ),
+            ],
+            &[
+                "--tls",
+                "--skew-clamp-secs",
+                "5",
+                "--alert-quorum-nodes",
+                "3",
+                (
+                    "ORACLE_AGG_POSTGRES_URL",
+                    "postgres://oracle:pg-s3cret@db:5432/oracle",
+                ),
+            ],
+        );
+        let settings = Settings::resolve(cli.settings, Settings::default()).unwrap();
+        assert_eq!(settings.admin_secret.as_deref(), Some("s3cret-from-env"));
+        assert!(settings.tls_config().is_some());
+
+        let dump = settings.dump().unwrap();
+        assert!(!dump.contains("s3cret-from-env"));
+        assert!(!dump.contains("hook-s3cret-from-env"));
+        assert!(!dump.contains("0xkey-from-env"));
+        assert!(!dump.contains("slack-token"));
+        assert!(!dump.contains("pg-s3cret"));
+        assert!(dump.contains(REDACTED));
+        assert!(dump.contains("skew_clamp_secs = 5"));
+
+        // 출력은 그대로 설정 파일로 쓸 수 있음 (비밀값만 다름)
+        let reloaded: Settings = toml::from_str(&dump).unwrap();
+        assert_eq!(reloaded, settings.redacted());
+    }
+
+    #[test]
+    fn test_export_subcommand_shares_server_settings() {
+        let cli = parse_with_env(
+            &[("ORACLE_AGG_WAL_DIR", "/var/lib/aggregator/wal")],
+            &[
+                "export",
+                "--config",
+                "aggregator.toml",
+                "--from",
+                "2023-11-14T22:13:20Z",
+                "--to",
+                "1700003600",
+                "--out",
+                "history.csv",
+            ],
+        );
+        assert_eq!(cli.config, Some(PathBuf::from("aggregator.toml")));
+        assert_eq!(
+            cli.settings.wal_dir,
+            Some(PathBuf::from("/var/lib/aggregator/wal"))
+        );
+        assert_eq!(
+            cli.command,
+            Some(Command::Export(ExportArgs {
+                from: Some(1_700_000_000),
+                to: Some(1_700_003_600),
+                pair: None,
+                out: PathBuf::from("history.csv"),
+            }))
+        );
+        assert_eq!(parse_with_env(&[], &[]).command, None);
+    }
+
+    #[test]
+    fn test_invalid_settings_are_rejected() {
+        let invalid = [
+            Settings {
+                strategy: Some("mean".to_string()),
+                ..Settings::default()
+            },
+            Settings {
+                strategy: Some("trust-weighted-median".to_string()),
+                trust_volume_exponent: Some(-1.0),
+                ..Settings::default()
+            },
+            Settings {
+                source_weights: Some(BTreeMap::from([("kraken".to_string(), -1.0)])),
+                ..Settings::default()
+            },
+            Settings {
+                sla_target: Some(99.9),
+                ..Settings::default()
+            },
+            Settings {
+                wal_retention_secs: Some(0),
+                ..Settings::default()
+            },
+            Settings {
+                aggregation_mode: Some("harmonic".to_string()),
+                ..Settings::default()
+            },
+            Settings {
+                min_points: Some(0),
+                ..Settings::default()
+            },
+            Settings {
+                ws_idle_timeout_secs: Some(0),
+                ..Settings::default()
+            },
+            Settings {
+                grpc_keepalive_timeout_secs: Some(0),
+                ..Settings::default()
+            },
+            Settings {
+                price_unit: Some("satoshis".to_string()),
+                ..Settings::default()
+            },
+            Settings {
+                onchain_rpc_url: Some("http://localhost:8545".to_string()),
+                onchain_private_key: Some("0x01".to_string()),
+                ..Settings::default()
+            },
+            Settings {
+                onchain_rpc_url: Some("http://localhost:8545".to_string()),
+                onchain_contract: Some("0x5FbDB2315678afecb367f032d93F642f64180aa3".to_string()),
+                onchain_private_key: Some("0x01".to_string()),
+                onchain_keystore: Some(PathBuf::from("keys/oracle.json")),
+                onchain_keystore_password: Some("hunter2".to_string()),
+                ..Settings::default()
+            },
+            Settings {
+                onchain_rpc_url: Some("http://localhost:8545".to_string()),
+                onchain_contract: Some("0x5FbDB2315678afecb367f032d93F642f64180aa3".to_string()),
+                onchain_keystore: Some(PathBuf::from("keys/oracle.json")),
+                ..Settings::default()
+            },
+            Settings {
+                postgres_url: Some("postgres://oracle@db/oracle".to_string()),
+                postgres_max_connections: Some(0),
+                ..Settings::default()
+            },
+            Settings {
+                postgres_url: Some("postgres://oracle@db/oracle".to_string()),
+                postgres_statement_timeout_secs: Some(0),
+                ..Settings::default()
+            },
+            Settings {
+                postgres_url: Some("postgres://oracle@db/oracle".to_string()),
+                postgres_batch_size: Some(0),
+                ..Settings::default()
+            },
+            Settings {
+                postgres_url: Some("postgres://oracle@db/oracle".to_string()),
+                postgres_retention_secs: Some(0),
+                ..Settings::default()
+            },
+            Settings {
+                postgres_url: Some("postgres://oracle@db/oracle".to_string()),
+                postgres_vacuum_interval_secs: Some(0),
+                ..Settings::default()
+            },
+            Settings {
+                webhook_urls: Some(vec!["ftp://hooks.example/oracle".to_string()]),
+                ..Settings::default()
+            },
+            Settings {
+                webhook_urls: Some(vec!["https://hooks.example/oracle".to_string()]),
+                webhook_events: Some(vec!["quarantine".to_string()]),
+                ..Settings::default()
+            },
+            Settings {
+                alert_quorum_nodes: Some(3),
+                ..Settings::default()
+            },
+            Settings {
+                alert_slack_urls: Some(vec!["https://hooks.slack.com/services/x".to_string()]),
+                ..Settings::default()
+            },
+            Settings {
+                alert_no_data_secs: Some(0),
+                alert_slack_urls: Some(vec!["https://hooks.slack.com/services/x".to_string()]),
+                ..Settings::default()
+            },
+            Settings {
+                alert_price_move_bps: Some(500),
+                alert_discord_urls: Some(vec!["discord.com/api/webhooks/1/y".to_string()]),
+                ..Settings::default()
+            },
+            Settings {
+                file_sink_path: Some(PathBuf::from("aggregates.jsonl")),
+                file_sink_fsync: Some("sometimes".to_string()),
+                ..Settings::default()
+            },
+            Settings {
+                file_sink_path: Some(PathBuf::from("aggregates.jsonl")),
+                file_sink_max_bytes: Some(0),
+                ..Settings::default()
+            },
+        ];
+        for settings in invalid {
+            assert!(Settings::resolve(settings, Settings::default()).is_err());
+        }
+
+        assert!(toml::from_str::<Settings>("staleness = 5").is_err());
+        assert!(Settings::load_file(Some(Path::new("/nonexistent/aggregator.toml"))).is_err());
+        // 예제 설정 파일은 그대로 읽혀야 함
+        let example: Settings =
+            toml::from_str(include_str!("../config/aggregator.example.toml")).unwrap();
+        assert!(Settings::resolve(Settings::default(), example).is_ok());
+
+        // 0이면 평판 회복 비활성
+        let settings = Settings {
+            reputation_half_life_secs: Some(0),
+            ..Settings::default()
+        };
+        let config = settings.aggregator_config().unwrap();
+        assert_eq!(config.reputation_half_life_secs, None);
+    }
+}
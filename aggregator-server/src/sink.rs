@@ -0,0 +1,1110 @@
+//! 수락된 제출과 게시된 집계를 외부 시스템(Kafka, Redis, 파일)으로 내보내는 싱크
+//!
+//! `submit_price`는 수락한 제출을 bounded 채널에 `try_send`로만 넘기므로 싱크가 느리거나 멈춰도
+//! 제출 지연은 없다 (채널이 가득 차면 버리고 `dropped`로 셈). 집계 결과는 게시 채널을 구독해 받는다.
+//! 전송은 싱크마다 백그라운드 태스크 하나가 레코드를 JSON으로 직렬화해 `Sink`로 넘기며, 실패하면
+//! 백오프하며 `max_attempts`까지 재시도한다. 서버 종료 알림을 받으면 채널에 남은 제출을 보낸 뒤
+//! flush하고 끝난다. 싱크마다 태스크와 채널이 따로라서 여러 싱크를 함께 켜도 서로 막지 않는다.
+//!
+//! - `KafkaSink`: 제출과 집계를 각각의 토픽에 pair를 키로 보낸다. rdkafka 구현(`KafkaProducer`)은
+//!   `kafka` feature로만 빌드된다.
+//! - `RedisSink`: 집계만 받아 최신 값을 `oracle:price:<pair>` 키에 TTL과 함께 저장하고 같은 내용을
+//!   `oracle:updates` 채널로 발행한다. redis 구현(`RedisConnection`)은 `redis` feature로만 빌드된다.
+//! - `FileSink`: 집계만 받아 JSONL 파일에 한 줄씩 덧붙인다. fsync 주기와 크기 기준 로테이션은
+//!   `oracle_vm_common::jsonl`이 맡는다.
+
+use oracle_vm_common::jsonl::{JsonlConfig, JsonlWriter};
+use oracle_vm_common::Price;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::sync::mpsc::{self, error::TrySendError};
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::snapshot::{AggregateSnapshot, AggregateUpdate};
+use crate::DEFAULT_PAIR;
+
+/// 싱크 전송 설정 (모든 싱크 공통)
+#[derive(Debug, Clone, PartialEq)]
+pub struct SinkConfig {
+    /// 전송 대기 채널 크기 (가득 차면 제출 레코드를 버림)
+    pub channel_capacity: usize,
+    /// 레코드 하나의 최대 전송 시도 횟수
+    pub max_attempts: u32,
+    /// 첫 재시도 전 대기 시간 (재시도마다 두 배, `max_backoff`까지)
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    /// 종료할 때 미전송 레코드를 기다리는 시간
+    pub flush_timeout: Duration,
+}
+
+impl Default for SinkConfig {
+    fn default() -> Self {
+        Self {
+            channel_capacity: 10_000,
+            max_attempts: 5,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(5),
+            flush_timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+/// 레코드 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordKind {
+    /// 수락된 제출 (`SubmissionMessage`)
+    Submission,
+    /// 게시된 집계 (`AggregateMessage`)
+    Aggregate,
+}
+
+/// 싱크로 넘기는 직렬화된 레코드
+#[derive(Debug, Clone, PartialEq)]
+pub struct SinkRecord {
+    pub kind: RecordKind,
+    /// 자산 쌍 (예: "BTC/USD")
+    pub pair: String,
+    /// JSON 본문
+    pub payload: Vec<u8>,
+}
+
+/// 레코드를 내보내는 출력
+///
+/// 싱크 태스크가 재시도와 종료 처리를 맡으므로 구현은 레코드 하나를 한 번 보내기만 하면 된다.
+#[tonic::async_trait]
+pub trait Sink: Send + 'static {
+    /// 로그용 설명
+    fn describe(&self) -> String;
+
+    /// 수락된 제출도 받는지 (false면 제출은 채널에 넣지 않고 집계만 전달)
+    fn accepts_submissions(&self) -> bool {
+        true
+    }
+
+    /// 레코드 하나를 보내고 전달 확인까지 대기
+    async fn send(&mut self, record: &SinkRecord) -> Result<(), String>;
+
+    /// 아직 전달되지 않은 레코드를 `timeout`까지 전송
+    async fn flush(&mut self, _timeout: Duration) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// Kafka 싱크 설정
+#[derive(Debug, Clone, PartialEq)]
+pub struct KafkaConfig {
+    /// Kafka 브로커 목록 (쉼표로 구분, 예: "kafka-1:9092,kafka-2:9092")
+    pub brokers: String,
+    /// 수락된 제출을 보내는 토픽
+    pub submissions_topic: String,
+    /// 게시된 집계를 보내는 토픽
+    pub aggregates_topic: String,
+}
+
+impl Default for KafkaConfig {
+    fn default() -> Self {
+        Self {
+            brokers: "localhost:9092".to_string(),
+            submissions_topic: "oracle.submissions".to_string(),
+            aggregates_topic: "oracle.aggregates".to_string(),
+        }
+    }
+}
+
+/// 레코드를 Kafka로 실제 전송하는 producer
+#[tonic::async_trait]
+pub trait RecordProducer: Send + 'static {
+    /// `topic`에 `key`로 레코드 하나를 보내고 전달 확인까지 대기
+    async fn send(&mut self, topic: &str, key: &str, payload: &[u8]) -> Result<(), String>;
+
+    /// 아직 전달되지 않은 레코드를 `timeout`까지 전송
+    async fn flush(&mut self, timeout: Duration) -> Result<(), String>;
+}
+
+/// 제출과 집계를 종류별 토픽에 pair를 키로 보내는 싱크
+pub struct KafkaSink<P> {
+    producer: P,
+    config: KafkaConfig,
+}
+
+impl<P: RecordProducer> KafkaSink<P> {
+    pub fn new(producer: P, config: KafkaConfig) -> Self {
+        Self { producer, config }
+    }
+}
+
+#[tonic::async_trait]
+impl<P: RecordProducer> Sink for KafkaSink<P> {
+    fn describe(&self) -> String {
+        format!(
+            "Kafka (submissions to {}, aggregates to {}, brokers {})",
+            self.config.submissions_topic, self.config.aggregates_topic, self.config.brokers
+        )
+    }
+
+    async fn send(&mut self, record: &SinkRecord) -> Result<(), String> {
+        let topic = match record.kind {
+            RecordKind::Submission => &self.config.submissions_topic,
+            RecordKind::Aggregate => &self.config.aggregates_topic,
+        };
+        self.producer
+            .send(topic, &record.pair, &record.payload)
+            .await
+    }
+
+    async fn flush(&mut self, timeout: Duration) -> Result<(), String> {
+        self.producer.flush(timeout).await
+    }
+}
+
+/// Redis 싱크 설정
+#[derive(Debug, Clone, PartialEq)]
+pub struct RedisConfig {
+    /// Redis 주소 (예: "redis://localhost:6379")
+    pub url: String,
+    /// 최신 집계를 저장하는 키 접두사 (키는 접두사 + pair, "/"는 "-"로 바꿈)
+    pub key_prefix: String,
+    /// 집계를 발행하는 pub/sub 채널
+    pub channel: String,
+    /// 저장한 키의 만료 시간 (집계가 끊기면 오래된 가격이 남지 않도록)
+    pub ttl: Duration,
+}
+
+impl Default for RedisConfig {
+    fn default() -> Self {
+        Self {
+            url: "redis://localhost:6379".to_string(),
+            key_prefix: "oracle:price:".to_string(),
+            channel: "oracle:updates".to_string(),
+            ttl: Duration::from_secs(120),
+        }
+    }
+}
+
+impl RedisConfig {
+    /// pair의 최신 집계를 저장하는 키 (예: "oracle:price:BTC-USD")
+    pub fn key(&self, pair: &str) -> String {
+        format!("{}{}", self.key_prefix, pair.replace('/', "-"))
+    }
+}
+
+/// Redis로 실제 명령을 보내는 연결
+#[tonic::async_trait]
+pub trait RedisCommands: Send + 'static {
+    /// `key`에 `value`를 `ttl` 만료로 저장 (SET .. PX)
+    async fn set_with_ttl(&mut self, key: &str, value: &[u8], ttl: Duration) -> Result<(), String>;
+
+    /// `channel`로 `payload` 발행 (PUBLISH)
+    async fn publish(&mut self, channel: &str, payload: &[u8]) -> Result<(), String>;
+}
+
+/// 최신 집계를 키에 저장하고 pub/sub 채널로 발행하는 싱크 (제출은 받지 않음)
+pub struct RedisSink<C> {
+    commands: C,
+    config: RedisConfig,
+}
+
+impl<C: RedisCommands> RedisSink<C> {
+    pub fn new(commands: C, config: RedisConfig) -> Self {
+        Self { commands, config }
+    }
+}
+
+#[tonic::async_trait]
+impl<C: RedisCommands> Sink for RedisSink<C> {
+    fn describe(&self) -> String {
+        format!(
+            "Redis (keys {}<pair>, channel {}, ttl {:?}, {})",
+            self.config.key_prefix, self.config.channel, self.config.ttl, self.config.url
+        )
+    }
+
+    fn accepts_submissions(&self) -> bool {
+        false
+    }
+
+    async fn send(&mut self, record: &SinkRecord) -> Result<(), String> {
+        if record.kind != RecordKind::Aggregate {
+            return Ok(());
+        }
+        // 저장이 성공한 뒤에만 발행하므로 구독자가 알림을 받고 GET하면 같은 값을 읽음
+        let key = self.config.key(&record.pair);
+        self.commands
+            .set_with_ttl(&key, &record.payload, self.config.ttl)
+            .await?;
+        self.commands
+            .publish(&self.config.channel, &record.payload)
+            .await
+    }
+}
+
+/// 게시된 집계를 JSONL 파일에 한 줄씩 덧붙이는 싱크
+pub struct FileSink {
+    writer: JsonlWriter,
+}
+
+impl FileSink {
+    /// 파일을 열고 (없으면 만듦) 이전 실행이 남긴 잘린 마지막 줄을 잘라 냄
+    pub fn open(config: JsonlConfig) -> std::io::Result<Self> {
+        let writer = JsonlWriter::open(config)?;
+        if writer.recovered_bytes() > 0 {
+            warn!(
+                "⚠️ Dropped torn last line ({} bytes) of {}",
+                writer.recovered_bytes(),
+                writer.path().display()
+            );
+        }
+        Ok(Self { writer })
+    }
+}
+
+#[tonic::async_trait]
+impl Sink for FileSink {
+    fn describe(&self) -> String {
+        let config = self.writer.config();
+        match config.max_bytes {
+            Some(max_bytes) => format!(
+                "JSONL file {} (fsync {}, rotate at {} bytes)",
+                config.path.display(),
+                config.fsync,
+                max_bytes
+            ),
+            None => format!(
+                "JSONL file {} (fsync {})",
+                config.path.display(),
+                config.fsync
+            ),
+        }
+    }
+
+    fn accepts_submissions(&self) -> bool {
+        false
+    }
+
+    async fn send(&mut self, record: &SinkRecord) -> Result<(), String> {
+        if record.kind != RecordKind::Aggregate {
+            return Ok(());
+        }
+        self.writer
+            .append_line(&record.payload)
+            .map_err(|e| e.to_string())
+    }
+
+    async fn flush(&mut self, _timeout: Duration) -> Result<(), String> {
+        self.writer.sync().map_err(|e| e.to_string())
+    }
+}
+
+/// 수락된 제출 레코드
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SubmissionMessage {
+    pub pair: String,
+    pub node_id: String,
+    pub source: String,
+    /// 가격 (USD)
+    pub price: f64,
+    /// 고정소수점 가격 (price_scaled × 10^-price_decimals)
+    pub price_scaled: u64,
+    pub price_decimals: u32,
+    /// 노드가 보고한 관측 시각
+    pub timestamp: u64,
+    /// 서버 수신 시각
+    pub received_at: u64,
+    /// 상태에 반영된 순서
+    pub seq: Option<u64>,
+}
+
+impl SubmissionMessage {
+    pub fn new(
+        pair: String,
+        node_id: &str,
+        source: &str,
+        price: Price,
+        timestamp: u64,
+        received_at: u64,
+        seq: Option<u64>,
+    ) -> Self {
+        let (price_scaled, price_decimals) = price.to_scaled();
+        Self {
+            pair,
+            node_id: node_id.to_string(),
+            source: source.to_string(),
+            price: price.to_f64_dollars(),
+            price_scaled,
+            price_decimals,
+            timestamp,
+            received_at,
+            seq,
+        }
+    }
+}
+
+/// 게시된 집계 레코드 (집계 가격이 없으면 가격 필드가 null)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AggregateMessage {
+    pub pair: String,
+    /// 집계 가격 (USD)
+    pub aggregated_price: Option<f64>,
+    pub price_scaled: Option<u64>,
+    pub price_decimals: Option<u32>,
+    pub contributing_nodes: usize,
+    pub stale: bool,
+    /// 집계 시각
+    pub timestamp: u64,
+}
+
+impl From<&AggregateSnapshot> for AggregateMessage {
+    fn from(snapshot: &AggregateSnapshot) -> Self {
+        let scaled = snapshot.aggregated_price.map(|price| price.to_scaled());
+        Self {
+            pair: DEFAULT_PAIR.to_string(),
+            aggregated_price: snapshot
+                .aggregated_price
+                .map(|price| price.to_f64_dollars()),
+            price_scaled: scaled.map(|(mantissa, _)| mantissa),
+            price_decimals: scaled.map(|(_, decimals)| decimals),
+            contributing_nodes: snapshot.contributing_nodes,
+            stale: snapshot.stale,
+            timestamp: snapshot.timestamp,
+        }
+    }
+}
+
+/// 싱크 누적 통계
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SinkStats {
+    /// 전달 확인된 레코드 수
+    pub sent: u64,
+    /// 실패 후 재시도한 횟수
+    pub retries: u64,
+    /// 재시도를 모두 실패해 포기한 레코드 수
+    pub failed: u64,
+    /// 채널이 가득 차거나 집계 구독이 밀려 보내지 못하고 버린 레코드 수
+    pub dropped: u64,
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    sent: AtomicU64,
+    retries: AtomicU64,
+    failed: AtomicU64,
+    dropped: AtomicU64,
+}
+
+impl Counters {
+    fn stats(&self) -> SinkStats {
+        SinkStats {
+            sent: self.sent.load(Ordering::Relaxed),
+            retries: self.retries.load(Ordering::Relaxed),
+            failed: self.failed.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// 제출 레코드를 싱크 태스크로 넘기는 핸들 (복제본은 같은 채널 공유)
+#[derive(Debug, Clone)]
+pub struct SinkSender {
+    tx: mpsc::Sender<SubmissionMessage>,
+    counters: Arc<Counters>,
+    accepts_submissions: bool,
+}
+
+impl SinkSender {
+    /// 제출 레코드 전달 (대기하지 않음, 채널이 가득 찼거나 닫혔으면 버림)
+    pub fn send(&self, message: SubmissionMessage) {
+        if !self.accepts_submissions {
+            return;
+        }
+        match self.tx.try_send(message) {
+            Ok(()) => {}
+            Err(TrySendError::Full(_)) | Err(TrySendError::Closed(_)) => {
+                self.counters.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// 누적 통계
+    pub fn stats(&self) -> SinkStats {
+        self.counters.stats()
+    }
+}
+
+/// 전송을 직접 맡는 태스크용 제출 채널 (제출을 모아 한 번에 쓰는 저장소 기록 태스크가 사용)
+pub(crate) fn channel(capacity: usize) -> (SinkSender, SubmissionReceiver) {
+    let (tx, rx) = mpsc::channel(capacity.max(1));
+    let counters = Arc::new(Counters::default());
+    let sender = SinkSender {
+        tx,
+        counters: counters.clone(),
+        accepts_submissions: true,
+    };
+    (sender, SubmissionReceiver { rx, counters })
+}
+
+/// `channel`의 수신 측 (전송 결과는 `SinkSender::stats`에 반영)
+pub(crate) struct SubmissionReceiver {
+    pub(crate) rx: mpsc::Receiver<SubmissionMessage>,
+    counters: Arc<Counters>,
+}
+
+impl SubmissionReceiver {
+    pub(crate) fn record_sent(&self, count: usize) {
+        self.counters
+            .sent
+            .fetch_add(count as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_failed(&self, count: usize) {
+        self.counters
+            .failed
+            .fetch_add(count as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn stats(&self) -> SinkStats {
+        self.counters.stats()
+    }
+}
+
+/// 채널 없이 전달 결과만 세는 카운터 (복제본은 같은 통계 공유)
+///
+/// 저장소 기록 태스크가 게시된 집계를 제출(`SinkSender::stats`)과 따로 세는 데 쓴다.
+#[derive(Debug, Clone, Default)]
+pub struct RecordCounter {
+    counters: Arc<Counters>,
+}
+
+impl RecordCounter {
+    pub(crate) fn record_sent(&self) {
+        self.counters.sent.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_failed(&self) {
+        self.counters.failed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_dropped(&self, count: u64) {
+        self.counters.dropped.fetch_add(count, Ordering::Relaxed);
+    }
+
+    /// 누적 통계
+    pub fn stats(&self) -> SinkStats {
+        self.counters.stats()
+    }
+}
+
+/// 싱크 태스크 실행
+///
+/// `updates`는 집계 게시 구독(`AggregatorServiceImpl::subscribe`)이며, 종료 알림을 받으면 남은
+/// 제출을 보내고 싱크를 flush한 뒤 태스크가 끝난다. 여러 싱크를 쓰려면 싱크마다 실행해
+/// 각 `SinkSender`를 `with_sink`로 등록한다.
+pub fn spawn<S: Sink>(
+    sink: S,
+    config: SinkConfig,
+    updates: broadcast::Receiver<AggregateUpdate>,
+) -> (SinkSender, JoinHandle<()>) {
+    let (tx, rx) = mpsc::channel(config.channel_capacity.max(1));
+    let sender = SinkSender {
+        tx,
+        counters: Arc::new(Counters::default()),
+        accepts_submissions: sink.accepts_submissions(),
+    };
+    info!("📤 Publishing to {}", sink.describe());
+    let task = SinkTask {
+        sink,
+        config,
+        counters: sender.counters.clone(),
+    };
+    let handle = tokio::spawn(task.run(rx, updates));
+    (sender, handle)
+}
+
+struct SinkTask<S> {
+    sink: S,
+    config: SinkConfig,
+    counters: Arc<Counters>,
+}
+
+impl<S: Sink> SinkTask<S> {
+    async fn run(
+        mut self,
+        mut submissions: mpsc::Receiver<SubmissionMessage>,
+        mut updates: broadcast::Receiver<AggregateUpdate>,
+    ) {
+        loop {
+            // 제출을 먼저 보내 집계 레코드가 그 집계에 포함된 제출보다 앞서지 않게 함
+            tokio::select! {
+                biased;
+                Some(message) = submissions.recv() => {
+                    self.deliver(RecordKind::Submission, &message.pair, &message).await;
+                }
+                update = updates.recv() => match update {
+                    Ok(AggregateUpdate::Published(snapshot)) => {
+                        let message = AggregateMessage::from(&*snapshot);
+                        self.deliver(RecordKind::Aggregate, &message.pair, &message).await;
+                    }
+                    Ok(AggregateUpdate::Shutdown) | Err(RecvError::Closed) => break,
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!("⚠️ Sink lagged, skipped {} aggregates", skipped);
+                        self.counters.dropped.fetch_add(skipped, Ordering::Relaxed);
+                    }
+                },
+            }
+        }
+
+        // 종료: 이미 받은 제출까지 보낸 뒤 flush (이후 제출은 버림)
+        submissions.close();
+        while let Some(message) = submissions.recv().await {
+            self.deliver(RecordKind::Submission, &message.pair, &message)
+                .await;
+        }
+        match self.sink.flush(self.config.flush_timeout).await {
+            Ok(()) => info!("📤 Sink flushed ({:?})", self.counters.stats()),
+            Err(e) => warn!(
+                "⚠️ Failed to flush sink: {} ({:?})",
+                e,
+                self.counters.stats()
+            ),
+        }
+    }
+
+    // 레코드 하나 전송 (실패하면 백오프하며 재시도, 모두 실패하면 포기)
+    async fn deliver<T: Serialize>(&mut self, kind: RecordKind, pair: &str, message: &T) {
+        let record = match serde_json::to_vec(message) {
+            Ok(payload) => SinkRecord {
+                kind,
+                pair: pair.to_string(),
+                payload,
+            },
+            Err(e) => {
+                warn!("⚠️ Failed to encode {:?} sink record: {}", kind, e);
+                self.counters.failed.fetch_add(1, Ordering::Relaxed);
+                return;
+            }
+        };
+
+        let max_attempts = self.config.max_attempts.max(1);
+        let mut backoff = self.config.initial_backoff;
+        for attempt in 1..=max_attempts {
+            match self.sink.send(&record).await {
+                Ok(()) => {
+                    self.counters.sent.fetch_add(1, Ordering::Relaxed);
+                    return;
+                }
+                Err(e) if attempt < max_attempts => {
+                    warn!(
+                        "⚠️ {:?} sink delivery failed ({}/{}): {}, retrying in {:?}",
+                        kind, attempt, max_attempts, e, backoff
+                    );
+                    self.counters.retries.fetch_add(1, Ordering::Relaxed);
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(self.config.max_backoff);
+                }
+                Err(e) => {
+                    warn!(
+                        "❌ Giving up on {:?} sink record after {} attempts: {}",
+                        kind, max_attempts, e
+                    );
+                    self.counters.failed.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        }
+    }
+}
+
+/// rdkafka producer
+#[cfg(feature = "kafka")]
+pub struct KafkaProducer {
+    producer: rdkafka::producer::FutureProducer,
+}
+
+#[cfg(feature = "kafka")]
+impl KafkaProducer {
+    /// `brokers`에 연결하는 producer (연결은 첫 전송 때 이루어짐)
+    pub fn new(brokers: &str) -> anyhow::Result<Self> {
+        let producer = rdkafka::ClientConfig::new()
+            .set("bootstrap.servers", brokers)
+            .set("message.timeout.ms", "5000")
+            .create()?;
+        Ok(Self { producer })
+    }
+}
+
+#[cfg(feature = "kafka")]
+#[tonic::async_trait]
+impl RecordProducer for KafkaProducer {
+    async fn send(&mut self, topic: &str, key: &str, payload: &[u8]) -> Result<(), String> {
+        let record = rdkafka::producer::FutureRecord::to(topic)
+            .key(key)
+            .payload(payload);
+        self.producer
+            .send(record, Duration::ZERO)
+            .await
+            .map(|_| ())
+            .map_err(|(e, _)| e.to_string())
+    }
+
+    async fn flush(&mut self, timeout: Duration) -> Result<(), String> {
+        use rdkafka::producer::Producer;
+        self.producer.flush(timeout).map_err(|e| e.to_string())
+    }
+}
+
+/// redis 연결 (명령이 실패하면 연결을 버리고 다음 시도에서 다시 연결)
+#[cfg(feature = "redis")]
+pub struct RedisConnection {
+    client: redis::Client,
+    connection: Option<redis::aio::MultiplexedConnection>,
+}
+
+#[cfg(feature = "redis")]
+impl RedisConnection {
+    /// `url`에 연결하는 클라이언트 (연결은 첫 명령 때 이루어짐)
+    pub fn new(url: &str) -> anyhow::Result<Self> {
+        Ok(Self {
+            client: redis::Client::open(url)?,
+            connection: None,
+        })
+    }
+
+    async fn query(&mut self, cmd: &redis::Cmd) -> Result<(), String> {
+        let mut connection = match self.connection.take() {
+            Some(connection) => connection,
+            None => {
+                let connection = self
+                    .client
+                    .get_multiplexed_async_connection()
+                    .await
+                    .map_err(|e| format!("failed to connect: {}", e))?;
+                info!("🔌 Connected to Redis");
+                connection
+            }
+        };
+        match cmd.query_async::<redis::Value>(&mut connection).await {
+            Ok(_) => {
+                self.connection = Some(connection);
+                Ok(())
+            }
+            Err(e) => {
+                warn!(
+                    "🔌 Redis command failed, reconnecting on next attempt: {}",
+                    e
+                );
+                Err(e.to_string())
+            }
+        }
+    }
+}
+
+#[cfg(feature = "redis")]
+#[tonic::async_trait]
+impl RedisCommands for RedisConnection {
+    async fn set_with_ttl(&mut self, key: &str, value: &[u8], ttl: Duration) -> Result<(), String> {
+        let mut cmd = redis::cmd("SET");
+        cmd.arg(key)
+            .arg(value)
+            .arg("PX")
+            .arg(ttl.as_millis() as u64);
+        self.query(&cmd).await
+    }
+
+    async fn publish(&mut self, channel: &str, payload: &[u8]) -> Result<(), String> {
+        let mut cmd = redis::cmd("PUBLISH");
+        cmd.arg(channel).arg(payload);
+        self.query(&cmd).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oracle::oracle_service_server::OracleService;
+    use crate::oracle::PriceRequest;
+    use crate::AggregatorServiceImpl;
+    use std::sync::Mutex;
+    use std::time::Instant;
+    use tokio::sync::Notify;
+    use tonic::Request;
+
+    type Sent = Arc<Mutex<Vec<(String, String, serde_json::Value)>>>;
+
+    /// 보낸 레코드를 기록하는 producer (처음 `failures`번은 실패)
+    #[derive(Default)]
+    struct MockProducer {
+        sent: Sent,
+        failures: u32,
+        flushed: Arc<Mutex<bool>>,
+    }
+
+    #[tonic::async_trait]
+    impl RecordProducer for MockProducer {
+        async fn send(&mut self, topic: &str, key: &str, payload: &[u8]) -> Result<(), String> {
+            if self.failures > 0 {
+                self.failures -= 1;
+                return Err("broker unavailable".to_string());
+            }
+            self.sent.lock().unwrap().push((
+                topic.to_string(),
+                key.to_string(),
+                serde_json::from_slice(payload).unwrap(),
+            ));
+            Ok(())
+        }
+
+        async fn flush(&mut self, _timeout: Duration) -> Result<(), String> {
+            *self.flushed.lock().unwrap() = true;
+            Ok(())
+        }
+    }
+
+    /// 풀어 줄 때까지 전송이 끝나지 않는 producer
+    struct StalledProducer {
+        release: Arc<Notify>,
+    }
+
+    #[tonic::async_trait]
+    impl RecordProducer for StalledProducer {
+        async fn send(&mut self, _topic: &str, _key: &str, _payload: &[u8]) -> Result<(), String> {
+            self.release.notified().await;
+            Ok(())
+        }
+
+        async fn flush(&mut self, _timeout: Duration) -> Result<(), String> {
+            Ok(())
+        }
+    }
+
+    fn price_request(price: f64, node_id: &str) -> Request<PriceRequest> {
+        Request::new(PriceRequest {
+            price,
+            timestamp: chrono::Utc::now().timestamp() as u64,
+            source: "binance".to_string(),
+            node_id: node_id.to_string(),
+            signature: None,
+            price_scaled: None,
+            price_decimals: None,
+            symbol: Some("BTCUSDT".to_string()),
+            historical: false,
+            volume: None,
+        })
+    }
+
+    /// 보낸 명령을 기록하는 Redis 연결 (처음 `failures`번은 실패)
+    #[derive(Default)]
+    struct MockRedis {
+        sets: Arc<Mutex<Vec<(String, serde_json::Value, Duration)>>>,
+        publishes: Arc<Mutex<Vec<(String, serde_json::Value)>>>,
+        failures: u32,
+    }
+
+    #[tonic::async_trait]
+    impl RedisCommands for MockRedis {
+        async fn set_with_ttl(
+            &mut self,
+            key: &str,
+            value: &[u8],
+            ttl: Duration,
+        ) -> Result<(), String> {
+            if self.failures > 0 {
+                self.failures -= 1;
+                return Err("connection refused".to_string());
+            }
+            self.sets.lock().unwrap().push((
+                key.to_string(),
+                serde_json::from_slice(value).unwrap(),
+                ttl,
+            ));
+            Ok(())
+        }
+
+        async fn publish(&mut self, channel: &str, payload: &[u8]) -> Result<(), String> {
+            self.publishes.lock().unwrap().push((
+                channel.to_string(),
+                serde_json::from_slice(payload).unwrap(),
+            ));
+            Ok(())
+        }
+    }
+
+    fn kafka<P: RecordProducer>(producer: P) -> KafkaSink<P> {
+        KafkaSink::new(producer, KafkaConfig::default())
+    }
+
+    fn quick_config() -> SinkConfig {
+        SinkConfig {
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(5),
+            ..SinkConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_publishes_accepted_submissions_and_aggregates_keyed_by_pair() {
+        let producer = MockProducer {
+            failures: 2,
+            ..MockProducer::default()
+        };
+        let (sent, flushed) = (producer.sent.clone(), producer.flushed.clone());
+        let service = AggregatorServiceImpl::new();
+        let (sink, task) = spawn(kafka(producer), quick_config(), service.subscribe());
+        let service = service.with_sink(sink.clone());
+
+        service
+            .submit_price(price_request(70_000.5, "node-a"))
+            .await
+            .unwrap();
+        // 거부된 제출은 보내지 않음
+        service
+            .submit_price(price_request(-1.0, "node-b"))
+            .await
+            .unwrap();
+        service.publish_snapshot().await;
+        service.shutdown();
+        task.await.unwrap();
+
+        let sent = sent.lock().unwrap();
+        assert_eq!(sent.len(), 2, "{:?}", sent);
+        let (topic, key, submission) = &sent[0];
+        assert_eq!(topic, "oracle.submissions");
+        assert_eq!(key, "BTC/USD");
+        assert_eq!(submission["node_id"], "node-a");
+        assert_eq!(submission["source"], "binance");
+        assert_eq!(submission["price"], 70_000.5);
+        assert_eq!(submission["pair"], "BTC/USD");
+
+        let (topic, key, aggregate) = &sent[1];
+        assert_eq!(topic, "oracle.aggregates");
+        assert_eq!(key, "BTC/USD");
+        assert_eq!(aggregate["aggregated_price"], 70_000.5);
+        assert_eq!(aggregate["contributing_nodes"], 1);
+
+        // 처음 두 번의 실패는 재시도로 복구되고, 종료할 때 flush
+        assert_eq!(
+            sink.stats(),
+            SinkStats {
+                sent: 2,
+                retries: 2,
+                failed: 0,
+                dropped: 0,
+            }
+        );
+        assert!(*flushed.lock().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_attempts() {
+        let producer = MockProducer {
+            failures: u32::MAX,
+            ..MockProducer::default()
+        };
+        let service = AggregatorServiceImpl::new();
+        let config = SinkConfig {
+            max_attempts: 3,
+            ..quick_config()
+        };
+        let (sink, task) = spawn(kafka(producer), config, service.subscribe());
+        let service = service.with_sink(sink.clone());
+
+        service
+            .submit_price(price_request(70_000.0, "node-a"))
+            .await
+            .unwrap();
+        service.shutdown();
+        task.await.unwrap();
+
+        assert_eq!(sink.stats().failed, 1);
+        assert_eq!(sink.stats().retries, 2);
+        assert_eq!(sink.stats().sent, 0);
+    }
+
+    #[tokio::test]
+    async fn test_stalled_sink_never_delays_submissions() {
+        let release = Arc::new(Notify::new());
+        let producer = StalledProducer {
+            release: release.clone(),
+        };
+        let service = AggregatorServiceImpl::new();
+        let config = SinkConfig {
+            channel_capacity: 2,
+            ..quick_config()
+        };
+        let (sink, task) = spawn(kafka(producer), config, service.subscribe());
+        let service = service.with_sink(sink.clone());
+
+        // 첫 레코드는 멈춘 전송에 붙잡히고 채널 두 칸이 차면 나머지는 버려짐
+        let started = Instant::now();
+        for i in 0..20 {
+            service
+                .submit_price(price_request(70_000.0 + i as f64, &format!("node-{}", i)))
+                .await
+                .unwrap();
+        }
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert!(sink.stats().dropped >= 17, "{:?}", sink.stats());
+        assert_eq!(sink.stats().sent, 0);
+
+        task.abort();
+    }
+
+    #[tokio::test]
+    async fn test_redis_sink_stores_and_publishes_latest_aggregate_alongside_kafka() {
+        let producer = MockProducer::default();
+        let kafka_sent = producer.sent.clone();
+        let redis = MockRedis::default();
+        let (sets, publishes) = (redis.sets.clone(), redis.publishes.clone());
+        let redis_config = RedisConfig {
+            ttl: Duration::from_secs(180),
+            ..RedisConfig::default()
+        };
+
+        let service = AggregatorServiceImpl::new();
+        let (kafka_sink, kafka_task) = spawn(kafka(producer), quick_config(), service.subscribe());
+        let (redis_sink, redis_task) = spawn(
+            RedisSink::new(redis, redis_config),
+            quick_config(),
+            service.subscribe(),
+        );
+        let service = service
+            .with_sink(kafka_sink.clone())
+            .with_sink(redis_sink.clone());
+
+        service
+            .submit_price(price_request(70_000.5, "node-a"))
+            .await
+            .unwrap();
+        service.publish_snapshot().await;
+        service.shutdown();
+        kafka_task.await.unwrap();
+        redis_task.await.unwrap();
+
+        // Kafka는 제출과 집계를 모두 받음
+        assert_eq!(kafka_sent.lock().unwrap().len(), 2);
+        assert_eq!(kafka_sink.stats().sent, 2);
+
+        // Redis는 집계만 받아 키에 TTL로 저장하고 같은 내용을 발행
+        let sets = sets.lock().unwrap();
+        assert_eq!(sets.len(), 1, "{:?}", sets);
+        let (key, value, ttl) = &sets[0];
+        assert_eq!(key, "oracle:price:BTC-USD");
+        assert_eq!(*ttl, Duration::from_secs(180));
+        assert_eq!(value["pair"], "BTC/USD");
+        assert_eq!(value["aggregated_price"], 70_000.5);
+        assert_eq!(value["contributing_nodes"], 1);
+
+        let publishes = publishes.lock().unwrap();
+        assert_eq!(
+            *publishes,
+            vec![("oracle:updates".to_string(), value.clone())]
+        );
+        assert_eq!(
+            redis_sink.stats(),
+            SinkStats {
+                sent: 1,
+                retries: 0,
+                failed: 0,
+                dropped: 0,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_redis_sink_counts_failures_and_recovers() {
+        let redis = MockRedis {
+            failures: 3,
+            ..MockRedis::default()
+        };
+        let sets = redis.sets.clone();
+        let service = AggregatorServiceImpl::new();
+        let config = SinkConfig {
+            max_attempts: 2,
+            ..quick_config()
+        };
+        let (sink, task) = spawn(
+            RedisSink::new(redis, RedisConfig::default()),
+            config,
+            service.subscribe(),
+        );
+        let service = service.with_sink(sink.clone());
+
+        service
+            .submit_price(price_request(70_000.0, "node-a"))
+            .await
+            .unwrap();
+        // 첫 집계는 두 번 모두 실패해 포기하고, 다음 집계는 재시도 한 번 만에 성공
+        service.publish_snapshot().await;
+        service.publish_snapshot().await;
+        service.shutdown();
+        task.await.unwrap();
+
+        assert_eq!(
+            sink.stats(),
+            SinkStats {
+                sent: 1,
+                retries: 2,
+                failed: 1,
+                dropped: 0,
+            }
+        );
+        assert_eq!(sets.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_redis_key_replaces_pair_separator() {
+        let config = RedisConfig::default();
+        assert_eq!(config.key("BTC/USD"), "oracle:price:BTC-USD");
+        assert_eq!(config.key("ETH/USDT"), "oracle:price:ETH-USDT");
+    }
+
+    #[tokio::test]
+    async fn test_file_sink_appends_aggregates_after_torn_line() {
+        use oracle_vm_common::jsonl::{read_jsonl, rotated_paths, FsyncPolicy};
+
+        let dir = std::env::temp_dir().join(format!("aggregator-jsonl-{}", uuid::Uuid::new_v4()));
+        let path = dir.join("aggregates.jsonl");
+        std::fs::create_dir_all(&dir).unwrap();
+        // 이전 실행이 줄 중간에 죽은 상태
+        std::fs::write(&path, b"{\"pair\":\"BTC/USD\"}\n{\"pair\":\"BT").unwrap();
+
+        let config = JsonlConfig::new(&path)
+            .with_fsync(FsyncPolicy::EveryN(10))
+            .with_max_bytes(400);
+        let service = AggregatorServiceImpl::new();
+        let (sink, task) = spawn(
+            FileSink::open(config).unwrap(),
+            quick_config(),
+            service.subscribe(),
+        );
+        let service = service.with_sink(sink.clone());
+
+        for i in 0..3 {
+            service
+                .submit_price(price_request(70_000.0 + i as f64, &format!("node-{}", i)))
+                .await
+                .unwrap();
+            service.publish_snapshot().await;
+        }
+        service.shutdown();
+        task.await.unwrap();
+        assert_eq!(sink.stats().sent, 3);
+
+        // 제출은 쓰지 않고 집계만 남으며, 잘린 줄은 사라지고 로테이션된 파일까지 순서대로 이어짐
+        let mut lines: Vec<serde_json::Value> = Vec::new();
+        for file in rotated_paths(&path).unwrap().iter().chain([&path]) {
+            lines.extend(read_jsonl::<serde_json::Value>(file).unwrap());
+        }
+        assert!(!rotated_paths(&path).unwrap().is_empty());
+        assert_eq!(lines.len(), 4, "{:?}", lines);
+        assert_eq!(lines[0], serde_json::json!({ "pair": "BTC/USD" }));
+        let nodes: Vec<_> = lines[1..]
+            .iter()
+            .map(|line| line["contributing_nodes"].as_u64().unwrap())
+            .collect();
+        assert_eq!(nodes, [1, 2, 3]);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
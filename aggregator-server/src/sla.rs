@@ -0,0 +1,234 @@
+//! 집계 가격 가용성 SLA (정족수를 채운 신선한 중간값이 있었던 시간의 비율)
+//!
+//! 집계를 게시할 때마다 그 시점의 상태(신선한지)를 기록하고, 다음 기록까지 흐른 시간을 직전
+//! 상태에 더한다. 시작 이후 누적 비율과 최근 `window_secs` 동안의 비율을 함께 유지하며, 최근
+//! 비율이 목표 아래로 내려가면 경고를 남긴다. 시작 직후 잠깐의 공백으로 경고하지 않도록
+//! 윈도우가 한 번 다 찬 뒤부터 목표와 비교한다.
+
+use std::collections::VecDeque;
+use tracing::{info, warn};
+
+/// 최근 가용성을 계산하는 기본 윈도우 (초)
+pub const DEFAULT_SLA_WINDOW_SECS: u64 = 3_600;
+
+/// 가용성 집계 결과
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SlaReport {
+    /// 시작 이후 신선한 집계가 있었던 시간 (초)
+    pub fresh_secs: u64,
+    /// 시작 이후 측정한 시간 (초)
+    pub total_secs: u64,
+    /// 최근 윈도우 안에서 신선한 집계가 있었던 시간 (초)
+    pub window_fresh_secs: u64,
+    /// 최근 윈도우 안에서 측정한 시간 (초, 최대 window_secs)
+    pub window_total_secs: u64,
+    pub window_secs: u64,
+    pub target: Option<f64>,
+    /// 윈도우가 다 찼고 최근 가용성이 목표보다 낮은지
+    pub below_target: bool,
+    /// 마지막 게시 시점에 신선한 집계가 있었는지
+    pub fresh: bool,
+}
+
+impl SlaReport {
+    /// 시작 이후 가용성 (측정한 시간이 없으면 None)
+    pub fn availability(&self) -> Option<f64> {
+        ratio(self.fresh_secs, self.total_secs)
+    }
+
+    /// 최근 윈도우의 가용성 (측정한 시간이 없으면 None)
+    pub fn rolling_availability(&self) -> Option<f64> {
+        ratio(self.window_fresh_secs, self.window_total_secs)
+    }
+}
+
+fn ratio(fresh: u64, total: u64) -> Option<f64> {
+    (total > 0).then(|| fresh as f64 / total as f64)
+}
+
+// 같은 상태가 이어진 구간 [start, end)
+#[derive(Debug, Clone, Copy)]
+struct Segment {
+    start: u64,
+    end: u64,
+    fresh: bool,
+}
+
+/// 신선한 시간 / 전체 시간 추적
+#[derive(Debug)]
+pub struct SlaTracker {
+    window_secs: u64,
+    target: Option<f64>,
+    fresh_secs: u64,
+    total_secs: u64,
+    // 최근 윈도우와 겹치는 구간 (오래된 순)
+    segments: VecDeque<Segment>,
+    // 마지막 기록 시각과 상태
+    last: Option<(u64, bool)>,
+    below_target: bool,
+}
+
+impl SlaTracker {
+    /// `window_secs` 동안의 가용성이 `target`(0~1)보다 낮으면 경고 (None이면 경고하지 않음)
+    pub fn new(window_secs: u64, target: Option<f64>) -> Self {
+        Self {
+            window_secs: window_secs.max(1),
+            target,
+            fresh_secs: 0,
+            total_secs: 0,
+            segments: VecDeque::new(),
+            last: None,
+            below_target: false,
+        }
+    }
+
+    /// `now` 시점의 상태 기록 (직전 기록 이후 흐른 시간은 직전 상태로 계산)
+    pub fn record(&mut self, now: u64, fresh: bool) {
+        if let Some((since, was_fresh)) = self.last {
+            if now <= since {
+                // 같은 시각에 다시 게시되면 최신 상태만 반영
+                self.last = Some((since, fresh));
+                return;
+            }
+            self.total_secs += now - since;
+            if was_fresh {
+                self.fresh_secs += now - since;
+            }
+            match self.segments.back_mut() {
+                Some(segment) if segment.fresh == was_fresh && segment.end == since => {
+                    segment.end = now
+                }
+                _ => self.segments.push_back(Segment {
+                    start: since,
+                    end: now,
+                    fresh: was_fresh,
+                }),
+            }
+        }
+        self.last = Some((now, fresh));
+
+        let window_start = now.saturating_sub(self.window_secs);
+        while self
+            .segments
+            .front()
+            .is_some_and(|segment| segment.end <= window_start)
+        {
+            self.segments.pop_front();
+        }
+
+        self.check_target(now);
+    }
+
+    /// `now` 시점의 가용성 (마지막 기록 이후 시간은 마지막 상태로 계산)
+    pub fn report(&self, now: u64) -> SlaReport {
+        let (window_fresh_secs, window_total_secs) = self.window_totals(now);
+        let pending = self.last.map_or(0, |(since, _)| now.saturating_sub(since));
+        let fresh = self.last.is_some_and(|(_, fresh)| fresh);
+
+        SlaReport {
+            fresh_secs: self.fresh_secs + if fresh { pending } else { 0 },
+            total_secs: self.total_secs + pending,
+            window_fresh_secs,
+            window_total_secs,
+            window_secs: self.window_secs,
+            target: self.target,
+            below_target: self.below_target,
+            fresh,
+        }
+    }
+
+    // 최근 윈도우와 겹치는 (신선한 시간, 전체 시간)
+    fn window_totals(&self, now: u64) -> (u64, u64) {
+        let window_start = now.saturating_sub(self.window_secs);
+        let pending = self.last.map(|(since, fresh)| Segment {
+            start: since,
+            end: now.max(since),
+            fresh,
+        });
+
+        self.segments
+            .iter()
+            .chain(pending.iter())
+            .fold((0, 0), |(fresh, total), segment| {
+                let secs = segment
+                    .end
+                    .min(now)
+                    .saturating_sub(segment.start.max(window_start));
+                (fresh + if segment.fresh { secs } else { 0 }, total + secs)
+            })
+    }
+
+    // 목표 이탈/회복 시 한 번씩 로그
+    fn check_target(&mut self, now: u64) {
+        let Some(target) = self.target else {
+            return;
+        };
+        let (fresh, total) = self.window_totals(now);
+        let below = total >= self.window_secs && (fresh as f64) < target * total as f64;
+        if below == self.below_target {
+            return;
+        }
+        self.below_target = below;
+
+        let availability = ratio(fresh, total).unwrap_or(0.0) * 100.0;
+        if below {
+            warn!(
+                "🚨 Price availability {:.2}% over the last {}s is below the {:.2}% target",
+                availability,
+                self.window_secs,
+                target * 100.0
+            );
+        } else {
+            info!(
+                "✅ Price availability back to {:.2}% over the last {}s (target {:.2}%)",
+                availability,
+                self.window_secs,
+                target * 100.0
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts_time_in_the_previous_state() {
+        let mut sla = SlaTracker::new(100, Some(0.9));
+        assert_eq!(sla.report(0).availability(), None);
+
+        sla.record(1_000, true);
+        sla.record(1_060, false);
+        sla.record(1_080, true);
+        let report = sla.report(1_100);
+        assert_eq!((report.fresh_secs, report.total_secs), (80, 100));
+        assert_eq!(report.availability(), Some(0.8));
+        assert!(report.fresh);
+        // 기록 사이에는 아직 목표와 비교하지 않음 (다음 기록에서 판정)
+        assert!(!report.below_target);
+
+        sla.record(1_100, true);
+        assert!(sla.report(1_100).below_target);
+
+        // 윈도우 밖으로 밀려난 공백은 최근 가용성에서 빠짐
+        sla.record(1_200, true);
+        let report = sla.report(1_200);
+        assert_eq!(report.rolling_availability(), Some(1.0));
+        assert_eq!(report.availability(), Some(0.9));
+        assert!(!report.below_target);
+    }
+
+    #[test]
+    fn test_no_alert_until_window_filled() {
+        let mut sla = SlaTracker::new(100, Some(0.99));
+        sla.record(0, false);
+        sla.record(50, false);
+        let report = sla.report(50);
+        assert_eq!(report.rolling_availability(), Some(0.0));
+        assert!(!report.below_target);
+
+        sla.record(100, true);
+        assert!(sla.report(100).below_target);
+    }
+}
@@ -0,0 +1,101 @@
+use oracle_vm_common::Price;
+use std::sync::Arc;
+
+use crate::{PriceEntry, MIN_QUORUM_NODES, SOURCE_STALE_SECS};
+
+/// 집계 결과의 불변 스냅샷
+///
+/// 집계 태스크가 집계할 때마다 새로 만들어 교체하며, 조회 경로는 락 없이 이 값만 읽는다.
+#[derive(Debug, Default)]
+pub struct AggregateSnapshot {
+    /// 집계된 가격 (윈도우 내 데이터가 없으면 None, stale이면 마지막 유효 집계 가격)
+    pub aggregated_price: Option<Price>,
+    /// 윈도우가 비어 이전 집계 가격을 이어받았는지
+    pub stale: bool,
+    /// `aggregated_price`를 계산한 시각 (stale이 아니면 `timestamp`와 같음)
+    pub aggregated_at: u64,
+    /// 윈도우 내 가격 분포의 25 / 75 백분위수 (선형 보간)
+    pub p25: Option<f64>,
+    pub p75: Option<f64>,
+    /// 집계에 참여한 서로 다른 노드 수
+    pub contributing_nodes: usize,
+    /// 활성 노드 수
+    pub active_nodes: usize,
+    /// 활성 노드 ID (오래된 순, 스트림 구독자마다 상태 락을 잡지 않도록 게시할 때 한 번 계산)
+    pub active_node_ids: Vec<Arc<str>>,
+    /// 저장된 가격 데이터 수
+    pub stored_prices: usize,
+    /// 최근 가격 데이터 (최신순)
+    pub recent_prices: Vec<PriceEntry>,
+    /// 소스별 가장 최근 가격의 타임스탬프 (소스 이름 순)
+    pub source_last_seen: Vec<(Arc<str>, u64)>,
+    /// 집계 시각
+    pub timestamp: u64,
+}
+
+impl AggregateSnapshot {
+    /// 정족수를 채운 집계 가격이 있는지 (가용성 SLA의 신선한 상태)
+    pub fn meets_quorum(&self) -> bool {
+        self.aggregated_price.is_some()
+            && !self.stale
+            && self.contributing_nodes >= MIN_QUORUM_NODES
+    }
+
+    /// 헬스체크가 비정상으로 보고하는 원인 (정상이면 None)
+    ///
+    /// 최근 `SOURCE_STALE_SECS` 안에 보고한 소스가 없으면 "no sources", 집계 가격이 없거나
+    /// 이어받은 가격이면 "stale median", 참여 노드가 정족수보다 적으면 "below quorum" 순으로 확인한다.
+    pub fn degradation(&self, now: u64) -> Option<&'static str> {
+        let fresh_source = self
+            .source_last_seen
+            .iter()
+            .any(|(_, last_timestamp)| now.saturating_sub(*last_timestamp) < SOURCE_STALE_SECS);
+        if !fresh_source {
+            Some("no sources")
+        } else if self.aggregated_price.is_none() || self.stale {
+            Some("stale median")
+        } else if self.contributing_nodes < MIN_QUORUM_NODES {
+            Some("below quorum")
+        } else {
+            None
+        }
+    }
+
+    /// 집계 가격이 없으면 `previous`의 집계 가격을 stale로 이어받음 (이어받았으면 true)
+    ///
+    /// 이어받은 가격은 처음 계산된 시각부터 `grace_secs`가 지날 때까지만 게시하므로, 모든 노드의
+    /// 짧은 네트워크 단절에도 소비자는 가격을 받고 단절이 길어지면 집계 가격 없음으로 돌아간다.
+    pub fn fall_back_to(&mut self, previous: &AggregateSnapshot, grace_secs: u64) -> bool {
+        let Some(price) = previous.aggregated_price else {
+            return false;
+        };
+        if self.aggregated_price.is_some()
+            || self.timestamp.saturating_sub(previous.aggregated_at) > grace_secs
+        {
+            return false;
+        }
+        self.aggregated_price = Some(price);
+        self.aggregated_at = previous.aggregated_at;
+        self.stale = true;
+        true
+    }
+}
+
+/// 집계 결과 구독 채널로 전달되는 메시지
+#[derive(Debug, Clone)]
+pub enum AggregateUpdate {
+    /// 새로 게시된 집계 결과
+    Published(Arc<AggregateSnapshot>),
+    /// 계획된 서버 종료 (이후 더 이상 게시되지 않음)
+    Shutdown,
+}
+
+impl AggregateUpdate {
+    /// 게시된 집계 결과 (종료 알림이면 None)
+    pub fn snapshot(&self) -> Option<&Arc<AggregateSnapshot>> {
+        match self {
+            AggregateUpdate::Published(snapshot) => Some(snapshot),
+            AggregateUpdate::Shutdown => None,
+        }
+    }
+}
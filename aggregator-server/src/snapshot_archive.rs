@@ -0,0 +1,245 @@
+//! 집계 스냅샷의 디스크 보관 (gzip 압축, 최근 N개만 유지)
+//!
+//! 주기마다 게시된 스냅샷을 `snapshot-<번호>.json.gz`로 기록한다. 임시 파일에 쓴 뒤 교체하므로
+//! 기록 도중 장애가 나도 온전한 파일만 남고, 보관 개수를 넘으면 가장 오래된 파일부터 지운다.
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use oracle_vm_common::Price;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::snapshot::AggregateSnapshot;
+use crate::PriceEntry;
+
+const ARCHIVE_PREFIX: &str = "snapshot-";
+const ARCHIVE_SUFFIX: &str = ".json.gz";
+
+/// 스냅샷 보관 설정
+#[derive(Debug, Clone)]
+pub struct SnapshotArchiveConfig {
+    /// 스냅샷 파일 디렉터리
+    pub dir: PathBuf,
+    /// 유지할 최근 스냅샷 수 (최소 1)
+    pub keep: usize,
+    /// 기록 주기 (초)
+    pub interval_secs: u64,
+}
+
+impl Default for SnapshotArchiveConfig {
+    fn default() -> Self {
+        Self {
+            dir: PathBuf::from("snapshots"),
+            keep: 1_440,
+            interval_secs: 60,
+        }
+    }
+}
+
+/// 보관된 가격 데이터
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArchivedPrice {
+    pub price: Price,
+    pub timestamp: u64,
+    pub source: String,
+    pub node_id: String,
+    pub pair: String,
+}
+
+impl From<&PriceEntry> for ArchivedPrice {
+    fn from(entry: &PriceEntry) -> Self {
+        Self {
+            price: entry.price,
+            timestamp: entry.timestamp,
+            source: entry.source.to_string(),
+            node_id: entry.node_id.to_string(),
+            pair: entry.pair.to_string(),
+        }
+    }
+}
+
+/// 보관된 스냅샷 (AggregateSnapshot과 같은 필드)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArchivedSnapshot {
+    pub aggregated_price: Option<Price>,
+    /// 이전 실행의 보관 파일에는 없음
+    #[serde(default)]
+    pub stale: bool,
+    pub p25: Option<f64>,
+    pub p75: Option<f64>,
+    pub contributing_nodes: usize,
+    pub active_nodes: usize,
+    pub stored_prices: usize,
+    pub recent_prices: Vec<ArchivedPrice>,
+    pub source_last_seen: Vec<(String, u64)>,
+    pub timestamp: u64,
+}
+
+impl From<&AggregateSnapshot> for ArchivedSnapshot {
+    fn from(snapshot: &AggregateSnapshot) -> Self {
+        Self {
+            aggregated_price: snapshot.aggregated_price,
+            stale: snapshot.stale,
+            p25: snapshot.p25,
+            p75: snapshot.p75,
+            contributing_nodes: snapshot.contributing_nodes,
+            active_nodes: snapshot.active_nodes,
+            stored_prices: snapshot.stored_prices,
+            recent_prices: snapshot.recent_prices.iter().map(Into::into).collect(),
+            source_last_seen: snapshot
+                .source_last_seen
+                .iter()
+                .map(|(source, last_seen)| (source.to_string(), *last_seen))
+                .collect(),
+            timestamp: snapshot.timestamp,
+        }
+    }
+}
+
+/// 스냅샷 파일 기록기
+#[derive(Debug)]
+pub struct SnapshotArchive {
+    dir: PathBuf,
+    keep: usize,
+    next_index: u64,
+}
+
+impl SnapshotArchive {
+    /// 디렉터리를 열어 이전 실행의 마지막 번호 다음부터 기록
+    pub fn open(dir: impl Into<PathBuf>, keep: usize) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        let next_index = archive_paths(&dir)?
+            .last()
+            .and_then(|path| archive_index(path))
+            .map_or(0, |index| index + 1);
+
+        Ok(Self {
+            dir,
+            keep: keep.max(1),
+            next_index,
+        })
+    }
+
+    /// 스냅샷을 압축해 기록하고 보관 개수를 넘는 오래된 파일 삭제
+    pub fn write(&mut self, snapshot: &AggregateSnapshot) -> io::Result<PathBuf> {
+        let path = self.dir.join(format!(
+            "{}{:010}{}",
+            ARCHIVE_PREFIX, self.next_index, ARCHIVE_SUFFIX
+        ));
+        let tmp = path.with_extension("tmp");
+
+        let mut encoder =
+            GzEncoder::new(BufWriter::new(File::create(&tmp)?), Compression::default());
+        serde_json::to_writer(&mut encoder, &ArchivedSnapshot::from(snapshot))?;
+        let mut writer = encoder.finish()?;
+        writer.flush()?;
+        writer
+            .into_inner()
+            .map_err(|e| e.into_error())?
+            .sync_all()?;
+        fs::rename(&tmp, &path)?;
+        self.next_index += 1;
+
+        self.rotate()?;
+        Ok(path)
+    }
+
+    // 최근 `keep`개만 남기고 삭제 (삭제한 파일 수)
+    fn rotate(&self) -> io::Result<usize> {
+        let paths = archive_paths(&self.dir)?;
+        let excess = paths.len().saturating_sub(self.keep);
+        for path in &paths[..excess] {
+            fs::remove_file(path)?;
+        }
+        Ok(excess)
+    }
+}
+
+fn archive_index(path: &Path) -> Option<u64> {
+    path.file_name()?
+        .to_str()?
+        .strip_prefix(ARCHIVE_PREFIX)?
+        .strip_suffix(ARCHIVE_SUFFIX)?
+        .parse()
+        .ok()
+}
+
+/// 디렉터리의 스냅샷 파일 목록 (오래된 순)
+pub fn archive_paths(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut archives: Vec<(u64, PathBuf)> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter_map(|path| archive_index(&path).map(|index| (index, path)))
+        .collect();
+    archives.sort_unstable_by_key(|(index, _)| *index);
+    Ok(archives.into_iter().map(|(_, path)| path).collect())
+}
+
+/// 스냅샷 파일 읽기
+pub fn read_snapshot(path: &Path) -> io::Result<ArchivedSnapshot> {
+    let decoder = GzDecoder::new(BufReader::new(File::open(path)?));
+    Ok(serde_json::from_reader(decoder)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn temp_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("aggregator-snapshots-{}", uuid::Uuid::new_v4()))
+    }
+
+    fn snapshot(timestamp: u64) -> AggregateSnapshot {
+        AggregateSnapshot {
+            aggregated_price: Some(Price::from_cents(7_000_000 + timestamp)),
+            contributing_nodes: 3,
+            recent_prices: vec![PriceEntry {
+                price: Price::from_cents(7_000_000),
+                timestamp,
+                source: Arc::from("binance"),
+                node_id: Arc::from("node-a"),
+                pair: Arc::from("BTC/USD"),
+                volume: None,
+            }],
+            source_last_seen: vec![(Arc::from("binance"), timestamp)],
+            timestamp,
+            ..AggregateSnapshot::default()
+        }
+    }
+
+    #[test]
+    fn test_keeps_only_most_recent_compressed_snapshots() {
+        let dir = temp_dir();
+        let mut archive = SnapshotArchive::open(&dir, 3).unwrap();
+        for timestamp in 0..5 {
+            archive.write(&snapshot(timestamp)).unwrap();
+        }
+
+        let paths = archive_paths(&dir).unwrap();
+        assert_eq!(paths.len(), 3);
+        let timestamps: Vec<u64> = paths
+            .iter()
+            .map(|path| {
+                // gzip 매직 넘버로 시작
+                assert_eq!(&fs::read(path).unwrap()[..2], &[0x1f, 0x8b]);
+                read_snapshot(path).unwrap().timestamp
+            })
+            .collect();
+        assert_eq!(timestamps, [2, 3, 4]);
+
+        let newest = read_snapshot(paths.last().unwrap()).unwrap();
+        assert_eq!(newest, ArchivedSnapshot::from(&snapshot(4)));
+
+        // 다시 열면 번호를 이어서 사용
+        let mut reopened = SnapshotArchive::open(&dir, 3).unwrap();
+        let path = reopened.write(&snapshot(5)).unwrap();
+        assert_eq!(archive_index(&path), Some(5));
+        assert_eq!(archive_paths(&dir).unwrap().len(), 3);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,124 @@
+//! 거래소(source)별 가중치 (가중 중간값에서 사용)
+
+use std::collections::HashMap;
+
+/// 목록에 없는 거래소의 가중치
+pub const DEFAULT_SOURCE_WEIGHT: f64 = 1.0;
+
+/// 가중치로 사용할 수 있는 값인지 (유한하고 0 이상, 0이면 집계에서 제외)
+pub fn is_valid_weight(weight: f64) -> bool {
+    weight.is_finite() && weight >= 0.0
+}
+
+/// 거래소별 가중치 (기본값과 다른 거래소만 보관, 이름은 소문자로 비교)
+#[derive(Debug, Clone, Default)]
+pub struct SourceWeights {
+    weights: HashMap<String, f64>,
+}
+
+impl SourceWeights {
+    /// 초기 가중치 (유효하지 않은 값은 무시)
+    pub fn new(weights: impl IntoIterator<Item = (String, f64)>) -> Self {
+        let mut source_weights = Self::default();
+        for (source, weight) in weights {
+            if is_valid_weight(weight) {
+                source_weights.set(&source, weight);
+            }
+        }
+        source_weights
+    }
+
+    /// 거래소 가중치
+    ///
+    /// 노드가 여러 거래소의 중간값을 `binance+coinbase`처럼 제출한 경우 각 거래소 가중치의 평균이다.
+    pub fn get(&self, source: &str) -> f64 {
+        if self.weights.is_empty() {
+            return DEFAULT_SOURCE_WEIGHT;
+        }
+        let (sum, count) = source
+            .split('+')
+            .map(|part| self.single(part))
+            .fold((0.0, 0usize), |(sum, count), weight| {
+                (sum + weight, count + 1)
+            });
+        sum / count as f64
+    }
+
+    fn single(&self, source: &str) -> f64 {
+        self.weights
+            .get(&source.trim().to_lowercase())
+            .copied()
+            .unwrap_or(DEFAULT_SOURCE_WEIGHT)
+    }
+
+    /// 가중치 설정 (기본값이면 목록에서 제거)
+    ///
+    /// 유효하지 않은 값은 호출 전에 거부해야 한다.
+    pub fn set(&mut self, source: &str, weight: f64) {
+        debug_assert!(is_valid_weight(weight));
+        let source = source.trim().to_lowercase();
+        if weight == DEFAULT_SOURCE_WEIGHT {
+            self.weights.remove(&source);
+        } else {
+            self.weights.insert(source, weight);
+        }
+    }
+
+    /// 기본값과 다른 가중치
+    pub fn as_map(&self) -> &HashMap<String, f64> {
+        &self.weights
+    }
+
+    pub fn len(&self) -> usize {
+        self.weights.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.weights.is_empty()
+    }
+}
+
+/// `coinbase=2,thinex=0.5` 형식의 가중치 목록 파싱
+pub fn parse(spec: &str) -> Result<HashMap<String, f64>, String> {
+    spec.split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(|item| {
+            let (source, weight) = item
+                .split_once('=')
+                .ok_or_else(|| format!("expected source=weight, got {:?}", item))?;
+            let weight: f64 = weight
+                .trim()
+                .parse()
+                .map_err(|_| format!("invalid weight in {:?}", item))?;
+            if !is_valid_weight(weight) {
+                return Err(format!("weight must be finite and >= 0 in {:?}", item));
+            }
+            Ok((source.trim().to_lowercase(), weight))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weights_default_and_combine() {
+        let weights = SourceWeights::new(parse("Coinbase=3, thinex=0").unwrap());
+
+        assert_eq!(weights.get("coinbase"), 3.0);
+        assert_eq!(weights.get("binance"), DEFAULT_SOURCE_WEIGHT);
+        assert_eq!(weights.get("thinex"), 0.0);
+        // 여러 거래소의 로컬 중간값은 평균 가중치
+        assert_eq!(weights.get("binance+coinbase"), 2.0);
+
+        let mut weights = weights;
+        weights.set("COINBASE", DEFAULT_SOURCE_WEIGHT);
+        assert_eq!(weights.len(), 1);
+
+        assert!(parse("coinbase").is_err());
+        assert!(parse("coinbase=-1").is_err());
+        assert!(parse("coinbase=NaN").is_err());
+    }
+}
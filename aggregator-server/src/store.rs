@@ -0,0 +1,605 @@
+use std::collections::{HashMap, VecDeque};
+use std::mem::size_of;
+use std::sync::Arc;
+
+use crate::aggregation::{self, RunningStats, RunningStatsSnapshot};
+use crate::PriceEntry;
+
+/// 제거 사유별 누적 제거 수
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EvictionCounts {
+    /// 최대 보관 시간이 지나 제거
+    pub age: u64,
+    /// 전체 최대 크기를 넘어 제거
+    pub count: u64,
+    /// 노드별 할당량을 넘어 제거
+    pub quota: u64,
+}
+
+/// 최근 가격 데이터 버퍼 (전체 최대 크기 + 노드별 할당량)
+///
+/// 한 노드가 할당량을 넘기면 다른 노드의 데이터가 아니라 그 노드의 가장 오래된 데이터를 제거한다.
+/// 최대 보관 시간이 설정되면 `prune`으로 그보다 오래된 데이터도 제거하므로,
+/// 보관량은 전체 한도가 아니라 제출 빈도 × 보관 시간으로 제한된다.
+///
+/// 데이터마다 추가 순번을 붙여 두고 노드별로 순번과 (timestamp, source) 색인을 유지한다. 전체 한도
+/// 제거는 앞에서 꺼내고, 할당량 제거는 중간 칸을 비워 두기만 하므로 어느 쪽도 버퍼를 옮기지 않는다.
+/// 빈 칸은 앞으로 밀려나면 버리고, 칸 수가 최대 크기의 두 배에 이르면 한 번에 정리한다.
+#[derive(Debug)]
+pub struct PriceStore {
+    /// 오래된 순서의 칸 (추가 순번 증가 순서라 이진 탐색으로 위치를 찾음)
+    slots: VecDeque<Slot>,
+    /// 비어 있지 않은 칸 수
+    len: usize,
+    next_seq: u64,
+    /// node_id -> 보관 중인 데이터의 색인
+    per_node: HashMap<Arc<str>, NodeEntries>,
+    /// pair -> 보관 중인 데이터의 누적 통계
+    stats: HashMap<Arc<str>, RunningStats>,
+    max_entries: usize,
+    per_node_quota: usize,
+    /// 최대 보관 시간 (초, None이면 시간으로 제거하지 않음)
+    max_age: Option<u64>,
+    evictions: EvictionCounts,
+}
+
+/// 버퍼의 한 칸 (할당량으로 제거되면 비어 있음)
+#[derive(Debug)]
+struct Slot {
+    seq: u64,
+    /// 노드가 보낸 timestamp (중복 확인 기준, 보정되지 않았으면 `entry.timestamp`와 같음)
+    submitted_at: u64,
+    entry: Option<PriceEntry>,
+}
+
+/// 한 노드가 보관 중인 데이터의 색인
+#[derive(Debug, Default)]
+struct NodeEntries {
+    /// 추가 순번 (오래된 순서)
+    seqs: VecDeque<u64>,
+    /// 노드가 보낸 timestamp -> (source, 추가 순번)
+    submissions: HashMap<u64, Vec<(Arc<str>, u64)>>,
+}
+
+impl NodeEntries {
+    fn insert(&mut self, seq: u64, submitted_at: u64, entry: &PriceEntry) {
+        self.seqs.push_back(seq);
+        self.submissions
+            .entry(submitted_at)
+            .or_default()
+            .push((entry.source.clone(), seq));
+    }
+
+    fn remove(&mut self, seq: u64, submitted_at: u64) {
+        // 제거되는 것은 대개 노드의 가장 오래된 데이터
+        if self.seqs.front() == Some(&seq) {
+            self.seqs.pop_front();
+        } else if let Ok(index) = self.seqs.binary_search(&seq) {
+            self.seqs.remove(index);
+        }
+        if let Some(sources) = self.submissions.get_mut(&submitted_at) {
+            sources.retain(|(_, s)| *s != seq);
+            if sources.is_empty() {
+                self.submissions.remove(&submitted_at);
+            }
+        }
+    }
+}
+
+impl PriceStore {
+    pub fn new(max_entries: usize, per_node_quota: usize) -> Self {
+        let max_entries = max_entries.max(1);
+        Self {
+            slots: VecDeque::with_capacity(max_entries * 2),
+            len: 0,
+            next_seq: 0,
+            per_node: HashMap::new(),
+            stats: HashMap::new(),
+            max_entries,
+            per_node_quota: per_node_quota.max(1),
+            max_age: None,
+            evictions: EvictionCounts::default(),
+        }
+    }
+
+    /// 최대 보관 시간 설정 (초)
+    pub fn with_max_age(mut self, max_age: u64) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    pub fn max_age(&self) -> Option<u64> {
+        self.max_age
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn max_entries(&self) -> usize {
+        self.max_entries
+    }
+
+    pub fn per_node_quota(&self) -> usize {
+        self.per_node_quota
+    }
+
+    /// 제거 사유별 누적 제거 수
+    pub fn evictions(&self) -> EvictionCounts {
+        self.evictions
+    }
+
+    /// 보관 중인 가장 오래된 데이터의 타임스탬프
+    pub fn oldest_timestamp(&self) -> Option<u64> {
+        self.entries().map(|e| e.timestamp).min()
+    }
+
+    /// 버퍼와 집계용 맵이 차지하는 메모리 추정치 (바이트)
+    ///
+    /// 할당된 용량 기준이며, 노드/소스/pair가 공유하는 인터닝된 문자열은 제외한다.
+    pub fn estimated_bytes(&self) -> usize {
+        let index_bytes: usize = self
+            .per_node
+            .values()
+            .map(|node| {
+                node.seqs.capacity() * size_of::<u64>()
+                    + node.submissions.capacity() * size_of::<(u64, Vec<(Arc<str>, u64)>)>()
+                    + node
+                        .submissions
+                        .values()
+                        .map(|sources| sources.capacity() * size_of::<(Arc<str>, u64)>())
+                        .sum::<usize>()
+            })
+            .sum();
+        self.slots.capacity() * size_of::<Slot>()
+            + self.per_node.capacity() * size_of::<(Arc<str>, NodeEntries)>()
+            + index_bytes
+            + self.stats.capacity() * size_of::<(Arc<str>, RunningStats)>()
+    }
+
+    /// 오래된 순서의 전체 가격 데이터
+    pub fn entries(&self) -> impl DoubleEndedIterator<Item = &PriceEntry> + Clone + '_ {
+        self.slots.iter().filter_map(|slot| slot.entry.as_ref())
+    }
+
+    /// 노드가 현재 보관 중인 데이터 수
+    pub fn node_usage(&self, node_id: &str) -> usize {
+        self.per_node.get(node_id).map_or(0, |node| node.seqs.len())
+    }
+
+    /// 노드별 보관 데이터 수 (node_id 순)
+    pub fn usage(&self) -> Vec<(Arc<str>, usize)> {
+        let mut usage: Vec<(Arc<str>, usize)> = self
+            .per_node
+            .iter()
+            .map(|(node_id, node)| (node_id.clone(), node.seqs.len()))
+            .collect();
+        usage.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        usage
+    }
+
+    /// 같은 (node_id, 노드가 보낸 timestamp, source)로 보관 중인 데이터 (여럿이면 가장 최근 데이터)
+    ///
+    /// 시계 오차로 timestamp가 보정된 데이터도 보정 전 값으로 찾으므로 같은 제출의 재전송을 알아본다.
+    pub fn find_submission(
+        &self,
+        node_id: &str,
+        timestamp: u64,
+        source: &str,
+    ) -> Option<&PriceEntry> {
+        let seq = self
+            .per_node
+            .get(node_id)?
+            .submissions
+            .get(&timestamp)?
+            .iter()
+            .rev()
+            .find(|(s, _)| &**s == source)
+            .map(|&(_, seq)| seq)?;
+        self.slots[self.position(seq)?].entry.as_ref()
+    }
+
+    /// pair의 보관 데이터 통계 (버퍼를 훑지 않음)
+    pub fn stats(&self, pair: &str) -> Option<RunningStatsSnapshot> {
+        self.stats.get(pair).map(RunningStats::snapshot)
+    }
+
+    /// 보관 중인 데이터가 모두 `pair`인지 (비어 있어도 true)
+    pub fn contains_only_pair(&self, pair: &str) -> bool {
+        self.stats.keys().all(|key| &**key == pair)
+    }
+
+    /// pair별 통계 (pair 순)
+    pub fn pair_stats(&self) -> Vec<(Arc<str>, RunningStatsSnapshot)> {
+        let mut stats: Vec<(Arc<str>, RunningStatsSnapshot)> = self
+            .stats
+            .iter()
+            .map(|(pair, stats)| (pair.clone(), stats.snapshot()))
+            .collect();
+        stats.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        stats
+    }
+
+    /// 가격 추가
+    ///
+    /// 노드가 할당량에 도달했으면 그 노드의 가장 오래된 데이터를 먼저 제거하고,
+    /// 그래도 가득 차 있으면 전체에서 가장 오래된 데이터를 제거한다.
+    /// 칸은 처음 할당한 크기(최대 크기의 두 배) 안에서만 쓰므로 버퍼는 다시 커지지 않는다.
+    pub fn push(&mut self, entry: PriceEntry) {
+        self.push_submitted(entry.timestamp, entry);
+    }
+
+    /// 노드가 보낸 timestamp(`submitted_at`)가 보관할 timestamp와 다른 가격 추가 (시계 오차 보정)
+    pub fn push_submitted(&mut self, submitted_at: u64, entry: PriceEntry) {
+        if self.node_usage(&entry.node_id) >= self.per_node_quota {
+            let oldest = self
+                .per_node
+                .get(&entry.node_id)
+                .and_then(|node| node.seqs.front().copied());
+            if let Some(index) = oldest.and_then(|seq| self.position(seq)) {
+                self.take_at(index);
+                self.evictions.quota += 1;
+            }
+        }
+
+        while self.len >= self.max_entries {
+            if self.take_at(0) {
+                self.evictions.count += 1;
+            }
+        }
+
+        if self.slots.len() >= self.max_entries * 2 {
+            self.slots.retain(|slot| slot.entry.is_some());
+        }
+
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.per_node
+            .entry(entry.node_id.clone())
+            .or_default()
+            .insert(seq, submitted_at, &entry);
+        self.stats
+            .entry(entry.pair.clone())
+            .or_default()
+            .insert(entry.price);
+        self.slots.push_back(Slot {
+            seq,
+            submitted_at,
+            entry: Some(entry),
+        });
+        self.len += 1;
+    }
+
+    /// `now` 기준 최대 보관 시간보다 오래된 데이터 제거 (제거된 수 반환)
+    ///
+    /// 타임스탬프가 `now`보다 미래인 데이터는 남겨 둔다.
+    pub fn prune(&mut self, now: u64) -> usize {
+        let Some(max_age) = self.max_age else {
+            return 0;
+        };
+
+        // 할당된 버퍼를 그대로 유지하도록 제자리에서 제거 (빈 칸도 함께 정리)
+        let mut removed = Vec::new();
+        self.slots.retain_mut(|slot| match &slot.entry {
+            Some(entry) if aggregation::is_stale(entry.timestamp, now, max_age) => {
+                removed.push((slot.seq, slot.submitted_at, slot.entry.take().unwrap()));
+                false
+            }
+            entry => entry.is_some(),
+        });
+        self.len -= removed.len();
+
+        // 통계의 극값은 남은 데이터 기준으로 다시 계산된다
+        for (seq, submitted_at, entry) in &removed {
+            self.forget(*seq, *submitted_at, entry);
+        }
+        self.evictions.age += removed.len() as u64;
+        removed.len()
+    }
+
+    // 추가 순번의 현재 칸 위치
+    fn position(&self, seq: u64) -> Option<usize> {
+        self.slots.binary_search_by_key(&seq, |slot| slot.seq).ok()
+    }
+
+    // 칸을 비우고 앞쪽 빈 칸을 버림 (비어 있던 칸이면 false)
+    fn take_at(&mut self, index: usize) -> bool {
+        let Some(slot) = self.slots.get_mut(index) else {
+            return false;
+        };
+        let (seq, submitted_at) = (slot.seq, slot.submitted_at);
+        let removed = slot.entry.take();
+        while self.slots.front().is_some_and(|slot| slot.entry.is_none()) {
+            self.slots.pop_front();
+        }
+        let Some(removed) = removed else {
+            return false;
+        };
+        self.len -= 1;
+        self.forget(seq, submitted_at, &removed);
+        true
+    }
+
+    // 제거된 데이터를 노드별 색인과 pair 통계에서 제외
+    fn forget(&mut self, seq: u64, submitted_at: u64, removed: &PriceEntry) {
+        if let Some(node) = self.per_node.get_mut(&removed.node_id) {
+            node.remove(seq, submitted_at);
+            if node.seqs.is_empty() {
+                self.per_node.remove(&removed.node_id);
+            }
+        }
+
+        let slots = &self.slots;
+        if let Some(stats) = self.stats.get_mut(&removed.pair) {
+            stats.remove(removed.price, || {
+                slots
+                    .iter()
+                    .filter_map(|slot| slot.entry.as_ref())
+                    .filter(|e| e.pair == removed.pair)
+                    .map(|e| e.price)
+            });
+            if stats.is_empty() {
+                self.stats.remove(&removed.pair);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DEFAULT_PAIR;
+    use oracle_vm_common::Price;
+
+    fn entry(node_id: &str, price: f64) -> PriceEntry {
+        entry_at(node_id, price, 1_700_000_000)
+    }
+
+    fn entry_at(node_id: &str, price: f64, timestamp: u64) -> PriceEntry {
+        PriceEntry {
+            price: Price::from_f64_dollars(price, Price::USD_DECIMALS).unwrap(),
+            timestamp,
+            source: Arc::from("binance"),
+            node_id: Arc::from(node_id),
+            pair: Arc::from(DEFAULT_PAIR),
+            volume: None,
+        }
+    }
+
+    #[test]
+    fn test_quota_evicts_own_oldest_entry() {
+        let mut store = PriceStore::new(10, 2);
+
+        store.push(entry("a", 1.0));
+        store.push(entry("b", 2.0));
+        store.push(entry("a", 3.0));
+        store.push(entry("a", 4.0)); // a의 가장 오래된 1.0 제거
+
+        let prices: Vec<f64> = store.entries().map(|e| e.price.to_f64_dollars()).collect();
+        assert_eq!(prices, vec![2.0, 3.0, 4.0]);
+        assert_eq!(store.node_usage("a"), 2);
+        assert_eq!(store.node_usage("b"), 1);
+    }
+
+    #[test]
+    fn test_global_cap_drops_oldest_and_updates_usage() {
+        let mut store = PriceStore::new(3, 3);
+
+        for (node_id, price) in [("a", 1.0), ("b", 2.0), ("c", 3.0), ("d", 4.0)] {
+            store.push(entry(node_id, price));
+        }
+
+        assert_eq!(store.len(), 3);
+        assert_eq!(store.node_usage("a"), 0);
+        assert_eq!(
+            store.usage(),
+            vec![
+                (Arc::from("b"), 1),
+                (Arc::from("c"), 1),
+                (Arc::from("d"), 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pair_stats_follow_evictions() {
+        let mut store = PriceStore::new(3, 3);
+        let mut eth = entry("e", 3_500.0);
+        eth.pair = Arc::from("ETH/USD");
+
+        store.push(entry("a", 1.0)); // 최솟값, 곧 전체 한도로 제거됨
+        store.push(eth);
+        store.push(entry("b", 5.0));
+        store.push(entry("c", 3.0));
+
+        let btc = store.stats(DEFAULT_PAIR).unwrap();
+        assert_eq!(btc.count, 2);
+        assert_eq!(btc.min, Some(Price::from_cents(300)));
+        assert_eq!(btc.max, Some(Price::from_cents(500)));
+        assert_eq!(btc.mean, Some(4.0));
+        assert_eq!(btc.last, Some(Price::from_cents(300)));
+        assert_eq!(store.stats("ETH/USD").unwrap().count, 1);
+
+        // ETH 데이터가 모두 제거되면 통계도 사라짐
+        store.push(entry("d", 4.0));
+        assert_eq!(store.stats("ETH/USD"), None);
+        assert_eq!(
+            store
+                .pair_stats()
+                .into_iter()
+                .map(|(pair, stats)| (pair, stats.count))
+                .collect::<Vec<_>>(),
+            vec![(Arc::from(DEFAULT_PAIR), 3)]
+        );
+    }
+
+    #[test]
+    fn test_prune_removes_entries_older_than_max_age() {
+        let now = 1_700_000_000;
+        let mut store = PriceStore::new(100, 100).with_max_age(60);
+
+        store.push(entry_at("a", 1.0, now - 2_400)); // 40분 전
+        store.push(entry_at("b", 9.0, now - 60)); // 경계: 만료
+        store.push(entry_at("a", 3.0, now - 59));
+        store.push(entry_at("c", 5.0, now + 5)); // 미래 시각은 유지
+
+        assert_eq!(store.prune(now), 2);
+        assert_eq!(
+            store
+                .entries()
+                .map(|e| e.price.to_f64_dollars())
+                .collect::<Vec<_>>(),
+            vec![3.0, 5.0]
+        );
+        assert_eq!(store.node_usage("a"), 1);
+        assert_eq!(store.node_usage("b"), 0);
+
+        let stats = store.stats(DEFAULT_PAIR).unwrap();
+        assert_eq!(stats.count, 2);
+        assert_eq!(stats.min, Some(Price::from_cents(300)));
+        assert_eq!(stats.max, Some(Price::from_cents(500)));
+
+        // 최대 보관 시간이 없으면 제거하지 않음
+        let mut unbounded = PriceStore::new(100, 100);
+        unbounded.push(entry_at("a", 1.0, now - 2_400));
+        assert_eq!(unbounded.prune(now), 0);
+        assert_eq!(unbounded.len(), 1);
+    }
+
+    #[test]
+    fn test_eviction_counts_split_by_reason() {
+        let now = 1_700_000_000;
+        let mut store = PriceStore::new(4, 2).with_max_age(60);
+        let capacity = store.estimated_bytes();
+        let slots = store.slots.capacity();
+
+        // 할당량: a의 세 번째 제출이 a의 가장 오래된 데이터를 밀어냄
+        store.push(entry_at("a", 1.0, now - 100));
+        store.push(entry_at("a", 2.0, now - 90));
+        store.push(entry_at("a", 3.0, now - 10));
+        assert_eq!(store.oldest_timestamp(), Some(now - 90));
+
+        // 전체 크기: b, c, d가 들어오며 가장 오래된 데이터 하나를 밀어냄
+        store.push(entry_at("b", 4.0, now - 5));
+        store.push(entry_at("c", 5.0, now - 5));
+        store.push(entry_at("d", 6.0, now - 5));
+        assert_eq!(
+            store.evictions(),
+            EvictionCounts {
+                age: 0,
+                count: 1,
+                quota: 1
+            }
+        );
+
+        // 보관 시간: 남은 데이터는 모두 60초 이내
+        assert_eq!(store.prune(now), 0);
+        assert_eq!(store.prune(now + 56), 4);
+        assert_eq!(
+            store.evictions(),
+            EvictionCounts {
+                age: 4,
+                count: 1,
+                quota: 1
+            }
+        );
+        assert_eq!(store.oldest_timestamp(), None);
+
+        // 미리 할당한 버퍼는 다시 커지지 않음
+        assert_eq!(store.slots.capacity(), slots);
+        assert!(store.estimated_bytes() >= capacity);
+    }
+
+    #[test]
+    fn test_find_submission_follows_evictions() {
+        let now = 1_700_000_000;
+        let mut store = PriceStore::new(3, 2);
+
+        store.push(entry_at("a", 1.0, now - 3));
+        store.push(entry_at("b", 2.0, now - 2));
+        store.push(entry_at("a", 3.0, now - 1));
+        assert_eq!(
+            store
+                .find_submission("b", now - 2, "binance")
+                .map(|e| e.price.to_f64_dollars()),
+            Some(2.0)
+        );
+        assert!(store.find_submission("b", now - 2, "kraken").is_none());
+
+        // a의 할당량으로 a의 1.0, 전체 한도로 b의 2.0이 빠짐
+        store.push(entry_at("a", 4.0, now));
+        store.push(entry_at("c", 5.0, now));
+        assert!(store.find_submission("a", now - 3, "binance").is_none());
+        assert!(store.find_submission("b", now - 2, "binance").is_none());
+        assert_eq!(
+            store
+                .find_submission("a", now - 1, "binance")
+                .map(|e| e.price.to_f64_dollars()),
+            Some(3.0)
+        );
+        assert_eq!(store.len(), 3);
+        assert_eq!(store.node_usage("b"), 0);
+    }
+
+    #[test]
+    fn test_find_submission_uses_the_submitted_timestamp() {
+        let now = 1_700_000_000;
+        let mut store = PriceStore::new(3, 2);
+
+        // 미래 timestamp가 서버 시각으로 보정되어 저장된 제출
+        store.push_submitted(now + 30, entry_at("a", 1.0, now));
+        assert_eq!(store.entries().next().unwrap().timestamp, now);
+        assert_eq!(
+            store
+                .find_submission("a", now + 30, "binance")
+                .map(|e| e.price.to_f64_dollars()),
+            Some(1.0)
+        );
+        assert!(store.find_submission("a", now, "binance").is_none());
+
+        // 제거되면 보정 전 timestamp의 색인도 함께 빠짐
+        store.push(entry_at("a", 2.0, now + 1));
+        store.push(entry_at("a", 3.0, now + 2));
+        assert!(store.find_submission("a", now + 30, "binance").is_none());
+        assert_eq!(store.node_usage("a"), 2);
+    }
+
+    #[test]
+    fn test_index_matches_buffer_through_mixed_evictions() {
+        let mut generator = crate::testing::PriceGenerator::new(7, 8);
+        let now = 1_700_000_000;
+        let mut store = PriceStore::new(50, 9).with_max_age(crate::PRICE_WINDOW_SECS / 2);
+        let slots = store.slots.capacity();
+
+        for round in 0..2_000 {
+            store.push(generator.next_entry());
+            if round % 100 == 99 {
+                store.prune(now);
+            }
+
+            assert!(store.len() <= 50);
+            for entry in store.entries() {
+                let found = store
+                    .find_submission(&entry.node_id, entry.timestamp, &entry.source)
+                    .unwrap();
+                assert_eq!(
+                    (found.timestamp, &found.node_id, &found.source),
+                    (entry.timestamp, &entry.node_id, &entry.source)
+                );
+            }
+            let mut counted: HashMap<Arc<str>, usize> = HashMap::new();
+            for entry in store.entries() {
+                *counted.entry(entry.node_id.clone()).or_default() += 1;
+            }
+            let mut counted: Vec<_> = counted.into_iter().collect();
+            counted.sort_unstable();
+            assert_eq!(store.usage(), counted);
+            assert!(counted.iter().all(|(_, count)| *count <= 9));
+        }
+        assert_eq!(store.slots.capacity(), slots);
+    }
+}
@@ -0,0 +1,772 @@
+//! 집계 방식 (AggregatorServiceImpl을 수정하지 않고 교체 가능)
+
+use oracle_vm_common::Price;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::aggregation::{
+    contributing_nodes, is_recent, median_exact_in_place_with, one_vote_per_node_prices,
+    trimmed_mean_in_place_with, weighted_median_in_place_with, AggregationMode,
+};
+use crate::reputation::Reputation;
+use crate::source_weights::SourceWeights;
+use crate::trust::{volume_reference, TrustCoefficients};
+use crate::{PriceEntry, PRICE_WINDOW_SECS};
+
+/// 집계 결과
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AggregationResult {
+    /// 집계된 가격
+    pub price: Price,
+    /// 계산에 사용한 가격 데이터 수
+    pub data_points: usize,
+}
+
+/// 가중 집계에 사용하는 Aggregator 상태 (거래소 가중치, 노드 평판)
+#[derive(Debug, Clone, Copy)]
+pub struct WeightContext<'a> {
+    pub sources: &'a SourceWeights,
+    pub reputation: &'a Reputation,
+}
+
+/// 집계 방식
+///
+/// 집계에 참여하는 가격 데이터(기본 pair, 참여 기간 필터 적용)를 받아 하나의 가격을 만든다.
+/// 윈도우 적용은 구현이 담당한다. 데이터는 버퍼에 있는 항목의 참조로 받으므로 집계할 때마다
+/// 복사하지 않는다.
+pub trait AggregationStrategy: fmt::Debug + Send + Sync + 'static {
+    /// 로그에 표시할 이름
+    fn name(&self) -> &str;
+
+    /// `now` 시점의 집계 결과 (사용할 데이터가 없으면 None)
+    fn aggregate(&self, entries: &[&PriceEntry], now: u64) -> Option<AggregationResult>;
+
+    /// 거래소 가중치, 노드 평판을 반영한 집계 결과 (기본 구현은 가중치를 쓰지 않음)
+    fn aggregate_weighted(
+        &self,
+        entries: &[&PriceEntry],
+        now: u64,
+        _context: &WeightContext<'_>,
+    ) -> Option<AggregationResult> {
+        self.aggregate(entries, now)
+    }
+}
+
+/// 이름으로 기본 설정의 집계 방식 선택
+/// (median, trimmed-by-one-median, one-vote-per-node, weighted-median, trust-weighted-median)
+pub fn by_name(name: &str) -> Option<Arc<dyn AggregationStrategy>> {
+    by_name_with_mode(name, AggregationMode::Arithmetic)
+}
+
+/// 이름으로 `mode` 공간에서 계산하는 집계 방식 선택
+pub fn by_name_with_mode(
+    name: &str,
+    mode: AggregationMode,
+) -> Option<Arc<dyn AggregationStrategy>> {
+    match name {
+        "median" => Some(Arc::new(Median {
+            mode,
+            ..Median::default()
+        })),
+        "trimmed-by-one-median" => Some(Arc::new(TrimmedByOneMedian {
+            mode,
+            ..TrimmedByOneMedian::default()
+        })),
+        "one-vote-per-node" => Some(Arc::new(OneVotePerNodeMedian {
+            mode,
+            ..OneVotePerNodeMedian::default()
+        })),
+        "weighted-median" => Some(Arc::new(WeightedMedian {
+            mode,
+            ..WeightedMedian::default()
+        })),
+        "trust-weighted-median" => Some(Arc::new(TrustWeightedMedian {
+            mode,
+            ..TrustWeightedMedian::default()
+        })),
+        _ => None,
+    }
+}
+
+// 윈도우 내 가격 (기하 모드에서는 0인 가격 제외)
+fn recent_prices(
+    entries: &[&PriceEntry],
+    now: u64,
+    window_secs: u64,
+    mode: AggregationMode,
+) -> Vec<Price> {
+    let mut prices = Vec::with_capacity(entries.len());
+    prices.extend(
+        entries
+            .iter()
+            .filter(|entry| is_recent(entry.timestamp, now, window_secs))
+            .map(|entry| entry.price)
+            .filter(|price| mode.accepts(price)),
+    );
+    prices
+}
+
+// 모드에 따른 표시 이름
+fn mode_name(
+    mode: AggregationMode,
+    arithmetic: &'static str,
+    geometric: &'static str,
+) -> &'static str {
+    match mode {
+        AggregationMode::Arithmetic => arithmetic,
+        AggregationMode::Geometric => geometric,
+    }
+}
+
+/// 윈도우 내 모든 가격의 중간값 (정수 공간에서 계산, 기본값)
+#[derive(Debug, Clone, Copy)]
+pub struct Median {
+    pub window_secs: u64,
+    /// 짝수 개일 때 가운데 두 가격을 평균하는 공간
+    pub mode: AggregationMode,
+}
+
+impl Default for Median {
+    fn default() -> Self {
+        Self {
+            window_secs: PRICE_WINDOW_SECS,
+            mode: AggregationMode::default(),
+        }
+    }
+}
+
+impl AggregationStrategy for Median {
+    fn name(&self) -> &str {
+        mode_name(self.mode, "median", "geometric median")
+    }
+
+    fn aggregate(&self, entries: &[&PriceEntry], now: u64) -> Option<AggregationResult> {
+        let mut prices = recent_prices(entries, now, self.window_secs, self.mode);
+        let data_points = prices.len();
+        median_exact_in_place_with(&mut prices, self.mode)
+            .map(|price| AggregationResult { price, data_points })
+    }
+}
+
+/// 거래소마다 가장 높은 가격과 가장 낮은 가격을 하나씩 제외한 중간값
+///
+/// IQR 필터보다 가볍게 거래소별 극단값의 영향을 없앤다. 노드 수가 적을 때를 위한 방식으로,
+/// 윈도우 내 가격이 [`TrimmedByOneMedian::MIN_PRICES`]개 미만인 거래소는 제외하지 않는다.
+#[derive(Debug, Clone, Copy)]
+pub struct TrimmedByOneMedian {
+    pub window_secs: u64,
+    pub mode: AggregationMode,
+}
+
+impl TrimmedByOneMedian {
+    /// 양 끝을 제외하는 최소 가격 수 (제외한 뒤에도 하나 이상 남음)
+    pub const MIN_PRICES: usize = 3;
+
+    /// 거래소별로 양 끝을 제외하고 남은 윈도우 내 가격
+    pub fn trimmed_prices(&self, entries: &[&PriceEntry], now: u64) -> Vec<Price> {
+        let mut by_source: HashMap<&str, Vec<Price>> = HashMap::new();
+        for entry in entries
+            .iter()
+            .filter(|entry| is_recent(entry.timestamp, now, self.window_secs))
+            .filter(|entry| self.mode.accepts(&entry.price))
+        {
+            by_source
+                .entry(&entry.source)
+                .or_default()
+                .push(entry.price);
+        }
+
+        let mut prices = Vec::with_capacity(entries.len());
+        for mut source_prices in by_source.into_values() {
+            if source_prices.len() < Self::MIN_PRICES {
+                prices.extend(source_prices);
+            } else {
+                source_prices.sort_unstable();
+                prices.extend_from_slice(&source_prices[1..source_prices.len() - 1]);
+            }
+        }
+        prices
+    }
+}
+
+impl Default for TrimmedByOneMedian {
+    fn default() -> Self {
+        Self {
+            window_secs: PRICE_WINDOW_SECS,
+            mode: AggregationMode::default(),
+        }
+    }
+}
+
+impl AggregationStrategy for TrimmedByOneMedian {
+    fn name(&self) -> &str {
+        mode_name(
+            self.mode,
+            "trimmed-by-one median",
+            "trimmed-by-one geometric median",
+        )
+    }
+
+    fn aggregate(&self, entries: &[&PriceEntry], now: u64) -> Option<AggregationResult> {
+        let mut prices = self.trimmed_prices(entries, now);
+        let data_points = prices.len();
+        median_exact_in_place_with(&mut prices, self.mode)
+            .map(|price| AggregationResult { price, data_points })
+    }
+}
+
+/// 노드마다 윈도우 내 가장 최근 가격 하나만 사용한 중간값
+#[derive(Debug, Clone, Copy)]
+pub struct OneVotePerNodeMedian {
+    pub window_secs: u64,
+    pub mode: AggregationMode,
+}
+
+impl Default for OneVotePerNodeMedian {
+    fn default() -> Self {
+        Self {
+            window_secs: PRICE_WINDOW_SECS,
+            mode: AggregationMode::default(),
+        }
+    }
+}
+
+impl AggregationStrategy for OneVotePerNodeMedian {
+    fn name(&self) -> &str {
+        mode_name(
+            self.mode,
+            "one-vote-per-node median",
+            "one-vote-per-node geometric median",
+        )
+    }
+
+    fn aggregate(&self, entries: &[&PriceEntry], now: u64) -> Option<AggregationResult> {
+        let data_points = contributing_nodes(entries, now, self.window_secs);
+        let mut prices = one_vote_per_node_prices(entries.iter().copied(), now, self.window_secs);
+        median_exact_in_place_with(&mut prices, self.mode)
+            .map(|price| AggregationResult { price, data_points })
+    }
+}
+
+/// 윈도우 내 가격을 거래소 가중치로 가중한 중간값 (목록에 없는 거래소는 1.0)
+#[derive(Debug, Clone, Copy)]
+pub struct WeightedMedian {
+    pub window_secs: u64,
+    pub mode: AggregationMode,
+}
+
+impl Default for WeightedMedian {
+    fn default() -> Self {
+        Self {
+            window_secs: PRICE_WINDOW_SECS,
+            mode: AggregationMode::default(),
+        }
+    }
+}
+
+impl AggregationStrategy for WeightedMedian {
+    fn name(&self) -> &str {
+        mode_name(self.mode, "weighted median", "weighted geometric median")
+    }
+
+    fn aggregate(&self, entries: &[&PriceEntry], now: u64) -> Option<AggregationResult> {
+        let context = WeightContext {
+            sources: &SourceWeights::default(),
+            reputation: &Reputation::default(),
+        };
+        self.aggregate_weighted(entries, now, &context)
+    }
+
+    fn aggregate_weighted(
+        &self,
+        entries: &[&PriceEntry],
+        now: u64,
+        context: &WeightContext<'_>,
+    ) -> Option<AggregationResult> {
+        let mut prices: Vec<(Price, f64)> = entries
+            .iter()
+            .filter(|entry| is_recent(entry.timestamp, now, self.window_secs))
+            .filter(|entry| self.mode.accepts(&entry.price))
+            .map(|entry| (entry.price, context.sources.get(&entry.source)))
+            .collect();
+        let data_points = prices.len();
+        weighted_median_in_place_with(&mut prices, self.mode)
+            .map(|price| AggregationResult { price, data_points })
+    }
+}
+
+/// 윈도우 내 가격을 합성 신뢰 가중치로 가중한 중간값
+///
+/// 가중치는 거래소 가중치 × 평판 항목 × 거래량 항목 × 최신성 항목이다.
+/// 기본 계수와 기본 거래소 가중치에서는 모든 가중치가 1이므로 [`Median`]과 같다.
+#[derive(Debug, Clone, Copy)]
+pub struct TrustWeightedMedian {
+    pub window_secs: u64,
+    pub coefficients: TrustCoefficients,
+    pub mode: AggregationMode,
+}
+
+impl Default for TrustWeightedMedian {
+    fn default() -> Self {
+        Self {
+            window_secs: PRICE_WINDOW_SECS,
+            coefficients: TrustCoefficients::default(),
+            mode: AggregationMode::default(),
+        }
+    }
+}
+
+impl TrustWeightedMedian {
+    /// 윈도우 내 가격과 합성 가중치
+    pub fn weights(
+        &self,
+        entries: &[&PriceEntry],
+        now: u64,
+        context: &WeightContext<'_>,
+    ) -> Vec<(Price, f64)> {
+        let recent: Vec<&PriceEntry> = entries
+            .iter()
+            .copied()
+            .filter(|entry| is_recent(entry.timestamp, now, self.window_secs))
+            .filter(|entry| self.mode.accepts(&entry.price))
+            .collect();
+        let reference = volume_reference(recent.iter().copied());
+
+        recent
+            .into_iter()
+            .map(|entry| {
+                let coefficients = &self.coefficients;
+                let weight = context.sources.get(&entry.source)
+                    * coefficients.reputation_factor(context.reputation.get(&entry.node_id))
+                    * coefficients.volume_factor(entry.volume, reference)
+                    * coefficients.recency_factor(now.saturating_sub(entry.timestamp));
+                (entry.price, weight)
+            })
+            .collect()
+    }
+}
+
+impl AggregationStrategy for TrustWeightedMedian {
+    fn name(&self) -> &str {
+        mode_name(
+            self.mode,
+            "trust-weighted median",
+            "trust-weighted geometric median",
+        )
+    }
+
+    fn aggregate(&self, entries: &[&PriceEntry], now: u64) -> Option<AggregationResult> {
+        let context = WeightContext {
+            sources: &SourceWeights::default(),
+            reputation: &Reputation::default(),
+        };
+        self.aggregate_weighted(entries, now, &context)
+    }
+
+    fn aggregate_weighted(
+        &self,
+        entries: &[&PriceEntry],
+        now: u64,
+        context: &WeightContext<'_>,
+    ) -> Option<AggregationResult> {
+        let mut prices = self.weights(entries, now, context);
+        let data_points = prices.len();
+        weighted_median_in_place_with(&mut prices, self.mode)
+            .map(|price| AggregationResult { price, data_points })
+    }
+}
+
+/// 양 끝에서 `trim_ratio` 비율만큼 제외한 평균 (센트 단위로 반올림)
+///
+/// 기하 모드에서는 남은 가격의 기하 평균이다.
+#[derive(Debug, Clone, Copy)]
+pub struct TrimmedMean {
+    pub window_secs: u64,
+    pub trim_ratio: f64,
+    pub mode: AggregationMode,
+}
+
+impl AggregationStrategy for TrimmedMean {
+    fn name(&self) -> &str {
+        mode_name(self.mode, "trimmed mean", "trimmed geometric mean")
+    }
+
+    fn aggregate(&self, entries: &[&PriceEntry], now: u64) -> Option<AggregationResult> {
+        let mut prices: Vec<f64> = recent_prices(entries, now, self.window_secs, self.mode)
+            .into_iter()
+            .map(|price| price.to_f64_dollars())
+            .collect();
+        let data_points = prices.len();
+        let mean = trimmed_mean_in_place_with(&mut prices, self.trim_ratio, self.mode)?;
+        let price = Price::from_f64_dollars(mean, Price::USD_DECIMALS).ok()?;
+        Some(AggregationResult { price, data_points })
+    }
+}
+
+/// 윈도우 내 가격이 `min_points`개 미만이면 설정된 방식 대신 중간값으로 집계
+///
+/// 절사 평균이나 가중 중간값처럼 데이터가 적으면 불안정한 방식이 몇 안 되는 가격을 모두 잘라 내거나
+/// 한 가격에 끌려가지 않게 한다. 이름은 감싼 방식의 이름을 그대로 쓴다.
+#[derive(Debug, Clone)]
+pub struct MinPoints {
+    pub inner: Arc<dyn AggregationStrategy>,
+    pub min_points: usize,
+    /// 가격이 부족할 때 쓰는 중간값 (가격 수도 이 윈도우와 모드로 셈)
+    pub fallback: Median,
+}
+
+impl MinPoints {
+    /// `mode` 공간의 중간값으로 대체하는 `inner`
+    pub fn new(
+        inner: Arc<dyn AggregationStrategy>,
+        min_points: usize,
+        mode: AggregationMode,
+    ) -> Self {
+        Self {
+            inner,
+            min_points,
+            fallback: Median {
+                mode,
+                ..Median::default()
+            },
+        }
+    }
+
+    // 윈도우 내 가격이 하한보다 적은지
+    fn below_floor(&self, entries: &[&PriceEntry], now: u64) -> bool {
+        let points = entries
+            .iter()
+            .filter(|entry| is_recent(entry.timestamp, now, self.fallback.window_secs))
+            .filter(|entry| self.fallback.mode.accepts(&entry.price))
+            .count();
+        points < self.min_points
+    }
+}
+
+impl AggregationStrategy for MinPoints {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn aggregate(&self, entries: &[&PriceEntry], now: u64) -> Option<AggregationResult> {
+        if self.below_floor(entries, now) {
+            return self.fallback.aggregate(entries, now);
+        }
+        self.inner.aggregate(entries, now)
+    }
+
+    fn aggregate_weighted(
+        &self,
+        entries: &[&PriceEntry],
+        now: u64,
+        context: &WeightContext<'_>,
+    ) -> Option<AggregationResult> {
+        if self.below_floor(entries, now) {
+            return self.fallback.aggregate(entries, now);
+        }
+        self.inner.aggregate_weighted(entries, now, context)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(cents: u64, node_id: &str, timestamp: u64) -> PriceEntry {
+        PriceEntry {
+            price: Price::from_cents(cents),
+            timestamp,
+            source: Arc::from("binance"),
+            node_id: Arc::from(node_id),
+            pair: Arc::from("BTC/USD"),
+            volume: None,
+        }
+    }
+
+    #[test]
+    fn test_builtin_strategies_apply_their_window() {
+        let now = 1_700_000_000;
+        let entries = [
+            entry(7_000_000, "node-a", now - 1),
+            entry(7_000_100, "node-a", now),
+            entry(7_000_200, "node-b", now),
+            entry(9_900_000, "node-c", now),
+            // 윈도우 밖
+            entry(1_000_000, "node-d", now - PRICE_WINDOW_SECS),
+        ];
+        let entries: Vec<&PriceEntry> = entries.iter().collect();
+
+        let median = Median::default().aggregate(&entries, now).unwrap();
+        assert_eq!(median.price, Price::from_cents(7_000_150));
+        assert_eq!(median.data_points, 4);
+
+        let one_vote = OneVotePerNodeMedian::default()
+            .aggregate(&entries, now)
+            .unwrap();
+        assert_eq!(one_vote.price, Price::from_cents(7_000_200));
+        assert_eq!(one_vote.data_points, 3);
+
+        let trimmed = TrimmedMean {
+            window_secs: PRICE_WINDOW_SECS,
+            trim_ratio: 0.25,
+            mode: AggregationMode::Arithmetic,
+        }
+        .aggregate(&entries, now)
+        .unwrap();
+        assert_eq!(trimmed.price, Price::from_cents(7_000_150));
+
+        assert_eq!(Median::default().aggregate(&entries[4..], now), None);
+    }
+
+    #[test]
+    fn test_min_points_falls_back_to_median_below_the_floor() {
+        let now = 1_700_000_000;
+        let mean = Arc::new(TrimmedMean {
+            window_secs: PRICE_WINDOW_SECS,
+            trim_ratio: 0.0,
+            mode: AggregationMode::Arithmetic,
+        });
+        let strategy = MinPoints::new(mean, 4, AggregationMode::Arithmetic);
+        assert_eq!(strategy.name(), "trimmed mean");
+        let entries = [
+            entry(7_000_000, "node-a", now),
+            entry(7_000_200, "node-b", now),
+            entry(7_001_000, "node-c", now),
+            // 윈도우 밖 (하한에 세지 않음)
+            entry(1_000_000, "node-d", now - PRICE_WINDOW_SECS),
+            entry(7_010_000, "node-e", now),
+        ];
+        let entries: Vec<&PriceEntry> = entries.iter().collect();
+
+        // 3개: 평균(7,000,400) 대신 중간값
+        let fallback = strategy.aggregate(&entries[..4], now).unwrap();
+        assert_eq!(fallback.price, Price::from_cents(7_000_200));
+        assert_eq!(fallback.data_points, 3);
+        let sources = SourceWeights::default();
+        let reputation = Reputation::default();
+        let context = WeightContext {
+            sources: &sources,
+            reputation: &reputation,
+        };
+        assert_eq!(
+            strategy.aggregate_weighted(&entries[..4], now, &context),
+            Some(fallback)
+        );
+
+        // 4개: 설정된 방식(평균) 사용
+        let result = strategy.aggregate(&entries, now).unwrap();
+        assert_eq!(result.price, Price::from_cents(7_002_800));
+        assert_eq!(result.data_points, 4);
+
+        assert_eq!(strategy.aggregate(&entries[3..4], now), None);
+    }
+
+    #[test]
+    fn test_trimmed_by_one_median_excludes_each_sources_extremes() {
+        let now = 1_700_000_000;
+        let sourced = |cents, node_id, source: &str| PriceEntry {
+            source: Arc::from(source),
+            ..entry(cents, node_id, now)
+        };
+        let entries = [
+            // binance의 극단값 두 개 (7_100_000, 6_900_000)가 제외됨
+            sourced(7_100_000, "node-a", "binance"),
+            sourced(7_100_000, "node-b", "binance"),
+            sourced(7_000_000, "node-c", "binance"),
+            sourced(6_900_000, "node-d", "binance"),
+            // coinbase의 극단값 두 개 (7_300_000, 7_000_100)가 제외됨
+            sourced(7_300_000, "node-a", "coinbase"),
+            sourced(7_200_000, "node-b", "coinbase"),
+            sourced(7_200_000, "node-c", "coinbase"),
+            sourced(7_000_100, "node-d", "coinbase"),
+        ];
+        let entries: Vec<&PriceEntry> = entries.iter().collect();
+
+        // 제외 전: 가운데 두 가격 7_100_000, 7_100_000
+        assert_eq!(
+            Median::default().aggregate(&entries, now).unwrap().price,
+            Price::from_cents(7_100_000)
+        );
+        // 제외 후: 7_000_000, 7_100_000, 7_200_000, 7_200_000
+        let strategy = TrimmedByOneMedian::default();
+        let mut trimmed = strategy.trimmed_prices(&entries, now);
+        trimmed.sort_unstable();
+        assert_eq!(
+            trimmed,
+            [7_000_000, 7_100_000, 7_200_000, 7_200_000].map(Price::from_cents)
+        );
+        let result = strategy.aggregate(&entries, now).unwrap();
+        assert_eq!(result.price, Price::from_cents(7_150_000));
+        assert_eq!(result.data_points, 4);
+        assert_eq!(
+            by_name("trimmed-by-one-median").unwrap().name(),
+            "trimmed-by-one median"
+        );
+    }
+
+    #[test]
+    fn test_trimmed_by_one_median_keeps_tiny_sources() {
+        let now = 1_700_000_000;
+        let strategy = TrimmedByOneMedian::default();
+        let entries = [
+            entry(7_000_000, "node-a", now),
+            entry(7_000_200, "node-b", now),
+            entry(9_000_000, "node-c", now),
+        ];
+        let entries: Vec<&PriceEntry> = entries.iter().collect();
+
+        // 두 개 이하는 제외하면 남는 가격이 없으므로 그대로 사용
+        assert_eq!(strategy.aggregate(&entries[..0], now), None);
+        for len in 1..TrimmedByOneMedian::MIN_PRICES {
+            assert_eq!(
+                strategy.aggregate(&entries[..len], now),
+                Median::default().aggregate(&entries[..len], now)
+            );
+        }
+        // 세 개면 가운데 하나만 남음
+        let result = strategy.aggregate(&entries, now).unwrap();
+        assert_eq!(result.price, Price::from_cents(7_000_200));
+        assert_eq!(result.data_points, 1);
+    }
+
+    #[test]
+    fn test_geometric_mode_aggregates_in_log_space() {
+        let now = 1_700_000_000;
+        // 두 배씩 벌어지는 가격: 로그 공간에서는 등간격
+        let entries: Vec<PriceEntry> = [10_000, 20_000, 40_000, 80_000]
+            .iter()
+            .enumerate()
+            .map(|(i, &cents)| entry(cents, &format!("node-{}", i), now))
+            .collect();
+        let entries: Vec<&PriceEntry> = entries.iter().collect();
+        let aggregate = |strategy: &dyn AggregationStrategy, entries: &[&PriceEntry]| {
+            strategy.aggregate(entries, now).unwrap()
+        };
+        let geometric = Median {
+            mode: AggregationMode::Geometric,
+            ..Median::default()
+        };
+        let mean = |mode| TrimmedMean {
+            window_secs: PRICE_WINDOW_SECS,
+            trim_ratio: 0.0,
+            mode,
+        };
+
+        // 산술: (200 + 400) / 2, 기하: sqrt(200 × 400) = 282.84...
+        assert_eq!(
+            aggregate(&Median::default(), &entries).price,
+            Price::from_cents(30_000)
+        );
+        assert_eq!(
+            aggregate(&geometric, &entries).price,
+            Price::from_cents(28_284)
+        );
+        // 평균은 큰 가격 쪽으로 치우치지 않음: (100 × 200 × 400 × 800)^(1/4) < 375
+        assert_eq!(
+            aggregate(&mean(AggregationMode::Arithmetic), &entries).price,
+            Price::from_cents(37_500)
+        );
+        assert_eq!(
+            aggregate(&mean(AggregationMode::Geometric), &entries).price,
+            Price::from_cents(28_284)
+        );
+        // 홀수 개의 중간값은 모드와 관계없이 같음
+        assert_eq!(
+            aggregate(&geometric, &entries[..3]),
+            aggregate(&Median::default(), &entries[..3])
+        );
+
+        // 0인 가격은 로그를 취할 수 없으므로 기하 모드에서만 제외
+        let zero = entry(0, "node-z", now);
+        let mut with_zero = entries.clone();
+        with_zero.push(&zero);
+        assert_eq!(aggregate(&Median::default(), &with_zero).data_points, 5);
+        let result = aggregate(&geometric, &with_zero);
+        assert_eq!(result.data_points, 4);
+        assert_eq!(result.price, Price::from_cents(28_284));
+        assert_eq!(
+            aggregate(&mean(AggregationMode::Geometric), &with_zero).price,
+            Price::from_cents(28_284)
+        );
+        assert_eq!(geometric.aggregate(&with_zero[4..], now), None);
+        assert_eq!(geometric.name(), "geometric median");
+    }
+
+    #[test]
+    fn test_trust_weights_are_equal_under_default_coefficients() {
+        let now = 1_700_000_000;
+        let mut reputation = Reputation::default();
+        reputation.set(Arc::from("node-0"), 0.1);
+        let sources = SourceWeights::default();
+        let context = WeightContext {
+            sources: &sources,
+            reputation: &reputation,
+        };
+
+        for seed in 0..20 {
+            let mut entries = crate::testing::PriceGenerator::new(seed, 7)
+                .with_now(now)
+                .entries(25 + seed as usize);
+            for (i, entry) in entries.iter_mut().enumerate() {
+                entry.volume = (i % 3 != 0).then_some(i as u64 * 1_000);
+            }
+            let entries: Vec<&PriceEntry> = entries.iter().collect();
+
+            let strategy = TrustWeightedMedian::default();
+            let weights = strategy.weights(&entries, now, &context);
+            assert!(weights.iter().all(|&(_, weight)| weight == 1.0));
+            assert_eq!(
+                strategy.aggregate_weighted(&entries, now, &context),
+                Median::default().aggregate(&entries, now)
+            );
+        }
+    }
+
+    #[test]
+    fn test_trust_weights_combine_reputation_volume_and_recency() {
+        let now = 1_700_000_000;
+        let mut entries = [
+            entry(7_000_000, "node-a", now),
+            entry(7_000_100, "node-b", now),
+            entry(7_000_200, "node-c", now - 30),
+        ];
+        entries[0].volume = Some(100);
+        entries[1].volume = Some(400);
+        let entries: Vec<&PriceEntry> = entries.iter().collect();
+        let mut reputation = Reputation::default();
+        reputation.set(Arc::from("node-a"), 0.5);
+        let sources = SourceWeights::default();
+        let context = WeightContext {
+            sources: &sources,
+            reputation: &reputation,
+        };
+
+        let strategy = TrustWeightedMedian {
+            window_secs: PRICE_WINDOW_SECS,
+            coefficients: TrustCoefficients {
+                reputation_exponent: 1.0,
+                volume_exponent: 1.0,
+                recency_half_life_secs: Some(30),
+            },
+            mode: AggregationMode::Arithmetic,
+        };
+        // 거래량 기준값은 보고된 거래량의 중간값 250, 거래량이 없는 node-c는 1.0
+        let weights: Vec<f64> = strategy
+            .weights(&entries, now, &context)
+            .into_iter()
+            .map(|(_, weight)| weight)
+            .collect();
+        let expected = [0.5 * 0.4, 1.6, 0.5];
+        for (weight, expected) in weights.iter().zip(expected) {
+            assert!((weight - expected).abs() < 1e-12, "{:?}", weights);
+        }
+
+        // node-b가 가중치의 절반 이상을 차지
+        let result = strategy
+            .aggregate_weighted(&entries, now, &context)
+            .unwrap();
+        assert_eq!(result.price, Price::from_cents(7_000_100));
+        assert_eq!(result.data_points, 3);
+    }
+}
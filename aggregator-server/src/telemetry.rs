@@ -0,0 +1,114 @@
+//! 분산 추적: 로거 설치, OTLP 내보내기, 노드가 보낸 trace context 이어받기
+//!
+//! `otlp_endpoint`가 설정되면 제출 처리(`submit_price`)와 집계(`aggregate`) span을 OTLP/gRPC로
+//! 내보낸다. 노드가 요청 메타데이터에 W3C `traceparent`를 붙여 보내면 `submit_price` span은 노드
+//! 라운드 span의 자식이 된다.
+
+use anyhow::{Context as _, Result};
+use opentelemetry::propagation::{Extractor, TextMapPropagator};
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::TracerProvider;
+use opentelemetry_sdk::{runtime, Resource};
+use tonic::metadata::{KeyRef, MetadataMap};
+use tracing::level_filters::LevelFilter;
+use tracing::Span;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+
+/// 내보내는 span의 서비스 이름
+pub const SERVICE_NAME: &str = "oracle-aggregator";
+
+/// 콘솔 로거 설치 (`otlp_endpoint`가 있으면 span도 내보냄)
+///
+/// 반환된 provider는 종료할 때 `shutdown`해야 남은 span이 전송된다.
+pub fn init(otlp_endpoint: Option<&str>) -> Result<Option<TracerProvider>> {
+    let provider = otlp_endpoint.map(otlp_tracer_provider).transpose()?;
+    let otel_layer = provider
+        .as_ref()
+        .map(|provider| tracing_opentelemetry::layer().with_tracer(provider.tracer(SERVICE_NAME)));
+    tracing_subscriber::registry()
+        .with(LevelFilter::INFO)
+        .with(tracing_subscriber::fmt::layer())
+        .with(otel_layer)
+        .try_init()
+        .context("Failed to install the logger")?;
+    Ok(provider)
+}
+
+/// `endpoint`(예: http://localhost:4317)로 span을 일괄 전송하는 tracer provider
+pub fn otlp_tracer_provider(endpoint: &str) -> Result<TracerProvider> {
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_tonic()
+        .with_endpoint(endpoint)
+        .build()
+        .with_context(|| format!("Invalid OTLP endpoint {:?}", endpoint))?;
+    Ok(TracerProvider::builder()
+        .with_batch_exporter(exporter, runtime::Tokio)
+        .with_resource(Resource::new([KeyValue::new("service.name", SERVICE_NAME)]))
+        .build())
+}
+
+/// 요청 메타데이터에 trace context가 있으면 `span`을 그 trace의 자식으로 만듦
+pub fn continue_remote_trace(span: &Span, metadata: &MetadataMap) {
+    let context = TraceContextPropagator::new().extract(&MetadataExtractor(metadata));
+    span.set_parent(context);
+}
+
+struct MetadataExtractor<'a>(&'a MetadataMap);
+
+impl Extractor for MetadataExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0
+            .keys()
+            .filter_map(|key| match key {
+                KeyRef::Ascii(key) => Some(key.as_str()),
+                KeyRef::Binary(_) => None,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::trace::TraceContextExt;
+    use tonic::metadata::MetadataValue;
+
+    #[test]
+    fn test_continues_trace_from_traceparent() {
+        let provider = TracerProvider::builder().build();
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+        tracing::subscriber::with_default(subscriber, || {
+            let mut metadata = MetadataMap::new();
+            metadata.insert(
+                "traceparent",
+                MetadataValue::from_static(
+                    "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+                ),
+            );
+            let span = tracing::info_span!("submit_price");
+            continue_remote_trace(&span, &metadata);
+            assert_eq!(
+                span.context().span().span_context().trace_id().to_string(),
+                "4bf92f3577b34da6a3ce929d0e0e4736"
+            );
+
+            // 메타데이터가 없으면 새 trace를 시작
+            let span = tracing::info_span!("submit_price");
+            continue_remote_trace(&span, &MetadataMap::new());
+            assert_ne!(
+                span.context().span().span_context().trace_id().to_string(),
+                "4bf92f3577b34da6a3ce929d0e0e4736"
+            );
+        });
+    }
+}
@@ -0,0 +1,208 @@
+//! 테스트와 벤치마크에서 공유하는 가격 데이터 생성기
+//!
+//! 외부 난수 크레이트 없이 시드 고정 의사 난수를 사용하므로 같은 시드는 항상 같은 데이터를 만든다.
+
+use oracle_vm_common::Price;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::cadence::Clock;
+use crate::oracle::PriceRequest;
+use crate::{PriceEntry, DEFAULT_PAIR, PRICE_WINDOW_SECS};
+
+/// 생성 데이터에 사용하는 거래소 목록
+pub const SOURCES: [&str; 3] = ["binance", "coinbase", "kraken"];
+
+/// 노드/소스/가격 분포를 흉내 낸 가격 데이터 생성기
+///
+/// - 노드는 균등하게 선택된다
+/// - 가격은 기준가 ±0.1% 범위에서 흔들리고, 약 1%는 ±5% 이상치다
+/// - 타임스탬프는 `now` 이전 집계 윈도우 안에 분포한다
+pub struct PriceGenerator {
+    state: u64,
+    node_ids: Vec<Arc<str>>,
+    sources: Vec<Arc<str>>,
+    base_price: f64,
+    now: u64,
+}
+
+impl PriceGenerator {
+    pub fn new(seed: u64, nodes: usize) -> Self {
+        Self {
+            state: seed,
+            node_ids: (0..nodes.max(1))
+                .map(|i| Arc::from(format!("oracle-node-{:05}", i)))
+                .collect(),
+            sources: SOURCES.into_iter().map(Arc::from).collect(),
+            base_price: 70_000.0,
+            now: 1_700_000_000,
+        }
+    }
+
+    pub fn with_base_price(mut self, base_price: f64) -> Self {
+        self.base_price = base_price;
+        self
+    }
+
+    pub fn with_now(mut self, now: u64) -> Self {
+        self.now = now;
+        self
+    }
+
+    pub fn node_ids(&self) -> &[Arc<str>] {
+        &self.node_ids
+    }
+
+    // splitmix64
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // [0, 1) 범위의 실수
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn next_index(&mut self, len: usize) -> usize {
+        (self.next_u64() % len as u64) as usize
+    }
+
+    fn next_price(&mut self) -> Price {
+        let spread = if self.next_index(100) == 0 {
+            0.05
+        } else {
+            0.001
+        };
+        let offset = (self.next_f64() * 2.0 - 1.0) * spread;
+        // cent 단위로 반올림
+        Price::from_cents((self.base_price * (1.0 + offset) * 100.0).round() as u64)
+    }
+
+    pub fn next_entry(&mut self) -> PriceEntry {
+        let node_index = self.next_index(self.node_ids.len());
+        let source_index = self.next_index(self.sources.len());
+        let node_id = self.node_ids[node_index].clone();
+        let source = self.sources[source_index].clone();
+        let age = self.next_u64() % PRICE_WINDOW_SECS;
+
+        PriceEntry {
+            price: self.next_price(),
+            timestamp: self.now - age,
+            source,
+            node_id,
+            pair: Arc::from(DEFAULT_PAIR),
+            volume: None,
+        }
+    }
+
+    pub fn next_request(&mut self) -> PriceRequest {
+        let entry = self.next_entry();
+        let (price_scaled, price_decimals) = entry.price.to_scaled();
+
+        PriceRequest {
+            price: entry.price.to_f64_dollars(),
+            timestamp: entry.timestamp,
+            source: entry.source.to_string(),
+            node_id: entry.node_id.to_string(),
+            signature: None,
+            price_scaled: Some(price_scaled),
+            price_decimals: Some(price_decimals),
+            symbol: Some(entry.pair.to_string()),
+            historical: false,
+            volume: entry.volume,
+        }
+    }
+
+    pub fn entries(&mut self, count: usize) -> Vec<PriceEntry> {
+        (0..count).map(|_| self.next_entry()).collect()
+    }
+
+    pub fn requests(&mut self, count: usize) -> Vec<PriceRequest> {
+        (0..count).map(|_| self.next_request()).collect()
+    }
+}
+
+/// tokio 시간을 따르는 시계
+///
+/// `tokio::time::pause()` 상태에서는 sleep이 즉시 시간을 앞당기므로 테스트가 벽시계 경계를 정확히 재현할 수 있다.
+pub struct TokioClock {
+    base_millis: u64,
+    start: tokio::time::Instant,
+}
+
+impl TokioClock {
+    pub fn starting_at(base_millis: u64) -> Self {
+        Self {
+            base_millis,
+            start: tokio::time::Instant::now(),
+        }
+    }
+}
+
+impl Clock for TokioClock {
+    fn now_millis(&self) -> u64 {
+        self.base_millis + self.start.elapsed().as_millis() as u64
+    }
+}
+
+/// 직접 조작하는 시계 (`advance`/`set_millis`를 호출할 때만 시간이 흐름)
+///
+/// tokio 시간과 무관하므로 sleep 없이 윈도우와 타임아웃을 원하는 시각으로 재현할 수 있다.
+#[derive(Debug, Default)]
+pub struct ManualClock {
+    millis: AtomicU64,
+}
+
+impl ManualClock {
+    pub fn starting_at(millis: u64) -> Self {
+        Self {
+            millis: AtomicU64::new(millis),
+        }
+    }
+
+    /// 시간을 `by`만큼 앞당김
+    pub fn advance(&self, by: Duration) {
+        self.millis
+            .fetch_add(by.as_millis() as u64, Ordering::SeqCst);
+    }
+
+    /// 시각을 지정 (밀리초)
+    pub fn set_millis(&self, millis: u64) {
+        self.millis.store(millis, Ordering::SeqCst);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now_millis(&self) -> u64 {
+        self.millis.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generator_is_deterministic_and_in_range() {
+        let a = PriceGenerator::new(42, 10).entries(1_000);
+        let b = PriceGenerator::new(42, 10).entries(1_000);
+
+        for (x, y) in a.iter().zip(&b) {
+            assert_eq!(x.price, y.price);
+            assert_eq!(x.node_id, y.node_id);
+        }
+
+        assert!(a
+            .iter()
+            .all(|e| e.price >= Price::from_cents(6_650_000)
+                && e.price <= Price::from_cents(7_350_000)));
+        assert!(a
+            .iter()
+            .all(|e| 1_700_000_000 - e.timestamp < PRICE_WINDOW_SECS));
+    }
+}
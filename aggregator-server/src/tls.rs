@@ -0,0 +1,212 @@
+//! gRPC 서버 TLS 설정 (인증서와 개인키를 PEM 파일에서 읽음)
+//!
+//! TLS를 켜지 않으면 서버는 기존처럼 평문 gRPC로 동작한다. 클라이언트 CA를 지정하면 그 CA가
+//! 서명한 클라이언트 인증서를 제시한 노드만 TLS 핸드셰이크를 통과한다 (mTLS).
+
+use std::io;
+use std::path::{Path, PathBuf};
+use tonic::transport::{Certificate, Identity, ServerTlsConfig};
+
+/// 기본 인증서 경로
+pub const DEFAULT_TLS_CERT_PATH: &str = "certs/server.crt";
+/// 기본 개인키 경로
+pub const DEFAULT_TLS_KEY_PATH: &str = "certs/server.key";
+
+/// TLS 인증서/개인키 경로
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TlsConfig {
+    /// PEM 인증서 체인 (서버 인증서가 먼저)
+    pub cert_path: PathBuf,
+    /// PEM 개인키
+    pub key_path: PathBuf,
+    /// 클라이언트 인증서를 검증할 PEM CA (None이면 클라이언트 인증서를 요구하지 않음)
+    pub client_ca_path: Option<PathBuf>,
+}
+
+impl Default for TlsConfig {
+    fn default() -> Self {
+        Self {
+            cert_path: PathBuf::from(DEFAULT_TLS_CERT_PATH),
+            key_path: PathBuf::from(DEFAULT_TLS_KEY_PATH),
+            client_ca_path: None,
+        }
+    }
+}
+
+impl TlsConfig {
+    /// 인증서와 개인키를 읽어 tonic 서버 설정 생성
+    pub fn load(&self) -> io::Result<ServerTlsConfig> {
+        let cert = read_pem(&self.cert_path)?;
+        let key = read_pem(&self.key_path)?;
+        let config = ServerTlsConfig::new().identity(Identity::from_pem(cert, key));
+        Ok(match &self.client_ca_path {
+            Some(path) => config.client_ca_root(Certificate::from_pem(read_pem(path)?)),
+            None => config,
+        })
+    }
+}
+
+// 파일 경로를 포함한 에러로 읽기
+fn read_pem(path: &Path) -> io::Result<Vec<u8>> {
+    std::fs::read(path).map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oracle::oracle_service_client::OracleServiceClient;
+    use crate::oracle::oracle_service_server::OracleServiceServer;
+    use crate::oracle::HealthRequest;
+    use crate::AggregatorServiceImpl;
+    use rcgen::{BasicConstraints, CertificateParams, IsCa, KeyPair};
+    use std::net::SocketAddr;
+    use tokio::task::JoinHandle;
+    use tokio_stream::wrappers::TcpListenerStream;
+    use tonic::transport::{Channel, ClientTlsConfig, Server};
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aggregator-tls-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    // localhost용 자체 서명 인증서를 기록 (인증서 PEM 반환)
+    fn self_signed(dir: &Path) -> (TlsConfig, String) {
+        let certified = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+        let cert = certified.cert.pem();
+        let config = TlsConfig {
+            cert_path: dir.join("server.crt"),
+            key_path: dir.join("server.key"),
+            client_ca_path: None,
+        };
+        std::fs::write(&config.cert_path, &cert).unwrap();
+        std::fs::write(&config.key_path, certified.key_pair.serialize_pem()).unwrap();
+        (config, cert)
+    }
+
+    // 클라이언트 인증서를 서명할 CA
+    struct TestCa {
+        cert: rcgen::Certificate,
+        key: KeyPair,
+    }
+
+    impl TestCa {
+        fn new(name: &str) -> Self {
+            let mut params = CertificateParams::new(vec![name.to_string()]).unwrap();
+            params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+            let key = KeyPair::generate().unwrap();
+            let cert = params.self_signed(&key).unwrap();
+            Self { cert, key }
+        }
+
+        // 이 CA가 서명한 클라이언트 인증서
+        fn client_identity(&self, node_id: &str) -> Identity {
+            let key = KeyPair::generate().unwrap();
+            let cert = CertificateParams::new(vec![node_id.to_string()])
+                .unwrap()
+                .signed_by(&key, &self.cert, &self.key)
+                .unwrap();
+            Identity::from_pem(cert.pem(), key.serialize_pem())
+        }
+    }
+
+    async fn serve(
+        config: &TlsConfig,
+    ) -> (SocketAddr, JoinHandle<Result<(), tonic::transport::Error>>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(
+            Server::builder()
+                .tls_config(config.load().unwrap())
+                .unwrap()
+                .add_service(OracleServiceServer::new(AggregatorServiceImpl::new()))
+                .serve_with_incoming(TcpListenerStream::new(listener)),
+        );
+        (addr, server)
+    }
+
+    // 서버 인증서를 신뢰하는 TLS 클라이언트로 HealthCheck 호출 (응답을 받았는지만 확인)
+    async fn health_over_tls(
+        addr: SocketAddr,
+        server_cert: &str,
+        identity: Option<Identity>,
+    ) -> Result<(), String> {
+        let mut tls = ClientTlsConfig::new()
+            .domain_name("localhost")
+            .ca_certificate(Certificate::from_pem(server_cert));
+        if let Some(identity) = identity {
+            tls = tls.identity(identity);
+        }
+        let channel = Channel::from_shared(format!("https://{}", addr))
+            .unwrap()
+            .tls_config(tls)
+            .unwrap()
+            .connect()
+            .await
+            .map_err(|e| e.to_string())?;
+        OracleServiceClient::new(channel)
+            .health_check(HealthRequest {
+                node_id: "node-a".to_string(),
+            })
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+
+    #[tokio::test]
+    async fn test_tls_client_connects() {
+        let dir = temp_dir();
+        let (config, cert) = self_signed(&dir);
+        let (addr, server) = serve(&config).await;
+
+        assert_eq!(health_over_tls(addr, &cert, None).await, Ok(()));
+
+        // 평문 클라이언트는 TLS 서버와 통신할 수 없음
+        let plaintext = OracleServiceClient::connect(format!("http://{}", addr)).await;
+        if let Ok(mut client) = plaintext {
+            let request = HealthRequest {
+                node_id: "node-a".to_string(),
+            };
+            assert!(client.health_check(request).await.is_err());
+        }
+
+        server.abort();
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_mutual_tls_rejects_clients_without_provisioned_cert() {
+        let dir = temp_dir();
+        let (mut config, cert) = self_signed(&dir);
+        let ca = TestCa::new("oracle-node-ca");
+        let ca_path = dir.join("client-ca.crt");
+        std::fs::write(&ca_path, ca.cert.pem()).unwrap();
+        config.client_ca_path = Some(ca_path);
+        let (addr, server) = serve(&config).await;
+
+        // CA가 서명한 인증서를 제시한 노드만 통과
+        let provisioned = ca.client_identity("node-a");
+        assert_eq!(
+            health_over_tls(addr, &cert, Some(provisioned)).await,
+            Ok(())
+        );
+
+        assert!(health_over_tls(addr, &cert, None).await.is_err());
+        let unknown = TestCa::new("rogue-ca").client_identity("node-a");
+        assert!(health_over_tls(addr, &cert, Some(unknown)).await.is_err());
+
+        server.abort();
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_missing_files_name_the_path() {
+        let config = TlsConfig {
+            cert_path: PathBuf::from("/nonexistent/server.crt"),
+            ..TlsConfig::default()
+        };
+        let error = config.load().unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+        assert!(error.to_string().contains("/nonexistent/server.crt"));
+    }
+}
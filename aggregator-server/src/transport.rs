@@ -0,0 +1,322 @@
+//! gRPC 서버 연결 설정 (HTTP/2 keepalive, 요청 시간 제한)
+//!
+//! 노드가 응답 없이 사라져도 TCP 연결은 닫히지 않은 채 남을 수 있다. 서버는 `keepalive_interval`마다
+//! HTTP/2 PING을 보내고 `keepalive_timeout` 안에 응답이 없으면 연결을 닫아 자원을 돌려받는다.
+//! 요청 처리가 `request_timeout`을 넘으면 deadline_exceeded로 끝낸다 (클라이언트가 `grpc-timeout`으로
+//! 더 짧은 값을 보내면 그 값을 사용). StreamPrices는 응답 스트림을 바로 돌려주므로 스트림이 열려 있는
+//! 동안에는 시간 제한이 적용되지 않고 keepalive만 적용된다.
+//!
+//! tonic의 서버 시간 제한은 만료되면 cancelled를 돌려주므로, [`DeadlineLayer`]가 그보다 먼저 같은
+//! deadline으로 deadline_exceeded를 돌려준다. 처리기는 별도 태스크에서 돌므로 deadline이 지나도
+//! 중간에 버려지지 않는다. 가격 저장과 WAL 기록 사이에서 멈춘 요청이 저장만 되고 기록은 빠지는
+//! 일이 없고, 늦게 끝난 처리기의 응답만 버려진다.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tonic::body::BoxBody;
+use tonic::codegen::http;
+use tonic::transport::Server;
+use tonic::Status;
+use tower::{Layer, Service};
+
+/// 클라이언트가 보내는 요청 시간 제한 헤더
+pub const GRPC_TIMEOUT_HEADER: &str = "grpc-timeout";
+
+/// gRPC 서버 연결 설정
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransportConfig {
+    /// HTTP/2 PING 주기
+    pub keepalive_interval: Duration,
+    /// PING 응답을 기다리는 시간 (넘으면 연결을 닫음)
+    pub keepalive_timeout: Duration,
+    /// 요청 하나의 최대 처리 시간
+    pub request_timeout: Duration,
+}
+
+impl Default for TransportConfig {
+    fn default() -> Self {
+        Self {
+            keepalive_interval: Duration::from_secs(30),
+            keepalive_timeout: Duration::from_secs(10),
+            request_timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+impl TransportConfig {
+    /// 이 설정을 적용한 서버 빌더
+    pub fn server(&self) -> Server {
+        Server::builder()
+            .http2_keepalive_interval(Some(self.keepalive_interval))
+            .http2_keepalive_timeout(Some(self.keepalive_timeout))
+            .timeout(self.request_timeout)
+    }
+
+    /// 요청 deadline을 적용하는 tower 레이어
+    pub fn deadline_layer(&self) -> DeadlineLayer {
+        DeadlineLayer {
+            server_timeout: self.request_timeout,
+        }
+    }
+}
+
+/// `grpc-timeout` 헤더 해석 (최대 8자리 정수 + 단위 H, M, S, m, u, n)
+pub fn client_timeout(headers: &http::HeaderMap) -> Option<Duration> {
+    let value = headers.get(GRPC_TIMEOUT_HEADER)?.to_str().ok()?;
+    let (digits, unit) = value.split_at(value.len().checked_sub(1)?);
+    if digits.is_empty() || digits.len() > 8 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let amount: u64 = digits.parse().ok()?;
+    match unit {
+        "H" => Some(Duration::from_secs(amount * 60 * 60)),
+        "M" => Some(Duration::from_secs(amount * 60)),
+        "S" => Some(Duration::from_secs(amount)),
+        "m" => Some(Duration::from_millis(amount)),
+        "u" => Some(Duration::from_micros(amount)),
+        "n" => Some(Duration::from_nanos(amount)),
+        _ => None,
+    }
+}
+
+/// 클라이언트 deadline과 서버 시간 제한 중 짧은 쪽이 지나면 deadline_exceeded로 응답하는 레이어
+///
+/// 타이머를 `call`에서 바로 시작하므로 바깥의 tonic 타이머보다 먼저 또는 같이 만료된다.
+/// 만료되어도 처리기 태스크는 끝까지 실행된다.
+#[derive(Debug, Clone, Copy)]
+pub struct DeadlineLayer {
+    server_timeout: Duration,
+}
+
+impl<S> Layer<S> for DeadlineLayer {
+    type Service = Deadline<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Deadline {
+            inner,
+            server_timeout: self.server_timeout,
+        }
+    }
+}
+
+/// `DeadlineLayer`가 감싼 서비스
+#[derive(Debug, Clone)]
+pub struct Deadline<S> {
+    inner: S,
+    server_timeout: Duration,
+}
+
+impl<S, B> Service<http::Request<B>> for Deadline<S>
+where
+    S: Service<http::Request<B>, Response = http::Response<BoxBody>>,
+    S::Future: Send + 'static,
+    S::Error: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: http::Request<B>) -> Self::Future {
+        let timeout = client_timeout(request.headers()).map_or(self.server_timeout, |client| {
+            client.min(self.server_timeout)
+        });
+        let deadline = tokio::time::sleep(timeout);
+        // 상태 변경 도중 future가 버려지지 않도록 처리기를 별도 태스크로 실행
+        let handler = tokio::spawn(self.inner.call(request));
+        Box::pin(async move {
+            tokio::select! {
+                biased;
+                joined = handler => match joined {
+                    Ok(response) => response,
+                    Err(err) if err.is_panic() => std::panic::resume_unwind(err.into_panic()),
+                    Err(_) => Ok(Status::cancelled("request handler was cancelled").into_http()),
+                },
+                _ = deadline => Ok(Status::deadline_exceeded(format!(
+                    "request exceeded its {:?} deadline",
+                    timeout
+                ))
+                .into_http()),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oracle::oracle_service_client::OracleServiceClient;
+    use crate::oracle::oracle_service_server::OracleServiceServer;
+    use crate::rest::tests::price_request;
+    use crate::wal::{self, WalBackpressure, WalDecision};
+    use crate::AggregatorServiceImpl;
+    use prost::Message;
+    use std::net::SocketAddr;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpStream;
+    use tokio::sync::mpsc::error::TryRecvError;
+    use tokio::time::Instant;
+    use tokio_stream::wrappers::TcpListenerStream;
+    use tonic::Code;
+
+    async fn serve(mut server: Server) -> SocketAddr {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(
+            server
+                .add_service(OracleServiceServer::new(AggregatorServiceImpl::new()))
+                .serve_with_incoming(TcpListenerStream::new(listener)),
+        );
+        addr
+    }
+
+    // HTTP/2 연결을 연 뒤 PING에 응답하지 않는 클라이언트 (서버가 연결을 닫을 때까지 읽기만 함)
+    async fn stalled_connection(addr: SocketAddr, wait: Duration) -> Option<Duration> {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n")
+            .await
+            .unwrap();
+        // 빈 SETTINGS 프레임 (길이 0, 타입 4, 플래그 0, 스트림 0)
+        stream
+            .write_all(&[0, 0, 0, 4, 0, 0, 0, 0, 0])
+            .await
+            .unwrap();
+
+        let started = Instant::now();
+        let mut buf = [0u8; 1024];
+        let closed = tokio::time::timeout(wait, async {
+            loop {
+                match stream.read(&mut buf).await {
+                    Ok(0) | Err(_) => break,
+                    Ok(_) => {}
+                }
+            }
+        })
+        .await;
+        closed.ok().map(|()| started.elapsed())
+    }
+
+    #[tokio::test]
+    async fn test_stalled_connection_is_reaped_after_keepalive_timeout() {
+        let config = TransportConfig {
+            keepalive_interval: Duration::from_millis(500),
+            keepalive_timeout: Duration::from_millis(500),
+            ..TransportConfig::default()
+        };
+        let reaped = serve(config.server()).await;
+        // keepalive가 없으면 같은 연결이 계속 열려 있음
+        let unbounded = serve(Server::builder()).await;
+
+        let wait = Duration::from_secs(3);
+        let (reaped, unbounded) = tokio::join!(
+            stalled_connection(reaped, wait),
+            stalled_connection(unbounded, wait)
+        );
+        let elapsed = reaped.expect("stalled connection was not closed");
+        assert!(elapsed >= config.keepalive_interval, "{:?}", elapsed);
+        assert!(unbounded.is_none(), "{:?}", unbounded);
+    }
+
+    #[test]
+    fn test_client_timeout_parses_grpc_timeout_header() {
+        let timeout = |value: &str| {
+            let mut headers = http::HeaderMap::new();
+            headers.insert(GRPC_TIMEOUT_HEADER, value.parse().unwrap());
+            client_timeout(&headers)
+        };
+
+        assert_eq!(timeout("2H"), Some(Duration::from_secs(7_200)));
+        assert_eq!(timeout("3M"), Some(Duration::from_secs(180)));
+        assert_eq!(timeout("5S"), Some(Duration::from_secs(5)));
+        assert_eq!(timeout("250m"), Some(Duration::from_millis(250)));
+        assert_eq!(timeout("7u"), Some(Duration::from_micros(7)));
+        assert_eq!(timeout("99999999n"), Some(Duration::from_nanos(99_999_999)));
+        for invalid in ["", "S", "100", "123456789S", "1.5S", "-1S", "10s"] {
+            assert_eq!(timeout(invalid), None, "{:?}", invalid);
+        }
+        assert_eq!(client_timeout(&http::HeaderMap::new()), None);
+    }
+
+    #[tokio::test]
+    async fn test_client_deadline_aborts_slow_request() {
+        // 비우지 않는 WAL 채널 (한 건이 차면 다음 제출은 기록을 기다리며 멈춤)
+        let (sender, mut records) = wal::channel(1, WalBackpressure::Block);
+        let service = AggregatorServiceImpl::new().with_wal(sender);
+        let config = TransportConfig::default();
+        let state = service.state.clone();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(
+            config
+                .server()
+                .layer(config.deadline_layer())
+                .add_service(OracleServiceServer::new(service))
+                .serve_with_incoming(TcpListenerStream::new(listener)),
+        );
+        let mut client = OracleServiceClient::connect(format!("http://{}", addr))
+            .await
+            .unwrap();
+        client
+            .submit_price(price_request(70_000.0, "node-a", "BTC/USD"))
+            .await
+            .unwrap();
+
+        // tonic 클라이언트는 같은 deadline에 스스로 포기하므로 서버 응답을 보려고 HTTP/2 요청을 직접 보냄
+        let payload = price_request(70_010.0, "node-b", "BTC/USD").encode_to_vec();
+        let mut body = vec![0];
+        body.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        body.extend_from_slice(&payload);
+        let started = Instant::now();
+        let response = reqwest::Client::builder()
+            .http2_prior_knowledge()
+            .build()
+            .unwrap()
+            .post(format!("http://{}/oracle.OracleService/SubmitPrice", addr))
+            .header("content-type", "application/grpc")
+            .header("te", "trailers")
+            .header(GRPC_TIMEOUT_HEADER, "200m")
+            .body(body)
+            .send()
+            .await
+            .unwrap();
+        let header = |name: &str| {
+            response
+                .headers()
+                .get(name)
+                .map(|value| value.to_str().unwrap().to_string())
+        };
+        let message = header("grpc-message");
+        assert_eq!(
+            header("grpc-status").and_then(|code| code.parse().ok()),
+            Some(Code::DeadlineExceeded as i32),
+            "{:?}",
+            message
+        );
+        assert!(
+            started.elapsed() < config.request_timeout,
+            "{:?}",
+            started.elapsed()
+        );
+
+        // 응답만 버려지고 멈춰 있던 처리기는 WAL에 자리가 나면 기록을 마침
+        assert_eq!(records.try_recv().unwrap().request.node_id, "node-a");
+        let record = tokio::time::timeout(Duration::from_secs(1), records.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(record.request.node_id, "node-b");
+        assert!(matches!(record.decision, WalDecision::Accepted));
+        assert_eq!(records.try_recv().unwrap_err(), TryRecvError::Empty);
+
+        // 버퍼와 WAL이 같은 제출을 가짐
+        let state = state.read().await;
+        let stored: Vec<&str> = state.prices.entries().map(|e| &*e.node_id).collect();
+        assert_eq!(stored, ["node-a", "node-b"]);
+    }
+}
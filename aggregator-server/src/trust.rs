@@ -0,0 +1,126 @@
+//! 신뢰 가중치: 노드 평판 × 거래량 × 최신성을 하나의 가중치로 합성 (신뢰 가중 중간값에서 사용)
+//!
+//! 각 항목은 계수로 세기를 조절하며, 기본 계수에서는 모든 항목이 1이 되어 일반 중간값과 같다.
+
+use crate::PriceEntry;
+
+/// 합성 가중치 계수
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TrustCoefficients {
+    /// 평판 지수: 가중치에 `reputation^reputation_exponent`를 곱함 (0이면 평판 무시)
+    pub reputation_exponent: f64,
+    /// 거래량 지수: 가중치에 `(거래량 / 윈도우 내 거래량 중간값)^volume_exponent`를 곱함 (0이면 무시)
+    ///
+    /// 거래량을 보고하지 않은 가격은 중간값 거래량으로 본다.
+    pub volume_exponent: f64,
+    /// 최신성 반감기 (초): 가격이 이만큼 오래될 때마다 가중치가 절반 (None이면 무시)
+    pub recency_half_life_secs: Option<u64>,
+}
+
+impl TrustCoefficients {
+    /// 계수가 유효한지 (지수는 유한하고 0 이상, 반감기는 0보다 큼)
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, exponent) in [
+            ("reputation_exponent", self.reputation_exponent),
+            ("volume_exponent", self.volume_exponent),
+        ] {
+            if !exponent.is_finite() || exponent < 0.0 {
+                return Err(format!("{} must be a non-negative number", name));
+            }
+        }
+        if self.recency_half_life_secs == Some(0) {
+            return Err("recency_half_life_secs must be positive".to_string());
+        }
+        Ok(())
+    }
+
+    /// 평판 항목
+    pub fn reputation_factor(&self, reputation: f64) -> f64 {
+        if self.reputation_exponent == 0.0 {
+            return 1.0;
+        }
+        reputation.max(0.0).powf(self.reputation_exponent)
+    }
+
+    /// 거래량 항목 (`reference`는 윈도우 내 보고된 거래량의 중간값)
+    pub fn volume_factor(&self, volume: Option<u64>, reference: Option<u64>) -> f64 {
+        match (volume, reference) {
+            (Some(volume), Some(reference)) if self.volume_exponent != 0.0 && reference > 0 => {
+                (volume as f64 / reference as f64).powf(self.volume_exponent)
+            }
+            _ => 1.0,
+        }
+    }
+
+    /// 최신성 항목
+    pub fn recency_factor(&self, age_secs: u64) -> f64 {
+        match self.recency_half_life_secs {
+            Some(half_life) if half_life > 0 => 0.5_f64.powf(age_secs as f64 / half_life as f64),
+            _ => 1.0,
+        }
+    }
+}
+
+/// 가격 데이터의 거래량 중간값 (보고된 것이 없으면 None, 짝수 개면 가운데 두 값의 평균)
+pub fn volume_reference<'a>(entries: impl IntoIterator<Item = &'a PriceEntry>) -> Option<u64> {
+    let mut volumes: Vec<u64> = entries
+        .into_iter()
+        .filter_map(|entry| entry.volume)
+        .collect();
+    if volumes.is_empty() {
+        return None;
+    }
+    volumes.sort_unstable();
+    let mid = volumes.len() / 2;
+    if volumes.len().is_multiple_of(2) {
+        Some(volumes[mid - 1].midpoint(volumes[mid]))
+    } else {
+        Some(volumes[mid])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_coefficients_are_neutral() {
+        let coefficients = TrustCoefficients::default();
+        assert!(coefficients.validate().is_ok());
+        assert_eq!(coefficients.reputation_factor(0.0), 1.0);
+        assert_eq!(coefficients.reputation_factor(0.3), 1.0);
+        assert_eq!(coefficients.volume_factor(Some(10), Some(1_000)), 1.0);
+        assert_eq!(coefficients.recency_factor(3_600), 1.0);
+    }
+
+    #[test]
+    fn test_factors_follow_coefficients() {
+        let coefficients = TrustCoefficients {
+            reputation_exponent: 2.0,
+            volume_exponent: 1.0,
+            recency_half_life_secs: Some(60),
+        };
+        assert!((coefficients.reputation_factor(0.5) - 0.25).abs() < 1e-12);
+        assert!((coefficients.volume_factor(Some(300), Some(100)) - 3.0).abs() < 1e-12);
+        // 거래량을 보고하지 않으면 중간값으로 취급
+        assert_eq!(coefficients.volume_factor(None, Some(100)), 1.0);
+        assert!((coefficients.recency_factor(120) - 0.25).abs() < 1e-12);
+
+        for invalid in [
+            TrustCoefficients {
+                reputation_exponent: -1.0,
+                ..TrustCoefficients::default()
+            },
+            TrustCoefficients {
+                volume_exponent: f64::NAN,
+                ..TrustCoefficients::default()
+            },
+            TrustCoefficients {
+                recency_half_life_secs: Some(0),
+                ..TrustCoefficients::default()
+            },
+        ] {
+            assert!(invalid.validate().is_err());
+        }
+    }
+}
@@ -0,0 +1,777 @@
+//! 제출 기록용 append-only WAL (분쟁 대응 감사 + 장애 후 상태 복구)
+//!
+//! 레코드는 한 줄에 하나씩 JSON으로 기록되며(JSONL), 세그먼트 파일이 설정 크기를 넘으면
+//! 다음 번호의 파일로 넘어간다. `submit_price`는 bounded 채널로 레코드를 넘기고
+//! 실제 파일 쓰기는 별도 blocking 태스크가 담당한다.
+//!
+//! 보존 기간을 설정하면 시작할 때와 그 뒤 `prune_interval`마다 (제출이 없어도) 마지막 레코드가 보존
+//! 기간보다 오래된 세그먼트를 통째로 지운다. 정리는 쓰기 태스크와 따로 돌아 기록을 막지 않으며,
+//! 세그먼트의 마지막 레코드는 파일 끝부분만 읽어 찾는다. 기록 중인 세그먼트는 지우지 않는다.
+
+use oracle_vm_common::Price;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::mpsc::{self, error::TrySendError};
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::oracle::PriceRequest;
+use crate::RejectionReason;
+
+const SEGMENT_PREFIX: &str = "wal-";
+const SEGMENT_SUFFIX: &str = ".jsonl";
+/// 기본 보존 기간 확인 주기
+pub const DEFAULT_PRUNE_INTERVAL: Duration = Duration::from_secs(600);
+// 마지막 레코드를 찾을 때 처음 읽는 세그먼트 끝부분 크기 (레코드가 없으면 두 배씩 늘림)
+const TAIL_READ_BYTES: u64 = 64 * 1024;
+
+/// 채널이 가득 찼을 때의 동작
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WalBackpressure {
+    /// 자리가 날 때까지 `submit_price`를 대기 (레코드 유실 없음)
+    #[default]
+    Block,
+    /// 레코드를 버리고 카운터 증가 (제출 지연 없음)
+    Drop,
+}
+
+/// WAL 설정
+#[derive(Debug, Clone)]
+pub struct WalConfig {
+    /// 세그먼트 파일 디렉터리
+    pub dir: PathBuf,
+    /// 세그먼트 최대 크기 (바이트), 넘으면 새 세그먼트로 교체
+    pub max_segment_bytes: u64,
+    /// 기록 대기 채널 크기
+    pub channel_capacity: usize,
+    pub backpressure: WalBackpressure,
+    /// 레코드 보존 기간 (None이면 지우지 않음)
+    pub retention: Option<Duration>,
+    /// 보존 기간이 지난 세그먼트를 찾는 주기
+    pub prune_interval: Duration,
+}
+
+impl Default for WalConfig {
+    fn default() -> Self {
+        Self {
+            dir: PathBuf::from("wal"),
+            max_segment_bytes: 64 * 1024 * 1024,
+            channel_capacity: 1_024,
+            backpressure: WalBackpressure::Block,
+            retention: None,
+            prune_interval: DEFAULT_PRUNE_INTERVAL,
+        }
+    }
+}
+
+/// 요청 원문 (PriceRequest와 같은 필드)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WalRequest {
+    pub price: f64,
+    pub timestamp: u64,
+    pub source: String,
+    pub node_id: String,
+    pub signature: Option<String>,
+    pub price_scaled: Option<u64>,
+    pub price_decimals: Option<u32>,
+    #[serde(default)]
+    pub symbol: Option<String>,
+    #[serde(default)]
+    pub historical: bool,
+    #[serde(default)]
+    pub volume: Option<u64>,
+}
+
+impl From<&PriceRequest> for WalRequest {
+    fn from(request: &PriceRequest) -> Self {
+        Self {
+            price: request.price,
+            timestamp: request.timestamp,
+            source: request.source.clone(),
+            node_id: request.node_id.clone(),
+            signature: request.signature.clone(),
+            price_scaled: request.price_scaled,
+            price_decimals: request.price_decimals,
+            symbol: request.symbol.clone(),
+            historical: request.historical,
+            volume: request.volume,
+        }
+    }
+}
+
+impl From<WalRequest> for PriceRequest {
+    fn from(request: WalRequest) -> Self {
+        Self {
+            price: request.price,
+            timestamp: request.timestamp,
+            source: request.source,
+            node_id: request.node_id,
+            signature: request.signature,
+            price_scaled: request.price_scaled,
+            price_decimals: request.price_decimals,
+            symbol: request.symbol,
+            historical: request.historical,
+            volume: request.volume,
+        }
+    }
+}
+
+/// 제출 처리 결과
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum WalDecision {
+    /// 저장됨
+    Accepted,
+    /// 이미 저장된 동일 제출 (저장하지 않음)
+    Duplicate,
+    /// 재전송된 과거 관측값 (기록만 하며 복구 시 재적용하지 않음)
+    Historical,
+    /// 거부됨 (`code`는 지표 레이블로 쓰는 고정 사유, 사유 코드가 생기기 전의 기록에는 없음)
+    Rejected {
+        reason: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        code: Option<RejectionReason>,
+    },
+}
+
+impl WalDecision {
+    /// 지표 레이블과 span에 쓰는 결과 이름
+    pub fn name(&self) -> &'static str {
+        match self {
+            WalDecision::Accepted => "accepted",
+            WalDecision::Duplicate => "duplicate",
+            WalDecision::Historical => "historical",
+            WalDecision::Rejected { .. } => "rejected",
+        }
+    }
+}
+
+/// 제출 한 건의 기록
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WalRecord {
+    /// 상태에 반영된 순서 (저장된 제출만 가짐, 복구 시 이 순서로 재적용)
+    pub seq: Option<u64>,
+    /// 서버 수신 시각
+    pub received_at: u64,
+    pub request: WalRequest,
+    /// 시계 오차로 `request.timestamp`가 서버 시각으로 보정된 경우 노드가 보낸 원래 값
+    /// (복구한 상태도 보정 전 값으로 재전송을 알아보도록 함)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clamped_from: Option<u64>,
+    pub decision: WalDecision,
+    /// 응답에 포함된 집계 가격
+    pub aggregate: Option<Price>,
+    /// 집계에 참여한 노드 수
+    pub contributing_nodes: usize,
+}
+
+/// `submit_price`에서 WAL 태스크로 레코드를 넘기는 핸들
+#[derive(Debug, Clone)]
+pub struct WalSender {
+    tx: mpsc::Sender<WalRecord>,
+    backpressure: WalBackpressure,
+    dropped: Arc<AtomicU64>,
+}
+
+impl WalSender {
+    /// 레코드 전달 (정책에 따라 대기하거나 버림)
+    pub async fn send(&self, record: WalRecord) {
+        match self.backpressure {
+            WalBackpressure::Block => {
+                if self.tx.send(record).await.is_err() {
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            WalBackpressure::Drop => match self.tx.try_send(record) {
+                Ok(()) => {}
+                Err(TrySendError::Full(_)) | Err(TrySendError::Closed(_)) => {
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                }
+            },
+        }
+    }
+
+    /// 기록하지 못하고 버린 레코드 수 (누적)
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+/// 쓰기 태스크 없이 채널만 생성 (수신 측은 직접 소비)
+pub fn channel(
+    capacity: usize,
+    backpressure: WalBackpressure,
+) -> (WalSender, mpsc::Receiver<WalRecord>) {
+    let (tx, rx) = mpsc::channel(capacity.max(1));
+    (
+        WalSender {
+            tx,
+            backpressure,
+            dropped: Arc::new(AtomicU64::new(0)),
+        },
+        rx,
+    )
+}
+
+/// WAL 쓰기 태스크 시작
+///
+/// 모든 `WalSender`가 drop되면 남은 레코드를 기록한 뒤 태스크가 종료된다. 보존 기간이 있으면
+/// 반환하기 전에 한 번 정리하고, 쓰기 태스크가 끝날 때까지 `prune_interval`마다 따로 정리한다.
+pub fn spawn_writer(config: WalConfig) -> io::Result<(WalSender, JoinHandle<io::Result<()>>)> {
+    let mut writer = SegmentWriter::open(&config.dir, config.max_segment_bytes)?;
+    let (sender, mut rx) = channel(config.channel_capacity, config.backpressure);
+
+    info!(
+        "📝 Writing WAL to {} (segment {})",
+        config.dir.display(),
+        writer.index
+    );
+
+    // 정리 태스크는 이 번호 이후의 세그먼트(기록 중인 세그먼트 포함)를 건드리지 않음
+    let active = writer.active.clone();
+    let (stop_pruning, stopped) = oneshot::channel::<()>();
+    let pruner = config.retention.map(|retention| {
+        let dir = config.dir.clone();
+        prune_expired(&dir, retention, active.load(Ordering::SeqCst));
+        tokio::spawn(run_pruner(
+            dir,
+            retention,
+            config.prune_interval,
+            active,
+            stopped,
+        ))
+    });
+
+    let writer_task = tokio::task::spawn_blocking(move || {
+        while let Some(record) = rx.blocking_recv() {
+            if let Err(e) = writer.append(&record) {
+                warn!("⚠️ WAL write failed: {}", e);
+                return Err(e);
+            }
+        }
+        Ok(())
+    });
+    let handle = tokio::spawn(async move {
+        let result = writer_task.await.map_err(io::Error::other)?;
+        drop(stop_pruning);
+        if let Some(pruner) = pruner {
+            let _ = pruner.await;
+        }
+        result
+    });
+
+    Ok((sender, handle))
+}
+
+// 쓰기 태스크가 끝날 때까지 `interval`마다 보존 기간이 지난 세그먼트 정리
+async fn run_pruner(
+    dir: PathBuf,
+    retention: Duration,
+    interval: Duration,
+    active: Arc<AtomicU64>,
+    mut stopped: oneshot::Receiver<()>,
+) {
+    let mut ticker = tokio::time::interval(interval.max(Duration::from_millis(1)));
+    ticker.tick().await; // 시작할 때의 정리는 이미 함
+    loop {
+        tokio::select! {
+            _ = &mut stopped => break,
+            _ = ticker.tick() => {
+                let dir = dir.clone();
+                let active = active.load(Ordering::SeqCst);
+                let _ = tokio::task::spawn_blocking(move || prune_expired(&dir, retention, active))
+                    .await;
+            }
+        }
+    }
+}
+
+// 보존 기간 정리 (실패해도 기록은 계속)
+fn prune_expired(dir: &Path, retention: Duration, active: u64) {
+    let cutoff = unix_now().saturating_sub(retention.as_secs());
+    match prune_segments(dir, cutoff, active) {
+        Ok(0) => {}
+        Ok(removed) => info!(
+            "🧹 Removed {} WAL segment(s) older than {:?}",
+            removed, retention
+        ),
+        Err(e) => warn!("⚠️ WAL pruning failed: {}", e),
+    }
+}
+
+// 현재 세그먼트에 이어 쓰고 크기를 넘으면 다음 세그먼트로 교체
+struct SegmentWriter {
+    dir: PathBuf,
+    max_segment_bytes: u64,
+    index: u64,
+    /// 기록 중인 세그먼트 번호 (정리 태스크와 공유)
+    active: Arc<AtomicU64>,
+    file: BufWriter<File>,
+    written: u64,
+}
+
+impl SegmentWriter {
+    fn open(dir: &Path, max_segment_bytes: u64) -> io::Result<Self> {
+        fs::create_dir_all(dir)?;
+
+        // 이전 실행의 마지막 세그먼트는 끝이 잘렸을 수 있으므로 항상 새 세그먼트에서 시작
+        let index = segment_paths(dir)?
+            .last()
+            .and_then(|path| segment_index(path))
+            .map_or(0, |index| index + 1);
+
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            max_segment_bytes,
+            index,
+            active: Arc::new(AtomicU64::new(index)),
+            file: create_segment(dir, index)?,
+            written: 0,
+        })
+    }
+
+    fn append(&mut self, record: &WalRecord) -> io::Result<()> {
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+
+        if self.written > 0 && self.written + line.len() as u64 > self.max_segment_bytes {
+            self.file.flush()?;
+            self.index += 1;
+            self.file = create_segment(&self.dir, self.index)?;
+            self.active.store(self.index, Ordering::SeqCst);
+            self.written = 0;
+        }
+
+        self.file.write_all(&line)?;
+        self.file.flush()?;
+        self.written += line.len() as u64;
+        Ok(())
+    }
+}
+
+fn create_segment(dir: &Path, index: u64) -> io::Result<BufWriter<File>> {
+    let path = dir.join(format!("{}{:08}{}", SEGMENT_PREFIX, index, SEGMENT_SUFFIX));
+    let file = OpenOptions::new()
+        .create_new(true)
+        .append(true)
+        .open(path)?;
+    Ok(BufWriter::new(file))
+}
+
+fn segment_index(path: &Path) -> Option<u64> {
+    path.file_name()?
+        .to_str()?
+        .strip_prefix(SEGMENT_PREFIX)?
+        .strip_suffix(SEGMENT_SUFFIX)?
+        .parse()
+        .ok()
+}
+
+/// 디렉터리의 세그먼트 파일 목록 (번호 순)
+pub fn segment_paths(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut segments: Vec<(u64, PathBuf)> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter_map(|path| segment_index(&path).map(|index| (index, path)))
+        .collect();
+    segments.sort_unstable_by_key(|(index, _)| *index);
+    Ok(segments.into_iter().map(|(_, path)| path).collect())
+}
+
+/// 마지막 레코드의 수신 시각이 `cutoff`(Unix 초)보다 이른 세그먼트 삭제, 지운 세그먼트 수 반환
+///
+/// 세그먼트는 기록 순서대로 번호가 붙으므로 오래된 것부터 확인하고 보존할 세그먼트를 만나면 멈춘다.
+/// `active` 이후 번호의 세그먼트(기록 중인 세그먼트 포함)와 레코드가 없는 세그먼트는 지우지 않는다.
+pub fn prune_segments(dir: &Path, cutoff: u64, active: u64) -> io::Result<usize> {
+    let mut removed = 0;
+    for path in segment_paths(dir)? {
+        if segment_index(&path).is_none_or(|index| index >= active) {
+            break;
+        }
+        match last_received_at(&path)? {
+            Some(received_at) if received_at < cutoff => {
+                fs::remove_file(&path)?;
+                removed += 1;
+            }
+            _ => break,
+        }
+    }
+    Ok(removed)
+}
+
+/// 가장 최근 세그먼트 번호 (기록 중일 수 있음, 세그먼트가 없으면 None)
+pub fn last_segment_index(dir: &Path) -> io::Result<Option<u64>> {
+    Ok(segment_paths(dir)?
+        .last()
+        .and_then(|path| segment_index(path)))
+}
+
+// 세그먼트에서 읽을 수 있는 마지막 레코드의 수신 시각 (잘린 줄은 건너뜀)
+//
+// 파일 끝부분만 읽고, 그 안에 온전한 레코드가 없을 때만 읽는 범위를 두 배씩 늘린다.
+fn last_received_at(path: &Path) -> io::Result<Option<u64>> {
+    let mut file = File::open(path)?;
+    let len = file.metadata()?.len();
+    let mut window = TAIL_READ_BYTES;
+    loop {
+        let start = len.saturating_sub(window);
+        file.seek(SeekFrom::Start(start))?;
+        let mut tail = Vec::with_capacity((len - start) as usize);
+        (&mut file).take(len - start).read_to_end(&mut tail)?;
+
+        // 중간에서 시작했으면 첫 줄은 앞부분이 잘린 줄
+        let mut lines = tail.split(|&byte| byte == b'\n');
+        if start > 0 {
+            lines.next();
+        }
+        let last = lines
+            .rev()
+            .find_map(|line| serde_json::from_slice::<WalRecord>(line).ok());
+        if let Some(record) = last {
+            return Ok(Some(record.received_at));
+        }
+        if start == 0 {
+            return Ok(None);
+        }
+        window = window.saturating_mul(2);
+    }
+}
+
+pub(crate) fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// 모든 세그먼트의 레코드를 기록 순서대로 읽기
+///
+/// 세그먼트 마지막 줄이 잘려 있으면 (쓰기 도중 장애) 그 줄만 무시한다.
+pub fn read_records(dir: &Path) -> io::Result<Vec<WalRecord>> {
+    let mut records = Vec::new();
+    for_each_record(dir, |record| {
+        records.push(record);
+        Ok(())
+    })?;
+    Ok(records)
+}
+
+/// 모든 세그먼트의 레코드를 기록 순서대로 하나씩 `f`에 넘김 (전체를 메모리에 올리지 않음)
+///
+/// `read_records`와 같이 세그먼트 마지막의 잘린 줄은 무시하고, `f`가 에러를 반환하면 중단한다.
+pub fn for_each_record(
+    dir: &Path,
+    mut f: impl FnMut(WalRecord) -> io::Result<()>,
+) -> io::Result<()> {
+    for path in segment_paths(dir)? {
+        // 해석하지 못한 줄은 뒤에 줄이 더 있을 때만 에러
+        let mut torn: Option<(usize, serde_json::Error)> = None;
+        for (i, line) in BufReader::new(File::open(&path)?).lines().enumerate() {
+            let line = line?;
+            if let Some((line_no, e)) = torn.take() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}:{}: {}", path.display(), line_no, e),
+                ));
+            }
+            match serde_json::from_str(&line) {
+                Ok(record) => f(record)?,
+                Err(e) => torn = Some((i + 1, e)),
+            }
+        }
+        if torn.is_some() {
+            warn!("⚠️ Ignoring torn WAL record at end of {}", path.display());
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn temp_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("aggregator-wal-{}", uuid::Uuid::new_v4()))
+    }
+
+    fn record(node_id: &str, seq: u64) -> WalRecord {
+        WalRecord {
+            seq: Some(seq),
+            received_at: 1_700_000_000 + seq,
+            request: WalRequest {
+                price: 70_000.12,
+                timestamp: 1_700_000_000 + seq,
+                source: "binance".to_string(),
+                node_id: node_id.to_string(),
+                signature: Some("deadbeef".to_string()),
+                price_scaled: Some(7_000_012),
+                price_decimals: Some(2),
+                symbol: Some("BTCUSDT".to_string()),
+                historical: false,
+                volume: None,
+            },
+            clamped_from: None,
+            decision: WalDecision::Accepted,
+            aggregate: Some(Price::from_cents(7_000_012)),
+            contributing_nodes: 1,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_writer_rotates_segments_and_reader_restores_order() {
+        let dir = temp_dir();
+        let (sender, handle) = spawn_writer(WalConfig {
+            dir: dir.clone(),
+            max_segment_bytes: 600,
+            ..WalConfig::default()
+        })
+        .unwrap();
+
+        let records: Vec<WalRecord> = (0..10).map(|i| record("node-a", i)).collect();
+        for record in records.clone() {
+            sender.send(record).await;
+        }
+        drop(sender);
+        handle.await.unwrap().unwrap();
+
+        assert!(segment_paths(&dir).unwrap().len() > 1);
+        assert_eq!(read_records(&dir).unwrap(), records);
+
+        // 재시작하면 기존 세그먼트 뒤에 새 세그먼트 생성
+        let segments = segment_paths(&dir).unwrap().len();
+        let (sender, handle) = spawn_writer(WalConfig {
+            dir: dir.clone(),
+            ..WalConfig::default()
+        })
+        .unwrap();
+        sender.send(record("node-b", 10)).await;
+        drop(sender);
+        handle.await.unwrap().unwrap();
+
+        assert_eq!(segment_paths(&dir).unwrap().len(), segments + 1);
+        assert_eq!(read_records(&dir).unwrap().len(), 11);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_prune_removes_only_segments_past_retention() {
+        let dir = temp_dir();
+        fs::create_dir_all(&dir).unwrap();
+        let segment = |index: u64, records: &[WalRecord]| {
+            let lines: String = records
+                .iter()
+                .map(|record| serde_json::to_string(record).unwrap() + "\n")
+                .collect();
+            fs::write(dir.join(format!("wal-{:08}.jsonl", index)), lines).unwrap();
+        };
+        // received_at = 1_700_000_000 + seq
+        segment(0, &[record("node-a", 0), record("node-a", 10)]);
+        segment(1, &[record("node-a", 20), record("node-a", 30)]);
+        segment(2, &[record("node-a", 40), record("node-a", 50)]);
+        segment(3, &[record("node-a", 60)]);
+
+        // 마지막 레코드가 기준보다 이른 세그먼트만 삭제 (기준과 겹치는 세그먼트는 유지)
+        assert_eq!(prune_segments(&dir, 1_700_000_045, 3).unwrap(), 2);
+        let remaining: Vec<Option<u64>> = read_records(&dir)
+            .unwrap()
+            .iter()
+            .map(|record| record.seq)
+            .collect();
+        assert_eq!(remaining, [Some(40), Some(50), Some(60)]);
+
+        // 기록 중인 세그먼트는 오래되어도 지우지 않음
+        assert_eq!(prune_segments(&dir, 1_800_000_000, 3).unwrap(), 1);
+        assert_eq!(segment_paths(&dir).unwrap().len(), 1);
+        assert_eq!(read_records(&dir).unwrap().len(), 1);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_writer_prunes_expired_segments() {
+        let dir = temp_dir();
+        fs::create_dir_all(&dir).unwrap();
+        let old = serde_json::to_string(&record("node-a", 0)).unwrap();
+        fs::write(dir.join("wal-00000000.jsonl"), old + "\n").unwrap();
+
+        let (sender, handle) = spawn_writer(WalConfig {
+            dir: dir.clone(),
+            retention: Some(Duration::from_secs(3_600)),
+            ..WalConfig::default()
+        })
+        .unwrap();
+        let recent = WalRecord {
+            received_at: unix_now(),
+            ..record("node-b", 1)
+        };
+        sender.send(recent.clone()).await;
+        drop(sender);
+        handle.await.unwrap().unwrap();
+
+        assert_eq!(read_records(&dir).unwrap(), vec![recent]);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_idle_writer_prunes_on_its_own_timer() {
+        let dir = temp_dir();
+        fs::create_dir_all(&dir).unwrap();
+        let recent = WalRecord {
+            received_at: unix_now(),
+            ..record("node-a", 0)
+        };
+        let segment = dir.join("wal-00000000.jsonl");
+        fs::write(&segment, serde_json::to_string(&recent).unwrap() + "\n").unwrap();
+
+        let (sender, handle) = spawn_writer(WalConfig {
+            dir: dir.clone(),
+            retention: Some(Duration::from_secs(3_600)),
+            prune_interval: Duration::from_millis(20),
+            ..WalConfig::default()
+        })
+        .unwrap();
+        assert!(segment.exists());
+
+        // 제출 없이 시간이 지나 세그먼트가 보존 기간을 넘김
+        let old = serde_json::to_string(&record("node-a", 0)).unwrap();
+        fs::write(&segment, old + "\n").unwrap();
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while segment.exists() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        drop(sender);
+        handle.await.unwrap().unwrap();
+        assert_eq!(segment_paths(&dir).unwrap().len(), 1);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_last_received_at_reads_only_the_tail() {
+        let dir = temp_dir();
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("wal-00000000.jsonl");
+        // 끝부분 읽기 범위보다 큰 세그먼트, 마지막 줄은 잘림
+        let mut lines: String = (0..2_000)
+            .map(|seq| serde_json::to_string(&record("node-a", seq)).unwrap() + "\n")
+            .collect();
+        assert!(lines.len() as u64 > TAIL_READ_BYTES);
+        lines.push_str("{\"seq\":2000,\"rece");
+        fs::write(&path, &lines).unwrap();
+        assert_eq!(last_received_at(&path).unwrap(), Some(1_700_001_999));
+
+        // 끝부분에 온전한 레코드가 없으면 범위를 늘려 찾음
+        let padding = "x".repeat(TAIL_READ_BYTES as usize * 3);
+        let single = serde_json::to_string(&record("node-a", 7)).unwrap() + "\n" + &padding;
+        fs::write(&path, single).unwrap();
+        assert_eq!(last_received_at(&path).unwrap(), Some(1_700_000_007));
+
+        fs::write(&path, "").unwrap();
+        assert_eq!(last_received_at(&path).unwrap(), None);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_reader_ignores_torn_last_line_only() {
+        let dir = temp_dir();
+        fs::create_dir_all(&dir).unwrap();
+        let line = serde_json::to_string(&record("node-a", 0)).unwrap();
+
+        fs::write(
+            dir.join("wal-00000000.jsonl"),
+            format!("{}\n{{\"seq\":1,\"rece", line),
+        )
+        .unwrap();
+        assert_eq!(read_records(&dir).unwrap(), vec![record("node-a", 0)]);
+
+        // 중간 줄이 깨진 경우는 오류
+        fs::write(
+            dir.join("wal-00000001.jsonl"),
+            format!("not json\n{}\n", line),
+        )
+        .unwrap();
+        assert_eq!(
+            read_records(&dir).unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_rejection_code_round_trips_and_is_optional() {
+        let decision = WalDecision::Rejected {
+            reason: "Node is not in the allowlist".to_string(),
+            code: Some(RejectionReason::NodeNotAllowed),
+        };
+        let line = serde_json::to_string(&decision).unwrap();
+        assert_eq!(
+            line,
+            r#"{"status":"rejected","reason":"Node is not in the allowlist","code":"node_not_allowed"}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<WalDecision>(&line).unwrap(),
+            decision
+        );
+
+        // 사유 코드가 생기기 전에 기록된 거부
+        assert_eq!(
+            serde_json::from_str::<WalDecision>(r#"{"status":"rejected","reason":"outlier"}"#)
+                .unwrap(),
+            WalDecision::Rejected {
+                reason: "outlier".to_string(),
+                code: None,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_drop_policy_counts_dropped_records() {
+        let (sender, mut rx) = channel(2, WalBackpressure::Drop);
+
+        for i in 0..5 {
+            sender.send(record("node-a", i)).await;
+        }
+
+        assert_eq!(sender.dropped(), 3);
+        assert_eq!(rx.recv().await.unwrap().seq, Some(0));
+        assert_eq!(rx.recv().await.unwrap().seq, Some(1));
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_block_policy_waits_for_capacity() {
+        let (sender, mut rx) = channel(1, WalBackpressure::Block);
+
+        sender.send(record("node-a", 0)).await;
+        // 채널이 가득 차면 대기
+        assert!(
+            tokio::time::timeout(Duration::from_millis(50), sender.send(record("node-a", 1)))
+                .await
+                .is_err()
+        );
+
+        let blocked = {
+            let sender = sender.clone();
+            tokio::spawn(async move { sender.send(record("node-a", 2)).await })
+        };
+        assert_eq!(rx.recv().await.unwrap().seq, Some(0));
+        blocked.await.unwrap();
+
+        assert_eq!(rx.recv().await.unwrap().seq, Some(2));
+        assert_eq!(sender.dropped(), 0);
+    }
+}
@@ -0,0 +1,713 @@
+//! 게시된 집계와 가격 급변을 HTTP 웹훅으로 알리는 디스패처
+//!
+//! 디스패처 태스크가 집계 게시 채널을 구독해 필터에 맞는 이벤트를 JSON 본문으로 만들고, 엔드포인트마다
+//! 따로 있는 bounded 채널에 `try_send`로 넘긴다 (가득 차면 버리고 `dropped`로 셈). 서명 비밀 키가
+//! 있으면 본문의 HMAC-SHA256을 `X-Oracle-Signature: sha256=<hex>` 헤더로 붙인다.
+//!
+//! 엔드포인트 태스크는 POST가 5xx, 429, 연결 오류로 실패하면 백오프하며 `max_attempts`까지 재시도한다.
+//! 이벤트를 연속으로 `breaker_threshold`개 전달하지 못하면 회로를 열고 `breaker_cooldown` 동안은 보내지
+//! 않고 버린다 (`short_circuited`). 쿨다운이 지나면 다음 이벤트 하나를 한 번만 시험 삼아 보내고 성공하면
+//! 회로를 닫는다. 그래서 죽은 엔드포인트가 큐를 막거나 다른 엔드포인트를 늦추지 않는다. 전송 결과는
+//! `webhook_deliveries_total{endpoint, outcome}` 지표로 센다.
+//!
+//! - `aggregate`: 게시된 집계마다
+//! - `deviation`: 집계 가격이 직전 집계 가격에서 `deviation_bps` 이상 움직였을 때
+
+use anyhow::{Context, Result};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::sync::mpsc::{self, error::TrySendError};
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::metrics::AggregatorMetrics;
+use crate::sink::AggregateMessage;
+use crate::snapshot::{AggregateSnapshot, AggregateUpdate};
+
+/// 본문 서명 헤더 (`sha256=<hex HMAC-SHA256>`)
+pub const SIGNATURE_HEADER: &str = "X-Oracle-Signature";
+/// 이벤트 종류 헤더
+pub const EVENT_HEADER: &str = "X-Oracle-Event";
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// 웹훅 이벤트 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEvent {
+    /// 게시된 집계
+    Aggregate,
+    /// 집계 가격 급변
+    Deviation,
+}
+
+impl WebhookEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Aggregate => "aggregate",
+            Self::Deviation => "deviation",
+        }
+    }
+}
+
+impl fmt::Display for WebhookEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for WebhookEvent {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "aggregate" => Ok(Self::Aggregate),
+            "deviation" => Ok(Self::Deviation),
+            _ => Err(format!(
+                "unknown webhook event '{}' (expected aggregate or deviation)",
+                value
+            )),
+        }
+    }
+}
+
+/// 웹훅 디스패처 설정
+#[derive(Debug, Clone, PartialEq)]
+pub struct WebhookConfig {
+    /// POST를 받을 엔드포인트 URL
+    pub urls: Vec<String>,
+    /// 본문 서명 비밀 키 (없으면 서명하지 않음)
+    pub secret: Option<String>,
+    /// 보낼 이벤트 종류
+    pub events: Vec<WebhookEvent>,
+    /// `deviation` 이벤트를 보내는 직전 집계 대비 가격 변화 (bp)
+    pub deviation_bps: u32,
+    /// 엔드포인트별 전송 대기 채널 크기 (가득 차면 이벤트를 버림)
+    pub channel_capacity: usize,
+    /// 이벤트 하나의 최대 전송 시도 횟수
+    pub max_attempts: u32,
+    /// 첫 재시도 전 대기 시간 (재시도마다 두 배, `max_backoff`까지)
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    /// 요청 하나의 시간 제한
+    pub request_timeout: Duration,
+    /// 회로를 여는 연속 전달 실패 이벤트 수
+    pub breaker_threshold: u32,
+    /// 회로를 연 뒤 다시 시험하기까지 기다리는 시간
+    pub breaker_cooldown: Duration,
+    /// 종료할 때 미전송 이벤트를 기다리는 시간
+    pub drain_timeout: Duration,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            urls: Vec::new(),
+            secret: None,
+            events: vec![WebhookEvent::Aggregate, WebhookEvent::Deviation],
+            deviation_bps: 100,
+            channel_capacity: 1_000,
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(10),
+            request_timeout: Duration::from_secs(5),
+            breaker_threshold: 5,
+            breaker_cooldown: Duration::from_secs(60),
+            drain_timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+/// 웹훅 본문
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookPayload {
+    pub event: WebhookEvent,
+    pub aggregate: AggregateMessage,
+    /// 직전 집계 가격 (deviation 이벤트만)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_price: Option<f64>,
+    /// 직전 집계 가격 대비 변화 (bp, deviation 이벤트만)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deviation_bps: Option<f64>,
+}
+
+/// `body`의 서명 헤더 값 (`sha256=<hex>`)
+pub fn sign(secret: &[u8], body: &[u8]) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// 서명 헤더 값이 `body`의 서명과 같은지 (상수 시간 비교)
+pub fn verify(secret: &[u8], body: &[u8], signature: &str) -> bool {
+    let Some(digest) = signature
+        .strip_prefix("sha256=")
+        .and_then(|digest| hex::decode(digest).ok())
+    else {
+        return false;
+    };
+    let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    mac.verify_slice(&digest).is_ok()
+}
+
+/// 디스패처 실행
+///
+/// `updates`는 집계 게시 구독(`AggregatorServiceImpl::subscribe`)이며, 종료 알림을 받으면 이미 받은
+/// 이벤트를 `drain_timeout`까지 보낸 뒤 태스크가 끝난다.
+pub fn spawn(
+    config: WebhookConfig,
+    metrics: AggregatorMetrics,
+    updates: broadcast::Receiver<AggregateUpdate>,
+) -> Result<JoinHandle<()>> {
+    let client = reqwest::Client::builder()
+        .timeout(config.request_timeout)
+        .build()
+        .context("Failed to build webhook HTTP client")?;
+
+    let mut endpoints = Vec::with_capacity(config.urls.len());
+    for url in &config.urls {
+        let url =
+            reqwest::Url::parse(url).with_context(|| format!("Invalid webhook URL '{}'", url))?;
+        let label = endpoint_label(&url);
+        let (tx, rx) = mpsc::channel(config.channel_capacity.max(1));
+        let task = EndpointTask {
+            client: client.clone(),
+            url,
+            label: label.clone(),
+            config: config.clone(),
+            metrics: metrics.clone(),
+            consecutive_failures: 0,
+            opened_at: None,
+        };
+        info!("🪝 Sending {:?} webhooks to {}", config.events, label);
+        endpoints.push(Endpoint {
+            tx,
+            label,
+            handle: tokio::spawn(task.run(rx)),
+        });
+    }
+
+    let dispatcher = Dispatcher {
+        signer: config.secret.clone(),
+        events: config.events.clone(),
+        deviation_bps: config.deviation_bps,
+        drain_timeout: config.drain_timeout,
+        metrics,
+        previous_price: None,
+    };
+    Ok(tokio::spawn(dispatcher.run(updates, endpoints)))
+}
+
+// 지표 라벨로 쓰는 URL (자격 증명과 쿼리 문자열 제외)
+fn endpoint_label(url: &reqwest::Url) -> String {
+    let mut url = url.clone();
+    let _ = url.set_username("");
+    let _ = url.set_password(None);
+    url.set_query(None);
+    url.set_fragment(None);
+    url.to_string()
+}
+
+/// 엔드포인트로 보낼 이벤트 (직렬화와 서명은 디스패처에서 한 번만)
+#[derive(Debug, Clone)]
+struct Delivery {
+    event: WebhookEvent,
+    body: Vec<u8>,
+    signature: Option<String>,
+}
+
+struct Endpoint {
+    tx: mpsc::Sender<Delivery>,
+    label: String,
+    handle: JoinHandle<()>,
+}
+
+struct Dispatcher {
+    signer: Option<String>,
+    events: Vec<WebhookEvent>,
+    deviation_bps: u32,
+    drain_timeout: Duration,
+    metrics: AggregatorMetrics,
+    previous_price: Option<f64>,
+}
+
+impl Dispatcher {
+    async fn run(
+        mut self,
+        mut updates: broadcast::Receiver<AggregateUpdate>,
+        endpoints: Vec<Endpoint>,
+    ) {
+        loop {
+            match updates.recv().await {
+                Ok(AggregateUpdate::Published(snapshot)) => {
+                    for payload in self.payloads(&snapshot) {
+                        let Some(delivery) = self.encode(&payload) else {
+                            continue;
+                        };
+                        for endpoint in &endpoints {
+                            match endpoint.tx.try_send(delivery.clone()) {
+                                Ok(()) => {}
+                                Err(TrySendError::Full(_)) | Err(TrySendError::Closed(_)) => {
+                                    self.metrics
+                                        .record_webhook_delivery(&endpoint.label, "dropped");
+                                }
+                            }
+                        }
+                    }
+                }
+                Ok(AggregateUpdate::Shutdown) | Err(RecvError::Closed) => break,
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!(
+                        "⚠️ Webhook dispatcher lagged, skipped {} aggregates",
+                        skipped
+                    );
+                }
+            }
+        }
+
+        // 종료: 채널을 닫고 엔드포인트 태스크가 남은 이벤트를 보내기를 기다림
+        let deadline = tokio::time::Instant::now() + self.drain_timeout;
+        for Endpoint {
+            tx,
+            label,
+            mut handle,
+        } in endpoints
+        {
+            drop(tx);
+            if tokio::time::timeout_at(deadline, &mut handle)
+                .await
+                .is_err()
+            {
+                warn!("⚠️ Gave up on pending webhooks for {}", label);
+                handle.abort();
+            }
+        }
+        info!("🪝 Webhook dispatcher stopped");
+    }
+
+    // 게시된 집계 하나로 보낼 이벤트 (필터 적용, 직전 집계 가격 갱신)
+    fn payloads(&mut self, snapshot: &AggregateSnapshot) -> Vec<WebhookPayload> {
+        let aggregate = AggregateMessage::from(snapshot);
+        let mut payloads = Vec::new();
+
+        let deviation = match (self.previous_price, aggregate.aggregated_price) {
+            (Some(previous), Some(price)) if previous > 0.0 => {
+                let bps = (price - previous).abs() / previous * 10_000.0;
+                (bps >= f64::from(self.deviation_bps)).then_some((previous, bps))
+            }
+            _ => None,
+        };
+        if aggregate.aggregated_price.is_some() {
+            self.previous_price = aggregate.aggregated_price;
+        }
+
+        if self.events.contains(&WebhookEvent::Aggregate) {
+            payloads.push(WebhookPayload {
+                event: WebhookEvent::Aggregate,
+                aggregate: aggregate.clone(),
+                previous_price: None,
+                deviation_bps: None,
+            });
+        }
+        if let Some((previous, bps)) = deviation {
+            if self.events.contains(&WebhookEvent::Deviation) {
+                payloads.push(WebhookPayload {
+                    event: WebhookEvent::Deviation,
+                    aggregate,
+                    previous_price: Some(previous),
+                    deviation_bps: Some(bps),
+                });
+            }
+        }
+        payloads
+    }
+
+    fn encode(&self, payload: &WebhookPayload) -> Option<Delivery> {
+        match serde_json::to_vec(payload) {
+            Ok(body) => Some(Delivery {
+                event: payload.event,
+                signature: self
+                    .signer
+                    .as_ref()
+                    .map(|secret| sign(secret.as_bytes(), &body)),
+                body,
+            }),
+            Err(e) => {
+                warn!("⚠️ Failed to encode {} webhook: {}", payload.event, e);
+                None
+            }
+        }
+    }
+}
+
+/// 전송 실패 (재시도할 수 있는지)
+enum PostError {
+    Retryable(String),
+    Permanent(String),
+}
+
+struct EndpointTask {
+    client: reqwest::Client,
+    url: reqwest::Url,
+    label: String,
+    config: WebhookConfig,
+    metrics: AggregatorMetrics,
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+}
+
+impl EndpointTask {
+    async fn run(mut self, mut deliveries: mpsc::Receiver<Delivery>) {
+        while let Some(delivery) = deliveries.recv().await {
+            let trial = match self.opened_at {
+                Some(opened_at) if opened_at.elapsed() < self.config.breaker_cooldown => {
+                    self.metrics
+                        .record_webhook_delivery(&self.label, "short_circuited");
+                    continue;
+                }
+                Some(_) => true,
+                None => false,
+            };
+
+            // 회로가 열렸다가 쿨다운이 지났으면 재시도 없이 한 번만 시험
+            let max_attempts = if trial {
+                1
+            } else {
+                self.config.max_attempts.max(1)
+            };
+            if self.deliver(&delivery, max_attempts).await {
+                if self.opened_at.take().is_some() {
+                    info!("🔌 Webhook {} recovered, circuit closed", self.label);
+                }
+                self.consecutive_failures = 0;
+            } else {
+                self.consecutive_failures += 1;
+                if self.consecutive_failures >= self.config.breaker_threshold.max(1) {
+                    if !trial {
+                        warn!(
+                            "🔌 Webhook {} failed {} events in a row, circuit open for {:?}",
+                            self.label, self.consecutive_failures, self.config.breaker_cooldown
+                        );
+                    }
+                    self.opened_at = Some(Instant::now());
+                }
+            }
+        }
+    }
+
+    // 이벤트 하나 전송 (재시도할 수 있는 실패면 백오프하며 재시도, 전달했으면 true)
+    async fn deliver(&self, delivery: &Delivery, max_attempts: u32) -> bool {
+        let mut backoff = self.config.initial_backoff;
+        for attempt in 1..=max_attempts {
+            match self.post(delivery).await {
+                Ok(()) => {
+                    self.metrics
+                        .record_webhook_delivery(&self.label, "delivered");
+                    return true;
+                }
+                Err(PostError::Retryable(e)) if attempt < max_attempts => {
+                    warn!(
+                        "⚠️ Webhook {} failed ({}/{}): {}, retrying in {:?}",
+                        self.label, attempt, max_attempts, e, backoff
+                    );
+                    self.metrics.record_webhook_delivery(&self.label, "retried");
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(self.config.max_backoff);
+                }
+                Err(PostError::Retryable(e)) | Err(PostError::Permanent(e)) => {
+                    warn!(
+                        "❌ Giving up on {} webhook to {} after {} attempts: {}",
+                        delivery.event, self.label, attempt, e
+                    );
+                    self.metrics.record_webhook_delivery(&self.label, "failed");
+                    return false;
+                }
+            }
+        }
+        false
+    }
+
+    async fn post(&self, delivery: &Delivery) -> Result<(), PostError> {
+        let mut request = self
+            .client
+            .post(self.url.clone())
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(EVENT_HEADER, delivery.event.name())
+            .body(delivery.body.clone());
+        if let Some(signature) = &delivery.signature {
+            request = request.header(SIGNATURE_HEADER, signature);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| PostError::Retryable(e.to_string()))?;
+        let status = response.status();
+        if status.is_success() {
+            Ok(())
+        } else if status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            Err(PostError::Retryable(format!("HTTP {}", status)))
+        } else {
+            Err(PostError::Permanent(format!("HTTP {}", status)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::extract::State;
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::post;
+    use axum::Router;
+    use oracle_vm_common::Price;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    const SECRET: &str = "webhook-s3cret";
+
+    /// 받은 요청을 기록하는 엔드포인트 (처음 `failures`번은 500)
+    #[derive(Default)]
+    struct Receiver {
+        requests: Mutex<Vec<(HeaderMap, Vec<u8>)>>,
+        hits: AtomicU32,
+        failures: AtomicU32,
+    }
+
+    async fn receive(
+        State(receiver): State<Arc<Receiver>>,
+        headers: HeaderMap,
+        body: axum::body::Bytes,
+    ) -> StatusCode {
+        receiver.hits.fetch_add(1, Ordering::SeqCst);
+        let failing = receiver
+            .failures
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |left| {
+                left.checked_sub(1)
+            })
+            .is_ok();
+        if failing {
+            return StatusCode::INTERNAL_SERVER_ERROR;
+        }
+        receiver
+            .requests
+            .lock()
+            .unwrap()
+            .push((headers, body.to_vec()));
+        StatusCode::NO_CONTENT
+    }
+
+    async fn serve(receiver: Arc<Receiver>) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = Router::new()
+            .route("/hook", post(receive))
+            .with_state(receiver);
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        format!("http://{}/hook", addr)
+    }
+
+    fn config(url: String) -> WebhookConfig {
+        WebhookConfig {
+            urls: vec![url],
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(5),
+            ..WebhookConfig::default()
+        }
+    }
+
+    fn published(price: f64) -> AggregateUpdate {
+        AggregateUpdate::Published(Arc::new(AggregateSnapshot {
+            aggregated_price: Some(Price::from_f64_dollars(price, 8).unwrap()),
+            contributing_nodes: 3,
+            timestamp: 1_700_000_000,
+            ..AggregateSnapshot::default()
+        }))
+    }
+
+    // `outcome` 전송 결과 수 (Prometheus 텍스트 출력에서 읽음)
+    fn deliveries(metrics: &AggregatorMetrics, outcome: &str) -> u64 {
+        let label = format!("outcome=\"{}\"", outcome);
+        metrics
+            .encode()
+            .unwrap()
+            .lines()
+            .filter(|line| line.starts_with("oracle_aggregator_webhook_deliveries_total"))
+            .filter(|line| line.contains(&label))
+            .filter_map(|line| line.rsplit(' ').next()?.parse::<u64>().ok())
+            .sum()
+    }
+
+    // 업데이트를 모두 보내고 종료 알림 후 디스패처가 끝날 때까지 기다림
+    async fn dispatch(
+        config: WebhookConfig,
+        metrics: &AggregatorMetrics,
+        updates: Vec<AggregateUpdate>,
+    ) {
+        let (tx, rx) = broadcast::channel(64);
+        let handle = spawn(config, metrics.clone(), rx).unwrap();
+        for update in updates {
+            tx.send(update).unwrap();
+        }
+        tx.send(AggregateUpdate::Shutdown).unwrap();
+        handle.await.unwrap();
+    }
+
+    #[test]
+    fn test_payloads_follow_event_filters_and_deviation_threshold() {
+        let mut dispatcher = Dispatcher {
+            signer: None,
+            events: vec![WebhookEvent::Deviation],
+            deviation_bps: 100,
+            drain_timeout: Duration::ZERO,
+            metrics: AggregatorMetrics::new(),
+            previous_price: None,
+        };
+        let snapshot = |price: Option<f64>| AggregateSnapshot {
+            aggregated_price: price.map(|price| Price::from_f64_dollars(price, 8).unwrap()),
+            ..AggregateSnapshot::default()
+        };
+
+        // 첫 집계는 비교할 가격이 없고, 0.5% 변화는 임계값 미만
+        assert!(dispatcher.payloads(&snapshot(Some(70_000.0))).is_empty());
+        assert!(dispatcher.payloads(&snapshot(Some(70_350.0))).is_empty());
+        // 가격이 없는 집계는 직전 가격을 바꾸지 않음
+        assert!(dispatcher.payloads(&snapshot(None)).is_empty());
+
+        let payloads = dispatcher.payloads(&snapshot(Some(69_000.0)));
+        assert_eq!(payloads.len(), 1);
+        assert_eq!(payloads[0].event, WebhookEvent::Deviation);
+        assert_eq!(payloads[0].previous_price, Some(70_350.0));
+        let bps = payloads[0].deviation_bps.unwrap();
+        assert!((bps - 191.9).abs() < 0.1, "{}", bps);
+
+        // aggregate 필터를 켜면 집계마다 보냄
+        dispatcher.events = vec![WebhookEvent::Aggregate, WebhookEvent::Deviation];
+        let payloads = dispatcher.payloads(&snapshot(Some(69_010.0)));
+        assert_eq!(payloads.len(), 1);
+        assert_eq!(payloads[0].event, WebhookEvent::Aggregate);
+        assert_eq!(payloads[0].deviation_bps, None);
+
+        assert_eq!("Deviation".parse(), Ok(WebhookEvent::Deviation));
+        assert!("quarantine".parse::<WebhookEvent>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_delivers_signed_payloads() {
+        let receiver = Arc::new(Receiver::default());
+        let url = serve(receiver.clone()).await;
+        let metrics = AggregatorMetrics::new();
+        let config = WebhookConfig {
+            secret: Some(SECRET.to_string()),
+            ..config(url)
+        };
+
+        dispatch(
+            config,
+            &metrics,
+            vec![published(70_000.0), published(71_000.0)],
+        )
+        .await;
+
+        let requests = receiver.requests.lock().unwrap();
+        let events: Vec<&str> = requests
+            .iter()
+            .map(|(headers, _)| headers[EVENT_HEADER].to_str().unwrap())
+            .collect();
+        assert_eq!(events, ["aggregate", "aggregate", "deviation"]);
+
+        for (headers, body) in requests.iter() {
+            assert_eq!(headers["content-type"], "application/json");
+            let signature = headers[SIGNATURE_HEADER].to_str().unwrap();
+            assert!(verify(SECRET.as_bytes(), body, signature));
+            assert!(!verify(b"wrong-secret", body, signature));
+        }
+        // 본문이 바뀌면 서명이 맞지 않음
+        let (headers, body) = &requests[2];
+        let signature = headers[SIGNATURE_HEADER].to_str().unwrap();
+        let mut tampered = body.clone();
+        tampered.push(b' ');
+        assert!(!verify(SECRET.as_bytes(), &tampered, signature));
+
+        let payload: serde_json::Value = serde_json::from_slice(body).unwrap();
+        assert_eq!(payload["event"], "deviation");
+        assert_eq!(payload["aggregate"]["pair"], "BTC/USD");
+        assert_eq!(payload["aggregate"]["aggregated_price"], 71_000.0);
+        assert_eq!(payload["aggregate"]["contributing_nodes"], 3);
+        assert_eq!(payload["previous_price"], 70_000.0);
+        assert!(payload["deviation_bps"].as_f64().unwrap() > 142.0);
+        let first: serde_json::Value = serde_json::from_slice(&requests[0].1).unwrap();
+        assert!(first.get("previous_price").is_none());
+
+        assert_eq!(deliveries(&metrics, "delivered"), 3);
+        assert_eq!(deliveries(&metrics, "failed"), 0);
+    }
+
+    #[tokio::test]
+    async fn test_retries_server_errors_until_delivered() {
+        let receiver = Arc::new(Receiver {
+            failures: AtomicU32::new(2),
+            ..Receiver::default()
+        });
+        let url = serve(receiver.clone()).await;
+        let metrics = AggregatorMetrics::new();
+
+        dispatch(config(url.clone()), &metrics, vec![published(70_000.0)]).await;
+
+        // 500 두 번 뒤 세 번째 시도에서 전달, 서명 비밀 키가 없으면 서명 헤더도 없음
+        assert_eq!(receiver.hits.load(Ordering::SeqCst), 3);
+        let requests = receiver.requests.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert!(!requests[0].0.contains_key(SIGNATURE_HEADER));
+        assert_eq!(deliveries(&metrics, "retried"), 2);
+        assert_eq!(deliveries(&metrics, "delivered"), 1);
+        assert!(metrics.encode().unwrap().contains(&url));
+    }
+
+    #[tokio::test]
+    async fn test_breaker_opens_after_consecutive_failures_and_recovers() {
+        let receiver = Arc::new(Receiver {
+            failures: AtomicU32::new(u32::MAX),
+            ..Receiver::default()
+        });
+        let url = serve(receiver.clone()).await;
+        let metrics = AggregatorMetrics::new();
+        let config = WebhookConfig {
+            events: vec![WebhookEvent::Aggregate],
+            max_attempts: 2,
+            breaker_threshold: 2,
+            breaker_cooldown: Duration::from_millis(300),
+            ..config(url)
+        };
+
+        let (tx, rx) = broadcast::channel(64);
+        let handle = spawn(config, metrics.clone(), rx).unwrap();
+        for price in [70_000.0, 70_010.0, 70_020.0, 70_030.0, 70_040.0] {
+            tx.send(published(price)).unwrap();
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        // 두 이벤트(각 2번 시도) 뒤 회로가 열려 나머지는 보내지 않고 버림
+        assert_eq!(receiver.hits.load(Ordering::SeqCst), 4);
+        assert_eq!(deliveries(&metrics, "failed"), 2);
+        assert_eq!(deliveries(&metrics, "short_circuited"), 3);
+
+        // 쿨다운 뒤 시험 전송이 성공하면 회로가 닫힘
+        receiver.failures.store(0, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        tx.send(published(70_050.0)).unwrap();
+        tx.send(published(70_060.0)).unwrap();
+        tx.send(AggregateUpdate::Shutdown).unwrap();
+        handle.await.unwrap();
+
+        assert_eq!(receiver.requests.lock().unwrap().len(), 2);
+        assert_eq!(deliveries(&metrics, "delivered"), 2);
+        assert_eq!(deliveries(&metrics, "short_circuited"), 3);
+    }
+}
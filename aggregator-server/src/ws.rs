@@ -0,0 +1,332 @@
+//! REST 게이트웨이의 `/v1/ws`: 게시되는 집계 결과를 WebSocket으로 전달
+//!
+//! stream_prices와 같은 구독 채널에서 받은 집계 결과를 AggregatedPriceUpdate와 같은 필드의 JSON으로
+//! 보낸다. 클라이언트는 `{"pairs": ["BTC-USD"]}`를 보내 받을 pair를 고를 수 있다 (보내기 전에는 모든
+//! pair). 서버가 주기적으로 ping을 보내 유휴 시간 동안 아무 메시지도 없는 클라이언트는 끊고, 전송이
+//! 밀리는 클라이언트는 메시지를 쌓아 두지 않고 close 코드와 함께 끊는다.
+
+use axum::extract::ws::{close_code, CloseFrame, Message, WebSocket, WebSocketUpgrade};
+use axum::extract::State;
+use axum::response::Response;
+use axum::Extension;
+use oracle_vm_common::AssetPair;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::time::{self, Instant};
+use tracing::{debug, info, warn};
+
+use crate::oracle::AggregatedPriceUpdate;
+use crate::rest::ErrorBody;
+use crate::{AggregateUpdate, AggregatorServiceImpl};
+
+/// 기본 ping 주기
+pub const DEFAULT_WS_PING_INTERVAL: Duration = Duration::from_secs(15);
+/// 기본 유휴 시간 (이 시간 동안 pong을 포함해 아무 메시지도 없으면 끊음)
+pub const DEFAULT_WS_IDLE_TIMEOUT: Duration = Duration::from_secs(45);
+/// 기본 전송 제한 시간 (메시지 하나를 보내는 데 이보다 오래 걸리면 느린 클라이언트로 보고 끊음)
+pub const DEFAULT_WS_SEND_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// WebSocket 연결 설정
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WsConfig {
+    pub ping_interval: Duration,
+    pub idle_timeout: Duration,
+    pub send_timeout: Duration,
+}
+
+impl Default for WsConfig {
+    fn default() -> Self {
+        Self {
+            ping_interval: DEFAULT_WS_PING_INTERVAL,
+            idle_timeout: DEFAULT_WS_IDLE_TIMEOUT,
+            send_timeout: DEFAULT_WS_SEND_TIMEOUT,
+        }
+    }
+}
+
+/// 집계 결과 메시지 (AggregatedPriceUpdate와 같은 필드)
+#[derive(Debug, Serialize)]
+pub struct UpdateBody {
+    pub pair: String,
+    pub aggregated_price: f64,
+    pub data_points: u32,
+    pub timestamp: u64,
+    pub active_nodes: Vec<String>,
+    pub stale: bool,
+    pub quote_currency: String,
+    pub unit: String,
+}
+
+impl From<AggregatedPriceUpdate> for UpdateBody {
+    fn from(update: AggregatedPriceUpdate) -> Self {
+        Self {
+            pair: update.pair,
+            aggregated_price: update.aggregated_price,
+            data_points: update.data_points,
+            timestamp: update.timestamp,
+            active_nodes: update.active_nodes,
+            stale: update.stale,
+            quote_currency: update.quote_currency,
+            unit: update.unit,
+        }
+    }
+}
+
+/// 클라이언트가 보내는 구독 메시지 (`pairs`가 없으면 모든 pair)
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Subscription {
+    pairs: Option<Vec<String>>,
+}
+
+/// 구독 확인 (`subscribed`가 null이면 모든 pair)
+#[derive(Debug, Serialize)]
+struct Subscribed<'a> {
+    subscribed: Option<&'a BTreeSet<String>>,
+}
+
+// 구독 메시지 해석 (pair는 `BTC/USD` 형태로 정규화)
+fn parse_subscription(text: &str) -> Result<Option<BTreeSet<String>>, String> {
+    let subscription: Subscription =
+        serde_json::from_str(text).map_err(|e| format!("invalid subscription: {}", e))?;
+    subscription
+        .pairs
+        .map(|pairs| {
+            pairs
+                .iter()
+                .map(|symbol| {
+                    AssetPair::from_symbol(symbol)
+                        .map(|pair| pair.0)
+                        .ok_or_else(|| format!("unknown pair {:?}", symbol))
+                })
+                .collect()
+        })
+        .transpose()
+}
+
+pub(crate) async fn upgrade(
+    State(service): State<AggregatorServiceImpl>,
+    Extension(config): Extension<WsConfig>,
+    upgrade: WebSocketUpgrade,
+) -> Response {
+    upgrade.on_upgrade(move |socket| session(socket, service, config))
+}
+
+// 연결 하나 (구독 채널을 읽어 보내다가 종료, 유휴, 전송 지연 시 close 코드를 보내고 끝냄)
+async fn session(mut socket: WebSocket, service: AggregatorServiceImpl, config: WsConfig) {
+    let mut updates = service.subscribe();
+    let _subscriber = service.metrics().stream_subscriber();
+    let mut pairs: Option<BTreeSet<String>> = None;
+    let mut ping = time::interval_at(Instant::now() + config.ping_interval, config.ping_interval);
+    let mut last_seen = Instant::now();
+    debug!("WebSocket client connected");
+
+    let (code, reason) = loop {
+        tokio::select! {
+            message = socket.recv() => match message {
+                Some(Ok(Message::Text(text))) => {
+                    last_seen = Instant::now();
+                    let reply = match parse_subscription(&text) {
+                        Ok(selected) => {
+                            pairs = selected;
+                            serde_json::to_string(&Subscribed { subscribed: pairs.as_ref() })
+                        }
+                        Err(error) => serde_json::to_string(&ErrorBody { error }),
+                    };
+                    let reply = reply.expect("serializable reply");
+                    if !send(&mut socket, Message::Text(reply), config).await {
+                        break (close_code::POLICY, "client too slow");
+                    }
+                }
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+                // pong, ping, binary은 살아 있다는 표시로만 사용
+                Some(Ok(_)) => last_seen = Instant::now(),
+            },
+            update = updates.recv() => match update {
+                Ok(AggregateUpdate::Published(snapshot)) => {
+                    let update = service.price_update(&snapshot);
+                    if pairs.as_ref().is_some_and(|pairs| !pairs.contains(&update.pair)) {
+                        continue;
+                    }
+                    let body = serde_json::to_string(&UpdateBody::from(update))
+                        .expect("serializable update");
+                    if !send(&mut socket, Message::Text(body), config).await {
+                        break (close_code::POLICY, "client too slow");
+                    }
+                }
+                Ok(AggregateUpdate::Shutdown) | Err(RecvError::Closed) => {
+                    break (close_code::AWAY, "server shutting down");
+                }
+                // 밀린 업데이트를 쌓아 두지 않고 끊음
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!("⚠️ WebSocket client lagged by {} updates, disconnecting", skipped);
+                    break (close_code::POLICY, "client too slow");
+                }
+            },
+            _ = ping.tick() => {
+                if last_seen.elapsed() > config.idle_timeout {
+                    info!("🔌 Disconnecting idle WebSocket client");
+                    break (close_code::AWAY, "idle timeout");
+                }
+                if !send(&mut socket, Message::Ping(Vec::new()), config).await {
+                    break (close_code::POLICY, "client too slow");
+                }
+            }
+        }
+    };
+
+    let close = Message::Close(Some(CloseFrame {
+        code,
+        reason: reason.into(),
+    }));
+    if !send(&mut socket, close, config).await {
+        return;
+    }
+    // 클라이언트의 Close 응답까지 기다려 밀려 있던 pong 등을 받아 줌 (전송 제한 시간까지만)
+    let _ = time::timeout(config.send_timeout, async {
+        while let Some(Ok(message)) = socket.recv().await {
+            if matches!(message, Message::Close(_)) {
+                break;
+            }
+        }
+    })
+    .await;
+}
+
+// 전송 제한 시간 안에 보냈는지 (연결이 끊긴 경우도 false)
+async fn send(socket: &mut WebSocket, message: Message, config: WsConfig) -> bool {
+    matches!(
+        time::timeout(config.send_timeout, socket.send(message)).await,
+        Ok(Ok(()))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oracle::oracle_service_client::OracleServiceClient;
+    use crate::rest::tests::{local_config, price_request, start};
+    use crate::rest::RestConfig;
+    use futures_util::{SinkExt, StreamExt};
+    use serde_json::Value;
+    use std::net::SocketAddr;
+    use tokio::net::TcpStream;
+    use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+    use tokio_tungstenite::tungstenite::Message as WsMessage;
+    use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+
+    type Client = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+    async fn connect(addr: SocketAddr) -> Client {
+        connect_async(format!("ws://{}/v1/ws", addr))
+            .await
+            .unwrap()
+            .0
+    }
+
+    // 다음 텍스트 메시지 (ping 등은 건너뜀, 시간 안에 없으면 None)
+    async fn next_text(client: &mut Client, wait: Duration) -> Option<Value> {
+        time::timeout(wait, async {
+            loop {
+                match client.next().await {
+                    Some(Ok(WsMessage::Text(text))) => return serde_json::from_str(&text).ok(),
+                    Some(Ok(WsMessage::Close(_))) | Some(Err(_)) | None => return None,
+                    Some(Ok(_)) => {}
+                }
+            }
+        })
+        .await
+        .ok()
+        .flatten()
+    }
+
+    async fn subscribe(client: &mut Client, pairs: &str) -> Value {
+        let message = format!(r#"{{"pairs": {}}}"#, pairs);
+        client.send(WsMessage::Text(message)).await.unwrap();
+        next_text(client, Duration::from_secs(5)).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_clients_receive_only_subscribed_pairs() {
+        let service = AggregatorServiceImpl::new();
+        let (grpc_addr, http_addr, _) = start(service.clone(), local_config(Vec::new())).await;
+
+        let mut btc = connect(http_addr).await;
+        let mut eth = connect(http_addr).await;
+        assert_eq!(
+            subscribe(&mut btc, r#"["BTC-USD"]"#).await["subscribed"],
+            serde_json::json!(["BTC/USD"])
+        );
+        assert_eq!(
+            subscribe(&mut eth, r#"["eth/usd"]"#).await["subscribed"],
+            serde_json::json!(["ETH/USD"])
+        );
+        // 잘못된 구독은 오류만 알리고 이전 구독 유지
+        assert!(subscribe(&mut eth, r#"["nope"]"#).await["error"].is_string());
+
+        let mut client = OracleServiceClient::connect(format!("http://{}", grpc_addr))
+            .await
+            .unwrap();
+        for (price, node_id) in [(70_000.0, "node-a"), (70_010.0, "node-b")] {
+            client
+                .submit_price(price_request(price, node_id, "BTC-USD"))
+                .await
+                .unwrap();
+        }
+        service.publish_snapshot().await;
+
+        let update = next_text(&mut btc, Duration::from_secs(5)).await.unwrap();
+        assert_eq!(update["pair"], "BTC/USD");
+        assert_eq!(update["aggregated_price"], 70_005.0);
+        assert_eq!(update["data_points"], 2);
+        assert_eq!(
+            update["active_nodes"],
+            serde_json::json!(["node-a", "node-b"])
+        );
+
+        // BTC/USD 집계만 게시되므로 ETH/USD 구독자는 받지 않음
+        assert_eq!(next_text(&mut eth, Duration::from_millis(300)).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_stalled_client_is_disconnected() {
+        let service = AggregatorServiceImpl::new();
+        let config = RestConfig {
+            ws: WsConfig {
+                ping_interval: Duration::from_millis(50),
+                idle_timeout: Duration::from_millis(200),
+                send_timeout: Duration::from_secs(1),
+            },
+            ..local_config(Vec::new())
+        };
+        let (_, http_addr, _) = start(service.clone(), config).await;
+
+        let mut stalled = connect(http_addr).await;
+        let mut live = connect(http_addr).await;
+        // 읽는 클라이언트는 ping에 자동으로 응답
+        let live_task =
+            tokio::spawn(async move { next_text(&mut live, Duration::from_secs(5)).await });
+
+        // 유휴 시간 동안 읽지 않아 pong을 보내지 못한 클라이언트는 close 코드와 함께 끊김
+        time::sleep(Duration::from_millis(600)).await;
+        let close = time::timeout(Duration::from_secs(5), async {
+            loop {
+                match stalled.next().await {
+                    Some(Ok(WsMessage::Close(frame))) => return frame,
+                    Some(Ok(_)) => {}
+                    Some(Err(_)) | None => return None,
+                }
+            }
+        })
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(close.code, CloseCode::Away);
+        assert_eq!(close.reason, "idle timeout");
+
+        service.publish_snapshot().await;
+        assert!(live_task.await.unwrap().is_some());
+    }
+}
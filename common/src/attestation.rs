@@ -0,0 +1,336 @@
+//! Signed price attestations
+//!
+//! An attestation is the aggregator's statement "pair was `price` at `timestamp`, agreed by
+//! `contributing_nodes` nodes", signed with its ed25519 key. The signed message is the
+//! versioned canonical encoding below, so anyone holding the aggregator's public key can
+//! check an attestation with [`verify_attestation`] without trusting the transport it came over.
+//!
+//! Canonical encoding, version 1 (all integers big-endian):
+//!
+//! | field                | encoding                            |
+//! |----------------------|-------------------------------------|
+//! | domain               | ASCII `ORACLE-VM-PRICE-ATTESTATION` |
+//! | version              | `u8` (1)                            |
+//! | pair                 | `u8` length + ASCII bytes           |
+//! | price_scaled         | `u64`                               |
+//! | price_decimals       | `u8`                                |
+//! | timestamp            | `u64` (Unix seconds)                |
+//! | contributing_nodes   | `u32`                               |
+//!
+//! Any change to this layout gets a new version number; verifiers reject versions they
+//! do not know rather than guessing.
+
+use crate::crypto::sha256;
+use crate::price::Price;
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey, PUBLIC_KEY_LENGTH};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Current canonical encoding version
+pub const ATTESTATION_VERSION: u8 = 1;
+/// Domain separator prefixed to every canonical encoding
+pub const ATTESTATION_DOMAIN: &[u8] = b"ORACLE-VM-PRICE-ATTESTATION";
+/// Number of public key hash bytes in a key id
+const KEY_ID_BYTES: usize = 8;
+
+/// Why an attestation could not be encoded or verified
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum AttestationError {
+    #[error("Unsupported attestation version {0} (expected {ATTESTATION_VERSION})")]
+    UnsupportedVersion(u8),
+
+    #[error("Pair must be 1-255 ASCII characters, got {0:?}")]
+    InvalidPair(String),
+
+    #[error("Price decimals {0} exceed the maximum of {max}", max = Price::MAX_DECIMALS)]
+    InvalidDecimals(u32),
+
+    #[error("Malformed {field}: {reason}")]
+    Malformed { field: &'static str, reason: String },
+
+    #[error("Key {0} is not a trusted attestation key")]
+    UntrustedKey(String),
+
+    #[error("Key id {key_id} does not match the public key (expected {expected})")]
+    KeyIdMismatch { key_id: String, expected: String },
+
+    #[error("Canonical bytes do not match the attested fields")]
+    CanonicalMismatch,
+
+    #[error("Invalid signature")]
+    InvalidSignature,
+}
+
+/// The attested aggregate
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PriceAttestation {
+    /// Canonical encoding version
+    pub version: u8,
+    /// Asset pair (e.g. `BTC/USD`)
+    pub pair: String,
+    /// Price as a scaled integer: `price_scaled × 10^-price_decimals`
+    pub price_scaled: u64,
+    pub price_decimals: u32,
+    /// When the aggregate was computed (Unix seconds)
+    pub timestamp: u64,
+    /// Distinct nodes whose prices went into the aggregate
+    pub contributing_nodes: u32,
+}
+
+impl PriceAttestation {
+    /// Attestation in the current encoding version
+    pub fn new(pair: &str, price: Price, timestamp: u64, contributing_nodes: u32) -> Self {
+        let (price_scaled, price_decimals) = price.to_scaled();
+        Self {
+            version: ATTESTATION_VERSION,
+            pair: pair.to_string(),
+            price_scaled,
+            price_decimals,
+            timestamp,
+            contributing_nodes,
+        }
+    }
+
+    /// Attested price
+    pub fn price(&self) -> Option<Price> {
+        Price::from_scaled(self.price_scaled, self.price_decimals).ok()
+    }
+
+    /// The bytes that get signed (see the module docs for the layout)
+    pub fn canonical_bytes(&self) -> Result<Vec<u8>, AttestationError> {
+        if self.version != ATTESTATION_VERSION {
+            return Err(AttestationError::UnsupportedVersion(self.version));
+        }
+        let pair_len = u8::try_from(self.pair.len())
+            .ok()
+            .filter(|&len| len > 0 && self.pair.is_ascii())
+            .ok_or_else(|| AttestationError::InvalidPair(self.pair.clone()))?;
+        let decimals = u8::try_from(self.price_decimals)
+            .ok()
+            .filter(|&decimals| u32::from(decimals) <= Price::MAX_DECIMALS)
+            .ok_or(AttestationError::InvalidDecimals(self.price_decimals))?;
+
+        let mut bytes = Vec::with_capacity(ATTESTATION_DOMAIN.len() + 2 + self.pair.len() + 21);
+        bytes.extend_from_slice(ATTESTATION_DOMAIN);
+        bytes.push(self.version);
+        bytes.push(pair_len);
+        bytes.extend_from_slice(self.pair.as_bytes());
+        bytes.extend_from_slice(&self.price_scaled.to_be_bytes());
+        bytes.push(decimals);
+        bytes.extend_from_slice(&self.timestamp.to_be_bytes());
+        bytes.extend_from_slice(&self.contributing_nodes.to_be_bytes());
+        Ok(bytes)
+    }
+}
+
+/// An attestation together with its canonical bytes, signature and signing key (all hex)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedPriceAttestation {
+    #[serde(flatten)]
+    pub attestation: PriceAttestation,
+    /// Id of the signing key, see [`key_id`]
+    pub key_id: String,
+    /// ed25519 public key
+    pub public_key: String,
+    /// Canonical encoding of `attestation`
+    pub canonical: String,
+    /// ed25519 signature over `canonical`
+    pub signature: String,
+}
+
+impl SignedPriceAttestation {
+    /// Sign an attestation (ed25519 signatures are deterministic, so the same inputs
+    /// always give the same output)
+    pub fn sign(attestation: PriceAttestation, key: &SigningKey) -> Result<Self, AttestationError> {
+        let canonical = attestation.canonical_bytes()?;
+        let signature = key.sign(&canonical);
+        let public_key = key.verifying_key();
+        Ok(Self {
+            attestation,
+            key_id: key_id(&public_key),
+            public_key: hex::encode(public_key.as_bytes()),
+            canonical: hex::encode(canonical),
+            signature: hex::encode(signature.to_bytes()),
+        })
+    }
+}
+
+/// Short, stable id of a public key: the first 8 bytes of its SHA-256, in hex
+///
+/// Rotating the aggregator key changes the id, so consumers can tell which key signed what.
+pub fn key_id(public_key: &VerifyingKey) -> String {
+    hex::encode(&sha256(public_key.as_bytes())[..KEY_ID_BYTES])
+}
+
+/// Check that `signed` was produced by one of `trusted_keys` and that nothing in it changed
+///
+/// The canonical bytes are recomputed from the attested fields and must match the ones
+/// that were signed, so a consumer reading the fields can rely on them directly.
+pub fn verify_attestation<'a>(
+    signed: &'a SignedPriceAttestation,
+    trusted_keys: &[[u8; PUBLIC_KEY_LENGTH]],
+) -> Result<&'a PriceAttestation, AttestationError> {
+    let public_key: [u8; PUBLIC_KEY_LENGTH] = decode("public_key", &signed.public_key)?;
+    let verifying_key =
+        VerifyingKey::from_bytes(&public_key).map_err(|e| AttestationError::Malformed {
+            field: "public_key",
+            reason: e.to_string(),
+        })?;
+    let expected = key_id(&verifying_key);
+    if !trusted_keys.contains(&public_key) {
+        return Err(AttestationError::UntrustedKey(expected));
+    }
+    if signed.key_id != expected {
+        return Err(AttestationError::KeyIdMismatch {
+            key_id: signed.key_id.clone(),
+            expected,
+        });
+    }
+
+    let canonical = signed.attestation.canonical_bytes()?;
+    if hex::decode(&signed.canonical).ok().as_deref() != Some(canonical.as_slice()) {
+        return Err(AttestationError::CanonicalMismatch);
+    }
+    let signature = Signature::from_bytes(&decode("signature", &signed.signature)?);
+    verifying_key
+        .verify_strict(&canonical, &signature)
+        .map_err(|_| AttestationError::InvalidSignature)?;
+    Ok(&signed.attestation)
+}
+
+fn decode<const N: usize>(field: &'static str, value: &str) -> Result<[u8; N], AttestationError> {
+    let malformed = |reason: String| AttestationError::Malformed { field, reason };
+    hex::decode(value)
+        .map_err(|e| malformed(e.to_string()))?
+        .try_into()
+        .map_err(|bytes: Vec<u8>| malformed(format!("expected {} bytes, got {}", N, bytes.len())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32])
+    }
+
+    fn attestation() -> PriceAttestation {
+        PriceAttestation::new("BTC/USD", Price::from_cents(7_000_012), 1_700_000_000, 3)
+    }
+
+    fn trusted(key: &SigningKey) -> [[u8; PUBLIC_KEY_LENGTH]; 1] {
+        [key.verifying_key().to_bytes()]
+    }
+
+    #[test]
+    fn test_sign_and_verify_round_trip() {
+        let key = key(7);
+        let signed = SignedPriceAttestation::sign(attestation(), &key).unwrap();
+
+        let verified = verify_attestation(&signed, &trusted(&key)).unwrap();
+        assert_eq!(verified, &attestation());
+        assert_eq!(verified.price(), Some(Price::from_cents(7_000_012)));
+
+        // Survives the JSON form consumers receive
+        let json = serde_json::to_string(&signed).unwrap();
+        let parsed: SignedPriceAttestation = serde_json::from_str(&json).unwrap();
+        assert!(verify_attestation(&parsed, &trusted(&key)).is_ok());
+    }
+
+    #[test]
+    fn test_any_field_mutation_is_rejected() {
+        let key = key(7);
+        let signed = SignedPriceAttestation::sign(attestation(), &key).unwrap();
+        let mutations: [fn(&mut SignedPriceAttestation); 7] = [
+            |s| s.attestation.pair = "ETH/USD".to_string(),
+            |s| s.attestation.price_scaled += 1,
+            |s| s.attestation.price_decimals = 3,
+            |s| s.attestation.timestamp += 1,
+            |s| s.attestation.contributing_nodes += 1,
+            |s| s.canonical.replace_range(0..2, "00"),
+            |s| s.signature.replace_range(0..2, "00"),
+        ];
+
+        for (i, mutate) in mutations.into_iter().enumerate() {
+            let mut tampered = signed.clone();
+            mutate(&mut tampered);
+            assert_ne!(tampered, signed, "mutation {} changed nothing", i);
+            assert!(
+                verify_attestation(&tampered, &trusted(&key)).is_err(),
+                "mutation {} was accepted",
+                i
+            );
+        }
+
+        // Re-signing the mutated fields with another key does not help either
+        let mut forged = attestation();
+        forged.price_scaled += 1;
+        let forged = SignedPriceAttestation::sign(forged, &self::key(8)).unwrap();
+        assert_eq!(
+            verify_attestation(&forged, &trusted(&key)),
+            Err(AttestationError::UntrustedKey(forged.key_id.clone()))
+        );
+    }
+
+    #[test]
+    fn test_version_and_key_id_are_checked() {
+        let key = key(7);
+        let mut signed = SignedPriceAttestation::sign(attestation(), &key).unwrap();
+        signed.key_id = key_id(&self::key(8).verifying_key());
+        assert!(matches!(
+            verify_attestation(&signed, &trusted(&key)),
+            Err(AttestationError::KeyIdMismatch { .. })
+        ));
+
+        let mut future = attestation();
+        future.version = ATTESTATION_VERSION + 1;
+        assert_eq!(
+            SignedPriceAttestation::sign(future, &key),
+            Err(AttestationError::UnsupportedVersion(
+                ATTESTATION_VERSION + 1
+            ))
+        );
+    }
+
+    #[test]
+    fn test_canonical_bytes_are_stable() {
+        // Pinned so any change to the encoding shows up here and gets a new version
+        let canonical = attestation().canonical_bytes().unwrap();
+        assert_eq!(
+            hex::encode(&canonical),
+            concat!(
+                "4f5241434c452d564d2d50524943452d4154544553544154494f4e", // domain
+                "01",                                                     // version
+                "07",                                                     // pair length
+                "4254432f555344",                                         // BTC/USD
+                "00000000006acfcc",                                       // 7_000_012
+                "02",                                                     // decimals
+                "000000006553f100",                                       // timestamp
+                "00000003",                                               // nodes
+            )
+        );
+        assert_eq!(attestation().canonical_bytes().unwrap(), canonical);
+
+        // Same key and fields always give the same signature
+        let first = SignedPriceAttestation::sign(attestation(), &key(7)).unwrap();
+        let second = SignedPriceAttestation::sign(attestation(), &key(7)).unwrap();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_invalid_fields_have_no_encoding() {
+        let mut long_pair = attestation();
+        long_pair.pair = "X".repeat(256);
+        assert!(matches!(
+            long_pair.canonical_bytes(),
+            Err(AttestationError::InvalidPair(_))
+        ));
+
+        let mut decimals = attestation();
+        decimals.price_decimals = Price::MAX_DECIMALS + 1;
+        assert_eq!(
+            decimals.canonical_bytes(),
+            Err(AttestationError::InvalidDecimals(Price::MAX_DECIMALS + 1))
+        );
+    }
+}
@@ -0,0 +1,408 @@
+//! Append-only JSON Lines files
+//!
+//! [`JsonlWriter`] appends one JSON value per line to a file, syncs it to disk according to an
+//! [`FsyncPolicy`], and rotates it once it would grow past `max_bytes`: the full file is renamed
+//! to `<path>.<n>` (1 for the oldest) and a fresh file is started at `path`.
+//!
+//! A crash can leave a final line without its newline. The writer cuts such a torn line off when
+//! it opens the file, so new records never get glued onto it, and [`read_jsonl`] skips it on the
+//! reader side for files that are still being written or were copied before recovery.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// When appended lines are synced to disk
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FsyncPolicy {
+    /// After every line (no acknowledged line is lost on power failure)
+    #[default]
+    Always,
+    /// After every N lines (up to N - 1 lines can be lost)
+    EveryN(u32),
+    /// Never explicitly; the OS writes the page cache back on its own schedule
+    Never,
+}
+
+impl FromStr for FsyncPolicy {
+    type Err = String;
+
+    /// Parses `always`, `never` or `every:N` (N >= 1)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "always" => Ok(Self::Always),
+            "never" => Ok(Self::Never),
+            _ => match s.strip_prefix("every:").map(str::parse::<u32>) {
+                Some(Ok(n)) if n > 0 => Ok(Self::EveryN(n)),
+                _ => Err(format!(
+                    "Unknown fsync policy {:?} (expected always, never or every:N)",
+                    s
+                )),
+            },
+        }
+    }
+}
+
+impl fmt::Display for FsyncPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Always => write!(f, "always"),
+            Self::EveryN(n) => write!(f, "every:{}", n),
+            Self::Never => write!(f, "never"),
+        }
+    }
+}
+
+/// Where and how a JSON Lines file is written
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonlConfig {
+    /// Active file; rotated files sit next to it as `<path>.1`, `<path>.2`, ...
+    pub path: PathBuf,
+    #[serde(with = "fsync_policy_name")]
+    pub fsync: FsyncPolicy,
+    /// Rotate before a line would make the active file larger than this (None: never rotate)
+    pub max_bytes: Option<u64>,
+}
+
+impl JsonlConfig {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            fsync: FsyncPolicy::default(),
+            max_bytes: None,
+        }
+    }
+
+    pub fn with_fsync(mut self, fsync: FsyncPolicy) -> Self {
+        self.fsync = fsync;
+        self
+    }
+
+    pub fn with_max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+}
+
+mod fsync_policy_name {
+    use super::FsyncPolicy;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(policy: &FsyncPolicy, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(policy)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<FsyncPolicy, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Appends JSON values to a file, one per line
+#[derive(Debug)]
+pub struct JsonlWriter {
+    config: JsonlConfig,
+    file: File,
+    /// Bytes in the active file
+    len: u64,
+    /// Lines written since the last sync
+    unsynced: u32,
+    /// Index the next rotated file gets
+    next_rotation: u64,
+    /// Bytes of a torn final line cut off on open
+    recovered_bytes: u64,
+}
+
+impl JsonlWriter {
+    /// Opens (or creates) the active file, cutting off a torn final line
+    pub fn open(config: JsonlConfig) -> io::Result<Self> {
+        if let Some(dir) = config.path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        let contents = match fs::read(&config.path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e),
+        };
+        let complete = complete_len(&contents);
+
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&config.path)?;
+        if complete < contents.len() {
+            file.set_len(complete as u64)?;
+            file.sync_all()?;
+        }
+        let next_rotation = rotated_paths(&config.path)?
+            .last()
+            .and_then(|path| rotation_index(&config.path, path))
+            .map_or(1, |index| index + 1);
+
+        Ok(Self {
+            file,
+            len: complete as u64,
+            unsynced: 0,
+            next_rotation,
+            recovered_bytes: (contents.len() - complete) as u64,
+            config,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.config.path
+    }
+
+    pub fn config(&self) -> &JsonlConfig {
+        &self.config
+    }
+
+    /// Bytes of a torn final line that `open` cut off (0 if the file ended cleanly)
+    pub fn recovered_bytes(&self) -> u64 {
+        self.recovered_bytes
+    }
+
+    /// Appends `value` as one line
+    pub fn append<T: Serialize>(&mut self, value: &T) -> io::Result<()> {
+        let line = serde_json::to_vec(value).map_err(io::Error::from)?;
+        self.append_line(&line)
+    }
+
+    /// Appends an already encoded JSON value as one line
+    ///
+    /// The value must not contain a newline (compact `serde_json` output never does).
+    pub fn append_line(&mut self, json: &[u8]) -> io::Result<()> {
+        if json.contains(&b'\n') {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "JSON line must not contain a newline",
+            ));
+        }
+        let line_len = json.len() as u64 + 1;
+        if let Some(max_bytes) = self.config.max_bytes {
+            if self.len > 0 && self.len + line_len > max_bytes {
+                self.rotate()?;
+            }
+        }
+
+        let mut line = Vec::with_capacity(json.len() + 1);
+        line.extend_from_slice(json);
+        line.push(b'\n');
+        self.file.write_all(&line)?;
+        self.len += line_len;
+        self.unsynced += 1;
+
+        let due = match self.config.fsync {
+            FsyncPolicy::Always => true,
+            FsyncPolicy::EveryN(n) => self.unsynced >= n,
+            FsyncPolicy::Never => false,
+        };
+        if due {
+            self.sync()?;
+        }
+        Ok(())
+    }
+
+    /// Syncs every line written so far to disk
+    pub fn sync(&mut self) -> io::Result<()> {
+        if self.unsynced > 0 {
+            self.file.sync_data()?;
+            self.unsynced = 0;
+        }
+        Ok(())
+    }
+
+    // Renames the active file to `<path>.<n>` and starts a new one
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.sync_data()?;
+        let rotated = rotated_path(&self.config.path, self.next_rotation);
+        fs::rename(&self.config.path, &rotated)?;
+        self.file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.config.path)?;
+        self.next_rotation += 1;
+        self.len = 0;
+        self.unsynced = 0;
+        Ok(())
+    }
+}
+
+/// Path of the `index`-th rotated file (`<path>.<index>`)
+pub fn rotated_path(path: &Path, index: u64) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{}", index));
+    PathBuf::from(name)
+}
+
+fn rotation_index(path: &Path, rotated: &Path) -> Option<u64> {
+    let name = rotated.file_name()?.to_str()?;
+    let base = path.file_name()?.to_str()?;
+    name.strip_prefix(base)?.strip_prefix('.')?.parse().ok()
+}
+
+/// Rotated files of `path`, oldest first
+pub fn rotated_paths(path: &Path) -> io::Result<Vec<PathBuf>> {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let mut rotated = Vec::new();
+    for entry in entries {
+        let candidate = dir.join(entry?.file_name());
+        if let Some(index) = rotation_index(path, &candidate) {
+            rotated.push((index, candidate));
+        }
+    }
+    rotated.sort();
+    Ok(rotated.into_iter().map(|(_, path)| path).collect())
+}
+
+/// Reads every complete line of a JSON Lines file
+///
+/// A final line without its newline is a torn write and is skipped; any other line that does
+/// not parse is an `InvalidData` error. Blank lines are ignored.
+pub fn read_jsonl<T: DeserializeOwned>(path: &Path) -> io::Result<Vec<T>> {
+    let contents = fs::read(path)?;
+    contents[..complete_len(&contents)]
+        .split(|&byte| byte == b'\n')
+        .enumerate()
+        .filter(|(_, line)| !line.iter().all(u8::is_ascii_whitespace))
+        .map(|(number, line)| {
+            serde_json::from_slice(line).map_err(|e| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{} line {}: {}", path.display(), number + 1, e),
+                )
+            })
+        })
+        .collect()
+}
+
+// Length up to and including the last newline
+fn complete_len(contents: &[u8]) -> usize {
+    contents
+        .iter()
+        .rposition(|&byte| byte == b'\n')
+        .map_or(0, |newline| newline + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "jsonl-{}-{}-{}",
+            name,
+            std::process::id(),
+            rand::random::<u32>()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn record(seq: u64) -> Value {
+        json!({ "seq": seq, "price": "70000.12" })
+    }
+
+    #[test]
+    fn test_fsync_policy_round_trips_its_name() {
+        for policy in [
+            FsyncPolicy::Always,
+            FsyncPolicy::EveryN(100),
+            FsyncPolicy::Never,
+        ] {
+            assert_eq!(policy.to_string().parse::<FsyncPolicy>(), Ok(policy));
+        }
+        for invalid in ["sometimes", "every:0", "every:", "every:x", "every:-1"] {
+            assert!(invalid.parse::<FsyncPolicy>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_writes_through_rotation() {
+        let dir = temp_dir("rotation");
+        let path = dir.join("aggregates.jsonl");
+        let line_len = serde_json::to_vec(&record(0)).unwrap().len() as u64 + 1;
+        // three lines per file
+        let config = JsonlConfig::new(&path)
+            .with_fsync(FsyncPolicy::EveryN(2))
+            .with_max_bytes(line_len * 3);
+
+        let mut writer = JsonlWriter::open(config.clone()).unwrap();
+        for seq in 0..7 {
+            writer.append(&record(seq)).unwrap();
+        }
+        writer.sync().unwrap();
+        drop(writer);
+        // reopening continues the numbering instead of overwriting `.1`
+        let mut writer = JsonlWriter::open(config).unwrap();
+        for seq in 7..10 {
+            writer.append(&record(seq)).unwrap();
+        }
+
+        let rotated = rotated_paths(&path).unwrap();
+        assert_eq!(
+            rotated,
+            [1, 2, 3].map(|index| rotated_path(&path, index))
+        );
+        let mut seqs = Vec::new();
+        for file in rotated.iter().chain([&path]) {
+            assert!(fs::metadata(file).unwrap().len() <= line_len * 3);
+            let values: Vec<Value> = read_jsonl(file).unwrap();
+            seqs.extend(values.iter().map(|value| value["seq"].as_u64().unwrap()));
+        }
+        assert_eq!(seqs, (0..10).collect::<Vec<_>>());
+
+        // one line larger than max_bytes still goes to its own file
+        let big = json!({ "seq": 10, "padding": "x".repeat(line_len as usize * 4) });
+        writer.append(&big).unwrap();
+        assert_eq!(read_jsonl::<Value>(&path).unwrap(), [big]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_torn_final_line_is_skipped_by_the_reader_and_cut_by_the_writer() {
+        let dir = temp_dir("torn");
+        let path = dir.join("submissions.jsonl");
+        let mut writer = JsonlWriter::open(JsonlConfig::new(&path)).unwrap();
+        writer.append(&record(0)).unwrap();
+        writer.append(&record(1)).unwrap();
+        drop(writer);
+
+        // a crash in the middle of the third line
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(br#"{"seq":2,"pri"#).unwrap();
+        drop(file);
+
+        let values: Vec<Value> = read_jsonl(&path).unwrap();
+        assert_eq!(values, [record(0), record(1)]);
+
+        let mut writer = JsonlWriter::open(JsonlConfig::new(&path)).unwrap();
+        assert_eq!(writer.recovered_bytes(), 13);
+        writer.append(&record(3)).unwrap();
+        let values: Vec<Value> = read_jsonl(&path).unwrap();
+        assert_eq!(values, [record(0), record(1), record(3)]);
+
+        // a damaged line before the end is not a torn write
+        fs::write(&path, b"{\"seq\":0}\nnot json\n{\"seq\":1}\n").unwrap();
+        let error = read_jsonl::<Value>(&path).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(error.to_string().contains("line 2"), "{}", error);
+
+        assert!(writer.append_line(b"{\"a\":\n1}").is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
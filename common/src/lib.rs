@@ -1,9 +1,13 @@
 //! Common types and utilities shared across Oracle VM components
 
+pub mod attestation;
 pub mod config;
 pub mod crypto;
 pub mod error;
+pub mod jsonl;
+pub mod price;
 pub mod types;
 
 pub use error::*;
+pub use price::{Price, RoundingMode};
 pub use types::*;
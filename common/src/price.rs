@@ -0,0 +1,486 @@
+//! Fixed-point price type shared by oracle nodes and the aggregator
+
+use crate::error::{OracleVmError, Result};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+/// How to round an amount that has more decimals than the target price
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RoundingMode {
+    /// Toward zero
+    Floor,
+    /// Away from zero
+    Ceil,
+    /// To the nearest value, halves away from zero
+    #[default]
+    Nearest,
+    /// To the nearest value, halves to the even neighbour (banker's rounding)
+    HalfEven,
+}
+
+impl fmt::Display for RoundingMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Floor => write!(f, "floor"),
+            Self::Ceil => write!(f, "ceil"),
+            Self::Nearest => write!(f, "nearest"),
+            Self::HalfEven => write!(f, "half-even"),
+        }
+    }
+}
+
+impl FromStr for RoundingMode {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "floor" => Ok(Self::Floor),
+            "ceil" => Ok(Self::Ceil),
+            "nearest" => Ok(Self::Nearest),
+            "half-even" | "bankers" => Ok(Self::HalfEven),
+            _ => Err(format!(
+                "unknown rounding mode '{}' (expected floor, ceil, nearest or half-even)",
+                value
+            )),
+        }
+    }
+}
+
+/// Fixed-point price: `mantissa × 10^-decimals` units of the quote currency
+///
+/// `Price::from_cents(7_000_012)` is $70,000.12. Float conversion only happens at
+/// explicit boundaries: [`Price::to_f64_dollars`] / [`Price::from_f64_dollars`] for
+/// the legacy `double` proto field, and [`Price::to_scaled`] / [`Price::from_scaled`]
+/// for the `price_scaled` / `price_decimals` proto fields.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Price {
+    mantissa: u64,
+    decimals: u32,
+}
+
+impl Price {
+    /// Decimals used for USD prices (cents)
+    pub const USD_DECIMALS: u32 = 2;
+    /// Largest supported number of decimals
+    pub const MAX_DECIMALS: u32 = 18;
+
+    /// Create a price from a mantissa and number of decimals
+    pub fn new(mantissa: u64, decimals: u32) -> Result<Self> {
+        Self::check_decimals(decimals)?;
+        Ok(Self { mantissa, decimals })
+    }
+
+    /// Create a USD price from cents
+    pub const fn from_cents(cents: u64) -> Self {
+        Self {
+            mantissa: cents,
+            decimals: Self::USD_DECIMALS,
+        }
+    }
+
+    pub const fn mantissa(&self) -> u64 {
+        self.mantissa
+    }
+
+    pub const fn decimals(&self) -> u32 {
+        self.decimals
+    }
+
+    pub const fn is_zero(&self) -> bool {
+        self.mantissa == 0
+    }
+
+    /// Convert to a float amount in whole currency units (lossy above 2^53)
+    pub fn to_f64_dollars(&self) -> f64 {
+        self.mantissa as f64 / 10f64.powi(self.decimals as i32)
+    }
+
+    /// Convert a float amount in whole currency units, rounding to `decimals`
+    pub fn from_f64_dollars(value: f64, decimals: u32) -> Result<Self> {
+        if !value.is_finite() || value < 0.0 {
+            return Err(OracleVmError::InvalidData(format!(
+                "price must be a non-negative finite number, got {}",
+                value
+            )));
+        }
+        Self::check_decimals(decimals)?;
+
+        let scaled = (value * 10f64.powi(decimals as i32)).round();
+        // u64::MAX as f64 rounds up to 2^64, which itself does not fit
+        if scaled >= u64::MAX as f64 {
+            return Err(OracleVmError::InvalidData(format!(
+                "price {} does not fit in {} decimals",
+                value, decimals
+            )));
+        }
+
+        Ok(Self {
+            mantissa: scaled as u64,
+            decimals,
+        })
+    }
+
+    /// Parse a plain decimal string such as `"50000.50500000"` exactly, rounding to `decimals`
+    ///
+    /// Exchanges send prices as decimal strings; parsing them without going through `f64`
+    /// keeps ties like `50000.505` exact, so every rounding mode sees the value the
+    /// exchange sent.
+    pub fn parse_decimal(value: &str, decimals: u32, mode: RoundingMode) -> Result<Self> {
+        Self::check_decimals(decimals)?;
+        let invalid = || OracleVmError::InvalidData(format!("invalid decimal price {:?}", value));
+
+        let (whole, fraction) = value.trim().split_once('.').unwrap_or((value.trim(), ""));
+        let is_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+        if (whole.is_empty() && fraction.is_empty()) || !is_digits(whole) || !is_digits(fraction) {
+            return Err(invalid());
+        }
+
+        let split = fraction.len().min(decimals as usize);
+        let (kept, dropped) = fraction.split_at(split);
+        let mut mantissa: u128 = 0;
+        for digit in whole.bytes().chain(kept.bytes()) {
+            mantissa = mantissa
+                .checked_mul(10)
+                .and_then(|m| m.checked_add(u128::from(digit - b'0')))
+                .ok_or_else(invalid)?;
+        }
+        mantissa = mantissa
+            .checked_mul(10u128.pow(decimals - split as u32))
+            .ok_or_else(invalid)?;
+
+        // Compare the dropped digits with one half of the last kept unit
+        let dropped = dropped.trim_end_matches('0');
+        let half = dropped
+            .as_bytes()
+            .first()
+            .map(|first| match first.cmp(&b'5') {
+                Ordering::Equal if dropped.len() == 1 => Ordering::Equal,
+                Ordering::Equal => Ordering::Greater,
+                other => other,
+            });
+        let round_up = match (mode, half) {
+            (_, None) | (RoundingMode::Floor, _) => false,
+            (RoundingMode::Ceil, _) => true,
+            (RoundingMode::Nearest, Some(half)) => half != Ordering::Less,
+            (RoundingMode::HalfEven, Some(Ordering::Equal)) => mantissa % 2 == 1,
+            (RoundingMode::HalfEven, Some(half)) => half == Ordering::Greater,
+        };
+        if round_up {
+            mantissa += 1;
+        }
+
+        let mantissa = u64::try_from(mantissa).map_err(|_| {
+            OracleVmError::InvalidData(format!(
+                "price {} does not fit in {} decimals",
+                value, decimals
+            ))
+        })?;
+        Ok(Self { mantissa, decimals })
+    }
+
+    /// Convert a float amount with an explicit rounding mode
+    ///
+    /// The float is read as its shortest decimal form (`50000.505`, not the binary
+    /// `50000.50499999…`) and then rounded like [`Price::parse_decimal`].
+    pub fn from_f64_dollars_rounded(value: f64, decimals: u32, mode: RoundingMode) -> Result<Self> {
+        if !value.is_finite() || value < 0.0 {
+            return Err(OracleVmError::InvalidData(format!(
+                "price must be a non-negative finite number, got {}",
+                value
+            )));
+        }
+        Self::parse_decimal(&value.to_string(), decimals, mode)
+    }
+
+    /// Proto representation: (`price_scaled`, `price_decimals`)
+    pub const fn to_scaled(&self) -> (u64, u32) {
+        (self.mantissa, self.decimals)
+    }
+
+    /// Build from the proto `price_scaled` / `price_decimals` fields
+    pub fn from_scaled(price_scaled: u64, price_decimals: u32) -> Result<Self> {
+        Self::new(price_scaled, price_decimals)
+    }
+
+    /// Same value with a different number of decimals, if representable exactly
+    pub fn rescale(&self, decimals: u32) -> Option<Self> {
+        if decimals > Self::MAX_DECIMALS {
+            return None;
+        }
+
+        let mantissa = if decimals >= self.decimals {
+            self.mantissa
+                .checked_mul(10u64.checked_pow(decimals - self.decimals)?)?
+        } else {
+            let divisor = 10u64.pow(self.decimals - decimals);
+            if !self.mantissa.is_multiple_of(divisor) {
+                return None;
+            }
+            self.mantissa / divisor
+        };
+
+        Some(Self { mantissa, decimals })
+    }
+
+    /// Exact average of two prices
+    ///
+    /// Uses the larger of the two decimals, adding one more decimal when the sum is odd.
+    /// At `MAX_DECIMALS` an odd sum is rounded to the even neighbour instead.
+    /// Returns `None` if the result does not fit in a `u64` mantissa.
+    pub fn midpoint(&self, other: &Self) -> Option<Self> {
+        let decimals = self.decimals.max(other.decimals);
+        let widen = |p: &Self| p.mantissa as u128 * 10u128.pow(decimals - p.decimals);
+        let sum = widen(self) + widen(other);
+
+        let (mantissa, decimals) = if sum.is_multiple_of(2) {
+            (sum / 2, decimals)
+        } else if decimals < Self::MAX_DECIMALS {
+            (sum * 5, decimals + 1)
+        } else {
+            let half = sum / 2;
+            (half + (half % 2), decimals)
+        };
+
+        Self::new(u64::try_from(mantissa).ok()?, decimals).ok()
+    }
+
+    fn check_decimals(decimals: u32) -> Result<()> {
+        if decimals > Self::MAX_DECIMALS {
+            return Err(OracleVmError::InvalidData(format!(
+                "price decimals {} exceeds maximum {}",
+                decimals,
+                Self::MAX_DECIMALS
+            )));
+        }
+        Ok(())
+    }
+
+    // Mantissa expressed with MAX_DECIMALS (always fits in u128)
+    fn widened(&self) -> u128 {
+        self.mantissa as u128 * 10u128.pow(Self::MAX_DECIMALS - self.decimals)
+    }
+}
+
+impl PartialEq for Price {
+    fn eq(&self, other: &Self) -> bool {
+        self.widened() == other.widened()
+    }
+}
+
+impl Eq for Price {}
+
+impl PartialOrd for Price {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Price {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.widened().cmp(&other.widened())
+    }
+}
+
+impl fmt::Display for Price {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.decimals == 0 {
+            return write!(f, "{}", self.mantissa);
+        }
+
+        let divisor = 10u64.pow(self.decimals);
+        write!(
+            f,
+            "{}.{:0width$}",
+            self.mantissa / divisor,
+            self.mantissa % divisor,
+            width = self.decimals as usize
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_cents_round_trip_through_f64() {
+        let price = Price::from_cents(7_000_012);
+
+        assert_eq!(price.to_f64_dollars(), 70_000.12);
+        assert_eq!(
+            Price::from_f64_dollars(70_000.12, Price::USD_DECIMALS).unwrap(),
+            price
+        );
+        assert_eq!(price.to_string(), "70000.12");
+    }
+
+    #[test]
+    fn test_from_f64_rounds_instead_of_truncating() {
+        // 70000.12 * 100 = 7000011.999999999 in f64
+        let price = Price::from_f64_dollars(70_000.12, 2).unwrap();
+        assert_eq!(price.mantissa(), 7_000_012);
+
+        assert_eq!(Price::from_f64_dollars(0.005, 2).unwrap().mantissa(), 1);
+    }
+
+    #[test]
+    fn test_parse_decimal_rounding_modes() {
+        let cents = |value: &str, mode| {
+            Price::parse_decimal(value, Price::USD_DECIMALS, mode)
+                .unwrap()
+                .mantissa()
+        };
+
+        // Exactly half a cent above 50000.50
+        assert_eq!(cents("50000.505", RoundingMode::Floor), 5_000_050);
+        assert_eq!(cents("50000.505", RoundingMode::Ceil), 5_000_051);
+        assert_eq!(cents("50000.505", RoundingMode::Nearest), 5_000_051);
+        assert_eq!(cents("50000.505", RoundingMode::HalfEven), 5_000_050);
+        assert_eq!(cents("50000.515", RoundingMode::HalfEven), 5_000_052);
+
+        // Not a tie: every nearest mode agrees
+        assert_eq!(cents("50000.50500001", RoundingMode::HalfEven), 5_000_051);
+        assert_eq!(cents("50000.504999", RoundingMode::Nearest), 5_000_050);
+        assert_eq!(cents("50000.504999", RoundingMode::Ceil), 5_000_051);
+
+        // Trailing zeros are exact under every mode
+        for mode in [
+            RoundingMode::Floor,
+            RoundingMode::Ceil,
+            RoundingMode::Nearest,
+            RoundingMode::HalfEven,
+        ] {
+            assert_eq!(cents("50000.50000000", mode), 5_000_050);
+            assert_eq!(cents("50000", mode), 5_000_000);
+            assert_eq!(cents(".5", mode), 50);
+        }
+
+        for invalid in ["", ".", "-1.00", "1e5", "12.3.4", "abc", "1,000.00"] {
+            assert!(
+                Price::parse_decimal(invalid, 2, RoundingMode::Nearest).is_err(),
+                "{}",
+                invalid
+            );
+        }
+        assert!(Price::parse_decimal("184467440737095516.16", 2, RoundingMode::Floor).is_err());
+    }
+
+    #[test]
+    fn test_from_f64_rounded_uses_shortest_decimal_form() {
+        // The binary value is slightly off 50000.505; the rounding sees the decimal form
+        let rounded = |mode| {
+            Price::from_f64_dollars_rounded(50_000.505, 2, mode)
+                .unwrap()
+                .mantissa()
+        };
+        assert_eq!(rounded(RoundingMode::Floor), 5_000_050);
+        assert_eq!(rounded(RoundingMode::Ceil), 5_000_051);
+        assert_eq!(rounded(RoundingMode::Nearest), 5_000_051);
+        assert_eq!(rounded(RoundingMode::HalfEven), 5_000_050);
+
+        assert!(Price::from_f64_dollars_rounded(-1.0, 2, RoundingMode::Nearest).is_err());
+        assert!(Price::from_f64_dollars_rounded(f64::NAN, 2, RoundingMode::Nearest).is_err());
+    }
+
+    #[test]
+    fn test_rounding_mode_names() {
+        for mode in [
+            RoundingMode::Floor,
+            RoundingMode::Ceil,
+            RoundingMode::Nearest,
+            RoundingMode::HalfEven,
+        ] {
+            assert_eq!(mode.to_string().parse(), Ok(mode));
+        }
+        assert_eq!("bankers".parse(), Ok(RoundingMode::HalfEven));
+        assert!("up".parse::<RoundingMode>().is_err());
+        assert_eq!(RoundingMode::default(), RoundingMode::Nearest);
+    }
+
+    #[test]
+    fn test_from_f64_rejects_invalid_values() {
+        assert!(Price::from_f64_dollars(-1.0, 2).is_err());
+        assert!(Price::from_f64_dollars(f64::NAN, 2).is_err());
+        assert!(Price::from_f64_dollars(f64::INFINITY, 2).is_err());
+        assert!(Price::from_f64_dollars(1.0, Price::MAX_DECIMALS + 1).is_err());
+        // 1e18 dollars in cents overflows u64
+        assert!(Price::from_f64_dollars(1e18, 2).is_err());
+    }
+
+    #[test]
+    fn test_scaled_round_trip_is_exact_at_large_magnitudes() {
+        for (mantissa, decimals) in [(u64::MAX, 2), (u64::MAX, 18), (9_007_199_254_740_993, 8)] {
+            let price = Price::from_scaled(mantissa, decimals).unwrap();
+            assert_eq!(price.to_scaled(), (mantissa, decimals));
+        }
+
+        // 2^53 + 1 is not representable as f64: the integer form keeps it, the float does not
+        let price = Price::from_cents(9_007_199_254_740_993);
+        assert_eq!(price.to_string(), "90071992547409.93");
+        assert_ne!(
+            Price::from_f64_dollars(price.to_f64_dollars(), 2).unwrap(),
+            price
+        );
+    }
+
+    #[test]
+    fn test_equality_and_ordering_across_decimals() {
+        let cents = Price::from_cents(7_000_012);
+        let micros = Price::new(70_000_120_000, 6).unwrap();
+
+        assert_eq!(cents, micros);
+        assert!(Price::new(70_000_120_001, 6).unwrap() > cents);
+        assert_eq!(micros.rescale(2), Some(cents));
+        assert_eq!(Price::new(70_000_120_001, 6).unwrap().rescale(2), None);
+        assert_eq!(cents.rescale(6).unwrap().mantissa(), 70_000_120_000);
+        assert_eq!(Price::from_cents(u64::MAX).rescale(3), None);
+    }
+
+    #[test]
+    fn test_midpoint_is_exact() {
+        let a = Price::from_cents(7_000_000);
+        let b = Price::from_cents(7_000_001);
+        assert_eq!(a.midpoint(&b).unwrap().to_scaled(), (70_000_005, 3));
+        assert_eq!(a.midpoint(&a).unwrap().to_scaled(), (7_000_000, 2));
+
+        // Mixed decimals use the finer scale
+        let c = Price::new(70_000_500_000, 6).unwrap();
+        assert_eq!(a.midpoint(&c).unwrap().to_scaled(), (70_000_250_000, 6));
+
+        assert_eq!(
+            Price::new(u64::MAX, 0)
+                .unwrap()
+                .midpoint(&Price::new(u64::MAX, 0).unwrap())
+                .unwrap()
+                .mantissa(),
+            u64::MAX
+        );
+    }
+
+    #[test]
+    fn test_display_pads_fraction() {
+        assert_eq!(Price::from_cents(5).to_string(), "0.05");
+        assert_eq!(Price::new(42, 0).unwrap().to_string(), "42");
+        assert_eq!(Price::new(1_000_001, 6).unwrap().to_string(), "1.000001");
+    }
+
+    proptest! {
+        #[test]
+        // Exact while float error stays below half a cent (~$11T)
+        fn prop_cents_round_trip_through_f64(cents in 0u64..(1u64 << 50)) {
+            let price = Price::from_cents(cents);
+            let back = Price::from_f64_dollars(price.to_f64_dollars(), Price::USD_DECIMALS).unwrap();
+            prop_assert_eq!(back.mantissa(), cents);
+        }
+
+        #[test]
+        fn prop_scaled_round_trip(mantissa in any::<u64>(), decimals in 0u32..=Price::MAX_DECIMALS) {
+            let price = Price::from_scaled(mantissa, decimals).unwrap();
+            prop_assert_eq!(price.to_scaled(), (mantissa, decimals));
+        }
+    }
+}
@@ -1,5 +1,6 @@
 //! Common types for Oracle VM
 
+use crate::price::Price;
 use bitcoin::PublicKey;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -23,13 +24,71 @@ impl AssetPair {
     pub fn as_str(&self) -> &str {
         &self.0
     }
+
+    /// Quote asset of a `BASE/QUOTE` pair, i.e. the currency the price is expressed in
+    pub fn quote(&self) -> &str {
+        self.0.split_once('/').map_or(&self.0, |(_, quote)| quote)
+    }
+
+    /// Parse an exchange symbol into a `BASE/QUOTE` pair
+    ///
+    /// Accepts separated forms (`BTC/USD`, `btc-usd`, `BTC_USD`), concatenated forms
+    /// (`BTCUSDT`) and Kraken's prefixed form (`XXBTZUSD`). `XBT` is read as `BTC`, and
+    /// USD stablecoin quotes (`USDT`, `USDC`) as `USD`, matching how the node reports
+    /// Binance `BTCUSDT` closes as BTC/USD. Returns `None` when the symbol is not recognised.
+    pub fn from_symbol(symbol: &str) -> Option<Self> {
+        let symbol = symbol.trim().to_ascii_uppercase();
+        if !symbol.is_ascii() {
+            return None;
+        }
+
+        let (base, quote) = match symbol.split_once(['/', '-', '_']) {
+            Some((base, quote)) => (base.to_string(), quote.to_string()),
+            None => Self::split_concatenated(&symbol)?,
+        };
+
+        let is_asset =
+            |s: &str| (2..=6).contains(&s.len()) && s.chars().all(|c| c.is_ascii_alphanumeric());
+        if !is_asset(&base) || !is_asset(&quote) {
+            return None;
+        }
+
+        let base = Self::normalize_asset(&base);
+        let quote = Self::normalize_asset(&quote);
+        if base == quote {
+            return None;
+        }
+
+        Some(Self(format!("{}/{}", base, quote)))
+    }
+
+    fn split_concatenated(symbol: &str) -> Option<(String, String)> {
+        // Kraken: X + 3-letter crypto base, Z + 3-letter fiat quote
+        if symbol.len() == 8 && symbol.starts_with('X') && symbol[4..].starts_with('Z') {
+            return Some((symbol[1..4].to_string(), symbol[5..].to_string()));
+        }
+
+        const QUOTES: [&str; 6] = ["USDT", "USDC", "USD", "EUR", "KRW", "BTC"];
+        QUOTES.iter().find_map(|quote| {
+            let base = symbol.strip_suffix(quote)?;
+            (!base.is_empty()).then(|| (base.to_string(), quote.to_string()))
+        })
+    }
+
+    fn normalize_asset(asset: &str) -> &str {
+        match asset {
+            "XBT" => "BTC",
+            "USDT" | "USDC" => "USD",
+            other => other,
+        }
+    }
 }
 
 /// Price data from an oracle source
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PriceData {
     pub pair: AssetPair,
-    pub price: u64, // Price in cents (for USD pairs)
+    pub price: Price, // Fixed-point price (cents for USD pairs)
     pub timestamp: DateTime<Utc>,
     pub volume: Option<u64>, // 24h volume
     pub source: String,      // Exchange name
@@ -99,3 +158,62 @@ pub struct UtxoRef {
     pub amount: u64,
     pub address: String, // Address as string for serde compatibility
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_symbol_exchange_conventions() {
+        for symbol in [
+            "BTC/USD",
+            "btc-usd",
+            "BTC_USD",
+            "BTCUSD",
+            "BTCUSDT",
+            "XBTUSD",
+            "XXBTZUSD",
+            " BTCUSDC ",
+        ] {
+            assert_eq!(
+                AssetPair::from_symbol(symbol),
+                Some(AssetPair::btc_usd()),
+                "{}",
+                symbol
+            );
+        }
+
+        assert_eq!(
+            AssetPair::from_symbol("ETHBTC").unwrap().as_str(),
+            "ETH/BTC"
+        );
+        assert_eq!(
+            AssetPair::from_symbol("KRW-BTC").unwrap().as_str(),
+            "KRW/BTC"
+        );
+        assert_eq!(
+            AssetPair::from_symbol("ETH-EUR").unwrap().as_str(),
+            "ETH/EUR"
+        );
+        assert_eq!(AssetPair::from_symbol("ETH-EUR").unwrap().quote(), "EUR");
+        assert_eq!(AssetPair::btc_usd().quote(), "USD");
+    }
+
+    #[test]
+    fn test_from_symbol_rejects_unknown_symbols() {
+        for symbol in [
+            "",
+            "BTC",
+            "USDT",
+            "BTC/",
+            "/USD",
+            "BTC/USD/EUR",
+            "BTCXYZ",
+            "USDUSDT",
+            "BTC$/USD",
+            "XéBTZUSD",
+        ] {
+            assert_eq!(AssetPair::from_symbol(symbol), None, "{}", symbol);
+        }
+    }
+}
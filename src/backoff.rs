@@ -0,0 +1,151 @@
+use std::fmt::Display;
+use std::future::Future;
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+/// 지수 백오프: `base * 2^attempt` (최대 `max`), 최대 `max_retries`번 재시도
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Backoff {
+    pub base: Duration,
+    pub max: Duration,
+    pub max_retries: u32,
+}
+
+/// 거래소 API 재시도: 1초, 2초, 4초...
+pub const EXCHANGE_BACKOFF: Backoff =
+    Backoff::new(Duration::from_secs(1), Duration::from_secs(8), 2);
+
+/// Aggregator 재연결: 200ms, 400ms, 800ms 후 포기 (다음 Aggregator 또는 다음 라운드로)
+pub const AGGREGATOR_BACKOFF: Backoff =
+    Backoff::new(Duration::from_millis(200), Duration::from_secs(5), 3);
+
+impl Backoff {
+    pub const fn new(base: Duration, max: Duration, max_retries: u32) -> Self {
+        Self {
+            base,
+            max,
+            max_retries,
+        }
+    }
+
+    /// `attempt`번째(0부터) 실패 후 대기 시간
+    pub fn delay(&self, attempt: u32) -> Duration {
+        self.base
+            .checked_mul(2u32.saturating_pow(attempt))
+            .map_or(self.max, |delay| delay.min(self.max))
+    }
+
+    /// `attempt`번째(0부터) 실패 후 재시도 대기 시간 (재시도 횟수를 다 쓰면 None)
+    pub fn retry_delay(&self, attempt: u32) -> Option<Duration> {
+        (attempt < self.max_retries).then(|| self.delay(attempt))
+    }
+
+    /// 성공할 때까지 `operation` 실행 (첫 시도 + 최대 `max_retries`번 재시도)
+    ///
+    /// 첫 시도는 `max_retries`와 무관하게 항상 하므로 결과는 성공 값이거나 마지막 시도의 에러다.
+    /// `label`은 로그에 쓰는 대상 이름 (예: "BTC price from Binance").
+    pub async fn retry<T, E, F, Fut>(&self, label: &str, mut operation: F) -> Result<T, E>
+    where
+        E: Display,
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let attempts = self.max_retries.saturating_add(1);
+        let mut attempt = 0;
+        loop {
+            info!("Fetching {} (attempt {}/{})", label, attempt + 1, attempts);
+            match operation().await {
+                Ok(value) => return Ok(value),
+                Err(e) => match self.retry_delay(attempt) {
+                    Some(delay) => {
+                        warn!(
+                            "Failed to fetch {} (attempt {}): {}. Retrying in {:?}...",
+                            label,
+                            attempt + 1,
+                            e,
+                            delay
+                        );
+                        tokio::time::sleep(delay).await;
+                    }
+                    None => {
+                        error!(
+                            "Failed to fetch {} after {} attempts: {}",
+                            label, attempts, e
+                        );
+                        return Err(e);
+                    }
+                },
+            }
+            attempt += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delay_doubles_up_to_max() {
+        let backoff = Backoff::new(Duration::from_millis(100), Duration::from_millis(500), 10);
+
+        let delays: Vec<u64> = (0..5)
+            .map(|a| backoff.delay(a).as_millis() as u64)
+            .collect();
+        assert_eq!(delays, vec![100, 200, 400, 500, 500]);
+        // 오버플로 없이 최대값
+        assert_eq!(backoff.delay(u32::MAX), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_retry_delay_stops_after_max_retries() {
+        assert_eq!(
+            EXCHANGE_BACKOFF.retry_delay(0),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(
+            EXCHANGE_BACKOFF.retry_delay(1),
+            Some(Duration::from_secs(2))
+        );
+        assert_eq!(EXCHANGE_BACKOFF.retry_delay(2), None);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_retry_attempts_at_least_once() {
+        async fn run(backoff: Backoff, failures: u32) -> (Result<u32, String>, u32) {
+            let mut calls = 0;
+            let result = backoff
+                .retry("test price", || {
+                    calls += 1;
+                    let call = calls;
+                    async move {
+                        if call <= failures {
+                            Err(format!("failure {}", call))
+                        } else {
+                            Ok(call)
+                        }
+                    }
+                })
+                .await;
+            (result, calls)
+        }
+
+        // 재시도가 0번이어도 한 번은 시도
+        let once = Backoff::new(Duration::from_secs(1), Duration::from_secs(1), 0);
+        assert_eq!(run(once, 0).await, (Ok(1), 1));
+        assert_eq!(run(once, 1).await, (Err("failure 1".to_string()), 1));
+
+        // 재시도를 다 쓰면 마지막 에러
+        assert_eq!(run(EXCHANGE_BACKOFF, 1).await, (Ok(2), 2));
+        assert_eq!(run(EXCHANGE_BACKOFF, 2).await, (Ok(3), 3));
+        assert_eq!(
+            run(EXCHANGE_BACKOFF, 5).await,
+            (Err("failure 3".to_string()), 3)
+        );
+
+        // 재시도 사이에 1초, 2초 대기
+        let start = tokio::time::Instant::now();
+        run(EXCHANGE_BACKOFF, 5).await.0.unwrap_err();
+        assert_eq!(start.elapsed(), Duration::from_secs(3));
+    }
+}
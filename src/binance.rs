@@ -1,17 +1,21 @@
+use crate::backoff::EXCHANGE_BACKOFF;
+use crate::clock_drift::{TimeReference, TimeSource};
+use crate::price_band::PriceBands;
 use crate::price_provider::PriceProvider;
 use oracle_vm_common::types::{PriceData, AssetPair};
+use oracle_vm_common::{Price, RoundingMode};
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use chrono::{DateTime, Timelike};
 use reqwest::Client;
+use serde::Deserialize;
 use std::time::Duration;
-use tokio::time::sleep;
-use tracing::{error, info, warn};
+use tracing::info;
 
 /// 바이낸스 API URL
 const BINANCE_API_URL: &str = "https://api.binance.com/api/v3/klines";
-/// 최대 재시도 횟수
-const MAX_RETRIES: u32 = 3;
+/// 바이낸스 서버 시각 URL
+const BINANCE_TIME_URL: &str = "https://api.binance.com/api/v3/time";
 /// HTTP 요청 타임아웃 (초)
 const REQUEST_TIMEOUT: u64 = 10;
 
@@ -19,9 +23,18 @@ const REQUEST_TIMEOUT: u64 = 10;
 /// [timestamp, open, high, low, close, volume, close_time, quote_asset_volume, count, taker_buy_base_asset_volume, taker_buy_quote_asset_volume, ignore]
 type BinanceKlineResponse = Vec<Vec<serde_json::Value>>;
 
+/// 바이낸스 서버 시각 응답 ({"serverTime": 밀리초})
+#[derive(Debug, Deserialize)]
+struct BinanceServerTime {
+    #[serde(rename = "serverTime")]
+    server_time: i64,
+}
+
 /// 바이낸스와 통신하는 클라이언트
 pub struct BinanceClient {
     client: Client, // HTTP 요청을 보내는 도구
+    rounding: RoundingMode, // 종가를 센트로 바꿀 때의 반올림 방식
+    bands: PriceBands, // pair별 가격 허용 범위
 }
 
 impl BinanceClient {
@@ -33,46 +46,41 @@ impl BinanceClient {
             .build()
             .expect("Failed to create HTTP client");
 
-        Self { client }
+        Self {
+            client,
+            rounding: RoundingMode::default(),
+            bands: PriceBands::default(),
+        }
     }
 
-    /// 비트코인 가격을 가져옵니다 (재시도 포함)
-    pub async fn fetch_btc_price(&self) -> Result<PriceData> {
-        self.fetch_btc_price_with_retry(MAX_RETRIES).await
+    /// 종가를 센트로 바꿀 때의 반올림 방식 지정 (기본값: nearest)
+    pub fn with_rounding_mode(mut self, rounding: RoundingMode) -> Self {
+        self.rounding = rounding;
+        self
     }
 
-    /// 재시도 로직이 포함된 가격 가져오기
-    async fn fetch_btc_price_with_retry(&self, max_retries: u32) -> Result<PriceData> {
-        for attempt in 1..=max_retries {
-            info!(
-                "Fetching BTC price from Binance (attempt {}/{})",
-                attempt, max_retries
-            );
-
-            match self.fetch_btc_price_once().await {
-                Ok(price_data) => {
-                    info!("Successfully fetched BTC price: ${:.2}", price_data.price);
-                    return Ok(price_data);
-                }
-                Err(e) if attempt < max_retries => {
-                    let wait_time = 2_u64.pow(attempt - 1); // 1초, 2초, 4초... (지수적 백오프)
-                    warn!(
-                        "Failed to fetch price (attempt {}): {}. Retrying in {}s...",
-                        attempt, e, wait_time
-                    );
-                    sleep(Duration::from_secs(wait_time)).await;
-                }
-                Err(e) => {
-                    error!(
-                        "Failed to fetch price after {} attempts: {}",
-                        max_retries, e
-                    );
-                    return Err(e);
-                }
-            }
-        }
+    /// pair별 가격 허용 범위와 범위를 벗어났을 때의 처리 지정 (기본값: BTC/USD 1,000~1,000,000 USD, 경고)
+    pub fn with_price_bands(mut self, bands: PriceBands) -> Self {
+        self.bands = bands;
+        self
+    }
 
-        unreachable!("This should never be reached")
+    /// 종가 문자열을 f64를 거치지 않고 센트로 변환
+    fn close_to_price(&self, close: &str) -> Result<Price> {
+        Price::parse_decimal(close, Price::USD_DECIMALS, self.rounding)
+            .with_context(|| format!("Failed to convert close price {} to cents", close))
+    }
+
+    /// 비트코인 가격을 가져옵니다 (재시도 포함)
+    pub async fn fetch_btc_price(&self) -> Result<PriceData> {
+        let price_data = EXCHANGE_BACKOFF
+            .retry("BTC price from Binance", || self.fetch_btc_price_once())
+            .await?;
+        info!(
+            "✅ Successfully fetched BTC price from Binance: ${}",
+            price_data.price
+        );
+        Ok(price_data)
     }
 
     /// 한 번만 가격을 가져오기 (재시도 없음)
@@ -124,9 +132,10 @@ impl BinanceClient {
 
         // 5. 첫 번째 (그리고 유일한) K-line에서 종가 추출
         let kline = &klines[0];
-        let close_price = kline[4]
+        let close = kline[4]
             .as_str()
-            .ok_or_else(|| anyhow::anyhow!("Close price is not a string"))?
+            .ok_or_else(|| anyhow::anyhow!("Close price is not a string"))?;
+        let close_price = close
             .parse::<f64>()
             .context("Failed to parse close price as number")?;
 
@@ -145,7 +154,7 @@ impl BinanceClient {
         );
 
         // 6. 가격이 말이 되는지 검증
-        self.validate_price(close_price)?;
+        self.validate_price(&AssetPair::btc_usd(), close_price)?;
 
         // 7. 현재 시간을 타임스탬프로 사용
         let current_timestamp = chrono::Utc::now().timestamp() as u64;
@@ -153,7 +162,7 @@ impl BinanceClient {
         // 8. 최종 결과 반환
         Ok(PriceData {
             pair: AssetPair::btc_usd(),
-            price: (close_price * 100.0) as u64, // Convert to cents
+            price: self.close_to_price(close)?, // cents
             timestamp: DateTime::from_timestamp(current_timestamp as i64, 0)
                 .unwrap_or_else(chrono::Utc::now),
             volume: None,
@@ -161,6 +170,25 @@ impl BinanceClient {
         })
     }
 
+    /// 바이낸스 서버 시각 (Unix 밀리초, 시계 오차 확인용)
+    pub async fn server_time_millis(&self) -> Result<i64> {
+        let response = self
+            .client
+            .get(BINANCE_TIME_URL)
+            .send()
+            .await
+            .context("Failed to send request to Binance")?;
+        if !response.status().is_success() {
+            anyhow::bail!("HTTP error: {}", response.status().as_u16());
+        }
+
+        let time: BinanceServerTime = response
+            .json()
+            .await
+            .context("Failed to parse Binance server time")?;
+        Ok(time.server_time)
+    }
+
     /// HTTP 에러를 처리합니다
     fn handle_http_error(&self, status_code: u16) -> Result<PriceData> {
         match status_code {
@@ -174,45 +202,119 @@ impl BinanceClient {
         }
     }
 
-    /// 가격이 합리적인지 검증합니다
-    fn validate_price(&self, price: f64) -> Result<()> {
-        if price <= 0.0 {
-            anyhow::bail!("Invalid price: must be positive, got {}", price);
-        }
+    /// 가격이 pair의 허용 범위 안에 있는지 검증합니다
+    fn validate_price(&self, pair: &AssetPair, price: f64) -> Result<()> {
+        self.bands.check("binance", pair, price)
+    }
+}
 
-        if price < 1000.0 {
-            warn!("Unusually low BTC price: ${:.2}", price);
-        }
+impl Default for BinanceClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-        if price > 1_000_000.0 {
-            warn!("Unusually high BTC price: ${:.2}", price);
-        }
+#[async_trait]
+impl PriceProvider for BinanceClient {
+    async fn fetch_btc_price(&self) -> Result<PriceData> {
+        BinanceClient::fetch_btc_price(self).await
+    }
+    
+    fn name(&self) -> &str {
+        "binance"
+    }
+
+    // BTCUSDT 1분봉만 조회 (USDT를 USD로 취급)
+    fn supported_pairs(&self) -> Vec<AssetPair> {
+        vec![AssetPair::btc_usd()]
+    }
+}
+
+#[async_trait]
+impl TimeReference for BinanceClient {
+    fn source(&self) -> TimeSource {
+        TimeSource::Binance
+    }
 
-        Ok(())
+    async fn server_time_millis(&self) -> Result<i64> {
+        BinanceClient::server_time_millis(self).await
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::price_band::{OutOfBandAction, PriceBand};
 
     #[test]
     fn test_client_creation() {
-        let client = BinanceClient::new();
+        let _client = BinanceClient::new();
         // 클라이언트가 성공적으로 생성되는지 확인 (단순히 패닉 없이 생성되면 OK)
         // HTTP 클라이언트가 정상적으로 생성되었는지만 확인
     }
 
+    #[test]
+    fn test_supported_pairs_default_btc_usd() {
+        let client = BinanceClient::new();
+        assert_eq!(client.supported_pairs(), vec![AssetPair::btc_usd()]);
+    }
+
     #[test]
     fn test_price_validation() {
         let client = BinanceClient::new();
 
         // 정상적인 가격
-        assert!(client.validate_price(50000.0).is_ok());
+        assert!(client.validate_price(&AssetPair::btc_usd(), 50000.0).is_ok());
 
         // 비정상적인 가격들
-        assert!(client.validate_price(0.0).is_err());
-        assert!(client.validate_price(-100.0).is_err());
+        assert!(client.validate_price(&AssetPair::btc_usd(), 0.0).is_err());
+        assert!(client.validate_price(&AssetPair::btc_usd(), -100.0).is_err());
+    }
+
+    #[test]
+    fn test_price_validation_uses_configured_band() {
+        let band = PriceBand::new(40_000.0, 60_000.0).unwrap();
+        let bands = PriceBands::default().with_band(AssetPair::btc_usd(), band);
+
+        // 기본은 경고만 하고 통과
+        let client = BinanceClient::new().with_price_bands(bands.clone());
+        assert!(client.validate_price(&AssetPair::btc_usd(), 70_000.0).is_ok());
+
+        let client =
+            BinanceClient::new().with_price_bands(bands.with_action(OutOfBandAction::Reject));
+        assert!(client.validate_price(&AssetPair::btc_usd(), 50_000.0).is_ok());
+        assert!(client.validate_price(&AssetPair::btc_usd(), 70_000.0).is_err());
+        assert!(client.validate_price(&AssetPair::btc_usd(), 30_000.0).is_err());
+    }
+
+    #[test]
+    fn test_close_price_rounding_modes() {
+        let cents = |mode| {
+            BinanceClient::new()
+                .with_rounding_mode(mode)
+                .close_to_price("50000.50500000")
+                .unwrap()
+                .mantissa()
+        };
+        assert_eq!(cents(RoundingMode::Floor), 5_000_050);
+        assert_eq!(cents(RoundingMode::Ceil), 5_000_051);
+        assert_eq!(cents(RoundingMode::Nearest), 5_000_051);
+        assert_eq!(cents(RoundingMode::HalfEven), 5_000_050);
+
+        // 기본값은 nearest
+        let client = BinanceClient::new();
+        assert_eq!(
+            client.close_to_price("50000.505").unwrap().mantissa(),
+            5_000_051
+        );
+        assert!(client.close_to_price("not a price").is_err());
+    }
+
+    #[test]
+    fn test_server_time_parsing() {
+        let time: BinanceServerTime =
+            serde_json::from_str(r#"{"serverTime":1700000000123}"#).unwrap();
+        assert_eq!(time.server_time, 1_700_000_000_123);
     }
 
     #[test]
@@ -234,9 +336,12 @@ mod tests {
 
         match result {
             Ok(price_data) => {
-                assert!(price_data.price > 0.0);
+                assert!(!price_data.price.is_zero());
                 assert_eq!(price_data.source, "binance");
-                println!("Real BTC price: ${:.2}", price_data.price);
+                println!(
+                    "Real BTC price: ${:.2}",
+                    price_data.price.to_f64_dollars()
+                );
             }
             Err(e) => {
                 println!("API call failed (this might be expected): {}", e);
@@ -244,14 +349,3 @@ mod tests {
         }
     }
 }
-
-#[async_trait]
-impl PriceProvider for BinanceClient {
-    async fn fetch_btc_price(&self) -> Result<PriceData> {
-        self.fetch_btc_price_with_retry(MAX_RETRIES).await
-    }
-    
-    fn name(&self) -> &str {
-        "binance"
-    }
-}
@@ -0,0 +1,288 @@
+use crate::backoff::EXCHANGE_BACKOFF;
+use crate::price_band::PriceBands;
+use crate::price_provider::PriceProvider;
+use oracle_vm_common::types::{PriceData, AssetPair};
+use oracle_vm_common::{Price, RoundingMode};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::DateTime;
+use reqwest::Client;
+use serde::Deserialize;
+use std::time::Duration;
+use tracing::info;
+
+/// 바이낸스 선물(USDⓈ-M) mark/index 가격 URL
+const BINANCE_FUTURES_API_URL: &str = "https://fapi.binance.com/fapi/v1/premiumIndex";
+/// 조회하는 무기한 선물 심볼
+const SYMBOL: &str = "BTCUSDT";
+/// 제출하는 source 이름 (현물 "binance"와 구분)
+pub const BINANCE_FUTURES_SOURCE: &str = "binance-futures";
+/// HTTP 요청 타임아웃 (초)
+const REQUEST_TIMEOUT: u64 = 10;
+
+/// premiumIndex 응답 (가격은 문자열, 시각은 Unix 밀리초)
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[allow(dead_code)] // 응답 형식 전체를 역직렬화 (mark 가격과 시각만 사용)
+struct PremiumIndex {
+    symbol: String,
+    mark_price: String,
+    index_price: String,
+    estimated_settle_price: String,
+    last_funding_rate: String,
+    interest_rate: String,
+    next_funding_time: i64,
+    time: i64,
+}
+
+/// 바이낸스 선물의 BTCUSDT 무기한 mark 가격을 가져오는 클라이언트
+///
+/// 현물 종가가 아니라 무기한 선물 청산 기준인 mark 가격이 필요한 소비자를 위한 것으로,
+/// source는 "binance-futures"라서 현물 `BinanceClient`("binance")와 따로 집계된다.
+pub struct BinanceFuturesClient {
+    client: Client,
+    rounding: RoundingMode,
+    bands: PriceBands,
+}
+
+impl BinanceFuturesClient {
+    /// 새로운 바이낸스 선물 클라이언트를 만듭니다
+    pub fn new() -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(REQUEST_TIMEOUT))
+            .user_agent("OracleVM/1.0")
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self {
+            client,
+            rounding: RoundingMode::default(),
+            bands: PriceBands::default(),
+        }
+    }
+
+    /// mark 가격을 센트로 바꿀 때의 반올림 방식 지정 (기본값: nearest)
+    pub fn with_rounding_mode(mut self, rounding: RoundingMode) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// pair별 가격 허용 범위와 범위를 벗어났을 때의 처리 지정 (기본값: BTC/USD 1,000~1,000,000 USD, 경고)
+    pub fn with_price_bands(mut self, bands: PriceBands) -> Self {
+        self.bands = bands;
+        self
+    }
+
+    /// 비트코인 mark 가격을 가져옵니다 (재시도 포함)
+    pub async fn fetch_btc_price(&self) -> Result<PriceData> {
+        let price_data = EXCHANGE_BACKOFF
+            .retry("BTC mark price from Binance futures", || self.fetch_btc_price_once())
+            .await?;
+        info!(
+            "✅ Successfully fetched BTC mark price from Binance futures: ${}",
+            price_data.price
+        );
+        Ok(price_data)
+    }
+
+    /// 한 번만 가격을 가져오기 (재시도 없음)
+    async fn fetch_btc_price_once(&self) -> Result<PriceData> {
+        let url = format!("{}?symbol={}", BINANCE_FUTURES_API_URL, SYMBOL);
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .context("Failed to send request to Binance futures")?;
+
+        if !response.status().is_success() {
+            return self.handle_http_error(response.status().as_u16());
+        }
+
+        let index: PremiumIndex = response
+            .json()
+            .await
+            .context("Failed to parse Binance futures premiumIndex response")?;
+
+        self.parse_premium_index(&index)
+    }
+
+    /// premiumIndex 응답에서 mark 가격을 꺼내 `PriceData`로 변환
+    ///
+    /// 타임스탬프는 로컬 시각이 아니라 응답의 `time`(mark 가격 계산 시각)을 사용합니다.
+    fn parse_premium_index(&self, index: &PremiumIndex) -> Result<PriceData> {
+        if index.symbol != SYMBOL {
+            anyhow::bail!(
+                "Unexpected symbol {} in Binance futures response (expected {})",
+                index.symbol,
+                SYMBOL
+            );
+        }
+        let mark_price = index
+            .mark_price
+            .parse::<f64>()
+            .context("Failed to parse mark price as number")?;
+        let timestamp = DateTime::from_timestamp_millis(index.time)
+            .ok_or_else(|| anyhow::anyhow!("Invalid premiumIndex time {}", index.time))?;
+
+        info!(
+            "📊 Binance futures mark: {:.2} USD (index {}, time: {})",
+            mark_price,
+            index.index_price,
+            timestamp.format("%H:%M:%S")
+        );
+
+        self.validate_price(&AssetPair::btc_usd(), mark_price)?;
+
+        Ok(PriceData {
+            pair: AssetPair::btc_usd(),
+            price: self.mark_to_price(&index.mark_price)?, // cents
+            timestamp,
+            volume: None,
+            source: BINANCE_FUTURES_SOURCE.to_string(),
+        })
+    }
+
+    /// mark 가격 문자열을 f64를 거치지 않고 센트로 변환
+    fn mark_to_price(&self, mark: &str) -> Result<Price> {
+        Price::parse_decimal(mark, Price::USD_DECIMALS, self.rounding)
+            .with_context(|| format!("Failed to convert mark price {} to cents", mark))
+    }
+
+    /// HTTP 에러를 처리합니다
+    fn handle_http_error(&self, status_code: u16) -> Result<PriceData> {
+        match status_code {
+            400 => anyhow::bail!("Bad request - Check symbol ({})", SYMBOL),
+            418 | 429 => anyhow::bail!("Rate limit exceeded - Too many requests"),
+            451 => anyhow::bail!("Unavailable for legal reasons - Binance futures is restricted in this region"),
+            500..=599 => anyhow::bail!("Binance futures server error - Try again later"),
+            _ => anyhow::bail!("HTTP error: {}", status_code),
+        }
+    }
+
+    /// 가격이 pair의 허용 범위 안에 있는지 검증합니다
+    fn validate_price(&self, pair: &AssetPair, price: f64) -> Result<()> {
+        self.bands.check(BINANCE_FUTURES_SOURCE, pair, price)
+    }
+}
+
+impl Default for BinanceFuturesClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl PriceProvider for BinanceFuturesClient {
+    async fn fetch_btc_price(&self) -> Result<PriceData> {
+        BinanceFuturesClient::fetch_btc_price(self).await
+    }
+
+    fn name(&self) -> &str {
+        BINANCE_FUTURES_SOURCE
+    }
+
+    // BTCUSDT 무기한 선물만 조회 (USDT를 USD로 취급)
+    fn supported_pairs(&self) -> Vec<AssetPair> {
+        vec![AssetPair::btc_usd()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::price_band::{OutOfBandAction, PriceBand};
+
+    // fapi.binance.com/fapi/v1/premiumIndex?symbol=BTCUSDT 응답을 그대로 저장한 것
+    const CAPTURED_PREMIUM_INDEX: &str = r#"{"symbol":"BTCUSDT","markPrice":"67012.34500000","indexPrice":"67020.11212766","estimatedSettlePrice":"67015.66791236","lastFundingRate":"0.00010000","interestRate":"0.00010000","nextFundingTime":1718697600000,"time":1718686803000}"#;
+
+    fn captured() -> PremiumIndex {
+        serde_json::from_str(CAPTURED_PREMIUM_INDEX).unwrap()
+    }
+
+    #[test]
+    fn test_captured_premium_index_parses_mark_price() {
+        let price_data = BinanceFuturesClient::new()
+            .parse_premium_index(&captured())
+            .unwrap();
+
+        assert_eq!(price_data.pair, AssetPair::btc_usd());
+        // 현물과 구분되는 source
+        assert_eq!(price_data.source, "binance-futures");
+        // index가 아니라 mark 가격 (nearest 반올림)
+        assert_eq!(price_data.price, Price::from_cents(6_701_235));
+        assert_eq!(price_data.timestamp.timestamp_millis(), 1_718_686_803_000);
+        assert_eq!(price_data.volume, None);
+    }
+
+    #[test]
+    fn test_mark_price_rounding_modes() {
+        let cents = |mode| {
+            BinanceFuturesClient::new()
+                .with_rounding_mode(mode)
+                .parse_premium_index(&captured())
+                .unwrap()
+                .price
+                .mantissa()
+        };
+        assert_eq!(cents(RoundingMode::Floor), 6_701_234);
+        assert_eq!(cents(RoundingMode::Ceil), 6_701_235);
+        assert_eq!(cents(RoundingMode::HalfEven), 6_701_234);
+    }
+
+    #[test]
+    fn test_unexpected_or_malformed_payloads_are_rejected() {
+        let client = BinanceFuturesClient::new();
+
+        let eth = PremiumIndex {
+            symbol: "ETHUSDT".to_string(),
+            ..captured()
+        };
+        assert!(client.parse_premium_index(&eth).is_err());
+
+        let garbled = PremiumIndex {
+            mark_price: "not a price".to_string(),
+            ..captured()
+        };
+        assert!(client.parse_premium_index(&garbled).is_err());
+
+        // markPrice가 빠진 응답은 역직렬화부터 실패
+        let missing = CAPTURED_PREMIUM_INDEX.replace(r#""markPrice":"67012.34500000","#, "");
+        assert!(serde_json::from_str::<PremiumIndex>(&missing).is_err());
+    }
+
+    #[test]
+    fn test_price_validation_uses_configured_band() {
+        let band = PriceBand::new(70_000.0, 80_000.0).unwrap();
+        let bands = PriceBands::default()
+            .with_band(AssetPair::btc_usd(), band)
+            .with_action(OutOfBandAction::Reject);
+        let client = BinanceFuturesClient::new().with_price_bands(bands);
+        assert!(client.parse_premium_index(&captured()).is_err());
+    }
+
+    #[test]
+    fn test_provider_name_and_pairs() {
+        let client = BinanceFuturesClient::new();
+        assert_eq!(client.name(), "binance-futures");
+        assert_eq!(client.supported_pairs(), vec![AssetPair::btc_usd()]);
+        assert!(client.handle_http_error(451).is_err());
+    }
+
+    // 실제 API 호출 테스트 (인터넷 연결 필요)
+    #[tokio::test]
+    #[ignore] // cargo test --ignored 로만 실행
+    async fn test_real_api_call() {
+        let client = BinanceFuturesClient::new();
+        match client.fetch_btc_price().await {
+            Ok(price_data) => {
+                assert!(!price_data.price.is_zero());
+                assert_eq!(price_data.source, "binance-futures");
+            }
+            Err(e) => {
+                println!("API call failed (this might be expected): {}", e);
+            }
+        }
+    }
+}
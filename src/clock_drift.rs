@@ -0,0 +1,386 @@
+//! 로컬 시계 오차 감시 (Aggregator·거래소 시각과 비교)
+//!
+//! Aggregator 응답(`HealthResponse`/`PriceResponse`)의 `timestamp`와 바이낸스 서버 시각을 받을 때마다
+//! 요청 왕복의 중간 시점과 비교해 오차 표본을 쌓고, 최근 표본의 중간값을 추정 오차로 사용한다.
+//! 추정 오차가 경고 기준을 넘으면 경고하고 거부 기준을 넘으면 제출을 거부한다.
+//! 거부 중에도 하트비트와 거래소 시각 확인은 계속되므로 시계가 맞춰지면 제출이 다시 허용된다.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::Utc;
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info, warn};
+
+use crate::shutdown::ShutdownSignal;
+
+/// 기본 경고 기준
+pub const DEFAULT_SOFT_DRIFT: Duration = Duration::from_secs(2);
+/// 기본 제출 거부 기준
+pub const DEFAULT_HARD_DRIFT: Duration = Duration::from_secs(10);
+/// 기본 거래소 시각 확인 주기
+pub const DEFAULT_DRIFT_PROBE_INTERVAL: Duration = Duration::from_secs(300);
+/// 추정에 사용하는 최근 표본 수
+const MAX_SAMPLES: usize = 8;
+/// 이보다 오래 걸린 왕복은 중간 시점을 믿을 수 없으므로 표본에서 제외 (밀리초)
+const MAX_ROUND_TRIP_MS: i64 = 2_000;
+
+/// 비교 대상 시각의 출처
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeSource {
+    Aggregator,
+    Binance,
+}
+
+impl fmt::Display for TimeSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeSource::Aggregator => write!(f, "aggregator"),
+            TimeSource::Binance => write!(f, "binance"),
+        }
+    }
+}
+
+/// 추정 오차 수준
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DriftLevel {
+    /// 기준 이내 (표본이 없는 경우 포함)
+    Ok,
+    /// 경고 기준 초과
+    Warn,
+    /// 거부 기준 초과 (제출하지 않음)
+    Refuse,
+}
+
+/// 시계 오차가 거부 기준을 넘어 제출하지 않음
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Local clock is off by {offset_ms} ms (limit {limit_ms} ms), refusing to submit")]
+pub struct ClockDriftError {
+    pub offset_ms: i64,
+    pub limit_ms: i64,
+}
+
+/// 시계 오차를 확인할 기준 시각 (거래소 서버 시각 등)
+#[async_trait]
+pub trait TimeReference: Send + Sync {
+    /// 기준 시각의 출처
+    fn source(&self) -> TimeSource;
+
+    /// 기준 시각 (Unix 밀리초)
+    async fn server_time_millis(&self) -> Result<i64>;
+}
+
+#[derive(Debug)]
+struct State {
+    samples: VecDeque<i64>,
+    level: DriftLevel,
+}
+
+/// 시계 오차 추정기 (복제본은 표본을 공유)
+#[derive(Debug, Clone)]
+pub struct DriftMonitor {
+    state: Arc<Mutex<State>>,
+    soft_ms: i64,
+    hard_ms: i64,
+    correct: bool,
+}
+
+impl Default for DriftMonitor {
+    fn default() -> Self {
+        Self::new(DEFAULT_SOFT_DRIFT, DEFAULT_HARD_DRIFT)
+    }
+}
+
+impl DriftMonitor {
+    /// 경고 기준과 거부 기준으로 생성
+    pub fn new(soft: Duration, hard: Duration) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(State {
+                samples: VecDeque::with_capacity(MAX_SAMPLES),
+                level: DriftLevel::Ok,
+            })),
+            soft_ms: soft.as_millis() as i64,
+            hard_ms: hard.as_millis() as i64,
+            correct: false,
+        }
+    }
+
+    /// 제출 타임스탬프에 추정 오차를 더해 보정
+    pub fn with_correction(mut self, enabled: bool) -> Self {
+        self.correct = enabled;
+        self
+    }
+
+    /// 밀리초 단위 원격 시각 표본 기록
+    ///
+    /// `sent_millis`/`received_millis`는 요청을 보내고 응답을 받은 로컬 시각이며 원격 시각은 그 중간에
+    /// 찍힌 것으로 본다. 왕복이 너무 오래 걸린 표본은 버린다. 기록 후의 오차 수준을 반환한다.
+    pub fn observe(
+        &self,
+        source: TimeSource,
+        remote_millis: i64,
+        sent_millis: i64,
+        received_millis: i64,
+    ) -> DriftLevel {
+        let round_trip = received_millis - sent_millis;
+        if !(0..=MAX_ROUND_TRIP_MS).contains(&round_trip) {
+            debug!(
+                "🕰️ Ignoring {} time sample with {} ms round trip",
+                source, round_trip
+            );
+            return self.level();
+        }
+        let offset = remote_millis - (sent_millis + round_trip / 2);
+
+        let mut state = self.state.lock().unwrap();
+        if state.samples.len() == MAX_SAMPLES {
+            state.samples.pop_front();
+        }
+        state.samples.push_back(offset);
+        let estimate = median(&state.samples);
+        debug!(
+            "🕰️ {} clock sample: {:+} ms (estimate {:+} ms)",
+            source, offset, estimate
+        );
+
+        let level = self.classify(estimate);
+        if level != state.level {
+            match level {
+                DriftLevel::Ok => info!("✅ Local clock back within {} ms", self.soft_ms),
+                DriftLevel::Warn => warn!(
+                    "⏰ Local clock is off by {:+} ms (warn above {} ms)",
+                    estimate, self.soft_ms
+                ),
+                DriftLevel::Refuse => error!(
+                    "🚨 Local clock is off by {:+} ms, refusing to submit until it is within {} ms",
+                    estimate, self.hard_ms
+                ),
+            }
+            state.level = level;
+        }
+        level
+    }
+
+    /// 초 단위 원격 시각 표본 기록 (Aggregator 응답의 `timestamp`, 0이면 무시)
+    ///
+    /// 초 단위로 잘린 시각이므로 그 초의 중간으로 본다.
+    pub fn observe_secs(
+        &self,
+        source: TimeSource,
+        remote_secs: u64,
+        sent_millis: i64,
+        received_millis: i64,
+    ) -> DriftLevel {
+        if remote_secs == 0 {
+            return self.level();
+        }
+        let remote_millis = (remote_secs as i64).saturating_mul(1_000) + 500;
+        self.observe(source, remote_millis, sent_millis, received_millis)
+    }
+
+    /// 기준 시각을 한 번 확인해 표본 기록
+    pub async fn probe(&self, reference: &dyn TimeReference) -> Result<DriftLevel> {
+        let sent = local_millis();
+        let remote = reference.server_time_millis().await?;
+        let received = local_millis();
+        Ok(self.observe(reference.source(), remote, sent, received))
+    }
+
+    /// 추정 오차 (원격 - 로컬, 밀리초, 표본이 없으면 None)
+    pub fn offset_millis(&self) -> Option<i64> {
+        let state = self.state.lock().unwrap();
+        (!state.samples.is_empty()).then(|| median(&state.samples))
+    }
+
+    /// 현재 오차 수준
+    pub fn level(&self) -> DriftLevel {
+        self.state.lock().unwrap().level
+    }
+
+    /// 제출해도 되는지 (거부 기준을 넘었으면 에러)
+    pub fn check(&self) -> Result<(), ClockDriftError> {
+        match self.offset_millis() {
+            Some(offset_ms) if self.classify(offset_ms) == DriftLevel::Refuse => {
+                Err(ClockDriftError {
+                    offset_ms,
+                    limit_ms: self.hard_ms,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// 로컬 시각으로 찍은 초 단위 타임스탬프를 원격 시각 기준으로 보정 (보정을 끄면 그대로)
+    pub fn corrected_timestamp(&self, local_secs: u64) -> u64 {
+        match self.offset_millis() {
+            Some(offset) if self.correct => {
+                let offset_secs = (offset as f64 / 1_000.0).round() as i64;
+                local_secs.saturating_add_signed(offset_secs)
+            }
+            _ => local_secs,
+        }
+    }
+
+    /// 종료가 요청될 때까지 `interval`마다 기준 시각 확인 (실패는 기록만 하고 계속)
+    pub fn spawn_probe(
+        &self,
+        reference: Arc<dyn TimeReference>,
+        interval: Duration,
+        mut shutdown: ShutdownSignal,
+    ) -> JoinHandle<()> {
+        let monitor = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                tokio::select! {
+                    _ = shutdown.wait() => break,
+                    _ = ticker.tick() => {}
+                }
+                if let Err(e) = monitor.probe(reference.as_ref()).await {
+                    warn!("⚠️ Cannot read {} server time: {:#}", reference.source(), e);
+                }
+            }
+        })
+    }
+
+    fn classify(&self, offset_ms: i64) -> DriftLevel {
+        let drift = offset_ms.abs();
+        if drift > self.hard_ms {
+            DriftLevel::Refuse
+        } else if drift > self.soft_ms {
+            DriftLevel::Warn
+        } else {
+            DriftLevel::Ok
+        }
+    }
+}
+
+/// 현재 로컬 시각 (Unix 밀리초)
+pub fn local_millis() -> i64 {
+    Utc::now().timestamp_millis()
+}
+
+// 표본 중간값 (짝수 개면 가운데 두 값의 평균)
+fn median(samples: &VecDeque<i64>) -> i64 {
+    let mut sorted: Vec<i64> = samples.iter().copied().collect();
+    sorted.sort_unstable();
+    let mid = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        (sorted[mid - 1] + sorted[mid]) / 2
+    } else {
+        sorted[mid]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOCAL: i64 = 1_700_000_000_000;
+
+    // 로컬 시계보다 `offset_ms`만큼 앞선 원격 시각을 100 ms 왕복으로 관측
+    fn observe_skewed(monitor: &DriftMonitor, offset_ms: i64) -> DriftLevel {
+        monitor.observe(
+            TimeSource::Aggregator,
+            LOCAL + 50 + offset_ms,
+            LOCAL,
+            LOCAL + 100,
+        )
+    }
+
+    #[test]
+    fn test_levels_follow_estimated_offset() {
+        let monitor = DriftMonitor::default();
+        assert_eq!(monitor.level(), DriftLevel::Ok);
+        assert_eq!(monitor.offset_millis(), None);
+        assert!(monitor.check().is_ok());
+
+        assert_eq!(observe_skewed(&monitor, 500), DriftLevel::Ok);
+        assert_eq!(monitor.offset_millis(), Some(500));
+
+        // 표본이 쌓여 중간값이 기준을 넘을 때 전환
+        for _ in 0..2 {
+            observe_skewed(&monitor, 4_000);
+        }
+        assert_eq!(monitor.level(), DriftLevel::Warn);
+        assert!(monitor.check().is_ok());
+
+        for _ in 0..MAX_SAMPLES {
+            observe_skewed(&monitor, -15_000);
+        }
+        assert_eq!(monitor.level(), DriftLevel::Refuse);
+        assert_eq!(
+            monitor.check(),
+            Err(ClockDriftError {
+                offset_ms: -15_000,
+                limit_ms: 10_000
+            })
+        );
+
+        // 시계가 맞춰지면 다시 제출 가능
+        for _ in 0..MAX_SAMPLES {
+            observe_skewed(&monitor, 100);
+        }
+        assert_eq!(monitor.level(), DriftLevel::Ok);
+        assert!(monitor.check().is_ok());
+    }
+
+    #[test]
+    fn test_slow_round_trips_and_missing_timestamps_are_ignored() {
+        let monitor = DriftMonitor::default();
+        monitor.observe(
+            TimeSource::Binance,
+            LOCAL + 60_000,
+            LOCAL,
+            LOCAL + MAX_ROUND_TRIP_MS + 1,
+        );
+        monitor.observe_secs(TimeSource::Aggregator, 0, LOCAL, LOCAL + 10);
+        assert_eq!(monitor.offset_millis(), None);
+
+        // 초 단위 시각은 그 초의 중간으로 봄
+        monitor.observe_secs(TimeSource::Aggregator, 1_700_000_003, LOCAL, LOCAL);
+        assert_eq!(monitor.offset_millis(), Some(3_500));
+    }
+
+    #[test]
+    fn test_correction_applies_rounded_offset_only_when_enabled() {
+        let monitor = DriftMonitor::default();
+        observe_skewed(&monitor, -4_600);
+        assert_eq!(monitor.corrected_timestamp(1_700_000_000), 1_700_000_000);
+
+        let corrected = monitor.clone().with_correction(true);
+        assert_eq!(corrected.corrected_timestamp(1_700_000_000), 1_699_999_995);
+        // 표본이 없으면 그대로
+        assert_eq!(
+            DriftMonitor::default()
+                .with_correction(true)
+                .corrected_timestamp(1_700_000_000),
+            1_700_000_000
+        );
+    }
+
+    struct SkewedReference(i64);
+
+    #[async_trait]
+    impl TimeReference for SkewedReference {
+        fn source(&self) -> TimeSource {
+            TimeSource::Binance
+        }
+
+        async fn server_time_millis(&self) -> Result<i64> {
+            Ok(local_millis() + self.0)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_probe_reads_reference_time() {
+        let monitor = DriftMonitor::default();
+        let level = monitor.probe(&SkewedReference(30_000)).await.unwrap();
+        assert_eq!(level, DriftLevel::Refuse);
+        let offset = monitor.offset_millis().unwrap();
+        assert!((29_000..=30_000).contains(&offset), "{}", offset);
+    }
+}
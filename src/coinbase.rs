@@ -1,17 +1,20 @@
+use crate::backoff::Backoff;
+use crate::price_band::PriceBands;
 use crate::price_provider::PriceProvider;
 use oracle_vm_common::types::{PriceData, AssetPair};
+use oracle_vm_common::{Price, RoundingMode};
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use chrono::DateTime;
 use reqwest::Client;
 use std::time::Duration;
-use tokio::time::sleep;
-use tracing::{error, info, warn};
+use tracing::{info, warn};
 
 /// Coinbase Pro API URL
 const COINBASE_API_URL: &str = "https://api.exchange.coinbase.com/products/BTC-USD/candles";
-/// 최대 재시도 횟수
-const MAX_RETRIES: u32 = 3;
+/// 재시도: 2초 간격으로 최대 2번
+const COINBASE_BACKOFF: Backoff =
+    Backoff::new(Duration::from_secs(2), Duration::from_secs(2), 2);
 /// HTTP 요청 타임아웃 (초)
 const REQUEST_TIMEOUT: u64 = 10;
 
@@ -22,6 +25,8 @@ type CoinbaseCandleResponse = Vec<Vec<f64>>;
 /// Coinbase Pro와 통신하는 클라이언트
 pub struct CoinbaseClient {
     client: Client,
+    rounding: RoundingMode,
+    bands: PriceBands,
 }
 
 impl CoinbaseClient {
@@ -33,46 +38,35 @@ impl CoinbaseClient {
             .build()
             .expect("Failed to create HTTP client");
 
-        Self { client }
+        Self {
+            client,
+            rounding: RoundingMode::default(),
+            bands: PriceBands::default(),
+        }
     }
 
-    /// 비트코인 가격을 가져옵니다 (재시도 포함)
-    pub async fn fetch_btc_price(&self) -> Result<PriceData> {
-        self.fetch_btc_price_with_retry(MAX_RETRIES).await
+    /// 종가를 센트로 바꿀 때의 반올림 방식 지정 (기본값: nearest)
+    pub fn with_rounding_mode(mut self, rounding: RoundingMode) -> Self {
+        self.rounding = rounding;
+        self
     }
 
-    /// 재시도 로직이 포함된 가격 가져오기
-    async fn fetch_btc_price_with_retry(&self, max_retries: u32) -> Result<PriceData> {
-        for attempt in 1..=max_retries {
-            info!(
-                "Fetching BTC price from Coinbase (attempt {}/{})",
-                attempt, max_retries
-            );
-
-            match self.fetch_btc_price_once().await {
-                Ok(price_data) => {
-                    info!(
-                        "✅ Successfully fetched BTC price from Coinbase: ${:.2}",
-                        price_data.price
-                    );
-                    return Ok(price_data);
-                }
-                Err(e) => {
-                    if attempt < max_retries {
-                        warn!(
-                            "❌ Failed to fetch price (attempt {}): {}. Retrying...",
-                            attempt, e
-                        );
-                        sleep(Duration::from_secs(2)).await;
-                    } else {
-                        error!("❌ All attempts failed: {}", e);
-                        return Err(e);
-                    }
-                }
-            }
-        }
+    /// pair별 가격 허용 범위와 범위를 벗어났을 때의 처리 지정 (기본값: BTC/USD 1,000~1,000,000 USD, 경고)
+    pub fn with_price_bands(mut self, bands: PriceBands) -> Self {
+        self.bands = bands;
+        self
+    }
 
-        unreachable!()
+    /// 비트코인 가격을 가져옵니다 (재시도 포함)
+    pub async fn fetch_btc_price(&self) -> Result<PriceData> {
+        let price_data = COINBASE_BACKOFF
+            .retry("BTC price from Coinbase", || self.fetch_btc_price_once())
+            .await?;
+        info!(
+            "✅ Successfully fetched BTC price from Coinbase: ${}",
+            price_data.price
+        );
+        Ok(price_data)
     }
 
     /// 실제 API 호출을 수행하는 함수
@@ -118,15 +112,13 @@ impl CoinbaseClient {
         // 타임스탬프 로깅
         let dt = chrono::DateTime::from_timestamp(timestamp as i64, 0).unwrap_or_default();
         info!(
-            "📊 Coinbase candle: {:.2} USD (time: {})",
-            close_price,
+            "📊 Coinbase candle: {} (time: {})",
+            format_price_with_precision(close_price),
             dt.format("%Y-%m-%d %H:%M:%S UTC")
         );
 
-        // 2/3 합의 시스템 추가 전까지 간단한 검증
-        if close_price <= 0.0 {
-            anyhow::bail!("Invalid price from Coinbase: {}", close_price);
-        }
+        // pair의 허용 범위로 검증
+        self.bands.check("coinbase", &AssetPair::btc_usd(), close_price)?;
 
         // timestamp가 10분 이상 오래된 경우 경고
         let now = chrono::Utc::now().timestamp() as u64;
@@ -139,7 +131,11 @@ impl CoinbaseClient {
 
         Ok(PriceData {
             pair: AssetPair::btc_usd(),
-            price: (close_price * 100.0) as u64, // Convert to cents
+            price: Price::from_f64_dollars_rounded(
+                close_price,
+                Price::USD_DECIMALS,
+                self.rounding,
+            )?, // cents
             timestamp: DateTime::from_timestamp(timestamp as i64, 0)
                 .unwrap_or_else(chrono::Utc::now),
             volume: None,
@@ -165,12 +161,17 @@ fn format_price_with_precision(price: f64) -> String {
 #[async_trait]
 impl PriceProvider for CoinbaseClient {
     async fn fetch_btc_price(&self) -> Result<PriceData> {
-        self.fetch_btc_price_with_retry(MAX_RETRIES).await
+        CoinbaseClient::fetch_btc_price(self).await
     }
     
     fn name(&self) -> &str {
         "coinbase"
     }
+
+    // BTC-USD 1분봉만 조회
+    fn supported_pairs(&self) -> Vec<AssetPair> {
+        vec![AssetPair::btc_usd()]
+    }
 }
 
 #[cfg(test)]
@@ -214,9 +215,12 @@ mod tests {
         
         match result {
             Ok(price_data) => {
-                assert!(price_data.price > 0.0);
+                assert!(!price_data.price.is_zero());
                 assert_eq!(price_data.source, "coinbase");
-                println!("Real BTC price from Coinbase: ${:.2}", price_data.price);
+                println!(
+                    "Real BTC price from Coinbase: ${:.2}",
+                    price_data.price.to_f64_dollars()
+                );
             }
             Err(e) => {
                 println!("Coinbase API call failed (this might be expected): {}", e);
@@ -25,11 +25,11 @@ impl ConsensusManager {
         }
         
         // 가격만 추출 (cents를 다시 달러로 변환)
-        let mut price_values: Vec<f64> = prices.iter().map(|p| p.price as f64 / 100.0).collect();
+        let mut price_values: Vec<f64> = prices.iter().map(|p| p.price.to_f64_dollars()).collect();
         price_values.sort_by(|a, b| a.partial_cmp(b).unwrap());
         
         // 중간값 계산
-        let median = if price_values.len() % 2 == 0 {
+        let median = if price_values.len().is_multiple_of(2) {
             let mid = price_values.len() / 2;
             (price_values[mid - 1] + price_values[mid]) / 2.0
         } else {
@@ -81,10 +81,10 @@ impl ConsensusManager {
             return vec![];
         }
         
-        let mut price_values: Vec<f64> = prices.iter().map(|p| p.price as f64 / 100.0).collect();
+        let mut price_values: Vec<f64> = prices.iter().map(|p| p.price.to_f64_dollars()).collect();
         price_values.sort_by(|a, b| a.partial_cmp(b).unwrap());
         
-        let median = if price_values.len() % 2 == 0 {
+        let median = if price_values.len().is_multiple_of(2) {
             let mid = price_values.len() / 2;
             (price_values[mid - 1] + price_values[mid]) / 2.0
         } else {
@@ -94,7 +94,7 @@ impl ConsensusManager {
         prices
             .iter()
             .filter(|p| {
-                let price_usd = p.price as f64 / 100.0;
+                let price_usd = p.price.to_f64_dollars();
                 let deviation = ((price_usd - median) / median).abs();
                 deviation > self.max_price_deviation
             })
@@ -112,8 +112,9 @@ impl Default for ConsensusManager {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::{DateTime, Utc};
+    use chrono::DateTime;
     use oracle_vm_common::types::AssetPair;
+    use oracle_vm_common::Price;
     
     #[test]
     fn test_consensus_with_all_valid_prices() {
@@ -122,21 +123,21 @@ mod tests {
         let prices = vec![
             PriceData {
                 pair: AssetPair::btc_usd(),
-                price: 7000000, // $70,000 in cents
+                price: Price::from_cents(7000000), // $70,000 in cents
                 timestamp: DateTime::from_timestamp(1700000000, 0).unwrap(),
                 volume: None,
                 source: "binance".to_string(),
             },
             PriceData {
                 pair: AssetPair::btc_usd(),
-                price: 7010000, // $70,100 in cents
+                price: Price::from_cents(7010000), // $70,100 in cents
                 timestamp: DateTime::from_timestamp(1700000000, 0).unwrap(),
                 volume: None,
                 source: "coinbase".to_string(),
             },
             PriceData {
                 pair: AssetPair::btc_usd(),
-                price: 7005000, // $70,050 in cents
+                price: Price::from_cents(7005000), // $70,050 in cents
                 timestamp: DateTime::from_timestamp(1700000000, 0).unwrap(),
                 volume: None,
                 source: "kraken".to_string(),
@@ -157,21 +158,21 @@ mod tests {
         let prices = vec![
             PriceData {
                 pair: AssetPair::btc_usd(),
-                price: 7000000, // $70,000 in cents
+                price: Price::from_cents(7000000), // $70,000 in cents
                 timestamp: DateTime::from_timestamp(1700000000, 0).unwrap(),
                 volume: None,
                 source: "binance".to_string(),
             },
             PriceData {
                 pair: AssetPair::btc_usd(),
-                price: 7010000, // $70,100 in cents
+                price: Price::from_cents(7010000), // $70,100 in cents
                 timestamp: DateTime::from_timestamp(1700000000, 0).unwrap(),
                 volume: None,
                 source: "coinbase".to_string(),
             },
             PriceData {
                 pair: AssetPair::btc_usd(),
-                price: 7500000, // $75,000 in cents - Outlier (>7% deviation)
+                price: Price::from_cents(7500000), // $75,000 in cents - Outlier (>7% deviation)
                 timestamp: DateTime::from_timestamp(1700000000, 0).unwrap(),
                 volume: None,
                 source: "kraken".to_string(),
@@ -202,21 +203,21 @@ mod tests {
         let prices = vec![
             PriceData {
                 pair: AssetPair::btc_usd(),
-                price: 7000000, // $70,000 in cents
+                price: Price::from_cents(7000000), // $70,000 in cents
                 timestamp: DateTime::from_timestamp(1700000000, 0).unwrap(),
                 volume: None,
                 source: "binance".to_string(),
             },
             PriceData {
                 pair: AssetPair::btc_usd(),
-                price: 7500000, // $75,000 in cents - Too different
+                price: Price::from_cents(7500000), // $75,000 in cents - Too different
                 timestamp: DateTime::from_timestamp(1700000000, 0).unwrap(),
                 volume: None,
                 source: "coinbase".to_string(),
             },
             PriceData {
                 pair: AssetPair::btc_usd(),
-                price: 8000000, // $80,000 in cents - Too different
+                price: Price::from_cents(8000000), // $80,000 in cents - Too different
                 timestamp: DateTime::from_timestamp(1700000000, 0).unwrap(),
                 volume: None,
                 source: "kraken".to_string(),
@@ -234,21 +235,21 @@ mod tests {
         let prices = vec![
             PriceData {
                 pair: AssetPair::btc_usd(),
-                price: 7000000, // $70,000 in cents
+                price: Price::from_cents(7000000), // $70,000 in cents
                 timestamp: DateTime::from_timestamp(1700000000, 0).unwrap(),
                 volume: None,
                 source: "binance".to_string(),
             },
             PriceData {
                 pair: AssetPair::btc_usd(),
-                price: 7010000, // $70,100 in cents
+                price: Price::from_cents(7010000), // $70,100 in cents
                 timestamp: DateTime::from_timestamp(1700000000, 0).unwrap(),
                 volume: None,
                 source: "coinbase".to_string(),
             },
             PriceData {
                 pair: AssetPair::btc_usd(),
-                price: 7500000, // $75,000 in cents - Outlier
+                price: Price::from_cents(7500000), // $75,000 in cents - Outlier
                 timestamp: DateTime::from_timestamp(1700000000, 0).unwrap(),
                 volume: None,
                 source: "kraken".to_string(),
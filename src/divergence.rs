@@ -0,0 +1,225 @@
+//! 로컬 중간값과 Aggregator가 돌려준 네트워크 중간값의 괴리 감시
+//!
+//! 제출이 성공할 때마다 응답의 집계 가격을 이번 라운드의 로컬 중간값과 비교해 차이(bps)를 기록한다.
+//! 차이가 기준을 넘은 라운드가 `rounds`번 연속되면 한 번 에러로 알리고, 기준 안으로 돌아오면 회복을
+//! 기록한 뒤 처음부터 다시 센다. 노드의 거래소 수집이 고장난 경우와 Aggregator가 잘못된 값을
+//! 돌려주는 경우를 모두 잡는다.
+
+use oracle_vm_common::Price;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use tracing::{error, info, warn};
+
+/// 기본 괴리 기준 (bps)
+pub const DEFAULT_DIVERGENCE_BPS: u32 = 100;
+/// 기본 연속 라운드 수
+pub const DEFAULT_DIVERGENCE_ROUNDS: u32 = 3;
+/// 평균 괴리를 계산하는 최근 라운드 수
+const ROLLING_ROUNDS: usize = 20;
+
+/// 한 라운드의 비교 결과
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Divergence {
+    /// 기준 이내
+    Within { bps: f64 },
+    /// 기준을 넘음 (연속 횟수가 모자라거나 이미 알린 경우)
+    Diverging { bps: f64, consecutive: u32 },
+    /// 이번 라운드로 연속 횟수를 채워 알림
+    Alert { bps: f64, consecutive: u32 },
+}
+
+/// 괴리 감시 상태 (`/status`에 노출)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DivergenceReport {
+    pub threshold_bps: u32,
+    pub rounds: u32,
+    /// 마지막 라운드의 괴리 (bps)
+    pub last_bps: Option<f64>,
+    /// 최근 라운드의 평균 괴리 (bps)
+    pub rolling_bps: Option<f64>,
+    /// 기준을 넘은 연속 라운드 수
+    pub consecutive: u32,
+    /// 알린 뒤 아직 회복하지 않았는지
+    pub alerting: bool,
+    /// 지금까지 알린 횟수
+    pub alerts: u64,
+}
+
+/// 로컬 중간값 대 네트워크 중간값 괴리 감시기
+#[derive(Debug, Clone)]
+pub struct DivergenceMonitor {
+    threshold_bps: u32,
+    rounds: u32,
+    recent: VecDeque<f64>,
+    consecutive: u32,
+    alerting: bool,
+    alerts: u64,
+}
+
+impl Default for DivergenceMonitor {
+    fn default() -> Self {
+        Self::new(DEFAULT_DIVERGENCE_BPS, DEFAULT_DIVERGENCE_ROUNDS)
+    }
+}
+
+impl DivergenceMonitor {
+    /// 괴리가 `threshold_bps`를 넘은 라운드가 `rounds`번 연속되면 알림 (`rounds`는 최소 1)
+    pub fn new(threshold_bps: u32, rounds: u32) -> Self {
+        Self {
+            threshold_bps,
+            rounds: rounds.max(1),
+            recent: VecDeque::with_capacity(ROLLING_ROUNDS),
+            consecutive: 0,
+            alerting: false,
+            alerts: 0,
+        }
+    }
+
+    /// 로컬 중간값과 Aggregator가 돌려준 집계 가격 비교
+    pub fn observe(&mut self, local: Price, network: Price) -> Divergence {
+        let bps = divergence_bps(local, network);
+        if self.recent.len() == ROLLING_ROUNDS {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(bps);
+
+        if bps <= self.threshold_bps as f64 {
+            if self.alerting {
+                info!(
+                    "✅ Aggregator median ${} back within {} bps of local ${} ({:.1} bps)",
+                    network, self.threshold_bps, local, bps
+                );
+            }
+            self.consecutive = 0;
+            self.alerting = false;
+            return Divergence::Within { bps };
+        }
+
+        self.consecutive += 1;
+        if self.consecutive == self.rounds {
+            self.alerting = true;
+            self.alerts += 1;
+            error!(
+                "🚨 Aggregator median ${} diverges from local ${} by {:.1} bps for {} consecutive rounds (limit {} bps) - check this node's exchanges and the aggregator",
+                network, local, bps, self.consecutive, self.threshold_bps
+            );
+            return Divergence::Alert {
+                bps,
+                consecutive: self.consecutive,
+            };
+        }
+        if !self.alerting {
+            warn!(
+                "⚠️ Aggregator median ${} diverges from local ${} by {:.1} bps ({}/{} rounds)",
+                network, local, bps, self.consecutive, self.rounds
+            );
+        }
+        Divergence::Diverging {
+            bps,
+            consecutive: self.consecutive,
+        }
+    }
+
+    /// 현재 상태
+    pub fn report(&self) -> DivergenceReport {
+        DivergenceReport {
+            threshold_bps: self.threshold_bps,
+            rounds: self.rounds,
+            last_bps: self.recent.back().copied(),
+            rolling_bps: (!self.recent.is_empty())
+                .then(|| self.recent.iter().sum::<f64>() / self.recent.len() as f64),
+            consecutive: self.consecutive,
+            alerting: self.alerting,
+            alerts: self.alerts,
+        }
+    }
+}
+
+/// 로컬 중간값 대비 네트워크 중간값의 차이 (bps, 절댓값)
+pub fn divergence_bps(local: Price, network: Price) -> f64 {
+    let local = local.to_f64_dollars();
+    if local <= 0.0 {
+        return 0.0;
+    }
+    (network.to_f64_dollars() - local).abs() / local * 10_000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_divergence_bps() {
+        let local = Price::from_cents(7_000_000);
+        assert_eq!(divergence_bps(local, local), 0.0);
+        let bps = divergence_bps(local, Price::from_cents(7_070_000));
+        assert!((bps - 100.0).abs() < 1e-9);
+        let bps = divergence_bps(local, Price::from_cents(6_930_000));
+        assert!((bps - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_alerts_at_exactly_n_rounds_and_resets_on_convergence() {
+        let local = Price::from_cents(7_000_000);
+        let far = Price::from_cents(7_100_000);
+        let near = Price::from_cents(7_001_000);
+        let mut monitor = DivergenceMonitor::new(100, 3);
+
+        assert!(matches!(
+            monitor.observe(local, far),
+            Divergence::Diverging { consecutive: 1, .. }
+        ));
+        assert!(matches!(
+            monitor.observe(local, far),
+            Divergence::Diverging { consecutive: 2, .. }
+        ));
+        assert!(matches!(
+            monitor.observe(local, far),
+            Divergence::Alert { consecutive: 3, .. }
+        ));
+        // 이미 알렸으면 다시 알리지 않음
+        assert!(matches!(
+            monitor.observe(local, far),
+            Divergence::Diverging { consecutive: 4, .. }
+        ));
+        let report = monitor.report();
+        assert!(report.alerting);
+        assert_eq!(report.alerts, 1);
+
+        assert!(matches!(
+            monitor.observe(local, near),
+            Divergence::Within { .. }
+        ));
+        let report = monitor.report();
+        assert!(!report.alerting);
+        assert_eq!(report.consecutive, 0);
+
+        // 회복 후에는 처음부터 다시 셈
+        monitor.observe(local, far);
+        assert!(matches!(
+            monitor.observe(local, near),
+            Divergence::Within { .. }
+        ));
+        monitor.observe(local, far);
+        monitor.observe(local, far);
+        assert!(matches!(
+            monitor.observe(local, far),
+            Divergence::Alert { consecutive: 3, .. }
+        ));
+        assert_eq!(monitor.report().alerts, 2);
+    }
+
+    #[test]
+    fn test_rolling_divergence() {
+        let local = Price::from_cents(7_000_000);
+        let mut monitor = DivergenceMonitor::new(100, 1);
+        assert_eq!(monitor.report().rolling_bps, None);
+
+        monitor.observe(local, local);
+        monitor.observe(local, Price::from_cents(7_014_000));
+        let report = monitor.report();
+        assert!((report.last_bps.unwrap() - 20.0).abs() < 1e-9);
+        assert!((report.rolling_bps.unwrap() - 10.0).abs() < 1e-9);
+        assert!(!report.alerting);
+    }
+}
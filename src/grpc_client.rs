@@ -1,15 +1,173 @@
-use oracle_vm_common::types::PriceData;
+use oracle_vm_common::jsonl::JsonlWriter;
+use oracle_vm_common::types::{AssetPair, PriceData};
+use oracle_vm_common::Price;
 use anyhow::{Context, Result};
-use tonic::transport::Channel;
-use tonic::Request;
-use tracing::{error, info, warn};
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use tonic::transport::{Channel, Endpoint};
+use tonic::{Code, Request, Response, Status};
+use tracing::{debug, error, info, warn};
+
+use crate::backoff::{Backoff, AGGREGATOR_BACKOFF};
+use crate::clock_drift::{self, DriftMonitor, TimeSource};
+use crate::divergence::{self, DivergenceMonitor};
+use crate::metrics::NodeMetrics;
+use crate::offline_queue::{OfflineQueue, QueuedSubmission};
+use crate::network_price::AggregateCache;
+use crate::price_stream::PriceStream;
+use crate::shutdown::ShutdownSignal;
+use crate::telemetry;
 
 // gRPC 클라이언트 코드 (tonic-build로 자동 생성됨)
 pub mod oracle {
     tonic::include_proto!("oracle");
 }
 
-use oracle::{oracle_service_client::OracleServiceClient, HealthRequest, PriceRequest};
+/// Aggregator별 연결 제한 시간
+pub const FAILOVER_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+/// 집계 결과를 받지 못한 채 스트림이 연속으로 이만큼 끊기면 단건 제출로 전환
+pub const MAX_STREAM_BREAKS: u32 = 3;
+/// 한 번에 재전송하는 오프라인 큐 제출 최대 수 (라운드가 다음 분 경계를 넘지 않도록)
+pub const MAX_DRAIN_PER_ROUND: usize = 100;
+/// 장애 조치 후 주 Aggregator로 다시 시도하기까지의 기본 시간
+pub const DEFAULT_FAILBACK_AFTER: Duration = Duration::from_secs(300);
+
+/// 여러 Aggregator에 제출하는 방식
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AggregatorMode {
+    /// 우선순위가 가장 높은 정상 Aggregator 하나에 제출 (계속 실패하면 다음으로)
+    #[default]
+    Failover,
+    /// 모든 Aggregator에 동시에 제출 (하나라도 받아들이면 성공)
+    FanOut,
+}
+
+impl fmt::Display for AggregatorMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Failover => write!(f, "failover"),
+            Self::FanOut => write!(f, "fan-out"),
+        }
+    }
+}
+
+impl FromStr for AggregatorMode {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "failover" => Ok(Self::Failover),
+            "fan-out" | "fanout" => Ok(Self::FanOut),
+            _ => Err(format!(
+                "unknown aggregator mode '{}' (expected failover or fan-out)",
+                value
+            )),
+        }
+    }
+}
+
+/// 모든 Aggregator가 일시적 오류로 응답하지 않음 (오프라인 큐에 보관할 대상)
+#[derive(Debug, thiserror::Error)]
+#[error("All aggregators are unavailable")]
+pub struct AggregatorsUnavailable;
+
+use oracle::{
+    oracle_service_client::OracleServiceClient, GetPriceRequest, GetPriceResponse, HealthRequest,
+    HealthResponse, PriceRequest, PriceResponse, RegisterNodeRequest, RegisterNodeResponse,
+};
+
+// Oracle Node 고유 ID 생성
+fn generate_node_id() -> String {
+    format!("oracle-node-{}", &uuid::Uuid::new_v4().to_string()[..8])
+}
+
+// 가격 데이터를 gRPC 요청으로 변환
+// 고정소수점 필드와 기존 f64(달러) 필드를 함께 전송 (Aggregator는 고정소수점 우선)
+fn price_request(node_id: &str, price_data: &PriceData) -> PriceRequest {
+    let (price_scaled, price_decimals) = price_data.price.to_scaled();
+
+    PriceRequest {
+        price: price_data.price.to_f64_dollars(),
+        timestamp: price_data.timestamp.timestamp() as u64,
+        source: price_data.source.clone(),
+        node_id: node_id.to_string(),
+        signature: None, // 나중에 보안용으로 추가
+        price_scaled: Some(price_scaled),
+        price_decimals: Some(price_decimals),
+        symbol: Some(price_data.pair.as_str().to_string()),
+        historical: false,
+        volume: price_data.volume,
+    }
+}
+
+// 큐에 보관했던 제출을 과거 관측값으로 표시한 요청 (원래 node_id와 관측 시각 유지)
+fn historical_request(submission: &QueuedSubmission) -> PriceRequest {
+    PriceRequest {
+        historical: true,
+        ..price_request(&submission.node_id, &submission.price_data)
+    }
+}
+
+// 응답의 집계 가격 (고정소수점 필드 우선, 없으면 기존 f64 필드)
+fn response_aggregated_price(response: &PriceResponse) -> Option<Price> {
+    match (
+        response.aggregated_price_scaled,
+        response.aggregated_price_decimals,
+    ) {
+        (Some(scaled), Some(decimals)) => Price::from_scaled(scaled, decimals).ok(),
+        _ => response
+            .aggregated_price
+            .and_then(|price| Price::from_f64_dollars(price, Price::USD_DECIMALS).ok()),
+    }
+}
+
+// 조회 응답의 집계 가격 (고정소수점 필드 우선, 집계 결과가 없으면 None)
+fn network_aggregated_price(response: &GetPriceResponse) -> Option<Price> {
+    match (
+        response.aggregated_price_scaled,
+        response.aggregated_price_decimals,
+    ) {
+        (Some(scaled), Some(decimals)) => Price::from_scaled(scaled, decimals).ok(),
+        _ if response.success && response.aggregated_price > 0.0 => {
+            Price::from_f64_dollars(response.aggregated_price, Price::USD_DECIMALS).ok()
+        }
+        _ => None,
+    }
+}
+
+// Aggregator 응답 처리 (거부되면 에러, 성공하면 Aggregator가 돌려준 집계 가격)
+fn handle_price_response(response: PriceResponse) -> Result<Option<Price>> {
+    if response.success {
+        let aggregated_price = response_aggregated_price(&response);
+        if let Some(aggregated_price) = aggregated_price {
+            info!(
+                "✅ gRPC: Price sent successfully! Aggregated price: ${}",
+                aggregated_price
+            );
+        } else {
+            info!("✅ gRPC: Price sent successfully! {}", response.message);
+        }
+        Ok(aggregated_price)
+    } else {
+        warn!("❌ gRPC: Failed to submit price: {}", response.message);
+        anyhow::bail!("Aggregator rejected price: {}", response.message);
+    }
+}
+
+// 응답의 서버 시각을 시계 오차 표본으로 기록
+fn observe_time(drift: Option<&DriftMonitor>, remote_secs: u64, sent_millis: i64) {
+    if let Some(drift) = drift {
+        drift.observe_secs(
+            TimeSource::Aggregator,
+            remote_secs,
+            sent_millis,
+            clock_drift::local_millis(),
+        );
+    }
+}
 
 /// gRPC를 사용한 Aggregator 클라이언트
 pub struct GrpcAggregatorClient {
@@ -21,10 +179,7 @@ impl GrpcAggregatorClient {
     /// 새로운 gRPC Aggregator 클라이언트 생성
     pub async fn new(aggregator_url: &str) -> Result<Self> {
         // Oracle Node 고유 ID 생성
-        let node_id = format!(
-            "oracle-node-{}",
-            uuid::Uuid::new_v4().to_string()[..8].to_string()
-        );
+        let node_id = generate_node_id();
 
         // gRPC 채널 생성
         let channel = Channel::from_shared(aggregator_url.to_string())
@@ -43,48 +198,22 @@ impl GrpcAggregatorClient {
         Ok(Self { client, node_id })
     }
 
-    /// 가격 데이터를 gRPC로 Aggregator에 전송
-    pub async fn submit_price(&mut self, price_data: &PriceData) -> Result<()> {
-        // Convert cents to dollars for gRPC
-        let price_usd = price_data.price as f64 / 100.0;
-        
-        let request = Request::new(PriceRequest {
-            price: price_usd,
-            timestamp: price_data.timestamp.timestamp() as u64,
-            source: price_data.source.clone(),
-            node_id: self.node_id.clone(),
-            signature: None, // 나중에 보안용으로 추가
-        });
+    /// 가격 데이터를 gRPC로 Aggregator에 전송 (Aggregator가 돌려준 집계 가격 반환)
+    pub async fn submit_price(&mut self, price_data: &PriceData) -> Result<Option<Price>> {
+        let request = telemetry::traced_request(price_request(&self.node_id, price_data));
 
         info!(
-            "📤 Sending price ${:.2} to Aggregator via gRPC...",
-            price_usd
+            "📤 Sending price ${} to Aggregator via gRPC...",
+            price_data.price
         );
 
         match self.client.submit_price(request).await {
-            Ok(response) => {
-                let response = response.into_inner();
-                if response.success {
-                    if let Some(aggregated_price) = response.aggregated_price {
-                        info!(
-                            "✅ gRPC: Price sent successfully! Aggregated price: ${:.2}",
-                            aggregated_price
-                        );
-                    } else {
-                        info!("✅ gRPC: Price sent successfully! {}", response.message);
-                    }
-                } else {
-                    warn!("❌ gRPC: Failed to submit price: {}", response.message);
-                    anyhow::bail!("Aggregator rejected price: {}", response.message);
-                }
-            }
+            Ok(response) => handle_price_response(response.into_inner()),
             Err(e) => {
                 error!("❌ gRPC: Failed to send price: {}", e);
                 anyhow::bail!("gRPC communication error: {}", e);
             }
         }
-
-        Ok(())
     }
 
     /// gRPC를 통한 Aggregator 헬스체크
@@ -103,7 +232,7 @@ impl GrpcAggregatorClient {
                     );
                     Ok(true)
                 } else {
-                    warn!("❌ gRPC: Aggregator is unhealthy");
+                    warn!("❌ gRPC: Aggregator is unhealthy: {}", response.reason);
                     Ok(false)
                 }
             }
@@ -120,6 +249,987 @@ impl GrpcAggregatorClient {
     }
 }
 
+/// 재시도할 만한 일시적 오류인지 (Aggregator 재시작, 네트워크 단절 등)
+pub fn is_transient(code: Code) -> bool {
+    matches!(code, Code::Unavailable | Code::DeadlineExceeded)
+}
+
+/// Aggregator 연결 상태
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionState {
+    /// 아직 연결하지 않음
+    Idle,
+    /// 마지막 요청이 응답을 받음
+    Connected,
+    /// 마지막 요청이 일시적 오류로 실패 (다음 요청 때 다시 연결)
+    Disconnected,
+}
+
+impl fmt::Display for ConnectionState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Idle => write!(f, "idle"),
+            Self::Connected => write!(f, "connected"),
+            Self::Disconnected => write!(f, "disconnected"),
+        }
+    }
+}
+
+/// Aggregator 하나에 대한 연결 관리
+///
+/// 채널은 첫 요청 때 연결하고, 일시적 오류가 나면 버린 뒤 백오프 후 다시 연결한다.
+/// 상태 변화는 시도마다가 아니라 바뀔 때 한 번만 기록한다.
+pub struct AggregatorConnection {
+    url: String,
+    endpoint: Endpoint,
+    client: Option<OracleServiceClient<Channel>>,
+    state: ConnectionState,
+}
+
+impl AggregatorConnection {
+    pub fn new(url: &str) -> Result<Self> {
+        let endpoint = Endpoint::from_shared(url.to_string())
+            .with_context(|| format!("Invalid aggregator URL: {}", url))?
+            .connect_timeout(FAILOVER_CONNECT_TIMEOUT);
+
+        Ok(Self {
+            url: url.to_string(),
+            endpoint,
+            client: None,
+            state: ConnectionState::Idle,
+        })
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn state(&self) -> ConnectionState {
+        self.state
+    }
+
+    // 채널을 닫음 (다음 요청 때 다시 연결)
+    fn disconnect(&mut self) {
+        self.client = None;
+        self.state = ConnectionState::Idle;
+    }
+
+    // 상태가 바뀔 때만 기록
+    fn transition(&mut self, state: ConnectionState, detail: &str) {
+        if self.state == state {
+            return;
+        }
+        match state {
+            ConnectionState::Connected => info!("🔗 gRPC: Connected to aggregator {}", self.url),
+            ConnectionState::Disconnected => {
+                warn!("🔌 gRPC: Lost aggregator {}: {}", self.url, detail)
+            }
+            ConnectionState::Idle => {}
+        }
+        self.state = state;
+    }
+
+    // 연결된 클라이언트 (없으면 새로 연결)
+    async fn client(&mut self) -> Result<OracleServiceClient<Channel>, Status> {
+        if let Some(client) = &self.client {
+            return Ok(client.clone());
+        }
+
+        let channel = self.endpoint.connect().await.map_err(|e| {
+            Status::unavailable(format!("failed to connect to {}: {}", self.url, e))
+        })?;
+        let client = OracleServiceClient::new(channel);
+        self.client = Some(client.clone());
+        Ok(client)
+    }
+
+    /// RPC 호출 (일시적 오류는 다시 연결하며 `backoff`에 따라 재시도)
+    ///
+    /// 영구적 오류(InvalidArgument, Unauthenticated 등)는 재시도하지 않고 바로 반환한다.
+    pub async fn call<T, F, Fut>(&mut self, backoff: &Backoff, mut rpc: F) -> Result<T, Status>
+    where
+        F: FnMut(OracleServiceClient<Channel>) -> Fut,
+        Fut: Future<Output = Result<Response<T>, Status>>,
+    {
+        let mut attempt = 0;
+        loop {
+            let result = match self.client().await {
+                Ok(client) => rpc(client).await.map(Response::into_inner),
+                Err(status) => Err(status),
+            };
+
+            match result {
+                Ok(response) => {
+                    self.transition(ConnectionState::Connected, "");
+                    return Ok(response);
+                }
+                Err(status) if is_transient(status.code()) => {
+                    // 채널을 버려 다음 시도에서 새로 연결
+                    self.client = None;
+                    self.transition(ConnectionState::Disconnected, status.message());
+
+                    let Some(delay) = backoff.retry_delay(attempt) else {
+                        return Err(status);
+                    };
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                Err(status) => {
+                    // 응답은 왔으므로 연결 자체는 정상
+                    self.transition(ConnectionState::Connected, "");
+                    return Err(status);
+                }
+            }
+        }
+    }
+}
+
+/// 장애 조치(failover)를 지원하는 gRPC Aggregator 클라이언트
+///
+/// 우선순위 순서의 Aggregator 목록으로 만들며, 사용 중인 Aggregator가 재시도 후에도
+/// 일시적 오류(`Unavailable`, `DeadlineExceeded`)를 반환하면 다음 Aggregator로 넘어간다.
+/// 마지막으로 응답한 Aggregator를 기억해 다음 요청은 그곳부터 보내고, 장애 조치 후
+/// `failback_after`가 지나면 주 Aggregator부터 다시 시도한다.
+/// `AggregatorMode::FanOut`이면 매 제출을 모든 Aggregator에 동시에 보낸다.
+/// 모든 Aggregator에 같은 node_id로 제출한다.
+///
+/// 스트리밍을 켜면 현재 Aggregator와 stream_prices 스트림을 유지하며 그 위로 제출하고,
+/// 스트림을 쓸 수 없는 라운드는 단건 submit_price로 보낸다.
+pub struct MultiAggregatorClient {
+    endpoints: Vec<AggregatorConnection>,
+    current: usize,
+    node_id: String,
+    backoff: Backoff,
+    offline_queue: Option<OfflineQueue>,
+    submission_log: Option<JsonlWriter>,
+    streaming: bool,
+    stream: Option<PriceStream>,
+    stream_breaks: u32,
+    drift: Option<DriftMonitor>,
+    divergence: Option<DivergenceMonitor>,
+    // pair별 괴리 감시 (첫 제출 때 `divergence`를 복제해 만듦)
+    pair_divergence: HashMap<AssetPair, DivergenceMonitor>,
+    shutdown: Option<ShutdownSignal>,
+    mode: AggregatorMode,
+    failback_after: Duration,
+    failed_over_at: Option<Instant>,
+    dry_run: bool,
+    // 응답과 스트림으로 받은 마지막 집계 가격 (`/price`)
+    aggregate: AggregateCache,
+    metrics: Option<NodeMetrics>,
+    // 등록 때 받은 Aggregator 설정
+    registration: Option<RegisterNodeResponse>,
+}
+
+impl MultiAggregatorClient {
+    /// Aggregator URL 목록(우선순위 순)으로 클라이언트 생성
+    ///
+    /// 연결은 첫 요청 때 맺으므로 생성 시점에 내려가 있는 Aggregator가 있어도 된다.
+    pub fn new<S: AsRef<str>>(aggregator_urls: &[S]) -> Result<Self> {
+        if aggregator_urls.is_empty() {
+            anyhow::bail!("At least one aggregator URL is required");
+        }
+
+        let endpoints = aggregator_urls
+            .iter()
+            .map(|url| AggregatorConnection::new(url.as_ref()))
+            .collect::<Result<Vec<_>>>()?;
+
+        let node_id = generate_node_id();
+        info!(
+            "🔗 Created gRPC Aggregator client for {} aggregators with node_id: {}",
+            endpoints.len(),
+            node_id
+        );
+
+        Ok(Self {
+            endpoints,
+            current: 0,
+            node_id,
+            backoff: AGGREGATOR_BACKOFF,
+            offline_queue: None,
+            submission_log: None,
+            streaming: false,
+            stream: None,
+            stream_breaks: 0,
+            drift: None,
+            divergence: None,
+            pair_divergence: HashMap::new(),
+            shutdown: None,
+            mode: AggregatorMode::Failover,
+            failback_after: DEFAULT_FAILBACK_AFTER,
+            failed_over_at: None,
+            dry_run: false,
+            aggregate: AggregateCache::default(),
+            metrics: None,
+            registration: None,
+        })
+    }
+
+    /// node_id 지정
+    pub fn with_node_id(mut self, node_id: impl Into<String>) -> Self {
+        self.node_id = node_id.into();
+        self
+    }
+
+    /// Aggregator별 재시도 백오프 지정
+    pub fn with_backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// 모든 Aggregator에 연결할 수 없을 때 제출을 보관할 디스크 큐 지정
+    ///
+    /// 보관된 제출은 다음 제출이 성공한 뒤 오래된 순서대로 과거 관측값으로 재전송된다.
+    pub fn with_offline_queue(mut self, queue: OfflineQueue) -> Self {
+        self.offline_queue = Some(queue);
+        self.record_queue_depth();
+        self
+    }
+
+    /// 보내는 제출을 `QueuedSubmission` 한 줄씩 남길 JSONL 파일 지정
+    ///
+    /// 드라이런에서는 남기지 않으며, 기록에 실패해도 제출은 계속한다.
+    pub fn with_submission_log(mut self, log: JsonlWriter) -> Self {
+        self.submission_log = Some(log);
+        self
+    }
+
+    /// stream_prices 스트림으로 제출
+    ///
+    /// Aggregator가 스트림을 지원하지 않거나 스트림이 반복해서 끊기면 자동으로 단건 제출로 전환한다.
+    pub fn with_streaming(mut self, enabled: bool) -> Self {
+        self.streaming = enabled;
+        self
+    }
+
+    /// 응답의 `timestamp`로 로컬 시계 오차를 추정하고, 오차가 거부 기준을 넘으면 제출하지 않음
+    ///
+    /// 보정을 켠 감시기면 제출 타임스탬프에 추정 오차를 더한다.
+    pub fn with_drift_monitor(mut self, monitor: DriftMonitor) -> Self {
+        self.drift = Some(monitor);
+        self
+    }
+
+    /// 제출이 성공할 때마다 Aggregator가 돌려준 집계 가격을 로컬 중간값과 비교
+    ///
+    /// 괴리가 기준을 넘은 라운드가 연속되면 에러로 알린다 (드라이런에서는 비교하지 않음).
+    /// 연속 횟수는 pair마다 따로 센다.
+    pub fn with_divergence_monitor(mut self, monitor: DivergenceMonitor) -> Self {
+        self.divergence = Some(monitor);
+        self
+    }
+
+    /// 종료가 요청되면 오프라인 큐 재전송을 멈춤 (남은 제출은 큐에 그대로 둠)
+    pub fn with_shutdown(mut self, signal: ShutdownSignal) -> Self {
+        self.shutdown = Some(signal);
+        self
+    }
+
+    /// 제출 방식 지정 (FanOut이면 스트리밍을 사용하지 않음)
+    pub fn with_mode(mut self, mode: AggregatorMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// 장애 조치 후 주 Aggregator로 다시 시도하기까지의 시간
+    pub fn with_failback_after(mut self, failback_after: Duration) -> Self {
+        self.failback_after = failback_after;
+        self
+    }
+
+    /// 제출 결과(gRPC 상태 코드), 오프라인 큐 길이, 마지막 제출 가격, 괴리, 시계 오차를 지표로 기록
+    pub fn with_metrics(mut self, metrics: NodeMetrics) -> Self {
+        self.metrics = Some(metrics);
+        self.record_queue_depth();
+        self
+    }
+
+    /// 드라이런: submit_price를 호출하지 않고 보낼 요청을 기록한 뒤 네트워크 집계 가격만 조회
+    ///
+    /// 스트림과 오프라인 큐 재전송도 사용하지 않는다.
+    pub fn with_dry_run(mut self, enabled: bool) -> Self {
+        self.dry_run = enabled;
+        self
+    }
+
+    /// 드라이런 중인지
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// 제출 방식
+    pub fn mode(&self) -> AggregatorMode {
+        self.mode
+    }
+
+    /// 시계 오차 감시기 (설정된 경우)
+    pub fn drift_monitor(&self) -> Option<&DriftMonitor> {
+        self.drift.as_ref()
+    }
+
+    /// `pair`의 네트워크 중간값 괴리 감시기 (설정된 경우)
+    pub fn divergence_monitor(&self, pair: &AssetPair) -> Option<&DivergenceMonitor> {
+        self.pair_divergence
+            .get(pair)
+            .or(self.divergence.as_ref())
+    }
+
+    /// 스트림으로 제출 중인지 (단건 제출로 전환되었으면 false)
+    pub fn is_streaming(&self) -> bool {
+        self.streaming
+    }
+
+    /// 제출 응답, 스트림, 조회로 받은 마지막 네트워크 집계 가격 (복제본은 계속 갱신됨)
+    pub fn aggregate_cache(&self) -> &AggregateCache {
+        &self.aggregate
+    }
+
+    /// 스트림으로 받은 마지막 네트워크 집계 중간값
+    pub fn streamed_aggregate(&self) -> Option<Price> {
+        self.stream.as_ref().and_then(PriceStream::network_price)
+    }
+
+    /// 오프라인 큐 (설정된 경우)
+    pub fn offline_queue(&self) -> Option<&OfflineQueue> {
+        self.offline_queue.as_ref()
+    }
+
+    // 오프라인 큐 길이를 지표로 기록
+    fn record_queue_depth(&self) {
+        if let Some(metrics) = &self.metrics {
+            metrics
+                .set_offline_queue_depth(self.offline_queue.as_ref().map_or(0, OfflineQueue::len));
+        }
+    }
+
+    // Aggregator가 받아들인 로컬 중간값을 지표로 기록
+    fn record_submitted(&self, price_data: &PriceData) {
+        if let Some(metrics) = &self.metrics {
+            metrics.set_last_submitted_price(&price_data.pair, price_data.price);
+        }
+    }
+
+    // Aggregator 한 곳의 제출 결과를 지표로 기록
+    fn record_submission_code(&self, code: Code) {
+        if let Some(metrics) = &self.metrics {
+            metrics.record_submission(code);
+        }
+    }
+
+    // 마지막으로 응답한 Aggregator부터 목록 순서대로
+    fn attempt_order(&self) -> Vec<usize> {
+        let len = self.endpoints.len();
+        (0..len).map(|offset| (self.current + offset) % len).collect()
+    }
+
+    // 응답한 Aggregator를 기억
+    fn remember(&mut self, index: usize) {
+        if index != self.current {
+            info!(
+                "🔀 gRPC: Switched to aggregator {}",
+                self.endpoints[index].url()
+            );
+            self.current = index;
+            // 스트림은 새로 사용할 Aggregator와 다시 연다
+            self.stream = None;
+            self.failed_over_at = (index != 0).then(Instant::now);
+        }
+    }
+
+    // 장애 조치 후 충분히 지났으면 주 Aggregator부터 다시 시도
+    fn fail_back_if_due(&mut self) {
+        let due = self
+            .failed_over_at
+            .is_some_and(|at| at.elapsed() >= self.failback_after);
+        if self.current != 0 && due {
+            info!(
+                "↩️ gRPC: Trying primary aggregator {} again",
+                self.endpoints[0].url()
+            );
+            self.current = 0;
+            self.stream = None;
+            self.failed_over_at = None;
+        }
+    }
+
+    /// 가격 데이터를 Aggregator에 전송 (일시적 오류가 계속되면 다음 Aggregator로 장애 조치)
+    ///
+    /// 성공하면 Aggregator가 돌려준 집계 가격을 반환하고 오프라인 큐에 보관된 제출을 재전송한다.
+    /// 모든 Aggregator에 연결할 수 없으면 오프라인 큐(설정된 경우)에 보관한 뒤 에러를 반환한다.
+    /// 로컬 시계 오차가 거부 기준을 넘었으면 보내지 않고 `ClockDriftError`를 반환한다.
+    pub async fn submit_price(&mut self, price_data: &PriceData) -> Result<Option<Price>> {
+        let mut request = price_request(&self.node_id, price_data);
+        if let Some(drift) = &self.drift {
+            if let (Some(metrics), Some(offset)) = (&self.metrics, drift.offset_millis()) {
+                metrics.set_clock_drift_millis(offset);
+            }
+            drift.check()?;
+            request.timestamp = drift.corrected_timestamp(request.timestamp);
+        }
+        if self.dry_run {
+            return Ok(self.dry_run_submission(&request).await);
+        }
+        if let Some(log) = &mut self.submission_log {
+            let submission = QueuedSubmission {
+                node_id: self.node_id.clone(),
+                price_data: price_data.clone(),
+            };
+            if let Err(e) = log.append(&submission) {
+                warn!(
+                    "⚠️ Failed to write submission log {}: {}",
+                    log.path().display(),
+                    e
+                );
+            }
+        }
+        info!(
+            "📤 Sending price ${} to Aggregator via gRPC...",
+            price_data.price
+        );
+        self.fail_back_if_due();
+
+        // 스트림으로 보냈으면 처리 결과는 같은 스트림으로 받고, 집계 결과는 스트림으로 받은 마지막 값
+        let streaming = self.streaming && self.mode == AggregatorMode::Failover;
+        let sent = clock_drift::local_millis();
+        let streamed = if streaming {
+            self.send_via_stream(&request).await
+        } else {
+            None
+        };
+        if let Some(response) = streamed {
+            // 단건 제출처럼 응답을 받은 제출은 Ok로 세고, 거부는 에러로 돌려줌
+            self.record_submission_code(Code::Ok);
+            observe_time(self.drift.as_ref(), response.timestamp, sent);
+            handle_price_response(response)?;
+            self.record_submitted(price_data);
+            if let Err(e) = self.drain_offline_queue().await {
+                warn!("⚠️ Failed to drain offline queue: {:#}", e);
+            }
+            let aggregated_price = self.streamed_aggregate();
+            self.check_divergence(price_data, aggregated_price);
+            return Ok(aggregated_price);
+        }
+
+        let response = match self.send(request).await {
+            Ok(response) => response,
+            Err(e) => {
+                if e.is::<AggregatorsUnavailable>() {
+                    if let Some(queue) = &mut self.offline_queue {
+                        queue.push(QueuedSubmission {
+                            node_id: self.node_id.clone(),
+                            price_data: price_data.clone(),
+                        })?;
+                        info!(
+                            "📥 Queued price for later delivery ({} pending)",
+                            queue.len()
+                        );
+                    }
+                    self.record_queue_depth();
+                }
+                return Err(e);
+            }
+        };
+        let responded_at = response.timestamp;
+        let aggregated_price = handle_price_response(response)?;
+        self.record_submitted(price_data);
+        if let Some(price) = aggregated_price {
+            self.aggregate.record(price, responded_at);
+        }
+        self.check_divergence(price_data, aggregated_price);
+
+        // 연결이 돌아왔으므로 보관된 제출 재전송 (실패해도 이번 제출 결과에는 영향 없음)
+        if let Err(e) = self.drain_offline_queue().await {
+            warn!("⚠️ Failed to drain offline queue: {:#}", e);
+        }
+
+        Ok(aggregated_price)
+    }
+
+    // 제출한 로컬 중간값과 돌려받은 집계 가격 비교 (집계 결과가 없으면 건너뜀)
+    fn check_divergence(&mut self, local: &PriceData, aggregated_price: Option<Price>) {
+        if let (Some(metrics), Some(network)) = (&self.metrics, aggregated_price) {
+            metrics.set_divergence_bps(
+                &local.pair,
+                divergence::divergence_bps(local.price, network),
+            );
+        }
+        let (Some(template), Some(network)) = (&self.divergence, aggregated_price) else {
+            return;
+        };
+        self.pair_divergence
+            .entry(local.pair.clone())
+            .or_insert_with(|| template.clone())
+            .observe(local.price, network);
+    }
+
+    // 드라이런: 보낼 요청을 기록하고 비교용 네트워크 집계 가격 조회 (조회 실패는 None)
+    async fn dry_run_submission(&mut self, request: &PriceRequest) -> Option<Price> {
+        info!(
+            "🧪 DRY RUN: would submit {} ${} from {} as {} (timestamp {})",
+            request.symbol.as_deref().unwrap_or("BTC/USD"),
+            request
+                .price_scaled
+                .zip(request.price_decimals)
+                .and_then(|(scaled, decimals)| Price::from_scaled(scaled, decimals).ok())
+                .map_or_else(|| request.price.to_string(), |price| price.to_string()),
+            request.source,
+            request.node_id,
+            request.timestamp
+        );
+        if let Some(queue) = self.offline_queue.as_ref().filter(|queue| !queue.is_empty()) {
+            info!(
+                "🧪 DRY RUN: would resend {} queued price(s)",
+                queue.len()
+            );
+        }
+
+        match self.aggregated_price().await {
+            Ok(price) => price,
+            Err(e) => {
+                warn!("⚠️ DRY RUN: cannot read the network price: {:#}", e);
+                None
+            }
+        }
+    }
+
+    /// Aggregator의 현재 집계 가격 조회 (get_aggregated_price, 응답하는 Aggregator를 찾을 때까지 순서대로)
+    ///
+    /// 아직 집계 결과가 없으면 None을 반환한다.
+    pub async fn aggregated_price(&mut self) -> Result<Option<Price>> {
+        for index in self.attempt_order() {
+            let endpoint = &mut self.endpoints[index];
+            let result = endpoint
+                .call(&self.backoff, |mut client| async move {
+                    client
+                        .get_aggregated_price(Request::new(GetPriceRequest::default()))
+                        .await
+                })
+                .await;
+
+            match result {
+                Ok(response) => {
+                    let price = network_aggregated_price(&response);
+                    if let Some(price) = price {
+                        self.aggregate.record(price, response.last_update);
+                    }
+                    return Ok(price);
+                }
+                Err(status) if is_transient(status.code()) => {
+                    warn!(
+                        "⚠️ gRPC: Aggregator {} unavailable: {}",
+                        endpoint.url(),
+                        status.message()
+                    );
+                }
+                Err(status) => anyhow::bail!("gRPC communication error: {}", status),
+            }
+        }
+        Err(AggregatorsUnavailable.into())
+    }
+
+    /// 오프라인 큐에 보관된 제출을 오래된 순서대로 과거 관측값으로 재전송
+    ///
+    /// 최대 MAX_DRAIN_PER_ROUND개까지 보내며, 다시 연결이 끊기면 남은 제출은 그대로 둔다.
+    /// Aggregator가 거부한 제출은 다시 보내도 받아들여지지 않으므로 버린다.
+    /// 재전송한 제출 수를 반환한다.
+    pub async fn drain_offline_queue(&mut self) -> Result<usize> {
+        if self.dry_run {
+            return Ok(0);
+        }
+        let mut drained = 0;
+        while drained < MAX_DRAIN_PER_ROUND {
+            if self.shutdown.as_ref().is_some_and(ShutdownSignal::is_triggered) {
+                info!("🛑 Shutdown requested, leaving queued prices for the next run");
+                break;
+            }
+            let Some(submission) = self.offline_queue.as_ref().and_then(OfflineQueue::front)
+            else {
+                break;
+            };
+            let request = historical_request(submission);
+
+            match self.send(request).await {
+                Ok(response) if response.success => drained += 1,
+                Ok(response) => warn!(
+                    "🗑️ Aggregator rejected queued price, dropping it: {}",
+                    response.message
+                ),
+                Err(e) if e.is::<AggregatorsUnavailable>() => break,
+                Err(e) => warn!("🗑️ Dropping queued price: {:#}", e),
+            }
+            if let Some(queue) = &mut self.offline_queue {
+                queue.pop_front()?;
+            }
+        }
+
+        self.record_queue_depth();
+        if drained > 0 {
+            info!(
+                "📤 Delivered {} queued price(s) ({} pending)",
+                drained,
+                self.offline_queue.as_ref().map_or(0, OfflineQueue::len)
+            );
+        }
+        Ok(drained)
+    }
+
+    // 열려 있는 스트림으로 전송하고 처리 결과를 받음 (없으면 현재 Aggregator와 새로 연다)
+    // 결과를 받지 못하면 None을 돌려주며 이번 제출은 단건으로 보낸다
+    // (스트림으로 이미 도착했더라도 같은 제출은 Aggregator가 중복으로 처리함)
+    async fn send_via_stream(&mut self, request: &PriceRequest) -> Option<PriceResponse> {
+        if let Some(stream) = self.stream.take_if(|stream| stream.is_closed()) {
+            // 집계 결과를 받은 적이 있는 스트림이었다면 연속 끊김을 새로 셈
+            if stream.updates_received() > 0 {
+                self.stream_breaks = 0;
+            }
+            if !self.record_stream_break() {
+                return None;
+            }
+        }
+
+        if self.stream.is_none() {
+            let endpoint = &mut self.endpoints[self.current];
+            let opened = match endpoint.client().await {
+                Ok(client) => PriceStream::open(client, self.aggregate.clone()).await,
+                Err(status) => Err(status),
+            };
+            match opened {
+                Ok(stream) => self.stream = Some(stream),
+                Err(status) if status.code() == Code::Unimplemented => {
+                    warn!(
+                        "⚠️ gRPC: Aggregator {} does not support price streams, using unary submissions",
+                        endpoint.url()
+                    );
+                    self.streaming = false;
+                    return None;
+                }
+                Err(status) => {
+                    warn!(
+                        "⚠️ gRPC: Cannot open price stream to {}: {}",
+                        endpoint.url(),
+                        status.message()
+                    );
+                    // 연결 문제는 단건 제출의 재시도/장애 조치에 맡김
+                    if !is_transient(status.code()) {
+                        self.record_stream_break();
+                    }
+                    return None;
+                }
+            }
+        }
+
+        match &self.stream {
+            Some(stream) => stream.send(request.clone()).await,
+            None => None,
+        }
+    }
+
+    // 스트림 끊김을 세고 연속으로 MAX_STREAM_BREAKS번이면 단건 제출로 전환 (계속 스트리밍하면 true)
+    fn record_stream_break(&mut self) -> bool {
+        self.stream_breaks += 1;
+        if self.stream_breaks >= MAX_STREAM_BREAKS {
+            warn!(
+                "⚠️ gRPC: Price stream broke {} times in a row, switching to unary submissions",
+                self.stream_breaks
+            );
+            self.streaming = false;
+        }
+        self.streaming
+    }
+
+    // 요청을 Aggregator에 전송 (일시적 오류가 계속되면 다음 Aggregator로 장애 조치)
+    async fn send(&mut self, request: PriceRequest) -> Result<PriceResponse> {
+        if self.mode == AggregatorMode::FanOut {
+            return self.fan_out(request).await;
+        }
+
+        for index in self.attempt_order() {
+            let endpoint = &mut self.endpoints[index];
+            let sent = clock_drift::local_millis();
+            let result = endpoint
+                .call(&self.backoff, |mut client| {
+                    let request = telemetry::traced_request(request.clone());
+                    async move { client.submit_price(request).await }
+                })
+                .await;
+
+            if let Some(metrics) = &self.metrics {
+                metrics.record_submission(result.as_ref().map_or_else(Status::code, |_| Code::Ok));
+            }
+            match result {
+                Ok(response) => {
+                    self.remember(index);
+                    observe_time(self.drift.as_ref(), response.timestamp, sent);
+                    return Ok(response);
+                }
+                Err(status) if is_transient(status.code()) => {
+                    warn!(
+                        "⚠️ gRPC: Aggregator {} unavailable: {}",
+                        endpoint.url(),
+                        status.message()
+                    );
+                }
+                Err(status) => {
+                    // 영구적 오류는 재시도/장애 조치로 해결되지 않음
+                    error!(
+                        "🚨 gRPC: Aggregator {} refused the submission ({:?}): {}",
+                        endpoint.url(),
+                        status.code(),
+                        status.message()
+                    );
+                    anyhow::bail!("gRPC communication error: {}", status);
+                }
+            }
+        }
+
+        error!("❌ gRPC: All {} aggregators are unavailable", self.endpoints.len());
+        Err(AggregatorsUnavailable.into())
+    }
+
+    // 모든 Aggregator에 동시에 전송 (하나라도 받아들이면 성공)
+    //
+    // 받아들인 응답 중 우선순위가 가장 높은 것을 돌려준다. 아무도 받아들이지 않았을 때
+    // 거부한 Aggregator가 있으면 그 오류를, 모두 연결할 수 없으면 `AggregatorsUnavailable`을 반환한다.
+    async fn fan_out(&mut self, request: PriceRequest) -> Result<PriceResponse> {
+        let backoff = &self.backoff;
+        let sent = clock_drift::local_millis();
+        let results = futures::future::join_all(self.endpoints.iter_mut().map(|endpoint| {
+            let request = request.clone();
+            async move {
+                endpoint
+                    .call(backoff, |mut client| {
+                        let request = telemetry::traced_request(request.clone());
+                        async move { client.submit_price(request).await }
+                    })
+                    .await
+            }
+        }))
+        .await;
+
+        let mut accepted: Option<(usize, PriceResponse)> = None;
+        let mut refused = None;
+        let mut delivered = 0;
+        for (index, result) in results.into_iter().enumerate() {
+            self.record_submission_code(result.as_ref().map_or_else(Status::code, |_| Code::Ok));
+            let url = self.endpoints[index].url();
+            match result {
+                Ok(response) => {
+                    if !response.success {
+                        warn!(
+                            "⚠️ gRPC: Aggregator {} rejected the price: {}",
+                            url, response.message
+                        );
+                    }
+                    delivered += usize::from(response.success);
+                    // 받아들인 응답을 거부 응답보다 우선
+                    if accepted
+                        .as_ref()
+                        .is_none_or(|(_, best)| !best.success && response.success)
+                    {
+                        accepted = Some((index, response));
+                    }
+                }
+                Err(status) if is_transient(status.code()) => {
+                    warn!(
+                        "⚠️ gRPC: Aggregator {} unavailable: {}",
+                        url,
+                        status.message()
+                    );
+                }
+                Err(status) => {
+                    error!(
+                        "🚨 gRPC: Aggregator {} refused the submission ({:?}): {}",
+                        url,
+                        status.code(),
+                        status.message()
+                    );
+                    refused.get_or_insert(status);
+                }
+            }
+        }
+
+        if let Some((index, response)) = accepted {
+            if delivered < self.endpoints.len() {
+                warn!(
+                    "📡 gRPC: Price accepted by {}/{} aggregators",
+                    delivered,
+                    self.endpoints.len()
+                );
+            } else {
+                debug!("📡 gRPC: Price accepted by all {} aggregators", delivered);
+            }
+            self.remember(index);
+            observe_time(self.drift.as_ref(), response.timestamp, sent);
+            return Ok(response);
+        }
+        if let Some(status) = refused {
+            anyhow::bail!("gRPC communication error: {}", status);
+        }
+
+        error!("❌ gRPC: All {} aggregators are unavailable", self.endpoints.len());
+        Err(AggregatorsUnavailable.into())
+    }
+
+    /// Aggregator 헬스체크 (정상인 Aggregator를 찾을 때까지 순서대로 확인)
+    pub async fn check_health(&mut self) -> Result<bool> {
+        Ok(self
+            .health()
+            .await
+            .is_ok_and(|response| response.healthy))
+    }
+
+    /// 헬스체크 응답 (정상인 Aggregator를 찾을 때까지 순서대로 확인)
+    ///
+    /// 정상인 Aggregator가 없으면 마지막으로 응답한 비정상 응답을, 아무도 응답하지 않으면
+    /// `AggregatorsUnavailable`을 반환한다.
+    pub async fn health(&mut self) -> Result<HealthResponse> {
+        let request = HealthRequest {
+            node_id: self.node_id.clone(),
+        };
+        let mut unhealthy = None;
+
+        for index in self.attempt_order() {
+            let endpoint = &mut self.endpoints[index];
+            let sent = clock_drift::local_millis();
+            let result = endpoint
+                .call(&self.backoff, |mut client| {
+                    let request = telemetry::traced_request(request.clone());
+                    async move { client.health_check(request).await }
+                })
+                .await;
+            if let Ok(response) = &result {
+                observe_time(self.drift.as_ref(), response.timestamp, sent);
+            }
+
+            match result {
+                Ok(response) if response.healthy => {
+                    info!(
+                        "✅ gRPC: Aggregator {} is healthy (active nodes: {})",
+                        endpoint.url(),
+                        response.active_nodes
+                    );
+                    self.remember(index);
+                    return Ok(response);
+                }
+                Ok(response) => {
+                    warn!(
+                        "❌ gRPC: Aggregator {} is unhealthy: {}",
+                        endpoint.url(),
+                        response.reason
+                    );
+                    unhealthy = Some(response);
+                }
+                Err(e) => warn!("❌ gRPC: Cannot reach Aggregator {}: {}", endpoint.url(), e),
+            }
+        }
+
+        unhealthy.ok_or_else(|| AggregatorsUnavailable.into())
+    }
+
+    /// Aggregator에 노드를 등록하고 제출 규칙이 되는 현재 설정을 받음 (응답한 첫 Aggregator 기준)
+    ///
+    /// 연결할 수 있는 Aggregator가 없으면 `AggregatorsUnavailable`, RegisterNode가 없는 이전 버전
+    /// Aggregator면 `None`을 반환한다. 허용 목록에 없는 노드처럼 등록을 거부당하면 에러로 반환한다.
+    pub async fn register(&mut self, sources: &[String]) -> Result<Option<RegisterNodeResponse>> {
+        let request = RegisterNodeRequest {
+            node_id: self.node_id.clone(),
+            sources: sources.to_vec(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        };
+
+        for index in self.attempt_order() {
+            let endpoint = &mut self.endpoints[index];
+            let sent = clock_drift::local_millis();
+            let result = endpoint
+                .call(&self.backoff, |mut client| {
+                    let request = telemetry::traced_request(request.clone());
+                    async move { client.register_node(request).await }
+                })
+                .await;
+
+            match result {
+                Ok(response) => {
+                    observe_time(self.drift.as_ref(), response.timestamp, sent);
+                    info!(
+                        "🪪 gRPC: Registered {} with aggregator {}",
+                        self.node_id,
+                        endpoint.url()
+                    );
+                    self.remember(index);
+                    self.registration = Some(response.clone());
+                    return Ok(Some(response));
+                }
+                Err(status) if status.code() == Code::Unimplemented => {
+                    warn!(
+                        "⚠️ gRPC: Aggregator {} does not support RegisterNode",
+                        endpoint.url()
+                    );
+                    self.remember(index);
+                    return Ok(None);
+                }
+                Err(status) if is_transient(status.code()) => {
+                    warn!("❌ gRPC: Cannot reach Aggregator {}: {}", endpoint.url(), status);
+                }
+                Err(status) => {
+                    error!(
+                        "❌ gRPC: Aggregator {} refused registration: {}",
+                        endpoint.url(),
+                        status.message()
+                    );
+                    anyhow::bail!("Registration refused: {}", status.message());
+                }
+            }
+        }
+
+        Err(AggregatorsUnavailable.into())
+    }
+
+    /// 등록 때 받은 Aggregator 설정 (등록 전이거나 지원하지 않는 Aggregator면 None)
+    pub fn registration(&self) -> Option<&RegisterNodeResponse> {
+        self.registration.as_ref()
+    }
+
+    /// 종료 전 정리: 스트림과 gRPC 채널을 닫고 오프라인 큐를 디스크에 기록
+    pub fn close(&mut self) -> Result<()> {
+        self.stream = None;
+        for endpoint in &mut self.endpoints {
+            endpoint.disconnect();
+        }
+        if let Some(queue) = &self.offline_queue {
+            queue.flush()?;
+            if !queue.is_empty() {
+                info!("📥 {} queued price(s) saved for the next run", queue.len());
+            }
+        }
+        debug!("🔌 gRPC: Closed aggregator connections");
+        Ok(())
+    }
+
+    /// 현재 사용 중인 (마지막으로 응답한) Aggregator URL
+    pub fn current_url(&self) -> &str {
+        self.endpoints[self.current].url()
+    }
+
+    /// 현재 사용 중인 Aggregator의 연결 상태
+    pub fn connection_state(&self) -> ConnectionState {
+        self.endpoints[self.current].state()
+    }
+
+    /// Aggregator별 URL과 연결 상태 (우선순위 순)
+    pub fn endpoint_states(&self) -> Vec<(&str, ConnectionState)> {
+        self.endpoints
+            .iter()
+            .map(|endpoint| (endpoint.url(), endpoint.state()))
+            .collect()
+    }
+
+    /// Node ID 반환
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -134,4 +1244,607 @@ mod tests {
             Err(e) => println!("gRPC connection failed (expected): {}", e),
         }
     }
+
+    use oracle::oracle_service_server::{OracleService, OracleServiceServer};
+    use oracle::{
+        AggregatedPriceUpdate, ConfigRequest, ConfigResponse, GetPriceRequest, GetPriceResponse,
+        GetSignedPriceRequest, GetSignedPriceResponse, GetSlaRequest, GetSlaResponse,
+        GetStatsRequest, GetStatsResponse, GetTwapRequest, GetTwapResponse, HealthResponse,
+        NodeHistoryRequest, NodeHistoryResponse, PriceBatchRequest, PriceBatchResponse,
+        PriceHistoryRequest, PriceHistoryResponse, RegisterNodeRequest, RegisterNodeResponse,
+        SetNodeReputationRequest, SetNodeReputationResponse, SubscribePricesRequest,
+    };
+    use oracle_vm_common::types::AssetPair;
+    use oracle_vm_common::Price;
+    use std::net::SocketAddr;
+    use std::pin::Pin;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use tokio_stream::wrappers::TcpListenerStream;
+    use tokio_stream::Stream;
+    use tonic::{Response, Status};
+
+    /// 받은 제출을 기록하는 테스트용 Aggregator
+    ///
+    /// `reject_with`가 있으면 그 에러로 거부하고, `unavailable_for`가 남아 있는 동안은 `Unavailable`로 거부한다.
+    /// 가격 스트림은 지원하지 않으며, `close_streams`가 켜져 있으면 스트림을 열자마자 닫는다.
+    /// 응답의 `timestamp`는 현재 시각에 `clock_skew_secs`를 더한 값이고, 집계 가격은 `aggregated_price`에 넣어 둔 값이다.
+    #[derive(Clone, Default)]
+    struct RecordingAggregator {
+        received: Arc<Mutex<Vec<PriceRequest>>>,
+        aggregated_price: Arc<Mutex<Option<f64>>>,
+        attempts: Arc<AtomicUsize>,
+        unavailable_for: Arc<AtomicUsize>,
+        reject_with: Option<Code>,
+        close_streams: bool,
+        clock_skew_secs: i64,
+    }
+
+    impl RecordingAggregator {
+        fn server_time(&self) -> u64 {
+            (chrono::Utc::now().timestamp() + self.clock_skew_secs) as u64
+        }
+    }
+
+    // 테스트용 빠른 백오프
+    const FAST_BACKOFF: Backoff = Backoff::new(Duration::from_millis(1), Duration::from_millis(5), 3);
+
+    #[tonic::async_trait]
+    impl OracleService for RecordingAggregator {
+        type StreamPricesStream =
+            Pin<Box<dyn Stream<Item = Result<AggregatedPriceUpdate, Status>> + Send + 'static>>;
+        type SubscribePricesStream = Self::StreamPricesStream;
+
+        async fn submit_price(
+            &self,
+            request: tonic::Request<PriceRequest>,
+        ) -> Result<Response<PriceResponse>, Status> {
+            self.attempts.fetch_add(1, Ordering::SeqCst);
+            if let Some(code) = self.reject_with {
+                return Err(Status::new(code, "rejected by test aggregator"));
+            }
+            let remaining = self.unavailable_for.load(Ordering::SeqCst);
+            if remaining > 0 {
+                self.unavailable_for.store(remaining - 1, Ordering::SeqCst);
+                return Err(Status::unavailable("restarting"));
+            }
+            self.received.lock().unwrap().push(request.into_inner());
+            Ok(Response::new(PriceResponse {
+                success: true,
+                message: "ok".to_string(),
+                timestamp: self.server_time(),
+                aggregated_price: *self.aggregated_price.lock().unwrap(),
+                ..Default::default()
+            }))
+        }
+
+        async fn stream_prices(
+            &self,
+            _request: tonic::Request<tonic::Streaming<PriceRequest>>,
+        ) -> Result<Response<Self::StreamPricesStream>, Status> {
+            if self.close_streams {
+                return Ok(Response::new(Box::pin(tokio_stream::empty())));
+            }
+            Err(Status::unimplemented("not used"))
+        }
+
+        async fn health_check(
+            &self,
+            _request: tonic::Request<HealthRequest>,
+        ) -> Result<Response<HealthResponse>, Status> {
+            Ok(Response::new(HealthResponse {
+                healthy: true,
+                timestamp: self.server_time(),
+                ..Default::default()
+            }))
+        }
+
+        async fn update_config(
+            &self,
+            _request: tonic::Request<ConfigRequest>,
+        ) -> Result<Response<ConfigResponse>, Status> {
+            Err(Status::unimplemented("not used"))
+        }
+
+        async fn get_aggregated_price(
+            &self,
+            _request: tonic::Request<GetPriceRequest>,
+        ) -> Result<Response<GetPriceResponse>, Status> {
+            Err(Status::unimplemented("not used"))
+        }
+
+        async fn get_stats(
+            &self,
+            _request: tonic::Request<GetStatsRequest>,
+        ) -> Result<Response<GetStatsResponse>, Status> {
+            Err(Status::unimplemented("not used"))
+        }
+
+        async fn submit_price_batch(
+            &self,
+            _request: tonic::Request<PriceBatchRequest>,
+        ) -> Result<Response<PriceBatchResponse>, Status> {
+            Err(Status::unimplemented("not used"))
+        }
+
+        async fn get_price_history(
+            &self,
+            _request: tonic::Request<PriceHistoryRequest>,
+        ) -> Result<Response<PriceHistoryResponse>, Status> {
+            Err(Status::unimplemented("not used"))
+        }
+
+        async fn get_twap(
+            &self,
+            _request: tonic::Request<GetTwapRequest>,
+        ) -> Result<Response<GetTwapResponse>, Status> {
+            Err(Status::unimplemented("not used"))
+        }
+
+        async fn set_node_reputation(
+            &self,
+            _request: tonic::Request<SetNodeReputationRequest>,
+        ) -> Result<Response<SetNodeReputationResponse>, Status> {
+            Err(Status::unimplemented("not used"))
+        }
+
+        async fn get_sla(
+            &self,
+            _request: tonic::Request<GetSlaRequest>,
+        ) -> Result<Response<GetSlaResponse>, Status> {
+            Err(Status::unimplemented("not used"))
+        }
+
+        async fn get_node_history(
+            &self,
+            _request: tonic::Request<NodeHistoryRequest>,
+        ) -> Result<Response<NodeHistoryResponse>, Status> {
+            Err(Status::unimplemented("not used"))
+        }
+
+        async fn get_signed_price(
+            &self,
+            _request: tonic::Request<GetSignedPriceRequest>,
+        ) -> Result<Response<GetSignedPriceResponse>, Status> {
+            Err(Status::unimplemented("not used"))
+        }
+
+        async fn subscribe_prices(
+            &self,
+            _request: tonic::Request<SubscribePricesRequest>,
+        ) -> Result<Response<Self::SubscribePricesStream>, Status> {
+            Err(Status::unimplemented("not used"))
+        }
+
+        async fn register_node(
+            &self,
+            request: tonic::Request<RegisterNodeRequest>,
+        ) -> Result<Response<RegisterNodeResponse>, Status> {
+            if let Some(code) = self.reject_with {
+                return Err(Status::new(code, "rejected by test aggregator"));
+            }
+            Ok(Response::new(RegisterNodeResponse {
+                timestamp: self.server_time(),
+                window_secs: 60,
+                min_quorum_nodes: 3,
+                allowed_nodes: vec![request.into_inner().node_id],
+                aggregation_mode: "arithmetic".to_string(),
+                strategy: "median".to_string(),
+                ..Default::default()
+            }))
+        }
+    }
+
+    async fn spawn_aggregator(aggregator: RecordingAggregator) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(OracleServiceServer::new(aggregator))
+                .serve_with_incoming(TcpListenerStream::new(listener)),
+        );
+        format!("http://{}", addr)
+    }
+
+    // 아무도 듣지 않는 주소
+    fn unreachable_url() -> String {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr: SocketAddr = listener.local_addr().unwrap();
+        drop(listener);
+        format!("http://{}", addr)
+    }
+
+    fn price_data(price: f64) -> PriceData {
+        PriceData {
+            pair: AssetPair::btc_usd(),
+            price: Price::from_f64_dollars(price, Price::USD_DECIMALS).unwrap(),
+            timestamp: chrono::Utc::now(),
+            volume: None,
+            source: "binance".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fails_over_to_secondary_when_primary_is_down() {
+        let secondary = RecordingAggregator::default();
+        let primary_url = unreachable_url();
+        let secondary_url = spawn_aggregator(secondary.clone()).await;
+
+        let mut client = MultiAggregatorClient::new(&[&primary_url, &secondary_url])
+            .unwrap()
+            .with_node_id("node-a")
+            .with_backoff(FAST_BACKOFF);
+        assert_eq!(client.current_url(), primary_url);
+
+        client.submit_price(&price_data(70_000.0)).await.unwrap();
+        assert_eq!(client.current_url(), secondary_url);
+
+        // 이후 제출은 기억된 secondary로 바로 전송
+        client.submit_price(&price_data(70_100.0)).await.unwrap();
+        assert_eq!(client.current_url(), secondary_url);
+
+        let received = secondary.received.lock().unwrap();
+        assert_eq!(received.len(), 2);
+        assert!(received.iter().all(|request| request.node_id == "node-a"));
+        assert_eq!(received[1].price_scaled, Some(7_010_000));
+    }
+
+    #[tokio::test]
+    async fn test_register_returns_config_from_first_reachable_aggregator() {
+        let secondary_url = spawn_aggregator(RecordingAggregator::default()).await;
+        let mut client = MultiAggregatorClient::new(&[&unreachable_url(), &secondary_url])
+            .unwrap()
+            .with_node_id("node-a")
+            .with_backoff(FAST_BACKOFF);
+        assert!(client.registration().is_none());
+
+        let sources = vec!["binance".to_string()];
+        let config = client.register(&sources).await.unwrap().unwrap();
+        assert_eq!(config.window_secs, 60);
+        assert_eq!(config.min_quorum_nodes, 3);
+        assert_eq!(config.allowed_nodes, ["node-a"]);
+        assert_eq!(config.aggregation_mode, "arithmetic");
+        assert_eq!(client.registration(), Some(&config));
+        assert_eq!(client.current_url(), secondary_url);
+
+        // RegisterNode가 없는 이전 Aggregator는 설정 없이 진행
+        let old = spawn_aggregator(RecordingAggregator {
+            reject_with: Some(Code::Unimplemented),
+            ..RecordingAggregator::default()
+        })
+        .await;
+        let mut client = MultiAggregatorClient::new(&[old]).unwrap();
+        assert_eq!(client.register(&sources).await.unwrap(), None);
+
+        // 허용 목록에 없는 노드처럼 거부당하면 다른 Aggregator로 넘어가지 않고 에러
+        let refusing = spawn_aggregator(RecordingAggregator {
+            reject_with: Some(Code::PermissionDenied),
+            ..RecordingAggregator::default()
+        })
+        .await;
+        let mut client = MultiAggregatorClient::new(&[refusing, secondary_url]).unwrap();
+        let error = client.register(&sources).await.unwrap_err();
+        assert!(error.to_string().contains("Registration refused"), "{}", error);
+        assert!(client.registration().is_none());
+
+        let mut client = MultiAggregatorClient::new(&[unreachable_url()])
+            .unwrap()
+            .with_backoff(FAST_BACKOFF);
+        let error = client.register(&sources).await.unwrap_err();
+        assert!(error.is::<AggregatorsUnavailable>());
+    }
+
+    #[tokio::test]
+    async fn test_does_not_fail_over_on_other_errors() {
+        let primary = RecordingAggregator {
+            reject_with: Some(Code::InvalidArgument),
+            ..RecordingAggregator::default()
+        };
+        let secondary = RecordingAggregator::default();
+        let primary_url = spawn_aggregator(primary.clone()).await;
+        let secondary_url = spawn_aggregator(secondary.clone()).await;
+
+        let mut client = MultiAggregatorClient::new(&[&primary_url, &secondary_url])
+            .unwrap()
+            .with_backoff(FAST_BACKOFF);
+
+        // 영구적 오류는 재시도하지 않음
+        assert!(client.submit_price(&price_data(70_000.0)).await.is_err());
+        assert_eq!(primary.attempts.load(Ordering::SeqCst), 1);
+        assert_eq!(client.current_url(), primary_url);
+        assert_eq!(client.connection_state(), ConnectionState::Connected);
+        assert!(secondary.received.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_all_aggregators_down() {
+        let mut client = MultiAggregatorClient::new(&[unreachable_url(), unreachable_url()])
+            .unwrap()
+            .with_backoff(FAST_BACKOFF);
+
+        let error = client.submit_price(&price_data(70_000.0)).await.unwrap_err();
+        assert!(error.to_string().contains("unavailable"));
+        assert!(!client.check_health().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_transient_errors_are_retried_on_the_same_aggregator() {
+        let primary = RecordingAggregator::default();
+        primary.unavailable_for.store(2, Ordering::SeqCst);
+        let secondary = RecordingAggregator::default();
+        let primary_url = spawn_aggregator(primary.clone()).await;
+        let secondary_url = spawn_aggregator(secondary.clone()).await;
+
+        let mut client = MultiAggregatorClient::new(&[&primary_url, &secondary_url])
+            .unwrap()
+            .with_backoff(FAST_BACKOFF);
+        assert_eq!(client.connection_state(), ConnectionState::Idle);
+
+        client.submit_price(&price_data(70_000.0)).await.unwrap();
+
+        // 두 번 실패 후 세 번째 시도에 성공, 장애 조치 없음
+        assert_eq!(primary.attempts.load(Ordering::SeqCst), 3);
+        assert_eq!(primary.received.lock().unwrap().len(), 1);
+        assert!(secondary.received.lock().unwrap().is_empty());
+        assert_eq!(client.current_url(), primary_url);
+        assert_eq!(client.connection_state(), ConnectionState::Connected);
+    }
+
+    #[tokio::test]
+    async fn test_streaming_falls_back_to_unary_when_unimplemented() {
+        let aggregator = RecordingAggregator::default();
+        let url = spawn_aggregator(aggregator.clone()).await;
+        let mut client = MultiAggregatorClient::new(&[&url])
+            .unwrap()
+            .with_streaming(true);
+
+        client.submit_price(&price_data(70_000.0)).await.unwrap();
+
+        // 스트림을 지원하지 않으면 같은 제출을 단건으로 보내고 이후로도 단건 제출
+        assert!(!client.is_streaming());
+        assert_eq!(aggregator.received.lock().unwrap().len(), 1);
+        client.submit_price(&price_data(70_001.0)).await.unwrap();
+        assert_eq!(aggregator.received.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_streaming_falls_back_to_unary_after_repeated_breaks() {
+        let aggregator = RecordingAggregator {
+            close_streams: true,
+            ..RecordingAggregator::default()
+        };
+        let url = spawn_aggregator(aggregator.clone()).await;
+        let mut client = MultiAggregatorClient::new(&[&url])
+            .unwrap()
+            .with_streaming(true);
+
+        let mut submissions = 0;
+        while client.is_streaming() && submissions < 20 {
+            client.submit_price(&price_data(70_000.0)).await.unwrap();
+            submissions += 1;
+            // 수신 태스크가 스트림 종료를 확인할 시간
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+
+        assert!(!client.is_streaming());
+        assert_eq!(submissions, MAX_STREAM_BREAKS as usize + 1);
+
+        // 전환 후에는 모든 제출이 단건으로 도착
+        let delivered = aggregator.received.lock().unwrap().len();
+        assert!(delivered >= 1);
+        client.submit_price(&price_data(70_001.0)).await.unwrap();
+        assert_eq!(aggregator.received.lock().unwrap().len(), delivered + 1);
+    }
+
+    #[tokio::test]
+    async fn test_refuses_to_submit_when_clock_drift_exceeds_hard_limit() {
+        let aggregator = RecordingAggregator {
+            clock_skew_secs: 30,
+            ..RecordingAggregator::default()
+        };
+        let url = spawn_aggregator(aggregator.clone()).await;
+        let mut client = MultiAggregatorClient::new(&[&url])
+            .unwrap()
+            .with_backoff(FAST_BACKOFF)
+            .with_drift_monitor(DriftMonitor::default());
+
+        // 아직 표본이 없으므로 첫 제출은 보내고, 응답 시각으로 오차를 추정
+        client.submit_price(&price_data(70_000.0)).await.unwrap();
+        assert_eq!(
+            client.drift_monitor().unwrap().level(),
+            clock_drift::DriftLevel::Refuse
+        );
+
+        let error = client.submit_price(&price_data(70_100.0)).await.unwrap_err();
+        assert!(error.is::<clock_drift::ClockDriftError>());
+        assert_eq!(aggregator.received.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_alerts_when_aggregator_median_diverges_for_n_rounds() {
+        let aggregator = RecordingAggregator::default();
+        let url = spawn_aggregator(aggregator.clone()).await;
+        let mut client = MultiAggregatorClient::new(&[&url])
+            .unwrap()
+            .with_backoff(FAST_BACKOFF)
+            .with_divergence_monitor(DivergenceMonitor::new(100, 3));
+        async fn submit_against(
+            client: &mut MultiAggregatorClient,
+            aggregator: &RecordingAggregator,
+            network: f64,
+        ) {
+            *aggregator.aggregated_price.lock().unwrap() = Some(network);
+            client.submit_price(&price_data(70_000.0)).await.unwrap();
+        }
+
+        // 1.5% 괴리: 세 번째 라운드에 정확히 알림
+        for round in 1..=3 {
+            submit_against(&mut client, &aggregator, 71_050.0).await;
+            let report = client.divergence_monitor(&AssetPair::btc_usd()).unwrap().report();
+            assert_eq!(report.consecutive, round);
+            assert_eq!(report.alerting, round == 3);
+        }
+        // 수렴하면 해제되고 다시 처음부터 셈
+        submit_against(&mut client, &aggregator, 70_007.0).await;
+        let report = client.divergence_monitor(&AssetPair::btc_usd()).unwrap().report();
+        assert_eq!((report.consecutive, report.alerting, report.alerts), (0, false, 1));
+        assert!((report.last_bps.unwrap() - 1.0).abs() < 1e-6);
+
+        submit_against(&mut client, &aggregator, 69_000.0).await;
+        submit_against(&mut client, &aggregator, 69_000.0).await;
+        let report = client.divergence_monitor(&AssetPair::btc_usd()).unwrap().report();
+        assert_eq!((report.consecutive, report.alerting), (2, false));
+    }
+
+    #[tokio::test]
+    async fn test_corrects_submission_timestamp_by_estimated_offset() {
+        let aggregator = RecordingAggregator {
+            clock_skew_secs: 5,
+            ..RecordingAggregator::default()
+        };
+        let url = spawn_aggregator(aggregator.clone()).await;
+        let mut client = MultiAggregatorClient::new(&[&url])
+            .unwrap()
+            .with_backoff(FAST_BACKOFF)
+            .with_drift_monitor(DriftMonitor::default().with_correction(true));
+
+        // 경고 수준의 오차는 제출을 막지 않음
+        client.health().await.unwrap();
+        assert_eq!(
+            client.drift_monitor().unwrap().level(),
+            clock_drift::DriftLevel::Warn
+        );
+
+        let data = price_data(70_000.0);
+        client.submit_price(&data).await.unwrap();
+        let sent = aggregator.received.lock().unwrap()[0].timestamp as i64;
+        let shift = sent - data.timestamp.timestamp();
+        assert!((4..=6).contains(&shift), "{}", shift);
+    }
+
+    #[tokio::test]
+    async fn test_fails_back_to_primary_after_outage() {
+        let primary = RecordingAggregator::default();
+        // 첫 제출의 모든 재시도 동안 내려가 있음
+        primary
+            .unavailable_for
+            .store(FAST_BACKOFF.max_retries as usize + 1, Ordering::SeqCst);
+        let secondary = RecordingAggregator::default();
+        let primary_url = spawn_aggregator(primary.clone()).await;
+        let secondary_url = spawn_aggregator(secondary.clone()).await;
+
+        let mut client = MultiAggregatorClient::new(&[&primary_url, &secondary_url])
+            .unwrap()
+            .with_backoff(FAST_BACKOFF)
+            .with_failback_after(Duration::from_millis(100));
+
+        client.submit_price(&price_data(70_000.0)).await.unwrap();
+        assert_eq!(client.current_url(), secondary_url);
+        assert_eq!(
+            client.endpoint_states(),
+            vec![
+                (primary_url.as_str(), ConnectionState::Disconnected),
+                (secondary_url.as_str(), ConnectionState::Connected),
+            ]
+        );
+
+        // 대기 시간 전에는 secondary에 계속 제출
+        client.submit_price(&price_data(70_100.0)).await.unwrap();
+        assert_eq!(secondary.received.lock().unwrap().len(), 2);
+        assert!(primary.received.lock().unwrap().is_empty());
+
+        // 대기 시간이 지나면 복구된 primary로 돌아감
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        client.submit_price(&price_data(70_200.0)).await.unwrap();
+        assert_eq!(client.current_url(), primary_url);
+        assert_eq!(primary.received.lock().unwrap().len(), 1);
+        assert_eq!(secondary.received.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_fail_back_returns_to_secondary_while_primary_is_down() {
+        let secondary = RecordingAggregator::default();
+        let primary_url = unreachable_url();
+        let secondary_url = spawn_aggregator(secondary.clone()).await;
+
+        let mut client = MultiAggregatorClient::new(&[&primary_url, &secondary_url])
+            .unwrap()
+            .with_backoff(FAST_BACKOFF)
+            .with_failback_after(Duration::ZERO);
+
+        for price in [70_000.0, 70_100.0] {
+            client.submit_price(&price_data(price)).await.unwrap();
+            assert_eq!(client.current_url(), secondary_url);
+        }
+        assert_eq!(secondary.received.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_fan_out_submits_to_every_aggregator() {
+        let primary = RecordingAggregator::default();
+        let secondary = RecordingAggregator::default();
+        let primary_url = spawn_aggregator(primary.clone()).await;
+        let secondary_url = spawn_aggregator(secondary.clone()).await;
+
+        let mut client = MultiAggregatorClient::new(&[&primary_url, &secondary_url])
+            .unwrap()
+            .with_backoff(FAST_BACKOFF)
+            .with_mode(AggregatorMode::FanOut)
+            .with_streaming(true);
+
+        client.submit_price(&price_data(70_000.0)).await.unwrap();
+        assert_eq!(primary.received.lock().unwrap().len(), 1);
+        assert_eq!(secondary.received.lock().unwrap().len(), 1);
+        assert_eq!(client.current_url(), primary_url);
+    }
+
+    #[tokio::test]
+    async fn test_fan_out_succeeds_when_any_aggregator_accepts() {
+        let refusing = RecordingAggregator {
+            reject_with: Some(Code::InvalidArgument),
+            ..RecordingAggregator::default()
+        };
+        let accepting = RecordingAggregator::default();
+        let refusing_url = spawn_aggregator(refusing.clone()).await;
+        let accepting_url = spawn_aggregator(accepting.clone()).await;
+
+        let mut client =
+            MultiAggregatorClient::new(&[&unreachable_url(), &refusing_url, &accepting_url])
+                .unwrap()
+                .with_backoff(FAST_BACKOFF)
+                .with_mode(AggregatorMode::FanOut);
+
+        client.submit_price(&price_data(70_000.0)).await.unwrap();
+        assert_eq!(accepting.received.lock().unwrap().len(), 1);
+        assert_eq!(client.current_url(), accepting_url);
+
+        // 받아들인 곳이 없으면 거부 오류, 모두 연결할 수 없으면 AggregatorsUnavailable
+        let mut refused = MultiAggregatorClient::new(&[&unreachable_url(), &refusing_url])
+            .unwrap()
+            .with_backoff(FAST_BACKOFF)
+            .with_mode(AggregatorMode::FanOut);
+        let error = refused.submit_price(&price_data(70_000.0)).await.unwrap_err();
+        assert!(!error.is::<AggregatorsUnavailable>());
+
+        let mut down = MultiAggregatorClient::new(&[unreachable_url(), unreachable_url()])
+            .unwrap()
+            .with_backoff(FAST_BACKOFF)
+            .with_mode(AggregatorMode::FanOut);
+        let error = down.submit_price(&price_data(70_000.0)).await.unwrap_err();
+        assert!(error.is::<AggregatorsUnavailable>());
+    }
+
+    #[test]
+    fn test_parse_aggregator_mode() {
+        assert_eq!("failover".parse(), Ok(AggregatorMode::Failover));
+        assert_eq!("fan-out".parse(), Ok(AggregatorMode::FanOut));
+        assert_eq!("FanOut".parse(), Ok(AggregatorMode::FanOut));
+        assert!("broadcast".parse::<AggregatorMode>().is_err());
+        assert_eq!(AggregatorMode::FanOut.to_string(), "fan-out");
+    }
+
+    #[test]
+    fn test_requires_at_least_one_aggregator() {
+        assert!(MultiAggregatorClient::new::<&str>(&[]).is_err());
+    }
 }
@@ -0,0 +1,272 @@
+//! Aggregator 하트비트 (health_check를 제출보다 짧은 주기로 전송)
+//!
+//! 거래소 수집이 실패해 제출이 없어도 Aggregator가 노드를 활성으로 볼 수 있도록 별도 태스크에서
+//! 헬스체크를 보낸다. 제출용과 다른 클라이언트를 사용하므로 실패 시 백오프도 제출 루프와 독립적이다.
+//! 매번 주기에 임의 지연을 더해 같은 주기로 도는 노드들이 한꺼번에 헬스체크를 보내지 않게 한다.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info, warn};
+
+use crate::backoff::Backoff;
+use crate::grpc_client::oracle::HealthResponse;
+use crate::grpc_client::MultiAggregatorClient;
+use crate::scheduler::random_jitter;
+use crate::shutdown::{Shutdown, ShutdownSignal};
+use crate::supervisor::{catch_panic, Supervisor, DEFAULT_MAX_RESTARTS, GAVE_UP_EXIT_CODE};
+
+/// 기본 하트비트 주기
+pub const DEFAULT_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(20);
+/// 하트비트 주기에 더하는 임의 지연의 기본 최대값
+pub const DEFAULT_HEARTBEAT_JITTER: Duration = Duration::from_secs(5);
+/// 노드가 기대하는 Aggregator 버전
+pub const EXPECTED_AGGREGATOR_VERSION: &str = "1.0.0";
+/// 연속 실패 시 하트비트 간격이 늘어나는 최대 배수
+const MAX_BACKOFF_FACTOR: u32 = 8;
+
+/// 하트비트 주기: `interval`(실패가 이어지면 백오프)에 0 ~ `jitter` 사이의 임의 지연을 더함
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeartbeatSchedule {
+    pub interval: Duration,
+    pub jitter: Duration,
+}
+
+impl HeartbeatSchedule {
+    pub const fn new(interval: Duration, jitter: Duration) -> Self {
+        Self { interval, jitter }
+    }
+
+    /// 연속 실패 `failures`번 뒤 다음 헬스체크까지의 대기 시간
+    ///
+    /// 실패가 이어지면 간격을 두 배씩(최대 8배) 늘리고, 임의 지연은 늘어난 간격에도 그대로 더한다.
+    pub fn next_delay(&self, failures: u32) -> Duration {
+        let backoff = Backoff::new(self.interval, self.interval * MAX_BACKOFF_FACTOR, 0);
+        backoff.delay(failures) + random_jitter(self.jitter)
+    }
+}
+
+/// 임의 지연 없이 고정 주기
+impl From<Duration> for HeartbeatSchedule {
+    fn from(interval: Duration) -> Self {
+        Self::new(interval, Duration::ZERO)
+    }
+}
+
+/// 하트비트 응답에서 발견한 문제
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HeartbeatAlert {
+    /// Aggregator가 `healthy: false`로 응답 (Aggregator가 보낸 원인)
+    Unhealthy(String),
+    /// 기대와 다른 Aggregator 버전
+    UnexpectedVersion(String),
+    /// 활성 노드가 이 노드 하나뿐
+    Alone,
+}
+
+/// 헬스체크 응답 검사
+pub fn assess(response: &HealthResponse, expected_version: &str) -> Vec<HeartbeatAlert> {
+    let mut alerts = Vec::new();
+    if !response.healthy {
+        alerts.push(HeartbeatAlert::Unhealthy(response.reason.clone()));
+    }
+    if response.version != expected_version {
+        alerts.push(HeartbeatAlert::UnexpectedVersion(response.version.clone()));
+    }
+    if response.active_nodes == 1 {
+        alerts.push(HeartbeatAlert::Alone);
+    }
+    alerts
+}
+
+/// 실행 중인 하트비트 태스크
+pub struct Heartbeat {
+    shutdown: Shutdown,
+    task: JoinHandle<()>,
+    beats: Arc<AtomicU64>,
+}
+
+impl Heartbeat {
+    /// 즉시 한 번 보낸 뒤 `schedule`에 따라 헬스체크 전송 (`Duration`이면 임의 지연 없음)
+    ///
+    /// 실패가 이어지면 간격을 두 배씩(최대 8배) 늘리고, 응답을 받으면 원래 주기로 돌아온다.
+    pub fn spawn(client: MultiAggregatorClient, schedule: impl Into<HeartbeatSchedule>) -> Self {
+        Self::spawn_until(client, schedule, Shutdown::new())
+    }
+
+    /// `spawn`과 같되 노드 전체의 종료 요청을 받으면 멈춤
+    pub fn spawn_until(
+        client: MultiAggregatorClient,
+        schedule: impl Into<HeartbeatSchedule>,
+        shutdown: Shutdown,
+    ) -> Self {
+        let supervisor = Supervisor::new("heartbeat", DEFAULT_MAX_RESTARTS);
+        Self::spawn_supervised(client, schedule, shutdown, supervisor)
+    }
+
+    /// `spawn_until`과 같되 패닉하면 `supervisor`에 따라 다시 시작
+    ///
+    /// 재시작 한도를 넘으면 외부 감시자가 이어받도록 프로세스를 종료한다.
+    pub fn spawn_supervised(
+        mut client: MultiAggregatorClient,
+        schedule: impl Into<HeartbeatSchedule>,
+        shutdown: Shutdown,
+        mut supervisor: Supervisor,
+    ) -> Self {
+        let schedule = schedule.into();
+        let mut stopped = shutdown.subscribe();
+        let beats = Arc::new(AtomicU64::new(0));
+        let task_beats = beats.clone();
+
+        let task = tokio::spawn(async move {
+            loop {
+                let attempt =
+                    beat_until_stopped(&mut client, schedule, stopped.clone(), &task_beats);
+                let Err(panic) = catch_panic(attempt).await else {
+                    break;
+                };
+                match supervisor.on_panic(panic) {
+                    Ok(delay) => tokio::select! {
+                        _ = stopped.wait() => break,
+                        _ = tokio::time::sleep(delay) => {}
+                    },
+                    Err(gave_up) => {
+                        error!("❌ {}", gave_up);
+                        std::process::exit(GAVE_UP_EXIT_CODE);
+                    }
+                }
+            }
+            debug!("💤 Heartbeat stopped");
+        });
+
+        Self {
+            shutdown,
+            task,
+            beats,
+        }
+    }
+
+    /// 지금까지 받은 헬스체크 응답 수
+    pub fn beats(&self) -> u64 {
+        self.beats.load(Ordering::Relaxed)
+    }
+
+    /// 태스크를 멈추고 끝날 때까지 대기
+    pub async fn shutdown(self) {
+        self.shutdown.trigger();
+        let _ = self.task.await;
+    }
+}
+
+// 종료 요청까지 헬스체크 반복
+async fn beat_until_stopped(
+    client: &mut MultiAggregatorClient,
+    schedule: HeartbeatSchedule,
+    mut stopped: ShutdownSignal,
+    beats: &AtomicU64,
+) {
+    let mut failures = 0u32;
+    let mut alerts = Vec::new();
+    loop {
+        let result = tokio::select! {
+            _ = stopped.wait() => break,
+            result = client.health() => result,
+        };
+        match result {
+            Ok(response) => {
+                failures = 0;
+                beats.fetch_add(1, Ordering::Relaxed);
+                debug!("💓 Heartbeat: {} active node(s)", response.active_nodes);
+                report(&mut alerts, assess(&response, EXPECTED_AGGREGATOR_VERSION));
+            }
+            Err(e) => {
+                failures = failures.saturating_add(1);
+                warn!("💔 Heartbeat failed ({} in a row): {:#}", failures, e);
+            }
+        }
+
+        tokio::select! {
+            _ = stopped.wait() => break,
+            _ = tokio::time::sleep(schedule.next_delay(failures)) => {}
+        }
+    }
+}
+
+// 새로 생긴 문제는 경고로, 사라진 문제는 정보로 기록 (같은 문제를 매번 반복하지 않음)
+fn report(current: &mut Vec<HeartbeatAlert>, next: Vec<HeartbeatAlert>) {
+    for alert in next.iter().filter(|alert| !current.contains(alert)) {
+        match alert {
+            HeartbeatAlert::Unhealthy(reason) => {
+                warn!("🚨 Heartbeat: Aggregator reports unhealthy: {}", reason)
+            }
+            HeartbeatAlert::UnexpectedVersion(version) => warn!(
+                "🚨 Heartbeat: Aggregator version {:?}, expected {:?}",
+                version, EXPECTED_AGGREGATOR_VERSION
+            ),
+            HeartbeatAlert::Alone => {
+                warn!("🚨 Heartbeat: This node is the only active node")
+            }
+        }
+    }
+    for alert in current.iter().filter(|alert| !next.contains(alert)) {
+        info!("✅ Heartbeat: Resolved {:?}", alert);
+    }
+    *current = next;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(healthy: bool, version: &str, active_nodes: u32) -> HealthResponse {
+        HealthResponse {
+            healthy,
+            timestamp: 1_700_000_000,
+            active_nodes,
+            version: version.to_string(),
+            sources: Vec::new(),
+            reason: if healthy { "" } else { "below quorum" }.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_assess_flags_unhealthy_version_and_solitude() {
+        assert!(assess(&response(true, "1.0.0", 3), "1.0.0").is_empty());
+        assert_eq!(
+            assess(&response(false, "2.0.0", 1), "1.0.0"),
+            vec![
+                HeartbeatAlert::Unhealthy("below quorum".to_string()),
+                HeartbeatAlert::UnexpectedVersion("2.0.0".to_string()),
+                HeartbeatAlert::Alone,
+            ]
+        );
+        // 아직 아무도 활성이 아닌 경우는 혼자인 것으로 보지 않음
+        assert!(assess(&response(true, "1.0.0", 0), "1.0.0").is_empty());
+    }
+
+    #[test]
+    fn test_next_delay_is_spread_within_jitter() {
+        let interval = Duration::from_secs(20);
+        let jitter = Duration::from_secs(5);
+        let schedule = HeartbeatSchedule::new(interval, jitter);
+
+        let delays: Vec<Duration> = (0..200).map(|_| schedule.next_delay(0)).collect();
+        assert!(delays
+            .iter()
+            .all(|delay| (interval..=interval + jitter).contains(delay)));
+        // 노드들이 같은 순간에 몰리지 않도록 구간 양쪽 절반에 모두 흩어짐
+        let midpoint = interval + jitter / 2;
+        assert!(delays.iter().any(|delay| *delay < midpoint));
+        assert!(delays.iter().any(|delay| *delay > midpoint));
+
+        // 백오프로 늘어난 간격에도 같은 폭의 임의 지연
+        let backed_off = schedule.next_delay(2);
+        assert!((interval * 4..=interval * 4 + jitter).contains(&backed_off));
+
+        // 고정 주기는 그대로
+        let fixed = HeartbeatSchedule::from(interval);
+        assert_eq!(fixed.next_delay(0), interval);
+        assert_eq!(fixed.next_delay(10), interval * MAX_BACKOFF_FACTOR);
+    }
+}
@@ -0,0 +1,288 @@
+//! 노드 신원 키 (ed25519)
+//!
+//! 키 파일은 공개키와 비밀키를 hex로 담은 JSON이며 Unix에서는 소유자만 읽을 수 있게(0600) 만든다.
+//! 공개키의 SHA-256 앞부분을 지문으로 사용해 기본 node_id를 만든다.
+
+use ed25519_dalek::{SigningKey, VerifyingKey, PUBLIC_KEY_LENGTH, SECRET_KEY_LENGTH};
+use oracle_vm_common::crypto::sha256;
+use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// 키 파일 기본 경로
+pub const DEFAULT_KEY_PATH: &str = "data/node_key.json";
+/// 키 파일의 알고리즘 표기
+pub const KEY_ALGORITHM: &str = "ed25519";
+/// 지문에 사용하는 공개키 해시 바이트 수
+const FINGERPRINT_BYTES: usize = 8;
+
+/// 키 파일 읽기/쓰기 실패
+#[derive(Debug, thiserror::Error)]
+pub enum KeyFileError {
+    #[error("Key file {0} does not exist (create one with `oracle-node keygen --out {0}`)")]
+    Missing(PathBuf),
+    #[error("Cannot read key file {path}: {source}")]
+    Unreadable {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Malformed key file {path}: {reason}")]
+    Malformed { path: PathBuf, reason: String },
+    #[error("Key file {0} already exists")]
+    AlreadyExists(PathBuf),
+    #[error("Cannot write key file {path}: {source}")]
+    Unwritable {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+}
+
+// 키 파일 형식
+#[derive(Serialize, Deserialize)]
+struct KeyFile {
+    algorithm: String,
+    public_key: String,
+    secret_key: String,
+}
+
+/// 노드 신원 키쌍
+pub struct NodeIdentity {
+    signing_key: SigningKey,
+}
+
+impl NodeIdentity {
+    /// 새 키쌍 생성
+    pub fn generate() -> Self {
+        Self {
+            signing_key: SigningKey::generate(&mut OsRng),
+        }
+    }
+
+    /// 비밀키 바이트로 생성
+    pub fn from_secret_bytes(secret: &[u8; SECRET_KEY_LENGTH]) -> Self {
+        Self {
+            signing_key: SigningKey::from_bytes(secret),
+        }
+    }
+
+    /// 공개키
+    pub fn public_key(&self) -> VerifyingKey {
+        self.signing_key.verifying_key()
+    }
+
+    /// 공개키 (hex)
+    pub fn public_key_hex(&self) -> String {
+        to_hex(self.public_key().as_bytes())
+    }
+
+    /// 공개키 지문 (공개키 SHA-256의 앞 8바이트, hex)
+    pub fn fingerprint(&self) -> String {
+        to_hex(&sha256(self.public_key().as_bytes())[..FINGERPRINT_BYTES])
+    }
+
+    /// 지문으로 만든 기본 node_id
+    pub fn node_id(&self) -> String {
+        format!("oracle-node-{}", self.fingerprint())
+    }
+
+    /// 서명용 비밀키
+    pub fn signing_key(&self) -> &SigningKey {
+        &self.signing_key
+    }
+
+    /// 키 파일 읽기
+    pub fn load(path: &Path) -> Result<Self, KeyFileError> {
+        let contents = std::fs::read_to_string(path).map_err(|source| {
+            if source.kind() == std::io::ErrorKind::NotFound {
+                KeyFileError::Missing(path.to_path_buf())
+            } else {
+                KeyFileError::Unreadable {
+                    path: path.to_path_buf(),
+                    source,
+                }
+            }
+        })?;
+        let malformed = |reason: String| KeyFileError::Malformed {
+            path: path.to_path_buf(),
+            reason,
+        };
+
+        let file: KeyFile =
+            serde_json::from_str(&contents).map_err(|e| malformed(e.to_string()))?;
+        if file.algorithm != KEY_ALGORITHM {
+            return Err(malformed(format!(
+                "unsupported algorithm {:?}, expected {:?}",
+                file.algorithm, KEY_ALGORITHM
+            )));
+        }
+        let secret: [u8; SECRET_KEY_LENGTH] =
+            from_hex(&file.secret_key).map_err(|e| malformed(format!("secret_key: {}", e)))?;
+        let public: [u8; PUBLIC_KEY_LENGTH] =
+            from_hex(&file.public_key).map_err(|e| malformed(format!("public_key: {}", e)))?;
+
+        let identity = Self::from_secret_bytes(&secret);
+        if identity.public_key().as_bytes() != &public {
+            return Err(malformed(
+                "public_key does not match secret_key".to_string(),
+            ));
+        }
+        Ok(identity)
+    }
+
+    /// 키 파일 쓰기 (이미 있으면 덮어쓰지 않음, Unix에서는 0600)
+    pub fn save(&self, path: &Path) -> Result<(), KeyFileError> {
+        let unwritable = |source| KeyFileError::Unwritable {
+            path: path.to_path_buf(),
+            source,
+        };
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir).map_err(unwritable)?;
+        }
+
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options.open(path).map_err(|source| {
+            if source.kind() == std::io::ErrorKind::AlreadyExists {
+                KeyFileError::AlreadyExists(path.to_path_buf())
+            } else {
+                unwritable(source)
+            }
+        })?;
+
+        let contents = KeyFile {
+            algorithm: KEY_ALGORITHM.to_string(),
+            public_key: self.public_key_hex(),
+            secret_key: to_hex(self.signing_key.as_bytes()),
+        };
+        let json = serde_json::to_string_pretty(&contents).expect("key file serializes");
+        file.write_all(json.as_bytes())
+            .and_then(|()| file.write_all(b"\n"))
+            .and_then(|()| file.sync_all())
+            .map_err(unwritable)
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn from_hex<const N: usize>(hex: &str) -> Result<[u8; N], String> {
+    if hex.len() != N * 2 {
+        return Err(format!(
+            "expected {} hex characters, got {}",
+            N * 2,
+            hex.len()
+        ));
+    }
+    let mut bytes = [0u8; N];
+    for (byte, chunk) in bytes.iter_mut().zip(hex.as_bytes().chunks(2)) {
+        let digits = std::str::from_utf8(chunk).map_err(|_| "invalid hex".to_string())?;
+        *byte = u8::from_str_radix(digits, 16).map_err(|_| "invalid hex".to_string())?;
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path() -> PathBuf {
+        std::env::temp_dir().join(format!("node-key-{}.json", uuid::Uuid::new_v4()))
+    }
+
+    #[test]
+    fn test_generated_key_round_trips_through_file() {
+        let path = temp_path();
+        let identity = NodeIdentity::generate();
+        identity.save(&path).unwrap();
+
+        let loaded = NodeIdentity::load(&path).unwrap();
+        assert_eq!(loaded.public_key(), identity.public_key());
+        assert_eq!(loaded.node_id(), identity.node_id());
+
+        // 기존 키 파일은 덮어쓰지 않음
+        assert!(matches!(
+            NodeIdentity::generate().save(&path),
+            Err(KeyFileError::AlreadyExists(_))
+        ));
+        assert_eq!(
+            NodeIdentity::load(&path).unwrap().node_id(),
+            identity.node_id()
+        );
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_fingerprint_is_stable() {
+        let identity = NodeIdentity::from_secret_bytes(&[7u8; SECRET_KEY_LENGTH]);
+        let again = NodeIdentity::from_secret_bytes(&[7u8; SECRET_KEY_LENGTH]);
+
+        assert_eq!(identity.fingerprint(), again.fingerprint());
+        assert_eq!(identity.fingerprint().len(), FINGERPRINT_BYTES * 2);
+        assert_eq!(
+            identity.fingerprint(),
+            to_hex(&sha256(identity.public_key().as_bytes())[..FINGERPRINT_BYTES])
+        );
+        assert_eq!(
+            identity.node_id(),
+            format!("oracle-node-{}", identity.fingerprint())
+        );
+        assert_ne!(
+            identity.fingerprint(),
+            NodeIdentity::from_secret_bytes(&[8u8; SECRET_KEY_LENGTH]).fingerprint()
+        );
+    }
+
+    #[test]
+    fn test_missing_or_malformed_key_files_are_reported() {
+        let path = temp_path();
+        assert!(matches!(
+            NodeIdentity::load(&path),
+            Err(KeyFileError::Missing(_))
+        ));
+
+        let identity = NodeIdentity::from_secret_bytes(&[7u8; SECRET_KEY_LENGTH]);
+        let other = NodeIdentity::from_secret_bytes(&[8u8; SECRET_KEY_LENGTH]);
+        let secret = to_hex(identity.signing_key().as_bytes());
+        for contents in [
+            "not json".to_string(),
+            format!(
+                r#"{{"algorithm":"secp256k1","public_key":"{}","secret_key":"{}"}}"#,
+                identity.public_key_hex(),
+                secret
+            ),
+            format!(
+                r#"{{"algorithm":"ed25519","public_key":"{}","secret_key":"abcd"}}"#,
+                identity.public_key_hex()
+            ),
+            format!(
+                r#"{{"algorithm":"ed25519","public_key":"{}","secret_key":"{}"}}"#,
+                other.public_key_hex(),
+                secret
+            ),
+        ] {
+            std::fs::write(&path, contents).unwrap();
+            assert!(matches!(
+                NodeIdentity::load(&path),
+                Err(KeyFileError::Malformed { .. })
+            ));
+        }
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -1,18 +1,18 @@
+use crate::backoff::EXCHANGE_BACKOFF;
+use crate::price_band::PriceBands;
 use crate::price_provider::PriceProvider;
 use oracle_vm_common::types::{PriceData, AssetPair};
+use oracle_vm_common::{Price, RoundingMode};
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use chrono::{DateTime, Timelike};
 use reqwest::Client;
 use serde::Deserialize;
 use std::time::Duration;
-use tokio::time::sleep;
-use tracing::{error, info, warn};
+use tracing::info;
 
 /// Kraken API URL
 const KRAKEN_API_URL: &str = "https://api.kraken.com/0/public/OHLC";
-/// 최대 재시도 횟수
-const MAX_RETRIES: u32 = 3;
 /// HTTP 요청 타임아웃 (초)
 const REQUEST_TIMEOUT: u64 = 10;
 
@@ -24,6 +24,7 @@ struct KrakenOHLCResponse {
 }
 
 #[derive(Debug, Deserialize)]
+#[allow(dead_code)] // 응답 형식 전체를 역직렬화 (일부 필드만 사용)
 struct KrakenResult {
     #[serde(rename = "XXBTZUSD")]
     btc_usd: Vec<KrakenOHLC>,
@@ -31,11 +32,14 @@ struct KrakenResult {
 }
 
 #[derive(Debug, Deserialize)]
+#[allow(dead_code)] // 종가와 타임스탬프만 사용
 struct KrakenOHLC(u64, String, String, String, String, String, String, u32); // [timestamp, open, high, low, close, vwap, volume, count]
 
 /// Kraken과 통신하는 클라이언트
 pub struct KrakenClient {
     client: Client,
+    rounding: RoundingMode,
+    bands: PriceBands,
 }
 
 impl KrakenClient {
@@ -47,49 +51,35 @@ impl KrakenClient {
             .build()
             .expect("Failed to create HTTP client");
 
-        Self { client }
+        Self {
+            client,
+            rounding: RoundingMode::default(),
+            bands: PriceBands::default(),
+        }
     }
 
-    /// 비트코인 가격을 가져옵니다 (재시도 포함)
-    pub async fn fetch_btc_price(&self) -> Result<PriceData> {
-        self.fetch_btc_price_with_retry(MAX_RETRIES).await
+    /// 종가를 센트로 바꿀 때의 반올림 방식 지정 (기본값: nearest)
+    pub fn with_rounding_mode(mut self, rounding: RoundingMode) -> Self {
+        self.rounding = rounding;
+        self
     }
 
-    /// 재시도 로직이 포함된 가격 가져오기
-    async fn fetch_btc_price_with_retry(&self, max_retries: u32) -> Result<PriceData> {
-        for attempt in 1..=max_retries {
-            info!(
-                "Fetching BTC price from Kraken (attempt {}/{})",
-                attempt, max_retries
-            );
-
-            match self.fetch_btc_price_once().await {
-                Ok(price_data) => {
-                    info!(
-                        "Successfully fetched BTC price from Kraken: ${:.2}",
-                        price_data.price
-                    );
-                    return Ok(price_data);
-                }
-                Err(e) if attempt < max_retries => {
-                    let wait_time = 2_u64.pow(attempt - 1);
-                    warn!(
-                        "Failed to fetch price from Kraken (attempt {}): {}. Retrying in {}s...",
-                        attempt, e, wait_time
-                    );
-                    sleep(Duration::from_secs(wait_time)).await;
-                }
-                Err(e) => {
-                    error!(
-                        "Failed to fetch price from Kraken after {} attempts: {}",
-                        max_retries, e
-                    );
-                    return Err(e);
-                }
-            }
-        }
+    /// pair별 가격 허용 범위와 범위를 벗어났을 때의 처리 지정 (기본값: BTC/USD 1,000~1,000,000 USD, 경고)
+    pub fn with_price_bands(mut self, bands: PriceBands) -> Self {
+        self.bands = bands;
+        self
+    }
 
-        unreachable!("This should never be reached")
+    /// 비트코인 가격을 가져옵니다 (재시도 포함)
+    pub async fn fetch_btc_price(&self) -> Result<PriceData> {
+        let price_data = EXCHANGE_BACKOFF
+            .retry("BTC price from Kraken", || self.fetch_btc_price_once())
+            .await?;
+        info!(
+            "✅ Successfully fetched BTC price from Kraken: ${}",
+            price_data.price
+        );
+        Ok(price_data)
     }
 
     /// 한 번만 가격을 가져오기 (재시도 없음)
@@ -161,13 +151,14 @@ impl KrakenClient {
         );
 
         // 가격 검증
-        self.validate_price(close_price)?;
+        self.validate_price(&AssetPair::btc_usd(), close_price)?;
 
         let timestamp = chrono::Utc::now().timestamp() as u64;
 
         Ok(PriceData {
             pair: AssetPair::btc_usd(),
-            price: (close_price * 100.0) as u64, // Convert to cents
+            price: Price::parse_decimal(&latest_ohlc.4, Price::USD_DECIMALS, self.rounding)
+                .context("Failed to convert Kraken close price to cents")?, // cents
             timestamp: DateTime::from_timestamp(timestamp as i64, 0)
                 .unwrap_or_else(chrono::Utc::now),
             volume: None,
@@ -188,21 +179,31 @@ impl KrakenClient {
         }
     }
 
-    /// 가격이 합리적인지 검증합니다
-    fn validate_price(&self, price: f64) -> Result<()> {
-        if price <= 0.0 {
-            anyhow::bail!("Invalid price: must be positive, got {}", price);
-        }
+    /// 가격이 pair의 허용 범위 안에 있는지 검증합니다
+    fn validate_price(&self, pair: &AssetPair, price: f64) -> Result<()> {
+        self.bands.check("kraken", pair, price)
+    }
+}
 
-        if price < 1000.0 {
-            warn!("Unusually low BTC price from Kraken: ${:.2}", price);
-        }
+impl Default for KrakenClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-        if price > 1_000_000.0 {
-            warn!("Unusually high BTC price from Kraken: ${:.2}", price);
-        }
+#[async_trait]
+impl PriceProvider for KrakenClient {
+    async fn fetch_btc_price(&self) -> Result<PriceData> {
+        KrakenClient::fetch_btc_price(self).await
+    }
+    
+    fn name(&self) -> &str {
+        "kraken"
+    }
 
-        Ok(())
+    // XBTUSD 1분봉만 조회
+    fn supported_pairs(&self) -> Vec<AssetPair> {
+        vec![AssetPair::btc_usd()]
     }
 }
 
@@ -212,7 +213,7 @@ mod tests {
 
     #[test]
     fn test_client_creation() {
-        let client = KrakenClient::new();
+        let _client = KrakenClient::new();
         // 클라이언트가 성공적으로 생성되는지 확인
     }
 
@@ -221,11 +222,11 @@ mod tests {
         let client = KrakenClient::new();
 
         // 정상적인 가격
-        assert!(client.validate_price(50000.0).is_ok());
+        assert!(client.validate_price(&AssetPair::btc_usd(), 50000.0).is_ok());
 
         // 비정상적인 가격들
-        assert!(client.validate_price(0.0).is_err());
-        assert!(client.validate_price(-100.0).is_err());
+        assert!(client.validate_price(&AssetPair::btc_usd(), 0.0).is_err());
+        assert!(client.validate_price(&AssetPair::btc_usd(), -100.0).is_err());
     }
 
     #[tokio::test]
@@ -236,9 +237,12 @@ mod tests {
 
         match result {
             Ok(price_data) => {
-                assert!(price_data.price > 0.0);
+                assert!(!price_data.price.is_zero());
                 assert_eq!(price_data.source, "kraken");
-                println!("Real BTC price from Kraken: ${:.2}", price_data.price);
+                println!(
+                    "Real BTC price from Kraken: ${:.2}",
+                    price_data.price.to_f64_dollars()
+                );
             }
             Err(e) => {
                 println!("Kraken API call failed (this might be expected): {}", e);
@@ -246,14 +250,3 @@ mod tests {
         }
     }
 }
-
-#[async_trait]
-impl PriceProvider for KrakenClient {
-    async fn fetch_btc_price(&self) -> Result<PriceData> {
-        self.fetch_btc_price_with_retry(MAX_RETRIES).await
-    }
-    
-    fn name(&self) -> &str {
-        "kraken"
-    }
-}
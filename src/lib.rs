@@ -1,8 +1,29 @@
+pub mod backoff;
 pub mod binance;
+pub mod binance_futures;
+pub mod cli;
+pub mod clock_drift;
 pub mod coinbase;
+pub mod divergence;
 pub mod grpc_client;
+pub mod heartbeat;
+pub mod identity;
 pub mod kraken;
+pub mod logging;
+pub mod metrics;
+pub mod network_price;
+pub mod offline_queue;
+pub mod pipeline;
+pub mod price_stream;
+pub mod round;
 pub mod safe_price;
+pub mod scheduler;
+pub mod shutdown;
+pub mod simulation;
+pub mod status;
+pub mod supervisor;
+pub mod telemetry;
+pub mod price_band;
 pub mod price_provider;
 pub mod consensus;
 
@@ -0,0 +1,443 @@
+//! 로깅 초기화: 콘솔 출력과 선택적인 파일 출력 (크기/일 단위 교체, 보관 개수 제한)
+//!
+//! 파일 출력은 `<dir>/oracle-node.log`에 쓰다가 크기나 날짜 기준을 넘으면
+//! `oracle-node.<시각>.log`로 이름을 바꾸고 새 파일을 연다. 교체할 때마다 현재 파일을 포함해
+//! `retention`개만 남기고 오래된 파일을 지운다. 쓰기는 `tracing-appender`의 별도 스레드에서 하므로
+//! 디스크가 느려도 라운드를 막지 않는다. `otlp_endpoint`가 설정되면 span도 OTLP로 내보낸다.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_sdk::trace::TracerProvider;
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer, Registry};
+
+use crate::telemetry;
+
+/// 현재 로그 파일 이름
+pub const LOG_FILE_NAME: &str = "oracle-node.log";
+/// 기본 보관 파일 수 (현재 파일 포함)
+pub const DEFAULT_LOG_RETENTION: usize = 7;
+/// 기본 로그 수준
+pub const DEFAULT_LOG_LEVEL: &str = "info";
+
+const LOG_FILE_PREFIX: &str = "oracle-node.";
+const LOG_FILE_SUFFIX: &str = ".log";
+
+/// 로그 형식
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// 사람이 읽는 한 줄 형식
+    #[default]
+    Pretty,
+    /// 줄마다 JSON 객체 하나 (로그 수집기용)
+    Json,
+}
+
+impl fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pretty => write!(f, "pretty"),
+            Self::Json => write!(f, "json"),
+        }
+    }
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "pretty" => Ok(Self::Pretty),
+            "json" => Ok(Self::Json),
+            _ => Err(format!(
+                "unknown log format '{}' (expected pretty or json)",
+                value
+            )),
+        }
+    }
+}
+
+/// 로그 파일 교체 기준
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogRotation {
+    /// UTC 날짜가 바뀌면 교체
+    #[default]
+    Daily,
+    /// 파일이 이 크기(바이트)를 넘으면 교체
+    Size(u64),
+}
+
+impl fmt::Display for LogRotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Daily => write!(f, "daily"),
+            Self::Size(bytes) => match [(1 << 20, "MB"), (1 << 10, "KB")]
+                .into_iter()
+                .find(|(unit, _)| bytes % unit == 0)
+            {
+                Some((unit, suffix)) => write!(f, "{}{}", bytes / unit, suffix),
+                None => write!(f, "{}B", bytes),
+            },
+        }
+    }
+}
+
+impl FromStr for LogRotation {
+    type Err = String;
+
+    /// "daily" 또는 크기 ("10MB", "512KB", "4096B")
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let lower = value.trim().to_ascii_lowercase();
+        if lower == "daily" {
+            return Ok(Self::Daily);
+        }
+        let (number, unit) = lower
+            .find(|c: char| !c.is_ascii_digit())
+            .map_or((lower.as_str(), ""), |i| lower.split_at(i));
+        let unit = match unit.trim() {
+            "" | "b" => 1,
+            "kb" => 1 << 10,
+            "mb" => 1 << 20,
+            "gb" => 1 << 30,
+            _ => return Err(invalid_rotation(value)),
+        };
+        match number.parse::<u64>() {
+            Ok(number) if number > 0 => Ok(Self::Size(number * unit)),
+            _ => Err(invalid_rotation(value)),
+        }
+    }
+}
+
+fn invalid_rotation(value: &str) -> String {
+    format!(
+        "invalid log rotation '{}' (expected daily or a size such as 10MB)",
+        value
+    )
+}
+
+/// 로깅 설정
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogConfig {
+    /// 로그 파일 디렉터리 (None이면 콘솔에만 출력)
+    pub dir: Option<PathBuf>,
+    pub rotation: LogRotation,
+    /// 남길 파일 수 (현재 파일 포함, 최소 1)
+    pub retention: usize,
+    pub format: LogFormat,
+    /// `EnvFilter` 지시어 (예: "info", "oracle_node=debug,info") - RUST_LOG가 있으면 RUST_LOG 우선
+    pub level: String,
+    /// span을 내보낼 OTLP/gRPC 수집기 주소 (None이면 내보내지 않음)
+    pub otlp_endpoint: Option<String>,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self {
+            dir: None,
+            rotation: LogRotation::default(),
+            retention: DEFAULT_LOG_RETENTION,
+            format: LogFormat::default(),
+            level: DEFAULT_LOG_LEVEL.to_string(),
+            otlp_endpoint: None,
+        }
+    }
+}
+
+/// 설치한 로거의 정리 담당 (드롭하면 남은 파일 로그를 기록하고 남은 span을 내보냄)
+#[derive(Debug)]
+pub struct LogGuard {
+    _file: Option<WorkerGuard>,
+    tracer_provider: Option<TracerProvider>,
+}
+
+impl Drop for LogGuard {
+    fn drop(&mut self) {
+        if let Some(provider) = self.tracer_provider.take() {
+            if let Err(e) = provider.shutdown() {
+                eprintln!("Failed to flush spans: {}", e);
+            }
+        }
+    }
+}
+
+/// 전역 로거 설치 (반환된 guard를 드롭하면 남은 파일 로그를 기록하고 파일 출력이 멈춤)
+///
+/// 로그 디렉터리를 만들 수 없거나 쓸 수 없으면 실패한다.
+pub fn init(config: &LogConfig) -> Result<LogGuard> {
+    let filter = match std::env::var("RUST_LOG") {
+        Ok(directives) if !directives.is_empty() => EnvFilter::try_new(&directives)
+            .with_context(|| format!("Invalid RUST_LOG {:?}", directives))?,
+        _ => EnvFilter::try_new(&config.level)
+            .with_context(|| format!("Invalid log_level {:?}", config.level))?,
+    };
+
+    let mut layers = vec![fmt_layer(config.format, io::stdout, true)];
+    let guard = match &config.dir {
+        Some(dir) => {
+            let file = RollingFile::open(dir, config.rotation, config.retention)?;
+            let (writer, guard) = tracing_appender::non_blocking(file);
+            layers.push(fmt_layer(config.format, writer, false));
+            Some(guard)
+        }
+        None => None,
+    };
+
+    let tracer_provider = match &config.otlp_endpoint {
+        Some(endpoint) => {
+            let provider = telemetry::otlp_tracer_provider(endpoint)?;
+            let tracer = provider.tracer(telemetry::SERVICE_NAME);
+            layers.push(tracing_opentelemetry::layer().with_tracer(tracer).boxed());
+            Some(provider)
+        }
+        None => None,
+    };
+
+    tracing_subscriber::registry()
+        .with(layers.with_filter(filter))
+        .try_init()
+        .context("Failed to install the logger")?;
+    Ok(LogGuard {
+        _file: guard,
+        tracer_provider,
+    })
+}
+
+fn fmt_layer<W>(format: LogFormat, writer: W, ansi: bool) -> Box<dyn Layer<Registry> + Send + Sync>
+where
+    W: for<'w> tracing_subscriber::fmt::MakeWriter<'w> + Send + Sync + 'static,
+{
+    let layer = tracing_subscriber::fmt::layer()
+        .with_writer(writer)
+        .with_ansi(ansi);
+    match format {
+        LogFormat::Pretty => layer.boxed(),
+        LogFormat::Json => layer.json().boxed(),
+    }
+}
+
+/// 크기/날짜 기준으로 교체하고 보관 개수만큼만 남기는 로그 파일
+#[derive(Debug)]
+pub struct RollingFile {
+    dir: PathBuf,
+    rotation: LogRotation,
+    retention: usize,
+    file: File,
+    size: u64,
+    // 현재 파일에 쓰기 시작한 날짜 (UTC)
+    day: NaiveDate,
+}
+
+impl RollingFile {
+    /// `dir/oracle-node.log`를 이어 쓰기로 열기 (디렉터리가 없으면 생성)
+    pub fn open(dir: &Path, rotation: LogRotation, retention: usize) -> Result<Self> {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("Cannot create log directory {}", dir.display()))?;
+        let path = dir.join(LOG_FILE_NAME);
+        let file = open_append(&path)
+            .with_context(|| format!("Log directory {} is not writable", dir.display()))?;
+        let metadata = file.metadata()?;
+        // 이전 실행의 파일이면 마지막으로 쓴 날짜부터 이어서 셈
+        let day = match metadata.modified() {
+            Ok(modified) if metadata.len() > 0 => DateTime::<Utc>::from(modified).date_naive(),
+            _ => Utc::now().date_naive(),
+        };
+
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            rotation,
+            retention: retention.max(1),
+            file,
+            size: metadata.len(),
+            day,
+        })
+    }
+
+    fn write_at(&mut self, buf: &[u8], now: DateTime<Utc>) -> io::Result<usize> {
+        let due = match self.rotation {
+            LogRotation::Daily => now.date_naive() != self.day,
+            LogRotation::Size(limit) => self.size > 0 && self.size + buf.len() as u64 > limit,
+        };
+        if due {
+            self.rotate(now)?;
+        }
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    // 현재 파일 이름을 바꾸고 새 파일을 연 뒤 오래된 파일 정리
+    fn rotate(&mut self, now: DateTime<Utc>) -> io::Result<()> {
+        self.file.flush()?;
+        let stamp = match self.rotation {
+            LogRotation::Daily => self.day.format("%Y-%m-%d").to_string(),
+            LogRotation::Size(_) => now.format("%Y-%m-%dT%H-%M-%S%.3f").to_string(),
+        };
+        let mut rotated = self
+            .dir
+            .join(format!("{}{}{}", LOG_FILE_PREFIX, stamp, LOG_FILE_SUFFIX));
+        let mut n = 1;
+        while rotated.exists() {
+            rotated = self.dir.join(format!(
+                "{}{}-{}{}",
+                LOG_FILE_PREFIX, stamp, n, LOG_FILE_SUFFIX
+            ));
+            n += 1;
+        }
+
+        let current = self.dir.join(LOG_FILE_NAME);
+        fs::rename(&current, &rotated)?;
+        self.file = open_append(&current)?;
+        self.size = 0;
+        self.day = now.date_naive();
+        prune_logs(&self.dir, self.retention - 1)?;
+        Ok(())
+    }
+}
+
+impl Write for RollingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.write_at(buf, Utc::now())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+fn open_append(path: &Path) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+/// 교체된 로그 파일 중 최근 `keep`개만 남기고 삭제 (삭제한 수 반환, 현재 파일은 그대로)
+pub fn prune_logs(dir: &Path, keep: usize) -> io::Result<usize> {
+    let mut rotated = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name != LOG_FILE_NAME
+            && name.starts_with(LOG_FILE_PREFIX)
+            && name.ends_with(LOG_FILE_SUFFIX)
+        {
+            rotated.push((entry.metadata()?.modified()?, name, entry.path()));
+        }
+    }
+    // 오래된 순
+    rotated.sort();
+
+    let excess = rotated.len().saturating_sub(keep);
+    for (_, _, path) in rotated.drain(..excess) {
+        fs::remove_file(path)?;
+    }
+    Ok(excess)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("node-logs-{}", uuid::Uuid::new_v4()))
+    }
+
+    fn log_files(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_parse_rotation_and_format() {
+        assert_eq!("daily".parse(), Ok(LogRotation::Daily));
+        assert_eq!("10MB".parse(), Ok(LogRotation::Size(10 << 20)));
+        assert_eq!("512 kb".parse(), Ok(LogRotation::Size(512 << 10)));
+        assert_eq!("4096".parse(), Ok(LogRotation::Size(4096)));
+        assert!("0MB".parse::<LogRotation>().is_err());
+        assert!("hourly".parse::<LogRotation>().is_err());
+        for rotation in [
+            LogRotation::Daily,
+            LogRotation::Size(10 << 20),
+            LogRotation::Size(1000),
+        ] {
+            assert_eq!(rotation.to_string().parse(), Ok(rotation));
+        }
+
+        assert_eq!("JSON".parse(), Ok(LogFormat::Json));
+        assert!("xml".parse::<LogFormat>().is_err());
+    }
+
+    #[test]
+    fn test_size_rotation_prunes_to_retention() {
+        let dir = scratch_dir();
+        let mut file = RollingFile::open(&dir, LogRotation::Size(1024), 3).unwrap();
+        let line = [b'x'; 99];
+        for _ in 0..200 {
+            file.write_all(&line).unwrap();
+            file.write_all(b"\n").unwrap();
+        }
+        file.flush().unwrap();
+
+        // 20KB를 1KB 단위로 교체했지만 현재 파일을 포함해 3개만 남음
+        let names = log_files(&dir);
+        assert_eq!(names.len(), 3, "{:?}", names);
+        assert!(names.contains(&LOG_FILE_NAME.to_string()));
+        for name in &names {
+            assert!(fs::metadata(dir.join(name)).unwrap().len() <= 1024);
+        }
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_daily_rotation_names_files_by_day() {
+        let dir = scratch_dir();
+        let mut file = RollingFile::open(&dir, LogRotation::Daily, 2).unwrap();
+        let today = Utc::now();
+        file.write_at(b"today\n", today).unwrap();
+        file.write_at(b"tomorrow\n", today + chrono::Duration::days(1))
+            .unwrap();
+        file.write_at(b"later\n", today + chrono::Duration::days(2))
+            .unwrap();
+
+        // 교체된 파일은 그 파일에 쓴 날짜로 이름이 붙고, 오늘 파일은 보관 개수를 넘어 삭제됨
+        let previous = format!(
+            "oracle-node.{}.log",
+            (today + chrono::Duration::days(1)).format("%Y-%m-%d")
+        );
+        assert_eq!(
+            log_files(&dir),
+            vec![previous.clone(), LOG_FILE_NAME.to_string()]
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join(previous)).unwrap(),
+            "tomorrow\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join(LOG_FILE_NAME)).unwrap(),
+            "later\n"
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_unwritable_log_dir_fails() {
+        // 일반 파일 아래에는 디렉터리를 만들 수 없음
+        let blocker = scratch_dir();
+        fs::write(&blocker, "").unwrap();
+        let error = RollingFile::open(&blocker.join("logs"), LogRotation::Daily, 1).unwrap_err();
+        assert!(error.to_string().contains("Cannot create log directory"));
+
+        let error = RollingFile::open(&blocker, LogRotation::Daily, 1).unwrap_err();
+        assert!(format!("{:#}", error).contains("log directory"));
+        fs::remove_file(&blocker).unwrap();
+    }
+}
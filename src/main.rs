@@ -1,83 +1,236 @@
-use anyhow::Result;
-use chrono::{Timelike, Utc};
+use anyhow::{Context, Result};
 use clap::Parser;
-use std::time::Duration;
-use tokio::time::interval;
-use tracing::{error, info};
-
-mod binance;
-mod coinbase;
-mod grpc_client;
-mod kraken;
-mod safe_price;
-mod price_provider;
-
-use binance::BinanceClient;
-use coinbase::CoinbaseClient;
-use grpc_client::GrpcAggregatorClient;
-use kraken::KrakenClient;
-use price_provider::PriceProvider;
-
-// PriceData는 common::types에서 가져옴
-use oracle_vm_common::types::PriceData;
-
-/// 거래소 클라이언트 생성 헬퍼
-fn create_exchange_provider(exchange: &str) -> Result<Box<dyn PriceProvider>> {
-    match exchange.to_lowercase().as_str() {
-        "binance" => Ok(Box::new(BinanceClient::new())),
-        "coinbase" => Ok(Box::new(CoinbaseClient::new())),
-        "kraken" => Ok(Box::new(KrakenClient::new())),
-        _ => anyhow::bail!(
-            "Unsupported exchange: {}. Supported: binance, coinbase, kraken",
-            exchange
-        ),
+use std::path::Path;
+use std::sync::Arc;
+use tracing::{error, info, warn};
+
+use oracle_node::binance::BinanceClient;
+use oracle_node::cli::{self, Cli, Command, FileConfig, Settings};
+use oracle_node::clock_drift::DEFAULT_DRIFT_PROBE_INTERVAL;
+use oracle_node::grpc_client::AggregatorsUnavailable;
+use oracle_node::heartbeat::Heartbeat;
+use oracle_node::identity::NodeIdentity;
+use oracle_node::logging;
+use oracle_node::metrics::NodeMetrics;
+use oracle_node::offline_queue::OfflineQueue;
+use oracle_node::pipeline::{self, PairPipeline};
+use oracle_node::scheduler;
+use oracle_node::shutdown::{Shutdown, DEFAULT_SHUTDOWN_TIMEOUT};
+use oracle_node::status::{self, NodeStatus};
+use oracle_node::supervisor::{GaveUp, Supervisor, GAVE_UP_EXIT_CODE};
+use oracle_vm_common::jsonl::JsonlWriter;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    // Parse CLI arguments (CLI > ORACLE_NODE_* env > config file > defaults)
+    let cli = Cli::parse();
+
+    let file = FileConfig::load(cli.config.as_deref())?;
+    let settings = Settings::resolve(&cli.args, file)?;
+    if cli.dump_config {
+        print!("{}", settings.dump()?);
+        return Ok(());
+    }
+
+    // Initialize logging (console, plus rotating files if log_dir is set, plus OTLP spans if
+    // otlp_endpoint is set); the guard flushes buffered file logs and spans when main returns
+    let _log_guard = logging::init(&settings.log_config())?;
+    for (old, new) in cli::renamed_env_vars() {
+        warn!("⚠️ {} is no longer read, use {} instead", old, new);
+    }
+
+    let result = match cli.command.unwrap_or(Command::Run) {
+        Command::Run => run(&settings).await,
+        Command::FetchOnce => fetch_once(&settings).await,
+        Command::Check => check(&settings).await,
+        Command::Keygen { out } => keygen(out.as_deref().unwrap_or(settings.key_path())),
+        Command::ShowId => show_id(&settings),
+    };
+    if let Err(e) = &result {
+        if let Some(gave_up) = e.downcast_ref::<GaveUp>() {
+            // Hand over to the external supervisor (systemd, Kubernetes, ...)
+            error!("❌ {}", gave_up);
+            drop(_log_guard);
+            std::process::exit(GAVE_UP_EXIT_CODE);
+        }
     }
+    result
 }
 
-/// Oracle Node CLI 인수
-#[derive(Parser)]
-#[command(name = "oracle-node")]
-#[command(about = "BTCFi Oracle Node for price data collection")]
-struct Args {
-    /// 설정 파일 경로
-    #[arg(short, long, default_value = "config/oracle-node.toml")]
-    config: String,
-
-    /// Node ID (설정 파일보다 우선)
-    #[arg(long)]
-    node_id: Option<String>,
-
-    /// Aggregator URL (설정 파일보다 우선)
-    #[arg(long, default_value = "http://localhost:50051")]
-    aggregator_url: String,
-
-    /// 가격 수집 간격 (초)
-    #[arg(long, default_value = "60")]
-    interval: u64,
-
-    /// 거래소 선택 (binance, coinbase, kraken)
-    #[arg(long, default_value = "binance")]
-    exchange: String,
+/// 새 신원 키 생성
+fn keygen(path: &Path) -> Result<()> {
+    let identity = NodeIdentity::generate();
+    identity.save(path)?;
+
+    println!("key:        {}", path.display());
+    println!("public key: {}", identity.public_key_hex());
+    println!("node id:    {}", identity.node_id());
+    Ok(())
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
-    // Parse CLI arguments
-    let args = Args::parse();
+/// 신원 키의 공개키와 node_id 출력
+fn show_id(settings: &Settings) -> Result<()> {
+    let identity = NodeIdentity::load(settings.key_path())?;
+
+    println!("public key: {}", identity.public_key_hex());
+    println!("node id:    {}", identity.node_id());
+    if let Some(node_id) = &settings.node_id {
+        println!("override:   {} (from --node-id)", node_id);
+    }
+    Ok(())
+}
+
+/// 한 번 수집해 출력 (제출하지 않음)
+async fn fetch_once(settings: &Settings) -> Result<()> {
+    let provider = settings.price_provider()?;
+    let aggregate = cli::fetch_once(&provider).await?;
+
+    println!("price:   ${}", aggregate.price.price);
+    println!("time:    {}", aggregate.price.timestamp);
+    println!("sources: {}", aggregate.sources.join(", "));
+    if !aggregate.failed.is_empty() {
+        println!("failed:  {}", aggregate.failed.join(", "));
+    }
+    println!("spread:  {:.3}%", aggregate.spread_pct);
+    if aggregate.disputed {
+        println!("disputed: spread exceeds the configured limit");
+    }
+    Ok(())
+}
 
-    // Initialize logging
-    tracing_subscriber::fmt::init();
+/// 설정과 연결 확인 (하나라도 실패하면 에러로 종료)
+async fn check(settings: &Settings) -> Result<()> {
+    println!("aggregators: {}", settings.aggregator_urls.join(", "));
+    println!(
+        "interval:    {}",
+        humantime::format_duration(settings.interval)
+    );
+    match settings.resolve_node_id()? {
+        Some(node_id) => println!("node id:     {}", node_id),
+        None => println!(
+            "node id:     random (no key at {})",
+            settings.key_path().display()
+        ),
+    }
 
-    info!("Starting Oracle Node with config: {}", args.config);
-    info!("Aggregator URL: {}", args.aggregator_url);
-    info!("Exchange: {}", args.exchange);
-    info!("Fetch interval: {}s", args.interval);
+    let provider = settings.price_provider()?;
+    let drift = settings.drift_monitor();
+    let mut client = settings
+        .aggregator_client()?
+        .with_drift_monitor(drift.clone());
+    let report = cli::check(&provider, &mut client).await;
+    if let Err(e) = drift.probe(&BinanceClient::new()).await {
+        println!("⚠️ clock: cannot read Binance server time: {:#}", e);
+    }
+    match drift.offset_millis() {
+        Some(offset) => println!(
+            "clock:       {:+} ms vs aggregator/Binance ({:?})",
+            offset,
+            drift.level()
+        ),
+        None => println!("clock:       unknown"),
+    }
 
-    // Create exchange provider based on CLI argument
-    let exchange_provider = create_exchange_provider(&args.exchange)?;
+    for (name, result) in &report.providers {
+        match result {
+            Ok(price) => println!("✅ {}: {}", name, price),
+            Err(e) => println!("❌ {}: {}", name, e),
+        }
+    }
+    match &report.aggregator {
+        Ok(true) => println!("✅ aggregator: healthy ({})", client.current_url()),
+        Ok(false) => println!("❌ aggregator: no healthy aggregator"),
+        Err(e) => println!("❌ aggregator: {}", e),
+    }
 
-    // Create gRPC Aggregator client
-    let mut grpc_client = GrpcAggregatorClient::new(&args.aggregator_url).await?;
+    if !report.is_ok() {
+        anyhow::bail!("Check failed");
+    }
+    Ok(())
+}
+
+/// 주기적으로 수집해 제출
+async fn run(settings: &Settings) -> Result<()> {
+    info!("Aggregator URLs: {}", settings.aggregator_urls.join(", "));
+    info!("Exchanges: {}", settings.providers.join(", "));
+    match settings.identity()? {
+        Some(identity) => info!("🔑 Node identity {}", identity.public_key_hex()),
+        None => info!(
+            "💡 No identity key at {}; using a random node_id (run `oracle-node keygen`)",
+            settings.key_path().display()
+        ),
+    }
+
+    // One metrics registry for the whole node, served on /metrics by the status endpoint
+    let metrics = NodeMetrics::new();
+
+    // One provider registry per pair, each with its own exchanges
+    let providers = settings
+        .pairs
+        .iter()
+        .map(|pair| {
+            settings
+                .pair_provider(pair)
+                .map(|provider| provider.with_metrics(metrics.clone()))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    // Create gRPC Aggregator client (primary first, then fallbacks)
+    if settings.aggregator_urls.len() > 1 {
+        info!(
+            "🔀 Submitting to {} aggregators in {} mode",
+            settings.aggregator_urls.len(),
+            settings.aggregator_mode
+        );
+    }
+    // A dry run reads the offline queue but never writes it back
+    let mut queue = OfflineQueue::open(&settings.offline_queue, settings.max_queued)?;
+    if settings.dry_run {
+        warn!("🧪 DRY RUN: prices are fetched and logged every round but never submitted");
+        queue = queue.read_only();
+    }
+    if !queue.is_empty() {
+        info!(
+            "📥 {} queued price(s) from a previous run will be resent",
+            queue.len()
+        );
+    }
+    // The first SIGINT/SIGTERM stops every task after the in-flight submission; a second one exits at once
+    let shutdown = Shutdown::new();
+    shutdown.listen_for_signals();
+
+    // Submissions and heartbeats share one clock drift estimate, also fed by Binance server time
+    let drift = settings.drift_monitor();
+    let mut grpc_client = settings
+        .aggregator_client()?
+        .with_offline_queue(queue)
+        // The price stream carries one network median, so several pairs submit with unary calls
+        .with_streaming(!settings.unary && !settings.dry_run && settings.pairs.len() == 1)
+        .with_dry_run(settings.dry_run)
+        .with_drift_monitor(drift.clone())
+        .with_divergence_monitor(settings.divergence_monitor())
+        .with_metrics(metrics.clone())
+        .with_shutdown(shutdown.subscribe());
+    // Every submission sent is also appended to a local JSONL file; a torn last line from a crash is cut off
+    if let Some(config) = settings.submission_log.clone() {
+        let path = config.path.clone();
+        let log = JsonlWriter::open(config)
+            .with_context(|| format!("Failed to open submission log {}", path.display()))?;
+        if log.recovered_bytes() > 0 {
+            warn!(
+                "⚠️ Dropped torn last line ({} bytes) of {}",
+                log.recovered_bytes(),
+                path.display()
+            );
+        }
+        info!("🧾 Logging submissions to {}", path.display());
+        grpc_client = grpc_client.with_submission_log(log);
+    }
+    let drift_probe = drift.spawn_probe(
+        Arc::new(BinanceClient::new()),
+        DEFAULT_DRIFT_PROBE_INTERVAL,
+        shutdown.subscribe(),
+    );
 
     // Check if gRPC Aggregator is healthy
     match grpc_client.check_health().await {
@@ -90,57 +243,127 @@ async fn main() -> Result<()> {
         }
     }
 
-    // Calculate next minute boundary (00 seconds)
-    let now = Utc::now();
-    let seconds_to_wait = 60 - now.second();
-
-    info!(
-        "Starting synchronized price collection every {}s...",
-        args.interval
-    );
-    info!(
-        "Waiting {}s to sync with next minute boundary...",
-        seconds_to_wait
-    );
-
-    // Wait until the next minute boundary (XX:XX:00)
-    tokio::time::sleep(Duration::from_secs(seconds_to_wait as u64)).await;
-
-    // Create interval for subsequent collections
-    let mut interval = interval(Duration::from_secs(args.interval));
+    // Register and learn the rules the aggregator applies to our submissions
+    match grpc_client.register(&settings.providers).await {
+        Ok(Some(config)) => {
+            info!(
+                "📋 Aggregator rules: {}s window, quorum {}, {} ({}), allowed nodes: {}",
+                config.window_secs,
+                config.min_quorum_nodes,
+                config.strategy,
+                config.aggregation_mode,
+                if config.allowed_nodes.is_empty() {
+                    "any".to_string()
+                } else {
+                    config.allowed_nodes.join(", ")
+                }
+            );
+            if let Some(reject_secs) = config.skew_reject_secs {
+                info!(
+                    "⏱️ Submissions more than {}s off aggregator time are rejected",
+                    reject_secs
+                );
+            }
+        }
+        Ok(None) => info!("💡 Aggregator does not report its rules; using built-in defaults"),
+        Err(e) if e.is::<AggregatorsUnavailable>() => {
+            warn!("⚠️ Could not register with an aggregator yet, continuing...")
+        }
+        Err(e) => return Err(e.context("Aggregator refused this node")),
+    }
 
-    // Skip the first tick (which would fire immediately)
-    interval.tick().await;
+    // Fixed offset after the interval boundary so the previous candle is closed,
+    // plus a jitter derived from node_id so nodes spread out but keep their slot across restarts.
+    // A panic in a provider or submission restarts that pair's loop with backoff instead of silently stopping it
+    let node_id = settings.resolve_node_id()?;
+    let mut pipelines = Vec::new();
+    for (pair, provider) in settings.pairs.iter().zip(providers) {
+        let delay =
+            scheduler::node_fetch_delay(node_id.as_deref(), pair.fetch_offset, settings.max_jitter);
+        info!(
+            "Collecting {} from {} every {} at +{:.3}s after the boundary",
+            pair.pair.as_str(),
+            pair.providers.join(", "),
+            humantime::format_duration(pair.interval),
+            delay.as_secs_f64()
+        );
+        pipelines.push(
+            PairPipeline::scheduled(provider, pair.interval, delay, settings.max_restarts)
+                .with_adaptive_interval(settings.adaptive_interval(pair.interval)),
+        );
+    }
+    if settings.adaptive_after_failures > 0 {
+        info!(
+            "🐢 Stretching the fetch interval up to {} after {} rounds with every provider failing",
+            humantime::format_duration(settings.adaptive_max_interval),
+            settings.adaptive_after_failures
+        );
+    }
 
-    loop {
-        // Collect price at synchronized time
-        let collection_time = Utc::now();
+    // Heartbeats use their own client so their backoff never delays a submission.
+    // A dry run sends none so the aggregator never counts the node as active
+    let heartbeat = if settings.dry_run {
+        None
+    } else {
         info!(
-            "🕐 Synchronized collection at {}:{:02}:{:02}",
-            collection_time.hour(),
-            collection_time.minute(),
-            collection_time.second()
+            "💓 Heartbeat every {} (+ up to {} jitter)",
+            humantime::format_duration(settings.heartbeat_interval),
+            humantime::format_duration(settings.heartbeat_jitter)
         );
+        Some(Heartbeat::spawn_supervised(
+            settings.aggregator_client()?.with_drift_monitor(drift),
+            settings.heartbeat_schedule(),
+            shutdown.clone(),
+            Supervisor::new("heartbeat", settings.max_restarts),
+        ))
+    };
 
-        match exchange_provider.fetch_btc_price().await {
-            Ok(price_data) => {
-                info!(
-                    "Fetched BTC price: ${:.2} at timestamp: {}",
-                    price_data.price, price_data.timestamp
-                );
+    // Optional local /status, /healthz and /metrics endpoint, stopped together with the node
+    // Healthy while the slowest pipeline could still have succeeded recently
+    let slowest = settings.pairs.iter().map(|pair| pair.interval).max();
+    let node_status = NodeStatus::new(slowest.unwrap_or(settings.interval))
+        .with_price_max_age(settings.price_max_age)
+        .with_metrics(metrics);
+    let status_server = match settings.status_addr {
+        Some(addr) => Some(
+            status::spawn(addr, node_status.clone(), shutdown.subscribe())
+                .await?
+                .1,
+        ),
+        None => None,
+    };
 
-                // Send to gRPC aggregator
-                match grpc_client.submit_price(&price_data).await {
-                    Ok(_) => info!("✅ Successfully sent price to gRPC aggregator"),
-                    Err(e) => error!("❌ Failed to send price to gRPC aggregator: {}", e),
-                }
-            }
-            Err(e) => {
-                error!("Failed to fetch price: {}", e);
-            }
-        }
+    let result = pipeline::run_pipelines(
+        pipelines,
+        &mut grpc_client,
+        shutdown.subscribe(),
+        DEFAULT_SHUTDOWN_TIMEOUT,
+        &node_status,
+    )
+    .await;
 
-        // Wait for next interval
-        interval.tick().await;
+    if let Some(heartbeat) = heartbeat {
+        heartbeat.shutdown().await;
     }
+    let exit = match result {
+        Ok(exit) => exit,
+        Err(gave_up) => {
+            shutdown.trigger();
+            return Err(gave_up.into());
+        }
+    };
+    let _ = drift_probe.await;
+    if let Some(status_server) = status_server {
+        let _ = status_server.await;
+    }
+    info!(
+        "👋 Oracle Node stopped after {} round(s){}",
+        exit.rounds,
+        if exit.abandoned {
+            " (in-flight round abandoned)"
+        } else {
+            ""
+        }
+    );
+    Ok(())
 }
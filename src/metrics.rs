@@ -0,0 +1,205 @@
+//! 노드 운영 지표 (Prometheus 텍스트 형식, 상태 서버의 `/metrics`)
+//!
+//! 노드가 레지스트리 하나를 만들어 거래소 레지스트리(`MultiExchangePriceProvider::with_metrics`)와
+//! Aggregator 클라이언트(`MultiAggregatorClient::with_metrics`)에 넘기면, 각자 수집과 제출 결과를
+//! 기록한다. 복제본은 같은 레지스트리를 공유하며 가동 시간은 내보낼 때 계산한다.
+
+use anyhow::Result;
+use oracle_vm_common::types::AssetPair;
+use oracle_vm_common::Price;
+use prometheus::{
+    Encoder, Gauge, GaugeVec, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, Opts, Registry,
+    TextEncoder,
+};
+use std::time::{Duration, Instant};
+use tonic::Code;
+
+/// 지표 이름 앞에 붙는 이름
+const NAMESPACE: &str = "oracle_node";
+
+/// 노드가 소유하는 지표 레지스트리 (복제본은 같은 값 공유)
+#[derive(Debug, Clone)]
+pub struct NodeMetrics {
+    registry: Registry,
+    started: Instant,
+    provider_fetches: IntCounterVec,
+    provider_fetch_duration: HistogramVec,
+    submissions: IntCounterVec,
+    offline_queue_depth: IntGauge,
+    last_submitted_price: GaugeVec,
+    divergence_bps: GaugeVec,
+    clock_drift: Gauge,
+    uptime: Gauge,
+}
+
+impl Default for NodeMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NodeMetrics {
+    pub fn new() -> Self {
+        let registry = Registry::new();
+        let metrics = Self {
+            started: Instant::now(),
+            provider_fetches: IntCounterVec::new(
+                opts(
+                    "provider_fetches_total",
+                    "Price fetches per provider by result",
+                ),
+                &["pair", "provider", "result"],
+            )
+            .expect("valid metric"),
+            provider_fetch_duration: HistogramVec::new(
+                HistogramOpts::new(
+                    "provider_fetch_duration_seconds",
+                    "Duration of price fetches",
+                )
+                .namespace(NAMESPACE),
+                &["pair", "provider"],
+            )
+            .expect("valid metric"),
+            submissions: IntCounterVec::new(
+                opts(
+                    "submissions_total",
+                    "Price submissions to aggregators by gRPC status code",
+                ),
+                &["code"],
+            )
+            .expect("valid metric"),
+            offline_queue_depth: IntGauge::with_opts(opts(
+                "offline_queue_depth",
+                "Submissions waiting in the offline queue",
+            ))
+            .expect("valid metric"),
+            last_submitted_price: GaugeVec::new(
+                opts("last_submitted_price", "Last submitted local median (USD)"),
+                &["pair"],
+            )
+            .expect("valid metric"),
+            divergence_bps: GaugeVec::new(
+                opts(
+                    "divergence_bps",
+                    "Divergence of the local median from the network median (bps)",
+                ),
+                &["pair"],
+            )
+            .expect("valid metric"),
+            clock_drift: Gauge::with_opts(opts(
+                "clock_drift_seconds",
+                "Estimated offset of the local clock from the aggregator/Binance clock",
+            ))
+            .expect("valid metric"),
+            uptime: Gauge::with_opts(opts("uptime_seconds", "Seconds since the node started"))
+                .expect("valid metric"),
+            registry,
+        };
+
+        let collectors: [Box<dyn prometheus::core::Collector>; 8] = [
+            Box::new(metrics.provider_fetches.clone()),
+            Box::new(metrics.provider_fetch_duration.clone()),
+            Box::new(metrics.submissions.clone()),
+            Box::new(metrics.offline_queue_depth.clone()),
+            Box::new(metrics.last_submitted_price.clone()),
+            Box::new(metrics.divergence_bps.clone()),
+            Box::new(metrics.clock_drift.clone()),
+            Box::new(metrics.uptime.clone()),
+        ];
+        for collector in collectors {
+            metrics
+                .registry
+                .register(collector)
+                .expect("metric names are unique");
+        }
+        metrics
+    }
+
+    /// 거래소 한 곳의 수집 결과와 걸린 시간
+    pub fn record_fetch(&self, pair: &AssetPair, provider: &str, latency: Duration, ok: bool) {
+        let result = if ok { "success" } else { "failure" };
+        self.provider_fetches
+            .with_label_values(&[pair.as_str(), provider, result])
+            .inc();
+        self.provider_fetch_duration
+            .with_label_values(&[pair.as_str(), provider])
+            .observe(latency.as_secs_f64());
+    }
+
+    /// Aggregator 한 곳에 보낸 제출의 gRPC 상태 코드 (재시도 후 최종 결과)
+    pub fn record_submission(&self, code: Code) {
+        self.submissions
+            .with_label_values(&[&format!("{:?}", code)])
+            .inc();
+    }
+
+    pub fn set_offline_queue_depth(&self, depth: usize) {
+        self.offline_queue_depth.set(depth as i64);
+    }
+
+    /// Aggregator가 받아들인 로컬 중간값
+    pub fn set_last_submitted_price(&self, pair: &AssetPair, price: Price) {
+        self.last_submitted_price
+            .with_label_values(&[pair.as_str()])
+            .set(price.to_f64_dollars());
+    }
+
+    pub fn set_divergence_bps(&self, pair: &AssetPair, bps: f64) {
+        self.divergence_bps
+            .with_label_values(&[pair.as_str()])
+            .set(bps);
+    }
+
+    /// 로컬 시계 오차 추정값 (원격 - 로컬, 로컬이 느리면 양수)
+    pub fn set_clock_drift_millis(&self, offset_millis: i64) {
+        self.clock_drift.set(offset_millis as f64 / 1_000.0);
+    }
+
+    /// Prometheus 텍스트 형식으로 모든 지표 출력
+    pub fn encode(&self) -> Result<String> {
+        self.uptime.set(self.started.elapsed().as_secs_f64());
+        let mut buffer = Vec::new();
+        TextEncoder::new().encode(&self.registry.gather(), &mut buffer)?;
+        Ok(String::from_utf8(buffer)?)
+    }
+}
+
+fn opts(name: &str, help: &str) -> Opts {
+    Opts::new(name, help).namespace(NAMESPACE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encodes_recorded_metrics_with_labels() {
+        let metrics = NodeMetrics::new();
+        let pair = AssetPair::btc_usd();
+        metrics.record_fetch(&pair, "binance", Duration::from_millis(20), true);
+        metrics.record_fetch(&pair, "kraken", Duration::from_millis(40), false);
+        metrics.record_submission(Code::Ok);
+        metrics.record_submission(Code::Unavailable);
+        metrics.set_offline_queue_depth(3);
+        metrics.set_last_submitted_price(&pair, Price::from_cents(7_000_050));
+        metrics.set_clock_drift_millis(-1_500);
+
+        // 복제본도 같은 레지스트리에 기록
+        metrics.clone().record_submission(Code::Ok);
+
+        let text = metrics.encode().unwrap();
+        for line in [
+            r#"oracle_node_provider_fetches_total{pair="BTC/USD",provider="binance",result="success"} 1"#,
+            r#"oracle_node_provider_fetches_total{pair="BTC/USD",provider="kraken",result="failure"} 1"#,
+            r#"oracle_node_provider_fetch_duration_seconds_count{pair="BTC/USD",provider="binance"} 1"#,
+            r#"oracle_node_submissions_total{code="Ok"} 2"#,
+            r#"oracle_node_submissions_total{code="Unavailable"} 1"#,
+            "oracle_node_offline_queue_depth 3",
+            r#"oracle_node_last_submitted_price{pair="BTC/USD"} 70000.5"#,
+            "oracle_node_clock_drift_seconds -1.5",
+        ] {
+            assert!(text.contains(line), "missing {:?} in\n{}", line, text);
+        }
+        assert!(text.contains("# TYPE oracle_node_uptime_seconds gauge"));
+    }
+}
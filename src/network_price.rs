@@ -0,0 +1,91 @@
+//! 마지막으로 받은 네트워크 집계 가격
+//!
+//! 제출 응답(`PriceResponse`), stream_prices 업데이트, GetAggregatedPrice 조회에서 받은 집계
+//! 가격을 받은 시각과 함께 기억한다. 상태 서버가 같은 캐시를 읽어 `/price`로 내보내므로, 노드와
+//! 같은 곳에서 도는 애플리케이션은 Aggregator용 gRPC 클라이언트 없이 네트워크 가격을 읽을 수 있다.
+
+use chrono::{DateTime, Utc};
+use oracle_vm_common::Price;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// 기본 최대 허용 나이 (이보다 오래되면 `/price`가 stale로 503 응답)
+pub const DEFAULT_PRICE_MAX_AGE: Duration = Duration::from_secs(120);
+
+/// 네트워크 집계 가격 하나
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NetworkAggregate {
+    pub price: Price,
+    /// Aggregator가 붙인 시각 (Unix 초)
+    pub timestamp: u64,
+    /// 노드가 받은 시각
+    pub received_at: DateTime<Utc>,
+}
+
+impl NetworkAggregate {
+    /// 받은 뒤 지난 시간 (노드 시계 기준이라 Aggregator와의 시계 오차에 영향받지 않음)
+    pub fn age(&self, now: DateTime<Utc>) -> Duration {
+        (now - self.received_at).to_std().unwrap_or_default()
+    }
+}
+
+/// 클라이언트와 상태 서버가 공유하는 최신 집계 가격 (복제본은 같은 값 공유)
+#[derive(Debug, Clone, Default)]
+pub struct AggregateCache {
+    latest: Arc<Mutex<Option<NetworkAggregate>>>,
+}
+
+impl AggregateCache {
+    /// 방금 받은 집계 가격 기록
+    pub fn record(&self, price: Price, timestamp: u64) {
+        self.record_at(price, timestamp, Utc::now());
+    }
+
+    /// `received_at`에 받은 집계 가격 기록
+    pub fn record_at(&self, price: Price, timestamp: u64, received_at: DateTime<Utc>) {
+        *self.lock() = Some(NetworkAggregate {
+            price,
+            timestamp,
+            received_at,
+        });
+    }
+
+    /// 마지막으로 받은 집계 가격 (한 번도 받지 못했으면 None)
+    pub fn latest(&self) -> Option<NetworkAggregate> {
+        *self.lock()
+    }
+
+    // 기록 도중 패닉이 나도 조회는 계속 동작
+    fn lock(&self) -> std::sync::MutexGuard<'_, Option<NetworkAggregate>> {
+        self.latest.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clones_share_the_latest_aggregate() {
+        let cache = AggregateCache::default();
+        let reader = cache.clone();
+        assert_eq!(reader.latest(), None);
+
+        let received_at = Utc::now() - chrono::Duration::seconds(30);
+        cache.record_at(Price::from_cents(7_000_000), 1_700_000_000, received_at);
+        cache.record(Price::from_cents(7_000_100), 1_700_000_060);
+
+        let latest = reader.latest().unwrap();
+        assert_eq!(latest.price, Price::from_cents(7_000_100));
+        assert_eq!(latest.timestamp, 1_700_000_060);
+        assert!(latest.age(Utc::now()) < Duration::from_secs(5));
+        assert_eq!(
+            NetworkAggregate {
+                received_at,
+                ..latest
+            }
+            .age(received_at + chrono::Duration::seconds(30)),
+            Duration::from_secs(30)
+        );
+    }
+}
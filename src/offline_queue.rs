@@ -0,0 +1,279 @@
+//! Aggregator에 연결할 수 없을 때 제출을 보관하는 디스크 큐
+//!
+//! 큐는 한 줄에 제출 하나씩 JSON으로 기록되며(JSONL), 변경할 때마다 임시 파일에 쓴 뒤
+//! 교체하므로 노드가 재시작되어도 보관된 제출이 남는다. 가득 차면 가장 오래된 제출부터 버린다.
+
+use anyhow::{Context, Result};
+use oracle_vm_common::types::PriceData;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+/// 기본 최대 보관 제출 수 (1분 라운드 기준 하루치)
+pub const DEFAULT_MAX_QUEUED_SUBMISSIONS: usize = 1_440;
+
+/// 큐에 보관된 제출 (원래 관측 시각과 node_id 유지)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueuedSubmission {
+    pub node_id: String,
+    pub price_data: PriceData,
+}
+
+/// 디스크 기반 오프라인 큐
+#[derive(Debug)]
+pub struct OfflineQueue {
+    path: PathBuf,
+    max_entries: usize,
+    entries: VecDeque<QueuedSubmission>,
+    evicted: u64,
+    read_only: bool,
+}
+
+impl OfflineQueue {
+    /// 큐 파일을 열어 보관된 제출을 불러옴 (파일이 없으면 빈 큐)
+    ///
+    /// 읽을 수 없는 줄은 건너뛰고, 최대 크기를 넘으면 가장 오래된 제출부터 버린다.
+    pub fn open(path: impl Into<PathBuf>, max_entries: usize) -> Result<Self> {
+        let path = path.into();
+        let mut queue = Self {
+            path,
+            max_entries: max_entries.max(1),
+            entries: VecDeque::new(),
+            evicted: 0,
+            read_only: false,
+        };
+
+        match File::open(&queue.path) {
+            Ok(file) => {
+                for line in BufReader::new(file).lines() {
+                    let line = line.with_context(|| {
+                        format!("Failed to read offline queue {}", queue.path.display())
+                    })?;
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    match serde_json::from_str(&line) {
+                        Ok(submission) => queue.entries.push_back(submission),
+                        Err(e) => warn!("⚠️ Skipping corrupt offline queue entry: {}", e),
+                    }
+                }
+                if queue.evict_overflow() > 0 {
+                    queue.persist()?;
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => {
+                return Err(e).with_context(|| {
+                    format!("Failed to open offline queue {}", queue.path.display())
+                })
+            }
+        }
+
+        Ok(queue)
+    }
+
+    /// 파일에 쓰지 않는 큐로 전환 (드라이런용, 변경은 메모리에만 남음)
+    pub fn read_only(mut self) -> Self {
+        self.read_only = true;
+        self
+    }
+
+    /// 파일에 쓰지 않는 큐인지
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// 제출을 큐 끝에 추가 (가득 차면 가장 오래된 제출 제거)
+    pub fn push(&mut self, submission: QueuedSubmission) -> Result<()> {
+        self.entries.push_back(submission);
+        let evicted = self.evict_overflow();
+        if evicted > 0 {
+            warn!(
+                "🗑️ Offline queue full: dropped {} oldest submission(s) ({} total)",
+                evicted, self.evicted
+            );
+        }
+        self.persist()
+    }
+
+    /// 가장 오래된 제출
+    pub fn front(&self) -> Option<&QueuedSubmission> {
+        self.entries.front()
+    }
+
+    /// 가장 오래된 제출을 큐에서 제거
+    pub fn pop_front(&mut self) -> Result<Option<QueuedSubmission>> {
+        let submission = self.entries.pop_front();
+        if submission.is_some() {
+            self.persist()?;
+        }
+        Ok(submission)
+    }
+
+    /// 보관 중인 제출 수
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 큐가 가득 차 버린 제출 수 (이 프로세스에서 누적)
+    pub fn evicted(&self) -> u64 {
+        self.evicted
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 현재 내용을 파일에 다시 쓰고 디스크에 동기화 (종료 직전 호출)
+    pub fn flush(&self) -> Result<()> {
+        self.persist()
+    }
+
+    // 최대 크기를 넘는 만큼 앞에서부터 제거
+    fn evict_overflow(&mut self) -> usize {
+        let overflow = self.entries.len().saturating_sub(self.max_entries);
+        self.entries.drain(..overflow);
+        self.evicted += overflow as u64;
+        overflow
+    }
+
+    // 임시 파일에 전체를 쓴 뒤 교체 (중간에 죽어도 이전 내용 또는 새 내용만 남음)
+    fn persist(&self) -> Result<()> {
+        if self.read_only {
+            return Ok(());
+        }
+        if let Some(dir) = self.path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+
+        let tmp = self.path.with_extension("tmp");
+        let mut writer = BufWriter::new(
+            File::create(&tmp).with_context(|| format!("Failed to create {}", tmp.display()))?,
+        );
+        for submission in &self.entries {
+            serde_json::to_writer(&mut writer, submission)?;
+            writer.write_all(b"\n")?;
+        }
+        writer
+            .into_inner()
+            .map_err(|e| e.into_error())?
+            .sync_all()?;
+        fs::rename(&tmp, &self.path)
+            .with_context(|| format!("Failed to replace {}", self.path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use oracle_vm_common::types::AssetPair;
+    use oracle_vm_common::Price;
+
+    fn submission(minute: i64) -> QueuedSubmission {
+        QueuedSubmission {
+            node_id: "node-a".to_string(),
+            price_data: PriceData {
+                pair: AssetPair::btc_usd(),
+                price: Price::from_cents(7_000_000 + minute as u64),
+                timestamp: Utc.timestamp_opt(1_700_000_000 + minute * 60, 0).unwrap(),
+                volume: None,
+                source: "binance".to_string(),
+            },
+        }
+    }
+
+    fn temp_path() -> PathBuf {
+        std::env::temp_dir().join(format!("offline-queue-{}.jsonl", uuid::Uuid::new_v4()))
+    }
+
+    #[test]
+    fn test_queue_survives_reopen_in_order() {
+        let path = temp_path();
+        let mut queue = OfflineQueue::open(&path, 10).unwrap();
+        for minute in 0..3 {
+            queue.push(submission(minute)).unwrap();
+        }
+        drop(queue);
+
+        let mut reopened = OfflineQueue::open(&path, 10).unwrap();
+        assert_eq!(reopened.len(), 3);
+        let first = reopened.pop_front().unwrap().unwrap();
+        assert_eq!(first.price_data.timestamp, submission(0).price_data.timestamp);
+        drop(reopened);
+
+        // 꺼낸 제출은 재시작 후에도 다시 나타나지 않음
+        let reopened = OfflineQueue::open(&path, 10).unwrap();
+        assert_eq!(reopened.len(), 2);
+        assert_eq!(
+            reopened.front().unwrap().price_data.price,
+            submission(1).price_data.price
+        );
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_full_queue_evicts_oldest_first() {
+        let path = temp_path();
+        let mut queue = OfflineQueue::open(&path, 3).unwrap();
+        for minute in 0..5 {
+            queue.push(submission(minute)).unwrap();
+        }
+
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue.evicted(), 2);
+        assert_eq!(
+            queue.front().unwrap().price_data.price,
+            submission(2).price_data.price
+        );
+
+        // 더 작은 한도로 다시 열면 넘치는 만큼 오래된 것부터 버림
+        let reopened = OfflineQueue::open(&path, 1).unwrap();
+        assert_eq!(reopened.len(), 1);
+        assert_eq!(reopened.evicted(), 2);
+        assert_eq!(
+            reopened.front().unwrap().price_data.price,
+            submission(4).price_data.price
+        );
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_read_only_queue_never_writes() {
+        let path = temp_path();
+        let mut queue = OfflineQueue::open(&path, 10).unwrap();
+        queue.push(submission(0)).unwrap();
+
+        let mut read_only = OfflineQueue::open(&path, 10).unwrap().read_only();
+        assert!(read_only.is_read_only());
+        read_only.push(submission(1)).unwrap();
+        read_only.pop_front().unwrap();
+        read_only.flush().unwrap();
+        assert_eq!(read_only.len(), 1);
+
+        // 파일에는 처음 제출만 남음
+        let reopened = OfflineQueue::open(&path, 10).unwrap();
+        assert_eq!(reopened.len(), 1);
+        assert_eq!(
+            reopened.front().unwrap().price_data.price,
+            submission(0).price_data.price
+        );
+        assert!(!OfflineQueue::open(temp_path(), 10)
+            .unwrap()
+            .read_only()
+            .path()
+            .exists());
+
+        fs::remove_file(path).unwrap();
+    }
+}
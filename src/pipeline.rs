@@ -0,0 +1,109 @@
+//! pair별 수집/제출 파이프라인
+//!
+//! 설정한 pair마다 거래소 목록, 수집 주기, 수집 지연이 다른 라운드 반복을 동시에 실행한다.
+//! 파이프라인은 Aggregator 클라이언트(gRPC 채널, 오프라인 큐, 시계 오차 추정)와 노드 상태를 나눠
+//! 쓰지만 라운드 결과와 패닉 재시작은 따로 관리하므로, 한 pair의 거래소가 모두 실패해도 다른 pair의
+//! 제출은 그대로 이어진다.
+
+use chrono::Utc;
+use futures::future::try_join_all;
+use oracle_vm_common::types::AssetPair;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tracing::{error, info};
+
+use crate::grpc_client::MultiAggregatorClient;
+use crate::price_provider::MultiExchangePriceProvider;
+use crate::round::{self, RoundLoopExit};
+use crate::scheduler::{self, AdaptiveInterval, RoundPacer};
+use crate::shutdown::ShutdownSignal;
+use crate::status::NodeStatus;
+use crate::supervisor::{GaveUp, Supervisor};
+
+/// pair 하나의 라운드 반복
+pub struct PairPipeline {
+    /// 이 pair를 가져오는 거래소 (`with_pair`로 pair 지정)
+    pub provider: MultiExchangePriceProvider,
+    /// 매 라운드 전에 기다릴 시간 (적응형 주기 포함)
+    pub pacer: RoundPacer<Box<dyn FnMut() -> Duration + Send>>,
+    pub supervisor: Supervisor,
+}
+
+impl PairPipeline {
+    /// `interval` 경계마다 `delay` 뒤에 수집하는 파이프라인
+    pub fn scheduled(
+        provider: MultiExchangePriceProvider,
+        interval: Duration,
+        delay: Duration,
+        max_restarts: u32,
+    ) -> Self {
+        let task = format!("{} fetch/submit loop", provider.pair().as_str());
+        Self {
+            provider,
+            // 매 라운드 다시 정렬해 느린 라운드가 있어도 경계에서 밀리지 않음
+            pacer: RoundPacer::new(Box::new(move || {
+                scheduler::time_until_next_round(Utc::now(), interval, delay)
+            })),
+            supervisor: Supervisor::new(task, max_restarts),
+        }
+    }
+
+    /// 모든 거래소가 계속 실패하면 주기를 늘림
+    pub fn with_adaptive_interval(mut self, adaptive: AdaptiveInterval) -> Self {
+        self.pacer = self.pacer.with_adaptive_interval(adaptive);
+        self
+    }
+
+    pub fn pair(&self) -> &AssetPair {
+        self.provider.pair()
+    }
+}
+
+/// 모든 파이프라인을 종료 요청까지 동시에 실행한 뒤 클라이언트를 닫음
+///
+/// 라운드 결과는 `status.for_pair`로 pair별로 기록한다. 한 파이프라인이 재시작 한도를 넘으면 나머지도
+/// 멈추고 `GaveUp`을 반환한다. 반환하는 라운드 수는 모든 파이프라인의 합이다.
+pub async fn run_pipelines(
+    pipelines: Vec<PairPipeline>,
+    client: &mut MultiAggregatorClient,
+    shutdown: ShutdownSignal,
+    grace: Duration,
+    status: &NodeStatus,
+) -> Result<RoundLoopExit, GaveUp> {
+    let shared = Mutex::new(&mut *client);
+    let runs = pipelines.into_iter().map(|mut pipeline| {
+        let shared = &shared;
+        let shutdown = shutdown.clone();
+        let status = status.for_pair(pipeline.pair().clone());
+        async move {
+            info!("🧵 Starting {} pipeline", pipeline.pair().as_str());
+            round::run_shared(
+                &pipeline.provider,
+                shared,
+                &mut pipeline.pacer,
+                shutdown,
+                grace,
+                &status,
+                &mut pipeline.supervisor,
+            )
+            .await
+        }
+    });
+    let result = try_join_all(runs).await.map(|exits| {
+        exits.into_iter().fold(
+            RoundLoopExit {
+                rounds: 0,
+                abandoned: false,
+            },
+            |total, exit| RoundLoopExit {
+                rounds: total.rounds + exit.rounds,
+                abandoned: total.abandoned || exit.abandoned,
+            },
+        )
+    });
+
+    if let Err(e) = client.close() {
+        error!("❌ Failed to flush offline queue: {:#}", e);
+    }
+    result
+}
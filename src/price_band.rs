@@ -0,0 +1,235 @@
+//! pair별 가격 허용 범위 (거래소가 터무니없는 가격을 돌려주지 않았는지 확인)
+
+use anyhow::Result;
+use oracle_vm_common::types::AssetPair;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use tracing::warn;
+
+/// BTC/USD 기본 허용 범위 하한 (USD)
+pub const DEFAULT_BTC_MIN_PRICE: f64 = 1_000.0;
+/// BTC/USD 기본 허용 범위 상한 (USD)
+pub const DEFAULT_BTC_MAX_PRICE: f64 = 1_000_000.0;
+
+/// 가격 허용 범위 (quote 통화 단위, 양 끝 포함)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PriceBand {
+    pub min: f64,
+    pub max: f64,
+}
+
+impl PriceBand {
+    /// 범위 생성 (0 <= min < max, 상한은 무한대 가능)
+    pub fn new(min: f64, max: f64) -> Result<Self> {
+        if !(min.is_finite() && min >= 0.0) || max.is_nan() || min >= max {
+            anyhow::bail!(
+                "price band must satisfy 0 <= min < max, got [{}, {}]",
+                min,
+                max
+            );
+        }
+        Ok(Self { min, max })
+    }
+
+    /// pair의 기본 범위 (BTC/USD만 있음)
+    pub fn default_for(pair: &AssetPair) -> Option<Self> {
+        (*pair == AssetPair::btc_usd()).then_some(Self {
+            min: DEFAULT_BTC_MIN_PRICE,
+            max: DEFAULT_BTC_MAX_PRICE,
+        })
+    }
+
+    /// 가격이 범위 안에 있는지
+    pub fn contains(&self, price: f64) -> bool {
+        (self.min..=self.max).contains(&price)
+    }
+}
+
+/// 범위를 벗어난 가격 처리 방식
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutOfBandAction {
+    /// 경고만 남기고 사용
+    #[default]
+    Warn,
+    /// 그 거래소의 가격을 실패로 처리
+    Reject,
+}
+
+impl fmt::Display for OutOfBandAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Warn => write!(f, "warn"),
+            Self::Reject => write!(f, "reject"),
+        }
+    }
+}
+
+impl FromStr for OutOfBandAction {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "warn" => Ok(Self::Warn),
+            "reject" => Ok(Self::Reject),
+            _ => Err(format!(
+                "unknown out-of-band action '{}' (expected warn or reject)",
+                value
+            )),
+        }
+    }
+}
+
+/// pair별 허용 범위 표 (범위가 없는 pair는 양수인지만 확인)
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceBands {
+    bands: HashMap<AssetPair, PriceBand>,
+    action: OutOfBandAction,
+}
+
+impl Default for PriceBands {
+    fn default() -> Self {
+        let btc_usd = AssetPair::btc_usd();
+        let band = PriceBand::default_for(&btc_usd).expect("BTC/USD has a default band");
+        Self {
+            bands: HashMap::from([(btc_usd, band)]),
+            action: OutOfBandAction::default(),
+        }
+    }
+}
+
+impl PriceBands {
+    /// `pair`의 허용 범위 지정 (기본 범위를 대체)
+    pub fn with_band(mut self, pair: AssetPair, band: PriceBand) -> Self {
+        self.bands.insert(pair, band);
+        self
+    }
+
+    /// 범위를 벗어난 가격 처리 방식 지정 (기본값: warn)
+    pub fn with_action(mut self, action: OutOfBandAction) -> Self {
+        self.action = action;
+        self
+    }
+
+    /// `pair`의 허용 범위
+    pub fn band(&self, pair: &AssetPair) -> Option<PriceBand> {
+        self.bands.get(pair).copied()
+    }
+
+    pub fn action(&self) -> OutOfBandAction {
+        self.action
+    }
+
+    /// `source`가 보낸 `pair` 가격 검증
+    ///
+    /// 양수가 아니면 항상 에러이고, 범위를 벗어나면 처리 방식에 따라 경고하거나 에러를 반환한다.
+    pub fn check(&self, source: &str, pair: &AssetPair, price: f64) -> Result<()> {
+        if price.is_nan() || price <= 0.0 {
+            anyhow::bail!("Invalid price: must be positive, got {}", price);
+        }
+
+        let Some(band) = self.band(pair) else {
+            return Ok(());
+        };
+        let (direction, bound) = if price < band.min {
+            ("low", band.min)
+        } else if price > band.max {
+            ("high", band.max)
+        } else {
+            return Ok(());
+        };
+
+        match self.action {
+            OutOfBandAction::Warn => {
+                warn!(
+                    "⚠️ Unusually {} {} price from {}: {:.2} (bound {})",
+                    direction,
+                    pair.as_str(),
+                    source,
+                    price,
+                    bound
+                );
+                Ok(())
+            }
+            OutOfBandAction::Reject => anyhow::bail!(
+                "{} price from {} is outside the sanity band [{}, {}]: {:.2}",
+                pair.as_str(),
+                source,
+                band.min,
+                band.max,
+                price
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eth_usd() -> AssetPair {
+        AssetPair::from_symbol("ETH/USD").unwrap()
+    }
+
+    #[test]
+    fn test_default_bands_cover_btc_only() {
+        let bands = PriceBands::default();
+        assert_eq!(
+            bands.band(&AssetPair::btc_usd()),
+            Some(PriceBand {
+                min: DEFAULT_BTC_MIN_PRICE,
+                max: DEFAULT_BTC_MAX_PRICE,
+            })
+        );
+        assert_eq!(bands.band(&eth_usd()), None);
+
+        // 범위가 없는 pair는 양수면 통과
+        assert!(bands.check("binance", &eth_usd(), 0.5).is_ok());
+        assert!(bands.check("binance", &eth_usd(), 0.0).is_err());
+        assert!(bands.check("binance", &AssetPair::btc_usd(), -1.0).is_err());
+        assert!(bands
+            .check("binance", &AssetPair::btc_usd(), f64::NAN)
+            .is_err());
+    }
+
+    #[test]
+    fn test_custom_band_accepts_inside_and_flags_outside() {
+        let band = PriceBand::new(100.0, 10_000.0).unwrap();
+        let warn = PriceBands::default().with_band(eth_usd(), band);
+        let reject = warn.clone().with_action(OutOfBandAction::Reject);
+
+        // 범위 안 (양 끝 포함)
+        for price in [100.0, 2_500.0, 10_000.0] {
+            assert!(warn.check("kraken", &eth_usd(), price).is_ok());
+            assert!(reject.check("kraken", &eth_usd(), price).is_ok());
+        }
+
+        // 범위 밖: warn이면 경고만, reject면 에러
+        for price in [50.0, 20_000.0] {
+            assert!(!band.contains(price));
+            assert!(warn.check("kraken", &eth_usd(), price).is_ok());
+            let err = reject.check("kraken", &eth_usd(), price).unwrap_err();
+            assert!(err.to_string().contains("ETH/USD"), "{}", err);
+        }
+
+        // BTC 기본 범위는 그대로
+        assert!(reject
+            .check("kraken", &AssetPair::btc_usd(), 50_000.0)
+            .is_ok());
+        assert!(reject
+            .check("kraken", &AssetPair::btc_usd(), 500.0)
+            .is_err());
+    }
+
+    #[test]
+    fn test_band_and_action_parsing() {
+        assert!(PriceBand::new(10.0, 10.0).is_err());
+        assert!(PriceBand::new(-1.0, 10.0).is_err());
+        assert!(PriceBand::new(0.0, f64::INFINITY).is_ok());
+
+        assert_eq!("Reject".parse(), Ok(OutOfBandAction::Reject));
+        assert_eq!("warn".parse(), Ok(OutOfBandAction::Warn));
+        assert!("drop".parse::<OutOfBandAction>().is_err());
+        assert_eq!(OutOfBandAction::Reject.to_string(), "reject");
+    }
+}
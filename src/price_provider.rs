@@ -1,40 +1,348 @@
 use anyhow::Result;
 use async_trait::async_trait;
-use oracle_vm_common::types::PriceData;
+use oracle_vm_common::types::{AssetPair, PriceData};
+use oracle_vm_common::Price;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::Instrument;
+
+use crate::metrics::NodeMetrics;
 
 /// Price provider trait for different exchanges
 #[async_trait]
 pub trait PriceProvider: Send + Sync {
     /// Fetch the current BTC price
     async fn fetch_btc_price(&self) -> Result<PriceData>;
+
+    /// Fetch the current price of `pair`
+    ///
+    /// Exchanges that only quote BTC/USD keep the default, which fails for every other pair.
+    async fn fetch_price(&self, pair: &AssetPair) -> Result<PriceData> {
+        if *pair == AssetPair::btc_usd() {
+            self.fetch_btc_price().await
+        } else {
+            anyhow::bail!("{} does not quote {}", self.name(), pair.as_str())
+        }
+    }
     
     /// Get the name of the exchange
     fn name(&self) -> &str;
+
+    /// Asset pairs this provider can serve
+    fn supported_pairs(&self) -> Vec<AssetPair>;
+}
+
+/// What to do when providers answer but their prices are far apart
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum DisagreementPolicy {
+    /// Take the median of whatever comes back
+    #[default]
+    Ignore,
+    /// Fail the round if `(max - min) / min` exceeds the percentage
+    Reject { max_spread_pct: f64 },
+    /// Return the median but mark it as disputed if the spread exceeds the percentage
+    Flag { max_spread_pct: f64 },
+}
+
+impl DisagreementPolicy {
+    fn max_spread_pct(&self) -> Option<f64> {
+        match *self {
+            Self::Ignore => None,
+            Self::Reject { max_spread_pct } | Self::Flag { max_spread_pct } => Some(max_spread_pct),
+        }
+    }
+}
+
+/// Median of one round of fetches across all providers
+#[derive(Debug, Clone)]
+pub struct LocalAggregate {
+    /// Median price; `source` joins the contributing exchange names with `+`
+    pub price: PriceData,
+    /// Exchanges whose price went into the median
+    pub sources: Vec<String>,
+    /// Exchanges that answered but are still in their new-source grace period
+    pub grace: Vec<String>,
+    /// Exchanges that failed this round
+    pub failed: Vec<String>,
+    /// Price returned by each exchange that answered, including those in grace
+    pub prices: Vec<PriceData>,
+    /// `(max - min) / min` across the contributing prices, in percent
+    pub spread_pct: f64,
+    /// Spread exceeded the limit under `DisagreementPolicy::Flag`
+    pub disputed: bool,
+}
+
+/// Every provider failed to return a price this round
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("No provider returned a price ({failed} failed)")]
+pub struct AllProvidersFailed {
+    pub failed: usize,
+}
+
+/// Failures in a row after which a provider is reported as down
+const DOWN_AFTER_FAILURES: u32 = 3;
+
+/// Health of a provider judged from its recent fetches
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProviderHealth {
+    /// Not fetched yet
+    Unknown,
+    /// The last fetch succeeded
+    Healthy,
+    /// The last fetch failed, but fewer than 3 in a row
+    Degraded,
+    /// 3 or more fetches in a row failed
+    Down,
+}
+
+/// Fetch statistics of one provider since startup
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProviderStats {
+    pub successes: u64,
+    pub failures: u64,
+    pub consecutive_failures: u32,
+    /// Duration of the last fetch, successful or not
+    pub last_latency_ms: Option<u64>,
+    /// Mean duration of all fetches
+    pub mean_latency_ms: Option<f64>,
+    /// Error of the last failed fetch
+    pub last_error: Option<String>,
+}
+
+impl ProviderStats {
+    pub fn health(&self) -> ProviderHealth {
+        match self.consecutive_failures {
+            _ if self.successes + self.failures == 0 => ProviderHealth::Unknown,
+            0 => ProviderHealth::Healthy,
+            n if n < DOWN_AFTER_FAILURES => ProviderHealth::Degraded,
+            _ => ProviderHealth::Down,
+        }
+    }
+
+    fn record(&mut self, latency: Duration, result: &Result<PriceData>) {
+        match result {
+            Ok(_) => {
+                self.successes += 1;
+                self.consecutive_failures = 0;
+            }
+            Err(e) => {
+                self.failures += 1;
+                self.consecutive_failures += 1;
+                self.last_error = Some(format!("{:#}", e));
+            }
+        }
+        let latency_ms = latency.as_secs_f64() * 1000.0;
+        let fetches = (self.successes + self.failures) as f64;
+        let mean = self.mean_latency_ms.unwrap_or(0.0);
+        self.last_latency_ms = Some(latency.as_millis() as u64);
+        self.mean_latency_ms = Some(mean + (latency_ms - mean) / fetches);
+    }
 }
 
 /// Multi-exchange price provider that can aggregate prices
 pub struct MultiExchangePriceProvider {
     providers: Vec<Box<dyn PriceProvider>>,
+    disagreement_policy: DisagreementPolicy,
+    new_source_grace: u32,
+    pair: AssetPair,
+    // Same order as `providers`
+    stats: Mutex<Vec<ProviderStats>>,
+    metrics: Option<NodeMetrics>,
 }
 
 impl MultiExchangePriceProvider {
+    /// Registry fetching BTC/USD from every provider
     pub fn new(providers: Vec<Box<dyn PriceProvider>>) -> Self {
-        Self { providers }
+        Self {
+            stats: Mutex::new(vec![ProviderStats::default(); providers.len()]),
+            providers,
+            disagreement_policy: DisagreementPolicy::default(),
+            new_source_grace: 0,
+            pair: AssetPair::btc_usd(),
+            metrics: None,
+        }
+    }
+
+    /// Fetch `pair` instead of BTC/USD
+    pub fn with_pair(mut self, pair: AssetPair) -> Self {
+        self.pair = pair;
+        self
+    }
+
+    /// Pair fetched from every provider
+    pub fn pair(&self) -> &AssetPair {
+        &self.pair
+    }
+
+    /// Also record every fetch's result and latency in the node's metrics
+    pub fn with_metrics(mut self, metrics: NodeMetrics) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Set how `fetch_median_price` handles providers that disagree
+    pub fn with_disagreement_policy(mut self, policy: DisagreementPolicy) -> Self {
+        self.disagreement_policy = policy;
+        self
+    }
+
+    /// Leave each provider's first `count` prices out of the median and the spread check
+    ///
+    /// A new exchange is judged only once it has some history; until then it cannot move the
+    /// median or fail the round. Ignored in rounds where no established provider answers.
+    pub fn with_new_source_grace(mut self, count: u32) -> Self {
+        self.new_source_grace = count;
+        self
     }
     
-    /// Fetch prices from all providers
+    /// Fetch the configured pair from all providers
     pub async fn fetch_all_prices(&self) -> Vec<(String, Result<PriceData>)> {
         let mut results = Vec::new();
         
-        for provider in &self.providers {
+        for (index, provider) in self.providers.iter().enumerate() {
             let name = provider.name().to_string();
-            let result = provider.fetch_btc_price().await;
+            let span = tracing::info_span!(
+                "fetch_price",
+                pair = self.pair.as_str(),
+                provider = name.as_str(),
+                outcome = tracing::field::Empty
+            );
+            let started = Instant::now();
+            let result = provider.fetch_price(&self.pair).instrument(span.clone()).await;
+            let latency = started.elapsed();
+            span.record("outcome", if result.is_ok() { "success" } else { "failure" });
+            self.lock_stats()[index].record(latency, &result);
+            if let Some(metrics) = &self.metrics {
+                metrics.record_fetch(&self.pair, &name, latency, result.is_ok());
+            }
             results.push((name, result));
         }
         
         results
     }
+
+    /// Name and fetch statistics of every provider, in configured order
+    pub fn provider_stats(&self) -> Vec<(String, ProviderStats)> {
+        let stats = self.lock_stats();
+        self.providers
+            .iter()
+            .zip(stats.iter())
+            .map(|(provider, stats)| (provider.name().to_string(), stats.clone()))
+            .collect()
+    }
+
+    // Stats stay readable even if a fetch panicked while holding the lock
+    fn lock_stats(&self) -> std::sync::MutexGuard<'_, Vec<ProviderStats>> {
+        self.stats.lock().unwrap_or_else(|e| e.into_inner())
+    }
     
+    /// Union of all providers' supported pairs (first-seen order, no duplicates)
+    pub fn supported_pairs(&self) -> Vec<AssetPair> {
+        let mut pairs: Vec<AssetPair> = Vec::new();
+        
+        for pair in self.providers.iter().flat_map(|p| p.supported_pairs()) {
+            if !pairs.contains(&pair) {
+                pairs.push(pair);
+            }
+        }
+        
+        pairs
+    }
+    
+    /// Fetch from all providers and take the exact median of the successful prices
+    ///
+    /// The timestamp is the newest among the contributing prices. Fails if no provider succeeds,
+    /// or if the prices disagree beyond the limit under `DisagreementPolicy::Reject`. Providers
+    /// still in their new-source grace period are reported in `grace` instead of contributing.
+    pub async fn fetch_median_price(&self) -> Result<LocalAggregate> {
+        // Judged by the prices returned before this round
+        let in_grace: Vec<bool> = self
+            .lock_stats()
+            .iter()
+            .map(|stats| stats.successes < u64::from(self.new_source_grace))
+            .collect();
+        let mut prices = Vec::new();
+        let mut established = Vec::new();
+        let mut failed = Vec::new();
+
+        for ((name, result), in_grace) in self.fetch_all_prices().await.into_iter().zip(in_grace) {
+            match result {
+                Ok(price_data) => {
+                    established.push(!in_grace);
+                    prices.push(price_data);
+                }
+                Err(e) => {
+                    tracing::warn!("⚠️ {} failed: {}", name, e);
+                    failed.push(name);
+                }
+            }
+        }
+
+        // With no established provider this round there is nothing to judge newcomers against
+        let judged = established.contains(&true);
+        let mut contributing = Vec::new();
+        let mut grace = Vec::new();
+        for (price_data, established) in prices.iter().zip(established) {
+            if established || !judged {
+                contributing.push(price_data.clone());
+            } else {
+                grace.push(price_data.source.clone());
+            }
+        }
+        if !grace.is_empty() {
+            tracing::info!("🆕 {} in new-source grace, left out of the median", grace.join(", "));
+        }
+
+        let price = median(contributing.iter().map(|p| p.price).collect())
+            .ok_or(AllProvidersFailed { failed: failed.len() })?;
+        let spread_pct = spread_pct(&contributing);
+        let disputed = match self.disagreement_policy.max_spread_pct() {
+            Some(max_spread_pct) if spread_pct > max_spread_pct => {
+                if let DisagreementPolicy::Reject { .. } = self.disagreement_policy {
+                    anyhow::bail!(
+                        "Providers disagree: spread {:.3}% exceeds {:.3}%",
+                        spread_pct,
+                        max_spread_pct
+                    );
+                }
+                tracing::warn!(
+                    "⚠️ Providers disagree: spread {:.3}% exceeds {:.3}%, flagging median ${}",
+                    spread_pct,
+                    max_spread_pct,
+                    price
+                );
+                true
+            }
+            _ => false,
+        };
+
+        let sources: Vec<String> = contributing.iter().map(|p| p.source.clone()).collect();
+        let newest = contributing
+            .iter()
+            .max_by_key(|p| p.timestamp)
+            .expect("median exists only for a non-empty round");
+
+        let price = PriceData {
+            pair: newest.pair.clone(),
+            price,
+            timestamp: newest.timestamp,
+            volume: None,
+            source: sources.join("+"),
+        };
+
+        Ok(LocalAggregate {
+            price,
+            sources,
+            grace,
+            failed,
+            prices,
+            spread_pct,
+            disputed,
+        })
+    }
+
     /// Fetch prices and return only successful ones
     pub async fn fetch_valid_prices(&self) -> Vec<PriceData> {
         let results = self.fetch_all_prices().await;
@@ -46,10 +354,35 @@ impl MultiExchangePriceProvider {
     }
 }
 
+/// `(max - min) / min` in percent (0 for fewer than two prices)
+fn spread_pct(prices: &[PriceData]) -> f64 {
+    let min = prices.iter().map(|p| p.price).min();
+    let max = prices.iter().map(|p| p.price).max();
+    match (min, max) {
+        (Some(min), Some(max)) if !min.is_zero() => {
+            (max.to_f64_dollars() - min.to_f64_dollars()) / min.to_f64_dollars() * 100.0
+        }
+        _ => 0.0,
+    }
+}
+
+/// Exact median (average of the two middle prices for an even count)
+fn median(mut prices: Vec<Price>) -> Option<Price> {
+    prices.sort();
+    let mid = prices.len() / 2;
+    match prices.len() {
+        0 => None,
+        len if len.is_multiple_of(2) => prices[mid - 1].midpoint(&prices[mid]),
+        _ => Some(prices[mid]),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::DateTime;
     use mockall::{mock, predicate::*};
+    use oracle_vm_common::Price;
     
     mock! {
         Provider {}
@@ -58,6 +391,7 @@ mod tests {
         impl PriceProvider for Provider {
             async fn fetch_btc_price(&self) -> Result<PriceData>;
             fn name(&self) -> &str;
+            fn supported_pairs(&self) -> Vec<AssetPair>;
         }
     }
     
@@ -71,8 +405,10 @@ mod tests {
         mock1.expect_fetch_btc_price()
             .times(1)
             .returning(|| Ok(PriceData {
-                price: 70000.0,
-                timestamp: 1700000000,
+                pair: AssetPair::btc_usd(),
+                price: Price::from_cents(7000000), // $70,000 in cents
+                timestamp: DateTime::from_timestamp(1700000000, 0).unwrap(),
+                volume: None,
                 source: "Exchange1".to_string(),
             }));
             
@@ -80,8 +416,10 @@ mod tests {
         mock2.expect_fetch_btc_price()
             .times(1)
             .returning(|| Ok(PriceData {
-                price: 70100.0,
-                timestamp: 1700000001,
+                pair: AssetPair::btc_usd(),
+                price: Price::from_cents(7010000), // $70,100 in cents
+                timestamp: DateTime::from_timestamp(1700000001, 0).unwrap(),
+                volume: None,
                 source: "Exchange2".to_string(),
             }));
         
@@ -95,8 +433,8 @@ mod tests {
         
         // Then
         assert_eq!(prices.len(), 2);
-        assert_eq!(prices[0].price, 70000.0);
-        assert_eq!(prices[1].price, 70100.0);
+        assert_eq!(prices[0].price, Price::from_cents(7000000));
+        assert_eq!(prices[1].price, Price::from_cents(7010000));
     }
     
     #[tokio::test]
@@ -114,8 +452,10 @@ mod tests {
         mock2.expect_fetch_btc_price()
             .times(1)
             .returning(|| Ok(PriceData {
-                price: 70100.0,
-                timestamp: 1700000001,
+                pair: AssetPair::btc_usd(),
+                price: Price::from_cents(7010000), // $70,100 in cents
+                timestamp: DateTime::from_timestamp(1700000001, 0).unwrap(),
+                volume: None,
                 source: "Exchange2".to_string(),
             }));
         
@@ -129,6 +469,267 @@ mod tests {
         
         // Then - Only successful price is returned
         assert_eq!(prices.len(), 1);
-        assert_eq!(prices[0].price, 70100.0);
+        assert_eq!(prices[0].price, Price::from_cents(7010000));
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_multi_exchange_unions_supported_pairs() {
+        // Given
+        let mut mock1 = MockProvider::new();
+        let mut mock2 = MockProvider::new();
+        
+        mock1.expect_supported_pairs()
+            .returning(|| vec![AssetPair::btc_usd()]);
+        mock2.expect_supported_pairs()
+            .returning(|| vec![AssetPair("ETH/USD".to_string()), AssetPair::btc_usd()]);
+        
+        let provider = MultiExchangePriceProvider::new(vec![
+            Box::new(mock1),
+            Box::new(mock2),
+        ]);
+        
+        // When
+        let pairs = provider.supported_pairs();
+        
+        // Then - BTC/USD appears once
+        assert_eq!(pairs, vec![AssetPair::btc_usd(), AssetPair("ETH/USD".to_string())]);
+    }
+
+    #[tokio::test]
+    async fn test_registry_fetches_its_pair() {
+        let mut mock = MockProvider::new();
+        mock.expect_name().return_const("Exchange1".to_string());
+        mock.expect_fetch_btc_price().never();
+
+        let eth = AssetPair("ETH/USD".to_string());
+        let provider = MultiExchangePriceProvider::new(vec![Box::new(mock)]).with_pair(eth.clone());
+        assert_eq!(provider.pair(), &eth);
+
+        // BTC-only exchanges fail for other pairs without fetching BTC
+        let error = provider.fetch_median_price().await.unwrap_err();
+        assert!(error.to_string().contains("No provider returned a price"));
+        let results = provider.fetch_all_prices().await;
+        assert!(results[0].1.as_ref().unwrap_err().to_string().contains("does not quote ETH/USD"));
+    }
+
+    #[tokio::test]
+    async fn test_provider_stats_track_health_and_latency() {
+        let mut failing = MockProvider::new();
+        failing.expect_name().return_const("kraken".to_string());
+        failing.expect_fetch_btc_price()
+            .times(3)
+            .returning(|| Err(anyhow::anyhow!("Network error")));
+        let mut answering = MockProvider::new();
+        answering.expect_name().return_const("binance".to_string());
+        answering.expect_fetch_btc_price()
+            .times(3)
+            .returning(|| Ok(PriceData {
+                pair: AssetPair::btc_usd(),
+                price: Price::from_cents(7000000),
+                timestamp: DateTime::from_timestamp(1700000000, 0).unwrap(),
+                volume: None,
+                source: "binance".to_string(),
+            }));
+
+        let provider = MultiExchangePriceProvider::new(vec![Box::new(answering), Box::new(failing)]);
+        let stats = provider.provider_stats();
+        assert_eq!(stats[0].1.health(), ProviderHealth::Unknown);
+        assert_eq!(stats[0].1.mean_latency_ms, None);
+
+        provider.fetch_all_prices().await;
+        assert_eq!(provider.provider_stats()[1].1.health(), ProviderHealth::Degraded);
+        provider.fetch_all_prices().await;
+        provider.fetch_all_prices().await;
+
+        let stats = provider.provider_stats();
+        assert_eq!(stats[0].0, "binance");
+        assert_eq!(stats[0].1.health(), ProviderHealth::Healthy);
+        assert_eq!((stats[0].1.successes, stats[0].1.failures), (3, 0));
+        assert!(stats[0].1.last_latency_ms.is_some());
+        assert!(stats[0].1.mean_latency_ms.is_some());
+        assert_eq!(stats[1].0, "kraken");
+        assert_eq!(stats[1].1.health(), ProviderHealth::Down);
+        assert_eq!(stats[1].1.consecutive_failures, 3);
+        assert_eq!(stats[1].1.last_error.as_deref(), Some("Network error"));
+    }
+
+    fn mock_returning(name: &'static str, cents: Option<u64>, timestamp: i64) -> MockProvider {
+        let mut mock = MockProvider::new();
+        mock.expect_name().return_const(name.to_string());
+        mock.expect_fetch_btc_price()
+            .times(1)
+            .returning(move || match cents {
+                Some(cents) => Ok(PriceData {
+                    pair: AssetPair::btc_usd(),
+                    price: Price::from_cents(cents),
+                    timestamp: DateTime::from_timestamp(timestamp, 0).unwrap(),
+                    volume: None,
+                    source: name.to_string(),
+                }),
+                None => Err(anyhow::anyhow!("Network error")),
+            });
+        mock
+    }
+    
+    #[tokio::test]
+    async fn test_fetch_median_price_aggregates_locally() {
+        // Given - one failing exchange, two that answer
+        let provider = MultiExchangePriceProvider::new(vec![
+            Box::new(mock_returning("binance", Some(7000000), 1700000000)),
+            Box::new(mock_returning("coinbase", None, 0)),
+            Box::new(mock_returning("kraken", Some(7000001), 1700000002)),
+        ]);
+        
+        // When
+        let aggregate = provider.fetch_median_price().await.unwrap();
+        
+        // Then - exact midpoint, newest timestamp
+        assert_eq!(aggregate.price.price.to_scaled(), (70000005, 3));
+        assert_eq!(aggregate.price.timestamp.timestamp(), 1700000002);
+        assert_eq!(aggregate.price.source, "binance+kraken");
+        assert_eq!(aggregate.sources, vec!["binance", "kraken"]);
+        assert_eq!(aggregate.failed, vec!["coinbase"]);
+    }
+    
+    #[tokio::test]
+    async fn test_fetch_median_price_fails_when_every_exchange_fails() {
+        let provider = MultiExchangePriceProvider::new(vec![
+            Box::new(mock_returning("binance", None, 0)),
+        ]);
+        
+        let error = provider.fetch_median_price().await.unwrap_err();
+        assert_eq!(error.downcast_ref(), Some(&AllProvidersFailed { failed: 1 }));
+    }
+
+    fn disagreeing_registry(policy: DisagreementPolicy, high_cents: u64) -> MultiExchangePriceProvider {
+        MultiExchangePriceProvider::new(vec![
+            Box::new(mock_returning("binance", Some(7000000), 1700000000)),
+            Box::new(mock_returning("coinbase", Some(7001000), 1700000000)),
+            Box::new(mock_returning("kraken", Some(high_cents), 1700000000)),
+        ])
+        .with_disagreement_policy(policy)
+    }
+    
+    #[tokio::test]
+    async fn test_tight_agreement_is_accepted_under_every_policy() {
+        for policy in [
+            DisagreementPolicy::Ignore,
+            DisagreementPolicy::Reject { max_spread_pct: 1.0 },
+            DisagreementPolicy::Flag { max_spread_pct: 1.0 },
+        ] {
+            // $70,000 .. $70,200 -> 0.29% spread
+            let aggregate = disagreeing_registry(policy, 7020000)
+                .fetch_median_price()
+                .await
+                .unwrap();
+            
+            assert_eq!(aggregate.price.price, Price::from_cents(7001000));
+            assert!((aggregate.spread_pct - 0.2857).abs() < 0.001);
+            assert!(!aggregate.disputed);
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_wide_disagreement_is_rejected() {
+        // $70,000 .. $77,000 -> 10% spread
+        let policy = DisagreementPolicy::Reject { max_spread_pct: 1.0 };
+        let error = disagreeing_registry(policy, 7700000)
+            .fetch_median_price()
+            .await
+            .unwrap_err();
+        
+        assert!(error.to_string().contains("disagree"));
+    }
+    
+    #[tokio::test]
+    async fn test_wide_disagreement_is_flagged() {
+        let policy = DisagreementPolicy::Flag { max_spread_pct: 1.0 };
+        let aggregate = disagreeing_registry(policy, 7700000)
+            .fetch_median_price()
+            .await
+            .unwrap();
+        
+        // The median is still returned, but marked
+        assert_eq!(aggregate.price.price, Price::from_cents(7001000));
+        assert!((aggregate.spread_pct - 10.0).abs() < 1e-9);
+        assert!(aggregate.disputed);
+    }
+    
+    #[tokio::test]
+    async fn test_wide_disagreement_is_ignored_by_default() {
+        let aggregate = disagreeing_registry(DisagreementPolicy::default(), 7700000)
+            .fetch_median_price()
+            .await
+            .unwrap();
+        
+        assert!(!aggregate.disputed);
+    }
+
+    // Fails for the first `absent_rounds` fetches, then quotes `cents` every round
+    fn mock_joining(name: &'static str, cents: u64, absent_rounds: usize) -> MockProvider {
+        let mut mock = MockProvider::new();
+        let mut calls = 0;
+        mock.expect_name().return_const(name.to_string());
+        mock.expect_fetch_btc_price().returning(move || {
+            calls += 1;
+            if calls <= absent_rounds {
+                return Err(anyhow::anyhow!("Not listed yet"));
+            }
+            Ok(PriceData {
+                pair: AssetPair::btc_usd(),
+                price: Price::from_cents(cents),
+                timestamp: DateTime::from_timestamp(1700000000, 0).unwrap(),
+                volume: None,
+                source: name.to_string(),
+            })
+        });
+        mock
+    }
+
+    #[tokio::test]
+    async fn test_new_source_bootstraps_during_grace() {
+        // okx joins in the third round quoting 10x the others
+        let provider = MultiExchangePriceProvider::new(vec![
+            Box::new(mock_joining("binance", 7000000, 0)),
+            Box::new(mock_joining("coinbase", 7001000, 0)),
+            Box::new(mock_joining("okx", 70000000, 2)),
+        ])
+        .with_disagreement_policy(DisagreementPolicy::Reject { max_spread_pct: 1.0 })
+        .with_new_source_grace(2);
+
+        // Nobody is established yet, so the first rounds are judged as usual
+        for _ in 0..2 {
+            let aggregate = provider.fetch_median_price().await.unwrap();
+            assert_eq!(aggregate.sources, vec!["binance", "coinbase"]);
+            assert!(aggregate.grace.is_empty());
+        }
+
+        // okx is neither rejected nor allowed to move the median for its first two prices
+        for _ in 0..2 {
+            let aggregate = provider.fetch_median_price().await.unwrap();
+            assert_eq!(aggregate.price.price, Price::from_cents(7000500));
+            assert_eq!(aggregate.price.source, "binance+coinbase");
+            assert_eq!(aggregate.grace, vec!["okx"]);
+            assert_eq!(aggregate.prices.len(), 3);
+            assert!((aggregate.spread_pct - 0.0143).abs() < 0.001);
+        }
+
+        // Then it is checked like every other exchange
+        let error = provider.fetch_median_price().await.unwrap_err();
+        assert!(error.to_string().contains("disagree"));
+    }
+
+    #[tokio::test]
+    async fn test_no_new_source_grace_by_default() {
+        let provider = MultiExchangePriceProvider::new(vec![
+            Box::new(mock_joining("binance", 7000000, 0)),
+            Box::new(mock_joining("coinbase", 7001000, 0)),
+            Box::new(mock_joining("okx", 70000000, 1)),
+        ])
+        .with_disagreement_policy(DisagreementPolicy::Reject { max_spread_pct: 1.0 });
+
+        assert!(provider.fetch_median_price().await.is_ok());
+        let error = provider.fetch_median_price().await.unwrap_err();
+        assert!(error.to_string().contains("disagree"));
+    }
+}
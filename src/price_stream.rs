@@ -0,0 +1,209 @@
+//! Aggregator와의 장기 실행 stream_prices 양방향 스트림
+//!
+//! 송신 측으로 라운드마다 가격을 보내고 Aggregator가 같은 스트림으로 돌려주는 처리 결과를 기다린다.
+//! 수신 측에서는 Aggregator가 게시하는 집계 결과를 받아 노드 자신의 값과 비교해 기록한다.
+//! 받은 집계 가격은 클라이언트의 `AggregateCache`에도 남긴다.
+//! 스트림이 끝나면(정상 종료든 연결 끊김이든) 닫힘으로 표시된다.
+
+use oracle_vm_common::Price;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::transport::Channel;
+use tonic::{Request, Status};
+use tracing::{debug, info, warn};
+
+use crate::grpc_client::oracle::{
+    oracle_service_client::OracleServiceClient, AggregatedPriceUpdate, PriceRequest, PriceResponse,
+};
+use crate::network_price::AggregateCache;
+
+/// 보내지 못한 가격을 쌓아 두는 송신 버퍼 크기
+const SEND_BUFFER: usize = 16;
+/// 보낸 가격의 처리 결과를 기다리는 시간 (넘기면 스트림을 닫힘으로 표시)
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(5);
+
+// 송신 측과 수신 태스크가 공유하는 상태
+#[derive(Default)]
+struct Shared {
+    local_price: Mutex<Option<Price>>,
+    network_price: Mutex<Option<Price>>,
+    updates: AtomicU64,
+    closed: AtomicBool,
+    aggregate: AggregateCache,
+    // 처리 결과를 기다리는 제출 (Aggregator는 받은 순서대로 결과를 돌려줌)
+    pending: Mutex<VecDeque<oneshot::Sender<PriceResponse>>>,
+}
+
+/// 열려 있는 stream_prices 스트림
+pub struct PriceStream {
+    sender: mpsc::Sender<PriceRequest>,
+    shared: Arc<Shared>,
+    receiver_task: JoinHandle<()>,
+}
+
+impl PriceStream {
+    /// 스트림 열기 (Aggregator가 지원하지 않으면 `Code::Unimplemented`)
+    ///
+    /// 받은 집계 가격은 `aggregate`에도 기록한다.
+    pub async fn open(
+        mut client: OracleServiceClient<Channel>,
+        aggregate: AggregateCache,
+    ) -> Result<Self, Status> {
+        let (sender, outbound) = mpsc::channel(SEND_BUFFER);
+        let mut inbound = client
+            .stream_prices(Request::new(ReceiverStream::new(outbound)))
+            .await?
+            .into_inner();
+
+        let shared = Arc::new(Shared {
+            aggregate,
+            ..Shared::default()
+        });
+        let receiver_shared = shared.clone();
+        let receiver_task = tokio::spawn(async move {
+            loop {
+                match inbound.message().await {
+                    Ok(Some(AggregatedPriceUpdate {
+                        submission: Some(response),
+                        ..
+                    })) => receiver_shared.respond(response),
+                    Ok(Some(update)) => receiver_shared.record(&update),
+                    Ok(None) => {
+                        info!("🔚 gRPC: Aggregator closed the price stream");
+                        break;
+                    }
+                    Err(status) => {
+                        warn!("🔌 gRPC: Price stream broke: {}", status.message());
+                        break;
+                    }
+                }
+            }
+            receiver_shared.close();
+        });
+
+        info!("📡 gRPC: Opened price stream");
+        Ok(Self {
+            sender,
+            shared,
+            receiver_task,
+        })
+    }
+
+    /// 가격을 보내고 Aggregator의 처리 결과를 기다림
+    ///
+    /// 스트림이 이미 닫혔거나 결과를 받기 전에 끊기면 None이다.
+    /// `RESPONSE_TIMEOUT` 안에 결과가 오지 않으면 이후 결과와 짝이 맞지 않으므로 스트림을 닫힘으로 표시한다.
+    pub async fn send(&self, request: PriceRequest) -> Option<PriceResponse> {
+        if self.is_closed() {
+            return None;
+        }
+        let local_price = request_price(&request);
+        let (respond, response) = oneshot::channel();
+        self.shared.pending.lock().unwrap().push_back(respond);
+        if self.sender.send(request).await.is_err() {
+            return None;
+        }
+        *self.shared.local_price.lock().unwrap() = local_price;
+
+        match tokio::time::timeout(RESPONSE_TIMEOUT, response).await {
+            Ok(Ok(response)) => Some(response),
+            Ok(Err(_)) => None,
+            Err(_) => {
+                warn!(
+                    "⚠️ gRPC: No result for the streamed price within {:?}",
+                    RESPONSE_TIMEOUT
+                );
+                self.shared.close();
+                None
+            }
+        }
+    }
+
+    /// 스트림이 끝났는지
+    pub fn is_closed(&self) -> bool {
+        self.shared.closed.load(Ordering::Acquire) || self.sender.is_closed()
+    }
+
+    /// 지금까지 받은 집계 결과 수
+    pub fn updates_received(&self) -> u64 {
+        self.shared.updates.load(Ordering::Relaxed)
+    }
+
+    /// 마지막으로 받은 네트워크 집계 중간값
+    pub fn network_price(&self) -> Option<Price> {
+        *self.shared.network_price.lock().unwrap()
+    }
+}
+
+impl Drop for PriceStream {
+    fn drop(&mut self) {
+        self.receiver_task.abort();
+    }
+}
+
+impl Shared {
+    // 가장 오래 기다린 제출에 처리 결과 전달
+    fn respond(&self, response: PriceResponse) {
+        match self.pending.lock().unwrap().pop_front() {
+            Some(respond) => {
+                // 기다리던 쪽이 시간 초과로 포기했으면 무시
+                let _ = respond.send(response);
+            }
+            None => warn!("⚠️ gRPC: Unexpected submission result on the price stream"),
+        }
+    }
+
+    // 닫힘으로 표시하고 결과를 기다리는 제출을 모두 깨움
+    fn close(&self) {
+        self.closed.store(true, Ordering::Release);
+        self.pending.lock().unwrap().clear();
+    }
+
+    // 받은 집계 결과를 저장하고 노드 자신의 값과 비교해 기록
+    fn record(&self, update: &AggregatedPriceUpdate) {
+        self.updates.fetch_add(1, Ordering::Relaxed);
+
+        // 아직 집계 결과가 없으면 0, Aggregator가 price_unit = cents이면 센트 단위
+        let dollars = match update.unit.as_str() {
+            "cents" => update.aggregated_price / 100.0,
+            _ => update.aggregated_price,
+        };
+        let network = (dollars > 0.0)
+            .then(|| Price::from_f64_dollars(dollars, Price::USD_DECIMALS).ok())
+            .flatten();
+        *self.network_price.lock().unwrap() = network;
+
+        let Some(network) = network else {
+            debug!("📡 gRPC: Aggregate update without a price yet");
+            return;
+        };
+        self.aggregate.record(network, update.timestamp);
+        match *self.local_price.lock().unwrap() {
+            Some(local) => {
+                let deviation_pct =
+                    (local.to_f64_dollars() / network.to_f64_dollars() - 1.0) * 100.0;
+                info!(
+                    "🌐 Network median ${} vs local ${} ({:+.3}%, {} nodes)",
+                    network,
+                    local,
+                    deviation_pct,
+                    update.active_nodes.len()
+                );
+            }
+            None => info!("🌐 Network median ${}", network),
+        }
+    }
+}
+
+// 요청의 가격 (고정소수점 필드 우선)
+fn request_price(request: &PriceRequest) -> Option<Price> {
+    match (request.price_scaled, request.price_decimals) {
+        (Some(scaled), Some(decimals)) => Price::from_scaled(scaled, decimals).ok(),
+        _ => Price::from_f64_dollars(request.price, Price::USD_DECIMALS).ok(),
+    }
+}
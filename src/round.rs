@@ -0,0 +1,409 @@
+//! 수집 라운드: 모든 거래소에서 가격을 가져와 로컬 중간값을 Aggregator에 제출
+//!
+//! 라운드 반복은 클라이언트를 `Mutex`로 감싸 여러 pair 파이프라인이 한 클라이언트(gRPC 채널,
+//! 오프라인 큐, 시계 오차 추정)를 나눠 쓸 수 있다. 잠금은 제출하는 동안만 잡으므로 한 파이프라인의
+//! 느린 거래소가 다른 파이프라인의 제출을 막지 않는다.
+//!
+//! 라운드마다 `round` span(node_id, pair, outcome)을 열고, 그 안에서 한 거래소 수집과 제출이
+//! 일어나므로 추적을 켜면 Aggregator 처리까지 한 trace로 이어진다.
+
+use anyhow::Result;
+use chrono::Utc;
+use oracle_vm_common::types::PriceData;
+use oracle_vm_common::Price;
+use std::borrow::BorrowMut;
+use std::fmt;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tracing::{error, field, info, info_span, warn, Instrument, Span};
+
+use crate::price_provider::LocalAggregate;
+
+use crate::grpc_client::MultiAggregatorClient;
+use crate::offline_queue::OfflineQueue;
+use crate::price_provider::{AllProvidersFailed, MultiExchangePriceProvider};
+use crate::scheduler::{IntervalChange, RoundPacer};
+use crate::shutdown::ShutdownSignal;
+use crate::status::NodeStatus;
+use crate::supervisor::{catch_panic, GaveUp, Supervisor};
+
+/// 한 라운드의 결과 요약
+#[derive(Debug, Clone)]
+pub struct RoundSummary {
+    /// 가격을 돌려준 거래소
+    pub sources: Vec<String>,
+    /// 이번 라운드에 실패한 거래소
+    pub failed: Vec<String>,
+    /// 거래소별로 받은 가격
+    pub prices: Vec<PriceData>,
+    /// 제출한 로컬 중간값
+    pub local_price: Price,
+    /// 거래소 간 가격 차이 (%)
+    pub spread_pct: f64,
+    /// 가격 차이가 허용 범위를 넘었지만 제출한 경우 (DisagreementPolicy::Flag)
+    pub disputed: bool,
+    /// Aggregator가 돌려준 집계 중간값 (아직 없으면 None)
+    pub aggregated_price: Option<Price>,
+    /// 재전송을 기다리는 오프라인 큐 제출 수
+    pub queued: usize,
+    /// 오프라인 큐가 가득 차 버린 제출 수 (누적)
+    pub queue_evicted: u64,
+    /// 드라이런 라운드 (제출하지 않았고 aggregated_price는 조회한 네트워크 값)
+    pub dry_run: bool,
+}
+
+impl fmt::Display for RoundSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.dry_run {
+            write!(f, "[dry-run] ")?;
+        }
+        write!(f, "sources=[{}]", self.sources.join(", "))?;
+        if !self.failed.is_empty() {
+            write!(f, " failed=[{}]", self.failed.join(", "))?;
+        }
+        write!(f, " local=${}", self.local_price)?;
+        if self.disputed {
+            write!(f, " disputed(spread={:.2}%)", self.spread_pct)?;
+        }
+        match self.aggregated_price {
+            Some(price) => write!(f, " aggregator=${}", price)?,
+            None => write!(f, " aggregator=n/a")?,
+        }
+        if self.queued > 0 {
+            write!(f, " queued={}", self.queued)?;
+        }
+        if self.queue_evicted > 0 {
+            write!(f, " queue_evicted={}", self.queue_evicted)?;
+        }
+        Ok(())
+    }
+}
+
+/// 한 라운드 실행: 수집 -> 로컬 중간값 -> 제출
+///
+/// 모든 거래소가 실패하거나 제출이 실패하면 에러를 반환하며, 다음 라운드에는 영향을 주지 않는다.
+pub async fn run_round(
+    provider: &MultiExchangePriceProvider,
+    client: &mut MultiAggregatorClient,
+) -> Result<RoundSummary> {
+    let span = round_span(provider, client.node_id());
+    let result = async {
+        let aggregate = provider.fetch_median_price().await?;
+        submit_aggregate(aggregate, client).await
+    }
+    .instrument(span.clone())
+    .await;
+    record_outcome(&span, &result);
+    result
+}
+
+/// `run_round`와 같되 클라이언트는 제출할 때만 잠금
+pub async fn run_shared_round<C: BorrowMut<MultiAggregatorClient>>(
+    provider: &MultiExchangePriceProvider,
+    client: &Mutex<C>,
+) -> Result<RoundSummary> {
+    let span = round_span(provider, (*client.lock().await).borrow().node_id());
+    let result = async {
+        let aggregate = provider.fetch_median_price().await?;
+        let mut client = client.lock().await;
+        submit_aggregate(aggregate, (*client).borrow_mut()).await
+    }
+    .instrument(span.clone())
+    .await;
+    record_outcome(&span, &result);
+    result
+}
+
+// 라운드 하나의 span (outcome은 끝난 뒤 기록)
+fn round_span(provider: &MultiExchangePriceProvider, node_id: &str) -> Span {
+    info_span!(
+        "round",
+        node_id,
+        pair = provider.pair().as_str(),
+        outcome = field::Empty
+    )
+}
+
+fn record_outcome(span: &Span, result: &Result<RoundSummary>) {
+    let outcome = match result {
+        Ok(summary) if summary.dry_run => "dry_run",
+        Ok(_) => "submitted",
+        Err(e) if e.is::<AllProvidersFailed>() => "all_providers_failed",
+        Err(_) => "failed",
+    };
+    span.record("outcome", outcome);
+}
+
+// 로컬 중간값 제출 후 요약
+async fn submit_aggregate(
+    aggregate: LocalAggregate,
+    client: &mut MultiAggregatorClient,
+) -> Result<RoundSummary> {
+    let aggregated_price = client.submit_price(&aggregate.price).await?;
+    let queue = client.offline_queue();
+
+    Ok(RoundSummary {
+        sources: aggregate.sources,
+        failed: aggregate.failed,
+        prices: aggregate.prices,
+        local_price: aggregate.price.price,
+        spread_pct: aggregate.spread_pct,
+        disputed: aggregate.disputed,
+        aggregated_price,
+        queued: queue.map_or(0, OfflineQueue::len),
+        queue_evicted: queue.map_or(0, OfflineQueue::evicted),
+        dry_run: client.is_dry_run(),
+    })
+}
+
+/// 라운드 반복을 마친 결과
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoundLoopExit {
+    /// 끝까지 실행한 라운드 수 (실패한 라운드 포함)
+    pub rounds: u64,
+    /// 종료 대기 시간 안에 끝나지 않아 중단한 라운드가 있었는지
+    pub abandoned: bool,
+}
+
+/// 종료가 요청될 때까지 라운드 반복
+///
+/// `next_wait`는 매 라운드 전에 기다릴 시간이고, 라운드 결과는 `status`에 기록한다. 종료가 요청되면
+/// 진행 중인 라운드가 끝나기를 `grace`만큼 기다린 뒤(넘으면 중단) 스트림과 gRPC 채널을 닫고
+/// 오프라인 큐를 디스크에 기록한다.
+pub async fn run_until_shutdown(
+    provider: &MultiExchangePriceProvider,
+    client: &mut MultiAggregatorClient,
+    next_wait: impl FnMut() -> Duration,
+    shutdown: ShutdownSignal,
+    grace: Duration,
+    status: &NodeStatus,
+) -> RoundLoopExit {
+    let mut exit = RoundLoopExit {
+        rounds: 0,
+        abandoned: false,
+    };
+    let shared = Mutex::new(&mut *client);
+    round_loop(
+        provider,
+        &shared,
+        &mut RoundPacer::new(next_wait),
+        shutdown,
+        grace,
+        status,
+        &mut exit,
+    )
+    .await;
+
+    if let Err(e) = client.close() {
+        error!("❌ Failed to flush offline queue: {:#}", e);
+    }
+    exit
+}
+
+// 종료 요청까지 라운드 반복 (끝낸 라운드 수는 패닉해도 남도록 `exit`에 바로 기록)
+async fn round_loop<C: BorrowMut<MultiAggregatorClient>, W: FnMut() -> Duration>(
+    provider: &MultiExchangePriceProvider,
+    client: &Mutex<C>,
+    pacer: &mut RoundPacer<W>,
+    mut shutdown: ShutdownSignal,
+    grace: Duration,
+    status: &NodeStatus,
+    exit: &mut RoundLoopExit,
+) {
+    let pair = provider.pair().as_str();
+    status.record_client((*client.lock().await).borrow());
+    status.record_providers(provider);
+    if pacer.adaptive().is_enabled() {
+        status.record_adaptive(pacer.adaptive().report());
+    }
+
+    while !shutdown.is_triggered() {
+        tokio::select! {
+            _ = tokio::time::sleep(pacer.next_wait()) => {}
+            _ = shutdown.wait() => break,
+        }
+        info!(
+            "🕐 Collecting {} at {}",
+            pair,
+            Utc::now().format("%H:%M:%S")
+        );
+        if (*client.lock().await).borrow().is_dry_run() {
+            warn!("🧪 DRY RUN: this round is fetched and logged only, nothing is submitted");
+        }
+
+        let result = {
+            let round = run_shared_round(provider, client);
+            tokio::pin!(round);
+            tokio::select! {
+                result = &mut round => Some(result),
+                _ = shutdown.wait() => {
+                    info!("⏳ Waiting up to {:?} for the in-flight round", grace);
+                    tokio::time::timeout(grace, &mut round).await.ok()
+                }
+            }
+        };
+
+        // 실패한 라운드는 기록만 하고 다음 라운드 진행
+        if let Some(result) = &result {
+            status.record_round((*client.lock().await).borrow(), result);
+            status.record_providers(provider);
+            let all_failed = result.as_ref().is_err_and(|e| e.is::<AllProvidersFailed>());
+            match pacer.record(all_failed) {
+                IntervalChange::Unchanged => {}
+                IntervalChange::Stretched(interval) => warn!(
+                    "🐢 {} degraded: every provider failed {} rounds in a row, fetching every {:?} (heartbeats unchanged)",
+                    pair,
+                    pacer.adaptive().report().consecutive_failures,
+                    interval
+                ),
+                IntervalChange::Restored(interval) => info!(
+                    "✅ {} providers answered again, fetching every {:?}",
+                    pair, interval
+                ),
+            }
+            if pacer.adaptive().is_enabled() {
+                status.record_adaptive(pacer.adaptive().report());
+            }
+        }
+        match result {
+            Some(Ok(summary)) => info!("📋 {} round complete: {}", pair, summary),
+            Some(Err(e)) => error!("❌ {} round failed: {:#}", pair, e),
+            None => {
+                warn!(
+                    "⌛ In-flight round did not finish within {:?}, abandoning it",
+                    grace
+                );
+                exit.abandoned = true;
+                break;
+            }
+        }
+        exit.rounds += 1;
+    }
+}
+
+/// `run_until_shutdown`과 같되 루프가 패닉하면 백오프 후 다시 시작
+///
+/// 오프라인 큐 재전송도 제출 안에서 일어나므로 함께 감시된다. 재시작 한도를 넘으면 `GaveUp`을
+/// 반환하며, 이때도 오프라인 큐는 디스크에 기록한다.
+pub async fn run_supervised(
+    provider: &MultiExchangePriceProvider,
+    client: &mut MultiAggregatorClient,
+    next_wait: impl FnMut() -> Duration,
+    shutdown: ShutdownSignal,
+    grace: Duration,
+    status: &NodeStatus,
+    supervisor: &mut Supervisor,
+) -> Result<RoundLoopExit, GaveUp> {
+    let shared = Mutex::new(&mut *client);
+    let result = run_shared(
+        provider,
+        &shared,
+        &mut RoundPacer::new(next_wait),
+        shutdown,
+        grace,
+        status,
+        supervisor,
+    )
+    .await;
+
+    if let Err(e) = client.close() {
+        error!("❌ Failed to flush offline queue: {:#}", e);
+    }
+    result
+}
+
+/// `run_supervised`와 같되 다른 파이프라인과 나눠 쓰는 클라이언트를 닫지 않음 (호출자가 닫음)
+///
+/// 대기 시간은 `pacer`가 정하며, 적응형 주기 상태는 패닉 후 재시작해도 이어진다.
+pub async fn run_shared<C: BorrowMut<MultiAggregatorClient>, W: FnMut() -> Duration>(
+    provider: &MultiExchangePriceProvider,
+    client: &Mutex<C>,
+    pacer: &mut RoundPacer<W>,
+    mut shutdown: ShutdownSignal,
+    grace: Duration,
+    status: &NodeStatus,
+    supervisor: &mut Supervisor,
+) -> Result<RoundLoopExit, GaveUp> {
+    let mut exit = RoundLoopExit {
+        rounds: 0,
+        abandoned: false,
+    };
+    loop {
+        let attempt = round_loop(
+            provider,
+            client,
+            pacer,
+            shutdown.clone(),
+            grace,
+            status,
+            &mut exit,
+        );
+        let Err(panic) = catch_panic(attempt).await else {
+            return Ok(exit);
+        };
+
+        // 패닉한 라운드도 한 라운드로 셈
+        exit.rounds += 1;
+        status.record_restart();
+        match supervisor.on_panic(panic) {
+            Ok(delay) => tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                _ = shutdown.wait() => {}
+            },
+            Err(gave_up) => return Err(gave_up),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_is_one_line() {
+        let summary = RoundSummary {
+            sources: vec!["binance".to_string(), "kraken".to_string()],
+            failed: vec!["coinbase".to_string()],
+            prices: Vec::new(),
+            local_price: Price::from_cents(7_000_050),
+            spread_pct: 0.0,
+            disputed: false,
+            aggregated_price: None,
+            queued: 0,
+            queue_evicted: 0,
+            dry_run: false,
+        };
+        assert_eq!(
+            summary.to_string(),
+            "sources=[binance, kraken] failed=[coinbase] local=$70000.50 aggregator=n/a"
+        );
+
+        let disputed = RoundSummary {
+            spread_pct: 3.25,
+            disputed: true,
+            aggregated_price: Some(Price::from_cents(7_000_000)),
+            ..summary
+        };
+        assert_eq!(
+            disputed.to_string(),
+            "sources=[binance, kraken] failed=[coinbase] local=$70000.50 disputed(spread=3.25%) aggregator=$70000.00"
+        );
+
+        let backlog = RoundSummary {
+            queued: 3,
+            queue_evicted: 1,
+            ..disputed
+        };
+        assert!(backlog
+            .to_string()
+            .ends_with("aggregator=$70000.00 queued=3 queue_evicted=1"));
+
+        let dry_run = RoundSummary {
+            dry_run: true,
+            ..backlog
+        };
+        assert!(dry_run
+            .to_string()
+            .starts_with("[dry-run] sources=[binance, kraken]"));
+    }
+}
@@ -149,7 +149,7 @@ impl SafeBtcPrice {
         sorted.sort_by_key(|p| p.satoshis);
         
         let len = sorted.len();
-        if len % 2 == 0 {
+        if len.is_multiple_of(2) {
             // 짝수 개인 경우 중간 두 값의 평균
             let mid1 = sorted[len / 2 - 1];
             let mid2 = sorted[len / 2];
@@ -206,8 +206,7 @@ pub struct SafePriceData {
 impl SafePriceData {
     /// 기존 PriceData에서 변환
     pub fn from_price_data(data: &PriceData) -> Result<Self> {
-        // Convert cents to dollars
-        let price_usd = data.price as f64 / 100.0;
+        let price_usd = data.price.to_f64_dollars();
         #[allow(deprecated)]
         let safe_price = SafeBtcPrice::from_f64(price_usd)?;
 
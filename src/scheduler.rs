@@ -0,0 +1,464 @@
+use chrono::{DateTime, DurationRound, TimeDelta, Utc};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// 분 경계 이후 수집 지연의 기본 최대값 (초)
+pub const DEFAULT_MAX_JITTER_SECS: u64 = 5;
+
+/// 분 경계 이후 기본 수집 지연 (초) - 직전 분봉이 확실히 마감된 뒤 수집
+pub const DEFAULT_FETCH_OFFSET_SECS: u64 = 2;
+
+/// 0 ~ `max_jitter` 사이의 임의 지연 시간을 선택합니다 (밀리초 단위)
+///
+/// 노드마다 다른 지연을 사용하면 모든 노드가 정각에 동시에 거래소를 호출하지 않습니다.
+pub fn random_jitter(max_jitter: Duration) -> Duration {
+    let max_millis = max_jitter.as_millis() as u64;
+    if max_millis == 0 {
+        return Duration::ZERO;
+    }
+    Duration::from_millis(rand::thread_rng().gen_range(0..=max_millis))
+}
+
+/// node_id로 정해지는 0 ~ `max_jitter` 사이의 지연 시간 (밀리초 단위)
+///
+/// 같은 node_id는 재시작해도 항상 같은 지연을 받고, 노드들은 구간 전체에 고르게 흩어집니다.
+/// 해시는 Rust 버전과 무관하게 고정된 FNV-1a를 사용합니다.
+pub fn node_jitter(node_id: &str, max_jitter: Duration) -> Duration {
+    let max_millis = max_jitter.as_millis() as u64;
+    if max_millis == 0 {
+        return Duration::ZERO;
+    }
+    Duration::from_millis(fnv1a(node_id.as_bytes()) % (max_millis + 1))
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// 노드의 수집 지연: 고정 지연 + 임의 지연 (0 ~ `max_jitter`)
+pub fn fetch_delay(offset: Duration, max_jitter: Duration) -> Duration {
+    offset + random_jitter(max_jitter)
+}
+
+/// 노드의 수집 지연: 고정 지연 + node_id로 정해지는 지연 (0 ~ `max_jitter`)
+///
+/// node_id가 없으면 임의 지연을 사용합니다. 어느 쪽이든 고정 지연 이후이므로 직전 봉은 마감되어 있고,
+/// [`next_round_time`]이 주기보다 짧게 제한하므로 라운드를 건너뛰지 않습니다.
+pub fn node_fetch_delay(node_id: Option<&str>, offset: Duration, max_jitter: Duration) -> Duration {
+    match node_id {
+        Some(node_id) => offset + node_jitter(node_id, max_jitter),
+        None => fetch_delay(offset, max_jitter),
+    }
+}
+
+/// 기본 수집 주기
+pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(60);
+
+/// `now` 이후 다음 `interval` 경계를 계산합니다 (Unix epoch 기준 정렬, 최소 1초)
+pub fn next_boundary(now: DateTime<Utc>, interval: Duration) -> DateTime<Utc> {
+    let interval = TimeDelta::from_std(interval)
+        .unwrap_or(TimeDelta::days(1))
+        .max(TimeDelta::seconds(1));
+    let current_start = now
+        .duration_trunc(interval)
+        .expect("interval truncation is always in range");
+    current_start + interval
+}
+
+/// `now` 이후 다음 분 경계(XX:XX:00)를 계산합니다
+pub fn next_minute_boundary(now: DateTime<Utc>) -> DateTime<Utc> {
+    next_boundary(now, DEFAULT_INTERVAL)
+}
+
+/// 다음 라운드 시각: 다음 `interval` 경계 + 노드별 지연
+///
+/// 지연은 주기보다 1초 짧게 제한되어 라운드를 건너뛰지 않습니다.
+pub fn next_round_time(now: DateTime<Utc>, interval: Duration, delay: Duration) -> DateTime<Utc> {
+    let max_delay = TimeDelta::from_std(interval.saturating_sub(Duration::from_secs(1)))
+        .unwrap_or(TimeDelta::zero());
+    let delay = TimeDelta::from_std(delay).unwrap_or(TimeDelta::zero());
+    next_boundary(now, interval) + delay.min(max_delay)
+}
+
+/// `now`부터 다음 라운드 시각까지 기다려야 하는 시간
+pub fn time_until_next_round(now: DateTime<Utc>, interval: Duration, delay: Duration) -> Duration {
+    (next_round_time(now, interval, delay) - now)
+        .to_std()
+        .unwrap_or(Duration::ZERO)
+}
+
+/// 다음 수집 시각: 다음 분 경계 + 노드별 지연
+///
+/// 지연은 1분 미만이어야 직전에 완성된 분봉을 그대로 가져옵니다.
+pub fn next_fetch_time(now: DateTime<Utc>, jitter: Duration) -> DateTime<Utc> {
+    next_round_time(now, DEFAULT_INTERVAL, jitter)
+}
+
+/// `now`부터 다음 수집 시각까지 기다려야 하는 시간
+pub fn time_until_next_fetch(now: DateTime<Utc>, jitter: Duration) -> Duration {
+    time_until_next_round(now, DEFAULT_INTERVAL, jitter)
+}
+
+/// 적응형 주기가 늘릴 수 있는 기본 최대 주기
+pub const DEFAULT_ADAPTIVE_MAX_INTERVAL: Duration = Duration::from_secs(300);
+
+/// 모든 거래소가 실패한 라운드가 이어질 때 수집 주기를 늘리는 적응형 주기
+///
+/// `after_failures`번 연속으로 모든 거래소가 실패하면 주기를 2배로 늘리고, 이후 실패할 때마다 다시
+/// 2배씩 늘리되 `max_interval`을 넘지 않는 원래 주기의 최대 정수배에서 멈춥니다. 늘어난 주기는 원래
+/// 주기의 정수배라 라운드는 계속 주기 경계에 맞춰지며, 한 라운드라도 가격을 받으면 바로 원래 주기로
+/// 돌아갑니다. 하트비트는 별도 작업이라 영향을 받지 않습니다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdaptiveInterval {
+    interval: Duration,
+    // 0이면 사용하지 않음
+    after_failures: u32,
+    max_interval: Duration,
+    consecutive_failures: u32,
+    multiplier: u32,
+}
+
+/// 라운드 결과로 바뀐 주기
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntervalChange {
+    Unchanged,
+    /// 주기를 늘림 (늘어난 주기)
+    Stretched(Duration),
+    /// 원래 주기로 돌아옴
+    Restored(Duration),
+}
+
+/// 적응형 주기 상태 (`/status`에 노출)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AdaptiveReport {
+    /// 주기를 늘린 상태인지
+    pub degraded: bool,
+    /// 모든 거래소가 실패한 연속 라운드 수
+    pub consecutive_failures: u32,
+    pub after_failures: u32,
+    /// 지금 적용 중인 주기 (초)
+    pub interval_secs: u64,
+    pub base_interval_secs: u64,
+    pub max_interval_secs: u64,
+}
+
+impl AdaptiveInterval {
+    /// `interval`로 수집하다가 `after_failures`번 연속 모두 실패하면 `max_interval`까지 늘림 (0이면 늘리지 않음)
+    pub fn new(interval: Duration, after_failures: u32, max_interval: Duration) -> Self {
+        Self {
+            interval,
+            after_failures,
+            max_interval,
+            consecutive_failures: 0,
+            multiplier: 1,
+        }
+    }
+
+    /// 주기를 늘리지 않는 적응형 주기
+    pub fn disabled() -> Self {
+        Self::new(Duration::ZERO, 0, Duration::ZERO)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.after_failures > 0 && !self.interval.is_zero()
+    }
+
+    pub fn is_degraded(&self) -> bool {
+        self.multiplier > 1
+    }
+
+    /// 지금 적용 중인 주기
+    pub fn current_interval(&self) -> Duration {
+        self.interval * self.multiplier
+    }
+
+    /// 원래 주기에 더해 기다릴 시간
+    pub fn extra_wait(&self) -> Duration {
+        self.interval * (self.multiplier - 1)
+    }
+
+    /// 라운드 결과 기록 (`all_failed`: 모든 거래소가 실패했는지)
+    pub fn record(&mut self, all_failed: bool) -> IntervalChange {
+        if !self.is_enabled() {
+            return IntervalChange::Unchanged;
+        }
+        if !all_failed {
+            self.consecutive_failures = 0;
+            if self.multiplier == 1 {
+                return IntervalChange::Unchanged;
+            }
+            self.multiplier = 1;
+            return IntervalChange::Restored(self.interval);
+        }
+
+        self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+        let doublings = (self.consecutive_failures + 1).checked_sub(self.after_failures);
+        let Some(doublings) = doublings else {
+            return IntervalChange::Unchanged;
+        };
+        let max_multiplier = (self.max_interval.as_nanos() / self.interval.as_nanos()).max(1);
+        let multiplier = 1u128
+            .checked_shl(doublings)
+            .unwrap_or(u128::MAX)
+            .min(max_multiplier) as u32;
+        if multiplier == self.multiplier {
+            return IntervalChange::Unchanged;
+        }
+        self.multiplier = multiplier;
+        IntervalChange::Stretched(self.current_interval())
+    }
+
+    pub fn report(&self) -> AdaptiveReport {
+        AdaptiveReport {
+            degraded: self.is_degraded(),
+            consecutive_failures: self.consecutive_failures,
+            after_failures: self.after_failures,
+            interval_secs: self.current_interval().as_secs(),
+            base_interval_secs: self.interval.as_secs(),
+            max_interval_secs: self.max_interval.as_secs(),
+        }
+    }
+}
+
+/// 라운드 사이 대기: 정해진 대기 시간에 적응형 주기가 늘린 만큼을 더함
+pub struct RoundPacer<W> {
+    next_wait: W,
+    adaptive: AdaptiveInterval,
+}
+
+impl<W: FnMut() -> Duration> RoundPacer<W> {
+    /// 매 라운드 전에 `next_wait()`만큼 기다림 (적응형 주기 없음)
+    pub fn new(next_wait: W) -> Self {
+        Self {
+            next_wait,
+            adaptive: AdaptiveInterval::disabled(),
+        }
+    }
+
+    pub fn with_adaptive_interval(mut self, adaptive: AdaptiveInterval) -> Self {
+        self.adaptive = adaptive;
+        self
+    }
+
+    /// 다음 라운드까지 기다릴 시간
+    pub fn next_wait(&mut self) -> Duration {
+        (self.next_wait)() + self.adaptive.extra_wait()
+    }
+
+    /// 라운드 결과를 적응형 주기에 기록
+    pub fn record(&mut self, all_failed: bool) -> IntervalChange {
+        self.adaptive.record(all_failed)
+    }
+
+    pub fn adaptive(&self) -> &AdaptiveInterval {
+        &self.adaptive
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Timelike;
+
+    fn at(hour: u32, minute: u32, second: u32, millis: u32) -> DateTime<Utc> {
+        DateTime::from_timestamp(1_700_000_000, 0)
+            .unwrap()
+            .with_hour(hour)
+            .unwrap()
+            .with_minute(minute)
+            .unwrap()
+            .with_second(second)
+            .unwrap()
+            .with_nanosecond(millis * 1_000_000)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_next_minute_boundary() {
+        assert_eq!(next_minute_boundary(at(14, 37, 12, 500)), at(14, 38, 0, 0));
+        // 정각이면 다음 분으로 넘어감
+        assert_eq!(next_minute_boundary(at(14, 37, 0, 0)), at(14, 38, 0, 0));
+        assert_eq!(next_minute_boundary(at(23, 59, 59, 999)), at(0, 0, 0, 0) + TimeDelta::days(1));
+    }
+
+    #[test]
+    fn test_next_fetch_time_within_jitter_window() {
+        let max_jitter = Duration::from_secs(DEFAULT_MAX_JITTER_SECS);
+
+        for second in [0, 1, 30, 59] {
+            let now = at(14, 37, second, 250);
+            let boundary = at(14, 38, 0, 0);
+
+            for _ in 0..100 {
+                let fetch_time = next_fetch_time(now, random_jitter(max_jitter));
+
+                assert!(fetch_time >= boundary, "{} before boundary", fetch_time);
+                assert!(
+                    fetch_time <= boundary + TimeDelta::from_std(max_jitter).unwrap(),
+                    "{} beyond jitter window",
+                    fetch_time
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_rounds_align_to_interval_boundaries() {
+        let interval = Duration::from_secs(15);
+        assert_eq!(next_boundary(at(14, 37, 12, 500), interval), at(14, 37, 15, 0));
+        assert_eq!(next_boundary(at(14, 37, 45, 0), interval), at(14, 38, 0, 0));
+        assert_eq!(
+            time_until_next_round(at(14, 37, 31, 0), interval, Duration::from_secs(2)),
+            Duration::from_secs(16)
+        );
+        // 지연은 주기보다 짧게 제한
+        assert_eq!(
+            next_round_time(at(14, 37, 31, 0), interval, Duration::from_secs(60)),
+            at(14, 37, 59, 0)
+        );
+    }
+
+    #[test]
+    fn test_jitter_never_skips_a_candle() {
+        // 설정이 1분 이상이어도 같은 분 안에서 수집
+        let now = at(14, 37, 10, 0);
+        let fetch_time = next_fetch_time(now, Duration::from_secs(600));
+
+        assert_eq!(fetch_time.minute(), 38);
+    }
+
+    #[test]
+    fn test_zero_jitter() {
+        assert_eq!(random_jitter(Duration::ZERO), Duration::ZERO);
+        assert_eq!(
+            time_until_next_fetch(at(14, 37, 45, 0), Duration::ZERO),
+            Duration::from_secs(15)
+        );
+    }
+
+    #[test]
+    fn test_fetch_delay_adds_offset_to_jitter() {
+        let offset = Duration::from_secs(DEFAULT_FETCH_OFFSET_SECS);
+        let max_jitter = Duration::from_secs(DEFAULT_MAX_JITTER_SECS);
+
+        for _ in 0..100 {
+            let delay = fetch_delay(offset, max_jitter);
+            assert!(delay >= offset && delay <= offset + max_jitter);
+        }
+        assert_eq!(fetch_delay(offset, Duration::ZERO), offset);
+    }
+
+    #[test]
+    fn test_node_jitter_is_stable_per_node() {
+        let max_jitter = Duration::from_secs(12);
+
+        for node_id in ["node-a", "node-b", "9f2c41d07b3e8a56"] {
+            let jitter = node_jitter(node_id, max_jitter);
+            assert_eq!(jitter, node_jitter(node_id, max_jitter));
+            assert!(jitter <= max_jitter);
+        }
+        // 알려진 값으로 고정 (해시가 바뀌면 모든 노드의 수집 시각이 바뀜)
+        assert_eq!(node_jitter("node-a", max_jitter), Duration::from_millis(78));
+        assert_eq!(node_jitter("node-a", Duration::ZERO), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_node_jitter_bounded_and_spread() {
+        let offset = Duration::from_secs(3);
+        let max_jitter = Duration::from_secs(12);
+
+        let delays: Vec<Duration> = (0..50)
+            .map(|i| node_fetch_delay(Some(&format!("node-{}", i)), offset, max_jitter))
+            .collect();
+        for delay in &delays {
+            assert!(*delay >= offset && *delay <= offset + max_jitter, "{:?}", delay);
+        }
+
+        // 서로 다른 노드는 대부분 다른 지연을 받음
+        let mut distinct = delays.clone();
+        distinct.sort();
+        distinct.dedup();
+        assert!(distinct.len() >= 45, "only {} distinct delays", distinct.len());
+        assert_ne!(node_jitter("node-a", max_jitter), node_jitter("node-b", max_jitter));
+
+        // 구간의 앞뒤 절반 모두에 노드가 있음
+        let middle = offset + max_jitter / 2;
+        assert!(delays.iter().any(|delay| *delay < middle));
+        assert!(delays.iter().any(|delay| *delay > middle));
+    }
+
+    #[test]
+    fn test_node_delay_never_skips_a_candle() {
+        let delay = node_fetch_delay(
+            Some("node-a"),
+            Duration::from_secs(3),
+            Duration::from_secs(600),
+        );
+        let fetch_time = next_fetch_time(at(14, 37, 10, 0), delay);
+
+        assert_eq!(fetch_time.minute(), 38);
+        assert!(fetch_time >= at(14, 38, 3, 0));
+    }
+
+    #[test]
+    fn test_time_until_next_fetch_from_arbitrary_now() {
+        let delay = Duration::from_secs(DEFAULT_FETCH_OFFSET_SECS);
+
+        // 분 중간: 다음 분 경계 + 2초
+        assert_eq!(
+            time_until_next_fetch(at(14, 37, 45, 500), delay),
+            Duration::from_millis(16_500)
+        );
+        // 이번 분의 수집 직후: 다음 분 경계 + 2초까지 기다림
+        assert_eq!(
+            time_until_next_fetch(at(14, 38, 2, 100), delay),
+            Duration::from_millis(59_900)
+        );
+        // 자정을 넘어가는 경우
+        assert_eq!(
+            next_fetch_time(at(23, 59, 30, 0), delay),
+            at(0, 0, 2, 0) + TimeDelta::days(1)
+        );
+    }
+
+    #[test]
+    fn test_adaptive_interval_stretches_caps_and_recovers() {
+        let minute = Duration::from_secs(60);
+        let mut adaptive = AdaptiveInterval::new(minute, 3, Duration::from_secs(300));
+        assert!(adaptive.is_enabled());
+
+        // 기준 횟수 전까지는 그대로
+        assert_eq!(adaptive.record(true), IntervalChange::Unchanged);
+        assert_eq!(adaptive.record(true), IntervalChange::Unchanged);
+        assert_eq!(adaptive.extra_wait(), Duration::ZERO);
+
+        assert_eq!(adaptive.record(true), IntervalChange::Stretched(minute * 2));
+        assert_eq!(adaptive.extra_wait(), minute);
+        assert_eq!(adaptive.record(true), IntervalChange::Stretched(minute * 4));
+        // 8분 대신 최대값 5분에서 멈춤
+        assert_eq!(adaptive.record(true), IntervalChange::Stretched(minute * 5));
+        assert_eq!(adaptive.record(true), IntervalChange::Unchanged);
+        assert_eq!(adaptive.current_interval(), minute * 5);
+        let report = adaptive.report();
+        assert!(report.degraded);
+        assert_eq!(report.consecutive_failures, 6);
+        assert_eq!(report.interval_secs, 300);
+
+        // 한 번 성공하면 바로 원래 주기
+        assert_eq!(adaptive.record(false), IntervalChange::Restored(minute));
+        assert!(!adaptive.is_degraded());
+        assert_eq!(adaptive.extra_wait(), Duration::ZERO);
+        assert_eq!(adaptive.record(false), IntervalChange::Unchanged);
+        assert_eq!(adaptive.record(true), IntervalChange::Unchanged);
+        assert_eq!(adaptive.report().consecutive_failures, 1);
+
+        let mut disabled = AdaptiveInterval::disabled();
+        for _ in 0..10 {
+            assert_eq!(disabled.record(true), IntervalChange::Unchanged);
+        }
+        assert_eq!(disabled.extra_wait(), Duration::ZERO);
+    }
+}
@@ -0,0 +1,140 @@
+//! 노드 종료 제어
+//!
+//! 종료 요청은 watch 채널로 전달되며 수집 루프, 하트비트, 오프라인 큐 재전송, 시계 오차 확인 태스크가
+//! 같은 채널을 구독한다. 첫 번째 SIGINT/SIGTERM은 정상 종료를 요청하고, 두 번째 신호는 즉시 종료한다.
+
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tracing::{error, info};
+
+/// 진행 중인 제출을 마무리하기 위해 기다리는 기본 시간
+pub const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
+/// 두 번째 신호로 강제 종료할 때의 종료 코드
+pub const FORCED_EXIT_CODE: i32 = 130;
+
+/// 종료 요청을 보내는 쪽 (복제본은 같은 채널 공유)
+#[derive(Debug, Clone)]
+pub struct Shutdown {
+    sender: Arc<watch::Sender<bool>>,
+}
+
+impl Default for Shutdown {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Shutdown {
+    pub fn new() -> Self {
+        let (sender, _) = watch::channel(false);
+        Self {
+            sender: Arc::new(sender),
+        }
+    }
+
+    /// 종료 요청 (여러 번 호출해도 됨)
+    pub fn trigger(&self) {
+        self.sender.send_replace(true);
+    }
+
+    /// 종료가 요청되었는지
+    pub fn is_triggered(&self) -> bool {
+        *self.sender.borrow()
+    }
+
+    /// 종료 요청을 기다릴 구독자
+    pub fn subscribe(&self) -> ShutdownSignal {
+        ShutdownSignal {
+            receiver: self.sender.subscribe(),
+        }
+    }
+
+    /// SIGINT(Ctrl-C)/SIGTERM을 받으면 종료 요청, 한 번 더 받으면 즉시 프로세스 종료
+    pub fn listen_for_signals(&self) -> JoinHandle<()> {
+        let shutdown = self.clone();
+        tokio::spawn(async move {
+            if let Err(e) = termination_signal().await {
+                error!("❌ Cannot listen for shutdown signals: {}", e);
+                return;
+            }
+            info!("🛑 Shutdown requested, finishing in-flight work (press Ctrl-C again to force)");
+            shutdown.trigger();
+
+            if termination_signal().await.is_ok() {
+                error!("💥 Second signal received, exiting immediately");
+                std::process::exit(FORCED_EXIT_CODE);
+            }
+        })
+    }
+}
+
+/// 종료 요청 구독자
+#[derive(Debug, Clone)]
+pub struct ShutdownSignal {
+    receiver: watch::Receiver<bool>,
+}
+
+impl ShutdownSignal {
+    /// 종료가 요청되었는지
+    pub fn is_triggered(&self) -> bool {
+        *self.receiver.borrow()
+    }
+
+    /// 종료가 요청될 때까지 대기 (이미 요청되었으면 바로 반환)
+    ///
+    /// 요청하는 쪽이 모두 사라지면 더 이상 요청될 수 없으므로 영원히 대기한다.
+    pub async fn wait(&mut self) {
+        if self
+            .receiver
+            .wait_for(|triggered| *triggered)
+            .await
+            .is_err()
+        {
+            std::future::pending::<()>().await;
+        }
+    }
+}
+
+// SIGINT 또는 SIGTERM (Unix가 아니면 Ctrl-C만)
+async fn termination_signal() -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        let mut terminate = signal(SignalKind::terminate())?;
+        tokio::select! {
+            result = tokio::signal::ctrl_c() => result,
+            _ = terminate.recv() => Ok(()),
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        tokio::signal::ctrl_c().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_subscribers_see_trigger() {
+        let shutdown = Shutdown::new();
+        let mut early = shutdown.subscribe();
+        assert!(!early.is_triggered());
+
+        let waiter = tokio::spawn(async move { early.wait().await });
+        shutdown.trigger();
+        tokio::time::timeout(Duration::from_secs(1), waiter)
+            .await
+            .unwrap()
+            .unwrap();
+
+        // 요청 이후에 구독해도 바로 반환
+        let mut late = shutdown.subscribe();
+        assert!(late.is_triggered());
+        late.wait().await;
+        assert!(shutdown.is_triggered());
+    }
+}
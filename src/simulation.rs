@@ -0,0 +1,298 @@
+//! 합성 가격 제공자 (부하 테스트와 데모용, 거래소에 요청하지 않음)
+//!
+//! 시드에서 시작하는 기하 브라운 운동(GBM) 경로를 따라 가격을 만든다. 같은 설정이면 항상 같은
+//! 가격 순서가 나오고, 지정한 시점에 한 번 가격을 급변시켜 편차 경보를 시험할 수 있다.
+//! 시간은 실제 시각이 아니라 조회 한 번마다 `step_secs`씩 흐르는 모의 시간이다.
+
+use crate::price_provider::PriceProvider;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::Utc;
+use oracle_vm_common::types::{AssetPair, PriceData};
+use oracle_vm_common::Price;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tracing::{info, warn};
+
+/// 제공자 이름 (`--provider simulation`)
+pub const SIMULATION_PROVIDER: &str = "simulation";
+/// 연율 계산에 쓰는 1년 (초)
+const SECONDS_PER_YEAR: f64 = 365.25 * 24.0 * 3600.0;
+
+/// 가격 급변 이벤트
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ShockConfig {
+    /// 시작 후 급변이 일어나는 모의 시간 (초)
+    pub after_secs: u64,
+    /// 가격 변화율 (%, 예: -20이면 20% 하락)
+    pub pct: f64,
+}
+
+/// 합성 가격 설정 (설정 파일의 `[simulation]` 표)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SimulationConfig {
+    /// 난수 시드 (같은 시드면 같은 가격 순서)
+    pub seed: u64,
+    /// 시작 가격 (USD)
+    pub initial_price: f64,
+    /// 연율 기대 수익률 (예: 0.05면 연 5%)
+    pub drift: f64,
+    /// 연율 변동성 (예: 0.6이면 연 60%)
+    pub volatility: f64,
+    /// 조회 한 번마다 흐르는 모의 시간 (초)
+    pub step_secs: u64,
+    /// 가격 급변 이벤트 (없으면 GBM만)
+    pub shock: Option<ShockConfig>,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            initial_price: 65_000.0,
+            drift: 0.0,
+            volatility: 0.6,
+            step_secs: 60,
+            shock: None,
+        }
+    }
+}
+
+impl SimulationConfig {
+    /// 설정이 유효한지 (가격은 양수, 변동성은 0 이상, 급변은 -100% 초과)
+    pub fn validate(&self) -> Result<()> {
+        if !self.initial_price.is_finite() || self.initial_price <= 0.0 {
+            anyhow::bail!("simulation initial_price must be positive");
+        }
+        if !self.drift.is_finite() {
+            anyhow::bail!("simulation drift must be a finite number");
+        }
+        if !self.volatility.is_finite() || self.volatility < 0.0 {
+            anyhow::bail!("simulation volatility must be non-negative");
+        }
+        if self.step_secs == 0 {
+            anyhow::bail!("simulation step_secs must be positive");
+        }
+        if let Some(shock) = &self.shock {
+            if !shock.pct.is_finite() || shock.pct <= -100.0 {
+                anyhow::bail!("simulation shock pct must be greater than -100");
+            }
+        }
+        Ok(())
+    }
+}
+
+// 현재 경로 상태
+#[derive(Debug)]
+struct SimulationState {
+    rng: StdRng,
+    price: f64,
+    elapsed_secs: u64,
+    shocked: bool,
+}
+
+/// GBM 경로를 따르는 합성 가격 제공자
+#[derive(Debug)]
+pub struct SimulationProvider {
+    config: SimulationConfig,
+    state: Mutex<SimulationState>,
+}
+
+impl SimulationProvider {
+    pub fn new(config: SimulationConfig) -> Result<Self> {
+        config.validate()?;
+        let state = SimulationState {
+            rng: StdRng::seed_from_u64(config.seed),
+            price: config.initial_price,
+            elapsed_secs: 0,
+            shocked: false,
+        };
+        Ok(Self {
+            config,
+            state: Mutex::new(state),
+        })
+    }
+
+    pub fn config(&self) -> &SimulationConfig {
+        &self.config
+    }
+
+    /// 현재 모의 시점의 가격을 반환하고 다음 시점으로 진행
+    ///
+    /// 급변 시점에 도달하면 그 가격부터 급변이 반영된다.
+    pub fn next_price(&self) -> f64 {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+
+        if let Some(shock) = self.config.shock {
+            if !state.shocked && state.elapsed_secs >= shock.after_secs {
+                state.shocked = true;
+                state.price *= 1.0 + shock.pct / 100.0;
+                warn!(
+                    "💥 Simulated price shock of {:+.2}% at t={}s",
+                    shock.pct, state.elapsed_secs
+                );
+            }
+        }
+        let price = state.price;
+
+        // S(t+dt) = S(t) * exp((μ - σ²/2)dt + σ√dt·Z)
+        let dt = self.config.step_secs as f64 / SECONDS_PER_YEAR;
+        let z = standard_normal(&mut state.rng);
+        let sigma = self.config.volatility;
+        state.price *=
+            ((self.config.drift - sigma * sigma / 2.0) * dt + sigma * dt.sqrt() * z).exp();
+        state.elapsed_secs += self.config.step_secs;
+
+        price
+    }
+}
+
+// 표준 정규분포 표본 (Box-Muller)
+fn standard_normal(rng: &mut StdRng) -> f64 {
+    // (0, 1] 구간이어야 ln이 유한
+    let u1 = 1.0 - rng.gen::<f64>();
+    let u2 = rng.gen::<f64>();
+    (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos()
+}
+
+#[async_trait]
+impl PriceProvider for SimulationProvider {
+    async fn fetch_btc_price(&self) -> Result<PriceData> {
+        let price = self.next_price();
+        info!("🧪 Simulated BTC price: ${:.2}", price);
+
+        Ok(PriceData {
+            pair: AssetPair::btc_usd(),
+            price: Price::from_f64_dollars(price, Price::USD_DECIMALS)?,
+            timestamp: Utc::now(),
+            volume: None,
+            source: SIMULATION_PROVIDER.to_string(),
+        })
+    }
+
+    fn name(&self) -> &str {
+        SIMULATION_PROVIDER
+    }
+
+    fn supported_pairs(&self) -> Vec<AssetPair> {
+        vec![AssetPair::btc_usd()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(config: SimulationConfig, steps: usize) -> Vec<f64> {
+        let provider = SimulationProvider::new(config).unwrap();
+        (0..steps).map(|_| provider.next_price()).collect()
+    }
+
+    #[test]
+    fn test_same_seed_gives_same_sequence() {
+        let config = SimulationConfig {
+            seed: 42,
+            ..SimulationConfig::default()
+        };
+        let first = path(config.clone(), 100);
+        assert_eq!(first, path(config, 100));
+        assert_eq!(first[0], 65_000.0);
+
+        let other = path(
+            SimulationConfig {
+                seed: 43,
+                ..SimulationConfig::default()
+            },
+            100,
+        );
+        assert_ne!(first, other);
+    }
+
+    #[test]
+    fn test_shock_applied_once_at_offset() {
+        let calm = SimulationConfig {
+            seed: 7,
+            ..SimulationConfig::default()
+        };
+        let shocked = SimulationConfig {
+            shock: Some(ShockConfig {
+                after_secs: 600,
+                pct: -20.0,
+            }),
+            ..calm.clone()
+        };
+        let calm = path(calm, 20);
+        let shocked = path(shocked, 20);
+
+        // 60초 간격이므로 11번째 가격(t=600s)부터 20% 하락이 계속 반영
+        assert_eq!(calm[..10], shocked[..10]);
+        for (calm, shocked) in calm[10..].iter().zip(&shocked[10..]) {
+            assert!((shocked / calm - 0.8).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_prices_stay_positive_and_plausible() {
+        let config = SimulationConfig {
+            seed: 1,
+            volatility: 3.0,
+            drift: -1.0,
+            step_secs: 3_600,
+            ..SimulationConfig::default()
+        };
+        let prices = path(config, 10_000);
+        assert!(prices.iter().all(|price| price.is_finite() && *price > 0.0));
+
+        // 변동성이 없으면 기대 수익률만큼 결정적으로 움직임
+        let prices = path(
+            SimulationConfig {
+                drift: 0.1,
+                volatility: 0.0,
+                step_secs: SECONDS_PER_YEAR as u64,
+                ..SimulationConfig::default()
+            },
+            2,
+        );
+        assert!((prices[1] / prices[0] - 0.1_f64.exp()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_invalid_config_rejected() {
+        for config in [
+            SimulationConfig {
+                initial_price: 0.0,
+                ..SimulationConfig::default()
+            },
+            SimulationConfig {
+                volatility: -0.1,
+                ..SimulationConfig::default()
+            },
+            SimulationConfig {
+                step_secs: 0,
+                ..SimulationConfig::default()
+            },
+            SimulationConfig {
+                shock: Some(ShockConfig {
+                    after_secs: 0,
+                    pct: -100.0,
+                }),
+                ..SimulationConfig::default()
+            },
+        ] {
+            assert!(SimulationProvider::new(config).is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_returns_simulated_price_data() {
+        let provider = SimulationProvider::new(SimulationConfig::default()).unwrap();
+        let data = provider.fetch_btc_price().await.unwrap();
+        assert_eq!(data.source, "simulation");
+        assert_eq!(data.price, Price::from_cents(6_500_000));
+        assert_eq!(provider.name(), "simulation");
+    }
+}
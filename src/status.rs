@@ -0,0 +1,660 @@
+//! 노드 상태 HTTP 엔드포인트 (`/status`, `/healthz`, `/providers`, `/price`, `/metrics`)
+//!
+//! 라운드 루프가 라운드마다 결과를 `NodeStatus`에 기록하고, 주소를 설정했을 때만 띄우는 작은 HTTP
+//! 서버가 이를 JSON으로 보여준다. 서버는 노드 종료 요청을 받으면 함께 멈춘다.
+//! pair 파이프라인은 `for_pair`로 얻은 복제본에 기록하며, 결과는 노드 전체와 pair별로 함께 집계된다.
+//! `/price`는 클라이언트가 마지막으로 받은 네트워크 집계 가격을 라운드와 상관없이 바로 보여준다.
+//! `/metrics`는 노드의 지표 레지스트리(`with_metrics`)를 Prometheus 텍스트 형식으로 내보낸다.
+
+use anyhow::Result;
+use axum::extract::State;
+use axum::http::{header, StatusCode};
+use axum::routing::get;
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+use oracle_vm_common::types::AssetPair;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+use tracing::{error, info};
+
+use crate::divergence::{DivergenceMonitor, DivergenceReport};
+use crate::grpc_client::MultiAggregatorClient;
+use crate::metrics::NodeMetrics;
+use crate::network_price::{AggregateCache, DEFAULT_PRICE_MAX_AGE};
+use crate::offline_queue::OfflineQueue;
+use crate::price_provider::{MultiExchangePriceProvider, ProviderHealth, ProviderStats};
+use crate::round::RoundSummary;
+use crate::scheduler::AdaptiveReport;
+use crate::shutdown::ShutdownSignal;
+
+/// 마지막 성공 라운드가 수집 주기의 몇 배 이내여야 정상인지
+const HEALTHY_INTERVALS: u32 = 2;
+
+/// 거래소별 마지막 수집 결과
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProviderReport {
+    pub name: String,
+    pub pair: String,
+    /// 마지막으로 받은 가격 (USD)
+    pub price: Option<f64>,
+    pub fetched_at: Option<DateTime<Utc>>,
+    /// 마지막 가격을 받은 뒤 지난 시간 (초)
+    pub age_secs: Option<i64>,
+    /// 마지막으로 실패한 시각
+    pub last_failed_at: Option<DateTime<Utc>>,
+    /// 최근 수집 결과로 본 상태
+    pub health: ProviderHealth,
+    /// 시작 이후 수집 통계 (성공/실패 횟수, 지연 시간)
+    pub stats: ProviderStats,
+}
+
+/// `/providers` 응답
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProvidersReport {
+    pub node_id: String,
+    /// 설정한 거래소 (pair, 이름 순)
+    pub providers: Vec<ProviderReport>,
+}
+
+/// 마지막 라운드 결과
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoundReport {
+    pub at: DateTime<Utc>,
+    pub age_secs: i64,
+    pub success: bool,
+    /// 제출한 로컬 중간값 (USD)
+    pub local_price: Option<f64>,
+    /// Aggregator가 돌려준 집계 가격 (USD)
+    pub aggregated_price: Option<f64>,
+    pub error: Option<String>,
+}
+
+/// pair 파이프라인별 결과
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PipelineReport {
+    pub pair: String,
+    pub last_round: Option<RoundReport>,
+    pub last_success_at: Option<DateTime<Utc>>,
+    pub rounds_succeeded: u64,
+    pub rounds_failed: u64,
+    /// 로컬 중간값 대 네트워크 중간값 괴리 (감시기가 설정된 경우)
+    pub divergence: Option<DivergenceReport>,
+    /// 적응형 수집 주기 상태 (켠 경우)
+    pub adaptive: Option<AdaptiveReport>,
+}
+
+/// `/price` 응답 (마지막으로 받은 네트워크 집계 가격)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PriceReport {
+    /// 집계 가격 (USD, 한 번도 받지 못했으면 None)
+    pub price: Option<f64>,
+    /// 고정소수점 집계 가격 (price_scaled × 10^-price_decimals)
+    pub price_scaled: Option<u64>,
+    pub price_decimals: Option<u32>,
+    /// Aggregator가 붙인 시각 (Unix 초)
+    pub timestamp: Option<u64>,
+    /// 노드가 받은 시각
+    pub received_at: Option<DateTime<Utc>>,
+    /// 받은 뒤 지난 시간 (초)
+    pub age_secs: Option<u64>,
+    pub max_age_secs: u64,
+    /// 받지 못했거나 `max_age_secs`보다 오래됨 (503으로 응답)
+    pub stale: bool,
+}
+
+/// Aggregator별 연결 상태
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AggregatorReport {
+    pub url: String,
+    /// idle, connected, disconnected
+    pub state: String,
+}
+
+/// `/status` 응답
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatusReport {
+    pub node_id: String,
+    pub uptime_secs: u64,
+    pub dry_run: bool,
+    /// `/healthz`와 같은 판정
+    pub healthy: bool,
+    pub providers: Vec<ProviderReport>,
+    pub last_round: Option<RoundReport>,
+    pub last_success_at: Option<DateTime<Utc>>,
+    pub offline_queue_depth: usize,
+    pub aggregators: Vec<AggregatorReport>,
+    /// 패닉 후 재시작한 횟수
+    pub restarts: u32,
+    /// pair별 파이프라인 (pair 순)
+    pub pipelines: Vec<PipelineReport>,
+}
+
+#[derive(Debug, Default)]
+struct ProviderState {
+    price: Option<f64>,
+    fetched_at: Option<DateTime<Utc>>,
+    last_failed_at: Option<DateTime<Utc>>,
+    stats: ProviderStats,
+}
+
+#[derive(Debug, Clone)]
+struct RoundRecord {
+    at: DateTime<Utc>,
+    local_price: Option<f64>,
+    aggregated_price: Option<f64>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct PipelineState {
+    last_round: Option<RoundRecord>,
+    last_success_at: Option<DateTime<Utc>>,
+    rounds_succeeded: u64,
+    rounds_failed: u64,
+    divergence: Option<DivergenceReport>,
+    adaptive: Option<AdaptiveReport>,
+}
+
+impl RoundRecord {
+    fn report(&self, now: DateTime<Utc>) -> RoundReport {
+        RoundReport {
+            at: self.at,
+            age_secs: (now - self.at).num_seconds(),
+            success: self.error.is_none(),
+            local_price: self.local_price,
+            aggregated_price: self.aggregated_price,
+            error: self.error.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct StatusState {
+    node_id: String,
+    dry_run: bool,
+    // (pair, 거래소 이름)
+    providers: BTreeMap<(String, String), ProviderState>,
+    last_round: Option<RoundRecord>,
+    last_success: Option<(DateTime<Utc>, Instant)>,
+    queue_depth: usize,
+    aggregators: Vec<AggregatorReport>,
+    restarts: u32,
+    pipelines: BTreeMap<String, PipelineState>,
+    aggregate: AggregateCache,
+}
+
+/// 라운드 루프와 HTTP 서버가 공유하는 노드 상태 (복제본은 같은 상태 공유)
+#[derive(Debug, Clone)]
+pub struct NodeStatus {
+    state: Arc<Mutex<StatusState>>,
+    started: Instant,
+    interval: Duration,
+    pair: AssetPair,
+    price_max_age: Duration,
+    metrics: NodeMetrics,
+}
+
+impl NodeStatus {
+    /// `interval`은 수집 주기 (정상 판정 기준), 라운드는 BTC/USD 파이프라인으로 기록
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            state: Arc::default(),
+            started: Instant::now(),
+            interval,
+            pair: AssetPair::btc_usd(),
+            price_max_age: DEFAULT_PRICE_MAX_AGE,
+            metrics: NodeMetrics::new(),
+        }
+    }
+
+    /// `/price`가 집계 가격을 stale로 보는 나이
+    pub fn with_price_max_age(mut self, price_max_age: Duration) -> Self {
+        self.price_max_age = price_max_age;
+        self
+    }
+
+    /// `/metrics`로 내보낼 지표 (거래소 레지스트리와 클라이언트에 넘긴 것과 같은 레지스트리)
+    pub fn with_metrics(mut self, metrics: NodeMetrics) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    /// 같은 상태에 `pair` 파이프라인의 라운드를 기록하는 복제본
+    pub fn for_pair(&self, pair: AssetPair) -> Self {
+        Self {
+            pair,
+            ..self.clone()
+        }
+    }
+
+    /// 클라이언트의 node_id, 연결 상태, 오프라인 큐 길이, 괴리 감시 상태 기록 (라운드 시작 전에도 호출)
+    ///
+    /// 클라이언트의 집계 가격 캐시도 연결하므로 이후 받는 집계 가격은 바로 `/price`에 나타난다.
+    pub fn record_client(&self, client: &MultiAggregatorClient) {
+        let mut state = self.lock();
+        state.node_id = client.node_id().to_string();
+        state.aggregate = client.aggregate_cache().clone();
+        state.dry_run = client.is_dry_run();
+        state.queue_depth = client.offline_queue().map_or(0, OfflineQueue::len);
+        state.aggregators = client
+            .endpoint_states()
+            .into_iter()
+            .map(|(url, connection)| AggregatorReport {
+                url: url.to_string(),
+                state: connection.to_string(),
+            })
+            .collect();
+        let divergence = client
+            .divergence_monitor(&self.pair)
+            .map(DivergenceMonitor::report);
+        state
+            .pipelines
+            .entry(self.pair.as_str().to_string())
+            .or_default()
+            .divergence = divergence;
+    }
+
+    /// 라운드 결과 기록
+    pub fn record_round(&self, client: &MultiAggregatorClient, result: &Result<RoundSummary>) {
+        self.record_client(client);
+        let now = Utc::now();
+        let mut state = self.lock();
+
+        let record = match result {
+            Ok(summary) => {
+                let pair = self.pair.as_str().to_string();
+                for price in &summary.prices {
+                    let provider = state
+                        .providers
+                        .entry((pair.clone(), price.source.clone()))
+                        .or_default();
+                    provider.price = Some(price.price.to_f64_dollars());
+                    provider.fetched_at = Some(price.timestamp);
+                }
+                for name in &summary.failed {
+                    state
+                        .providers
+                        .entry((pair.clone(), name.clone()))
+                        .or_default()
+                        .last_failed_at = Some(now);
+                }
+                state.last_success = Some((now, Instant::now()));
+                RoundRecord {
+                    at: now,
+                    local_price: Some(summary.local_price.to_f64_dollars()),
+                    aggregated_price: summary.aggregated_price.map(|price| price.to_f64_dollars()),
+                    error: None,
+                }
+            }
+            Err(e) => RoundRecord {
+                at: now,
+                local_price: None,
+                aggregated_price: None,
+                error: Some(format!("{:#}", e)),
+            },
+        };
+        let pipeline = state
+            .pipelines
+            .entry(self.pair.as_str().to_string())
+            .or_default();
+        if record.error.is_none() {
+            pipeline.rounds_succeeded += 1;
+            pipeline.last_success_at = Some(now);
+        } else {
+            pipeline.rounds_failed += 1;
+        }
+        pipeline.last_round = Some(record.clone());
+        state.last_round = Some(record);
+    }
+
+    /// 파이프라인 거래소의 수집 통계 기록 (라운드 시작 전에도 호출해 아직 수집하지 않은 거래소도 노출)
+    pub fn record_providers(&self, provider: &MultiExchangePriceProvider) {
+        let pair = self.pair.as_str().to_string();
+        let mut state = self.lock();
+        for (name, stats) in provider.provider_stats() {
+            state
+                .providers
+                .entry((pair.clone(), name))
+                .or_default()
+                .stats = stats;
+        }
+    }
+
+    /// 파이프라인의 적응형 수집 주기 상태 기록
+    pub fn record_adaptive(&self, report: AdaptiveReport) {
+        self.lock()
+            .pipelines
+            .entry(self.pair.as_str().to_string())
+            .or_default()
+            .adaptive = Some(report);
+    }
+
+    /// 패닉 후 재시작 기록
+    pub fn record_restart(&self) {
+        self.lock().restarts += 1;
+    }
+
+    /// 마지막 성공 라운드가 수집 주기의 2배 이내인지
+    pub fn is_healthy(&self) -> bool {
+        self.lock()
+            .last_success
+            .is_some_and(|(_, at)| at.elapsed() <= self.interval * HEALTHY_INTERVALS)
+    }
+
+    /// 현재 상태
+    pub fn report(&self) -> StatusReport {
+        let healthy = self.is_healthy();
+        let now = Utc::now();
+        let state = self.lock();
+
+        StatusReport {
+            node_id: state.node_id.clone(),
+            uptime_secs: self.started.elapsed().as_secs(),
+            dry_run: state.dry_run,
+            healthy,
+            providers: provider_reports(&state, now),
+            last_round: state.last_round.as_ref().map(|round| round.report(now)),
+            last_success_at: state.last_success.map(|(at, _)| at),
+            offline_queue_depth: state.queue_depth,
+            aggregators: state.aggregators.clone(),
+            restarts: state.restarts,
+            pipelines: state
+                .pipelines
+                .iter()
+                .map(|(pair, pipeline)| PipelineReport {
+                    pair: pair.clone(),
+                    last_round: pipeline.last_round.as_ref().map(|round| round.report(now)),
+                    last_success_at: pipeline.last_success_at,
+                    rounds_succeeded: pipeline.rounds_succeeded,
+                    rounds_failed: pipeline.rounds_failed,
+                    divergence: pipeline.divergence.clone(),
+                    adaptive: pipeline.adaptive.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    /// 거래소별 상태
+    pub fn providers_report(&self) -> ProvidersReport {
+        let state = self.lock();
+        ProvidersReport {
+            node_id: state.node_id.clone(),
+            providers: provider_reports(&state, Utc::now()),
+        }
+    }
+
+    /// 마지막으로 받은 네트워크 집계 가격
+    pub fn price_report(&self) -> PriceReport {
+        let latest = self.lock().aggregate.latest();
+        let age = latest.map(|aggregate| aggregate.age(Utc::now()));
+        let scaled = latest.map(|aggregate| aggregate.price.to_scaled());
+        PriceReport {
+            price: latest.map(|aggregate| aggregate.price.to_f64_dollars()),
+            price_scaled: scaled.map(|(mantissa, _)| mantissa),
+            price_decimals: scaled.map(|(_, decimals)| decimals),
+            timestamp: latest.map(|aggregate| aggregate.timestamp),
+            received_at: latest.map(|aggregate| aggregate.received_at),
+            age_secs: age.map(|age| age.as_secs()),
+            max_age_secs: self.price_max_age.as_secs(),
+            stale: age.is_none_or(|age| age > self.price_max_age),
+        }
+    }
+
+    // 기록 도중 패닉이 나도 상태 조회는 계속 동작
+    fn lock(&self) -> std::sync::MutexGuard<'_, StatusState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn provider_reports(state: &StatusState, now: DateTime<Utc>) -> Vec<ProviderReport> {
+    state
+        .providers
+        .iter()
+        .map(|((pair, name), provider)| ProviderReport {
+            name: name.clone(),
+            pair: pair.clone(),
+            price: provider.price,
+            fetched_at: provider.fetched_at,
+            age_secs: provider.fetched_at.map(|at| (now - at).num_seconds()),
+            last_failed_at: provider.last_failed_at,
+            health: provider.stats.health(),
+            stats: provider.stats.clone(),
+        })
+        .collect()
+}
+
+/// `/status`, `/healthz`, `/providers`, `/price`, `/metrics` 라우터
+pub fn router(status: NodeStatus) -> Router {
+    Router::new()
+        .route("/status", get(status_handler))
+        .route("/healthz", get(healthz_handler))
+        .route("/providers", get(providers_handler))
+        .route("/price", get(price_handler))
+        .route("/metrics", get(metrics_handler))
+        .with_state(status)
+}
+
+async fn status_handler(State(status): State<NodeStatus>) -> Json<StatusReport> {
+    Json(status.report())
+}
+
+async fn providers_handler(State(status): State<NodeStatus>) -> Json<ProvidersReport> {
+    Json(status.providers_report())
+}
+
+// 받지 못했거나 오래된 집계 가격은 본문은 그대로 두고 503으로 응답
+async fn price_handler(State(status): State<NodeStatus>) -> (StatusCode, Json<PriceReport>) {
+    let report = status.price_report();
+    let code = if report.stale {
+        StatusCode::SERVICE_UNAVAILABLE
+    } else {
+        StatusCode::OK
+    };
+    (code, Json(report))
+}
+
+async fn metrics_handler(
+    State(status): State<NodeStatus>,
+) -> (StatusCode, [(header::HeaderName, &'static str); 1], String) {
+    let content_type = [(header::CONTENT_TYPE, "text/plain; version=0.0.4")];
+    match status.metrics.encode() {
+        Ok(text) => (StatusCode::OK, content_type, text),
+        Err(e) => {
+            error!("❌ Failed to encode metrics: {:#}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                content_type,
+                String::new(),
+            )
+        }
+    }
+}
+
+async fn healthz_handler(State(status): State<NodeStatus>) -> (StatusCode, &'static str) {
+    if status.is_healthy() {
+        (StatusCode::OK, "ok")
+    } else {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            "no successful round within 2 intervals",
+        )
+    }
+}
+
+/// 상태 서버 시작 (주소에 바인드한 뒤 종료 요청까지 실행, 실제로 바인드한 주소 반환)
+pub async fn spawn(
+    addr: SocketAddr,
+    status: NodeStatus,
+    mut shutdown: ShutdownSignal,
+) -> Result<(SocketAddr, JoinHandle<()>)> {
+    let listener = TcpListener::bind(addr).await?;
+    let addr = listener.local_addr()?;
+    info!("🩺 Status endpoint at http://{}/status", addr);
+
+    let task = tokio::spawn(async move {
+        let result = axum::serve(listener, router(status))
+            .with_graceful_shutdown(async move { shutdown.wait().await })
+            .await;
+        if let Err(e) = result {
+            error!("❌ Status endpoint failed: {}", e);
+        }
+    });
+    Ok((addr, task))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oracle_vm_common::types::{AssetPair, PriceData};
+    use oracle_vm_common::Price;
+
+    fn summary() -> RoundSummary {
+        RoundSummary {
+            sources: vec!["binance".to_string()],
+            failed: vec!["kraken".to_string()],
+            prices: vec![PriceData {
+                pair: AssetPair::btc_usd(),
+                price: Price::from_cents(7_000_050),
+                timestamp: Utc::now(),
+                volume: None,
+                source: "binance".to_string(),
+            }],
+            local_price: Price::from_cents(7_000_050),
+            spread_pct: 0.0,
+            disputed: false,
+            aggregated_price: None,
+            queued: 0,
+            queue_evicted: 0,
+            dry_run: false,
+        }
+    }
+
+    #[test]
+    fn test_healthy_only_after_recent_success() {
+        let client = MultiAggregatorClient::new(&["http://127.0.0.1:1"])
+            .unwrap()
+            .with_node_id("node-a");
+        let status = NodeStatus::new(Duration::from_millis(50));
+        assert!(!status.is_healthy());
+
+        status.record_round(&client, &Err(anyhow::anyhow!("all exchanges down")));
+        assert!(!status.is_healthy());
+        let report = status.report();
+        assert_eq!(report.node_id, "node-a");
+        assert!(!report.last_round.as_ref().unwrap().success);
+        assert_eq!(report.aggregators[0].state, "idle");
+        assert_eq!(report.pipelines.len(), 1);
+        assert_eq!(report.pipelines[0].pair, "BTC/USD");
+        assert_eq!(report.pipelines[0].rounds_failed, 1);
+        assert!(report.pipelines[0].divergence.is_none());
+
+        status.record_round(&client, &Ok(summary()));
+        assert!(status.is_healthy());
+        let report = status.report();
+        assert_eq!(report.providers.len(), 2);
+        assert_eq!(report.providers[0].name, "binance");
+        assert_eq!(report.providers[0].price, Some(70_000.5));
+        assert!(report.providers[1].price.is_none());
+        assert!(report.providers[1].last_failed_at.is_some());
+
+        // 실패한 라운드가 이어져도 주기의 2배까지는 정상
+        status.record_round(&client, &Err(anyhow::anyhow!("aggregator down")));
+        assert!(status.is_healthy());
+        std::thread::sleep(Duration::from_millis(120));
+        assert!(!status.is_healthy());
+        assert!(status.report().last_success_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_price_endpoint_answers_503_until_fresh() {
+        let client = MultiAggregatorClient::new(&["http://127.0.0.1:1"]).unwrap();
+        let status =
+            NodeStatus::new(Duration::from_secs(60)).with_price_max_age(Duration::from_secs(30));
+        status.record_client(&client);
+        let shutdown = crate::shutdown::Shutdown::new();
+        let (addr, server) = spawn("127.0.0.1:0".parse().unwrap(), status, shutdown.subscribe())
+            .await
+            .unwrap();
+        let price = || async {
+            let response = reqwest::get(format!("http://{}/price", addr))
+                .await
+                .unwrap();
+            let code = response.status().as_u16();
+            (code, response.json::<PriceReport>().await.unwrap())
+        };
+
+        let (code, report) = price().await;
+        assert_eq!(code, 503);
+        assert!(report.stale && report.price.is_none());
+
+        // 최대 나이를 넘긴 값
+        let cache = client.aggregate_cache();
+        let old = Utc::now() - chrono::Duration::seconds(31);
+        cache.record_at(Price::from_cents(7_000_050), 1_700_000_000, old);
+        let (code, report) = price().await;
+        assert_eq!(code, 503);
+        assert!(report.stale);
+        assert_eq!(report.price, Some(70_000.5));
+        assert_eq!(report.age_secs, Some(31));
+        assert_eq!(report.max_age_secs, 30);
+
+        cache.record(Price::from_cents(7_000_100), 1_700_000_060);
+        let (code, report) = price().await;
+        assert_eq!(code, 200);
+        assert!(!report.stale);
+        assert_eq!(report.price, Some(70_001.0));
+        assert_eq!(report.price_scaled, Some(7_000_100));
+        assert_eq!(report.price_decimals, Some(2));
+        assert_eq!(report.timestamp, Some(1_700_000_060));
+
+        shutdown.trigger();
+        server.await.unwrap();
+    }
+
+    #[test]
+    fn test_provider_report_json() {
+        let report = ProviderReport {
+            name: "kraken".to_string(),
+            pair: "BTC/USD".to_string(),
+            price: Some(70_000.5),
+            fetched_at: Some("2026-01-01T00:00:00Z".parse().unwrap()),
+            age_secs: Some(3),
+            last_failed_at: None,
+            health: ProviderHealth::Degraded,
+            stats: ProviderStats {
+                successes: 9,
+                failures: 1,
+                consecutive_failures: 1,
+                last_latency_ms: Some(250),
+                mean_latency_ms: Some(120.5),
+                last_error: Some("timeout".to_string()),
+            },
+        };
+
+        assert_eq!(
+            serde_json::to_value(&report).unwrap(),
+            serde_json::json!({
+                "name": "kraken",
+                "pair": "BTC/USD",
+                "price": 70000.5,
+                "fetched_at": "2026-01-01T00:00:00Z",
+                "age_secs": 3,
+                "last_failed_at": null,
+                "health": "degraded",
+                "stats": {
+                    "successes": 9,
+                    "failures": 1,
+                    "consecutive_failures": 1,
+                    "last_latency_ms": 250,
+                    "mean_latency_ms": 120.5,
+                    "last_error": "timeout"
+                }
+            })
+        );
+    }
+}
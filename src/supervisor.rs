@@ -0,0 +1,144 @@
+//! 패닉한 노드 루프 재시작
+//!
+//! 거래소 응답 파싱 등에서 패닉이 나면 태스크만 조용히 죽고 프로세스는 살아 있어 제출이 멈춘다.
+//! 수집/제출 루프와 하트비트는 패닉을 잡아 기록하고 백오프 후 다시 시작하며, 재시작 한도를 넘으면
+//! 외부 감시자(systemd 등)가 이어받을 수 있도록 0이 아닌 코드로 프로세스를 끝낸다.
+
+use futures::FutureExt;
+use std::any::Any;
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::time::Duration;
+use thiserror::Error;
+use tracing::error;
+
+use crate::backoff::Backoff;
+
+/// 기본 재시작 한도 (루프마다)
+pub const DEFAULT_MAX_RESTARTS: u32 = 5;
+/// 재시작 대기: 1초, 2초, 4초... 최대 1분
+pub const RESTART_BACKOFF: Backoff =
+    Backoff::new(Duration::from_secs(1), Duration::from_secs(60), 0);
+/// 재시작 한도를 넘어 포기할 때의 종료 코드 (EX_SOFTWARE)
+pub const GAVE_UP_EXIT_CODE: i32 = 70;
+
+/// 재시작 한도를 넘음
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{task} panicked after {restarts} restart(s), giving up: {message}")]
+pub struct GaveUp {
+    pub task: String,
+    pub restarts: u32,
+    /// 마지막 패닉 메시지
+    pub message: String,
+}
+
+/// 루프 하나의 재시작 관리
+#[derive(Debug, Clone)]
+pub struct Supervisor {
+    task: String,
+    max_restarts: u32,
+    backoff: Backoff,
+    restarts: u32,
+}
+
+impl Supervisor {
+    pub fn new(task: impl Into<String>, max_restarts: u32) -> Self {
+        Self {
+            task: task.into(),
+            max_restarts,
+            backoff: RESTART_BACKOFF,
+            restarts: 0,
+        }
+    }
+
+    /// 재시작 대기 간격 지정
+    pub fn with_backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    pub fn task(&self) -> &str {
+        &self.task
+    }
+
+    /// 지금까지 재시작한 횟수
+    pub fn restarts(&self) -> u32 {
+        self.restarts
+    }
+
+    /// 패닉을 기록하고 재시작 전에 기다릴 시간 반환 (한도를 넘으면 GaveUp)
+    pub fn on_panic(&mut self, payload: Box<dyn Any + Send>) -> Result<Duration, GaveUp> {
+        let message = panic_message(payload.as_ref());
+        if self.restarts >= self.max_restarts {
+            error!(
+                "💥 {} panicked: {} (restart limit {} reached)",
+                self.task, message, self.max_restarts
+            );
+            return Err(GaveUp {
+                task: self.task.clone(),
+                restarts: self.restarts,
+                message,
+            });
+        }
+
+        let delay = self.backoff.delay(self.restarts);
+        self.restarts += 1;
+        error!(
+            "💥 {} panicked: {} (restart {}/{} in {:?})",
+            self.task, message, self.restarts, self.max_restarts, delay
+        );
+        Ok(delay)
+    }
+}
+
+/// future를 실행하며 패닉을 잡아 반환
+pub async fn catch_panic<F: Future>(future: F) -> Result<F::Output, Box<dyn Any + Send>> {
+    AssertUnwindSafe(future).catch_unwind().await
+}
+
+/// 패닉 값에서 메시지 추출
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "non-string panic payload".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_restarts_with_backoff_until_limit() {
+        let mut supervisor = Supervisor::new("fetch loop", 2).with_backoff(Backoff::new(
+            Duration::from_millis(10),
+            Duration::from_millis(15),
+            0,
+        ));
+
+        let panic = catch_panic(async { panic!("bad response") })
+            .await
+            .unwrap_err();
+        assert_eq!(supervisor.on_panic(panic), Ok(Duration::from_millis(10)));
+        let panic = catch_panic(async { panic!("{} fields", 3) })
+            .await
+            .unwrap_err();
+        assert_eq!(supervisor.on_panic(panic), Ok(Duration::from_millis(15)));
+        assert_eq!(supervisor.restarts(), 2);
+
+        let panic = catch_panic(async { panic!("again") }).await.unwrap_err();
+        assert_eq!(
+            supervisor.on_panic(panic),
+            Err(GaveUp {
+                task: "fetch loop".to_string(),
+                restarts: 2,
+                message: "again".to_string(),
+            })
+        );
+
+        assert_eq!(catch_panic(async { 7 }).await.unwrap(), 7);
+    }
+}
@@ -0,0 +1,84 @@
+//! 분산 추적: OTLP 내보내기와 Aggregator 요청으로의 trace context 전파
+//!
+//! `otlp_endpoint`가 설정되면 라운드, 거래소 수집 span을 OTLP/gRPC로 내보낸다. Aggregator에 보내는
+//! 요청에는 현재 span의 context를 W3C `traceparent` 메타데이터로 붙이므로, Aggregator도 추적을 켜면
+//! 노드에서 시작한 trace가 Aggregator 처리기 안에서 이어진다. 추적이 꺼져 있으면 아무것도 붙이지 않는다.
+
+use anyhow::{Context as _, Result};
+use opentelemetry::propagation::{Injector, TextMapPropagator};
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::TracerProvider;
+use opentelemetry_sdk::{runtime, Resource};
+use tonic::metadata::{MetadataKey, MetadataMap, MetadataValue};
+use tonic::Request;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+/// 내보내는 span의 서비스 이름
+pub const SERVICE_NAME: &str = "oracle-node";
+
+/// `endpoint`(예: http://localhost:4317)로 span을 일괄 전송하는 tracer provider
+///
+/// 종료할 때 `shutdown`을 호출해야 남은 span이 전송된다.
+pub fn otlp_tracer_provider(endpoint: &str) -> Result<TracerProvider> {
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_tonic()
+        .with_endpoint(endpoint)
+        .build()
+        .with_context(|| format!("Invalid OTLP endpoint {:?}", endpoint))?;
+    Ok(TracerProvider::builder()
+        .with_batch_exporter(exporter, runtime::Tokio)
+        .with_resource(Resource::new([KeyValue::new("service.name", SERVICE_NAME)]))
+        .build())
+}
+
+/// 현재 span의 trace context를 `traceparent` 메타데이터로 붙인 gRPC 요청
+pub fn traced_request<T>(message: T) -> Request<T> {
+    let mut request = Request::new(message);
+    let context = tracing::Span::current().context();
+    TraceContextPropagator::new()
+        .inject_context(&context, &mut MetadataInjector(request.metadata_mut()));
+    request
+}
+
+struct MetadataInjector<'a>(&'a mut MetadataMap);
+
+impl Injector for MetadataInjector<'_> {
+    fn set(&mut self, key: &str, value: String) {
+        // 전파 헤더 이름과 값은 항상 ASCII
+        if let (Ok(key), Ok(value)) = (
+            MetadataKey::from_bytes(key.as_bytes()),
+            MetadataValue::try_from(value),
+        ) {
+            self.0.insert(key, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::trace::{TraceContextExt, TracerProvider as _};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn test_traced_request_carries_the_current_span() {
+        assert!(traced_request(()).metadata().get("traceparent").is_none());
+
+        let provider = TracerProvider::builder().build();
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("round");
+            let _entered = span.enter();
+            let request = traced_request(());
+            let traceparent = request.metadata().get("traceparent").unwrap();
+            let trace_id = span.context().span().span_context().trace_id();
+            assert!(traceparent
+                .to_str()
+                .unwrap()
+                .contains(&trace_id.to_string()));
+        });
+    }
+}
@@ -1,5 +1,8 @@
 use anyhow::Result;
+use chrono::DateTime;
 use oracle_node::PriceData;
+use oracle_vm_common::types::AssetPair;
+use oracle_vm_common::Price;
 
 /// 컨센서스 매니저 (TDD를 위한 struct)
 pub struct ConsensusManager {
@@ -17,12 +20,12 @@ impl ConsensusManager {
             return Ok(None); // 최소 3개 필요
         }
 
-        // 가격만 추출하여 정렬
-        let mut sorted_prices: Vec<f64> = prices.iter().map(|p| p.price).collect();
+        // 가격만 추출하여 정렬 (cents -> 달러)
+        let mut sorted_prices: Vec<f64> = prices.iter().map(|p| p.price.to_f64_dollars()).collect();
         sorted_prices.sort_by(|a, b| a.partial_cmp(b).unwrap());
 
         // 중간값 계산
-        let median = if sorted_prices.len() % 2 == 0 {
+        let median = if sorted_prices.len().is_multiple_of(2) {
             let mid = sorted_prices.len() / 2;
             (sorted_prices[mid - 1] + sorted_prices[mid]) / 2.0
         } else {
@@ -40,7 +43,7 @@ impl ConsensusManager {
             .collect();
 
         // 2/3 이상이 동의하는지 확인
-        let required_count = (prices.len() * 2 + 2) / 3; // ceil(2/3)
+        let required_count = (prices.len() * 2).div_ceil(3); // ceil(2/3)
         if valid_prices.len() >= required_count {
             // 유효한 가격들의 평균 반환
             let consensus_price = valid_prices.iter().sum::<f64>() / valid_prices.len() as f64;
@@ -58,11 +61,11 @@ impl ConsensusManager {
 
         let mut sorted_prices: Vec<(String, f64)> = prices
             .iter()
-            .map(|p| (p.source.clone(), p.price))
+            .map(|p| (p.source.clone(), p.price.to_f64_dollars()))
             .collect();
         sorted_prices.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
 
-        let median = if sorted_prices.len() % 2 == 0 {
+        let median = if sorted_prices.len().is_multiple_of(2) {
             let mid = sorted_prices.len() / 2;
             (sorted_prices[mid - 1].1 + sorted_prices[mid].1) / 2.0
         } else {
@@ -86,8 +89,10 @@ mod tests {
 
     fn create_price_data(source: &str, price: f64) -> PriceData {
         PriceData {
-            price,
-            timestamp: 1700000000,
+            pair: AssetPair::btc_usd(),
+            price: Price::from_f64_dollars(price, Price::USD_DECIMALS).unwrap(),
+            timestamp: DateTime::from_timestamp(1700000000, 0).unwrap(),
+            volume: None,
             source: source.to_string(),
         }
     }
@@ -1,9 +1,15 @@
 use anyhow::Result;
 use async_trait::async_trait;
+use chrono::Utc;
 use oracle_node::{PriceData, PriceProvider};
+use oracle_vm_common::types::AssetPair;
+use oracle_vm_common::Price;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
+/// 시뮬레이션된 거래소의 호가 소수 자릿수 (Binance BTC 호가와 같은 8자리)
+const EXCHANGE_DECIMALS: u32 = 8;
+
 /// 시뮬레이션된 거래소 클라이언트
 pub struct SimulatedExchange {
     name: String,
@@ -35,10 +41,10 @@ impl PriceProvider for SimulatedExchange {
         *index += 1;
 
         Ok(PriceData {
-            price,
-            timestamp: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)?
-                .as_secs(),
+            pair: AssetPair::btc_usd(),
+            price: Price::from_f64_dollars(price, EXCHANGE_DECIMALS).unwrap(),
+            timestamp: Utc::now(),
+            volume: None,
             source: self.name.clone(),
         })
     }
@@ -80,10 +86,10 @@ impl OracleSystem {
             return None;
         }
 
-        let mut price_values: Vec<f64> = prices.iter().map(|p| p.price).collect();
+        let mut price_values: Vec<f64> = prices.iter().map(|p| p.price.to_f64_dollars()).collect();
         price_values.sort_by(|a, b| a.partial_cmp(b).unwrap());
 
-        let median = if price_values.len() % 2 == 0 {
+        let median = if price_values.len().is_multiple_of(2) {
             let mid = price_values.len() / 2;
             (price_values[mid - 1] + price_values[mid]) / 2.0
         } else {
@@ -101,7 +107,7 @@ impl OracleSystem {
             .collect();
 
         // 2/3 이상 동의 확인
-        let required_count = (prices.len() * 2 + 2) / 3;
+        let required_count = (prices.len() * 2).div_ceil(3);
         if valid_prices.len() >= required_count {
             Some(valid_prices.iter().sum::<f64>() / valid_prices.len() as f64)
         } else {
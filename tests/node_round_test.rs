@@ -0,0 +1,1383 @@
+//! 노드 라운드를 프로세스 내 Aggregator 서버에 대해 실행하는 통합 테스트
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::Utc;
+use oracle_node::backoff::Backoff;
+use oracle_node::clock_drift::DriftMonitor;
+use oracle_node::grpc_client::{ConnectionState, MultiAggregatorClient};
+use oracle_node::heartbeat::Heartbeat;
+use oracle_node::metrics::NodeMetrics;
+use oracle_node::offline_queue::OfflineQueue;
+use oracle_node::pipeline::{run_pipelines, PairPipeline};
+use oracle_node::price_provider::{MultiExchangePriceProvider, PriceProvider, ProviderHealth};
+use oracle_node::round::{run_round, run_supervised, run_until_shutdown, RoundLoopExit};
+use oracle_node::scheduler::{AdaptiveInterval, RoundPacer};
+use oracle_node::shutdown::Shutdown;
+use oracle_node::status::{self, NodeStatus, PriceReport, ProvidersReport, StatusReport};
+use oracle_node::supervisor::Supervisor;
+use oracle_node::PriceData;
+use oracle_vm_common::jsonl::{read_jsonl, FsyncPolicy, JsonlConfig, JsonlWriter};
+use oracle_vm_common::types::AssetPair;
+use oracle_vm_common::Price;
+
+use aggregator_server::config::{AggregatorConfig, PriceUnit};
+use aggregator_server::oracle::oracle_service_server::OracleServiceServer;
+use aggregator_server::testing::ManualClock;
+use aggregator_server::wal::{self, WalConfig, WalDecision};
+use aggregator_server::AggregatorServiceImpl;
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_sdk::export::trace::SpanData;
+use opentelemetry_sdk::testing::trace::InMemorySpanExporter;
+use opentelemetry_sdk::trace::TracerProvider;
+use oracle_node::offline_queue::QueuedSubmission;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tokio_stream::wrappers::TcpListenerStream;
+use tracing_subscriber::layer::SubscriberExt;
+
+/// 고정 가격(없으면 실패)을 돌려주는 거래소
+struct FixedExchange {
+    name: &'static str,
+    cents: Option<u64>,
+}
+
+#[async_trait]
+impl PriceProvider for FixedExchange {
+    async fn fetch_btc_price(&self) -> Result<PriceData> {
+        let cents = self
+            .cents
+            .ok_or_else(|| anyhow::anyhow!("{} is down", self.name))?;
+        Ok(PriceData {
+            pair: AssetPair::btc_usd(),
+            price: Price::from_cents(cents),
+            timestamp: Utc::now(),
+            volume: None,
+            source: self.name.to_string(),
+        })
+    }
+
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn supported_pairs(&self) -> Vec<AssetPair> {
+        vec![AssetPair::btc_usd()]
+    }
+}
+
+/// 응답하기 전에 `delay`만큼 걸리는 거래소
+struct SlowExchange {
+    delay: Duration,
+    cents: u64,
+}
+
+#[async_trait]
+impl PriceProvider for SlowExchange {
+    async fn fetch_btc_price(&self) -> Result<PriceData> {
+        tokio::time::sleep(self.delay).await;
+        Ok(PriceData {
+            pair: AssetPair::btc_usd(),
+            price: Price::from_cents(self.cents),
+            timestamp: Utc::now(),
+            volume: None,
+            source: "slow".to_string(),
+        })
+    }
+
+    fn name(&self) -> &str {
+        "slow"
+    }
+
+    fn supported_pairs(&self) -> Vec<AssetPair> {
+        vec![AssetPair::btc_usd()]
+    }
+}
+
+/// 가져온 횟수를 세는 거래소
+struct CountingExchange {
+    fetches: Arc<AtomicUsize>,
+    cents: u64,
+}
+
+#[async_trait]
+impl PriceProvider for CountingExchange {
+    async fn fetch_btc_price(&self) -> Result<PriceData> {
+        self.fetches.fetch_add(1, Ordering::SeqCst);
+        Ok(PriceData {
+            pair: AssetPair::btc_usd(),
+            price: Price::from_cents(self.cents),
+            timestamp: Utc::now(),
+            volume: None,
+            source: "counting".to_string(),
+        })
+    }
+
+    fn name(&self) -> &str {
+        "counting"
+    }
+
+    fn supported_pairs(&self) -> Vec<AssetPair> {
+        vec![AssetPair::btc_usd()]
+    }
+}
+
+/// 처음 `failures`번은 실패하고 이후 응답하는 거래소 (가져온 시각 기록)
+struct ScriptedExchange {
+    fetched_at: Arc<Mutex<Vec<Instant>>>,
+    failures: usize,
+}
+
+#[async_trait]
+impl PriceProvider for ScriptedExchange {
+    async fn fetch_btc_price(&self) -> Result<PriceData> {
+        let fetches = {
+            let mut fetched_at = self.fetched_at.lock().unwrap();
+            fetched_at.push(Instant::now());
+            fetched_at.len()
+        };
+        if fetches <= self.failures {
+            anyhow::bail!("scripted outage");
+        }
+        Ok(PriceData {
+            pair: AssetPair::btc_usd(),
+            price: Price::from_cents(7_000_000),
+            timestamp: Utc::now(),
+            volume: None,
+            source: "scripted".to_string(),
+        })
+    }
+
+    fn name(&self) -> &str {
+        "scripted"
+    }
+
+    fn supported_pairs(&self) -> Vec<AssetPair> {
+        vec![AssetPair::btc_usd()]
+    }
+}
+
+/// 처음 `panics`번은 응답 파싱 중 패닉하는 거래소
+struct PanickyExchange {
+    fetches: Arc<AtomicUsize>,
+    panics: usize,
+}
+
+#[async_trait]
+impl PriceProvider for PanickyExchange {
+    async fn fetch_btc_price(&self) -> Result<PriceData> {
+        if self.fetches.fetch_add(1, Ordering::SeqCst) < self.panics {
+            panic!("unexpected response shape");
+        }
+        Ok(PriceData {
+            pair: AssetPair::btc_usd(),
+            price: Price::from_cents(7_000_000),
+            timestamp: Utc::now(),
+            volume: None,
+            source: "panicky".to_string(),
+        })
+    }
+
+    fn name(&self) -> &str {
+        "panicky"
+    }
+
+    fn supported_pairs(&self) -> Vec<AssetPair> {
+        vec![AssetPair::btc_usd()]
+    }
+}
+
+fn panicky_registry(panics: usize) -> (MultiExchangePriceProvider, Arc<AtomicUsize>) {
+    let fetches = Arc::new(AtomicUsize::new(0));
+    let provider = MultiExchangePriceProvider::new(vec![Box::new(PanickyExchange {
+        fetches: fetches.clone(),
+        panics,
+    })]);
+    (provider, fetches)
+}
+
+fn quick_supervisor(max_restarts: u32) -> Supervisor {
+    Supervisor::new("fetch/submit loop", max_restarts).with_backoff(Backoff::new(
+        Duration::from_millis(10),
+        Duration::from_millis(20),
+        0,
+    ))
+}
+
+fn slow_registry(delay: Duration) -> MultiExchangePriceProvider {
+    MultiExchangePriceProvider::new(vec![Box::new(SlowExchange {
+        delay,
+        cents: 7_000_000,
+    })])
+}
+
+fn registry(prices: &[(&'static str, Option<u64>)]) -> MultiExchangePriceProvider {
+    MultiExchangePriceProvider::new(
+        prices
+            .iter()
+            .map(|&(name, cents)| Box::new(FixedExchange { name, cents }) as Box<dyn PriceProvider>)
+            .collect(),
+    )
+}
+
+/// 임의 포트에 Aggregator를 띄우고 URL 반환
+async fn spawn_aggregator() -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(
+        tonic::transport::Server::builder()
+            .add_service(OracleServiceServer::new(AggregatorServiceImpl::new()))
+            .serve_with_incoming(TcpListenerStream::new(listener)),
+    );
+    format!("http://{}", addr)
+}
+
+/// 집계 태스크까지 실행해 게시 결과를 스트림으로 보내는 Aggregator를 띄우고 URL 반환
+async fn spawn_publishing_aggregator() -> String {
+    spawn_publishing_service(AggregatorServiceImpl::new()).await
+}
+
+async fn spawn_publishing_service(service: AggregatorServiceImpl) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    service.spawn_aggregation_task();
+    tokio::spawn(
+        tonic::transport::Server::builder()
+            .add_service(OracleServiceServer::new(service))
+            .serve_with_incoming(TcpListenerStream::new(listener)),
+    );
+    format!("http://{}", addr)
+}
+
+/// 지정한 주소에서 Aggregator 실행 (종료 신호를 보내면 연결을 닫고 멈춤)
+async fn start_aggregator_at(addr: SocketAddr) -> (oneshot::Sender<()>, JoinHandle<()>) {
+    start_service_at(addr, AggregatorServiceImpl::new()).await
+}
+
+async fn start_service_at(
+    addr: SocketAddr,
+    service: AggregatorServiceImpl,
+) -> (oneshot::Sender<()>, JoinHandle<()>) {
+    let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
+    let (stop, stopped) = oneshot::channel::<()>();
+    let server = tokio::spawn(async move {
+        tonic::transport::Server::builder()
+            .add_service(OracleServiceServer::new(service))
+            .serve_with_incoming_shutdown(TcpListenerStream::new(listener), async {
+                let _ = stopped.await;
+            })
+            .await
+            .unwrap();
+    });
+    (stop, server)
+}
+
+// 지금은 아무도 듣지 않는 주소
+fn free_addr() -> SocketAddr {
+    std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_submissions_resume_after_aggregator_restart() {
+    let addr = free_addr();
+    let url = format!("http://{}", addr);
+    let provider = registry(&[("binance", Some(7_000_000))]);
+    let mut client = MultiAggregatorClient::new(&[&url])
+        .unwrap()
+        .with_backoff(Backoff::new(Duration::from_millis(10), Duration::from_millis(50), 3));
+
+    // 노드가 먼저 시작: 아직 Aggregator가 없으므로 이번 라운드만 실패
+    assert!(run_round(&provider, &mut client).await.is_err());
+    assert_eq!(client.connection_state(), ConnectionState::Disconnected);
+
+    // Aggregator가 뜨면 같은 클라이언트로 제출 재개
+    let (stop, server) = start_aggregator_at(addr).await;
+    let summary = run_round(&provider, &mut client).await.unwrap();
+    assert_eq!(summary.aggregated_price, Some(Price::from_cents(7_000_000)));
+    assert_eq!(client.connection_state(), ConnectionState::Connected);
+
+    // 실행 중 Aggregator 종료
+    stop.send(()).unwrap();
+    server.await.unwrap();
+    assert!(run_round(&provider, &mut client).await.is_err());
+    assert_eq!(client.connection_state(), ConnectionState::Disconnected);
+
+    // 재시작 후 수동 조치 없이 제출 재개
+    let (stop, server) = start_aggregator_at(addr).await;
+    let summary = run_round(&provider, &mut client).await.unwrap();
+    assert_eq!(summary.aggregated_price, Some(Price::from_cents(7_000_000)));
+    assert_eq!(client.connection_state(), ConnectionState::Connected);
+
+    stop.send(()).unwrap();
+    server.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_offline_queue_survives_node_restart_and_drains() {
+    let addr = free_addr();
+    let url = format!("http://{}", addr);
+    let queue_path =
+        std::env::temp_dir().join(format!("offline-queue-{}.jsonl", uuid::Uuid::new_v4()));
+    let wal_dir = std::env::temp_dir().join(format!("aggregator-wal-{}", uuid::Uuid::new_v4()));
+    let backoff = Backoff::new(Duration::from_millis(10), Duration::from_millis(50), 1);
+
+    // 장애 중: 노드가 두 라운드를 제출하지 못하고 큐에 보관
+    let first_node_id = {
+        let provider = registry(&[("binance", Some(7_000_000))]);
+        let mut client = MultiAggregatorClient::new(&[&url])
+            .unwrap()
+            .with_backoff(backoff)
+            .with_offline_queue(OfflineQueue::open(&queue_path, 10).unwrap());
+        assert!(run_round(&provider, &mut client).await.is_err());
+        assert!(run_round(&provider, &mut client).await.is_err());
+        assert_eq!(client.offline_queue().unwrap().len(), 2);
+        client.node_id().to_string()
+        // 클라이언트를 버려 노드 프로세스 종료를 흉내냄
+    };
+
+    // 재시작한 노드가 디스크에서 큐를 다시 읽음
+    let queue = OfflineQueue::open(&queue_path, 10).unwrap();
+    assert_eq!(queue.len(), 2);
+    let provider = registry(&[("binance", Some(7_000_100))]);
+    let mut client = MultiAggregatorClient::new(&[&url])
+        .unwrap()
+        .with_backoff(backoff)
+        .with_offline_queue(queue);
+    assert_ne!(client.node_id(), first_node_id);
+
+    // 연결이 돌아오면 실시간 제출 후 보관된 제출을 재전송
+    let (wal, writer) = wal::spawn_writer(WalConfig {
+        dir: wal_dir.clone(),
+        ..WalConfig::default()
+    })
+    .unwrap();
+    let (stop, server) = start_service_at(addr, AggregatorServiceImpl::new().with_wal(wal)).await;
+    let summary = run_round(&provider, &mut client).await.unwrap();
+    // 과거 관측값은 실시간 중간값에 반영되지 않음
+    assert_eq!(summary.aggregated_price, Some(Price::from_cents(7_000_100)));
+    assert_eq!(summary.queued, 0);
+    assert!(OfflineQueue::open(&queue_path, 10).unwrap().is_empty());
+
+    stop.send(()).unwrap();
+    server.await.unwrap();
+    writer.await.unwrap().unwrap();
+
+    let records = wal::read_records(&wal_dir).unwrap();
+    assert_eq!(records.len(), 3);
+    assert_eq!(records[0].decision, WalDecision::Accepted);
+    for record in &records[1..] {
+        assert_eq!(record.decision, WalDecision::Historical);
+        assert!(record.request.historical);
+        assert_eq!(record.request.node_id, first_node_id);
+        assert_eq!(record.request.price_scaled, Some(7_000_000));
+    }
+
+    std::fs::remove_file(queue_path).unwrap();
+    std::fs::remove_dir_all(wal_dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_submission_log_records_every_submission_across_restarts() {
+    let url = spawn_aggregator().await;
+    let log_path =
+        std::env::temp_dir().join(format!("submissions-{}.jsonl", uuid::Uuid::new_v4()));
+    let config = JsonlConfig::new(&log_path).with_fsync(FsyncPolicy::Never);
+
+    let provider = registry(&[("binance", Some(7_000_000))]);
+    let mut client = MultiAggregatorClient::new(&[&url])
+        .unwrap()
+        .with_submission_log(JsonlWriter::open(config.clone()).unwrap());
+    run_round(&provider, &mut client).await.unwrap();
+    let first_node_id = client.node_id().to_string();
+    drop(client);
+
+    // 기록 중에 죽어 마지막 줄이 잘린 채로 재시작
+    let mut torn = std::fs::read(&log_path).unwrap();
+    torn.extend_from_slice(br#"{"node_id":"#);
+    std::fs::write(&log_path, torn).unwrap();
+
+    let provider = registry(&[("binance", Some(7_000_100))]);
+    let mut client = MultiAggregatorClient::new(&[&url])
+        .unwrap()
+        .with_submission_log(JsonlWriter::open(config).unwrap());
+    run_round(&provider, &mut client).await.unwrap();
+
+    let logged: Vec<QueuedSubmission> = read_jsonl(&log_path).unwrap();
+    let prices: Vec<_> = logged
+        .iter()
+        .map(|submission| submission.price_data.price)
+        .collect();
+    assert_eq!(
+        prices,
+        [Price::from_cents(7_000_000), Price::from_cents(7_000_100)]
+    );
+    assert_eq!(logged[0].node_id, first_node_id);
+    assert_eq!(logged[1].node_id, client.node_id());
+
+    std::fs::remove_file(log_path).unwrap();
+}
+
+#[tokio::test]
+async fn test_dry_run_fetches_but_never_submits() {
+    let addr = free_addr();
+    let url = format!("http://{}", addr);
+    let wal_dir = std::env::temp_dir().join(format!("aggregator-wal-{}", uuid::Uuid::new_v4()));
+    let queue_path =
+        std::env::temp_dir().join(format!("offline-queue-{}.jsonl", uuid::Uuid::new_v4()));
+    let (wal, writer) = wal::spawn_writer(WalConfig {
+        dir: wal_dir.clone(),
+        ..WalConfig::default()
+    })
+    .unwrap();
+    let service = AggregatorServiceImpl::new().with_wal(wal);
+    service.spawn_aggregation_task();
+    let (stop, server) = start_service_at(addr, service).await;
+
+    // 다른 노드가 실제로 제출한 네트워크 가격
+    let provider = registry(&[("binance", Some(7_000_000))]);
+    let mut live = MultiAggregatorClient::new(&[&url]).unwrap().with_node_id("live");
+    run_round(&provider, &mut live).await.unwrap();
+
+    // 이전 실행에서 남은 제출이 하나 있는 큐
+    let mut queue = OfflineQueue::open(&queue_path, 10).unwrap();
+    queue
+        .push(QueuedSubmission {
+            node_id: "dry".to_string(),
+            price_data: provider.fetch_median_price().await.unwrap().price,
+        })
+        .unwrap();
+    let queued_file = std::fs::read(&queue_path).unwrap();
+
+    let fetches = Arc::new(AtomicUsize::new(0));
+    let provider = MultiExchangePriceProvider::new(vec![Box::new(CountingExchange {
+        fetches: fetches.clone(),
+        cents: 7_100_000,
+    })]);
+    let mut dry = MultiAggregatorClient::new(&[&url])
+        .unwrap()
+        .with_node_id("dry")
+        .with_streaming(true)
+        .with_dry_run(true)
+        .with_offline_queue(OfflineQueue::open(&queue_path, 10).unwrap().read_only());
+    // 집계 태스크가 게시할 때까지 대기
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while dry.aggregated_price().await.unwrap().is_none() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .unwrap();
+
+    for _ in 0..3 {
+        let summary = run_round(&provider, &mut dry).await.unwrap();
+        assert!(summary.dry_run);
+        assert_eq!(summary.local_price, Price::from_cents(7_100_000));
+        // 네트워크 값은 조회만 하므로 다른 노드의 가격 그대로
+        assert_eq!(summary.aggregated_price, Some(Price::from_cents(7_000_000)));
+        assert_eq!(summary.queued, 1);
+    }
+    assert_eq!(fetches.load(Ordering::SeqCst), 3);
+    dry.close().unwrap();
+    assert_eq!(std::fs::read(&queue_path).unwrap(), queued_file);
+
+    stop.send(()).unwrap();
+    server.await.unwrap();
+    writer.await.unwrap().unwrap();
+
+    // Aggregator에 도착한 제출은 실제 노드의 하나뿐
+    let records = wal::read_records(&wal_dir).unwrap();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].request.node_id, "live");
+
+    std::fs::remove_file(queue_path).unwrap();
+    std::fs::remove_dir_all(wal_dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_round_streams_submissions_and_receives_network_median() {
+    let url = spawn_publishing_aggregator().await;
+    let provider = registry(&[("binance", Some(7_000_000)), ("kraken", Some(7_000_200))]);
+    let mut client = MultiAggregatorClient::new(&[&url])
+        .unwrap()
+        .with_node_id("node-a")
+        .with_streaming(true);
+
+    run_round(&provider, &mut client).await.unwrap();
+    assert!(client.is_streaming());
+
+    // 스트림으로 보낸 로컬 중간값이 집계되어 같은 스트림으로 돌아옴
+    let network = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            if let Some(price) = client.streamed_aggregate() {
+                break price;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .unwrap();
+    assert_eq!(network, Price::from_cents(7_000_100));
+
+    // 다음 라운드도 같은 스트림으로 제출하고 요약에는 스트림으로 받은 네트워크 중간값
+    let summary = run_round(&provider, &mut client).await.unwrap();
+    assert!(client.is_streaming());
+    assert_eq!(summary.aggregated_price, Some(Price::from_cents(7_000_100)));
+}
+
+#[tokio::test]
+async fn test_streamed_median_reads_aggregator_price_unit() {
+    let config = AggregatorConfig::default().with_price_unit(PriceUnit::Cents);
+    let url = spawn_publishing_service(AggregatorServiceImpl::with_config(config)).await;
+    let provider = registry(&[("binance", Some(7_000_000)), ("kraken", Some(7_000_200))]);
+    let mut client = MultiAggregatorClient::new(&[&url])
+        .unwrap()
+        .with_node_id("node-a")
+        .with_streaming(true);
+
+    // Aggregator가 센트 단위로 보내도 노드는 같은 가격으로 읽음
+    run_round(&provider, &mut client).await.unwrap();
+    let network = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            if let Some(price) = client.streamed_aggregate() {
+                break price;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .unwrap();
+    assert_eq!(network, Price::from_cents(7_000_100));
+}
+
+#[tokio::test]
+async fn test_rejected_stream_submission_fails_the_round() {
+    let config = AggregatorConfig::default().with_allowed_nodes(["node-a"]);
+    let url = spawn_publishing_service(AggregatorServiceImpl::with_config(config)).await;
+    let provider = registry(&[("binance", Some(7_000_000))]);
+    let metrics = NodeMetrics::new();
+    let mut client = MultiAggregatorClient::new(&[&url])
+        .unwrap()
+        .with_node_id("node-b")
+        .with_streaming(true)
+        .with_metrics(metrics.clone());
+
+    // 허용 목록에 없는 노드: 거부 사유를 스트림으로 받아 단건 제출처럼 에러로 끝남
+    let error = run_round(&provider, &mut client).await.unwrap_err();
+    assert!(
+        format!("{:#}", error).contains("Aggregator rejected price"),
+        "{:#}",
+        error
+    );
+    // 스트림은 그대로 유지하고 단건으로 다시 보내지 않음
+    assert!(client.is_streaming());
+    let text = metrics.encode().unwrap();
+    assert_eq!(
+        metric_value(&text, r#"oracle_node_submissions_total{code="Ok"}"#),
+        Some(1.0)
+    );
+    assert_eq!(
+        metric_value(
+            &text,
+            r#"oracle_node_last_submitted_price{pair="BTC/USD"}"#
+        ),
+        None
+    );
+}
+
+#[tokio::test]
+async fn test_round_submits_local_median_to_aggregator() {
+    let url = spawn_aggregator().await;
+    let provider = registry(&[
+        ("binance", Some(7_000_000)),
+        ("coinbase", None),
+        ("kraken", Some(7_000_100)),
+    ]);
+
+    let mut node_a = MultiAggregatorClient::new(&[&url]).unwrap().with_node_id("node-a");
+    let summary = run_round(&provider, &mut node_a).await.unwrap();
+
+    assert_eq!(summary.sources, vec!["binance", "kraken"]);
+    assert_eq!(summary.failed, vec!["coinbase"]);
+    assert_eq!(summary.local_price, Price::from_cents(7_000_050));
+    // 참여 노드가 하나뿐이므로 Aggregator 중간값은 로컬 중간값과 같음
+    assert_eq!(summary.aggregated_price, Some(summary.local_price));
+
+    // 두 번째 노드가 더 높은 가격을 보내면 Aggregator 중간값이 두 노드의 평균이 됨
+    let provider_b = registry(&[("binance", Some(7_000_250))]);
+    let mut node_b = MultiAggregatorClient::new(&[&url]).unwrap().with_node_id("node-b");
+    let summary = run_round(&provider_b, &mut node_b).await.unwrap();
+    assert_eq!(summary.aggregated_price, Some(Price::from_cents(7_000_150)));
+}
+
+#[tokio::test]
+async fn test_failed_round_does_not_poison_the_next() {
+    let url = spawn_aggregator().await;
+    let mut client = MultiAggregatorClient::new(&[&url]).unwrap();
+
+    // 모든 거래소 실패 -> 이번 라운드만 실패
+    let down = registry(&[("binance", None), ("kraken", None)]);
+    assert!(run_round(&down, &mut client).await.is_err());
+
+    // 같은 클라이언트(같은 채널)로 다음 라운드는 정상 제출
+    let up = registry(&[("binance", Some(7_000_000))]);
+    let summary = run_round(&up, &mut client).await.unwrap();
+    assert_eq!(summary.aggregated_price, Some(Price::from_cents(7_000_000)));
+}
+
+// 하트비트가 `beats`번째 응답을 받을 때까지 대기
+async fn wait_for_beats(heartbeat: &Heartbeat, beats: u64) {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while heartbeat.beats() < beats {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("heartbeat did not respond in time");
+}
+
+#[tokio::test]
+async fn test_heartbeats_keep_node_active_while_submissions_pause() {
+    let clock = Arc::new(ManualClock::starting_at(
+        Utc::now().timestamp_millis() as u64
+    ));
+    let service = AggregatorServiceImpl::new().with_clock(clock.clone());
+    let addr = free_addr();
+    let url = format!("http://{}", addr);
+    let (stop, server) = start_service_at(addr, service).await;
+
+    // 한 번 제출한 뒤 거래소가 모두 실패해 제출이 멈춤
+    let mut client = MultiAggregatorClient::new(&[&url])
+        .unwrap()
+        .with_node_id("node-a");
+    run_round(&registry(&[("binance", Some(7_000_000))]), &mut client)
+        .await
+        .unwrap();
+    assert!(run_round(&registry(&[("binance", None)]), &mut client)
+        .await
+        .is_err());
+
+    let heartbeat = Heartbeat::spawn(
+        MultiAggregatorClient::new(&[&url])
+            .unwrap()
+            .with_node_id("node-a"),
+        Duration::from_millis(20),
+    );
+    let mut probe = MultiAggregatorClient::new(&[&url])
+        .unwrap()
+        .with_node_id("");
+
+    // 타임아웃(120초)을 넘기는 동안에도 하트비트가 노드를 활성으로 유지
+    for _ in 0..3 {
+        clock.advance(Duration::from_secs(60));
+        let beats = heartbeat.beats();
+        wait_for_beats(&heartbeat, beats + 1).await;
+    }
+    assert_eq!(probe.health().await.unwrap().active_nodes, 1);
+
+    // 하트비트가 멈추면 타임아웃 후 비활성
+    heartbeat.shutdown().await;
+    clock.advance(Duration::from_secs(180));
+    assert_eq!(probe.health().await.unwrap().active_nodes, 0);
+
+    stop.send(()).unwrap();
+    server.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_shutdown_finishes_in_flight_round_and_flushes_queue() {
+    let addr = free_addr();
+    let url = format!("http://{}", addr);
+    let (stop, server) = start_aggregator_at(addr).await;
+    let queue_path =
+        std::env::temp_dir().join(format!("offline-queue-{}.jsonl", uuid::Uuid::new_v4()));
+
+    let shutdown = Shutdown::new();
+    let provider = slow_registry(Duration::from_millis(300));
+    let mut client = MultiAggregatorClient::new(&[&url])
+        .unwrap()
+        .with_node_id("node-a")
+        .with_offline_queue(OfflineQueue::open(&queue_path, 10).unwrap())
+        .with_shutdown(shutdown.subscribe());
+
+    // 거래소 응답을 기다리는 도중에 종료 요청
+    let grace = Duration::from_secs(5);
+    let started = Instant::now();
+    let node_status = NodeStatus::new(Duration::from_secs(60));
+    let (exit, ()) = tokio::join!(
+        run_until_shutdown(
+            &provider,
+            &mut client,
+            || Duration::ZERO,
+            shutdown.subscribe(),
+            grace,
+            &node_status,
+        ),
+        async {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            shutdown.trigger();
+        }
+    );
+
+    // 진행 중이던 라운드는 제출까지 마친 뒤 멈추고 새 라운드는 시작하지 않음
+    assert_eq!(
+        exit,
+        RoundLoopExit {
+            rounds: 1,
+            abandoned: false
+        }
+    );
+    assert!(started.elapsed() < grace);
+    assert_eq!(client.connection_state(), ConnectionState::Idle);
+    assert!(queue_path.exists());
+
+    let mut probe = MultiAggregatorClient::new(&[&url])
+        .unwrap()
+        .with_node_id("");
+    assert_eq!(probe.health().await.unwrap().active_nodes, 1);
+
+    stop.send(()).unwrap();
+    server.await.unwrap();
+    std::fs::remove_file(queue_path).unwrap();
+}
+
+#[tokio::test]
+async fn test_shutdown_abandons_round_that_outlasts_timeout() {
+    let url = spawn_aggregator().await;
+    let shutdown = Shutdown::new();
+    let provider = slow_registry(Duration::from_secs(60));
+    let mut client = MultiAggregatorClient::new(&[&url]).unwrap();
+
+    let started = Instant::now();
+    let node_status = NodeStatus::new(Duration::from_secs(60));
+    let (exit, ()) = tokio::join!(
+        run_until_shutdown(
+            &provider,
+            &mut client,
+            || Duration::ZERO,
+            shutdown.subscribe(),
+            Duration::from_millis(200),
+            &node_status,
+        ),
+        async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            shutdown.trigger();
+        }
+    );
+
+    assert_eq!(
+        exit,
+        RoundLoopExit {
+            rounds: 0,
+            abandoned: true
+        }
+    );
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[tokio::test]
+async fn test_price_endpoint_serves_cached_network_aggregate() {
+    let url = spawn_aggregator().await;
+    let shutdown = Shutdown::new();
+    let provider = registry(&[("binance", Some(7_000_000))]);
+    let mut client = MultiAggregatorClient::new(&[&url]).unwrap();
+
+    let node_status = NodeStatus::new(Duration::from_secs(60));
+    node_status.record_client(&client);
+    let price = |node_status: NodeStatus| {
+        let shutdown = shutdown.subscribe();
+        async move {
+            let (addr, _) = status::spawn("127.0.0.1:0".parse().unwrap(), node_status, shutdown)
+                .await
+                .unwrap();
+            let response = reqwest::get(format!("http://{}/price", addr))
+                .await
+                .unwrap();
+            let code = response.status().as_u16();
+            (code, response.json::<PriceReport>().await.unwrap())
+        }
+    };
+
+    // 아직 집계 가격을 받지 못함
+    let (code, report) = price(node_status.clone()).await;
+    assert_eq!(code, 503);
+    assert!(report.stale);
+    assert_eq!(report.price, None);
+    assert_eq!(report.age_secs, None);
+
+    run_round(&provider, &mut client).await.unwrap();
+
+    // 제출 응답의 집계 가격을 라운드 기록 없이 바로 제공
+    let (code, report) = price(node_status.clone()).await;
+    assert_eq!(code, 200);
+    assert!(!report.stale);
+    assert_eq!(report.price, Some(70_000.0));
+    assert_eq!(
+        report
+            .price_scaled
+            .zip(report.price_decimals)
+            .map(|(scaled, decimals)| Price::from_scaled(scaled, decimals).unwrap()),
+        Some(Price::from_cents(7_000_000))
+    );
+    assert_eq!(report.age_secs, Some(0));
+    assert_eq!(report.max_age_secs, 120);
+    assert!(report.timestamp.is_some());
+
+    // 최대 나이를 넘으면 마지막 값을 보여 주되 503
+    let (code, report) = price(node_status.with_price_max_age(Duration::ZERO)).await;
+    assert_eq!(code, 503);
+    assert!(report.stale);
+    assert_eq!(report.price, Some(70_000.0));
+    shutdown.trigger();
+}
+
+/// Prometheus 텍스트에서 `series`(이름과 레이블) 값 찾기
+fn metric_value(text: &str, series: &str) -> Option<f64> {
+    text.lines()
+        .find_map(|line| line.strip_prefix(series)?.strip_prefix(' '))
+        .map(|value| value.parse().unwrap())
+}
+
+#[tokio::test]
+async fn test_metrics_endpoint_exports_round_metrics() {
+    let addr = free_addr();
+    let url = format!("http://{}", addr);
+    let queue_path =
+        std::env::temp_dir().join(format!("offline-queue-{}.jsonl", uuid::Uuid::new_v4()));
+    let metrics = NodeMetrics::new();
+    let provider =
+        registry(&[("binance", Some(7_000_000)), ("kraken", None)]).with_metrics(metrics.clone());
+    let mut client = MultiAggregatorClient::new(&[&url])
+        .unwrap()
+        .with_backoff(Backoff::new(
+            Duration::from_millis(10),
+            Duration::from_millis(50),
+            1,
+        ))
+        .with_offline_queue(OfflineQueue::open(&queue_path, 10).unwrap())
+        .with_drift_monitor(DriftMonitor::new(
+            Duration::from_secs(5),
+            Duration::from_secs(30),
+        ))
+        .with_metrics(metrics.clone());
+
+    let shutdown = Shutdown::new();
+    let node_status = NodeStatus::new(Duration::from_secs(60)).with_metrics(metrics);
+    let (status_addr, server) = status::spawn(
+        "127.0.0.1:0".parse().unwrap(),
+        node_status,
+        shutdown.subscribe(),
+    )
+    .await
+    .unwrap();
+    let scrape = || async {
+        let response = reqwest::get(format!("http://{}/metrics", status_addr))
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+        assert!(response.headers()["content-type"]
+            .to_str()
+            .unwrap()
+            .starts_with("text/plain"));
+        response.text().await.unwrap()
+    };
+
+    // Aggregator가 없는 첫 라운드는 큐에 보관
+    assert!(run_round(&provider, &mut client).await.is_err());
+    let text = scrape().await;
+    assert_eq!(
+        metric_value(&text, "oracle_node_offline_queue_depth"),
+        Some(1.0)
+    );
+    assert_eq!(
+        metric_value(
+            &text,
+            r#"oracle_node_submissions_total{code="Unavailable"}"#
+        ),
+        Some(1.0)
+    );
+
+    // 연결되면 두 라운드 제출과 보관된 제출 재전송
+    let (stop, aggregator) = start_aggregator_at(addr).await;
+    run_round(&provider, &mut client).await.unwrap();
+    run_round(&provider, &mut client).await.unwrap();
+
+    let text = scrape().await;
+    let value = |series: &str| {
+        metric_value(&text, series).unwrap_or_else(|| panic!("missing {} in\n{}", series, text))
+    };
+    assert_eq!(
+        value(
+            r#"oracle_node_provider_fetches_total{pair="BTC/USD",provider="binance",result="success"}"#
+        ),
+        3.0
+    );
+    assert_eq!(
+        value(
+            r#"oracle_node_provider_fetches_total{pair="BTC/USD",provider="kraken",result="failure"}"#
+        ),
+        3.0
+    );
+    assert_eq!(
+        value(
+            r#"oracle_node_provider_fetch_duration_seconds_count{pair="BTC/USD",provider="binance"}"#
+        ),
+        3.0
+    );
+    let fetch_secs = value(
+        r#"oracle_node_provider_fetch_duration_seconds_sum{pair="BTC/USD",provider="binance"}"#,
+    );
+    assert!((0.0..1.0).contains(&fetch_secs));
+    assert_eq!(value(r#"oracle_node_submissions_total{code="Ok"}"#), 3.0);
+    assert_eq!(
+        value(r#"oracle_node_submissions_total{code="Unavailable"}"#),
+        1.0
+    );
+    assert_eq!(value("oracle_node_offline_queue_depth"), 0.0);
+    assert_eq!(
+        value(r#"oracle_node_last_submitted_price{pair="BTC/USD"}"#),
+        70_000.0
+    );
+    assert_eq!(value(r#"oracle_node_divergence_bps{pair="BTC/USD"}"#), 0.0);
+    assert!(value("oracle_node_clock_drift_seconds").abs() < 5.0);
+    assert!(value("oracle_node_uptime_seconds") >= 0.0);
+
+    shutdown.trigger();
+    server.await.unwrap();
+    stop.send(()).unwrap();
+    aggregator.await.unwrap();
+    let _ = std::fs::remove_file(&queue_path);
+}
+
+#[tokio::test]
+async fn test_status_endpoint_reports_rounds_and_stops_with_node() {
+    let url = spawn_aggregator().await;
+    let shutdown = Shutdown::new();
+    let provider = registry(&[("binance", Some(7_000_000)), ("kraken", None)]);
+    let mut client = MultiAggregatorClient::new(&[&url])
+        .unwrap()
+        .with_node_id("node-a");
+
+    let node_status = NodeStatus::new(Duration::from_secs(60));
+    let (addr, server) = status::spawn(
+        "127.0.0.1:0".parse().unwrap(),
+        node_status.clone(),
+        shutdown.subscribe(),
+    )
+    .await
+    .unwrap();
+    let base = format!("http://{}", addr);
+
+    // 아직 성공한 라운드가 없으므로 비정상
+    let healthz = reqwest::get(format!("{}/healthz", base)).await.unwrap();
+    assert_eq!(healthz.status().as_u16(), 503);
+
+    // 첫 라운드는 바로, 이후에는 종료 요청까지 대기
+    let mut waits = 0;
+    let next_wait = || {
+        waits += 1;
+        if waits == 1 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs(3600)
+        }
+    };
+    let (exit, report) = tokio::join!(
+        run_until_shutdown(
+            &provider,
+            &mut client,
+            next_wait,
+            shutdown.subscribe(),
+            Duration::from_secs(5),
+            &node_status,
+        ),
+        async {
+            let report = tokio::time::timeout(Duration::from_secs(5), async {
+                loop {
+                    let report: StatusReport = reqwest::get(format!("{}/status", base))
+                        .await
+                        .unwrap()
+                        .json()
+                        .await
+                        .unwrap();
+                    if report.last_round.is_some() {
+                        return report;
+                    }
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                }
+            })
+            .await
+            .unwrap();
+            let healthz = reqwest::get(format!("{}/healthz", base)).await.unwrap();
+            assert_eq!(healthz.status().as_u16(), 200);
+            let providers: ProvidersReport = reqwest::get(format!("{}/providers", base))
+                .await
+                .unwrap()
+                .json()
+                .await
+                .unwrap();
+            assert_eq!(providers.node_id, "node-a");
+            assert_eq!(providers.providers.len(), 2);
+            assert_eq!(providers.providers[0].health, ProviderHealth::Healthy);
+            assert_eq!(providers.providers[0].stats.successes, 1);
+            assert!(providers.providers[0].stats.last_latency_ms.is_some());
+            assert_eq!(providers.providers[1].name, "kraken");
+            assert_eq!(providers.providers[1].health, ProviderHealth::Degraded);
+            assert_eq!(providers.providers[1].stats.failures, 1);
+            shutdown.trigger();
+            report
+        }
+    );
+    assert_eq!(exit.rounds, 1);
+
+    assert_eq!(report.node_id, "node-a");
+    assert!(report.healthy);
+    let round = report.last_round.unwrap();
+    assert!(round.success);
+    assert_eq!(round.local_price, Some(70_000.0));
+    assert_eq!(report.providers.len(), 2);
+    assert_eq!(report.providers[0].name, "binance");
+    assert_eq!(report.providers[0].price, Some(70_000.0));
+    assert!(report.providers[0].age_secs.unwrap() <= 1);
+    assert!(report.providers[1].last_failed_at.is_some());
+    assert_eq!(report.offline_queue_depth, 0);
+    assert_eq!(report.aggregators.len(), 1);
+    assert_eq!(report.aggregators[0].url, url);
+    assert_eq!(report.aggregators[0].state, "connected");
+
+    // 노드 종료와 함께 상태 서버도 멈춤
+    tokio::time::timeout(Duration::from_secs(5), server)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(reqwest::get(format!("{}/status", base)).await.is_err());
+}
+
+#[tokio::test]
+async fn test_supervised_loop_restarts_after_provider_panic() {
+    let url = spawn_aggregator().await;
+    let shutdown = Shutdown::new();
+    let (provider, fetches) = panicky_registry(2);
+    let mut client = MultiAggregatorClient::new(&[&url])
+        .unwrap()
+        .with_node_id("node-a");
+    let node_status = NodeStatus::new(Duration::from_secs(60));
+    let mut supervisor = quick_supervisor(3);
+
+    // 두 번 패닉한 뒤에도 루프가 다시 시작되어 계속 제출
+    let (result, ()) = tokio::join!(
+        run_supervised(
+            &provider,
+            &mut client,
+            || Duration::from_millis(10),
+            shutdown.subscribe(),
+            Duration::from_secs(5),
+            &node_status,
+            &mut supervisor,
+        ),
+        async {
+            tokio::time::timeout(Duration::from_secs(5), async {
+                while fetches.load(Ordering::SeqCst) < 5 {
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                }
+            })
+            .await
+            .unwrap();
+            shutdown.trigger();
+        }
+    );
+
+    let exit = result.unwrap();
+    assert!(exit.rounds >= 4);
+    assert!(!exit.abandoned);
+    assert_eq!(supervisor.restarts(), 2);
+    let report = node_status.report();
+    assert_eq!(report.restarts, 2);
+    assert!(report.last_round.unwrap().success);
+
+    let mut probe = MultiAggregatorClient::new(&[&url])
+        .unwrap()
+        .with_node_id("");
+    assert_eq!(probe.health().await.unwrap().active_nodes, 1);
+}
+
+#[tokio::test]
+async fn test_supervised_loop_gives_up_after_restart_limit() {
+    let url = spawn_aggregator().await;
+    let (provider, fetches) = panicky_registry(usize::MAX);
+    let mut client = MultiAggregatorClient::new(&[&url]).unwrap();
+    let mut supervisor = quick_supervisor(2);
+
+    let gave_up = run_supervised(
+        &provider,
+        &mut client,
+        || Duration::ZERO,
+        Shutdown::new().subscribe(),
+        Duration::from_secs(5),
+        &NodeStatus::new(Duration::from_secs(60)),
+        &mut supervisor,
+    )
+    .await
+    .unwrap_err();
+
+    assert_eq!(gave_up.restarts, 2);
+    assert_eq!(gave_up.message, "unexpected response shape");
+    assert_eq!(fetches.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_failing_pair_pipeline_does_not_affect_other_pairs() {
+    let url = spawn_aggregator().await;
+    let shutdown = Shutdown::new();
+    let mut client = MultiAggregatorClient::new(&[&url])
+        .unwrap()
+        .with_node_id("node-a");
+    let node_status = NodeStatus::new(Duration::from_secs(60));
+
+    // BTC만 제공하는 거래소라 ETH 파이프라인은 매 라운드 실패
+    let eth = AssetPair("ETH/USD".to_string());
+    let pipeline = |provider: MultiExchangePriceProvider| PairPipeline {
+        provider,
+        pacer: RoundPacer::new(Box::new(|| Duration::from_millis(10))),
+        supervisor: quick_supervisor(0),
+    };
+    let pipelines = vec![
+        pipeline(registry(&[("binance", Some(7_000_000))])),
+        pipeline(registry(&[("coinbase", Some(350_000))]).with_pair(eth.clone())),
+    ];
+
+    let rounds = |pair: &str, succeeded: bool| {
+        node_status
+            .report()
+            .pipelines
+            .iter()
+            .find(|pipeline| pipeline.pair == pair)
+            .map_or(0, |pipeline| {
+                if succeeded {
+                    pipeline.rounds_succeeded
+                } else {
+                    pipeline.rounds_failed
+                }
+            })
+    };
+    let (result, ()) = tokio::join!(
+        run_pipelines(
+            pipelines,
+            &mut client,
+            shutdown.subscribe(),
+            Duration::from_secs(5),
+            &node_status,
+        ),
+        async {
+            tokio::time::timeout(Duration::from_secs(5), async {
+                while rounds("BTC/USD", true) < 3 || rounds("ETH/USD", false) < 3 {
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                }
+            })
+            .await
+            .unwrap();
+            shutdown.trigger();
+        }
+    );
+
+    let exit = result.unwrap();
+    assert!(exit.rounds >= 6);
+    let report = node_status.report();
+    assert!(report.healthy);
+    assert_eq!(report.restarts, 0);
+
+    let [btc, eth_report] = &report.pipelines[..] else {
+        panic!("expected two pipelines: {:?}", report.pipelines);
+    };
+    assert_eq!(btc.pair, "BTC/USD");
+    assert_eq!(btc.rounds_failed, 0);
+    let last = btc.last_round.as_ref().unwrap();
+    assert!(last.success);
+    assert_eq!(last.aggregated_price, Some(70_000.0));
+
+    assert_eq!(eth_report.pair, eth.as_str());
+    assert_eq!(eth_report.rounds_succeeded, 0);
+    assert!(eth_report.last_success_at.is_none());
+    assert!(eth_report
+        .last_round
+        .as_ref()
+        .unwrap()
+        .error
+        .as_deref()
+        .unwrap()
+        .contains("No provider returned a price"));
+
+    // 거래소 상태는 pair별로 기록 (ETH는 가격을 받은 적 없음)
+    assert_eq!(report.providers.len(), 2);
+    assert_eq!(report.providers[0].name, "binance");
+    assert_eq!(report.providers[0].pair, "BTC/USD");
+    assert_eq!(report.providers[0].health, ProviderHealth::Healthy);
+    assert_eq!(report.providers[1].pair, eth.as_str());
+    assert!(report.providers[1].price.is_none());
+    assert_eq!(report.providers[1].stats.successes, 0);
+    assert_ne!(report.providers[1].health, ProviderHealth::Healthy);
+
+    let mut probe = MultiAggregatorClient::new(&[&url])
+        .unwrap()
+        .with_node_id("");
+    assert_eq!(probe.health().await.unwrap().active_nodes, 1);
+}
+
+#[tokio::test]
+async fn test_adaptive_interval_stretches_caps_and_recovers() {
+    let url = spawn_aggregator().await;
+    let shutdown = Shutdown::new();
+    let mut client = MultiAggregatorClient::new(&[&url])
+        .unwrap()
+        .with_node_id("node-a");
+    let node_status = NodeStatus::new(Duration::from_secs(60));
+
+    // 20ms 주기, 2번 연속 모두 실패하면 늘리고 최대 80ms (4배)
+    let base = Duration::from_millis(20);
+    let fetched_at = Arc::new(Mutex::new(Vec::new()));
+    let provider = MultiExchangePriceProvider::new(vec![Box::new(ScriptedExchange {
+        fetched_at: fetched_at.clone(),
+        failures: 5,
+    })]);
+    let pipeline = PairPipeline {
+        provider,
+        pacer: RoundPacer::new(Box::new(move || base)),
+        supervisor: quick_supervisor(0),
+    }
+    .with_adaptive_interval(AdaptiveInterval::new(base, 2, base * 4));
+
+    let adaptive = || {
+        node_status.report().pipelines[0]
+            .adaptive
+            .clone()
+            .expect("adaptive interval is enabled")
+    };
+    let (result, degraded) = tokio::join!(
+        run_pipelines(
+            vec![pipeline],
+            &mut client,
+            shutdown.subscribe(),
+            Duration::from_secs(5),
+            &node_status,
+        ),
+        async {
+            let mut degraded = false;
+            tokio::time::timeout(Duration::from_secs(5), async {
+                while fetched_at.lock().unwrap().len() < 7 {
+                    degraded |= adaptive().degraded;
+                    tokio::time::sleep(Duration::from_millis(5)).await;
+                }
+            })
+            .await
+            .unwrap();
+            shutdown.trigger();
+            degraded
+        }
+    );
+    result.unwrap();
+    assert!(degraded);
+
+    let fetched_at = fetched_at.lock().unwrap();
+    let gaps: Vec<Duration> = fetched_at.windows(2).map(|w| w[1] - w[0]).collect();
+    // 실패 1, 2번째 뒤: 2배, 3번째 뒤: 4배, 4번째 뒤: 최대값에서 멈춤
+    assert!(gaps[1] >= base * 2, "{:?}", gaps);
+    assert!(gaps[2] >= base * 4, "{:?}", gaps);
+    assert!(gaps[3] >= base * 4 && gaps[3] < base * 8, "{:?}", gaps);
+    // 6번째 라운드에 성공하면 바로 원래 주기
+    assert!(gaps[5] < base * 4, "{:?}", gaps);
+
+    let adaptive = adaptive();
+    assert!(!adaptive.degraded);
+    assert_eq!(adaptive.consecutive_failures, 0);
+    assert_eq!(adaptive.after_failures, 2);
+    let pipeline = &node_status.report().pipelines[0];
+    assert_eq!(pipeline.rounds_failed, 5);
+    assert!(pipeline.rounds_succeeded >= 1);
+}
+
+// span 속성 `key`의 값 (문자열로)
+fn span_attribute(span: &SpanData, key: &str) -> Option<String> {
+    span.attributes
+        .iter()
+        .find(|attribute| attribute.key.as_str() == key)
+        .map(|attribute| attribute.value.as_str().into_owned())
+}
+
+#[tokio::test]
+async fn test_round_trace_continues_into_aggregator_submit() {
+    // 노드와 Aggregator가 한 프로세스에 있으므로 같은 exporter에 모임 (서버 태스크도 이 스레드에서 실행)
+    let exporter = InMemorySpanExporter::default();
+    let provider = TracerProvider::builder()
+        .with_simple_exporter(exporter.clone())
+        .build();
+    let subscriber = tracing_subscriber::registry()
+        .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+    let _default = tracing::subscriber::set_default(subscriber);
+
+    let url = spawn_aggregator().await;
+    let provider_registry = registry(&[("binance", Some(7_000_000))]);
+    let mut client = MultiAggregatorClient::new(&[&url]).unwrap().with_node_id("node-a");
+    run_round(&provider_registry, &mut client).await.unwrap();
+    provider.force_flush();
+
+    let spans = exporter.get_finished_spans().unwrap();
+    let find = |name: &str| {
+        spans
+            .iter()
+            .find(|span| span.name == name)
+            .unwrap_or_else(|| panic!("no {} span in {:?}", name, spans))
+    };
+    let round = find("round");
+    let fetch = find("fetch_price");
+    let submit = find("submit_price");
+
+    // 거래소 수집은 라운드 안에서, Aggregator 처리는 gRPC 경계를 넘어 같은 trace에서 이어짐
+    let trace_id = round.span_context.trace_id();
+    assert_eq!(fetch.parent_span_id, round.span_context.span_id());
+    assert_eq!(submit.span_context.trace_id(), trace_id);
+    assert_eq!(submit.parent_span_id, round.span_context.span_id());
+
+    assert_eq!(span_attribute(round, "node_id").as_deref(), Some("node-a"));
+    assert_eq!(span_attribute(round, "pair").as_deref(), Some("BTC/USD"));
+    assert_eq!(span_attribute(round, "outcome").as_deref(), Some("submitted"));
+    assert_eq!(span_attribute(fetch, "provider").as_deref(), Some("binance"));
+    assert_eq!(span_attribute(fetch, "outcome").as_deref(), Some("success"));
+    assert_eq!(span_attribute(submit, "node_id").as_deref(), Some("node-a"));
+    assert_eq!(span_attribute(submit, "pair").as_deref(), Some("BTC/USD"));
+    assert_eq!(span_attribute(submit, "outcome").as_deref(), Some("accepted"));
+}
+
+#[tokio::test]
+async fn test_node_registers_and_learns_aggregator_rules() {
+    let config = AggregatorConfig::default()
+        .with_allowed_nodes(["node-a", "node-b"])
+        .with_publish_interval_secs(10)
+        .with_skew_reject_secs(30);
+    let url = spawn_publishing_service(AggregatorServiceImpl::with_config(config)).await;
+    let sources = vec!["binance".to_string(), "kraken".to_string()];
+
+    let mut client = MultiAggregatorClient::new(&[&url])
+        .unwrap()
+        .with_node_id("node-a");
+    let rules = client.register(&sources).await.unwrap().unwrap();
+    assert_eq!(rules.window_secs, 60);
+    assert_eq!(rules.min_quorum_nodes, 3);
+    assert_eq!(rules.allowed_nodes, ["node-a", "node-b"]);
+    assert_eq!(rules.aggregation_mode, "arithmetic");
+    assert_eq!(rules.strategy, "median");
+    assert_eq!(rules.publish_interval_secs, Some(10));
+    assert_eq!(rules.skew_reject_secs, Some(30));
+
+    // 허용 목록에 없는 노드는 시작할 때 바로 알 수 있음
+    let mut outsider = MultiAggregatorClient::new(&[&url])
+        .unwrap()
+        .with_node_id("node-z");
+    let error = outsider.register(&sources).await.unwrap_err();
+    assert!(error.to_string().contains("allowed_nodes"), "{}", error);
+}
@@ -1,7 +1,10 @@
 use anyhow::Result;
 use async_trait::async_trait;
+use chrono::DateTime;
 use mockall::{automock, predicate::*};
 use oracle_node::PriceData;
+use oracle_vm_common::types::AssetPair;
+use oracle_vm_common::Price;
 
 // MockPriceProvider를 위한 trait
 #[automock]
@@ -10,6 +13,27 @@ trait MockablePriceProvider: Send + Sync {
     async fn fetch_price(&self, symbol: &str) -> Result<PriceData>;
 }
 
+/// 가격 유효성 검증 (거래소에서 받은 달러 가격, cents 변환 전)
+fn is_valid_price(price: f64) -> bool {
+    price > 0.0 && price < 10_000_000.0 // 0 < price < $10M
+}
+
+/// 타임스탬프 유효성 검증
+fn is_valid_timestamp(data: &PriceData) -> bool {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    
+    let one_hour = 3600;
+    let min_timestamp = 1600000000; // 2020-09-13 (reasonable minimum)
+    
+    let timestamp = data.timestamp.timestamp() as u64;
+    timestamp >= min_timestamp && 
+    timestamp <= now + 60 && // Allow 1 minute clock drift
+    timestamp >= now - one_hour // Not older than 1 hour
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -20,8 +44,10 @@ mod tests {
         let mut mock_provider = MockMockablePriceProvider::new();
         
         let expected_price = PriceData {
-            price: 70000.0,
-            timestamp: 1700000000,
+            pair: AssetPair::btc_usd(),
+            price: Price::from_cents(7000000), // $70,000.00 in cents
+            timestamp: DateTime::from_timestamp(1700000000, 0).unwrap(),
+            volume: None,
             source: "mock".to_string(),
         };
         
@@ -37,8 +63,8 @@ mod tests {
         // Then - 올바른 가격 데이터 반환 확인
         assert!(result.is_ok());
         let price_data = result.unwrap();
-        assert_eq!(price_data.price, 70000.0);
-        assert_eq!(price_data.timestamp, 1700000000);
+        assert_eq!(price_data.price, Price::from_cents(7000000));
+        assert_eq!(price_data.timestamp.timestamp(), 1700000000);
         assert_eq!(price_data.source, "mock");
     }
 
@@ -64,40 +90,28 @@ mod tests {
     #[tokio::test]
     async fn test_price_validation_rejects_negative_price() {
         // Given - 음수 가격
-        let price_data = PriceData {
-            price: -100.0,
-            timestamp: 1700000000,
-            source: "test".to_string(),
-        };
+        let price = -100.0;
 
-        // When & Then - 가격 검증
-        assert!(!is_valid_price(&price_data));
+        // When & Then - 가격 검증 (cents 변환 전 거래소 원본 값)
+        assert!(!is_valid_price(price));
     }
 
     #[tokio::test]
     async fn test_price_validation_rejects_zero_price() {
         // Given - 0 가격
-        let price_data = PriceData {
-            price: 0.0,
-            timestamp: 1700000000,
-            source: "test".to_string(),
-        };
+        let price = 0.0;
 
-        // When & Then - 가격 검증
-        assert!(!is_valid_price(&price_data));
+        // When & Then - 가격 검증 (cents 변환 전 거래소 원본 값)
+        assert!(!is_valid_price(price));
     }
 
     #[tokio::test]
     async fn test_price_validation_rejects_excessive_price() {
         // Given - 비현실적으로 높은 가격 (1억 달러)
-        let price_data = PriceData {
-            price: 100_000_000.0,
-            timestamp: 1700000000,
-            source: "test".to_string(),
-        };
+        let price = 100_000_000.0;
 
-        // When & Then - 가격 검증
-        assert!(!is_valid_price(&price_data));
+        // When & Then - 가격 검증 (cents 변환 전 거래소 원본 값)
+        assert!(!is_valid_price(price));
     }
 
     #[tokio::test]
@@ -106,14 +120,8 @@ mod tests {
         let test_cases = vec![1000.0, 50000.0, 100000.0, 500000.0];
 
         for price in test_cases {
-            let price_data = PriceData {
-                price,
-                timestamp: 1700000000,
-                source: "test".to_string(),
-            };
-
             // When & Then - 가격 검증
-            assert!(is_valid_price(&price_data), "Price {} should be valid", price);
+            assert!(is_valid_price(price), "Price {} should be valid", price);
         }
     }
 
@@ -136,8 +144,10 @@ mod tests {
 
         for (timestamp, expected, desc) in test_cases {
             let price_data = PriceData {
-                price: 50000.0,
-                timestamp,
+                pair: AssetPair::btc_usd(),
+                price: Price::from_cents(5000000),
+                timestamp: DateTime::from_timestamp(timestamp as i64, 0).unwrap(),
+                volume: None,
                 source: "test".to_string(),
             };
 
@@ -151,23 +161,3 @@ mod tests {
         }
     }
 }
-
-/// 가격 데이터 유효성 검증
-fn is_valid_price(data: &PriceData) -> bool {
-    data.price > 0.0 && data.price < 10_000_000.0 // 0 < price < $10M
-}
-
-/// 타임스탬프 유효성 검증
-fn is_valid_timestamp(data: &PriceData) -> bool {
-    let now = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap()
-        .as_secs();
-    
-    let one_hour = 3600;
-    let min_timestamp = 1600000000; // 2020-09-13 (reasonable minimum)
-    
-    data.timestamp >= min_timestamp && 
-    data.timestamp <= now + 60 && // Allow 1 minute clock drift
-    data.timestamp >= now - one_hour // Not older than 1 hour
-}
\ No newline at end of file